@@ -14,7 +14,12 @@ object PrettyPrinter extends ByteStringPrettyPrinter {
     case Key.Value.Address(Key.Address(address)) => s"Address(${buildString(address)})"
     case Key.Value.Uref(Key.URef(id, accessRights)) =>
       s"URef(${buildString(id)}, ${buildString(accessRights)})"
-    case Key.Value.Hash(Key.Hash(hash)) => s"Hash(${buildString(hash)})"
+    case Key.Value.Hash(Key.Hash(hash))             => s"Hash(${buildString(hash)})"
+    case Key.Value.SystemRegistry(_)                => "SystemRegistry"
+    case Key.Value.Dictionary(Key.Dictionary(addr)) => s"Dictionary(${buildString(addr)})"
+    case Key.Value.ExecutedDeploy(Key.ExecutedDeploy(deployHash)) =>
+      s"ExecutedDeploy(${buildString(deployHash)})"
+    case Key.Value.Migration(Key.Migration(addr)) => s"Migration(${buildString(addr)})"
   }
 
   def buildString(t: Transform): String = t.transformInstance match {
@@ -32,6 +37,8 @@ object PrettyPrinter extends ByteStringPrettyPrinter {
         case Some(v) => s"Write(${buildString(v)})"
       }
     case Transform.TransformInstance.AddU64(TransformAddUInt64(x)) => s"AddU64($x)"
+    case Transform.TransformInstance.AddList(TransformAddList(_))  => "AddList"
+    case Transform.TransformInstance.Delete(_)                     => "Delete"
   }
 
   def buildString(v: Option[ProtocolVersion]): String = v match {
@@ -632,7 +632,10 @@ object ProtoUtil {
         payment = payment,
         gasPrice = GAS_PRICE,
         authorizationKeys = keyHashes,
-        deployHash = d.deployHash
+        deployHash = d.deployHash,
+        timestamp = d.getHeader.timestamp,
+        ttlMillis = d.getHeader.ttlMillis,
+        dependencies = d.getHeader.dependencies
       )
     }
   }
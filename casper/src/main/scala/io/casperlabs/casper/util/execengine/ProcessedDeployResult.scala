@@ -1,6 +1,7 @@
 package io.casperlabs.casper.util.execengine
 
 import io.casperlabs.casper.consensus.Deploy
+import io.casperlabs.crypto.codec.Base16
 import io.casperlabs.ipc
 
 sealed trait ProcessedDeployResult {
@@ -35,6 +36,21 @@ object ProcessedDeployResult {
     result match {
       case ipc.DeployResult(ipc.DeployResult.Value.PreconditionFailure(value)) =>
         PreconditionFailure(deploy, value.message)
+      case ipc.DeployResult(ipc.DeployResult.Value.DeployExpired(value)) =>
+        PreconditionFailure(
+          deploy,
+          s"Deploy expired at ${value.expiry} before block time ${value.blockTime}"
+        )
+      case ipc.DeployResult(ipc.DeployResult.Value.MissingDependency(value)) =>
+        PreconditionFailure(
+          deploy,
+          s"Deploy dependency not executed: ${Base16.encode(value.deployHash.toByteArray)}"
+        )
+      case ipc.DeployResult(ipc.DeployResult.Value.InvalidNonce(value)) =>
+        PreconditionFailure(
+          deploy,
+          s"Invalid deploy nonce: expected ${value.expected}, actual ${value.actual}"
+        )
       case ipc.DeployResult(ipc.DeployResult.Value.ExecutionResult(exec_result)) =>
         exec_result match {
           case ipc.DeployResult.ExecutionResult(Some(effects), Some(error), cost) =>
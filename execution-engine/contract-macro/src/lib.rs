@@ -0,0 +1,458 @@
+//! Procedural macros for writing smart contracts on the
+//! [CasperLabs Platform](https://techspec.casperlabs.io).
+//!
+//! The macros generate the boilerplate which otherwise has to be hand-written around each entry
+//! point of a contract: parsing the named arguments, dispatching on the requested entry point and
+//! serializing the return value.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #![no_std]
+//! #![no_main]
+//!
+//! extern crate alloc;
+//!
+//! use alloc::string::String;
+//!
+//! use casperlabs_contract::contract_api::{runtime, storage};
+//! use casperlabs_contract_macro::casperlabs_contract;
+//!
+//! #[casperlabs_contract]
+//! mod counter {
+//!     use super::*;
+//!
+//!     // Exported as the entry point "store", taking the named arguments "name" and "value".
+//!     #[casperlabs_method]
+//!     fn store(name: String, value: u64) {
+//!         runtime::put_key(&name, storage::new_uref(value).into());
+//!     }
+//!
+//!     // The returned value is passed to `runtime::ret` when called as a stored contract.
+//!     #[casperlabs_method]
+//!     fn double(value: u64) -> u64 {
+//!         value * 2
+//!     }
+//! }
+//! ```
+//!
+//! As session code, the contract above runs `store` or `double` depending on the value of its
+//! `"entry_point"` argument.
+//!
+//! Contract errors can derive `contract_api::error::UserError` with `#[derive(CLError)]`, which
+//! makes them usable with `runtime::revert` and `UnwrapOrRevert::unwrap_or_revert_with`.
+//!
+//! # Requirements
+//!
+//! The generated code refers to the `casperlabs-contract` and `casperlabs-types` crates, both of
+//! which must be dependencies of the contract (under any name), and to `alloc::string::String`, so
+//! the contract's crate root must declare `extern crate alloc;`.
+
+#![doc(html_root_url = "https://docs.rs/casperlabs-contract-macro/0.1.0")]
+#![doc(
+    html_favicon_url = "https://raw.githubusercontent.com/CasperLabs/CasperLabs/dev/images/CasperLabs_Logo_Favicon_RGB_50px.png",
+    html_logo_url = "https://raw.githubusercontent.com/CasperLabs/CasperLabs/dev/images/CasperLabs_Logo_Symbol_RGB.png"
+)]
+#![warn(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Expr, Fields, FnArg,
+    Ident, Item, ItemFn, ItemMod, Lit, Pat, ReturnType, UnOp,
+};
+
+const CONTRACT_CRATE: &str = "casperlabs-contract";
+const TYPES_CRATE: &str = "casperlabs-types";
+const METHOD_ATTRIBUTE: &str = "casperlabs_method";
+const CALL: &str = "call";
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+/// Exports the annotated function as a contract entry point of the same name.
+///
+/// Each parameter of the function is read from the named argument matching the parameter's name.
+/// If the function returns a value, it is converted to a `CLValue` and passed to `runtime::ret`.
+/// The function itself is left untouched, so it can still be called directly.
+#[proc_macro_attribute]
+pub fn casperlabs_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(
+            Span::call_site(),
+            "`casperlabs_method` doesn't take any arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let item_fn = parse_macro_input!(item as ItemFn);
+    let paths = CratePaths::new();
+    Method::new(&item_fn)
+        .map(|method| {
+            let entry_point = method.entry_point(&paths);
+            quote! {
+                #item_fn
+                #entry_point
+            }
+        })
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Expands every `#[casperlabs_method]` function in the annotated module and generates the
+/// `call` entry point dispatching to them.
+///
+/// `call` reads the `"entry_point"` argument and runs the method of that name, reverting with
+/// `ApiError::InvalidArgument` if there is none.  Values returned by methods are discarded, as
+/// session code can't return a value.  If the module already defines a `call` function, no
+/// dispatcher is generated.
+#[proc_macro_attribute]
+pub fn casperlabs_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(
+            Span::call_site(),
+            "`casperlabs_contract` doesn't take any arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let item_mod = parse_macro_input!(item as ItemMod);
+    expand_contract(item_mod)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Derives `contract_api::error::UserError` and `From<_> for ApiError` for a C-style enum.
+///
+/// The code of each variant is its discriminant, so explicit discriminants are preserved, and the
+/// variant converts to `ApiError::User(code)`.  Discriminants must therefore fit in a `u16`, and a
+/// discriminant outside that range is a compile error.  The name of each variant is its identifier.
+#[proc_macro_derive(CLError)]
+pub fn derive_cl_error(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    expand_cl_error(derive_input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Paths to the crates which generated code refers to, as named by the crate being compiled.
+struct CratePaths {
+    contract: TokenStream2,
+    types: TokenStream2,
+}
+
+impl CratePaths {
+    fn new() -> Self {
+        CratePaths {
+            contract: crate_path(CONTRACT_CRATE),
+            types: crate_path(TYPES_CRATE),
+        }
+    }
+}
+
+fn crate_path(package: &str) -> TokenStream2 {
+    let name = proc_macro_crate::crate_name(package).unwrap_or_else(|_| package.replace('-', "_"));
+    if name == "crate" {
+        return quote!(crate);
+    }
+    let ident = Ident::new(&name, Span::call_site());
+    quote!(::#ident)
+}
+
+/// The parts of a `#[casperlabs_method]` function's signature needed to call it.
+struct Method {
+    ident: Ident,
+    arg_names: Vec<String>,
+    returns_value: bool,
+}
+
+impl Method {
+    fn new(item_fn: &ItemFn) -> Result<Self, Error> {
+        let sig = &item_fn.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(Error::new(
+                sig.generics.span(),
+                "a `casperlabs_method` can't be generic",
+            ));
+        }
+        if let Some(asyncness) = sig.asyncness {
+            return Err(Error::new(
+                asyncness.span(),
+                "a `casperlabs_method` can't be async",
+            ));
+        }
+
+        let mut arg_names = Vec::with_capacity(sig.inputs.len());
+        for input in &sig.inputs {
+            match input {
+                FnArg::Typed(pat_type) => match &*pat_type.pat {
+                    Pat::Ident(pat_ident) => {
+                        let name = pat_ident.ident.to_string();
+                        arg_names.push(name.trim_start_matches("r#").to_string());
+                    }
+                    pat => {
+                        return Err(Error::new(
+                            pat.span(),
+                            "arguments of a `casperlabs_method` must be plain identifiers",
+                        ))
+                    }
+                },
+                FnArg::Receiver(receiver) => {
+                    return Err(Error::new(
+                        receiver.span(),
+                        "a `casperlabs_method` can't take `self`",
+                    ))
+                }
+            }
+        }
+
+        Ok(Method {
+            ident: sig.ident.clone(),
+            arg_names,
+            returns_value: matches!(sig.output, ReturnType::Type(..)),
+        })
+    }
+
+    /// Calls the method with its arguments read from the runtime's named arguments.
+    fn call(&self, paths: &CratePaths) -> TokenStream2 {
+        let contract = &paths.contract;
+        let ident = &self.ident;
+        let arg_names = &self.arg_names;
+        quote! {
+            #ident(#(#contract::contract_api::runtime::get_named_arg(#arg_names)),*)
+        }
+    }
+
+    /// Exports the method under its own name, returning its result via `runtime::ret`.
+    fn entry_point(&self, paths: &CratePaths) -> TokenStream2 {
+        let contract = &paths.contract;
+        let types = &paths.types;
+        let export_name = self.ident.to_string();
+        let wrapper = format_ident!("__casperlabs_method_{}", self.ident);
+        let call = self.call(paths);
+        let body = if self.returns_value {
+            quote! {
+                let result = #call;
+                let value = #contract::unwrap_or_revert::UnwrapOrRevert::unwrap_or_revert(
+                    #types::CLValue::from_t(result)
+                );
+                #contract::contract_api::runtime::ret(value)
+            }
+        } else {
+            quote! {
+                #call;
+            }
+        };
+        quote! {
+            #[doc(hidden)]
+            #[export_name = #export_name]
+            pub extern "C" fn #wrapper() {
+                #body
+            }
+        }
+    }
+}
+
+fn is_method_attribute(attr: &Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .map_or(false, |segment| segment.ident == METHOD_ATTRIBUTE)
+}
+
+fn expand_contract(item_mod: ItemMod) -> Result<TokenStream2, Error> {
+    let span = item_mod.span();
+    let ItemMod {
+        attrs,
+        vis,
+        mod_token,
+        ident,
+        content,
+        ..
+    } = item_mod;
+    let (_brace, items) = content.ok_or_else(|| {
+        Error::new(
+            span,
+            "`casperlabs_contract` must be applied to a module with an inline body",
+        )
+    })?;
+
+    let paths = CratePaths::new();
+    let mut has_call = false;
+    let mut methods = Vec::new();
+    let mut expanded_items = Vec::with_capacity(items.len());
+    for item in items {
+        match item {
+            Item::Fn(mut item_fn) => {
+                if item_fn.sig.ident == CALL {
+                    has_call = true;
+                }
+                let attr_count = item_fn.attrs.len();
+                item_fn.attrs.retain(|attr| !is_method_attribute(attr));
+                if item_fn.attrs.len() == attr_count {
+                    expanded_items.push(quote!(#item_fn));
+                    continue;
+                }
+                let method = Method::new(&item_fn)?;
+                let entry_point = method.entry_point(&paths);
+                expanded_items.push(quote! {
+                    #item_fn
+                    #entry_point
+                });
+                methods.push(method);
+            }
+            item => expanded_items.push(quote!(#item)),
+        }
+    }
+
+    if !has_call {
+        let contract = &paths.contract;
+        let types = &paths.types;
+        let invalid_argument = quote!(#types::ApiError::InvalidArgument);
+        let names = methods.iter().map(|method| method.ident.to_string());
+        let calls = methods.iter().map(|method| method.call(&paths));
+        expanded_items.push(quote! {
+            #[no_mangle]
+            pub extern "C" fn call() {
+                let casperlabs_entry_point: ::alloc::string::String =
+                    #contract::contract_api::runtime::get_named_arg(#ARG_ENTRY_POINT);
+                match casperlabs_entry_point.as_str() {
+                    #(#names => {
+                        #calls;
+                    })*
+                    _ => #contract::contract_api::runtime::revert(#invalid_argument),
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #mod_token #ident {
+            #(#expanded_items)*
+        }
+    })
+}
+
+fn expand_cl_error(derive_input: DeriveInput) -> Result<TokenStream2, Error> {
+    let ident = &derive_input.ident;
+    let data_enum = match &derive_input.data {
+        Data::Enum(data_enum) => data_enum,
+        _ => {
+            return Err(Error::new(
+                derive_input.span(),
+                "`CLError` can only be derived for enums",
+            ))
+        }
+    };
+    if !derive_input.generics.params.is_empty() {
+        return Err(Error::new(
+            derive_input.generics.span(),
+            "`CLError` can't be derived for generic enums",
+        ));
+    }
+    for variant in &data_enum.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(
+                variant.fields.span(),
+                "`CLError` can only be derived for enums without fields",
+            ));
+        }
+    }
+
+    // Discriminants given as integer literals are range-checked here.  Any other discriminant, and
+    // any implicit one following it, is checked by a constant evaluated by the compiler.
+    let mut next_discriminant = Some(0_i128);
+    let mut unchecked_variants = Vec::new();
+    for variant in &data_enum.variants {
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => literal_discriminant(expr),
+            None => next_discriminant,
+        };
+        match discriminant {
+            Some(value) if value < 0 || value > i128::from(u16::max_value()) => {
+                return Err(Error::new(
+                    variant.span(),
+                    format!(
+                        "`CLError` requires discriminants in the range of a u16, but `{}` is {}",
+                        variant.ident, value
+                    ),
+                ));
+            }
+            Some(_) => (),
+            None => unchecked_variants.push(&variant.ident),
+        }
+        next_discriminant = discriminant.and_then(|value| value.checked_add(1));
+    }
+
+    let paths = CratePaths::new();
+    let contract = &paths.contract;
+    let types = &paths.types;
+    let variants: Vec<&Ident> = data_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect();
+    let names = variants.iter().map(|variant| variant.to_string());
+
+    Ok(quote! {
+        #(
+            const _: () = {
+                const DISCRIMINANT: i128 = #ident::#unchecked_variants as i128;
+                const IN_RANGE: bool =
+                    (DISCRIMINANT >= 0) & (DISCRIMINANT <= u16::max_value() as i128);
+                // Fails to evaluate, and so to compile, if the discriminant doesn't fit in a u16.
+                let _ = [(); IN_RANGE as usize - 1];
+            };
+        )*
+
+        impl #contract::contract_api::error::UserError for #ident {
+            fn code(&self) -> u16 {
+                match *self {
+                    #(#ident::#variants => #ident::#variants as u16,)*
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match *self {
+                    #(#ident::#variants => #names,)*
+                }
+            }
+
+            fn from_code(code: u16) -> Option<Self> {
+                #(
+                    if code == #ident::#variants as u16 {
+                        return Some(#ident::#variants);
+                    }
+                )*
+                None
+            }
+        }
+
+        impl From<#ident> for #types::ApiError {
+            fn from(error: #ident) -> Self {
+                #types::ApiError::User(
+                    #contract::contract_api::error::UserError::code(&error)
+                )
+            }
+        }
+    })
+}
+
+/// Returns the value of a discriminant given as a possibly negated integer literal, or `None` for
+/// any other expression.
+fn literal_discriminant(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Int(lit_int) => lit_int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Unary(expr_unary) => match expr_unary.op {
+            UnOp::Neg(_) => literal_discriminant(&expr_unary.expr).map(|value| -value),
+            _ => None,
+        },
+        Expr::Paren(expr_paren) => literal_discriminant(&expr_paren.expr),
+        _ => None,
+    }
+}
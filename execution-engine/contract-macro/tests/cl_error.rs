@@ -0,0 +1,66 @@
+use casperlabs_contract::contract_api::error::UserError;
+use casperlabs_contract_macro::CLError;
+use casperlabs_types::ApiError;
+
+#[derive(CLError, Debug, PartialEq)]
+enum Error {
+    Zero,
+    One,
+    Five = 5,
+    Six,
+    Max = 65_535,
+}
+
+const BASE: isize = 10;
+
+#[derive(CLError, Debug, PartialEq)]
+enum ComputedError {
+    Ten = BASE,
+    Eleven,
+}
+
+#[test]
+fn should_use_discriminants_as_codes() {
+    assert_eq!(Error::Zero.code(), 0);
+    assert_eq!(Error::One.code(), 1);
+    assert_eq!(Error::Five.code(), 5);
+    assert_eq!(Error::Six.code(), 6);
+    assert_eq!(Error::Max.code(), u16::max_value());
+    assert_eq!(ComputedError::Ten.code(), 10);
+    assert_eq!(ComputedError::Eleven.code(), 11);
+}
+
+#[test]
+fn should_convert_to_user_api_error() {
+    assert_eq!(ApiError::User(0), Error::Zero.into());
+    assert_eq!(ApiError::User(5), Error::Five.into());
+    assert_eq!(65_541_u32, ApiError::from(Error::Five).into());
+}
+
+#[test]
+fn should_name_variants() {
+    assert_eq!(Error::Zero.name(), "Zero");
+    assert_eq!(Error::Six.name(), "Six");
+}
+
+#[test]
+fn should_round_trip_through_api_error() {
+    for error in vec![Error::Zero, Error::One, Error::Five, Error::Six] {
+        let api_error = ApiError::from(Error::from_code(error.code()).unwrap());
+        assert_eq!(Some(error), Error::from_api_error(api_error));
+    }
+    assert_eq!(None, Error::from_code(2));
+    assert_eq!(None, Error::from_api_error(ApiError::User(2)));
+    assert_eq!(None, Error::from_api_error(ApiError::MissingArgument));
+}
+
+#[test]
+fn should_round_trip_through_error_message() {
+    let message = ApiError::from(Error::Five).to_string();
+    assert_eq!(Some(Error::Five), Error::from_error_message(&message));
+    assert_eq!(
+        None,
+        Error::from_error_message(&ApiError::MissingArgument.to_string())
+    );
+    assert_eq!(None, Error::from_error_message("User error: x"));
+}
@@ -5,8 +5,8 @@ use core::convert::TryFrom;
 
 use casperlabs_types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetMainPurseFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr, URef, UREF_SERIALIZED_LENGTH,
 };
@@ -84,3 +84,17 @@ pub fn update_associated_key(
         Err(UpdateKeyFailure::try_from(result).unwrap_or_revert())
     }
 }
+
+/// Replaces the account's main purse with the given purse, e.g. after the old one was compromised.
+///
+/// Requires the deploy's authorization keys to meet the key management threshold, and `purse` to
+/// carry read, add and write access rights.
+pub fn set_main_purse(purse: URef) -> Result<(), SetMainPurseFailure> {
+    let (purse_ptr, purse_size, _bytes) = to_ptr(purse);
+    let result = unsafe { ext_ffi::set_main_purse(purse_ptr, purse_size) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(SetMainPurseFailure::try_from(result).unwrap_or_revert())
+    }
+}
@@ -0,0 +1,66 @@
+//! Support for contract-defined error types.
+//!
+//! Rather than casting C-style enum variants to `u16` by hand, contracts can derive [`UserError`]
+//! using `#[derive(CLError)]` from the `casperlabs-contract-macro` crate:
+//!
+//! ```rust,ignore
+//! use casperlabs_contract::contract_api::{error::UserError, runtime};
+//! use casperlabs_contract_macro::CLError;
+//! use casperlabs_types::ApiError;
+//!
+//! #[derive(CLError, Debug, PartialEq)]
+//! enum Error {
+//!     MissingValue,      // ApiError::User(0), i.e. 65,536
+//!     ValueTooLarge = 5, // ApiError::User(5), i.e. 65,541
+//! }
+//!
+//! assert_eq!(ApiError::User(5), Error::ValueTooLarge.into());
+//! assert_eq!("ValueTooLarge", Error::ValueTooLarge.name());
+//! assert_eq!(Some(Error::MissingValue), Error::from_api_error(ApiError::User(0)));
+//!
+//! runtime::revert(Error::ValueTooLarge);
+//! ```
+
+use casperlabs_types::ApiError;
+
+/// The prefix of the `Display` representation of an [`ApiError::User`], which is the form in
+/// which a reverted deploy's error is reported in its deploy result.
+const USER_ERROR_MESSAGE_PREFIX: &str = "User error: ";
+
+/// A contract-defined error which converts to an [`ApiError::User`].
+///
+/// The error's code is wrapped in an `ApiError::User`, and hence offset into the user range when
+/// the error is passed to `runtime::revert`.
+pub trait UserError: Into<ApiError> + Sized {
+    /// Returns the code of the error, i.e. the value wrapped by the corresponding
+    /// `ApiError::User`.
+    fn code(&self) -> u16;
+
+    /// Returns the name of the error.
+    fn name(&self) -> &'static str;
+
+    /// Returns the error with the given code, or `None` if there is no such error.
+    fn from_code(code: u16) -> Option<Self>;
+
+    /// Returns the error which was converted into `api_error`, or `None` if `api_error` is not an
+    /// `ApiError::User` or its code doesn't match any error.
+    fn from_api_error(api_error: ApiError) -> Option<Self> {
+        match api_error {
+            ApiError::User(code) => Self::from_code(code),
+            _ => None,
+        }
+    }
+
+    /// Returns the error which a deploy reverted with, given the error message of the deploy's
+    /// result, or `None` if the message doesn't describe a matching user error.
+    fn from_error_message(message: &str) -> Option<Self> {
+        let message = message.trim();
+        if !message.starts_with(USER_ERROR_MESSAGE_PREFIX) {
+            return None;
+        }
+        message[USER_ERROR_MESSAGE_PREFIX.len()..]
+            .parse()
+            .ok()
+            .and_then(Self::from_code)
+    }
+}
@@ -3,22 +3,36 @@
 // Can be removed once https://github.com/rust-lang/rustfmt/issues/3362 is resolved.
 #[rustfmt::skip]
 use alloc::vec;
-use alloc::vec::Vec;
+use alloc::{collections::btree_map, string::String, vec::Vec};
+#[cfg(not(feature = "mock-runtime"))]
+use core::cell::RefCell;
 use core::mem::MaybeUninit;
 
 use casperlabs_types::{
     account::AccountHash,
     api_error,
-    bytesrepr::{self, FromBytes},
-    contracts::{ContractVersion, NamedKeys},
-    ApiError, BlockTime, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Phase,
-    RuntimeArgs, URef, BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    bytesrepr::{self, FromBytes, U64_SERIALIZED_LENGTH},
+    contracts::{ContractVersion, NamedKeys, MAX_NAMED_KEYS_PAGE_SIZE},
+    system_contract_type::SystemContractRegistry,
+    AccessRights, ApiError, BlockTime, CLTyped, CLValue, CallStackElement, ContractHash,
+    ContractPackageHash, Key, Phase, RuntimeArgs, URef, BLOCKTIME_SERIALIZED_LENGTH,
+    PHASE_SERIALIZED_LENGTH, U512,
 };
 
-use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+use crate::{
+    contract_api::{self, storage},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+/// The length in bytes of a deploy hash.
+pub const DEPLOY_HASH_LENGTH: usize = 32;
 
 /// Returns the given [`CLValue`] to the host, terminating the currently running module.
 ///
+/// Any [`URef`]s embedded in `value` are granted to the caller, with the access rights they have
+/// in `value`.
+///
 /// Note this function is only relevant to contracts stored on chain which are invoked via
 /// [`call_contract`] and can thus return a value to their caller.  The return value of a directly
 /// deployed contract is never used.
@@ -29,6 +43,18 @@ pub fn ret(value: CLValue) -> ! {
     }
 }
 
+/// Returns the given [`CLValue`] to the host, terminating the currently running module, without
+/// granting the caller access to any [`URef`]s embedded in `value`.
+///
+/// The caller still receives the returned value, but any `URef`s in it are treated as forged unless
+/// the caller already holds their access rights.
+pub fn ret_without_transfer(value: CLValue) -> ! {
+    let (ptr, size, _bytes) = contract_api::to_ptr(value);
+    unsafe {
+        ext_ffi::ret_without_transfer(ptr, size);
+    }
+}
+
 /// Stops execution of a contract and reverts execution effects with a given [`ApiError`].
 ///
 /// The provided `ApiError` is returned in the form of a numeric exit code to the caller via the
@@ -73,6 +99,40 @@ pub fn call_contract<T: CLTyped + FromBytes>(
     deserialize_contract_result(bytes_written)
 }
 
+/// Calls the given stored contract like [`call_contract`], but without letting it modify global
+/// state.
+///
+/// Any changes the stored contract makes are discarded, and if it attempts to write to global state
+/// execution stops and `call_contract_read_only` doesn't return.
+pub fn call_contract_read_only<T: CLTyped + FromBytes>(
+    contract_hash: ContractHash,
+    entry_point_name: &str,
+    runtime_args: RuntimeArgs,
+) -> T {
+    let (contract_hash_ptr, contract_hash_size, _bytes1) = contract_api::to_ptr(contract_hash);
+    let (entry_point_name_ptr, entry_point_name_size, _bytes2) =
+        contract_api::to_ptr(entry_point_name);
+    let (runtime_args_ptr, runtime_args_size, _bytes3) = contract_api::to_ptr(runtime_args);
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::call_contract_read_only(
+                contract_hash_ptr,
+                contract_hash_size,
+                entry_point_name_ptr,
+                entry_point_name_size,
+                runtime_args_ptr,
+                runtime_args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { bytes_written.assume_init() }
+    };
+    deserialize_contract_result(bytes_written)
+}
+
 /// Invokes the specified `entry_point_name` of stored logic at a specific `contract_package_hash`
 /// address, for the most current version of a contract package by default or a specific
 /// `contract_version` if one is provided, and passing the provided `runtime_args` to it
@@ -120,8 +180,9 @@ fn deserialize_contract_result<T: CLTyped + FromBytes>(bytes_written: usize) ->
         // If no bytes were written, the host buffer hasn't been set and hence shouldn't be read.
         vec![]
     } else {
-        // NOTE: this is a copy of the contents of `read_host_buffer()`.  Calling that directly from
-        // here causes several contracts to fail with a Wasmi `Unreachable` error.
+        // NOTE: this is a copy of the contents of `read_host_buffer()` from before it reused the
+        // scratch buffer.  Calling that directly from here causes several contracts to fail with a
+        // Wasmi `Unreachable` error.
         let bytes_non_null_ptr = contract_api::alloc_bytes(bytes_written);
         let mut dest: Vec<u8> = unsafe {
             Vec::from_raw_parts(bytes_non_null_ptr.as_ptr(), bytes_written, bytes_written)
@@ -154,29 +215,32 @@ fn get_named_arg_size(name: &str) -> Option<usize> {
 /// Note that this is only relevant to contracts stored on-chain since a contract deployed directly
 /// is not invoked with any arguments.
 pub fn get_named_arg<T: FromBytes>(name: &str) -> T {
-    let arg_size = get_named_arg_size(name).unwrap_or_revert_with(ApiError::MissingArgument);
-    let arg_bytes = if arg_size > 0 {
-        let res = {
-            let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+    try_get_named_arg(name).unwrap_or_revert_with(ApiError::MissingArgument)
+}
+
+/// Returns given named argument passed to the host for the current module invocation, or `None`
+/// if no argument with that name was passed.
+pub fn try_get_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let arg_size = get_named_arg_size(name)?;
+    let result = if arg_size > 0 {
+        with_scratch_buffer(arg_size, |dest| {
             let ret = unsafe {
                 ext_ffi::get_named_arg(
                     name.as_bytes().as_ptr(),
                     name.len(),
-                    data_non_null_ptr.as_ptr(),
+                    dest.as_mut_ptr(),
                     arg_size,
                 )
             };
-            let data =
-                unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
-            api_error::result_from(ret).map(|_| data)
-        };
-        // Assumed to be safe as `get_named_arg_size` checks the argument already
-        res.unwrap_or_revert()
+            // Assumed to be safe as `get_named_arg_size` checks the argument already
+            api_error::result_from(ret).unwrap_or_revert();
+            bytesrepr::deserialize_from_slice(dest)
+        })
     } else {
-        // Avoids allocation with 0 bytes and a call to get_named_arg
-        Vec::new()
+        // Avoids a call to get_named_arg
+        bytesrepr::deserialize_from_slice(&[])
     };
-    bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument)
+    Some(result.unwrap_or_revert_with(ApiError::InvalidArgument))
 }
 
 /// Returns the caller of the current context, i.e. the [`AccountHash`] of the account which made
@@ -188,8 +252,125 @@ pub fn get_caller() -> AccountHash {
         api_error::result_from(ret).unwrap_or_revert();
         unsafe { output_size.assume_init() }
     };
-    let buf = read_host_buffer(output_size).unwrap_or_revert();
-    bytesrepr::deserialize(buf).unwrap_or_revert()
+    read_host_buffer(output_size).unwrap_or_revert()
+}
+
+/// Returns the call stack of the current execution.
+///
+/// The first element is the code invoked by the deploy, and the last one is the currently running
+/// code, so the second to last element (if any) is the code which called the current contract.
+pub fn get_call_stack() -> Vec<CallStackElement> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_call_stack(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    read_host_buffer(output_size).unwrap_or_revert()
+}
+
+/// Returns `true` if the caller of the current context is the reserved system account, i.e. the
+/// deploy is being run by the system on behalf of the platform rather than by a user.
+///
+/// System contracts use this to guard their privileged entry points.
+pub fn is_system_caller() -> bool {
+    unsafe { ext_ffi::is_system_caller() != 0 }
+}
+
+/// Returns `count` deterministic pseudo-random bytes.
+///
+/// The bytes are seeded from the deploy hash, the current [`Phase`] and the current [`BlockTime`],
+/// so they are replayable by every validator.  They are not secret and must not be used where
+/// unpredictability to the deployer is required.
+pub fn random_bytes(count: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; count];
+    if count > 0 {
+        unsafe { ext_ffi::random_bytes(bytes.as_mut_ptr(), count) }
+    }
+    bytes
+}
+
+/// Returns `a * b / c`, or `None` if `c` is zero or the result doesn't fit in a [`U512`].
+///
+/// The calculation is done on the host with a 1024-bit intermediate, so unlike `a * b / c` in
+/// Wasm, the product can't overflow.
+pub fn u512_mul_div(a: U512, b: U512, c: U512) -> Option<U512> {
+    let (a_ptr, a_size, _bytes1) = contract_api::to_ptr(a);
+    let (b_ptr, b_size, _bytes2) = contract_api::to_ptr(b);
+    let (c_ptr, c_size, _bytes3) = contract_api::to_ptr(c);
+    let mut dest = [0u8; 64];
+    let result = unsafe {
+        ext_ffi::u512_mul_div(
+            a_ptr,
+            a_size,
+            b_ptr,
+            b_size,
+            c_ptr,
+            c_size,
+            dest.as_mut_ptr(),
+        )
+    };
+    match api_error::result_from(result) {
+        Ok(()) => Some(U512::from_little_endian(&dest)),
+        Err(ApiError::None) => None,
+        Err(error) => revert(error),
+    }
+}
+
+/// Length in bytes of the digests returned by [`blake2b`] and [`keccak256`].
+pub const DIGEST_LENGTH: usize = 32;
+
+/// Returns the 32-byte blake2b digest of `data`, computed on the host.
+pub fn blake2b(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    let result =
+        unsafe { ext_ffi::blake2b(data.as_ptr(), data.len(), digest.as_mut_ptr(), digest.len()) };
+    api_error::result_from(result).unwrap_or_revert();
+    digest
+}
+
+/// Returns the 32-byte keccak-256 digest of `data`, computed on the host.
+pub fn keccak256(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    let result =
+        unsafe { ext_ffi::keccak256(data.as_ptr(), data.len(), digest.as_mut_ptr(), digest.len()) };
+    api_error::result_from(result).unwrap_or_revert();
+    digest
+}
+
+/// Returns `true` if `signature` is a valid Ed25519 signature of `message` by `public_key`.
+///
+/// Malformed signatures or public keys are reported as invalid.
+pub fn verify_ed25519(message: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+    let result = unsafe {
+        ext_ffi::verify_ed25519(
+            message.as_ptr(),
+            message.len(),
+            signature.as_ptr(),
+            signature.len(),
+            public_key.as_ptr(),
+            public_key.len(),
+        )
+    };
+    result != 0
+}
+
+/// Returns `true` if `signature` is a valid compact secp256k1 signature of the 32-byte
+/// `message_digest` by `public_key`.
+///
+/// Malformed digests, signatures or public keys are reported as invalid.
+pub fn verify_secp256k1(message_digest: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+    let result = unsafe {
+        ext_ffi::verify_secp256k1(
+            message_digest.as_ptr(),
+            message_digest.len(),
+            signature.as_ptr(),
+            signature.len(),
+            public_key.as_ptr(),
+            public_key.len(),
+        )
+    };
+    result != 0
 }
 
 /// Returns the current [`BlockTime`].
@@ -206,6 +387,42 @@ pub fn get_blocktime() -> BlockTime {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns the hash of the deploy being executed.
+///
+/// The hash is the same in every contract called by the deploy, and on every validator executing
+/// it, so it can be used as an idempotency key or to seed per-deploy values.
+pub fn get_deploy_hash() -> [u8; DEPLOY_HASH_LENGTH] {
+    let mut deploy_hash = [0u8; DEPLOY_HASH_LENGTH];
+    unsafe { ext_ffi::get_deploy_hash(deploy_hash.as_mut_ptr()) }
+    deploy_hash
+}
+
+/// Returns the gas price currently in effect, i.e. the number of motes charged per unit of gas.
+pub fn get_gas_price() -> u64 {
+    let dest_non_null_ptr = contract_api::alloc_bytes(U64_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::get_gas_price(dest_non_null_ptr.as_ptr());
+        Vec::from_raw_parts(
+            dest_non_null_ptr.as_ptr(),
+            U64_SERIALIZED_LENGTH,
+            U64_SERIALIZED_LENGTH,
+        )
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Returns the hash of the system contract registered under `name`, or `None` if there is no such
+/// system contract.
+///
+/// The built-in system contracts are registered under the names of their
+/// [`SystemContractType`](casperlabs_types::SystemContractType)s, e.g. `"mint"`.
+pub fn get_system_contract(name: &str) -> Option<ContractHash> {
+    let mut registry: SystemContractRegistry = storage::read_from_key(Key::SystemRegistry)
+        .unwrap_or_revert()
+        .unwrap_or_default();
+    registry.remove(name)
+}
+
 /// Returns the current [`Phase`].
 pub fn get_phase() -> Phase {
     let dest_non_null_ptr = contract_api::alloc_bytes(PHASE_SERIALIZED_LENGTH);
@@ -247,6 +464,30 @@ pub fn get_key(name: &str) -> Option<Key> {
     Some(key)
 }
 
+/// Increments the [`storage::Counter`] stored under `name` in the current context's named keys,
+/// and returns its new value.
+///
+/// Reverts with [`ApiError::MissingKey`] if there is no such named key, or with
+/// [`ApiError::UnexpectedKeyVariant`] if it is not a [`URef`].
+pub fn counter_inc(name: &str) -> u64 {
+    let uref = get_key(name)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::counter_inc(uref.into())
+}
+
+/// Retires the contract at `contract_hash`, leaving a tombstone in its place.
+///
+/// The tombstone keeps the contract's named keys, so they remain queryable, but any later call to
+/// the contract fails with a contract disabled error.  Its version is also disabled in the contract
+/// package.  Requires access to the contract package.
+pub fn disable_contract(contract_hash: ContractHash) -> Result<(), ApiError> {
+    let (contract_hash_ptr, contract_hash_size, _bytes) = contract_api::to_ptr(contract_hash);
+    let result = unsafe { ext_ffi::disable_contract(contract_hash_ptr, contract_hash_size) };
+    api_error::result_from(result)
+}
+
 /// Returns `true` if `name` exists in the current context's named keys.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
@@ -294,8 +535,83 @@ pub fn list_named_keys() -> NamedKeys {
     if total_keys == 0 {
         return NamedKeys::new();
     }
-    let bytes = read_host_buffer(result_size).unwrap_or_revert();
-    bytesrepr::deserialize(bytes).unwrap_or_revert()
+    read_host_buffer(result_size).unwrap_or_revert()
+}
+
+/// The amount of named keys loaded from the host at a time by [`named_keys_iter`].  It must not
+/// exceed [`MAX_NAMED_KEYS_PAGE_SIZE`], or a full page would be taken for the last one.
+pub const NAMED_KEYS_PAGE_SIZE: u32 = 100;
+
+/// Returns up to `limit` named keys of the current context in name order, starting after the name
+/// `start_after`, or from the first key if it is `None`.
+///
+/// The host returns at most [`MAX_NAMED_KEYS_PAGE_SIZE`] keys, whatever the `limit`.
+///
+/// The current context is either the caller's account or a stored contract depending on whether the
+/// currently-executing module is a direct call or a sub-call respectively.
+pub fn list_named_keys_paged(start_after: Option<&str>, limit: u32) -> NamedKeys {
+    let (start_after_ptr, start_after_size, _bytes) = contract_api::to_ptr(start_after);
+    let (page_keys, result_size) = {
+        let mut page_keys = MaybeUninit::uninit();
+        let mut result_size = 0;
+        let ret = unsafe {
+            ext_ffi::load_named_keys_paged(
+                start_after_ptr,
+                start_after_size,
+                limit,
+                page_keys.as_mut_ptr(),
+                &mut result_size as *mut usize,
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        let page_keys = unsafe { page_keys.assume_init() };
+        (page_keys, result_size)
+    };
+    if page_keys == 0 {
+        return NamedKeys::new();
+    }
+    read_host_buffer(result_size).unwrap_or_revert()
+}
+
+/// Returns an iterator over the named keys of the current context in name order.
+///
+/// Unlike [`list_named_keys`], the keys are loaded lazily, [`NAMED_KEYS_PAGE_SIZE`] at a time, so
+/// contexts with many named keys can be traversed incrementally.  Each page starts after the last
+/// name already returned, so a named key added or removed while iterating is only seen if its name
+/// comes after that one.
+pub fn named_keys_iter() -> NamedKeysIter {
+    NamedKeysIter {
+        last_name: None,
+        page: NamedKeys::new().into_iter(),
+        is_last_page: false,
+    }
+}
+
+/// A lazy iterator over the named keys of the current context, created by [`named_keys_iter`].
+pub struct NamedKeysIter {
+    last_name: Option<String>,
+    page: btree_map::IntoIter<String, Key>,
+    is_last_page: bool,
+}
+
+impl Iterator for NamedKeysIter {
+    type Item = (String, Key);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(named_key) = self.page.next() {
+            return Some(named_key);
+        }
+        if self.is_last_page {
+            return None;
+        }
+        let page = list_named_keys_paged(self.last_name.as_deref(), NAMED_KEYS_PAGE_SIZE);
+        self.is_last_page = (page.len() as u32) < NAMED_KEYS_PAGE_SIZE;
+        if let Some(last_name) = page.keys().next_back() {
+            self.last_name = Some(last_name.clone());
+        }
+        self.page = page.into_iter();
+        self.page.next()
+    }
 }
 
 /// Validates uref against named keys.
@@ -305,6 +621,19 @@ pub fn is_valid_uref(uref: URef) -> bool {
     result != 0
 }
 
+/// Returns a copy of `uref` granting only `access_rights`, e.g. to hand a read-only view of it to
+/// another contract.
+///
+/// The returned [`URef`] is valid wherever `uref` is, and a contract it's passed to is granted only
+/// the reduced rights.  Reverts with [`ApiError::NoAccessRights`] if `uref` isn't valid in the
+/// current context or if `access_rights` isn't a subset of the rights it carries.
+pub fn downgrade_uref(uref: URef, access_rights: AccessRights) -> URef {
+    if !is_valid_uref(uref) || uref.access_rights() & access_rights != access_rights {
+        revert(ApiError::NoAccessRights)
+    }
+    uref.with_access_rights(access_rights)
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
@@ -317,19 +646,73 @@ fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     Ok(unsafe { bytes_written.assume_init() })
 }
 
-pub(crate) fn read_host_buffer(size: usize) -> Result<Vec<u8>, ApiError> {
-    let mut dest: Vec<u8> = if size == 0 {
-        Vec::new()
-    } else {
-        let bytes_non_null_ptr = contract_api::alloc_bytes(size);
-        unsafe { Vec::from_raw_parts(bytes_non_null_ptr.as_ptr(), size, size) }
-    };
-    read_host_buffer_into(&mut dest)?;
-    Ok(dest)
+/// Scratch space into which the host writes data for the contract.  It's reused across host calls
+/// so that only a read larger than any before it has to allocate.
+#[cfg(not(feature = "mock-runtime"))]
+struct ScratchBuffer(RefCell<Vec<u8>>);
+
+// Contracts are single-threaded, so the scratch buffer is never accessed from several threads.
+#[cfg(not(feature = "mock-runtime"))]
+unsafe impl Sync for ScratchBuffer {}
+
+#[cfg(not(feature = "mock-runtime"))]
+static SCRATCH_BUFFER: ScratchBuffer = ScratchBuffer(RefCell::new(Vec::new()));
+
+/// Calls `f` with the first `size` bytes of the scratch buffer, growing it if required.
+///
+/// Reverts if `f` calls back into this function, as the scratch buffer is already borrowed.
+#[cfg(not(feature = "mock-runtime"))]
+fn with_scratch_buffer<F, R>(size: usize, f: F) -> R
+where
+    F: FnOnce(&mut [u8]) -> R,
+{
+    let mut scratch_buffer = SCRATCH_BUFFER
+        .0
+        .try_borrow_mut()
+        .map_err(|_| ApiError::HostBufferFull)
+        .unwrap_or_revert();
+    if scratch_buffer.len() < size {
+        scratch_buffer.resize(size, 0);
+    }
+    f(&mut scratch_buffer[..size])
+}
+
+/// Calls `f` with a freshly allocated buffer of `size` bytes.
+///
+/// Tests run on several threads at once, so the shared scratch buffer can't be used with the mock
+/// runtime.
+#[cfg(feature = "mock-runtime")]
+fn with_scratch_buffer<F, R>(size: usize, f: F) -> R
+where
+    F: FnOnce(&mut [u8]) -> R,
+{
+    f(&mut vec![0; size])
+}
+
+/// Reads `size` bytes from the host buffer and deserializes them into a `T`.
+///
+/// Reverts if the host buffer can't be read.
+pub(crate) fn read_host_buffer<T: FromBytes>(size: usize) -> Result<T, bytesrepr::Error> {
+    with_scratch_buffer(size, |dest| {
+        read_host_buffer_into(dest).unwrap_or_revert();
+        bytesrepr::deserialize_from_slice(dest)
+    })
 }
 
-#[cfg(feature = "test-support")]
-/// Prints a debug message
+/// Emits an event named `name` carrying `data`.
+///
+/// Events are not written to global state; they are returned to the node alongside the deploy's
+/// effects so that off-chain observers can follow contract activity.
+pub fn emit_event(name: &str, data: CLValue) {
+    let (name_ptr, name_size, _name_bytes) = contract_api::to_ptr(name);
+    let (data_ptr, data_size, _data_bytes) = contract_api::to_ptr(data);
+    unsafe { ext_ffi::emit_event(name_ptr, name_size, data_ptr, data_size) }
+}
+
+/// Prints a debug message to the engine log.
+///
+/// The message is only logged if the engine has contract debugging enabled, which is not meant to
+/// be the case in production.  The call is charged for regardless.
 pub fn print(text: &str) {
     let (text_ptr, text_size, _bytes) = contract_api::to_ptr(text);
     unsafe { ext_ffi::print(text_ptr, text_size) }
@@ -8,7 +8,7 @@ use casperlabs_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractVersion, EntryPoints, NamedKeys},
     AccessRights, ApiError, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, URef,
-    UREF_SERIALIZED_LENGTH,
+    U64_SERIALIZED_LENGTH, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -19,7 +19,13 @@ use crate::{
 
 /// Reads value under `uref` in the global state.
 pub fn read<T: CLTyped + FromBytes>(uref: URef) -> Result<Option<T>, bytesrepr::Error> {
-    let key: Key = uref.into();
+    read_from_key(uref.into())
+}
+
+/// Reads the value under `key` in the global state.
+pub(crate) fn read_from_key<T: CLTyped + FromBytes>(
+    key: Key,
+) -> Result<Option<T>, bytesrepr::Error> {
     let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
 
     let value_size = {
@@ -32,8 +38,7 @@ pub fn read<T: CLTyped + FromBytes>(uref: URef) -> Result<Option<T>, bytesrepr::
         }
     };
 
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    Ok(Some(bytesrepr::deserialize(value_bytes)?))
+    Ok(Some(runtime::read_host_buffer(value_size)?))
 }
 
 /// Reads value under `uref` in the global state, reverts if value not found or is not `T`.
@@ -61,8 +66,7 @@ pub fn read_local<K: ToBytes, V: CLTyped + FromBytes>(
         }
     };
 
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    Ok(Some(bytesrepr::deserialize(value_bytes)?))
+    Ok(Some(runtime::read_host_buffer(value_size)?))
 }
 
 /// Writes `value` under `uref` in the global state.
@@ -78,6 +82,75 @@ pub fn write<T: CLTyped + ToBytes>(uref: URef, value: T) {
     }
 }
 
+/// Writes `value` under `uref` in the global state, returning the value previously stored there,
+/// or `None` if there was none.
+///
+/// This costs a single host call, unlike a [`read`] followed by a [`write`].
+pub fn write_and_return_old<T: CLTyped + FromBytes + ToBytes>(
+    uref: URef,
+    value: T,
+) -> Result<Option<T>, bytesrepr::Error> {
+    let key = Key::from(uref);
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
+
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (cl_value_ptr, cl_value_size, _bytes2) = contract_api::to_ptr(cl_value);
+
+    let old_value_size = {
+        let mut old_value_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::write_and_return_old(
+                key_ptr,
+                key_size,
+                cl_value_ptr,
+                cl_value_size,
+                old_value_size.as_mut_ptr(),
+            )
+        };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { old_value_size.assume_init() },
+            Err(ApiError::ValueNotFound) => return Ok(None),
+            Err(e) => runtime::revert(e),
+        }
+    };
+
+    Ok(Some(runtime::read_host_buffer(old_value_size)?))
+}
+
+/// Writes `new` under `uref` in the global state only if the value currently stored there is equal
+/// to `expected`, and returns whether the write happened.
+///
+/// The comparison and the write happen within a single host call, so no sub-call can modify the
+/// value in between.  Both the [`CLType`](casperlabs_types::CLType) and the bytes of `expected`
+/// must match the current value.
+pub fn compare_and_swap(uref: URef, expected: CLValue, new: CLValue) -> bool {
+    let key = Key::from(uref);
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
+    let (expected_ptr, expected_size, _bytes2) = contract_api::to_ptr(expected);
+    let (new_ptr, new_size, _bytes3) = contract_api::to_ptr(new);
+
+    let ret = unsafe {
+        ext_ffi::compare_and_swap(
+            key_ptr,
+            key_size,
+            expected_ptr,
+            expected_size,
+            new_ptr,
+            new_size,
+        )
+    };
+    ret != 0
+}
+
+/// Deletes the value stored under `uref` in the global state, so that reading it afterwards finds
+/// nothing.
+///
+/// `uref` must have [`WRITE`](casperlabs_types::AccessRights::WRITE) access rights.
+pub fn delete(uref: URef) {
+    let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(uref);
+    unsafe { ext_ffi::delete(uref_ptr, uref_size) }
+}
+
 /// Writes `value` under `key` in the context-local partition of global state.
 pub fn write_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
@@ -104,6 +177,79 @@ pub fn add<T: CLTyped + ToBytes>(uref: URef, value: T) {
     }
 }
 
+/// Adds `value` to the one currently under `uref` in the global state and returns the sum,
+/// reverting if there is then no value under `uref` or it is not a `T`.
+///
+/// This suits counters, where the new total is needed but the caller shouldn't have to compute it
+/// from a separate read.
+pub fn add_and_read<T: CLTyped + FromBytes + ToBytes>(uref: URef, value: T) -> T {
+    add(uref, value);
+    read_or_revert(uref)
+}
+
+/// A `u64` counter in the global state, created by [`counter_new`].
+///
+/// The counter is only changed by adding to it, which makes it a cheap source of monotonically
+/// increasing ids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Counter(URef);
+
+impl Counter {
+    /// Returns the [`URef`] under which the counter is stored.
+    pub fn uref(&self) -> URef {
+        self.0
+    }
+}
+
+impl From<URef> for Counter {
+    fn from(uref: URef) -> Self {
+        Counter(uref)
+    }
+}
+
+impl From<Counter> for Key {
+    fn from(counter: Counter) -> Self {
+        Key::URef(counter.0)
+    }
+}
+
+/// Returns a new [`Counter`] starting at zero.
+pub fn counter_new() -> Counter {
+    Counter(new_uref(0u64))
+}
+
+/// Increments `counter` and returns its new value, in a single host call.
+///
+/// Reverts if there is no `u64` under the counter's `URef`.
+pub fn counter_inc(counter: Counter) -> u64 {
+    let key = Key::from(counter);
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+
+    let value_non_null_ptr = contract_api::alloc_bytes(U64_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::counter_inc(key_ptr, key_size, value_non_null_ptr.as_ptr());
+        Vec::from_raw_parts(
+            value_non_null_ptr.as_ptr(),
+            U64_SERIALIZED_LENGTH,
+            U64_SERIALIZED_LENGTH,
+        )
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Returns the current value of `counter`, reverting if there is no `u64` under its `URef`.
+pub fn counter_read(counter: Counter) -> u64 {
+    read_or_revert(counter.0)
+}
+
+/// Appends `item` to the `Vec<T>` currently under `uref` in the global state.
+///
+/// Only the new item is passed to the host, so the cost doesn't grow with the length of the stored
+/// list.  Reverts if the value under `uref` is not a `Vec<T>`.
+pub fn append<T: CLTyped + ToBytes>(uref: URef, item: T) {
+    add(uref, vec![item])
+}
+
 /// Adds `value` to the one currently under `key` in the context-local partition of global state.
 pub fn add_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
@@ -116,6 +262,72 @@ pub fn add_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     }
 }
 
+/// Creates a new dictionary and returns its seed [`URef`].
+///
+/// Items of the dictionary are stored under their own keys in global state, so the cost of
+/// accessing an item does not depend on the number of items in the dictionary.
+pub fn dictionary_new() -> URef {
+    let uref_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::dictionary_new(uref_non_null_ptr.as_ptr());
+        Vec::from_raw_parts(
+            uref_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+            UREF_SERIALIZED_LENGTH,
+        )
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Reads the value under `key` in the dictionary identified by `seed_uref`.
+pub fn dictionary_get<K: ToBytes, V: CLTyped + FromBytes>(
+    seed_uref: URef,
+    key: &K,
+) -> Result<Option<V>, bytesrepr::Error> {
+    let (uref_ptr, uref_size, _bytes1) = contract_api::to_ptr(seed_uref);
+    let key_bytes = key.to_bytes()?;
+
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::dictionary_get(
+                uref_ptr,
+                uref_size,
+                key_bytes.as_ptr(),
+                key_bytes.len(),
+                value_size.as_mut_ptr(),
+            )
+        };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { value_size.assume_init() },
+            Err(ApiError::ValueNotFound) => return Ok(None),
+            Err(e) => runtime::revert(e),
+        }
+    };
+
+    Ok(Some(runtime::read_host_buffer(value_size)?))
+}
+
+/// Writes `value` under `key` in the dictionary identified by `seed_uref`.
+pub fn dictionary_put<K: ToBytes, V: CLTyped + ToBytes>(seed_uref: URef, key: K, value: V) {
+    let (uref_ptr, uref_size, _bytes1) = contract_api::to_ptr(seed_uref);
+    let (key_ptr, key_size, _bytes2) = contract_api::to_ptr(key);
+
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (cl_value_ptr, cl_value_size, _bytes3) = contract_api::to_ptr(cl_value);
+
+    unsafe {
+        ext_ffi::dictionary_put(
+            uref_ptr,
+            uref_size,
+            key_ptr,
+            key_size,
+            cl_value_ptr,
+            cl_value_size,
+        );
+    }
+}
+
 /// Returns a new unforgeable pointer, where the value is initialized to `init`.
 pub fn new_uref<T: CLTyped + ToBytes>(init: T) -> URef {
     let uref_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
@@ -211,8 +423,7 @@ pub fn create_contract_user_group(
         unsafe { output_size.assume_init() }
     };
 
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    Ok(bytesrepr::deserialize(value_bytes).unwrap_or_revert())
+    Ok(runtime::read_host_buffer(value_size).unwrap_or_revert())
 }
 
 /// Extends specified group with a new `URef`.
@@ -237,8 +448,7 @@ pub fn provision_contract_user_group_uref(
         api_error::result_from(ret)?;
         unsafe { value_size.assume_init() }
     };
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    Ok(bytesrepr::deserialize(value_bytes).unwrap_or_revert())
+    Ok(runtime::read_host_buffer(value_size).unwrap_or_revert())
 }
 
 /// Removes specified urefs from a named group.
@@ -347,3 +557,30 @@ pub fn disable_contract_version(
 
     api_error::result_from(result)
 }
+
+/// Sets whether the given version of a contract is non-reentrant.
+///
+/// The engine rejects a call to a non-reentrant contract made while that contract is already on
+/// the call stack, failing the deploy with a reentrant call error. The setting applies to this
+/// version only; versions added later by `add_contract_version` start out reentrant.
+pub fn set_non_reentrant(
+    contract_package_hash: ContractPackageHash,
+    contract_hash: ContractHash,
+    non_reentrant: bool,
+) -> Result<(), ApiError> {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes1) =
+        contract_api::to_ptr(contract_package_hash);
+    let (contract_hash_ptr, contract_hash_size, _bytes2) = contract_api::to_ptr(contract_hash);
+
+    let result = unsafe {
+        ext_ffi::set_non_reentrant(
+            contract_package_hash_ptr,
+            contract_package_hash_size,
+            contract_hash_ptr,
+            contract_hash_size,
+            non_reentrant as i32,
+        )
+    };
+
+    api_error::result_from(result)
+}
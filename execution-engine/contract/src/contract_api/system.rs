@@ -1,11 +1,12 @@
 //! Functions for interacting with the system contracts.
 
-use alloc::vec::Vec;
+use alloc::{string::ToString, vec::Vec};
 use core::mem::MaybeUninit;
 
 use casperlabs_types::{
-    account::AccountHash, api_error, bytesrepr, ApiError, ContractHash, SystemContractType,
-    TransferResult, TransferredTo, URef, U512, UREF_SERIALIZED_LENGTH,
+    account::AccountHash, api_error, bytesrepr, runtime_args, scheduled_call::SCHEDULER_NAME,
+    system_contract_errors::mint, ApiError, BlockTime, ContractHash, RuntimeArgs,
+    SystemContractType, TransferResult, TransferredTo, URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -14,6 +15,27 @@ use crate::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 
+const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
+const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
+const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
+const METHOD_BURN: &str = "burn";
+const METHOD_READ_TOTAL_SUPPLY: &str = "read_total_supply";
+const METHOD_CREATE_TOKEN: &str = "create_token";
+const METHOD_CREATE_TOKEN_PURSE: &str = "create_token_purse";
+const METHOD_TOKEN_BALANCE: &str = "token_balance";
+const METHOD_TOKEN_TRANSFER: &str = "token_transfer";
+const METHOD_READ_TOKEN_TOTAL_SUPPLY: &str = "read_token_total_supply";
+const METHOD_SCHEDULE: &str = "schedule";
+const ARG_PURSE: &str = "purse";
+const ARG_AMOUNT: &str = "amount";
+const ARG_SOURCE: &str = "source";
+const ARG_TARGET: &str = "target";
+const ARG_TOKEN_ID: &str = "token_id";
+const ARG_INITIAL_SUPPLY: &str = "initial_supply";
+const ARG_ACTIVATION_TIME: &str = "activation_time";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_ARGS: &str = "args";
+
 fn get_system_contract(system_contract: SystemContractType) -> ContractHash {
     let system_contract_index = system_contract.into();
     let contract_hash: ContractHash = {
@@ -57,6 +79,24 @@ pub fn get_standard_payment() -> ContractHash {
     get_system_contract(SystemContractType::StandardPayment)
 }
 
+/// Registers the contract under `contract_hash` in the system contract registry as `name`, so
+/// that it can be looked up with [`runtime::get_system_contract`].
+///
+/// Only the system account may register system contracts, and only from within an upgrade
+/// installer; any other caller causes the deploy to fail.
+pub fn register_system_contract(name: &str, contract_hash: ContractHash) {
+    let (name_ptr, name_size, _bytes1) = contract_api::to_ptr(name);
+    let (contract_hash_ptr, contract_hash_size, _bytes2) = contract_api::to_ptr(contract_hash);
+    unsafe {
+        ext_ffi::register_system_contract(
+            name_ptr,
+            name_size,
+            contract_hash_ptr,
+            contract_hash_size,
+        )
+    }
+}
+
 /// Creates a new empty purse and returns its [`URef`].
 pub fn create_purse() -> URef {
     let purse_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
@@ -88,8 +128,7 @@ pub fn get_balance(purse: URef) -> Option<U512> {
             Err(error) => runtime::revert(error),
         }
     };
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    let value: U512 = bytesrepr::deserialize(value_bytes).unwrap_or_revert();
+    let value: U512 = runtime::read_host_buffer(value_size).unwrap_or_revert();
     Some(value)
 }
 
@@ -126,6 +165,23 @@ pub fn transfer_from_purse_to_account(
     TransferredTo::result_from(return_code)
 }
 
+/// Transfers motes from `source` purse to each of the `transfers` target accounts in one call to
+/// the mint.  Targets which don't exist will be created.  Either every transfer succeeds or none
+/// do.
+pub fn batch_transfer(source: URef, transfers: Vec<(AccountHash, U512)>) -> Result<(), ApiError> {
+    let (source_ptr, source_size, _bytes1) = contract_api::to_ptr(source);
+    let (transfers_ptr, transfers_size, _bytes2) = contract_api::to_ptr(transfers);
+    let result = unsafe {
+        ext_ffi::transfer_from_purse_to_accounts(
+            source_ptr,
+            source_size,
+            transfers_ptr,
+            transfers_size,
+        )
+    };
+    api_error::result_from(result)
+}
+
 /// Transfers `amount` of motes from `source` purse to `target` purse.  If `target` does not exist
 /// the transfer fails.
 pub fn transfer_from_purse_to_purse(
@@ -152,3 +208,158 @@ pub fn transfer_from_purse_to_purse(
         Err(ApiError::Transfer)
     }
 }
+
+/// Destroys `amount` of motes held in `purse`, reducing the total supply tracked by the mint by
+/// the same amount.  `purse` must be writeable, i.e. the caller must be able to withdraw from it.
+pub fn burn(purse: URef, amount: U512) -> Result<(), ApiError> {
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        get_mint(),
+        METHOD_BURN,
+        runtime_args! { ARG_PURSE => purse, ARG_AMOUNT => amount },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Returns the total supply of motes tracked by the mint, i.e. all motes minted since genesis less
+/// those burned.
+pub fn read_total_supply() -> U512 {
+    runtime::call_contract(get_mint(), METHOD_READ_TOTAL_SUPPLY, RuntimeArgs::default())
+}
+
+/// Creates a new application-defined token tracked by the mint, identified by `token_id` and with
+/// a fixed supply of `initial_supply`.  Returns a new purse of that token holding the whole supply.
+///
+/// Purses of such tokens are separate from purses holding motes and can only be used with the
+/// other token functions in this module.
+pub fn create_token(token_id: &str, initial_supply: U512) -> Result<URef, ApiError> {
+    let result: Result<URef, mint::Error> = runtime::call_contract(
+        get_mint(),
+        METHOD_CREATE_TOKEN,
+        runtime_args! {
+            ARG_TOKEN_ID => token_id.to_string(),
+            ARG_INITIAL_SUPPLY => initial_supply,
+        },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Creates a new empty purse of the token `token_id` and returns its [`URef`].
+pub fn create_token_purse(token_id: &str) -> Result<URef, ApiError> {
+    let result: Result<URef, mint::Error> = runtime::call_contract(
+        get_mint(),
+        METHOD_CREATE_TOKEN_PURSE,
+        runtime_args! { ARG_TOKEN_ID => token_id.to_string() },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Returns the balance of `purse` in the token `token_id`, or `None` if `purse` is not a purse of
+/// that token.
+pub fn get_token_balance(token_id: &str, purse: URef) -> Option<U512> {
+    runtime::call_contract(
+        get_mint(),
+        METHOD_TOKEN_BALANCE,
+        runtime_args! { ARG_TOKEN_ID => token_id.to_string(), ARG_PURSE => purse },
+    )
+}
+
+/// Transfers `amount` of the token `token_id` from `source` to `target`.
+pub fn transfer_token(
+    token_id: &str,
+    source: URef,
+    target: URef,
+    amount: U512,
+) -> Result<(), ApiError> {
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        get_mint(),
+        METHOD_TOKEN_TRANSFER,
+        runtime_args! {
+            ARG_TOKEN_ID => token_id.to_string(),
+            ARG_SOURCE => source,
+            ARG_TARGET => target,
+            ARG_AMOUNT => amount,
+        },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Returns the total supply of the token `token_id`.
+pub fn read_token_total_supply(token_id: &str) -> U512 {
+    runtime::call_contract(
+        get_mint(),
+        METHOD_READ_TOKEN_TOTAL_SUPPLY,
+        runtime_args! { ARG_TOKEN_ID => token_id.to_string() },
+    )
+}
+
+/// Schedules a call to the calling contract's `entry_point` with `args`, to be made at the first
+/// block whose time is at least `activation_time`.
+///
+/// Only stored contracts may schedule calls, and only to themselves.  The call is made on behalf of
+/// the account running the current deploy.  The calls are made in the
+/// order of their activation times, each with its own gas limit; a call which fails has no effect
+/// and is not retried.  Reverts with [`ApiError::InvalidSystemContract`] if no scheduler is
+/// registered.
+///
+/// The call's gas is paid for up front by moving `payment` from `purse` into the scheduler's purse.
+/// The call's gas limit is the gas `payment` buys, and the part of `payment` left unused by the
+/// call is refunded to the account's main purse.  The scheduler reverts if its queue is full.
+pub fn schedule_call(
+    activation_time: BlockTime,
+    entry_point: &str,
+    args: RuntimeArgs,
+    purse: URef,
+    payment: U512,
+) {
+    let scheduler = runtime::get_system_contract(SCHEDULER_NAME)
+        .unwrap_or_revert_with(ApiError::InvalidSystemContract);
+    let activation_time: u64 = activation_time.into();
+    runtime::call_contract(
+        scheduler,
+        METHOD_SCHEDULE,
+        runtime_args! {
+            ARG_ACTIVATION_TIME => activation_time,
+            ARG_ENTRY_POINT => entry_point.to_string(),
+            ARG_ARGS => args,
+            ARG_PURSE => purse,
+            ARG_AMOUNT => payment,
+        },
+    )
+}
+
+/// Returns the Proof of Stake contract's payment purse, to which payment code deposits the funds
+/// which pay for the current deploy.
+///
+/// The returned [`URef`] only allows depositing into the purse and reading its balance.  The
+/// purse is emptied at the end of every deploy.
+pub fn get_payment_purse() -> URef {
+    runtime::call_contract(
+        get_proof_of_stake(),
+        METHOD_GET_PAYMENT_PURSE,
+        RuntimeArgs::default(),
+    )
+}
+
+/// Sets the purse to which the unspent part of the payment for the current deploy is refunded,
+/// instead of the main purse of the deploying account.
+///
+/// Only payment code may set the refund purse; calling this from any other phase causes the deploy
+/// to fail.  The refund purse is unset again at the end of every deploy.
+pub fn set_refund_purse(purse: URef) {
+    runtime::call_contract(
+        get_proof_of_stake(),
+        METHOD_SET_REFUND_PURSE,
+        runtime_args! { ARG_PURSE => purse },
+    )
+}
+
+/// Returns the refund purse set for the current deploy, if any.
+///
+/// The returned [`URef`] has no access rights, so it can only be compared against other purses.
+pub fn get_refund_purse() -> Option<URef> {
+    runtime::call_contract(
+        get_proof_of_stake(),
+        METHOD_GET_REFUND_PURSE,
+        RuntimeArgs::default(),
+    )
+}
@@ -2,6 +2,13 @@
 //!
 //! Generally should not be used directly.  See the [`contract_api`](crate::contract_api) for
 //! high-level bindings suitable for writing smart contracts.
+//!
+//! With the `mock-runtime` feature enabled, these are provided by the `mock_runtime` module
+//! rather than imported from the host.
+#[cfg(feature = "mock-runtime")]
+pub use crate::mock_runtime::ffi::*;
+
+#[cfg(not(feature = "mock-runtime"))]
 extern "C" {
     /// The bytes in the span of wasm memory from `key_ptr` to `key_ptr + key_size` must correspond
     /// to a valid global state key, otherwise the function will fail. If the key is de-serialized
@@ -14,6 +21,16 @@ extern "C" {
     /// * `key_size` - size of the serialized key (in bytes)
     /// * `output_size` - pointer to a value where host will write size of bytes read from given key
     pub fn read_value(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
+    /// This function deletes the value stored under the provided URef (read via de-serializing the
+    /// bytes in wasm memory from offset `uref_ptr` to `uref_ptr + uref_size`) in the global state.
+    /// This function will cause a `Trap` if the URef fails to de-serialize or if writing to it is
+    /// not permitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer to bytes representing the URef whose value to delete
+    /// * `uref_size` - size of the URef (in bytes)
+    pub fn delete(uref_ptr: *const u8, uref_size: usize);
     /// The bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`
     /// will be used together with the current context’s seed to form a local key.
     /// The value at that local key is read from the global state, serialized and
@@ -40,7 +57,50 @@ extern "C" {
     /// * `value_ptr` - pointer to bytes representing the value to write at the key
     /// * `value_size` - size of the value (in bytes)
     pub fn write(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+    /// This function writes the provided value under the provided key in the global state, like
+    /// [`write`], and additionally serializes the value previously stored under that key and
+    /// buffers it in the runtime. This result can be obtained via the [`read_host_buffer`]
+    /// function. Returns standard error code, which is
+    /// [`ValueNotFound`](casperlabs_types::ApiError::ValueNotFound) if there was no previous
+    /// value, in which case the new value is written nonetheless.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ptr` - pointer to bytes representing the key to write to
+    /// * `key_size` - size of the key (in bytes)
+    /// * `value_ptr` - pointer to bytes representing the value to write at the key
+    /// * `value_size` - size of the value (in bytes)
+    /// * `output_size` - pointer to a value where host will write size of bytes of the old value
+    pub fn write_and_return_old(
+        key_ptr: *const u8,
+        key_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
 
+    /// This function writes the new value under the provided key in the global state, like
+    /// [`write`], but only if the value currently stored under the key is equal to the expected
+    /// value. Returns 1 if the value was written, and 0 otherwise. This function will cause a
+    /// `Trap` if the key or either value fail to de-serialize or if reading from or writing to that
+    /// key is not permitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ptr` - pointer to bytes representing the key to write to
+    /// * `key_size` - size of the key (in bytes)
+    /// * `expected_ptr` - pointer to bytes representing the value expected at the key
+    /// * `expected_size` - size of the expected value (in bytes)
+    /// * `new_ptr` - pointer to bytes representing the value to write at the key
+    /// * `new_size` - size of the new value (in bytes)
+    pub fn compare_and_swap(
+        key_ptr: *const u8,
+        key_size: usize,
+        expected_ptr: *const u8,
+        expected_size: usize,
+        new_ptr: *const u8,
+        new_size: usize,
+    ) -> i32;
     /// The bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`
     /// will be used together with the current context’s seed to form a local key.
     /// This function writes the provided value (read via de-serializing the bytes
@@ -75,6 +135,17 @@ extern "C" {
     /// * `value_ptr` - pointer to bytes representing the value to write at the key
     /// * `value_size` - size of the value (in bytes)
     pub fn add(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+    /// This function adds one to the `u64` counter under the provided key in the global state, and
+    /// writes the incremented value to `value_ptr`. This function will cause a `Trap` if the key
+    /// fails to de-serialize, if adding to or reading that key is not permitted, or if no `u64`
+    /// presently exists at that key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ptr` - pointer to bytes representing the key of the counter
+    /// * `key_size` - size of the key (in bytes)
+    /// * `value_ptr` - pointer to a buffer of 8 bytes where the incremented value will be written
+    pub fn counter_inc(key_ptr: *const u8, key_size: usize, value_ptr: *mut u8);
     ///
     pub fn add_local(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
     /// This function causes the runtime to generate a new [`casperlabs_types::uref::URef`], with
@@ -95,15 +166,33 @@ extern "C" {
     pub fn new_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize);
     ///
     pub fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32;
+    /// Loads up to `limit` named keys of the current context into the host buffer, in name order,
+    /// starting after the given name. The host returns at most
+    /// [`casperlabs_types::contracts::MAX_NAMED_KEYS_PAGE_SIZE`] keys, whatever the `limit`.
+    /// Returns a standard error code.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_after_ptr` - pointer to a serialized `Option<String>` holding the name the page
+    ///   starts after, or `None` for the first page
+    /// * `start_after_size` - size of the serialized name
+    /// * `limit` - maximum amount of named keys in the page
+    /// * `page_keys` - pointer to a value which will be set to the amount of named keys in the page
+    /// * `result_size` - pointer to a value which will be set to the size of the serialized page
+    pub fn load_named_keys_paged(
+        start_after_ptr: *const u8,
+        start_after_size: usize,
+        limit: u32,
+        page_keys: *mut usize,
+        result_size: *mut usize,
+    ) -> i32;
     /// This function causes a `Trap`, terminating the currently running module,
     /// but first copies the bytes from `value_ptr` to `value_ptr + value_size` to
     /// a buffer which is returned to the calling module (if this module was
     /// invoked by [`call_contract`] or [`call_versioned_contract`]). Additionally, the known
     /// [`casperlabs_types::uref::URef`]s of the calling context are augmented with the
-    /// [`casperlabs_types::uref::URef`]s de-serialized from wasm memory offset
-    /// `extra_urefs_ptr` to `extra_urefs_ptr + extra_urefs_size`. This function will cause a
-    /// `Trap` if the bytes at `extra_urefs_ptr` cannot be de-serialized as type `Vec<URef>`, or
-    /// if any of the extra [`casperlabs_types::uref::URef`]s are invalid in the current
+    /// [`casperlabs_types::uref::URef`]s embedded in the returned value. This function will cause
+    /// a `Trap` if any of those [`casperlabs_types::uref::URef`]s are invalid in the current
     /// context.
     ///
     /// # Arguments
@@ -111,6 +200,15 @@ extern "C" {
     /// * `value_ptr`: pointer to bytes representing the value to return to the caller
     /// * `value_size`: size of the value (in bytes)
     pub fn ret(value_ptr: *const u8, value_size: usize) -> !;
+    /// This function behaves like [`ret`], except that the known
+    /// [`casperlabs_types::uref::URef`]s of the calling context are not augmented with the
+    /// [`casperlabs_types::uref::URef`]s embedded in the returned value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_ptr`: pointer to bytes representing the value to return to the caller
+    /// * `value_size`: size of the value (in bytes)
+    pub fn ret_without_transfer(value_ptr: *const u8, value_size: usize) -> !;
     ///
     pub fn get_key(
         name_ptr: *const u8,
@@ -233,6 +331,18 @@ extern "C" {
     /// * `action` - index representing the action threshold to set
     /// * `threshold` - new value of the threshold for performing this action
     pub fn set_action_threshold(permission_level: u32, threshold: u32) -> i32;
+    /// This function attempts to replace the main purse of the account for this deploy. The return
+    /// value is a status code where 0 represents success, 1 means permission denied (this could
+    /// be because the function was called outside of session code or because the key management
+    /// threshold was not met by the keys authorizing the deploy), and 2 means the given purse is
+    /// invalid (it isn't a purse, or doesn't carry read, add and write access rights valid in the
+    /// current context).
+    ///
+    /// # Arguments
+    ///
+    /// * `purse_ptr` - pointer to the serialized purse
+    /// * `purse_size` - size of the serialized purse
+    pub fn set_main_purse(purse_ptr: *const u8, purse_size: usize) -> i32;
     /// This function returns the public key of the account for this deploy. The
     /// result is always 36-bytes in length (4 bytes prefix on a 32-byte public
     /// key); it is up to the caller to ensure the right amount of memory is
@@ -243,6 +353,18 @@ extern "C" {
     ///
     /// * `dest_ptr` - pointer to position in wasm memory where to write the result
     pub fn get_caller(output_size: *mut usize) -> i32;
+    /// This function writes the call stack of the current execution, a
+    /// `Vec<casperlabs_types::CallStackElement>`, to the host buffer and its serialized size to
+    /// `output_size`. The bytes can then be read with [`read_host_buffer`].
+    ///
+    /// # Arguments
+    ///
+    /// * `output_size` - pointer to a value where the size of the serialized call stack will be
+    ///   written
+    pub fn get_call_stack(output_size: *mut usize) -> i32;
+    /// This function returns 1 if the account for this deploy is the reserved system account
+    /// [`casperlabs_types::account::SYSTEM_ACCOUNT`], and 0 otherwise.
+    pub fn is_system_caller() -> i32;
     /// This function gets the timestamp which will be in the block this deploy is
     /// included in. The return value is always a 64-bit unsigned integer,
     /// representing the number of milliseconds since the Unix epoch. It is up to
@@ -253,6 +375,24 @@ extern "C" {
     ///
     /// * `dest_ptr` - pointer in wasm memory where to write the result
     pub fn get_blocktime(dest_ptr: *const u8);
+    /// This function gets the gas price currently in effect, i.e. the number of motes charged per
+    /// unit of gas. The return value is always a 64-bit unsigned integer. It is up to the caller
+    /// to ensure there are 8 bytes allocated at `dest_ptr`, otherwise data corruption in the wasm
+    /// memory may occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_ptr` - pointer in wasm memory where to write the result
+    pub fn get_gas_price(dest_ptr: *mut u8);
+    /// This function gets the hash of the deploy being executed, which is the same in every
+    /// contract it calls. The hash is always 32 bytes long. It is up to the caller to ensure
+    /// there are 32 bytes allocated at `dest_ptr`, otherwise data corruption in the wasm memory
+    /// may occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_ptr` - pointer in wasm memory where to write the result
+    pub fn get_deploy_hash(dest_ptr: *mut u8);
     /// This function uses the mint contract to create a new, empty purse. If the
     /// call is successful then the [`casperlabs_types::uref::URef`] (in serialized form) is written
     /// to the indicated place in wasm memory. It is up to the caller to ensure at
@@ -332,6 +472,26 @@ extern "C" {
         amount_ptr: *const u8,
         amount_size: usize,
     ) -> i32;
+    /// This function uses the mint contract’s batch transfer function to transfer tokens from the
+    /// specified purse to the main purses of several target accounts in one call.  Any target
+    /// account which does not exist is automatically created.  The source is a serialized
+    /// [`casperlabs_types::uref::URef`] and the transfers are a serialized list of
+    /// `(AccountHash, U512)` pairs.  Either every transfer succeeds or none do.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_ptr` - pointer in wasm memory to bytes representing the source
+    ///   [`casperlabs_types::uref::URef`] to transfer from
+    /// * `source_size` - size of the source [`casperlabs_types::uref::URef`] (in bytes)
+    /// * `transfers_ptr` - pointer in wasm memory to bytes representing the target accounts and the
+    ///   amounts to transfer to them
+    /// * `transfers_size` - size of the transfers (in bytes)
+    pub fn transfer_from_purse_to_accounts(
+        source_ptr: *const u8,
+        source_size: usize,
+        transfers_ptr: *const u8,
+        transfers_size: usize,
+    ) -> i32;
     /// This function uses the mint contract’s transfer function to transfer
     /// tokens from the specified source purse to the specified target purse. If
     /// the target account does not exist then it is automatically created, and
@@ -398,6 +558,22 @@ extern "C" {
         dest_ptr: *mut u8,
         dest_size: usize,
     ) -> i32;
+    /// Registers the contract under the given hash in the system contract registry under the
+    /// given name, replacing any existing entry with that name.  Only the system account may
+    /// call this function, and only while running an upgrade installer.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_ptr` - pointer to the serialized name of the system contract
+    /// * `name_size` - size of the serialized name
+    /// * `contract_hash_ptr` - pointer to the serialized contract hash
+    /// * `contract_hash_size` - size of the serialized contract hash
+    pub fn register_system_contract(
+        name_ptr: *const u8,
+        name_size: usize,
+        contract_hash_ptr: *const u8,
+        contract_hash_size: usize,
+    );
     ///
     pub fn get_main_purse(dest_ptr: *mut u8);
     /// This function copies the contents of the current runtime buffer into the
@@ -486,6 +662,33 @@ extern "C" {
         contract_hash_ptr: *const u8,
         contract_hash_size: usize,
     ) -> i32;
+    /// Retires a contract, leaving a tombstone which keeps the contract's named keys but can no
+    /// longer be called, and disables its version in its contract package. Returns non-zero
+    /// standard error for a failure, otherwise a zero indicates success.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_hash_ptr` - pointer to serialized contract hash.
+    /// * `contract_hash_size` - size of contract hash in serialized form.
+    pub fn disable_contract(contract_hash_ptr: *const u8, contract_hash_size: usize) -> i32;
+    /// Sets whether a contract in a contract package rejects calls made while it is already on the
+    /// call stack. Returns non-zero standard error for a failure, otherwise a zero indicates
+    /// success.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `contract_hash_ptr` - pointer to serialized contract hash.
+    /// * `contract_hash_size` - size of contract hash in serialized form.
+    /// * `non_reentrant` - non-zero if reentrant calls should be rejected, zero otherwise.
+    pub fn set_non_reentrant(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        contract_hash_ptr: *const u8,
+        contract_hash_size: usize,
+        non_reentrant: i32,
+    ) -> i32;
     /// Calls a contract by its hash. Requires entry point name that has to be present on a
     /// specified contract, and serialized named arguments. Returns a standard error code in
     /// case of failure, otherwise a successful execution returns zero. Bytes returned from contract
@@ -509,6 +712,27 @@ extern "C" {
         runtime_args_size: usize,
         result_size: *mut usize,
     ) -> i32;
+    /// Calls a contract by its hash like [`call_contract`], discarding any changes the contract
+    /// makes to global state. Execution fails if the called contract attempts a write.
+    ///
+    /// # Arguments
+    /// * `contract_hash_ptr` - pointer to serialized contract hash.
+    /// * `contract_hash_size` - size of contract hash in serialized form.
+    /// * `entry_point_name_ptr` - pointer to serialized contract entry point name
+    /// * `entry_point_name_size` - size of serialized contract entry point name
+    /// * `runtime_args_ptr` - pointer to serialized runtime arguments
+    /// * `runtime_args_size` - size of serialized runtime arguments
+    /// * `result_size` - a pointer to a value which will be set to a size of bytes of called
+    ///   contract return value
+    pub fn call_contract_read_only(
+        contract_hash_ptr: *const u8,
+        contract_hash_size: usize,
+        entry_point_name_ptr: *const u8,
+        entry_point_name_size: usize,
+        runtime_args_ptr: *const u8,
+        runtime_args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
     /// Calls a contract by its package hash. Optionally accepts a serialized `Option<u32>` as a
     /// version that for `None` case would call most recent version for given protocol version,
     /// otherwise it selects a specific contract version. Requires an entry point name
@@ -626,13 +850,161 @@ extern "C" {
         urefs_ptr: *const u8,
         urefs_size: usize,
     ) -> i32;
+    /// Writes `dest_size` deterministic pseudo-random bytes into wasm memory at `dest_ptr`. The
+    /// bytes are derived from the deploy hash, the current phase and the blocktime, so every
+    /// validator executing the deploy observes the same values. It is up to the caller to ensure
+    /// there are `dest_size` bytes allocated at `dest_ptr`, otherwise data corruption in the wasm
+    /// memory may occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_ptr` - pointer in wasm memory where to write the result
+    /// * `dest_size` - number of bytes to write
+    pub fn random_bytes(dest_ptr: *mut u8, dest_size: usize);
+    /// Computes `a * b / c` for the given serialized [`casperlabs_types::U512`]s, using a
+    /// 1024-bit intermediate so that the product can't overflow, and writes the result into wasm
+    /// memory at `dest_ptr` as 64 little-endian bytes. Returns [`casperlabs_types::ApiError::None`]
+    /// if `c` is zero or the result doesn't fit in a `U512`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a_ptr` - pointer in wasm memory to the serialized multiplicand
+    /// * `a_size` - size of the serialized multiplicand
+    /// * `b_ptr` - pointer in wasm memory to the serialized numerator
+    /// * `b_size` - size of the serialized numerator
+    /// * `c_ptr` - pointer in wasm memory to the serialized denominator
+    /// * `c_size` - size of the serialized denominator
+    /// * `dest_ptr` - pointer in wasm memory to a buffer of 64 bytes where to write the result
+    pub fn u512_mul_div(
+        a_ptr: *const u8,
+        a_size: usize,
+        b_ptr: *const u8,
+        b_size: usize,
+        c_ptr: *const u8,
+        c_size: usize,
+        dest_ptr: *mut u8,
+    ) -> i32;
+    /// Emits a named event carrying a [`casperlabs_types::CLValue`]. Events are recorded in the
+    /// execution effects of the deploy, and are discarded along with all other effects if the
+    /// deploy fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_ptr` - pointer to serialized event name
+    /// * `name_size` - size of serialized event name
+    /// * `data_ptr` - pointer to serialized event data
+    /// * `data_size` - size of serialized event data
+    pub fn emit_event(name_ptr: *const u8, name_size: usize, data_ptr: *const u8, data_size: usize);
+    /// Creates a new dictionary and writes its seed [`casperlabs_types::URef`] (in serialized
+    /// form) to the indicated place in wasm memory. It is up to the caller to ensure at least
+    /// `UREF_SERIALIZED_LENGTH` bytes are allocated at `uref_ptr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer in wasm memory where to write the result
+    pub fn dictionary_new(uref_ptr: *mut u8);
+    /// This function reads the value stored under the given item key in a dictionary, writing
+    /// it to the host buffer. Returns standard error code.
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer (offset in wasm memory) to the serialized dictionary seed uref
+    /// * `uref_size` - size of the serialized dictionary seed uref
+    /// * `key_ptr` - pointer to the serialized item key
+    /// * `key_size` - size of the serialized item key
+    /// * `output_size` - pointer to a value where host will write size of bytes of read value
+    pub fn dictionary_get(
+        uref_ptr: *const u8,
+        uref_size: usize,
+        key_ptr: *const u8,
+        key_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
+    /// This function writes the provided value under the given item key in a dictionary. The
+    /// caller must have write access to the dictionary seed uref.
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer (offset in wasm memory) to the serialized dictionary seed uref
+    /// * `uref_size` - size of the serialized dictionary seed uref
+    /// * `key_ptr` - pointer to the serialized item key
+    /// * `key_size` - size of the serialized item key
+    /// * `value_ptr` - pointer to bytes representing the value to write under the item key
+    /// * `value_size` - size of the value (in bytes)
+    pub fn dictionary_put(
+        uref_ptr: *const u8,
+        uref_size: usize,
+        key_ptr: *const u8,
+        key_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+
+    /// This function computes the 32-byte blake2b digest of the given bytes and writes it to
+    /// `out_ptr`. Returns standard error code.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_ptr` - pointer (offset in wasm memory) to the bytes to hash
+    /// * `in_size` - size of the bytes to hash
+    /// * `out_ptr` - pointer to the location where the host writes the digest
+    /// * `out_size` - size of the output buffer; must be at least 32
+    pub fn blake2b(in_ptr: *const u8, in_size: usize, out_ptr: *mut u8, out_size: usize) -> i32;
+    /// This function computes the 32-byte keccak-256 digest of the given bytes and writes it to
+    /// `out_ptr`. Returns standard error code.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_ptr` - pointer (offset in wasm memory) to the bytes to hash
+    /// * `in_size` - size of the bytes to hash
+    /// * `out_ptr` - pointer to the location where the host writes the digest
+    /// * `out_size` - size of the output buffer; must be at least 32
+    pub fn keccak256(in_ptr: *const u8, in_size: usize, out_ptr: *mut u8, out_size: usize) -> i32;
+    /// This function verifies an Ed25519 signature of a message. Returns 1 if the signature is
+    /// valid, and 0 otherwise (including when any input is malformed).
+    ///
+    /// # Arguments
+    ///
+    /// * `message_ptr` - pointer (offset in wasm memory) to the signed message
+    /// * `message_size` - size of the signed message
+    /// * `signature_ptr` - pointer to the 64-byte signature
+    /// * `signature_size` - size of the signature
+    /// * `public_key_ptr` - pointer to the 32-byte public key
+    /// * `public_key_size` - size of the public key
+    pub fn verify_ed25519(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
+    /// This function verifies a compact secp256k1 signature of a 32-byte message digest. Returns
+    /// 1 if the signature is valid, and 0 otherwise (including when any input is malformed).
+    ///
+    /// # Arguments
+    ///
+    /// * `message_ptr` - pointer (offset in wasm memory) to the 32-byte message digest
+    /// * `message_size` - size of the message digest
+    /// * `signature_ptr` - pointer to the 64-byte compact signature
+    /// * `signature_size` - size of the signature
+    /// * `public_key_ptr` - pointer to the compressed or uncompressed public key
+    /// * `public_key_size` - size of the public key
+    pub fn verify_secp256k1(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
 
-    /// Prints data directly to stanadard output on the host.
+    /// Writes debug output to the host's log, if the host has contract debugging enabled.  The
+    /// call is charged for either way.
     ///
     /// # Arguments
     ///
     /// * `text_ptr` - pointer to serialized text to print
     /// * `text_size` - size of serialized text to print
-    #[cfg(feature = "test-support")]
     pub fn print(text_ptr: *const u8, text_size: usize);
 }
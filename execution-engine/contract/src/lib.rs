@@ -47,6 +47,12 @@
 //!
 //! Support for writing smart contracts are contained in the [`contract_api`] module and its
 //! submodules.
+//!
+//! # Unit testing
+//!
+//! Enabling the crate's `mock-runtime` feature replaces the host functions with an in-memory
+//! stand-in, so contract code can be run by `cargo test` on the build machine.  See the
+//! `mock_runtime` module for details.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(
@@ -75,4 +81,6 @@ pub mod contract_api;
 pub mod ext_ffi;
 #[cfg(not(any(feature = "std", test)))]
 pub mod handlers;
+#[cfg(feature = "mock-runtime")]
+pub mod mock_runtime;
 pub mod unwrap_or_revert;
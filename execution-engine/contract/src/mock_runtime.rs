@@ -0,0 +1,838 @@
+//! An in-memory stand-in for the host, allowing contract code to be unit tested natively.
+//!
+//! With the `mock-runtime` feature enabled, the functions in [`ext_ffi`](crate::ext_ffi) are backed
+//! by a per-thread [`MockRuntime`] rather than being imported from the host.  Named keys, global
+//! state, runtime arguments and the caller can be set up before running the code under test, and
+//! inspected afterwards along with the host functions it called.
+//!
+//! Only the host functions needed to exercise storage, named keys and runtime arguments are
+//! supported; calling any other host function panics.
+//!
+//! ```rust,ignore
+//! use casperlabs_contract::{contract_api::runtime, mock_runtime::{self, Outcome}};
+//! use casperlabs_types::{runtime_args, RuntimeArgs};
+//!
+//! mock_runtime::reset();
+//! mock_runtime::set_args(runtime_args! { "amount" => 5u64 });
+//! let outcome = mock_runtime::call(|| {
+//!     let amount: u64 = runtime::get_named_arg("amount");
+//!     assert_eq!(amount, 5);
+//! });
+//! assert_eq!(outcome, Outcome::Completed);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use std::{cell::RefCell, collections::BTreeMap, panic};
+
+use casperlabs_types::{
+    account::AccountHash, contracts::NamedKeys, AccessRights, ApiError, BlockTime, CLValue,
+    CallStackElement, Key, Phase, RuntimeArgs, URef, U512,
+};
+
+thread_local! {
+    static RUNTIME: RefCell<MockRuntime> = RefCell::new(MockRuntime::default());
+}
+
+/// The state of the mocked host.
+#[derive(Debug)]
+pub struct MockRuntime {
+    args: RuntimeArgs,
+    named_keys: NamedKeys,
+    global_state: BTreeMap<Key, CLValue>,
+    caller: AccountHash,
+    call_stack: Vec<CallStackElement>,
+    blocktime: BlockTime,
+    phase: Phase,
+    gas_price: u64,
+    deploy_hash: [u8; 32],
+    host_buffer: Option<CLValue>,
+    next_uref_addr: u64,
+    host_calls: Vec<&'static str>,
+    printed: Vec<String>,
+    events: Vec<(String, CLValue)>,
+}
+
+impl Default for MockRuntime {
+    fn default() -> Self {
+        MockRuntime {
+            args: RuntimeArgs::new(),
+            named_keys: NamedKeys::new(),
+            global_state: BTreeMap::new(),
+            caller: AccountHash::new([0; 32]),
+            call_stack: Vec::new(),
+            blocktime: BlockTime::default(),
+            phase: Phase::Session,
+            gas_price: 1,
+            deploy_hash: [0; 32],
+            host_buffer: None,
+            next_uref_addr: 0,
+            host_calls: Vec::new(),
+            printed: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+/// How the code run by [`call`] ended.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The code returned normally.
+    Completed,
+    /// The code called [`runtime::ret`](crate::contract_api::runtime::ret) with the given value.
+    Returned(CLValue),
+    /// The code called [`runtime::revert`](crate::contract_api::runtime::revert) with the given
+    /// error.
+    Reverted(ApiError),
+}
+
+/// Unwound from `ret` and `revert`, which must not return, to end the code run by [`call`].
+enum Exit {
+    Returned(CLValue),
+    Reverted(ApiError),
+}
+
+/// Runs `f`, which may end by returning a value or reverting as contract code would.
+///
+/// Panics other than those raised by `ret` and `revert` are propagated.
+pub fn call<F: FnOnce()>(f: F) -> Outcome {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(()) => Outcome::Completed,
+        Err(payload) => match payload.downcast::<Exit>() {
+            Ok(exit) => match *exit {
+                Exit::Returned(value) => Outcome::Returned(value),
+                Exit::Reverted(error) => Outcome::Reverted(error),
+            },
+            Err(payload) => panic::resume_unwind(payload),
+        },
+    }
+}
+
+fn with_runtime<F: FnOnce(&mut MockRuntime) -> R, R>(f: F) -> R {
+    RUNTIME.with(|runtime| f(&mut runtime.borrow_mut()))
+}
+
+/// Resets the mocked host of the current thread to its initial, empty state.
+pub fn reset() {
+    with_runtime(|runtime| *runtime = MockRuntime::default())
+}
+
+/// Sets the arguments returned by [`runtime::get_named_arg`](crate::contract_api::runtime).
+pub fn set_args(args: RuntimeArgs) {
+    with_runtime(|runtime| runtime.args = args)
+}
+
+/// Sets the account returned by [`runtime::get_caller`](crate::contract_api::runtime).
+pub fn set_caller(caller: AccountHash) {
+    with_runtime(|runtime| runtime.caller = caller)
+}
+
+/// Sets the call stack returned by [`runtime::get_call_stack`](crate::contract_api::runtime).
+pub fn set_call_stack(call_stack: Vec<CallStackElement>) {
+    with_runtime(|runtime| runtime.call_stack = call_stack)
+}
+
+/// Sets the block time returned by [`runtime::get_blocktime`](crate::contract_api::runtime).
+pub fn set_blocktime(blocktime: BlockTime) {
+    with_runtime(|runtime| runtime.blocktime = blocktime)
+}
+
+/// Sets the phase returned by [`runtime::get_phase`](crate::contract_api::runtime).
+pub fn set_phase(phase: Phase) {
+    with_runtime(|runtime| runtime.phase = phase)
+}
+
+/// Sets the deploy hash returned by [`runtime::get_deploy_hash`](crate::contract_api::runtime).
+pub fn set_deploy_hash(deploy_hash: [u8; 32]) {
+    with_runtime(|runtime| runtime.deploy_hash = deploy_hash)
+}
+
+/// Stores `key` under `name` in the named keys of the current context.
+pub fn put_named_key(name: &str, key: Key) {
+    with_runtime(|runtime| {
+        runtime.named_keys.insert(name.into(), key);
+    })
+}
+
+/// Returns the named keys of the current context.
+pub fn named_keys() -> NamedKeys {
+    with_runtime(|runtime| runtime.named_keys.clone())
+}
+
+/// Writes `value` under `key` in global state.
+pub fn write(key: Key, value: CLValue) {
+    with_runtime(|runtime| {
+        runtime.global_state.insert(key.normalize(), value);
+    })
+}
+
+/// Returns the value stored under `key` in global state.
+pub fn read(key: Key) -> Option<CLValue> {
+    with_runtime(|runtime| runtime.global_state.get(&key.normalize()).cloned())
+}
+
+/// Returns the names of the host functions called so far, in order.
+pub fn host_calls() -> Vec<&'static str> {
+    with_runtime(|runtime| runtime.host_calls.clone())
+}
+
+/// Returns the messages passed to [`runtime::print`](crate::contract_api::runtime::print).
+pub fn printed() -> Vec<String> {
+    with_runtime(|runtime| runtime.printed.clone())
+}
+
+/// Returns the events emitted through
+/// [`runtime::emit_event`](crate::contract_api::runtime::emit_event).
+pub fn events() -> Vec<(String, CLValue)> {
+    with_runtime(|runtime| runtime.events.clone())
+}
+
+/// Mocked implementations of the host functions declared in [`ext_ffi`](crate::ext_ffi).
+#[allow(missing_docs, clippy::missing_safety_doc)]
+pub mod ffi {
+    use core::{ptr, slice};
+    use std::panic;
+
+    use casperlabs_types::{
+        account::SYSTEM_ACCOUNT,
+        api_error,
+        bytesrepr::{self, FromBytes, ToBytes},
+    };
+
+    use super::*;
+
+    fn record(name: &'static str) {
+        with_runtime(|runtime| runtime.host_calls.push(name))
+    }
+
+    fn unsupported(name: &'static str) -> ! {
+        panic!("`{}` is not supported by the mock runtime", name)
+    }
+
+    unsafe fn input<'a>(ptr: *const u8, size: usize) -> &'a [u8] {
+        if size == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(ptr, size)
+        }
+    }
+
+    unsafe fn deserialize<T: FromBytes>(ptr: *const u8, size: usize) -> T {
+        bytesrepr::deserialize(input(ptr, size).to_vec())
+            .unwrap_or_else(|error| panic!("mock runtime failed to deserialize input: {}", error))
+    }
+
+    unsafe fn output(bytes: &[u8], dest_ptr: *mut u8) {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dest_ptr, bytes.len())
+    }
+
+    /// Buffers `value` for a subsequent `read_host_buffer`, returning the size to be read.
+    fn buffer(value: CLValue) -> usize {
+        let size = value.inner_bytes().len();
+        with_runtime(|runtime| runtime.host_buffer = Some(value));
+        size
+    }
+
+    pub unsafe fn read_value(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32 {
+        record("read_value");
+        let key: Key = deserialize(key_ptr, key_size);
+        match read(key) {
+            Some(value) => {
+                *output_size = buffer(value);
+                api_error::i32_from(Ok(()))
+            }
+            None => api_error::i32_from(Err(ApiError::ValueNotFound)),
+        }
+    }
+
+    pub unsafe fn read_value_local(_: *const u8, _: usize, _: *mut usize) -> i32 {
+        unsupported("read_value_local")
+    }
+
+    pub unsafe fn write(
+        key_ptr: *const u8,
+        key_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    ) {
+        record("write");
+        let key: Key = deserialize(key_ptr, key_size);
+        let value: CLValue = deserialize(value_ptr, value_size);
+        super::write(key, value)
+    }
+
+    pub unsafe fn write_and_return_old(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("write_and_return_old")
+    }
+
+    pub unsafe fn compare_and_swap(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("compare_and_swap")
+    }
+
+    pub unsafe fn delete(uref_ptr: *const u8, uref_size: usize) {
+        record("delete");
+        let uref: URef = deserialize(uref_ptr, uref_size);
+        with_runtime(|runtime| {
+            runtime.global_state.remove(&Key::URef(uref).normalize());
+        })
+    }
+
+    pub unsafe fn write_local(_: *const u8, _: usize, _: *const u8, _: usize) {
+        unsupported("write_local")
+    }
+
+    pub unsafe fn add(_: *const u8, _: usize, _: *const u8, _: usize) {
+        unsupported("add")
+    }
+
+    pub unsafe fn counter_inc(key_ptr: *const u8, key_size: usize, value_ptr: *mut u8) {
+        record("counter_inc");
+        let key: Key = deserialize(key_ptr, key_size);
+        let value: u64 = super::read(key)
+            .expect("should have counter")
+            .into_t::<u64>()
+            .expect("should be u64")
+            + 1;
+        super::write(key, CLValue::from_t(value).expect("should convert u64"));
+        output(
+            &value.into_bytes().expect("should serialize u64"),
+            value_ptr,
+        )
+    }
+
+    pub unsafe fn add_local(_: *const u8, _: usize, _: *const u8, _: usize) {
+        unsupported("add_local")
+    }
+
+    pub unsafe fn new_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize) {
+        record("new_uref");
+        let value: CLValue = deserialize(value_ptr, value_size);
+        let uref = with_runtime(|runtime| {
+            let mut addr = [0; 32];
+            addr[..8].copy_from_slice(&runtime.next_uref_addr.to_le_bytes());
+            runtime.next_uref_addr += 1;
+            URef::new(addr, AccessRights::READ_ADD_WRITE)
+        });
+        super::write(Key::URef(uref), value);
+        output(&uref.into_bytes().expect("should serialize URef"), uref_ptr)
+    }
+
+    pub unsafe fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32 {
+        record("load_named_keys");
+        let named_keys = named_keys();
+        *total_keys = named_keys.len();
+        *result_size = buffer(CLValue::from_t(named_keys).expect("should convert named keys"));
+        api_error::i32_from(Ok(()))
+    }
+
+    pub unsafe fn load_named_keys_paged(
+        _: *const u8,
+        _: usize,
+        _: u32,
+        _: *mut usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("load_named_keys_paged")
+    }
+
+    pub unsafe fn ret(value_ptr: *const u8, value_size: usize) -> ! {
+        record("ret");
+        let value: CLValue = deserialize(value_ptr, value_size);
+        panic::resume_unwind(Box::new(Exit::Returned(value)))
+    }
+
+    pub unsafe fn ret_without_transfer(value_ptr: *const u8, value_size: usize) -> ! {
+        record("ret_without_transfer");
+        let value: CLValue = deserialize(value_ptr, value_size);
+        panic::resume_unwind(Box::new(Exit::Returned(value)))
+    }
+
+    pub unsafe fn get_key(
+        name_ptr: *const u8,
+        name_size: usize,
+        output_ptr: *mut u8,
+        output_size: usize,
+        bytes_written_ptr: *mut usize,
+    ) -> i32 {
+        record("get_key");
+        let name: String = deserialize(name_ptr, name_size);
+        let key = match with_runtime(|runtime| runtime.named_keys.get(&name).cloned()) {
+            Some(key) => key,
+            None => return api_error::i32_from(Err(ApiError::MissingKey)),
+        };
+        let key_bytes = key.into_bytes().expect("should serialize Key");
+        if key_bytes.len() > output_size {
+            return api_error::i32_from(Err(ApiError::BufferTooSmall));
+        }
+        output(&key_bytes, output_ptr);
+        *bytes_written_ptr = key_bytes.len();
+        api_error::i32_from(Ok(()))
+    }
+
+    pub unsafe fn has_key(name_ptr: *const u8, name_size: usize) -> i32 {
+        record("has_key");
+        let name: String = deserialize(name_ptr, name_size);
+        if with_runtime(|runtime| runtime.named_keys.contains_key(&name)) {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub unsafe fn put_key(
+        name_ptr: *const u8,
+        name_size: usize,
+        key_ptr: *const u8,
+        key_size: usize,
+    ) {
+        record("put_key");
+        let name: String = deserialize(name_ptr, name_size);
+        let key: Key = deserialize(key_ptr, key_size);
+        put_named_key(&name, key)
+    }
+
+    pub unsafe fn remove_key(name_ptr: *const u8, name_size: usize) {
+        record("remove_key");
+        let name: String = deserialize(name_ptr, name_size);
+        with_runtime(|runtime| {
+            runtime.named_keys.remove(&name);
+        })
+    }
+
+    pub unsafe fn revert(status: u32) -> ! {
+        record("revert");
+        panic::resume_unwind(Box::new(Exit::Reverted(ApiError::from(status))))
+    }
+
+    pub unsafe fn is_valid_uref(uref_ptr: *const u8, uref_size: usize) -> i32 {
+        record("is_valid_uref");
+        let uref: URef = deserialize(uref_ptr, uref_size);
+        let is_known = with_runtime(|runtime| {
+            runtime
+                .global_state
+                .contains_key(&Key::URef(uref).normalize())
+                || runtime
+                    .named_keys
+                    .values()
+                    .any(|key| key.as_uref().map(URef::addr) == Some(uref.addr()))
+        });
+        i32::from(is_known)
+    }
+
+    pub unsafe fn add_associated_key(_: *const u8, _: usize, _: i32) -> i32 {
+        unsupported("add_associated_key")
+    }
+
+    pub unsafe fn remove_associated_key(_: *const u8, _: usize) -> i32 {
+        unsupported("remove_associated_key")
+    }
+
+    pub unsafe fn update_associated_key(_: *const u8, _: usize, _: i32) -> i32 {
+        unsupported("update_associated_key")
+    }
+
+    pub unsafe fn set_action_threshold(_: u32, _: u32) -> i32 {
+        unsupported("set_action_threshold")
+    }
+
+    pub unsafe fn set_main_purse(_: *const u8, _: usize) -> i32 {
+        unsupported("set_main_purse")
+    }
+
+    pub unsafe fn get_caller(output_size: *mut usize) -> i32 {
+        record("get_caller");
+        let caller = with_runtime(|runtime| runtime.caller);
+        *output_size = buffer(CLValue::from_t(caller).expect("should convert caller"));
+        api_error::i32_from(Ok(()))
+    }
+
+    pub unsafe fn get_call_stack(output_size: *mut usize) -> i32 {
+        record("get_call_stack");
+        let call_stack = with_runtime(|runtime| runtime.call_stack.clone());
+        *output_size = buffer(CLValue::from_t(call_stack).expect("should convert call stack"));
+        api_error::i32_from(Ok(()))
+    }
+
+    pub unsafe fn is_system_caller() -> i32 {
+        record("is_system_caller");
+        let caller = with_runtime(|runtime| runtime.caller);
+        i32::from(caller == SYSTEM_ACCOUNT)
+    }
+
+    pub unsafe fn get_blocktime(dest_ptr: *const u8) {
+        record("get_blocktime");
+        let blocktime = with_runtime(|runtime| runtime.blocktime);
+        output(
+            &blocktime.into_bytes().expect("should serialize BlockTime"),
+            dest_ptr as *mut u8,
+        )
+    }
+
+    pub unsafe fn get_gas_price(dest_ptr: *mut u8) {
+        record("get_gas_price");
+        let gas_price = with_runtime(|runtime| runtime.gas_price);
+        output(
+            &gas_price.into_bytes().expect("should serialize u64"),
+            dest_ptr,
+        )
+    }
+
+    pub unsafe fn get_deploy_hash(dest_ptr: *mut u8) {
+        record("get_deploy_hash");
+        let deploy_hash = with_runtime(|runtime| runtime.deploy_hash);
+        output(&deploy_hash, dest_ptr)
+    }
+
+    pub unsafe fn create_purse(_: *const u8, _: usize) -> i32 {
+        unsupported("create_purse")
+    }
+
+    pub unsafe fn transfer_to_account(_: *const u8, _: usize, _: *const u8, _: usize) -> i32 {
+        unsupported("transfer_to_account")
+    }
+
+    pub unsafe fn transfer_from_purse_to_account(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("transfer_from_purse_to_account")
+    }
+
+    pub unsafe fn transfer_from_purse_to_accounts(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("transfer_from_purse_to_accounts")
+    }
+
+    pub unsafe fn transfer_from_purse_to_purse(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("transfer_from_purse_to_purse")
+    }
+
+    pub unsafe fn get_balance(_: *const u8, _: usize, _: *mut usize) -> i32 {
+        unsupported("get_balance")
+    }
+
+    pub unsafe fn get_phase(dest_ptr: *mut u8) {
+        record("get_phase");
+        let phase = with_runtime(|runtime| runtime.phase);
+        output(
+            &phase.into_bytes().expect("should serialize Phase"),
+            dest_ptr,
+        )
+    }
+
+    pub unsafe fn get_system_contract(_: u32, _: *mut u8, _: usize) -> i32 {
+        unsupported("get_system_contract")
+    }
+
+    pub unsafe fn register_system_contract(_: *const u8, _: usize, _: *const u8, _: usize) {
+        unsupported("register_system_contract")
+    }
+
+    pub unsafe fn get_main_purse(_: *mut u8) {
+        unsupported("get_main_purse")
+    }
+
+    pub unsafe fn read_host_buffer(
+        dest_ptr: *mut u8,
+        dest_size: usize,
+        bytes_written: *mut usize,
+    ) -> i32 {
+        record("read_host_buffer");
+        let value = match with_runtime(|runtime| runtime.host_buffer.take()) {
+            Some(value) => value,
+            None => return api_error::i32_from(Err(ApiError::HostBufferEmpty)),
+        };
+        let bytes = value.inner_bytes();
+        if bytes.len() > dest_size {
+            return api_error::i32_from(Err(ApiError::BufferTooSmall));
+        }
+        output(bytes, dest_ptr);
+        *bytes_written = bytes.len();
+        api_error::i32_from(Ok(()))
+    }
+
+    pub unsafe fn create_contract_package_at_hash(_: *mut u8, _: *mut u8) {
+        unsupported("create_contract_package_at_hash")
+    }
+
+    pub unsafe fn create_contract_user_group(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: u8,
+        _: *const u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("create_contract_user_group")
+    }
+
+    pub unsafe fn add_contract_version(
+        _: *const u8,
+        _: usize,
+        _: *const u32,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *mut u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("add_contract_version")
+    }
+
+    pub unsafe fn disable_contract_version(_: *const u8, _: usize, _: *const u8, _: usize) -> i32 {
+        unsupported("disable_contract_version")
+    }
+
+    pub unsafe fn disable_contract(_: *const u8, _: usize) -> i32 {
+        unsupported("disable_contract")
+    }
+
+    pub unsafe fn set_non_reentrant(_: *const u8, _: usize, _: *const u8, _: usize, _: i32) -> i32 {
+        unsupported("set_non_reentrant")
+    }
+
+    pub unsafe fn call_contract(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("call_contract")
+    }
+
+    pub unsafe fn call_contract_read_only(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("call_contract_read_only")
+    }
+
+    pub unsafe fn call_versioned_contract(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("call_versioned_contract")
+    }
+
+    /// Unlike other names passed to the host, the argument name is passed as raw UTF-8 bytes.
+    unsafe fn arg(name_ptr: *const u8, name_size: usize) -> Option<CLValue> {
+        let name = String::from_utf8(input(name_ptr, name_size).to_vec())
+            .expect("argument name should be valid UTF-8");
+        with_runtime(|runtime| runtime.args.get(&name).cloned())
+    }
+
+    pub unsafe fn get_named_arg_size(
+        name_ptr: *const u8,
+        name_size: usize,
+        dest_size: *mut usize,
+    ) -> i32 {
+        record("get_named_arg_size");
+        match arg(name_ptr, name_size) {
+            Some(value) => {
+                *dest_size = value.inner_bytes().len();
+                api_error::i32_from(Ok(()))
+            }
+            None => api_error::i32_from(Err(ApiError::MissingArgument)),
+        }
+    }
+
+    pub unsafe fn get_named_arg(
+        name_ptr: *const u8,
+        name_size: usize,
+        dest_ptr: *mut u8,
+        dest_size: usize,
+    ) -> i32 {
+        record("get_named_arg");
+        let value = match arg(name_ptr, name_size) {
+            Some(value) => value,
+            None => return api_error::i32_from(Err(ApiError::MissingArgument)),
+        };
+        let bytes = value.inner_bytes();
+        if bytes.len() > dest_size {
+            return api_error::i32_from(Err(ApiError::BufferTooSmall));
+        }
+        output(bytes, dest_ptr);
+        api_error::i32_from(Ok(()))
+    }
+
+    pub unsafe fn remove_contract_user_group(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("remove_contract_user_group")
+    }
+
+    pub unsafe fn provision_contract_user_group_uref(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const usize,
+    ) -> i32 {
+        unsupported("provision_contract_user_group_uref")
+    }
+
+    pub unsafe fn remove_contract_user_group_urefs(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("remove_contract_user_group_urefs")
+    }
+
+    /// Fills the destination with a repeating, predictable pattern rather than random bytes.
+    pub unsafe fn random_bytes(dest_ptr: *mut u8, dest_size: usize) {
+        record("random_bytes");
+        for index in 0..dest_size {
+            *dest_ptr.add(index) = index as u8;
+        }
+    }
+
+    pub unsafe fn u512_mul_div(
+        a_ptr: *const u8,
+        a_size: usize,
+        b_ptr: *const u8,
+        b_size: usize,
+        c_ptr: *const u8,
+        c_size: usize,
+        dest_ptr: *mut u8,
+    ) -> i32 {
+        record("u512_mul_div");
+        let a: U512 = deserialize(a_ptr, a_size);
+        let b: U512 = deserialize(b_ptr, b_size);
+        let c: U512 = deserialize(c_ptr, c_size);
+        match a.checked_mul_div(b, c) {
+            Some(result) => {
+                let mut bytes = [0u8; 64];
+                result.to_little_endian(&mut bytes);
+                output(&bytes, dest_ptr);
+                api_error::i32_from(Ok(()))
+            }
+            None => api_error::i32_from(Err(ApiError::None)),
+        }
+    }
+
+    pub unsafe fn emit_event(
+        name_ptr: *const u8,
+        name_size: usize,
+        data_ptr: *const u8,
+        data_size: usize,
+    ) {
+        record("emit_event");
+        let name: String = deserialize(name_ptr, name_size);
+        let data: CLValue = deserialize(data_ptr, data_size);
+        with_runtime(|runtime| runtime.events.push((name, data)))
+    }
+
+    pub unsafe fn dictionary_new(_: *mut u8) {
+        unsupported("dictionary_new")
+    }
+
+    pub unsafe fn dictionary_get(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *mut usize,
+    ) -> i32 {
+        unsupported("dictionary_get")
+    }
+
+    pub unsafe fn dictionary_put(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) {
+        unsupported("dictionary_put")
+    }
+
+    pub unsafe fn blake2b(_: *const u8, _: usize, _: *mut u8, _: usize) -> i32 {
+        unsupported("blake2b")
+    }
+
+    pub unsafe fn keccak256(_: *const u8, _: usize, _: *mut u8, _: usize) -> i32 {
+        unsupported("keccak256")
+    }
+
+    pub unsafe fn verify_ed25519(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("verify_ed25519")
+    }
+
+    pub unsafe fn verify_secp256k1(
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+        _: *const u8,
+        _: usize,
+    ) -> i32 {
+        unsupported("verify_secp256k1")
+    }
+
+    pub unsafe fn print(text_ptr: *const u8, text_size: usize) {
+        record("print");
+        let text: String = deserialize(text_ptr, text_size);
+        with_runtime(|runtime| runtime.printed.push(text))
+    }
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "mock-runtime")]
+
+use casperlabs_contract::{
+    contract_api::{runtime, storage},
+    mock_runtime::{self, Outcome},
+};
+use casperlabs_types::{
+    account::AccountHash, runtime_args, ApiError, CLValue, CallStackElement, Key, RuntimeArgs,
+    URef, U512,
+};
+
+const KEY_NAME: &str = "counter";
+const ARG_AMOUNT: &str = "amount";
+
+#[test]
+fn should_round_trip_named_key() {
+    mock_runtime::reset();
+    let key = Key::Account(AccountHash::new([1; 32]));
+
+    let outcome = mock_runtime::call(|| {
+        assert!(!runtime::has_key(KEY_NAME));
+        runtime::put_key(KEY_NAME, key);
+        assert!(runtime::has_key(KEY_NAME));
+        assert_eq!(runtime::get_key(KEY_NAME), Some(key));
+        runtime::remove_key(KEY_NAME);
+        assert_eq!(runtime::get_key(KEY_NAME), None);
+    });
+
+    assert_eq!(outcome, Outcome::Completed);
+    assert!(mock_runtime::named_keys().is_empty());
+}
+
+#[test]
+fn should_read_value_written_to_new_uref() {
+    mock_runtime::reset();
+
+    let mut uref = None;
+    let outcome = mock_runtime::call(|| {
+        let new_uref: URef = storage::new_uref(1u64);
+        assert!(runtime::is_valid_uref(new_uref));
+        storage::write(new_uref, 2u64);
+        assert_eq!(storage::read(new_uref), Ok(Some(2u64)));
+        uref = Some(new_uref);
+    });
+
+    assert_eq!(outcome, Outcome::Completed);
+    let value = mock_runtime::read(Key::URef(uref.unwrap())).expect("should have value");
+    assert_eq!(value, CLValue::from_t(2u64).unwrap());
+}
+
+#[test]
+fn should_not_read_deleted_value() {
+    mock_runtime::reset();
+
+    let mut uref = None;
+    let outcome = mock_runtime::call(|| {
+        let new_uref: URef = storage::new_uref(1u64);
+        storage::delete(new_uref);
+        assert_eq!(storage::read::<u64>(new_uref), Ok(None));
+        uref = Some(new_uref);
+    });
+
+    assert_eq!(outcome, Outcome::Completed);
+    assert_eq!(mock_runtime::read(Key::URef(uref.unwrap())), None);
+}
+
+#[test]
+fn should_get_named_arg() {
+    mock_runtime::reset();
+    mock_runtime::set_args(runtime_args! { ARG_AMOUNT => 5u64 });
+
+    let outcome = mock_runtime::call(|| {
+        let amount: u64 = runtime::get_named_arg(ARG_AMOUNT);
+        assert_eq!(amount, 5);
+    });
+
+    assert_eq!(outcome, Outcome::Completed);
+}
+
+#[test]
+fn should_capture_revert() {
+    mock_runtime::reset();
+
+    let outcome = mock_runtime::call(|| {
+        let _amount: u64 = runtime::get_named_arg(ARG_AMOUNT);
+    });
+
+    assert_eq!(outcome, Outcome::Reverted(ApiError::MissingArgument));
+}
+
+#[test]
+fn should_capture_return_value() {
+    mock_runtime::reset();
+
+    let outcome = mock_runtime::call(|| runtime::ret(CLValue::from_t(7u32).unwrap()));
+
+    assert_eq!(outcome, Outcome::Returned(CLValue::from_t(7u32).unwrap()));
+}
+
+#[test]
+fn should_record_host_calls() {
+    mock_runtime::reset();
+    let caller = AccountHash::new([2; 32]);
+    mock_runtime::set_caller(caller);
+
+    mock_runtime::call(|| {
+        assert_eq!(runtime::get_caller(), caller);
+        runtime::print("hello");
+    });
+
+    assert_eq!(
+        mock_runtime::host_calls(),
+        vec!["get_caller", "read_host_buffer", "print"]
+    );
+    assert_eq!(mock_runtime::printed(), vec!["hello".to_string()]);
+}
+
+#[test]
+fn should_get_deploy_hash() {
+    mock_runtime::reset();
+    mock_runtime::set_deploy_hash([3; 32]);
+
+    mock_runtime::call(|| {
+        assert_eq!(runtime::get_deploy_hash(), [3; 32]);
+    });
+
+    assert_eq!(mock_runtime::host_calls(), vec!["get_deploy_hash"]);
+}
+
+#[test]
+fn should_get_call_stack() {
+    mock_runtime::reset();
+    let call_stack = vec![CallStackElement::session(AccountHash::new([4; 32]))];
+    mock_runtime::set_call_stack(call_stack.clone());
+
+    mock_runtime::call(|| {
+        assert_eq!(runtime::get_call_stack(), call_stack);
+    });
+
+    assert_eq!(
+        mock_runtime::host_calls(),
+        vec!["get_call_stack", "read_host_buffer"]
+    );
+}
+
+#[test]
+fn should_increment_counter() {
+    mock_runtime::reset();
+
+    let outcome = mock_runtime::call(|| {
+        let counter = storage::counter_new();
+        runtime::put_key(KEY_NAME, counter.into());
+        assert_eq!(runtime::counter_inc(KEY_NAME), 1);
+        assert_eq!(storage::counter_inc(counter), 2);
+        assert_eq!(storage::counter_read(counter), 2);
+    });
+
+    assert_eq!(outcome, Outcome::Completed);
+    assert_eq!(
+        mock_runtime::host_calls(),
+        vec![
+            "new_uref",
+            "put_key",
+            "get_key",
+            "counter_inc",
+            "counter_inc",
+            "read_value",
+            "read_host_buffer"
+        ]
+    );
+}
+
+#[test]
+fn should_mul_div_u512() {
+    mock_runtime::reset();
+
+    let outcome = mock_runtime::call(|| {
+        let max = U512::max_value();
+        assert_eq!(runtime::u512_mul_div(max, max - 1, max), Some(max - 1));
+        assert_eq!(runtime::u512_mul_div(max, 2.into(), 1.into()), None);
+        assert_eq!(runtime::u512_mul_div(max, 1.into(), 0.into()), None);
+    });
+
+    assert_eq!(outcome, Outcome::Completed);
+    assert_eq!(
+        mock_runtime::host_calls(),
+        vec!["u512_mul_div", "u512_mul_div", "u512_mul_div"]
+    );
+}
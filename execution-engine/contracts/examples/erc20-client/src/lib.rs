@@ -0,0 +1,153 @@
+//! Names, error codes and typed call builders for the `erc20` example contract.
+//!
+//! The installer stores the contract package under [`PACKAGE_HASH_KEY`] in the installing
+//! account's named keys, so an [`Erc20Call`] can be sent as a deploy targeting that package, or
+//! made from another contract with `runtime::call_versioned_contract`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use types::{account::AccountHash, runtime_args, ApiError, RuntimeArgs, U256};
+
+/// Named key of the installer holding the contract package hash.
+pub const PACKAGE_HASH_KEY: &str = "erc20_package";
+/// Named key of the installer holding the contract package access URef.
+pub const PACKAGE_ACCESS_KEY: &str = "erc20_package_access";
+/// Named key of the installer holding the hash of the installed contract version.
+pub const CONTRACT_HASH_KEY: &str = "erc20";
+/// Named key of the installer holding the URef of the [`MINTER_GROUP`].
+pub const MINTER_ACCESS_KEY: &str = "erc20_minter_access";
+/// Label of the group allowed to call [`METHOD_MINT`].
+pub const MINTER_GROUP: &str = "minter";
+
+/// Named key of the contract holding the token name.
+pub const NAME_KEY: &str = "name";
+/// Named key of the contract holding the token symbol.
+pub const SYMBOL_KEY: &str = "symbol";
+/// Named key of the contract holding the number of decimals.
+pub const DECIMALS_KEY: &str = "decimals";
+/// Named key of the contract holding the total supply.
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+/// Named key of the contract holding the seed of the dictionary of balances, keyed by
+/// [`AccountHash`].
+pub const BALANCES_KEY: &str = "balances";
+/// Named key of the contract holding the seed of the dictionary of allowances, keyed by
+/// `(owner, spender)` pairs of [`AccountHash`]es.
+pub const ALLOWANCES_KEY: &str = "allowances";
+
+pub const METHOD_NAME: &str = "name";
+pub const METHOD_SYMBOL: &str = "symbol";
+pub const METHOD_DECIMALS: &str = "decimals";
+pub const METHOD_TOTAL_SUPPLY: &str = "total_supply";
+pub const METHOD_BALANCE_OF: &str = "balance_of";
+pub const METHOD_ALLOWANCE: &str = "allowance";
+pub const METHOD_TRANSFER: &str = "transfer";
+pub const METHOD_APPROVE: &str = "approve";
+pub const METHOD_TRANSFER_FROM: &str = "transfer_from";
+pub const METHOD_MINT: &str = "mint";
+
+pub const ARG_NAME: &str = "name";
+pub const ARG_SYMBOL: &str = "symbol";
+pub const ARG_DECIMALS: &str = "decimals";
+pub const ARG_INITIAL_SUPPLY: &str = "initial_supply";
+pub const ARG_ADDRESS: &str = "address";
+pub const ARG_OWNER: &str = "owner";
+pub const ARG_SPENDER: &str = "spender";
+pub const ARG_RECIPIENT: &str = "recipient";
+pub const ARG_AMOUNT: &str = "amount";
+
+/// Errors the contract reverts with, as [`ApiError::User`] codes.
+#[repr(u16)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The owner's balance is lower than the amount transferred.
+    InsufficientBalance = 1,
+    /// The spender's allowance is lower than the amount transferred.
+    InsufficientAllowance = 2,
+    /// A balance or the total supply would exceed `U256::MAX`.
+    Overflow = 3,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Returns the session arguments of the installer.
+pub fn install_args(
+    name: String,
+    symbol: String,
+    decimals: u8,
+    initial_supply: U256,
+) -> RuntimeArgs {
+    runtime_args! {
+        ARG_NAME => name,
+        ARG_SYMBOL => symbol,
+        ARG_DECIMALS => decimals,
+        ARG_INITIAL_SUPPLY => initial_supply,
+    }
+}
+
+/// A call to one of the state-changing entry points of the contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Erc20Call {
+    entry_point: &'static str,
+    args: RuntimeArgs,
+}
+
+impl Erc20Call {
+    /// Transfers `amount` from the caller to `recipient`.
+    pub fn transfer(recipient: AccountHash, amount: U256) -> Self {
+        Erc20Call {
+            entry_point: METHOD_TRANSFER,
+            args: runtime_args! { ARG_RECIPIENT => recipient, ARG_AMOUNT => amount },
+        }
+    }
+
+    /// Allows `spender` to transfer up to `amount` from the caller, replacing any previous
+    /// allowance.
+    pub fn approve(spender: AccountHash, amount: U256) -> Self {
+        Erc20Call {
+            entry_point: METHOD_APPROVE,
+            args: runtime_args! { ARG_SPENDER => spender, ARG_AMOUNT => amount },
+        }
+    }
+
+    /// Transfers `amount` from `owner` to `recipient`, spending the caller's allowance.
+    pub fn transfer_from(owner: AccountHash, recipient: AccountHash, amount: U256) -> Self {
+        Erc20Call {
+            entry_point: METHOD_TRANSFER_FROM,
+            args: runtime_args! {
+                ARG_OWNER => owner,
+                ARG_RECIPIENT => recipient,
+                ARG_AMOUNT => amount,
+            },
+        }
+    }
+
+    /// Creates `amount` new tokens for `recipient`.  Only callable by the [`MINTER_GROUP`].
+    pub fn mint(recipient: AccountHash, amount: U256) -> Self {
+        Erc20Call {
+            entry_point: METHOD_MINT,
+            args: runtime_args! { ARG_RECIPIENT => recipient, ARG_AMOUNT => amount },
+        }
+    }
+
+    /// Returns the name of the entry point to call.
+    pub fn entry_point(&self) -> &'static str {
+        self.entry_point
+    }
+
+    /// Returns the arguments of the call.
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    /// Consumes the call, returning its arguments.
+    pub fn into_args(self) -> RuntimeArgs {
+        self.args
+    }
+}
@@ -0,0 +1,261 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use erc20_client::{
+    Error, ALLOWANCES_KEY, ARG_ADDRESS, ARG_AMOUNT, ARG_DECIMALS, ARG_INITIAL_SUPPLY, ARG_NAME,
+    ARG_OWNER, ARG_RECIPIENT, ARG_SPENDER, ARG_SYMBOL, BALANCES_KEY, CONTRACT_HASH_KEY,
+    DECIMALS_KEY, METHOD_ALLOWANCE, METHOD_APPROVE, METHOD_BALANCE_OF, METHOD_DECIMALS,
+    METHOD_MINT, METHOD_NAME, METHOD_SYMBOL, METHOD_TOTAL_SUPPLY, METHOD_TRANSFER,
+    METHOD_TRANSFER_FROM, MINTER_ACCESS_KEY, MINTER_GROUP, NAME_KEY, PACKAGE_ACCESS_KEY,
+    PACKAGE_HASH_KEY, SYMBOL_KEY, TOTAL_SUPPLY_KEY,
+};
+use types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::NamedKeys,
+    ApiError, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Parameter, URef, U256,
+};
+
+fn named_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+fn read_named<T: CLTyped + FromBytes>(name: &str) -> T {
+    storage::read(named_uref(name))
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
+
+fn read_balance(balances: URef, address: &AccountHash) -> U256 {
+    storage::dictionary_get(balances, address)
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn read_allowance(allowances: URef, owner: AccountHash, spender: AccountHash) -> U256 {
+    storage::dictionary_get(allowances, &(owner, spender))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn ret<T: CLTyped + ToBytes>(value: T) {
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+}
+
+/// Moves `amount` from `owner` to `recipient`.  The owner's balance is written first, so a
+/// transfer to oneself leaves the balance unchanged.
+fn transfer_balance(owner: AccountHash, recipient: AccountHash, amount: U256) {
+    let balances = named_uref(BALANCES_KEY);
+
+    let owner_balance = read_balance(balances, &owner)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientBalance);
+    storage::dictionary_put(balances, owner, owner_balance);
+
+    let recipient_balance = read_balance(balances, &recipient)
+        .checked_add(amount)
+        .unwrap_or_revert_with(Error::Overflow);
+    storage::dictionary_put(balances, recipient, recipient_balance);
+}
+
+#[no_mangle]
+pub extern "C" fn name() {
+    ret(read_named::<String>(NAME_KEY))
+}
+
+#[no_mangle]
+pub extern "C" fn symbol() {
+    ret(read_named::<String>(SYMBOL_KEY))
+}
+
+#[no_mangle]
+pub extern "C" fn decimals() {
+    ret(read_named::<u8>(DECIMALS_KEY))
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() {
+    ret(read_named::<U256>(TOTAL_SUPPLY_KEY))
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: AccountHash = runtime::get_named_arg(ARG_ADDRESS);
+    ret(read_balance(named_uref(BALANCES_KEY), &address))
+}
+
+#[no_mangle]
+pub extern "C" fn allowance() {
+    let owner: AccountHash = runtime::get_named_arg(ARG_OWNER);
+    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
+    ret(read_allowance(named_uref(ALLOWANCES_KEY), owner, spender))
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    transfer_balance(runtime::get_caller(), recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let allowances = named_uref(ALLOWANCES_KEY);
+    storage::dictionary_put(allowances, (runtime::get_caller(), spender), amount);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let owner: AccountHash = runtime::get_named_arg(ARG_OWNER);
+    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let spender = runtime::get_caller();
+
+    let allowances = named_uref(ALLOWANCES_KEY);
+    let allowance = read_allowance(allowances, owner, spender)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientAllowance);
+    storage::dictionary_put(allowances, (owner, spender), allowance);
+
+    transfer_balance(owner, recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn mint() {
+    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let total_supply_uref = named_uref(TOTAL_SUPPLY_KEY);
+    let total_supply = read_named::<U256>(TOTAL_SUPPLY_KEY)
+        .checked_add(amount)
+        .unwrap_or_revert_with(Error::Overflow);
+    storage::write(total_supply_uref, total_supply);
+
+    // Balances can't exceed the total supply, so this can't overflow.
+    let balances = named_uref(BALANCES_KEY);
+    let balance = read_balance(balances, &recipient) + amount;
+    storage::dictionary_put(balances, recipient, balance);
+}
+
+fn contract_entry_point(
+    name: &str,
+    args: Vec<Parameter>,
+    ret: CLType,
+    access: EntryPointAccess,
+) -> EntryPoint {
+    EntryPoint::new(name, args, ret, access, EntryPointType::Contract)
+}
+
+fn entry_points() -> EntryPoints {
+    let address = || Parameter::new(ARG_ADDRESS, AccountHash::cl_type());
+    let owner = || Parameter::new(ARG_OWNER, AccountHash::cl_type());
+    let spender = || Parameter::new(ARG_SPENDER, AccountHash::cl_type());
+    let recipient = || Parameter::new(ARG_RECIPIENT, AccountHash::cl_type());
+    let amount = || Parameter::new(ARG_AMOUNT, CLType::U256);
+    let public = || EntryPointAccess::Public;
+
+    let mut entry_points = EntryPoints::new();
+    for entry_point in vec![
+        contract_entry_point(METHOD_NAME, vec![], CLType::String, public()),
+        contract_entry_point(METHOD_SYMBOL, vec![], CLType::String, public()),
+        contract_entry_point(METHOD_DECIMALS, vec![], CLType::U8, public()),
+        contract_entry_point(METHOD_TOTAL_SUPPLY, vec![], CLType::U256, public()),
+        contract_entry_point(METHOD_BALANCE_OF, vec![address()], CLType::U256, public()),
+        contract_entry_point(
+            METHOD_ALLOWANCE,
+            vec![owner(), spender()],
+            CLType::U256,
+            public(),
+        ),
+        contract_entry_point(
+            METHOD_TRANSFER,
+            vec![recipient(), amount()],
+            CLType::Unit,
+            public(),
+        ),
+        contract_entry_point(
+            METHOD_APPROVE,
+            vec![spender(), amount()],
+            CLType::Unit,
+            public(),
+        ),
+        contract_entry_point(
+            METHOD_TRANSFER_FROM,
+            vec![owner(), recipient(), amount()],
+            CLType::Unit,
+            public(),
+        ),
+        contract_entry_point(
+            METHOD_MINT,
+            vec![recipient(), amount()],
+            CLType::Unit,
+            EntryPointAccess::groups(&[MINTER_GROUP]),
+        ),
+    ] {
+        entry_points.add_entry_point(entry_point);
+    }
+    entry_points
+}
+
+/// Installs the token, crediting the whole initial supply to the installing account, which also
+/// receives the URef of the minter group under [`MINTER_ACCESS_KEY`].
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let symbol: String = runtime::get_named_arg(ARG_SYMBOL);
+    let decimals: u8 = runtime::get_named_arg(ARG_DECIMALS);
+    let initial_supply: U256 = runtime::get_named_arg(ARG_INITIAL_SUPPLY);
+
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+    runtime::put_key(PACKAGE_HASH_KEY, contract_package_hash.into());
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+
+    let minter_urefs = storage::create_contract_user_group(
+        contract_package_hash,
+        MINTER_GROUP,
+        1,
+        BTreeSet::new(),
+    )
+    .unwrap_or_revert();
+    runtime::put_key(MINTER_ACCESS_KEY, minter_urefs[0].into());
+
+    let named_keys = {
+        let balances = storage::dictionary_new();
+        storage::dictionary_put(balances, runtime::get_caller(), initial_supply);
+
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(NAME_KEY.to_string(), storage::new_uref(name).into());
+        named_keys.insert(SYMBOL_KEY.to_string(), storage::new_uref(symbol).into());
+        named_keys.insert(DECIMALS_KEY.to_string(), storage::new_uref(decimals).into());
+        named_keys.insert(
+            TOTAL_SUPPLY_KEY.to_string(),
+            storage::new_uref(initial_supply).into(),
+        );
+        named_keys.insert(BALANCES_KEY.to_string(), balances.into());
+        named_keys.insert(ALLOWANCES_KEY.to_string(), storage::dictionary_new().into());
+        named_keys
+    };
+
+    let (contract_hash, _contract_version) =
+        storage::add_contract_version(contract_package_hash, entry_points(), named_keys);
+    runtime::put_key(CONTRACT_HASH_KEY, contract_hash.into());
+}
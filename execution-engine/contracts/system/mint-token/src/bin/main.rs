@@ -20,3 +20,48 @@ pub extern "C" fn balance() {
 pub extern "C" fn transfer() {
     mint_token::transfer();
 }
+
+#[no_mangle]
+pub extern "C" fn transfer_batch() {
+    mint_token::transfer_batch();
+}
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    mint_token::burn();
+}
+
+#[no_mangle]
+pub extern "C" fn read_total_supply() {
+    mint_token::read_total_supply();
+}
+
+#[no_mangle]
+pub extern "C" fn set_vesting_schedule() {
+    mint_token::set_vesting_schedule();
+}
+
+#[no_mangle]
+pub extern "C" fn create_token() {
+    mint_token::create_token();
+}
+
+#[no_mangle]
+pub extern "C" fn create_token_purse() {
+    mint_token::create_token_purse();
+}
+
+#[no_mangle]
+pub extern "C" fn token_balance() {
+    mint_token::token_balance();
+}
+
+#[no_mangle]
+pub extern "C" fn token_transfer() {
+    mint_token::token_transfer();
+}
+
+#[no_mangle]
+pub extern "C" fn read_token_total_supply() {
+    mint_token::read_token_total_supply();
+}
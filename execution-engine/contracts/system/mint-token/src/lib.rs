@@ -3,31 +3,44 @@
 #[macro_use]
 extern crate alloc;
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use mint::{Mint, RuntimeProvider, StorageProvider};
+use mint::{Mint, RuntimeProvider, StorageProvider, VestingSchedule};
 use types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     contracts::Parameters,
     system_contract_errors::mint::Error,
-    CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
-    Parameter, URef, U512,
+    BlockTime, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Key, Motes, Parameter, URef, U512,
 };
 
 pub const METHOD_MINT: &str = "mint";
 pub const METHOD_CREATE: &str = "create";
 pub const METHOD_BALANCE: &str = "balance";
 pub const METHOD_TRANSFER: &str = "transfer";
+pub const METHOD_TRANSFER_BATCH: &str = "transfer_batch";
+pub const METHOD_BURN: &str = "burn";
+pub const METHOD_READ_TOTAL_SUPPLY: &str = "read_total_supply";
+pub const METHOD_SET_VESTING_SCHEDULE: &str = "set_vesting_schedule";
+pub const METHOD_CREATE_TOKEN: &str = "create_token";
+pub const METHOD_CREATE_TOKEN_PURSE: &str = "create_token_purse";
+pub const METHOD_TOKEN_BALANCE: &str = "token_balance";
+pub const METHOD_TOKEN_TRANSFER: &str = "token_transfer";
+pub const METHOD_READ_TOKEN_TOTAL_SUPPLY: &str = "read_token_total_supply";
 
 pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_PURSE: &str = "purse";
 pub const ARG_SOURCE: &str = "source";
 pub const ARG_TARGET: &str = "target";
+pub const ARG_TRANSFERS: &str = "transfers";
+pub const ARG_VESTING_SCHEDULE: &str = "vesting_schedule";
+pub const ARG_TOKEN_ID: &str = "token_id";
+pub const ARG_INITIAL_SUPPLY: &str = "initial_supply";
 
 pub struct MintContract;
 
@@ -39,6 +52,14 @@ impl RuntimeProvider for MintContract {
     fn put_key(&mut self, name: &str, key: Key) {
         runtime::put_key(name, key)
     }
+
+    fn get_key(&self, name: &str) -> Option<Key> {
+        runtime::get_key(name)
+    }
+
+    fn get_block_time(&self) -> BlockTime {
+        runtime::get_blocktime()
+    }
 }
 
 impl StorageProvider for MintContract {
@@ -76,7 +97,7 @@ impl Mint for MintContract {}
 
 pub fn mint() {
     let mut mint_contract = MintContract;
-    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let amount: Motes = runtime::get_named_arg(ARG_AMOUNT);
     let result: Result<URef, Error> = mint_contract.mint(amount);
     let ret = CLValue::from_t(result).unwrap_or_revert();
     runtime::ret(ret)
@@ -84,7 +105,9 @@ pub fn mint() {
 
 pub fn create() {
     let mut mint_contract = MintContract;
-    let uref = mint_contract.mint(U512::zero()).unwrap_or_revert();
+    let uref = mint_contract
+        .mint(Motes::new(U512::zero()))
+        .unwrap_or_revert();
     let ret = CLValue::from_t(uref).unwrap_or_revert();
     runtime::ret(ret)
 }
@@ -107,6 +130,85 @@ pub fn transfer() {
     runtime::ret(ret);
 }
 
+pub fn transfer_batch() {
+    let mut mint_contract = MintContract;
+    let source: URef = runtime::get_named_arg(ARG_SOURCE);
+    let transfers: Vec<(URef, U512)> = runtime::get_named_arg(ARG_TRANSFERS);
+    let result: Result<(), Error> = mint_contract.transfer_batch(source, transfers);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn burn() {
+    let mut mint_contract = MintContract;
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: Result<(), Error> = mint_contract.burn(purse, amount);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn read_total_supply() {
+    let mut mint_contract = MintContract;
+    let total_supply: U512 = mint_contract.total_supply().unwrap_or_revert();
+    let ret = CLValue::from_t(total_supply).unwrap_or_revert();
+    runtime::ret(ret)
+}
+
+pub fn set_vesting_schedule() {
+    let mut mint_contract = MintContract;
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let vesting_schedule: VestingSchedule = runtime::get_named_arg(ARG_VESTING_SCHEDULE);
+    let result: Result<(), Error> = mint_contract.set_vesting_schedule(purse, vesting_schedule);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn create_token() {
+    let mut mint_contract = MintContract;
+    let id: String = runtime::get_named_arg(ARG_TOKEN_ID);
+    let initial_supply: U512 = runtime::get_named_arg(ARG_INITIAL_SUPPLY);
+    let result: Result<URef, Error> = mint_contract.create_token(id, initial_supply);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret)
+}
+
+pub fn create_token_purse() {
+    let mut mint_contract = MintContract;
+    let id: String = runtime::get_named_arg(ARG_TOKEN_ID);
+    let result: Result<URef, Error> = mint_contract.create_token_purse(id);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret)
+}
+
+pub fn token_balance() {
+    let mut mint_contract = MintContract;
+    let id: String = runtime::get_named_arg(ARG_TOKEN_ID);
+    let uref: URef = runtime::get_named_arg(ARG_PURSE);
+    let balance: Option<U512> = mint_contract.token_balance(id, uref).unwrap_or_revert();
+    let ret = CLValue::from_t(balance).unwrap_or_revert();
+    runtime::ret(ret)
+}
+
+pub fn token_transfer() {
+    let mut mint_contract = MintContract;
+    let id: String = runtime::get_named_arg(ARG_TOKEN_ID);
+    let source: URef = runtime::get_named_arg(ARG_SOURCE);
+    let target: URef = runtime::get_named_arg(ARG_TARGET);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: Result<(), Error> = mint_contract.token_transfer(id, source, target, amount);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn read_token_total_supply() {
+    let mut mint_contract = MintContract;
+    let id: String = runtime::get_named_arg(ARG_TOKEN_ID);
+    let total_supply: U512 = mint_contract.token_total_supply(id).unwrap_or_revert();
+    let ret = CLValue::from_t(total_supply).unwrap_or_revert();
+    runtime::ret(ret)
+}
+
 pub fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
 
@@ -156,5 +258,124 @@ pub fn get_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_TRANSFER_BATCH,
+        vec![
+            Parameter::new(ARG_SOURCE, CLType::URef),
+            Parameter::new(ARG_TRANSFERS, Vec::<(URef, U512)>::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_BURN,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_READ_TOTAL_SUPPLY,
+        Parameters::new(),
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SET_VESTING_SCHEDULE,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_VESTING_SCHEDULE, VestingSchedule::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_CREATE_TOKEN,
+        vec![
+            Parameter::new(ARG_TOKEN_ID, CLType::String),
+            Parameter::new(ARG_INITIAL_SUPPLY, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::URef),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_CREATE_TOKEN_PURSE,
+        vec![Parameter::new(ARG_TOKEN_ID, CLType::String)],
+        CLType::Result {
+            ok: Box::new(CLType::URef),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_TOKEN_BALANCE,
+        vec![
+            Parameter::new(ARG_TOKEN_ID, CLType::String),
+            Parameter::new(ARG_PURSE, CLType::URef),
+        ],
+        CLType::Option(Box::new(CLType::U512)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_TOKEN_TRANSFER,
+        vec![
+            Parameter::new(ARG_TOKEN_ID, CLType::String),
+            Parameter::new(ARG_SOURCE, CLType::URef),
+            Parameter::new(ARG_TARGET, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_READ_TOKEN_TOTAL_SUPPLY,
+        vec![Parameter::new(ARG_TOKEN_ID, CLType::String)],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
@@ -11,8 +11,10 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use pos::{
-    ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
-    METHOD_GET_PAYMENT_PURSE, METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_UNBOND,
+    ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, ARG_VALIDATOR, METHOD_BOND, METHOD_DELEGATE,
+    METHOD_DISTRIBUTE_REWARDS, METHOD_FINALIZE_PAYMENT, METHOD_GET_PAYMENT_PURSE,
+    METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_SLASH, METHOD_STEP, METHOD_UNBOND,
+    METHOD_UNDELEGATE, METHOD_WITHDRAW_UNBONDED,
 };
 use proof_of_stake::Stakes;
 use types::{
@@ -26,13 +28,25 @@ use types::{
     CLType, CLValue, ContractPackageHash, Key, RuntimeArgs, URef, U512,
 };
 
-const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
+const POS_STAKES: &str = "pos_stakes";
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+const POS_INFLATION_RATE: &str = "pos_inflation_rate";
+const POS_SLASH_RATE: &str = "pos_slash_rate";
+const POS_UNBONDING_DELAY: &str = "pos_unbonding_delay";
+const POS_MIN_BOND: &str = "pos_min_bond";
+const POS_MAX_BOND: &str = "pos_max_bond";
+const POS_MAX_VALIDATOR_SLOTS: &str = "pos_max_validator_slots";
 
 const ARG_MINT_PACKAGE_HASH: &str = "mint_contract_package_hash";
 const ARG_GENESIS_VALIDATORS: &str = "genesis_validators";
+const ARG_INFLATION_RATE: &str = "inflation_rate";
+const ARG_SLASH_RATE: &str = "slash_rate";
+const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
+const ARG_MIN_BOND: &str = "min_bond";
+const ARG_MAX_BOND: &str = "max_bond";
+const ARG_MAX_VALIDATOR_SLOTS: &str = "max_validator_slots";
 const ENTRY_POINT_MINT: &str = "mint";
 
 const HASH_KEY_NAME: &str = "pos_hash";
@@ -48,6 +62,21 @@ pub extern "C" fn unbond() {
     pos::unbond();
 }
 
+#[no_mangle]
+pub extern "C" fn withdraw_unbonded() {
+    pos::withdraw_unbonded();
+}
+
+#[no_mangle]
+pub extern "C" fn delegate() {
+    pos::delegate();
+}
+
+#[no_mangle]
+pub extern "C" fn undelegate() {
+    pos::undelegate();
+}
+
 #[no_mangle]
 pub extern "C" fn get_payment_purse() {
     pos::get_payment_purse();
@@ -68,20 +97,42 @@ pub extern "C" fn finalize_payment() {
     pos::finalize_payment();
 }
 
+#[no_mangle]
+pub extern "C" fn distribute_rewards() {
+    pos::distribute_rewards();
+}
+
+#[no_mangle]
+pub extern "C" fn slash() {
+    pos::slash();
+}
+
+#[no_mangle]
+pub extern "C" fn step() {
+    pos::step();
+}
+
 #[no_mangle]
 pub extern "C" fn install() {
     let mint_package_hash: ContractPackageHash = runtime::get_named_arg(ARG_MINT_PACKAGE_HASH);
     let genesis_validators: BTreeMap<AccountHash, U512> =
         runtime::get_named_arg(ARG_GENESIS_VALIDATORS);
+    let inflation_rate: u64 = runtime::get_named_arg(ARG_INFLATION_RATE);
+    let slash_rate: u64 = runtime::get_named_arg(ARG_SLASH_RATE);
+    let unbonding_delay: u64 = runtime::get_named_arg(ARG_UNBONDING_DELAY);
+    let min_bond: u64 = runtime::get_named_arg(ARG_MIN_BOND);
+    let max_bond: u64 = runtime::get_named_arg(ARG_MAX_BOND);
+    let max_validator_slots: u32 = runtime::get_named_arg(ARG_MAX_VALIDATOR_SLOTS);
 
     let stakes = Stakes::new(genesis_validators);
 
-    // Add genesis validators to PoS contract object.
-    // For now, we are storing validators in `named_keys` map of the PoS contract
-    // in the form: key: "v_{validator_pk}_{validator_stake}", value: doesn't
-    // matter.
-    let mut named_keys: NamedKeys = stakes.strings().map(|key| (key, PLACEHOLDER_KEY)).collect();
     let total_bonds: U512 = stakes.total_bonds();
+
+    // Add genesis validators to PoS contract object.
+    let mut named_keys = NamedKeys::new();
+    let stakes_uref = storage::new_uref(stakes.0);
+    named_keys.insert(String::from(POS_STAKES), Key::URef(stakes_uref));
+
     let bonding_purse = mint_purse(mint_package_hash, total_bonds);
     let payment_purse = mint_purse(mint_package_hash, U512::zero());
     let rewards_purse = mint_purse(mint_package_hash, U512::zero());
@@ -97,6 +148,28 @@ pub extern "C" fn install() {
         named_keys.insert(String::from(*name), Key::URef(*uref));
     });
 
+    let inflation_rate_uref = storage::new_uref(inflation_rate);
+    named_keys.insert(
+        String::from(POS_INFLATION_RATE),
+        Key::URef(inflation_rate_uref),
+    );
+    let slash_rate_uref = storage::new_uref(slash_rate);
+    named_keys.insert(String::from(POS_SLASH_RATE), Key::URef(slash_rate_uref));
+    let unbonding_delay_uref = storage::new_uref(unbonding_delay);
+    named_keys.insert(
+        String::from(POS_UNBONDING_DELAY),
+        Key::URef(unbonding_delay_uref),
+    );
+    let min_bond_uref = storage::new_uref(min_bond);
+    named_keys.insert(String::from(POS_MIN_BOND), Key::URef(min_bond_uref));
+    let max_bond_uref = storage::new_uref(max_bond);
+    named_keys.insert(String::from(POS_MAX_BOND), Key::URef(max_bond_uref));
+    let max_validator_slots_uref = storage::new_uref(max_validator_slots);
+    named_keys.insert(
+        String::from(POS_MAX_VALIDATOR_SLOTS),
+        Key::URef(max_validator_slots_uref),
+    );
+
     let entry_points = {
         let mut entry_points = EntryPoints::new();
 
@@ -121,6 +194,40 @@ pub extern "C" fn install() {
         );
         entry_points.add_entry_point(unbond);
 
+        let withdraw_unbonded = EntryPoint::new(
+            METHOD_WITHDRAW_UNBONDED.to_string(),
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(withdraw_unbonded);
+
+        let delegate = EntryPoint::new(
+            METHOD_DELEGATE.to_string(),
+            vec![
+                Parameter::new(ARG_VALIDATOR, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(ARG_AMOUNT, CLType::U512),
+                Parameter::new(ARG_PURSE, CLType::URef),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(delegate);
+
+        let undelegate = EntryPoint::new(
+            METHOD_UNDELEGATE.to_string(),
+            vec![
+                Parameter::new(ARG_VALIDATOR, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(ARG_AMOUNT, CLType::Option(Box::new(CLType::U512))),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(undelegate);
+
         let get_payment_purse = EntryPoint::new(
             METHOD_GET_PAYMENT_PURSE.to_string(),
             vec![],
@@ -160,6 +267,36 @@ pub extern "C" fn install() {
         );
         entry_points.add_entry_point(finalize_payment);
 
+        let distribute_rewards = EntryPoint::new(
+            METHOD_DISTRIBUTE_REWARDS.to_string(),
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(distribute_rewards);
+
+        let slash = EntryPoint::new(
+            METHOD_SLASH.to_string(),
+            vec![Parameter::new(
+                ARG_VALIDATOR,
+                CLType::FixedList(Box::new(CLType::U8), 32),
+            )],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(slash);
+
+        let step = EntryPoint::new(
+            METHOD_STEP.to_string(),
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(step);
+
         entry_points
     };
 
@@ -12,26 +12,45 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    Delegations, DelegationsProvider, MintProvider, ProofOfStake, Queue, QueueProvider,
+    RewardsProvider, RuntimeProvider, SlashingProvider, Stakes, StakesProvider,
 };
 use types::{
-    account::AccountHash, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue, Key,
-    Phase, TransferResult, URef, U512,
+    account::AccountHash,
+    runtime_args,
+    system_contract_errors::{mint, pos::Error},
+    ApiError, BlockTime, CLValue, Key, Motes, Phase, RuntimeArgs, TransferResult, URef, U512,
 };
 
 pub const METHOD_BOND: &str = "bond";
 pub const METHOD_UNBOND: &str = "unbond";
+pub const METHOD_DELEGATE: &str = "delegate";
+pub const METHOD_UNDELEGATE: &str = "undelegate";
 pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
 pub const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
 pub const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 pub const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+pub const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+pub const METHOD_SLASH: &str = "slash";
+pub const METHOD_WITHDRAW_UNBONDED: &str = "withdraw_unbonded";
+pub const METHOD_STEP: &str = "step";
 
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
 
+const STAKES_KEY: &str = "pos_stakes";
+const INFLATION_RATE_KEY: &str = "pos_inflation_rate";
+const SLASH_RATE_KEY: &str = "pos_slash_rate";
+const UNBONDING_DELAY_KEY: &str = "pos_unbonding_delay";
+const MIN_BOND_KEY: &str = "pos_min_bond";
+const MAX_BOND_KEY: &str = "pos_max_bond";
+const MAX_VALIDATOR_SLOTS_KEY: &str = "pos_max_validator_slots";
+const METHOD_MINT: &str = "mint";
+
 pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_PURSE: &str = "purse";
 pub const ARG_ACCOUNT_KEY: &str = "account";
+pub const ARG_VALIDATOR: &str = "validator";
 
 pub struct ProofOfStakeContract;
 
@@ -57,6 +76,19 @@ impl MintProvider for ProofOfStakeContract {
     fn balance(&mut self, purse: URef) -> Option<U512> {
         system::get_balance(purse)
     }
+
+    fn mint(&mut self, amount: U512) -> Result<URef, ()> {
+        let args = runtime_args! {
+            ARG_AMOUNT => amount,
+        };
+        let result: Result<URef, mint::Error> =
+            runtime::call_contract(system::get_mint(), METHOD_MINT, args);
+        result.map_err(|_| ())
+    }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        system::burn(purse, amount).map_err(|_| ())
+    }
 }
 
 impl QueueProvider for ProofOfStakeContract {
@@ -83,6 +115,18 @@ impl QueueProvider for ProofOfStakeContract {
     fn write_unbonding(&mut self, queue: Queue) {
         storage::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Reads the unbonding delay from the contract's known urefs.
+    fn read_unbonding_delay(&mut self) -> Result<u64, Error> {
+        let uref = match runtime::get_key(UNBONDING_DELAY_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::UnbondingDelayKeyUnexpectedType),
+            None => return Err(Error::UnbondingDelayNotFound),
+        };
+        storage::read(uref)
+            .map_err(|_| Error::UnbondingDelayKeyUnexpectedType)?
+            .ok_or(Error::UnbondingDelayNotFound)
+    }
 }
 
 impl RuntimeProvider for ProofOfStakeContract {
@@ -113,30 +157,15 @@ impl RuntimeProvider for ProofOfStakeContract {
 
 impl StakesProvider for ProofOfStakeContract {
     /// Reads the current stakes from the contract's known urefs.
-    fn read(&self) -> Result<Stakes, Error> {
-        let mut stakes = BTreeMap::new();
-        for (name, _) in runtime::list_named_keys() {
-            let mut split_name = name.split('_');
-            if Some("v") != split_name.next() {
-                continue;
-            }
-            let hex_key = split_name
-                .next()
-                .ok_or(Error::StakesKeyDeserializationFailed)?;
-            if hex_key.len() != 64 {
-                return Err(Error::StakesKeyDeserializationFailed);
-            }
-            let mut key_bytes = [0u8; 32];
-            let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
-                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            debug_assert!(_bytes_written == key_bytes.len());
-            let pub_key = AccountHash::new(key_bytes);
-            let balance = split_name
-                .next()
-                .and_then(|b| U512::from_dec_str(b).ok())
-                .ok_or(Error::StakesDeserializationFailed)?;
-            stakes.insert(pub_key, balance);
-        }
+    fn read(&mut self) -> Result<Stakes, Error> {
+        let uref = match runtime::get_key(STAKES_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::StakesKeyDeserializationFailed),
+            None => return Err(Error::StakesNotFound),
+        };
+        let stakes: BTreeMap<AccountHash, U512> = storage::read(uref)
+            .map_err(|_| Error::StakesDeserializationFailed)?
+            .ok_or(Error::StakesNotFound)?;
         if stakes.is_empty() {
             return Err(Error::StakesNotFound);
         }
@@ -145,11 +174,63 @@ impl StakesProvider for ProofOfStakeContract {
 
     /// Writes the current stakes to the contract's known urefs.
     fn write(&mut self, stakes: &Stakes) {
-        // Encode the stakes as a set of uref names.
-        let mut new_urefs: BTreeSet<String> = stakes.strings().collect();
+        let uref = runtime::get_key(STAKES_KEY)
+            .and_then(Key::into_uref)
+            .unwrap_or_revert_with(Error::StakesNotFound);
+        storage::write(uref, stakes.0.clone());
+    }
+
+    /// Reads the minimum bond from the contract's known urefs, or zero if it isn't set.
+    fn read_min_bond(&mut self) -> Result<u64, Error> {
+        let uref = match runtime::get_key(MIN_BOND_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MinBondKeyUnexpectedType),
+            None => return Ok(0),
+        };
+        storage::read(uref)
+            .map_err(|_| Error::MinBondKeyUnexpectedType)?
+            .ok_or(Error::MinBondNotFound)
+    }
+
+    /// Reads the maximum bond from the contract's known urefs, or zero if it isn't set.
+    fn read_max_bond(&mut self) -> Result<u64, Error> {
+        let uref = match runtime::get_key(MAX_BOND_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MaxBondKeyUnexpectedType),
+            None => return Ok(0),
+        };
+        storage::read(uref)
+            .map_err(|_| Error::MaxBondKeyUnexpectedType)?
+            .ok_or(Error::MaxBondNotFound)
+    }
+
+    /// Reads the maximum number of validator slots from the contract's known urefs, or zero if it
+    /// isn't set.
+    fn read_max_validator_slots(&mut self) -> Result<u32, Error> {
+        let uref = match runtime::get_key(MAX_VALIDATOR_SLOTS_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::ValidatorSlotsKeyUnexpectedType),
+            None => return Ok(0),
+        };
+        storage::read(uref)
+            .map_err(|_| Error::ValidatorSlotsKeyUnexpectedType)?
+            .ok_or(Error::ValidatorSlotsNotFound)
+    }
+}
+
+impl DelegationsProvider for ProofOfStakeContract {
+    /// Reads the current delegations from the contract's known urefs.
+    fn read_delegations(&self) -> Result<Delegations, Error> {
+        Delegations::from_strings(runtime::list_named_keys().keys())
+    }
+
+    /// Writes the current delegations to the contract's known urefs.
+    fn write_delegations(&mut self, delegations: &Delegations) {
+        // Encode the delegations as a set of uref names.
+        let mut new_urefs: BTreeSet<String> = delegations.strings().collect();
         // Remove and add urefs to update the contract's known urefs accordingly.
         for (name, _) in runtime::list_named_keys() {
-            if name.starts_with("v_") && !new_urefs.remove(&name) {
+            if Delegations::is_delegation_string(&name) && !new_urefs.remove(&name) {
                 runtime::remove_key(&name);
             }
         }
@@ -159,6 +240,34 @@ impl StakesProvider for ProofOfStakeContract {
     }
 }
 
+impl RewardsProvider for ProofOfStakeContract {
+    /// Reads the inflation rate from the contract's known urefs.
+    fn read_inflation_rate(&mut self) -> Result<u64, Error> {
+        let uref = match runtime::get_key(INFLATION_RATE_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::InflationRateKeyUnexpectedType),
+            None => return Err(Error::InflationRateNotFound),
+        };
+        storage::read(uref)
+            .map_err(|_| Error::InflationRateKeyUnexpectedType)?
+            .ok_or(Error::InflationRateNotFound)
+    }
+}
+
+impl SlashingProvider for ProofOfStakeContract {
+    /// Reads the slash rate from the contract's known urefs.
+    fn read_slash_rate(&mut self) -> Result<u64, Error> {
+        let uref = match runtime::get_key(SLASH_RATE_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::SlashRateKeyUnexpectedType),
+            None => return Err(Error::SlashRateNotFound),
+        };
+        storage::read(uref)
+            .map_err(|_| Error::SlashRateKeyUnexpectedType)?
+            .ok_or(Error::SlashRateNotFound)
+    }
+}
+
 impl ProofOfStake for ProofOfStakeContract {}
 
 pub fn bond() {
@@ -191,6 +300,49 @@ pub fn unbond() {
         .unwrap_or_revert();
 }
 
+pub fn withdraw_unbonded() {
+    if !cfg!(feature = "enable-bonding") {
+        runtime::revert(ApiError::Unhandled)
+    }
+
+    let account = runtime::get_caller();
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.withdraw_unbonded(account).unwrap_or_revert();
+}
+
+pub fn delegate() {
+    if !cfg!(feature = "enable-bonding") {
+        runtime::revert(ApiError::Unhandled)
+    }
+
+    let delegator = runtime::get_caller();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    // source purse
+    let source: URef = runtime::get_named_arg(ARG_PURSE);
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract
+        .delegate(delegator, validator, amount, source)
+        .unwrap_or_revert();
+}
+
+pub fn undelegate() {
+    if !cfg!(feature = "enable-bonding") {
+        runtime::revert(ApiError::Unhandled)
+    }
+
+    let delegator = runtime::get_caller();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let maybe_amount = runtime::get_named_arg(ARG_AMOUNT);
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract
+        .undelegate(delegator, validator, maybe_amount)
+        .unwrap_or_revert();
+}
+
 pub fn get_payment_purse() {
     let pos_contract = ProofOfStakeContract;
     let rights_controlled_purse = pos_contract.get_payment_purse().unwrap_or_revert();
@@ -220,9 +372,26 @@ pub fn get_refund_purse() {
 pub fn finalize_payment() {
     let mut pos_contract = ProofOfStakeContract;
 
-    let amount_spent: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let amount_spent: Motes = runtime::get_named_arg(ARG_AMOUNT);
     let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT_KEY);
     pos_contract
         .finalize_payment(amount_spent, account)
         .unwrap_or_revert();
 }
+
+pub fn distribute_rewards() {
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.distribute_rewards().unwrap_or_revert();
+}
+
+pub fn slash() {
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.slash(validator).unwrap_or_revert();
+}
+
+pub fn step() {
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.step().unwrap_or_revert();
+}
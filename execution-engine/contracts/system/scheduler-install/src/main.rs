@@ -0,0 +1,157 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys, Parameter},
+    scheduled_call::{
+        SCHEDULER_MAX_QUEUE_LENGTH, SCHEDULER_NAME, SCHEDULER_PURSE_KEY, SCHEDULER_QUEUE_KEY,
+    },
+    ApiError, BlockTime, CLType, CLValue, CallStackElement, ContractHash, RuntimeArgs,
+    ScheduledCall, URef, U512,
+};
+
+const METHOD_SCHEDULE: &str = "schedule";
+const ARG_ACTIVATION_TIME: &str = "activation_time";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_ARGS: &str = "args";
+const ARG_PURSE: &str = "purse";
+const ARG_AMOUNT: &str = "amount";
+
+#[repr(u16)]
+enum Error {
+    /// The scheduler was not called directly by a stored contract.
+    InvalidCaller = 0,
+    /// The scheduler's queue is missing.
+    MissingQueue = 1,
+    /// The scheduler's queue holds the maximum number of calls.
+    QueueFull = 2,
+    /// The scheduler's purse is missing.
+    MissingPurse = 3,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn queue_uref() -> URef {
+    runtime::get_key(SCHEDULER_QUEUE_KEY)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(Error::MissingQueue)
+}
+
+fn purse_uref() -> URef {
+    runtime::get_key(SCHEDULER_PURSE_KEY)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(Error::MissingPurse)
+}
+
+/// Returns the hash of the stored contract which called the scheduler.
+///
+/// Contracts may only schedule calls to themselves, so that no other code can use the scheduler to
+/// call them later on behalf of the current account.
+fn calling_contract() -> ContractHash {
+    let call_stack = runtime::get_call_stack();
+    let caller = call_stack
+        .len()
+        .checked_sub(2)
+        .and_then(|index| call_stack.get(index))
+        .unwrap_or_revert_with(Error::InvalidCaller);
+    match caller {
+        CallStackElement::StoredContract { contract_hash, .. } => *contract_hash,
+        _ => runtime::revert(Error::InvalidCaller),
+    }
+}
+
+/// Adds a call to the calling contract's `entry_point` with `args` to the queue, to be made at the
+/// first block whose time is at least `activation_time`.
+///
+/// The call's gas is paid for by moving `amount` from `purse` into the scheduler's purse.
+#[no_mangle]
+pub extern "C" fn schedule() {
+    let activation_time: u64 = runtime::get_named_arg(ARG_ACTIVATION_TIME);
+    let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+    let args: RuntimeArgs = runtime::get_named_arg(ARG_ARGS);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let payment: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let scheduled_call = ScheduledCall::new(
+        BlockTime::new(activation_time),
+        runtime::get_caller(),
+        calling_contract(),
+        entry_point,
+        args,
+        payment,
+    );
+
+    let queue_uref = queue_uref();
+    let mut queue: Vec<ScheduledCall> = storage::read(queue_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(Error::MissingQueue);
+    if queue.len() >= SCHEDULER_MAX_QUEUE_LENGTH {
+        runtime::revert(Error::QueueFull);
+    }
+
+    system::transfer_from_purse_to_purse(purse, purse_uref(), payment).unwrap_or_revert();
+    // Keep the queue ordered by activation time, and calls due at the same time in the order they
+    // were scheduled
+    let index = queue
+        .iter()
+        .position(|queued| queued.activation_time() > scheduled_call.activation_time())
+        .unwrap_or_else(|| queue.len());
+    queue.insert(index, scheduled_call);
+    storage::write(queue_uref, queue);
+}
+
+#[no_mangle]
+pub extern "C" fn upgrade() {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+
+        let entry_point = EntryPoint::new(
+            METHOD_SCHEDULE,
+            vec![
+                Parameter::new(ARG_ACTIVATION_TIME, CLType::U64),
+                Parameter::new(ARG_ENTRY_POINT, CLType::String),
+                Parameter::new(ARG_ARGS, CLType::Any),
+                Parameter::new(ARG_PURSE, CLType::URef),
+                Parameter::new(ARG_AMOUNT, CLType::U512),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(entry_point);
+
+        entry_points
+    };
+
+    let mut named_keys = NamedKeys::new();
+    let queue: Vec<ScheduledCall> = Vec::new();
+    named_keys.insert(
+        String::from(SCHEDULER_QUEUE_KEY),
+        storage::new_uref(queue).into(),
+    );
+    named_keys.insert(
+        String::from(SCHEDULER_PURSE_KEY),
+        system::create_purse().into(),
+    );
+
+    let (contract_hash, _contract_version) =
+        storage::new_contract(entry_points, Some(named_keys), None, None);
+
+    system::register_system_contract(SCHEDULER_NAME, contract_hash);
+
+    // None of the built-in system contracts are upgraded
+    let upgrades: BTreeMap<ContractHash, ContractHash> = BTreeMap::new();
+    runtime::ret(CLValue::from_t(upgrades).unwrap_or_revert());
+}
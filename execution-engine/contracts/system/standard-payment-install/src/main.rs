@@ -9,7 +9,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use standard_payment::ARG_AMOUNT;
+use standard_payment::{ARG_AMOUNT, ARG_SOURCE};
 use types::{
     contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys, Parameter},
     CLType, CLValue,
@@ -31,7 +31,10 @@ pub extern "C" fn install() {
 
         let entry_point = EntryPoint::new(
             METHOD_CALL.to_string(),
-            vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+            vec![
+                Parameter::new(ARG_AMOUNT, CLType::U512),
+                Parameter::new(ARG_SOURCE, CLType::URef),
+            ],
             CLType::Result {
                 ok: Box::new(CLType::Unit),
                 err: Box::new(CLType::U32),
@@ -11,6 +11,7 @@ use types::{ApiError, RuntimeArgs, URef, U512};
 
 const GET_PAYMENT_PURSE: &str = "get_payment_purse";
 pub const ARG_AMOUNT: &str = "amount";
+pub const ARG_SOURCE: &str = "source";
 
 struct StandardPaymentContract;
 
@@ -47,5 +48,9 @@ pub fn delegate() {
 
     let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
 
-    standard_payment_contract.pay(amount).unwrap_or_revert();
+    match runtime::try_get_named_arg::<URef>(ARG_SOURCE) {
+        Some(source) => standard_payment_contract.pay_from(source, amount),
+        None => standard_payment_contract.pay(amount),
+    }
+    .unwrap_or_revert();
 }
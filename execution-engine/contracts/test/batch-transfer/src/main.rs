@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::AccountHash, U512};
+
+const ARG_TRANSFERS: &str = "transfers";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let transfers: Vec<(AccountHash, U512)> = runtime::get_named_arg(ARG_TRANSFERS);
+    system::batch_transfer(account::get_main_purse(), transfers).unwrap_or_revert();
+}
@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, ContractHash, RuntimeArgs};
+
+const ARG_METHOD: &str = "method";
+const CONTRACT_KEY: &str = "call_contract_read_only";
+const RESULT_KEY: &str = "result";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_named_arg(ARG_METHOD);
+    let contract_hash: ContractHash = runtime::get_key(CONTRACT_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_hash()
+        .unwrap_or_revert();
+
+    let result: u64 =
+        runtime::call_contract_read_only(contract_hash, &method, RuntimeArgs::default());
+
+    runtime::put_key(RESULT_KEY, storage::new_uref(result).into());
+}
@@ -0,0 +1,70 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::Parameters, ApiError, CLType, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, URef,
+};
+
+const METHOD_GET_COUNTER: &str = "get_counter";
+const METHOD_INCREMENT_COUNTER: &str = "increment_counter";
+const COUNTER_KEY: &str = "counter";
+const CONTRACT_KEY: &str = "call_contract_read_only";
+const INITIAL_COUNTER: u64 = 1;
+
+fn counter_uref() -> URef {
+    runtime::get_key(COUNTER_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+fn read_counter() -> u64 {
+    storage::read(counter_uref())
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
+
+#[no_mangle]
+pub extern "C" fn get_counter() {
+    let counter = read_counter();
+    runtime::ret(CLValue::from_t(counter).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn increment_counter() {
+    let counter = read_counter() + 1;
+    storage::write(counter_uref(), counter);
+    runtime::ret(CLValue::from_t(counter).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    for method in &[METHOD_GET_COUNTER, METHOD_INCREMENT_COUNTER] {
+        entry_points.add_entry_point(EntryPoint::new(
+            *method,
+            Parameters::new(),
+            CLType::U64,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    let named_keys = {
+        let mut named_keys = BTreeMap::new();
+        let counter_uref = storage::new_uref(INITIAL_COUNTER);
+        named_keys.insert(String::from(COUNTER_KEY), counter_uref.into());
+        named_keys
+    };
+    let (contract_hash, _contract_version) =
+        storage::new_contract(entry_points, Some(named_keys), None, None);
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+}
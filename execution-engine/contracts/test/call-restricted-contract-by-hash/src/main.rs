@@ -0,0 +1,14 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::runtime;
+use types::{ContractHash, RuntimeArgs};
+
+const RESTRICTED_CONTRACT: &str = "restricted_contract";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    runtime::call_contract::<i32>(contract_hash, RESTRICTED_CONTRACT, RuntimeArgs::default());
+}
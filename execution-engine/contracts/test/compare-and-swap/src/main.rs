@@ -0,0 +1,44 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{bytesrepr::ToBytes, CLTyped, CLValue};
+
+const VALUE_NAME: &str = "value";
+
+fn cl_value<T: CLTyped + ToBytes>(value: T) -> CLValue {
+    CLValue::from_t(value).unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let uref = storage::new_uref(1u64);
+
+    // Different value
+    assert!(!storage::compare_and_swap(
+        uref,
+        cl_value(2u64),
+        cl_value(3u64)
+    ));
+    assert_eq!(storage::read_or_revert::<u64>(uref), 1);
+
+    // Same bytes but different type
+    assert!(!storage::compare_and_swap(
+        uref,
+        cl_value(1i64),
+        cl_value(3u64)
+    ));
+    assert_eq!(storage::read_or_revert::<u64>(uref), 1);
+
+    assert!(storage::compare_and_swap(
+        uref,
+        cl_value(1u64),
+        cl_value(3u64)
+    ));
+    assert_eq!(storage::read_or_revert::<u64>(uref), 3);
+
+    runtime::put_key(VALUE_NAME, uref.into());
+}
@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use contract_macro::{casperlabs_contract, CLError};
+use types::U512;
+
+#[derive(CLError)]
+enum Error {
+    Overflow = 10,
+}
+
+#[casperlabs_contract]
+mod entry_points {
+    use super::*;
+
+    #[casperlabs_method]
+    fn store_value(name: String, value: U512) {
+        runtime::put_key(&name, storage::new_uref(value).into());
+    }
+
+    #[casperlabs_method]
+    fn store_sum(name: String, lhs: u64, rhs: u64) -> u64 {
+        let sum = lhs.checked_add(rhs).unwrap_or_revert_with(Error::Overflow);
+        runtime::put_key(&name, storage::new_uref(sum).into());
+        sum
+    }
+}
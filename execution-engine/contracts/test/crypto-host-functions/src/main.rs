@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::{runtime, storage};
+
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const BLAKE2B_KEY: &str = "blake2b";
+const KECCAK256_KEY: &str = "keccak256";
+const ED25519_VALID_KEY: &str = "ed25519_valid";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let message: Vec<u8> = runtime::get_named_arg(ARG_MESSAGE);
+    let signature: Vec<u8> = runtime::get_named_arg(ARG_SIGNATURE);
+    let public_key: Vec<u8> = runtime::get_named_arg(ARG_PUBLIC_KEY);
+
+    let blake2b_digest = runtime::blake2b(&message).to_vec();
+    let keccak256_digest = runtime::keccak256(&message).to_vec();
+    let ed25519_valid = runtime::verify_ed25519(&message, &signature, &public_key);
+
+    runtime::put_key(BLAKE2B_KEY, storage::new_uref(blake2b_digest).into());
+    runtime::put_key(KECCAK256_KEY, storage::new_uref(keccak256_digest).into());
+    runtime::put_key(ED25519_VALID_KEY, storage::new_uref(ed25519_valid).into());
+}
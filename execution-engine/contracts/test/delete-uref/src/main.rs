@@ -0,0 +1,34 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_METHOD: &str = "method";
+const METHOD_DELETE: &str = "delete";
+const METHOD_DELETE_READ_ONLY: &str = "delete_read_only";
+const VALUE_NAME: &str = "value";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_named_arg(ARG_METHOD);
+    let uref = storage::new_uref(1u64);
+
+    match method.as_str() {
+        METHOD_DELETE => {
+            storage::delete(uref);
+            assert_eq!(storage::read::<u64>(uref).unwrap_or_revert(), None);
+            runtime::put_key(VALUE_NAME, uref.into());
+        }
+        // Deleting requires WRITE access rights.
+        METHOD_DELETE_READ_ONLY => storage::delete(uref.into_read()),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
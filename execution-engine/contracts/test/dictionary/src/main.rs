@@ -0,0 +1,42 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::U512;
+
+const DICTIONARY_NAME: &str = "dictionary";
+const ITEM_KEY_1: &str = "alice";
+const ITEM_KEY_2: &str = "bob";
+const MISSING_ITEM_KEY: &str = "carol";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let seed_uref = storage::dictionary_new();
+
+    storage::dictionary_put(seed_uref, ITEM_KEY_1, U512::from(100));
+    storage::dictionary_put(seed_uref, ITEM_KEY_2, String::from("hello"));
+    storage::dictionary_put(seed_uref, ITEM_KEY_1, U512::from(200));
+
+    let value_1: U512 = storage::dictionary_get(seed_uref, &ITEM_KEY_1)
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    assert_eq!(value_1, U512::from(200));
+
+    let value_2: String = storage::dictionary_get(seed_uref, &ITEM_KEY_2)
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    assert_eq!(value_2, "hello");
+
+    let missing: Option<U512> =
+        storage::dictionary_get(seed_uref, &MISSING_ITEM_KEY).unwrap_or_revert();
+    assert!(missing.is_none());
+
+    runtime::put_key(DICTIONARY_NAME, seed_uref.into());
+}
@@ -0,0 +1,67 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::NamedKeys, ApiError, CLType, ContractHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, RuntimeArgs,
+};
+
+const METHOD_PING: &str = "ping";
+const ARG_ACTION: &str = "action";
+const ACTION_INSTALL: &str = "install";
+const ACTION_DISABLE: &str = "disable";
+const ACTION_CALL: &str = "call";
+const CONTRACT_KEY: &str = "disable_contract";
+const ACCESS_KEY: &str = "disable_contract_access";
+const AUDIT_KEY: &str = "audit";
+const AUDIT_VALUE: u64 = 42;
+
+#[no_mangle]
+pub extern "C" fn ping() {}
+
+fn contract_hash() -> ContractHash {
+    runtime::get_key(CONTRACT_KEY)
+        .and_then(|key| key.into_hash())
+        .unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+fn install() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_PING,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(AUDIT_KEY.into(), storage::new_uref(AUDIT_VALUE).into());
+
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+    let (contract_hash, _contract_version) =
+        storage::add_contract_version(contract_package_hash, entry_points, named_keys);
+
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+    runtime::put_key(ACCESS_KEY, access_uref.into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_named_arg(ARG_ACTION);
+
+    match action.as_str() {
+        ACTION_INSTALL => install(),
+        ACTION_DISABLE => runtime::disable_contract(contract_hash()).unwrap_or_revert(),
+        ACTION_CALL => runtime::call_contract(contract_hash(), METHOD_PING, RuntimeArgs::new()),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
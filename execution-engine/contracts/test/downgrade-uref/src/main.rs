@@ -0,0 +1,80 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    runtime_args, AccessRights, ApiError, CLType, CLValue, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Parameter, RuntimeArgs, URef,
+};
+
+const ARG_METHOD: &str = "method";
+const ARG_UREF: &str = "uref";
+const METHOD_READ_VALUE: &str = "read_value";
+const METHOD_WRITE_VALUE: &str = "write_value";
+const METHOD_UPGRADE: &str = "upgrade";
+const STORED_VALUE: &str = "Hello, world!";
+const NEW_VALUE: &str = "Goodbye, world!";
+
+#[no_mangle]
+pub extern "C" fn read_value() {
+    let uref: URef = runtime::get_named_arg(ARG_UREF);
+    let value: String = storage::read(uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn write_value() {
+    let uref: URef = runtime::get_named_arg(ARG_UREF);
+    // Regaining the rights removed by the caller makes the URef forged.
+    storage::write(uref.into_read_add_write(), String::from(NEW_VALUE));
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_named_arg(ARG_METHOD);
+
+    let mut entry_points = EntryPoints::new();
+    for (name, ret) in &[
+        (METHOD_READ_VALUE, CLType::String),
+        (METHOD_WRITE_VALUE, CLType::Unit),
+    ] {
+        entry_points.add_entry_point(EntryPoint::new(
+            *name,
+            vec![Parameter::new(ARG_UREF, CLType::URef)],
+            ret.clone(),
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+
+    let uref = storage::new_uref(String::from(STORED_VALUE));
+
+    if method == METHOD_UPGRADE {
+        // Can't gain rights which the URef doesn't carry.
+        let read_only = runtime::downgrade_uref(uref, AccessRights::READ);
+        runtime::downgrade_uref(read_only, AccessRights::READ_WRITE);
+        return;
+    }
+
+    let read_only = runtime::downgrade_uref(uref, AccessRights::READ);
+    assert_eq!(read_only.access_rights(), AccessRights::READ);
+    assert_eq!(read_only.addr(), uref.addr());
+
+    let args = runtime_args! { ARG_UREF => read_only };
+    if method == METHOD_READ_VALUE {
+        let value: String = runtime::call_contract(contract_hash, METHOD_READ_VALUE, args);
+        assert_eq!(value, STORED_VALUE);
+    } else {
+        runtime::call_contract::<()>(contract_hash, &method, args);
+    }
+}
@@ -0,0 +1,21 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::CLValue;
+
+const ARG_NAME: &str = "name";
+const ARG_VALUE: &str = "value";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let value: u64 = runtime::get_named_arg(ARG_VALUE);
+
+    runtime::emit_event(&name, CLValue::from_t(value).unwrap_or_revert());
+    runtime::emit_event(&name, CLValue::from_t(value + 1).unwrap_or_revert());
+}
@@ -0,0 +1,61 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    CLType, CLValue, CallStackElement, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    RuntimeArgs,
+};
+
+const ENTRY_POINT_NAME: &str = "store_call_stack";
+const HASH_KEY_NAME: &str = "get_call_stack_contract";
+const ACCESS_KEY_NAME: &str = "get_call_stack_contract_access";
+const CALL_STACK_KEY: &str = "call_stack";
+const SUBCALL_CALL_STACK_KEY: &str = "subcall_call_stack";
+
+#[no_mangle]
+pub extern "C" fn store_call_stack() {
+    let call_stack = runtime::get_call_stack();
+    let uref = storage::new_uref(call_stack.clone());
+    runtime::put_key(CALL_STACK_KEY, uref.into());
+    runtime::ret(CLValue::from_t(call_stack).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let call_stack = runtime::get_call_stack();
+    let uref = storage::new_uref(call_stack);
+    runtime::put_key(CALL_STACK_KEY, uref.into());
+
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let entry_point = EntryPoint::new(
+            ENTRY_POINT_NAME.to_string(),
+            Vec::new(),
+            CLType::List(Box::new(CLType::Any)),
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(entry_point);
+        entry_points
+    };
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(HASH_KEY_NAME.to_string()),
+        Some(ACCESS_KEY_NAME.to_string()),
+    );
+
+    let subcall_call_stack: Vec<CallStackElement> =
+        runtime::call_contract(contract_hash, ENTRY_POINT_NAME, RuntimeArgs::default());
+    let uref = storage::new_uref(subcall_call_stack);
+    runtime::put_key(SUBCALL_CALL_STACK_KEY, uref.into());
+}
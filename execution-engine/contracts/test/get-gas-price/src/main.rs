@@ -0,0 +1,17 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::runtime;
+
+const ARG_KNOWN_GAS_PRICE: &str = "known_gas_price";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_gas_price: u64 = runtime::get_named_arg(ARG_KNOWN_GAS_PRICE);
+    let actual_gas_price: u64 = runtime::get_gas_price();
+
+    assert_eq!(
+        actual_gas_price, known_gas_price,
+        "actual gas price not known gas price"
+    );
+}
@@ -0,0 +1,19 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, Key};
+
+const ARG_NAME: &str = "name";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let contract_hash =
+        runtime::get_system_contract(&name).unwrap_or_revert_with(ApiError::InvalidSystemContract);
+    runtime::put_key(&name, Key::Hash(contract_hash));
+}
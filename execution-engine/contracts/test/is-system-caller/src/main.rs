@@ -0,0 +1,13 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+
+const IS_SYSTEM_CALLER_KEY: &str = "is_system_caller";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let is_system_caller = runtime::is_system_caller();
+    let uref = storage::new_uref(is_system_caller);
+    runtime::put_key(IS_SYSTEM_CALLER_KEY, uref.into());
+}
@@ -6,13 +6,13 @@ extern crate alloc;
 use alloc::string::String;
 
 use contract::{
-    contract_api::{account, runtime},
+    contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetMainPurseFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     ApiError,
 };
@@ -56,6 +56,12 @@ pub extern "C" fn call() {
             Err(SetThresholdFailure::PermissionDeniedError) => {}
             Err(_) => runtime::revert(ApiError::User(501)),
         }
+
+        match account::set_main_purse(system::create_purse()) {
+            Ok(_) => runtime::revert(ApiError::User(600)),
+            Err(SetMainPurseFailure::PermissionDenied) => {}
+            Err(_) => runtime::revert(ApiError::User(601)),
+        }
     } else if stage == "test-key-mgmnt-succeed" {
         // Has to be executed with keys of total weight >= 254
         account::add_associated_key(AccountHash::new([44; 32]), Weight::new(1)).unwrap_or_revert();
@@ -0,0 +1,22 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use contract::contract_api::{runtime, storage};
+
+const ARG_COUNT: &str = "count";
+const ARG_SIZE: &str = "size";
+
+/// Stores `count` values of `size` bytes each under new named keys.
+#[no_mangle]
+pub extern "C" fn call() {
+    let count: u32 = runtime::get_named_arg(ARG_COUNT);
+    let size: u32 = runtime::get_named_arg(ARG_SIZE);
+
+    for i in 0..count {
+        let uref = storage::new_uref(vec![0u8; size as usize]);
+        runtime::put_key(&format!("value_{}", i), uref.into());
+    }
+}
@@ -0,0 +1,64 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::format;
+use core::cmp;
+
+use contract::contract_api::runtime;
+use types::{
+    contracts::{NamedKeys, MAX_NAMED_KEYS_PAGE_SIZE},
+    Key,
+};
+
+const ARG_KEY_COUNT: &str = "key_count";
+const PAGE_START: usize = 3;
+const PAGE_LIMIT: u32 = 7;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let key_count: u32 = runtime::get_named_arg(ARG_KEY_COUNT);
+
+    for i in 0..key_count {
+        let mut hash = [0u8; 32];
+        hash[..4].copy_from_slice(&i.to_le_bytes());
+        runtime::put_key(&format!("key_{:05}", i), Key::Hash(hash));
+    }
+
+    let expected_named_keys = runtime::list_named_keys();
+    assert_eq!(expected_named_keys.len(), key_count as usize);
+
+    // The lazy iterator visits every named key across all pages.
+    let actual_named_keys: NamedKeys = runtime::named_keys_iter().collect();
+    assert_eq!(actual_named_keys, expected_named_keys);
+
+    // A single page holds the named keys following the given name, in name order.
+    let start_after = expected_named_keys
+        .keys()
+        .nth(PAGE_START - 1)
+        .expect("should have enough named keys");
+    let expected_page: NamedKeys = expected_named_keys
+        .iter()
+        .skip(PAGE_START)
+        .take(PAGE_LIMIT as usize)
+        .map(|(name, key)| (name.clone(), *key))
+        .collect();
+    let actual_page = runtime::list_named_keys_paged(Some(start_after), PAGE_LIMIT);
+    assert_eq!(actual_page, expected_page);
+
+    // The first page starts from the first named key.
+    let first_page = runtime::list_named_keys_paged(None, PAGE_LIMIT);
+    assert_eq!(first_page.keys().next(), expected_named_keys.keys().next());
+
+    // Pages past the end are empty.
+    let last_name = expected_named_keys
+        .keys()
+        .next_back()
+        .expect("should have named keys");
+    assert!(runtime::list_named_keys_paged(Some(last_name), PAGE_LIMIT).is_empty());
+
+    // The host caps the size of a page.
+    let capped_page = runtime::list_named_keys_paged(None, u32::max_value());
+    assert_eq!(capped_page.len(), cmp::min(key_count, MAX_NAMED_KEYS_PAGE_SIZE) as usize);
+}
@@ -0,0 +1,24 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{AccessRights, U512};
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_ADD_ONLY: &str = "add_only";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let add_only: bool = runtime::get_named_arg(ARG_ADD_ONLY);
+
+    let mut purse = account::get_main_purse();
+    if add_only {
+        purse = purse.with_access_rights(AccessRights::ADD);
+    }
+
+    system::burn(purse, amount).unwrap_or_revert();
+}
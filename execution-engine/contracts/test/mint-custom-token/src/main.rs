@@ -0,0 +1,46 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::U512;
+
+const ARG_TOKEN_ID: &str = "token_id";
+const ARG_INITIAL_SUPPLY: &str = "initial_supply";
+const ARG_AMOUNT: &str = "amount";
+const SOURCE_PURSE_KEY: &str = "source_purse";
+const TARGET_PURSE_KEY: &str = "target_purse";
+const SOURCE_BALANCE_KEY: &str = "source_balance";
+const TARGET_BALANCE_KEY: &str = "target_balance";
+const NATIVE_BALANCE_KEY: &str = "native_balance";
+const TOTAL_SUPPLY_KEY: &str = "token_total_supply";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let token_id: String = runtime::get_named_arg(ARG_TOKEN_ID);
+    let initial_supply: U512 = runtime::get_named_arg(ARG_INITIAL_SUPPLY);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let source = system::create_token(&token_id, initial_supply).unwrap_or_revert();
+    let target = system::create_token_purse(&token_id).unwrap_or_revert();
+    system::transfer_token(&token_id, source, target, amount).unwrap_or_revert();
+
+    runtime::put_key(SOURCE_PURSE_KEY, source.into());
+    runtime::put_key(TARGET_PURSE_KEY, target.into());
+
+    let source_balance = system::get_token_balance(&token_id, source);
+    let target_balance = system::get_token_balance(&token_id, target);
+    let native_balance = system::get_balance(source);
+    let total_supply = system::read_token_total_supply(&token_id);
+
+    runtime::put_key(SOURCE_BALANCE_KEY, storage::new_uref(source_balance).into());
+    runtime::put_key(TARGET_BALANCE_KEY, storage::new_uref(target_balance).into());
+    runtime::put_key(NATIVE_BALANCE_KEY, storage::new_uref(native_balance).into());
+    runtime::put_key(TOTAL_SUPPLY_KEY, storage::new_uref(total_supply).into());
+}
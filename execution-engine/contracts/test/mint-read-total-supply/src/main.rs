@@ -0,0 +1,13 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage, system};
+
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let total_supply = system::read_total_supply();
+    let total_supply_uref = storage::new_uref(total_supply);
+    runtime::put_key(TOTAL_SUPPLY_KEY, total_supply_uref.into());
+}
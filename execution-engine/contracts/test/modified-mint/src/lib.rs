@@ -15,8 +15,8 @@ use types::{
     bytesrepr::{FromBytes, ToBytes},
     contracts::Parameters,
     system_contract_errors::mint::Error,
-    CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
-    Parameter, URef, U512,
+    BlockTime, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Key, Motes, Parameter, URef, U512,
 };
 
 pub const METHOD_MINT: &str = "mint";
@@ -39,6 +39,14 @@ impl RuntimeProvider for MintContract {
     fn put_key(&mut self, name: &str, key: Key) {
         runtime::put_key(name, key)
     }
+
+    fn get_key(&self, name: &str) -> Option<Key> {
+        runtime::get_key(name)
+    }
+
+    fn get_block_time(&self) -> BlockTime {
+        runtime::get_blocktime()
+    }
 }
 
 impl StorageProvider for MintContract {
@@ -76,7 +84,7 @@ impl Mint for MintContract {}
 
 pub fn mint() {
     let mut mint_contract = MintContract;
-    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let amount: Motes = runtime::get_named_arg(ARG_AMOUNT);
     let result: Result<URef, Error> = mint_contract.mint(amount);
     let ret = CLValue::from_t(result).unwrap_or_revert();
     runtime::ret(ret)
@@ -84,7 +92,9 @@ pub fn mint() {
 
 pub fn create() {
     let mut mint_contract = MintContract;
-    let uref = mint_contract.mint(U512::zero()).unwrap_or_revert();
+    let uref = mint_contract
+        .mint(Motes::new(U512::zero()))
+        .unwrap_or_revert();
     let ret = CLValue::from_t(uref).unwrap_or_revert();
     runtime::ret(ret)
 }
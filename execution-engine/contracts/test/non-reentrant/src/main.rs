@@ -0,0 +1,60 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::NamedKeys, runtime_args, CLType, CLTyped, ContractHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Parameter, RuntimeArgs,
+};
+
+const METHOD_RECURSE: &str = "recurse";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_DEPTH: &str = "depth";
+const ARG_NON_REENTRANT: &str = "non_reentrant";
+const CONTRACT_KEY: &str = "non_reentrant";
+
+/// Calls itself `depth` more times.
+#[no_mangle]
+pub extern "C" fn recurse() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    let depth: u32 = runtime::get_named_arg(ARG_DEPTH);
+    if depth > 0 {
+        let args = runtime_args! {
+            ARG_CONTRACT_HASH => contract_hash,
+            ARG_DEPTH => depth - 1,
+        };
+        runtime::call_contract::<()>(contract_hash, METHOD_RECURSE, args);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let non_reentrant: bool = runtime::get_named_arg(ARG_NON_REENTRANT);
+
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_RECURSE,
+        vec![
+            Parameter::new(ARG_CONTRACT_HASH, ContractHash::cl_type()),
+            Parameter::new(ARG_DEPTH, CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_package_hash, _access_uref) = storage::create_contract_package_at_hash();
+    let (contract_hash, _contract_version) =
+        storage::add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
+    storage::set_non_reentrant(contract_package_hash, contract_hash, non_reentrant)
+        .unwrap_or_revert();
+
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+}
@@ -17,6 +17,10 @@ const ARG_PURSE: &str = "purse";
 const ARG_ENTRY_POINT: &str = "entry_point";
 const ARG_BOND: &str = "bond";
 const ARG_UNBOND: &str = "unbond";
+const ARG_DELEGATE: &str = "delegate";
+const ARG_UNDELEGATE: &str = "undelegate";
+const ARG_WITHDRAW_UNBONDED: &str = "withdraw_unbonded";
+const ARG_VALIDATOR: &str = "validator";
 const ARG_ACCOUNT_HASH: &str = "account_hash";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
@@ -34,6 +38,9 @@ pub extern "C" fn call() {
     match command.as_str() {
         ARG_BOND => bond(),
         ARG_UNBOND => unbond(),
+        ARG_DELEGATE => delegate(),
+        ARG_UNDELEGATE => undelegate(),
+        ARG_WITHDRAW_UNBONDED => withdraw_unbonded(),
         TEST_BOND_FROM_MAIN_PURSE => bond_from_main_purse(),
         TEST_SEED_NEW_ACCOUNT => seed_new_account(),
         _ => runtime::revert(ApiError::User(Error::UnknownCommand as u16)),
@@ -79,6 +86,34 @@ fn unbonding(pos: ContractHash, unbond_amount: Option<U512>) {
     runtime::call_contract(pos, ARG_UNBOND, args)
 }
 
+fn withdraw_unbonded() {
+    let pos_contract_hash = system::get_proof_of_stake();
+    runtime::call_contract(pos_contract_hash, ARG_WITHDRAW_UNBONDED, runtime_args! {})
+}
+
+fn delegate() {
+    let pos_contract_hash = system::get_proof_of_stake();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let args = runtime_args! {
+        ARG_VALIDATOR => validator,
+        ARG_AMOUNT => amount,
+        ARG_PURSE => account::get_main_purse(),
+    };
+    runtime::call_contract(pos_contract_hash, ARG_DELEGATE, args)
+}
+
+fn undelegate() {
+    let pos_contract_hash = system::get_proof_of_stake();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let maybe_amount: Option<U512> = runtime::get_named_arg(ARG_AMOUNT);
+    let args = runtime_args! {
+        ARG_VALIDATOR => validator,
+        ARG_AMOUNT => maybe_amount,
+    };
+    runtime::call_contract(pos_contract_hash, ARG_UNDELEGATE, args)
+}
+
 fn seed_new_account() {
     let source = account::get_main_purse();
     let target: AccountHash = runtime::get_named_arg(ARG_ACCOUNT_HASH);
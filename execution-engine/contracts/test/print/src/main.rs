@@ -0,0 +1,16 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::contract_api::runtime;
+
+const ARG_TEXT: &str = "text";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let text: String = runtime::get_named_arg(ARG_TEXT);
+    runtime::print(&text);
+}
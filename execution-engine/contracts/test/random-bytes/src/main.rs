@@ -0,0 +1,39 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{
+    contract_api::{runtime, storage},
+    ext_ffi,
+};
+
+const ARG_COUNT: &str = "count";
+/// A `count` which asks the host to write far past the end of a one-byte buffer.
+const OUT_OF_BOUNDS_COUNT: u32 = u32::max_value();
+const RANDOM_BYTES_KEY: &str = "random_bytes";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let count: u32 = runtime::get_named_arg(ARG_COUNT);
+
+    if count == OUT_OF_BOUNDS_COUNT {
+        let mut byte = [0u8; 1];
+        unsafe { ext_ffi::random_bytes(byte.as_mut_ptr(), count as usize) }
+        return;
+    }
+
+    let bytes_1: Vec<u8> = runtime::random_bytes(count as usize);
+    let bytes_2: Vec<u8> = runtime::random_bytes(count as usize);
+
+    assert_eq!(bytes_1.len(), count as usize);
+    assert_eq!(bytes_2.len(), count as usize);
+    if count > 0 {
+        assert_ne!(bytes_1, bytes_2, "consecutive calls should yield distinct bytes");
+    }
+
+    let uref = storage::new_uref(bytes_1);
+    runtime::put_key(RANDOM_BYTES_KEY, uref.into());
+}
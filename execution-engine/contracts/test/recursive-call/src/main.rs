@@ -0,0 +1,48 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use contract::contract_api::{runtime, storage};
+use types::{
+    runtime_args, CLType, CLTyped, ContractHash, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, Parameter, RuntimeArgs,
+};
+
+const METHOD_RECURSE: &str = "recurse";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_DEPTH: &str = "depth";
+const CONTRACT_KEY: &str = "recursive_call";
+
+/// Calls itself `depth` more times.
+#[no_mangle]
+pub extern "C" fn recurse() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    let depth: u32 = runtime::get_named_arg(ARG_DEPTH);
+    if depth > 0 {
+        let args = runtime_args! {
+            ARG_CONTRACT_HASH => contract_hash,
+            ARG_DEPTH => depth - 1,
+        };
+        runtime::call_contract::<()>(contract_hash, METHOD_RECURSE, args);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_RECURSE,
+        vec![
+            Parameter::new(ARG_CONTRACT_HASH, ContractHash::cl_type()),
+            Parameter::new(ARG_DEPTH, CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+}
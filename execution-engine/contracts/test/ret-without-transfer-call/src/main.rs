@@ -0,0 +1,32 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, ContractHash, RuntimeArgs, URef};
+
+const ARG_METHOD: &str = "method";
+const CONTRACT_KEY: &str = "ret_without_transfer";
+const STORED_VALUE: &str = "Hello, world!";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_named_arg(ARG_METHOD);
+    let contract_hash: ContractHash = runtime::get_key(CONTRACT_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_hash()
+        .unwrap_or_revert();
+
+    let uref: URef = runtime::call_contract(contract_hash, &method, RuntimeArgs::default());
+
+    let value: String = storage::read(uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    assert_eq!(value, STORED_VALUE);
+}
@@ -0,0 +1,51 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::Parameters, CLType, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, URef,
+};
+
+const METHOD_RET: &str = "ret";
+const METHOD_RET_WITHOUT_TRANSFER: &str = "ret_without_transfer";
+const CONTRACT_KEY: &str = "ret_without_transfer";
+const STORED_VALUE: &str = "Hello, world!";
+
+fn new_value() -> CLValue {
+    let uref: URef = storage::new_uref(String::from(STORED_VALUE));
+    CLValue::from_t(uref).unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn ret() {
+    runtime::ret(new_value())
+}
+
+#[no_mangle]
+pub extern "C" fn ret_without_transfer() {
+    runtime::ret_without_transfer(new_value())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    for method in &[METHOD_RET, METHOD_RET_WITHOUT_TRANSFER] {
+        entry_points.add_entry_point(EntryPoint::new(
+            *method,
+            Parameters::new(),
+            CLType::URef,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+}
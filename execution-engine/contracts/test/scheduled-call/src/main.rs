@@ -0,0 +1,133 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    runtime_args, ApiError, BlockTime, CLType, ContractHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Parameter, RuntimeArgs, U512,
+};
+
+const CONTRACT_HASH_KEY: &str = "scheduled_call_contract_hash";
+const TICKS_KEY: &str = "ticks";
+const PURSE_KEY: &str = "purse";
+const METHOD_TICK: &str = "tick";
+const METHOD_FAIL: &str = "fail";
+const METHOD_SCHEDULE: &str = "schedule";
+const ARG_ACTIVATION_TIME: &str = "activation_time";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_DIRECT: &str = "direct";
+const ARG_PAYMENT: &str = "payment";
+/// The delay after the first activation time at which the last call is scheduled.
+const SECOND_TICK_DELAY: u64 = 1_000;
+
+#[no_mangle]
+pub extern "C" fn tick() {
+    let ticks = runtime::get_key(TICKS_KEY)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::add(ticks, 1u64);
+}
+
+#[no_mangle]
+pub extern "C" fn fail() {
+    runtime::revert(ApiError::User(1))
+}
+
+#[no_mangle]
+pub extern "C" fn schedule() {
+    let activation_time: u64 = runtime::get_named_arg(ARG_ACTIVATION_TIME);
+    let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+    let payment: U512 = runtime::get_named_arg(ARG_PAYMENT);
+    let purse = runtime::get_key(PURSE_KEY)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    system::schedule_call(
+        BlockTime::new(activation_time),
+        &entry_point,
+        RuntimeArgs::new(),
+        purse,
+        payment,
+    );
+}
+
+/// Stores the contract with a purse funded from the account's main purse with `funds`.
+fn install(funds: U512) -> ContractHash {
+    let mut entry_points = EntryPoints::new();
+    for method in &[METHOD_TICK, METHOD_FAIL] {
+        entry_points.add_entry_point(EntryPoint::new(
+            *method,
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SCHEDULE,
+        vec![
+            Parameter::new(ARG_ACTIVATION_TIME, CLType::U64),
+            Parameter::new(ARG_ENTRY_POINT, CLType::String),
+            Parameter::new(ARG_PAYMENT, CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(String::from(TICKS_KEY), storage::new_uref(0u64).into());
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, funds)
+        .unwrap_or_revert();
+    named_keys.insert(String::from(PURSE_KEY), purse.into());
+
+    let (contract_hash, _contract_version) =
+        storage::new_contract(entry_points, Some(named_keys), None, None);
+    runtime::put_key(CONTRACT_HASH_KEY, contract_hash.into());
+    contract_hash
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let activation_time: u64 = runtime::get_named_arg(ARG_ACTIVATION_TIME);
+    let direct: bool = runtime::get_named_arg(ARG_DIRECT);
+    let payment: U512 = runtime::get_named_arg(ARG_PAYMENT);
+
+    if direct {
+        // Only stored contracts may schedule calls, so this is expected to revert
+        system::schedule_call(
+            BlockTime::new(activation_time),
+            METHOD_TICK,
+            RuntimeArgs::new(),
+            account::get_main_purse(),
+            payment,
+        );
+        return;
+    }
+
+    let contract_hash = install(payment * 3);
+    let schedule = |activation_time: u64, entry_point: &str| {
+        runtime::call_contract::<()>(
+            contract_hash,
+            METHOD_SCHEDULE,
+            runtime_args! {
+                ARG_ACTIVATION_TIME => activation_time,
+                ARG_ENTRY_POINT => String::from(entry_point),
+                ARG_PAYMENT => payment,
+            },
+        )
+    };
+    // Scheduled out of order to check that calls are made by activation time
+    schedule(activation_time + SECOND_TICK_DELAY, METHOD_TICK);
+    schedule(activation_time, METHOD_TICK);
+    schedule(activation_time, METHOD_FAIL);
+}
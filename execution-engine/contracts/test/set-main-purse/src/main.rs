@@ -0,0 +1,44 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::SetMainPurseFailure, AccessRights, ApiError, URef};
+
+const ARG_STAGE: &str = "stage";
+const NEW_PURSE_KEY: &str = "new_purse";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    if stage == "rotate" {
+        let new_purse = system::create_purse();
+        account::set_main_purse(new_purse).unwrap_or_revert();
+        runtime::put_key(NEW_PURSE_KEY, new_purse.into());
+    } else if stage == "test-invalid-purse" {
+        // A purse the account can't write to
+        let read_only_purse = system::create_purse().into_read();
+        match account::set_main_purse(read_only_purse) {
+            Ok(_) => runtime::revert(ApiError::User(100)),
+            Err(SetMainPurseFailure::InvalidPurse) => {}
+            Err(_) => runtime::revert(ApiError::User(101)),
+        }
+
+        // A URef which isn't a purse
+        let not_a_purse = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+        match account::set_main_purse(not_a_purse) {
+            Ok(_) => runtime::revert(ApiError::User(200)),
+            Err(SetMainPurseFailure::InvalidPurse) => {}
+            Err(_) => runtime::revert(ApiError::User(201)),
+        }
+    } else {
+        runtime::revert(ApiError::User(1))
+    }
+}
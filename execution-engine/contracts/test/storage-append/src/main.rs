@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+
+use contract::contract_api::{runtime, storage};
+
+const LOG_NAME: &str = "log";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let log_uref = storage::new_uref(vec![String::from("first")]);
+
+    storage::append(log_uref, String::from("second"));
+    storage::append(log_uref, String::from("third"));
+
+    runtime::put_key(LOG_NAME, log_uref.into());
+}
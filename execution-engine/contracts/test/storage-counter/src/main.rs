@@ -0,0 +1,33 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage, storage::Counter},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const COUNTER_NAME: &str = "counter";
+const LAST_ID_NAME: &str = "last_id";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    if !runtime::has_key(COUNTER_NAME) {
+        let counter = storage::counter_new();
+        runtime::put_key(COUNTER_NAME, counter.into());
+    }
+
+    let first_id = runtime::counter_inc(COUNTER_NAME);
+
+    let counter: Counter = runtime::get_key(COUNTER_NAME)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into();
+    let second_id = storage::counter_inc(counter);
+    if second_id != first_id + 1 || storage::counter_read(counter) != second_id {
+        runtime::revert(ApiError::User(1));
+    }
+
+    let last_id = storage::new_uref(second_id);
+    runtime::put_key(LAST_ID_NAME, last_id.into());
+}
@@ -0,0 +1,53 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::Parameters, CLType, CLValue, ContractHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints,
+};
+
+const SYSTEM_CONTRACT_NAME: &str = "auction";
+const VERSION_ENTRY_POINT: &str = "version";
+const VERSION: &str = "1.0.0";
+
+#[no_mangle]
+pub extern "C" fn version() {
+    runtime::ret(CLValue::from_t(VERSION).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn upgrade() {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let entry_point = EntryPoint::new(
+            VERSION_ENTRY_POINT,
+            Parameters::new(),
+            CLType::String,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(entry_point);
+        entry_points
+    };
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+
+    system::register_system_contract(SYSTEM_CONTRACT_NAME, contract_hash);
+
+    // None of the built-in system contracts are upgraded
+    let upgrades: BTreeMap<ContractHash, ContractHash> = BTreeMap::new();
+    runtime::ret(CLValue::from_t(upgrades).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // Registering system contracts outside of an upgrade installer is expected to fail
+    system::register_system_contract(SYSTEM_CONTRACT_NAME, system::get_mint());
+}
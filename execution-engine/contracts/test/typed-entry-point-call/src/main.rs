@@ -0,0 +1,27 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{runtime_args, ApiError, ContractHash, RuntimeArgs, U512};
+
+const METHOD_SET_VALUE: &str = "set_value";
+const ARG_VALUE: &str = "value";
+const ARG_USE_WRONG_TYPE: &str = "use_wrong_type";
+const CONTRACT_KEY: &str = "typed_entry_point";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value: u64 = runtime::get_named_arg(ARG_VALUE);
+    let use_wrong_type: bool = runtime::get_named_arg(ARG_USE_WRONG_TYPE);
+    let contract_hash: ContractHash = runtime::get_key(CONTRACT_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_hash()
+        .unwrap_or_revert();
+
+    let args = if use_wrong_type {
+        runtime_args! { ARG_VALUE => value }
+    } else {
+        runtime_args! { ARG_VALUE => U512::from(value) }
+    };
+    runtime::call_contract::<()>(contract_hash, METHOD_SET_VALUE, args);
+}
@@ -0,0 +1,54 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::Parameter, ApiError, CLType, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, URef, U512,
+};
+
+const METHOD_SET_VALUE: &str = "set_value";
+const ARG_VALUE: &str = "value";
+const VALUE_KEY: &str = "value";
+const CONTRACT_KEY: &str = "typed_entry_point";
+
+fn value_uref() -> URef {
+    runtime::get_key(VALUE_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+#[no_mangle]
+pub extern "C" fn set_value() {
+    let value: U512 = runtime::get_named_arg(ARG_VALUE);
+    storage::write(value_uref(), value);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_VALUE,
+        vec![Parameter::new(ARG_VALUE, CLType::U512)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    let named_keys = {
+        let mut named_keys = BTreeMap::new();
+        let value_uref = storage::new_uref(U512::zero());
+        named_keys.insert(String::from(VALUE_KEY), value_uref.into());
+        named_keys
+    };
+    let (contract_hash, _contract_version) =
+        storage::new_contract(entry_points, Some(named_keys), None, None);
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+}
@@ -0,0 +1,22 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+use types::U512;
+
+const ARG_A: &str = "a";
+const ARG_B: &str = "b";
+const ARG_C: &str = "c";
+const RESULT_KEY: &str = "result";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let a: U512 = runtime::get_named_arg(ARG_A);
+    let b: U512 = runtime::get_named_arg(ARG_B);
+    let c: U512 = runtime::get_named_arg(ARG_C);
+
+    let result: Option<U512> = runtime::u512_mul_div(a, b, c);
+
+    let uref = storage::new_uref(result);
+    runtime::put_key(RESULT_KEY, uref.into());
+}
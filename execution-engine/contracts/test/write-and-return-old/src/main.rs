@@ -0,0 +1,43 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::U512;
+
+const VALUE_NAME: &str = "value";
+const COUNTER_NAME: &str = "counter";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value_uref = storage::new_uref(String::from("first"));
+
+    let old_value: String = storage::write_and_return_old(value_uref, String::from("second"))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    assert_eq!(old_value, "first");
+
+    let old_value: String = storage::write_and_return_old(value_uref, String::from("third"))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    assert_eq!(old_value, "second");
+
+    let counter_uref = storage::new_uref(U512::zero());
+    assert_eq!(
+        storage::add_and_read(counter_uref, U512::from(5)),
+        U512::from(5)
+    );
+    assert_eq!(
+        storage::add_and_read(counter_uref, U512::from(7)),
+        U512::from(12)
+    );
+
+    runtime::put_key(VALUE_NAME, value_uref.into());
+    runtime::put_key(COUNTER_NAME, counter_uref.into());
+}
@@ -0,0 +1,29 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::contract_api::{runtime, storage};
+
+const ARG_NAME_LENGTH: &str = "name_length";
+const ARG_VALUE_SIZE: &str = "value_size";
+const ARG_APPEND_SIZE: &str = "append_size";
+
+/// Stores a list holding a chunk of `value_size` bytes under a named key whose name is
+/// `name_length` bytes long, then appends a chunk of `append_size` bytes to it unless that is zero.
+#[no_mangle]
+pub extern "C" fn call() {
+    let name_length: u32 = runtime::get_named_arg(ARG_NAME_LENGTH);
+    let value_size: u32 = runtime::get_named_arg(ARG_VALUE_SIZE);
+    let append_size: u32 = runtime::get_named_arg(ARG_APPEND_SIZE);
+
+    let name: String = "k".repeat(name_length as usize);
+    let uref = storage::new_uref(vec![vec![0u8; value_size as usize]]);
+    if append_size > 0 {
+        storage::append(uref, vec![0u8; append_size as usize]);
+    }
+    runtime::put_key(&name, uref.into());
+}
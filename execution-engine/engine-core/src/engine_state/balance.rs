@@ -0,0 +1,38 @@
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_storage::trie::merkle_proof::TrieMerkleProof;
+use types::{account::AccountHash, Key, U512};
+
+pub enum BalanceResult {
+    RootNotFound,
+    ValueNotFound(String),
+    Success {
+        motes: U512,
+        /// Proofs of the account, of its main purse's balance mapping and of the balance itself,
+        /// in that order.
+        proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+    },
+}
+
+/// A request for the balance of an account's main purse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceRequest {
+    state_hash: Blake2bHash,
+    account_hash: AccountHash,
+}
+
+impl BalanceRequest {
+    pub fn new(state_hash: Blake2bHash, account_hash: AccountHash) -> Self {
+        BalanceRequest {
+            state_hash,
+            account_hash,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn account_hash(&self) -> AccountHash {
+        self.account_hash
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use types::{account::AccountHash, U512};
+
+/// A change to a validator's stake in the proof-of-stake contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondingEvent {
+    /// The validator's stake was increased by `amount`.
+    Bond {
+        validator: AccountHash,
+        amount: U512,
+    },
+    /// The validator's stake was decreased by `amount`.
+    Unbond {
+        validator: AccountHash,
+        amount: U512,
+    },
+}
+
+impl BondingEvent {
+    /// Returns the events which turn `pre_stakes` into `post_stakes`, ordered by validator.
+    pub fn from_stakes(
+        pre_stakes: &BTreeMap<AccountHash, U512>,
+        post_stakes: &BTreeMap<AccountHash, U512>,
+    ) -> Vec<BondingEvent> {
+        let mut validators: Vec<&AccountHash> =
+            pre_stakes.keys().chain(post_stakes.keys()).collect();
+        validators.sort();
+        validators.dedup();
+
+        validators
+            .into_iter()
+            .filter_map(|validator| {
+                let pre_stake = pre_stakes.get(validator).cloned().unwrap_or_default();
+                let post_stake = post_stakes.get(validator).cloned().unwrap_or_default();
+                if post_stake > pre_stake {
+                    Some(BondingEvent::Bond {
+                        validator: *validator,
+                        amount: post_stake - pre_stake,
+                    })
+                } else if post_stake < pre_stake {
+                    Some(BondingEvent::Unbond {
+                        validator: *validator,
+                        amount: pre_stake - post_stake,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn validator(&self) -> AccountHash {
+        match self {
+            BondingEvent::Bond { validator, .. } | BondingEvent::Unbond { validator, .. } => {
+                *validator
+            }
+        }
+    }
+
+    pub fn amount(&self) -> U512 {
+        match self {
+            BondingEvent::Bond { amount, .. } | BondingEvent::Unbond { amount, .. } => *amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALIDATOR_1: AccountHash = AccountHash::new([1; 32]);
+    const VALIDATOR_2: AccountHash = AccountHash::new([2; 32]);
+    const VALIDATOR_3: AccountHash = AccountHash::new([3; 32]);
+
+    fn stakes(stakes: &[(AccountHash, u64)]) -> BTreeMap<AccountHash, U512> {
+        stakes
+            .iter()
+            .map(|(validator, stake)| (*validator, U512::from(*stake)))
+            .collect()
+    }
+
+    #[test]
+    fn should_detect_bonding_events() {
+        let pre_stakes = stakes(&[(VALIDATOR_1, 100), (VALIDATOR_2, 200)]);
+        let post_stakes = stakes(&[(VALIDATOR_1, 150), (VALIDATOR_3, 50)]);
+
+        assert_eq!(
+            BondingEvent::from_stakes(&pre_stakes, &post_stakes),
+            vec![
+                BondingEvent::Bond {
+                    validator: VALIDATOR_1,
+                    amount: U512::from(50)
+                },
+                BondingEvent::Unbond {
+                    validator: VALIDATOR_2,
+                    amount: U512::from(200)
+                },
+                BondingEvent::Bond {
+                    validator: VALIDATOR_3,
+                    amount: U512::from(50)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_detect_events_for_unchanged_stakes() {
+        let pre_stakes = stakes(&[(VALIDATOR_1, 100), (VALIDATOR_2, 200)]);
+        assert!(BondingEvent::from_stakes(&pre_stakes, &pre_stakes).is_empty());
+    }
+}
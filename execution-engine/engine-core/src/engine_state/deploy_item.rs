@@ -1,11 +1,21 @@
 use std::collections::BTreeSet;
 
-use types::account::AccountHash;
+use types::{account::AccountHash, Key};
 
 use crate::{engine_state::executable_deploy_item::ExecutableDeployItem, DeployHash};
 
 type GasPrice = u64;
 
+/// How long after its execution the record of a deploy satisfies the dependencies of other
+/// deploys, in milliseconds.  Older records are treated as expired and may be pruned.
+pub const EXECUTED_DEPLOY_RETENTION_MILLIS: u64 = 10 * 24 * 60 * 60 * 1000;
+
+/// Returns the key under which the block time of the deploy's execution is recorded in global
+/// state, used to check that a deploy's dependencies have been executed.
+pub fn executed_deploy_key(deploy_hash: &DeployHash) -> Key {
+    Key::ExecutedDeploy(*deploy_hash)
+}
+
 /// Represents a deploy to be executed.  Corresponds to the similarly-named ipc protobuf message.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct DeployItem {
@@ -15,10 +25,19 @@ pub struct DeployItem {
     pub gas_price: GasPrice,
     pub authorization_keys: BTreeSet<AccountHash>,
     pub deploy_hash: DeployHash,
+    /// The time the deploy was created, in milliseconds.
+    pub timestamp: u64,
+    /// How long after `timestamp` the deploy may still be executed, in milliseconds.  Zero means
+    /// the deploy never expires.
+    pub ttl_millis: u32,
+    /// Hashes of deploys which must have been executed before this one.
+    pub dependencies: Vec<DeployHash>,
+    /// Must equal the account's nonce if replay protection is enabled for it; ignored otherwise.
+    pub nonce: u64,
 }
 
 impl DeployItem {
-    /// Creates a [`DeployItem`].
+    /// Creates a [`DeployItem`] which never expires and has no dependencies.
     pub fn new(
         address: AccountHash,
         session: ExecutableDeployItem,
@@ -34,6 +53,19 @@ impl DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            timestamp: 0,
+            ttl_millis: 0,
+            dependencies: Vec::new(),
+            nonce: 0,
+        }
+    }
+
+    /// Returns the block time after which the deploy may no longer be executed, if it expires.
+    pub fn expiry(&self) -> Option<u64> {
+        if self.ttl_millis == 0 {
+            None
+        } else {
+            Some(self.timestamp.saturating_add(self.ttl_millis.into()))
         }
     }
 }
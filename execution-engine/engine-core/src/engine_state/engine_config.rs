@@ -1,9 +1,42 @@
+use engine_wasm_prep::validation::WasmValidationConfig;
+
+/// Default maximum depth of nested contract calls.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 32;
+
+/// Default maximum number of preprocessed Wasm modules kept in the module cache.
+pub const DEFAULT_MODULE_CACHE_MAX_ENTRIES: usize = 256;
+
+/// Default maximum total size, in bytes of their original Wasm, of the modules kept in the module
+/// cache.
+pub const DEFAULT_MODULE_CACHE_MAX_SIZE: usize = 64 * 1024 * 1024;
+
 /// The runtime configuration of the execution engine
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone)]
 pub struct EngineConfig {
     // feature flags go here
     use_system_contracts: bool,
     enable_bonding: bool,
+    max_call_depth: usize,
+    wasm_validation_config: WasmValidationConfig,
+    module_cache_max_entries: usize,
+    module_cache_max_size: usize,
+    enable_execution_trace: bool,
+    enable_contract_debug: bool,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            use_system_contracts: false,
+            enable_bonding: false,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            wasm_validation_config: WasmValidationConfig::default(),
+            module_cache_max_entries: DEFAULT_MODULE_CACHE_MAX_ENTRIES,
+            module_cache_max_size: DEFAULT_MODULE_CACHE_MAX_SIZE,
+            enable_execution_trace: false,
+            enable_contract_debug: false,
+        }
+    }
 }
 
 impl EngineConfig {
@@ -29,4 +62,87 @@ impl EngineConfig {
         self.enable_bonding = enable_bonding;
         self
     }
+
+    /// Returns the maximum depth of nested contract calls, where session code runs at depth zero.
+    pub fn max_call_depth(self) -> usize {
+        self.max_call_depth
+    }
+
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> EngineConfig {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Returns the limits Wasm modules are validated against before execution.
+    pub fn wasm_validation_config(self) -> WasmValidationConfig {
+        self.wasm_validation_config
+    }
+
+    pub fn with_wasm_validation_config(
+        mut self,
+        wasm_validation_config: WasmValidationConfig,
+    ) -> EngineConfig {
+        self.wasm_validation_config = wasm_validation_config;
+        self
+    }
+
+    /// Returns the maximum number of 64 KiB pages a module's linear memory may be declared, and so
+    /// allocated or grown, with.
+    pub fn max_memory_pages(self) -> u32 {
+        self.wasm_validation_config.max_memory_pages()
+    }
+
+    pub fn with_max_memory_pages(mut self, max_memory_pages: u32) -> EngineConfig {
+        self.wasm_validation_config = self
+            .wasm_validation_config
+            .with_max_memory_pages(max_memory_pages);
+        self
+    }
+
+    /// Returns the maximum number of preprocessed Wasm modules kept in the module cache.  Zero
+    /// disables the cache.
+    pub fn module_cache_max_entries(self) -> usize {
+        self.module_cache_max_entries
+    }
+
+    pub fn with_module_cache_max_entries(
+        mut self,
+        module_cache_max_entries: usize,
+    ) -> EngineConfig {
+        self.module_cache_max_entries = module_cache_max_entries;
+        self
+    }
+
+    /// Returns the maximum total size, in bytes of their original Wasm, of the modules kept in the
+    /// module cache.
+    pub fn module_cache_max_size(self) -> usize {
+        self.module_cache_max_size
+    }
+
+    pub fn with_module_cache_max_size(mut self, module_cache_max_size: usize) -> EngineConfig {
+        self.module_cache_max_size = module_cache_max_size;
+        self
+    }
+
+    /// Returns `true` if the host functions invoked by Wasm are recorded in the trace of each
+    /// `ExecutionResult`.
+    pub fn enable_execution_trace(self) -> bool {
+        self.enable_execution_trace
+    }
+
+    pub fn with_enable_execution_trace(mut self, enable_execution_trace: bool) -> EngineConfig {
+        self.enable_execution_trace = enable_execution_trace;
+        self
+    }
+
+    /// Returns `true` if text passed to the `print` host function is written to the engine log.
+    /// Otherwise it is discarded, though the call is still charged for.
+    pub fn enable_contract_debug(self) -> bool {
+        self.enable_contract_debug
+    }
+
+    pub fn with_enable_contract_debug(mut self, enable_contract_debug: bool) -> EngineConfig {
+        self.enable_contract_debug = enable_contract_debug;
+        self
+    }
 }
@@ -0,0 +1,86 @@
+use std::{collections::HashMap, fmt};
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use types::{account::AccountHash, bytesrepr, BlockTime, Key, ProtocolVersion, U512};
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+pub enum EraEndResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+        /// The stakes of the validators bonded for the next era.
+        bonded_validators: HashMap<AccountHash, U512>,
+    },
+}
+
+impl fmt::Display for EraEndResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            EraEndResult::RootNotFound => write!(f, "Root not found"),
+            EraEndResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            EraEndResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            EraEndResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            EraEndResult::Success {
+                post_state_hash,
+                effect,
+                bonded_validators,
+            } => write!(
+                f,
+                "Success: {} {:?} {:?}",
+                post_state_hash, effect, bonded_validators
+            ),
+        }
+    }
+}
+
+/// A request to run the proof-of-stake bookkeeping due at the end of an era: distributing the
+/// era's rewards and processing the matured bonding and unbonding requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EraEndRequest {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    era_id: u64,
+    blocktime: BlockTime,
+}
+
+impl EraEndRequest {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        era_id: u64,
+        blocktime: BlockTime,
+    ) -> Self {
+        EraEndRequest {
+            pre_state_hash,
+            protocol_version,
+            era_id,
+            blocktime,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Returns the identifier of the era which is ending.
+    pub fn era_id(&self) -> u64 {
+        self.era_id
+    }
+
+    /// Returns the block time of the era boundary, against which the bonding and unbonding
+    /// requests' delays are checked.
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+}
@@ -19,6 +19,8 @@ pub enum Error {
     InvalidProtocolVersion(ProtocolVersion),
     #[fail(display = "Invalid upgrade config")]
     InvalidUpgradeConfig,
+    #[fail(display = "Invalid gas price: must be greater than zero")]
+    InvalidGasPrice,
     #[fail(display = "Wasm preprocessing error: {}", _0)]
     WasmPreprocessing(engine_wasm_prep::PreprocessingError),
     #[fail(display = "Wasm serialization error: {:?}", _0)]
@@ -47,6 +49,12 @@ pub enum Error {
     InvalidUpgradeResult,
     #[fail(display = "Unsupported deploy item variant: {}", _0)]
     InvalidDeployItemVariant(String),
+    #[fail(display = "Deploy expired at {} before block time {}", _0, _1)]
+    DeployExpired { expiry: u64, block_time: u64 },
+    #[fail(display = "Deploy dependency not executed: {}", _0)]
+    MissingDependency(Blake2bHash),
+    #[fail(display = "Invalid deploy nonce: expected {}, actual {}", _0, _1)]
+    InvalidNonce { expected: u64, actual: u64 },
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
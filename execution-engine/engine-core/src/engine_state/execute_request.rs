@@ -11,6 +11,13 @@ pub struct ExecuteRequest {
     pub block_time: u64,
     pub deploys: Vec<Result<DeployItem, ExecutionResult>>,
     pub protocol_version: ProtocolVersion,
+    /// Whether to record the host functions invoked by each deploy, regardless of the
+    /// `EngineConfig`.
+    pub enable_execution_trace: bool,
+    /// Whether to run each deploy's session code with a gas limit bounded only by the account's
+    /// main purse balance, so that its exact cost can be reported along with a
+    /// [`GasBreakdown`](super::execution_result::GasBreakdown).  Implies execution tracing.
+    pub estimate_gas: bool,
 }
 
 impl ExecuteRequest {
@@ -25,6 +32,8 @@ impl ExecuteRequest {
             block_time,
             deploys,
             protocol_version,
+            enable_execution_trace: false,
+            estimate_gas: false,
         }
     }
 
@@ -40,6 +49,8 @@ impl Default for ExecuteRequest {
             block_time: 0,
             deploys: vec![],
             protocol_version: Default::default(),
+            enable_execution_trace: false,
+            estimate_gas: false,
         }
     }
 }
@@ -1,16 +1,46 @@
 use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-use types::Key;
+use types::{CLValue, Key};
 
 use super::op::Op;
 
+/// A structured event emitted by a contract during execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractEvent {
+    /// The key of the account or contract which emitted the event.
+    pub emitter: Key,
+    pub name: String,
+    pub data: CLValue,
+}
+
+impl ContractEvent {
+    pub fn new(emitter: Key, name: String, data: CLValue) -> Self {
+        ContractEvent {
+            emitter,
+            name,
+            data,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ExecutionEffect {
     pub ops: AdditiveMap<Key, Op>,
     pub transforms: AdditiveMap<Key, Transform>,
+    /// Events emitted during execution, in emission order.
+    pub events: Vec<ContractEvent>,
 }
 
 impl ExecutionEffect {
     pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
-        ExecutionEffect { ops, transforms }
+        ExecutionEffect {
+            ops,
+            transforms,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn with_events(mut self, events: Vec<ContractEvent>) -> Self {
+        self.events = events;
+        self
     }
 }
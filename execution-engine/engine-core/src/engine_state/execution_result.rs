@@ -1,10 +1,28 @@
-use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use std::cmp;
+
+use super::{
+    deploy_item::executed_deploy_key,
+    error,
+    execution_effect::{ContractEvent, ExecutionEffect},
+    op::Op,
+};
+use crate::{
+    execution::{self, HostCall},
+    resolvers::v1_function_index::FunctionIndex,
+    DeployHash,
+};
 use engine_shared::{
-    additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
-    stored_value::StoredValue, transform::Transform,
+    account::Account,
+    additive_map::AdditiveMap,
+    gas::Gas,
+    motes::Motes,
+    newtypes::CorrelationId,
+    stored_value::StoredValue,
+    transform::{self, Transform},
+    TypeMismatch,
 };
 use engine_storage::global_state::StateReader;
-use types::{bytesrepr::FromBytes, CLTyped, CLValue, Key};
+use types::{bytesrepr::FromBytes, CLTyped, CLValue, Key, Phase};
 
 fn make_payment_error_effects(
     max_payment_cost: Motes,
@@ -45,9 +63,34 @@ pub enum ExecutionResult {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        trace: Vec<HostCall>,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        trace: Vec<HostCall>,
+    },
+}
+
+/// The cost of a deploy, split between the gas charged for the Wasm opcodes it executed and that
+/// charged for everything else, chiefly host function calls.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GasBreakdown {
+    opcodes: Gas,
+    host_functions: Gas,
+}
+
+impl GasBreakdown {
+    /// Returns the gas charged for executing Wasm opcodes.
+    pub fn opcodes(&self) -> Gas {
+        self.opcodes
+    }
+
+    /// Returns the gas charged for host function calls.
+    pub fn host_functions(&self) -> Gas {
+        self.host_functions
+    }
 }
 
 pub enum ForcedTransferResult {
@@ -66,6 +109,7 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            trace: Vec::new(),
         }
     }
 
@@ -106,25 +150,139 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the host functions invoked by Wasm during execution, in the order they were
+    /// invoked.  Empty unless execution tracing is enabled in the `EngineConfig`.
+    pub fn trace(&self) -> &[HostCall] {
+        match self {
+            ExecutionResult::Failure { trace, .. } => trace,
+            ExecutionResult::Success { trace, .. } => trace,
+        }
+    }
+
+    /// Splits [`ExecutionResult::cost`] between Wasm opcodes and host functions.
+    ///
+    /// Opcode gas is found in the trace, so unless execution tracing was enabled the whole cost is
+    /// attributed to host functions.  Finalization isn't paid for by the deploy, so is excluded.
+    pub fn gas_breakdown(&self) -> GasBreakdown {
+        let opcodes = self
+            .trace()
+            .iter()
+            .filter(|host_call| {
+                host_call.function() == FunctionIndex::GasFuncIndex
+                    && host_call.phase() != Phase::FinalizePayment
+            })
+            .fold(Gas::default(), |opcodes, host_call| {
+                opcodes + host_call.gas()
+            });
+        let cost = self.cost();
+        let opcodes = cmp::min(opcodes, cost);
+        GasBreakdown {
+            opcodes,
+            host_functions: cost - opcodes,
+        }
+    }
+
     pub fn with_cost(self, cost: Gas) -> Self {
         match self {
-            ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                trace,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                trace,
+            },
+            ExecutionResult::Success { effect, trace, .. } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
     pub fn with_effect(self, effect: ExecutionEffect) -> Self {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error, cost, trace, ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                trace,
+            },
+            ExecutionResult::Success { cost, trace, .. } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+            },
+        }
+    }
+
+    /// Adds a write of `block_time` under the deploy's [`executed_deploy_key`] to the effect,
+    /// recording that the deploy has been executed.
+    pub fn with_executed_deploy(self, deploy_hash: &DeployHash, block_time: u64) -> Self {
+        let key = executed_deploy_key(deploy_hash);
+        // from_t for u64 is assumed to never panic
+        let value = StoredValue::CLValue(CLValue::from_t(block_time).unwrap());
+        let mut effect = self.effect().clone();
+        effect.ops.insert(key, Op::Write);
+        effect.transforms.insert(key, Transform::Write(value));
+        self.with_effect(effect)
+    }
+
+    /// Adds a write of the account with its nonce incremented to the effect, on top of any changes
+    /// execution made to the account.  `account` is the account as it was before execution.
+    pub fn with_incremented_nonce(self, account: Account) -> Result<Self, execution::Error> {
+        let key = Key::Account(account.account_hash());
+        let mut effect = self.effect().clone();
+        let value = match effect.transforms.get(&key) {
+            Some(transform) => transform
+                .clone()
+                .apply(StoredValue::Account(account))
+                .map_err(|error| match error {
+                    transform::Error::Serialization(error) => execution::Error::BytesRepr(error),
+                    transform::Error::TypeMismatch(error) => execution::Error::TypeMismatch(error),
+                })?,
+            None => StoredValue::Account(account),
+        };
+        let mut account = match value {
+            StoredValue::Account(account) => account,
+            other => {
+                return Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                    "Account".to_string(),
+                    other.type_name(),
+                )))
+            }
+        };
+        account.increment_nonce();
+        effect.ops.insert(key, Op::Write);
+        effect
+            .transforms
+            .insert(key, Transform::Write(StoredValue::Account(account)));
+        Ok(self.with_effect(effect))
+    }
+
+    pub fn with_trace(self, trace: Vec<HostCall>) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                trace,
+            },
+            ExecutionResult::Success { effect, cost, .. } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
@@ -147,10 +305,11 @@ impl ExecutionResult {
     pub fn check_forced_transfer(
         &self,
         payment_purse_balance: Motes,
+        gas_price: u64,
     ) -> Option<ForcedTransferResult> {
-        let payment_result_cost = match Motes::from_gas(self.cost(), CONV_RATE) {
+        let payment_result_cost = match Motes::from_gas(self.cost(), gas_price) {
             Some(cost) => cost,
-            // Multiplying cost by CONV_RATE overflowed the U512 range
+            // Multiplying cost by gas price overflowed the U512 range
             None => return Some(ForcedTransferResult::InsufficientPayment),
         };
         // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during
@@ -176,6 +335,7 @@ impl ExecutionResult {
     pub fn new_payment_code_error(
         error: error::Error,
         max_payment_cost: Motes,
+        gas_price: u64,
         account_main_purse_balance: Motes,
         account_main_purse: Key,
         rewards_purse: Key,
@@ -186,11 +346,12 @@ impl ExecutionResult {
             account_main_purse,
             rewards_purse,
         );
-        let cost = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        let cost = Gas::from_motes(max_payment_cost, gas_price).unwrap_or_default();
         ExecutionResult::Failure {
             error,
             effect,
             cost,
+            trace: Vec::new(),
         }
     }
 
@@ -274,10 +435,14 @@ impl ExecutionResultBuilder {
         let cost = self.total_cost();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
+        let mut events = Vec::new();
+
+        let mut trace = Vec::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            trace: Vec::new(),
         };
 
         match self.payment_execution_result {
@@ -285,7 +450,8 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     return Ok(result);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
+                    trace.extend_from_slice(result.trace());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -295,10 +461,11 @@ impl ExecutionResultBuilder {
         // exec error
         match self.session_execution_result {
             Some(result) => {
+                trace.extend_from_slice(result.trace());
                 if result.is_failure() {
                     ret = result.with_cost(cost);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
@@ -312,21 +479,24 @@ impl ExecutionResultBuilder {
                         error::Error::Finalization,
                     ));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
+                    trace.extend_from_slice(result.trace());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
         }
 
         // Remove redundant writes to allow more opportunity to commute
-        let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
+        let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id)
+            .with_events(events);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret.with_effect(reduced_effect).with_trace(trace))
     }
 
     fn add_effects(
         ops: &mut AdditiveMap<Key, Op>,
         transforms: &mut AdditiveMap<Key, Transform>,
+        events: &mut Vec<ContractEvent>,
         effect: &ExecutionEffect,
     ) {
         for (k, op) in effect.ops.iter() {
@@ -335,6 +505,7 @@ impl ExecutionResultBuilder {
         for (k, t) in effect.transforms.iter() {
             transforms.insert_add(*k, t.clone())
         }
+        events.extend(effect.events.iter().cloned());
     }
 
     /// In the case we are writing the same value as was there originally,
@@ -1,4 +1,4 @@
-use std::{fmt, iter};
+use std::{fmt, iter, time::Duration};
 
 use num_traits::Zero;
 use rand::{
@@ -6,17 +6,42 @@ use rand::{
     Rng,
 };
 
-use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
-use engine_storage::global_state::CommitResult;
+use engine_shared::{
+    host_function_costs::{HostFunctionCosts, HOST_FUNCTION_COSTS_SERIALIZED_LENGTH},
+    motes::Motes,
+    newtypes::Blake2bHash,
+    TypeMismatch,
+};
+use engine_storage::{
+    global_state::CommitResult,
+    protocol_data::{
+        DEFAULT_MAX_EFFECTS_SIZE, DEFAULT_MAX_NAMED_KEY_LENGTH, DEFAULT_MAX_VALUE_SIZE,
+        DEFAULT_TRANSFER_COST,
+    },
+};
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
+use types::{account::AccountHash, bytesrepr, BlockTime, Key, ProtocolVersion, U512};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
 
+mod builder;
+mod config_toml;
+
+pub use ::mint::VestingSchedule;
+pub use builder::GenesisConfigBuilder;
+pub use config_toml::GenesisConfigTomlError;
+
 pub const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 pub const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+pub const POS_INFLATION_RATE: &str = "pos_inflation_rate";
+pub const POS_SLASH_RATE: &str = "pos_slash_rate";
+pub const POS_UNBONDING_DELAY: &str = "pos_unbonding_delay";
+pub const POS_STAKES: &str = "pos_stakes";
+pub const POS_MIN_BOND: &str = "pos_min_bond";
+pub const POS_MAX_BOND: &str = "pos_max_bond";
+pub const POS_MAX_VALIDATOR_SLOTS: &str = "pos_max_validator_slots";
 
 pub enum GenesisResult {
     RootNotFound,
@@ -66,6 +91,7 @@ pub struct GenesisAccount {
     account_hash: AccountHash,
     balance: Motes,
     bonded_amount: Motes,
+    vesting_schedule: Option<VestingSchedule>,
 }
 
 impl GenesisAccount {
@@ -74,9 +100,16 @@ impl GenesisAccount {
             account_hash,
             balance,
             bonded_amount,
+            vesting_schedule: None,
         }
     }
 
+    /// Locks part of the account's balance in its main purse according to `vesting_schedule`.
+    pub fn with_vesting_schedule(mut self, vesting_schedule: VestingSchedule) -> Self {
+        self.vesting_schedule = Some(vesting_schedule);
+        self
+    }
+
     pub fn account_hash(&self) -> AccountHash {
         self.account_hash
     }
@@ -88,6 +121,10 @@ impl GenesisAccount {
     pub fn bonded_amount(&self) -> Motes {
         self.bonded_amount
     }
+
+    pub fn vesting_schedule(&self) -> Option<VestingSchedule> {
+        self.vesting_schedule
+    }
 }
 
 impl Distribution<GenesisAccount> for Standard {
@@ -101,10 +138,23 @@ impl Distribution<GenesisAccount> for Standard {
         rng.fill_bytes(u512_array.as_mut());
         let bonded_amount = Motes::new(U512::from(u512_array.as_ref()));
 
+        let vesting_schedule = if rng.gen() {
+            rng.fill_bytes(u512_array.as_mut());
+            Some(VestingSchedule::new(
+                BlockTime::new(rng.gen()),
+                Duration::from_millis(rng.gen()),
+                Duration::from_millis(rng.gen()),
+                U512::from(u512_array.as_ref()),
+            ))
+        } else {
+            None
+        };
+
         GenesisAccount {
             account_hash,
             balance,
             bonded_amount,
+            vesting_schedule,
         }
     }
 }
@@ -188,15 +238,33 @@ pub struct ExecConfig {
     standard_payment_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    host_function_costs: HostFunctionCosts,
+    transfer_cost: u64,
+    max_effects_size: u64,
+    max_value_size: u64,
+    max_named_key_length: u64,
+    gas_price: u64,
+    inflation_rate: u64,
+    slash_rate: u64,
+    unbonding_delay: u64,
+    nonces_enabled: bool,
+    min_bond: u64,
+    max_bond: u64,
+    max_validator_slots: u32,
 }
 
 impl ExecConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         mint_installer_bytes: Vec<u8>,
         proof_of_stake_installer_bytes: Vec<u8>,
         standard_payment_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        gas_price: u64,
+        inflation_rate: u64,
+        slash_rate: u64,
+        unbonding_delay: u64,
     ) -> ExecConfig {
         ExecConfig {
             mint_installer_bytes,
@@ -204,6 +272,19 @@ impl ExecConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs: HostFunctionCosts::default(),
+            transfer_cost: DEFAULT_TRANSFER_COST,
+            max_effects_size: DEFAULT_MAX_EFFECTS_SIZE,
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+            max_named_key_length: DEFAULT_MAX_NAMED_KEY_LENGTH,
+            gas_price,
+            inflation_rate,
+            slash_rate,
+            unbonding_delay,
+            nonces_enabled: false,
+            min_bond: 0,
+            max_bond: 0,
+            max_validator_slots: 0,
         }
     }
     pub fn mint_installer_bytes(&self) -> &[u8] {
@@ -222,6 +303,115 @@ impl ExecConfig {
         self.wasm_costs
     }
 
+    /// Returns the costs of the host functions at genesis.
+    pub fn host_function_costs(&self) -> HostFunctionCosts {
+        self.host_function_costs
+    }
+
+    pub fn set_host_function_costs(&mut self, host_function_costs: HostFunctionCosts) {
+        self.host_function_costs = host_function_costs;
+    }
+
+    /// Returns the gas charged for a native transfer at genesis.
+    pub fn transfer_cost(&self) -> u64 {
+        self.transfer_cost
+    }
+
+    pub fn set_transfer_cost(&mut self, transfer_cost: u64) {
+        self.transfer_cost = transfer_cost;
+    }
+
+    /// Returns the maximum total size, in bytes, of the transforms a deploy may produce at
+    /// genesis.
+    pub fn max_effects_size(&self) -> u64 {
+        self.max_effects_size
+    }
+
+    pub fn set_max_effects_size(&mut self, max_effects_size: u64) {
+        self.max_effects_size = max_effects_size;
+    }
+
+    /// Returns the maximum size, in bytes of its serialized form, of a value a contract may write
+    /// at genesis.
+    pub fn max_value_size(&self) -> u64 {
+        self.max_value_size
+    }
+
+    pub fn set_max_value_size(&mut self, max_value_size: u64) {
+        self.max_value_size = max_value_size;
+    }
+
+    /// Returns the maximum length, in bytes, of the name a contract may give a named key at
+    /// genesis.
+    pub fn max_named_key_length(&self) -> u64 {
+        self.max_named_key_length
+    }
+
+    pub fn set_max_named_key_length(&mut self, max_named_key_length: u64) {
+        self.max_named_key_length = max_named_key_length;
+    }
+
+    /// Returns the gas price at genesis, i.e. the number of motes paid per unit of gas.
+    pub fn gas_price(&self) -> u64 {
+        self.gas_price
+    }
+
+    /// Returns the inflation rate, in millionths of the bonded stakes, minted as validator rewards
+    /// per era.
+    pub fn inflation_rate(&self) -> u64 {
+        self.inflation_rate
+    }
+
+    /// Returns the slash rate, in millionths of an equivocating validator's stake, burned when it
+    /// is slashed.
+    pub fn slash_rate(&self) -> u64 {
+        self.slash_rate
+    }
+
+    /// Returns the time, in block time units, an unbonding request has to wait before its funds
+    /// can be withdrawn.
+    pub fn unbonding_delay(&self) -> u64 {
+        self.unbonding_delay
+    }
+
+    /// Returns whether accounts carry a nonce which each of their deploys must match, protecting
+    /// them against replayed deploys.
+    pub fn nonces_enabled(&self) -> bool {
+        self.nonces_enabled
+    }
+
+    pub fn set_nonces_enabled(&mut self, nonces_enabled: bool) {
+        self.nonces_enabled = nonces_enabled;
+    }
+
+    /// Returns the minimum stake, in motes, a validator has to hold after bonding.
+    pub fn min_bond(&self) -> u64 {
+        self.min_bond
+    }
+
+    pub fn set_min_bond(&mut self, min_bond: u64) {
+        self.min_bond = min_bond;
+    }
+
+    /// Returns the maximum stake, in motes, a validator may hold after bonding. Zero means there
+    /// is no maximum.
+    pub fn max_bond(&self) -> u64 {
+        self.max_bond
+    }
+
+    pub fn set_max_bond(&mut self, max_bond: u64) {
+        self.max_bond = max_bond;
+    }
+
+    /// Returns the maximum number of validators. Zero means there is no limit.
+    pub fn max_validator_slots(&self) -> u32 {
+        self.max_validator_slots
+    }
+
+    pub fn set_max_validator_slots(&mut self, max_validator_slots: u32) {
+        self.max_validator_slots = max_validator_slots;
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (AccountHash, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -272,14 +462,48 @@ impl Distribution<ExecConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            verify_ed25519: rng.gen(),
+            verify_secp256k1: rng.gen(),
         };
 
+        let host_function_costs = {
+            let mut bytes = vec![0u8; HOST_FUNCTION_COSTS_SERIALIZED_LENGTH];
+            rng.fill(bytes.as_mut_slice());
+            bytesrepr::deserialize(bytes).expect("should deserialize host function costs")
+        };
+
+        let transfer_cost = rng.gen();
+        let max_effects_size = rng.gen();
+        let max_value_size = rng.gen();
+        let max_named_key_length = rng.gen();
+        let gas_price = rng.gen_range(1, 100);
+        let inflation_rate = rng.gen();
+        let slash_rate = rng.gen();
+        let unbonding_delay = rng.gen();
+        let nonces_enabled = rng.gen();
+        let min_bond = rng.gen();
+        let max_bond = rng.gen();
+        let max_validator_slots = rng.gen();
+
         ExecConfig {
             mint_installer_bytes,
             proof_of_stake_installer_bytes,
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            transfer_cost,
+            max_effects_size,
+            max_value_size,
+            max_named_key_length,
+            gas_price,
+            inflation_rate,
+            slash_rate,
+            unbonding_delay,
+            nonces_enabled,
+            min_bond,
+            max_bond,
+            max_validator_slots,
         }
     }
 }
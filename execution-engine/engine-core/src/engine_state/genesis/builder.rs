@@ -0,0 +1,220 @@
+use engine_shared::{host_function_costs::HostFunctionCosts, motes::Motes};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{account::AccountHash, ProtocolVersion};
+
+use super::{ExecConfig, GenesisAccount, GenesisConfig};
+use crate::engine_state::CONV_RATE;
+
+/// Builds a [`GenesisConfig`] one setting at a time.
+///
+/// Unless set otherwise, the chain has an empty name, a timestamp of zero, protocol version 1.0.0,
+/// no accounts, no installer Wasm, default Wasm and host function costs, a gas price of [`CONV_RATE`] and zero for
+/// every other parameter.
+#[derive(Debug, Clone)]
+pub struct GenesisConfigBuilder {
+    genesis_config: GenesisConfig,
+}
+
+impl Default for GenesisConfigBuilder {
+    fn default() -> Self {
+        let exec_config = ExecConfig::new(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            WasmCosts::default(),
+            CONV_RATE,
+            0,
+            0,
+            0,
+        );
+        let genesis_config =
+            GenesisConfig::new(String::new(), 0, ProtocolVersion::V1_0_0, exec_config);
+        GenesisConfigBuilder { genesis_config }
+    }
+}
+
+impl GenesisConfigBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_name<T: Into<String>>(mut self, name: T) -> Self {
+        self.genesis_config.name = name.into();
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.genesis_config.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.genesis_config.protocol_version = protocol_version;
+        self
+    }
+
+    /// Adds `account` to the accounts created at genesis.
+    pub fn with_account(mut self, account: GenesisAccount) -> Self {
+        self.genesis_config.ee_config.push_account(account);
+        self
+    }
+
+    /// Adds each of `accounts` to the accounts created at genesis.
+    pub fn with_accounts<I: IntoIterator<Item = GenesisAccount>>(mut self, accounts: I) -> Self {
+        self.genesis_config.ee_config.accounts.extend(accounts);
+        self
+    }
+
+    /// Adds a validator bonded with `stake` and holding no other funds.
+    pub fn with_validator(self, account_hash: AccountHash, stake: Motes) -> Self {
+        self.with_account(GenesisAccount::new(account_hash, Motes::default(), stake))
+    }
+
+    pub fn with_mint_installer_bytes(mut self, mint_installer_bytes: Vec<u8>) -> Self {
+        self.genesis_config.ee_config.mint_installer_bytes = mint_installer_bytes;
+        self
+    }
+
+    pub fn with_proof_of_stake_installer_bytes(
+        mut self,
+        proof_of_stake_installer_bytes: Vec<u8>,
+    ) -> Self {
+        self.genesis_config.ee_config.proof_of_stake_installer_bytes =
+            proof_of_stake_installer_bytes;
+        self
+    }
+
+    pub fn with_standard_payment_installer_bytes(
+        mut self,
+        standard_payment_installer_bytes: Vec<u8>,
+    ) -> Self {
+        self.genesis_config
+            .ee_config
+            .standard_payment_installer_bytes = standard_payment_installer_bytes;
+        self
+    }
+
+    pub fn with_wasm_costs(mut self, wasm_costs: WasmCosts) -> Self {
+        self.genesis_config.ee_config.wasm_costs = wasm_costs;
+        self
+    }
+
+    pub fn with_host_function_costs(mut self, host_function_costs: HostFunctionCosts) -> Self {
+        self.genesis_config.ee_config.host_function_costs = host_function_costs;
+        self
+    }
+
+    pub fn with_transfer_cost(mut self, transfer_cost: u64) -> Self {
+        self.genesis_config.ee_config.transfer_cost = transfer_cost;
+        self
+    }
+
+    pub fn with_max_effects_size(mut self, max_effects_size: u64) -> Self {
+        self.genesis_config.ee_config.max_effects_size = max_effects_size;
+        self
+    }
+
+    pub fn with_max_value_size(mut self, max_value_size: u64) -> Self {
+        self.genesis_config.ee_config.max_value_size = max_value_size;
+        self
+    }
+
+    pub fn with_max_named_key_length(mut self, max_named_key_length: u64) -> Self {
+        self.genesis_config.ee_config.max_named_key_length = max_named_key_length;
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.genesis_config.ee_config.gas_price = gas_price;
+        self
+    }
+
+    pub fn with_inflation_rate(mut self, inflation_rate: u64) -> Self {
+        self.genesis_config.ee_config.inflation_rate = inflation_rate;
+        self
+    }
+
+    pub fn with_slash_rate(mut self, slash_rate: u64) -> Self {
+        self.genesis_config.ee_config.slash_rate = slash_rate;
+        self
+    }
+
+    pub fn with_unbonding_delay(mut self, unbonding_delay: u64) -> Self {
+        self.genesis_config.ee_config.unbonding_delay = unbonding_delay;
+        self
+    }
+
+    pub fn with_nonces_enabled(mut self, nonces_enabled: bool) -> Self {
+        self.genesis_config.ee_config.nonces_enabled = nonces_enabled;
+        self
+    }
+
+    pub fn with_min_bond(mut self, min_bond: u64) -> Self {
+        self.genesis_config.ee_config.min_bond = min_bond;
+        self
+    }
+
+    pub fn with_max_bond(mut self, max_bond: u64) -> Self {
+        self.genesis_config.ee_config.max_bond = max_bond;
+        self
+    }
+
+    pub fn with_max_validator_slots(mut self, max_validator_slots: u32) -> Self {
+        self.genesis_config.ee_config.max_validator_slots = max_validator_slots;
+        self
+    }
+
+    pub fn build(self) -> GenesisConfig {
+        self.genesis_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::U512;
+
+    use super::*;
+
+    #[test]
+    fn should_build_genesis_config() {
+        let account = GenesisAccount::new(
+            AccountHash::new([1; 32]),
+            Motes::new(U512::from(100)),
+            Motes::default(),
+        );
+        let validator = AccountHash::new([2; 32]);
+        let wasm_costs = WasmCosts {
+            regular: 7,
+            ..WasmCosts::default()
+        };
+
+        let genesis_config = GenesisConfigBuilder::new()
+            .with_name("test-chain")
+            .with_timestamp(1_000)
+            .with_account(account)
+            .with_validator(validator, Motes::new(U512::from(50)))
+            .with_wasm_costs(wasm_costs)
+            .with_mint_installer_bytes(vec![1, 2, 3])
+            .with_gas_price(2)
+            .with_max_validator_slots(5)
+            .build();
+
+        assert_eq!(genesis_config.name(), "test-chain");
+        assert_eq!(genesis_config.timestamp(), 1_000);
+        assert_eq!(genesis_config.protocol_version(), ProtocolVersion::V1_0_0);
+
+        let exec_config = genesis_config.ee_config();
+        assert_eq!(exec_config.accounts().len(), 2);
+        assert_eq!(exec_config.accounts()[0], account);
+        assert_eq!(
+            exec_config.get_bonded_validators().collect::<Vec<_>>(),
+            vec![(validator, Motes::new(U512::from(50)))]
+        );
+        assert_eq!(exec_config.wasm_costs(), wasm_costs);
+        assert_eq!(exec_config.mint_installer_bytes(), &[1, 2, 3]);
+        assert!(exec_config.proof_of_stake_installer_bytes().is_empty());
+        assert_eq!(exec_config.gas_price(), 2);
+        assert_eq!(exec_config.max_validator_slots(), 5);
+    }
+}
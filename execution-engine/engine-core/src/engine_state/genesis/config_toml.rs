@@ -0,0 +1,484 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    time::Duration,
+};
+
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use engine_shared::{host_function_costs::HostFunctionCosts, motes::Motes};
+use engine_storage::protocol_data::{
+    DEFAULT_MAX_EFFECTS_SIZE, DEFAULT_MAX_NAMED_KEY_LENGTH, DEFAULT_MAX_VALUE_SIZE,
+    DEFAULT_TRANSFER_COST,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{account::AccountHash, BlockTime, ProtocolVersion, SemVer, U512};
+
+use super::{ExecConfig, GenesisAccount, GenesisConfig, VestingSchedule};
+
+/// An error converting a [`GenesisConfig`] to or from TOML.
+#[derive(Fail, Debug)]
+pub enum GenesisConfigTomlError {
+    #[fail(display = "Failed to parse genesis config TOML: {}", _0)]
+    Parse(toml::de::Error),
+    #[fail(display = "Failed to write genesis config TOML: {}", _0)]
+    Write(toml::ser::Error),
+    #[fail(display = "Invalid {} in genesis config: {}", field, message)]
+    InvalidField {
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl GenesisConfigTomlError {
+    fn invalid_field<T: ToString>(field: &'static str, message: T) -> Self {
+        GenesisConfigTomlError::InvalidField {
+            field,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl GenesisConfig {
+    /// Parses a genesis config from TOML in the form written by [`GenesisConfig::to_toml`].
+    pub fn from_toml(toml: &str) -> Result<GenesisConfig, GenesisConfigTomlError> {
+        let genesis_config_toml: GenesisConfigToml =
+            toml::from_str(toml).map_err(GenesisConfigTomlError::Parse)?;
+        genesis_config_toml.try_into()
+    }
+
+    /// Writes the genesis config as TOML.
+    ///
+    /// Installer Wasm and account hashes are written as base16 strings, and amounts of motes as
+    /// decimal strings since they may not fit in a TOML integer.
+    pub fn to_toml(&self) -> Result<String, GenesisConfigTomlError> {
+        toml::to_string(&GenesisConfigToml::from(self)).map_err(GenesisConfigTomlError::Write)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisConfigToml {
+    name: String,
+    timestamp: u64,
+    protocol_version: String,
+    exec_config: ExecConfigToml,
+}
+
+// Tables have to follow plain values when serialized, so `wasm_costs`, `host_function_costs` and
+// `accounts` come last.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExecConfigToml {
+    mint_installer_bytes: String,
+    proof_of_stake_installer_bytes: String,
+    standard_payment_installer_bytes: String,
+    gas_price: u64,
+    #[serde(default = "default_transfer_cost")]
+    transfer_cost: u64,
+    #[serde(default = "default_max_effects_size")]
+    max_effects_size: u64,
+    #[serde(default = "default_max_value_size")]
+    max_value_size: u64,
+    #[serde(default = "default_max_named_key_length")]
+    max_named_key_length: u64,
+    inflation_rate: u64,
+    slash_rate: u64,
+    unbonding_delay: u64,
+    #[serde(default)]
+    nonces_enabled: bool,
+    #[serde(default)]
+    min_bond: u64,
+    #[serde(default)]
+    max_bond: u64,
+    #[serde(default)]
+    max_validator_slots: u32,
+    wasm_costs: WasmCostsToml,
+    #[serde(default)]
+    host_function_costs: HostFunctionCosts,
+    #[serde(default)]
+    accounts: Vec<GenesisAccountToml>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WasmCostsToml {
+    regular: u32,
+    div: u32,
+    mul: u32,
+    mem: u32,
+    initial_mem: u32,
+    grow_mem: u32,
+    memcpy: u32,
+    max_stack_height: u32,
+    opcodes_mul: u32,
+    opcodes_div: u32,
+    verify_ed25519: u32,
+    verify_secp256k1: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisAccountToml {
+    account_hash: String,
+    balance: String,
+    #[serde(default = "zero_motes")]
+    bonded_amount: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vesting_schedule: Option<VestingScheduleToml>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VestingScheduleToml {
+    start_millis: u64,
+    cliff_millis: u64,
+    duration_millis: u64,
+    locked_amount: String,
+}
+
+fn zero_motes() -> String {
+    U512::zero().to_string()
+}
+
+fn default_transfer_cost() -> u64 {
+    DEFAULT_TRANSFER_COST
+}
+
+fn default_max_effects_size() -> u64 {
+    DEFAULT_MAX_EFFECTS_SIZE
+}
+
+fn default_max_value_size() -> u64 {
+    DEFAULT_MAX_VALUE_SIZE
+}
+
+fn default_max_named_key_length() -> u64 {
+    DEFAULT_MAX_NAMED_KEY_LENGTH
+}
+
+impl From<&GenesisConfig> for GenesisConfigToml {
+    fn from(genesis_config: &GenesisConfig) -> Self {
+        GenesisConfigToml {
+            name: genesis_config.name.clone(),
+            timestamp: genesis_config.timestamp,
+            protocol_version: genesis_config.protocol_version.to_string(),
+            exec_config: ExecConfigToml::from(&genesis_config.ee_config),
+        }
+    }
+}
+
+impl TryFrom<GenesisConfigToml> for GenesisConfig {
+    type Error = GenesisConfigTomlError;
+
+    fn try_from(genesis_config_toml: GenesisConfigToml) -> Result<Self, Self::Error> {
+        let protocol_version = SemVer::try_from(genesis_config_toml.protocol_version.as_str())
+            .map(ProtocolVersion::new)
+            .map_err(|error| GenesisConfigTomlError::invalid_field("protocol_version", error))?;
+        Ok(GenesisConfig::new(
+            genesis_config_toml.name,
+            genesis_config_toml.timestamp,
+            protocol_version,
+            genesis_config_toml.exec_config.try_into()?,
+        ))
+    }
+}
+
+impl From<&ExecConfig> for ExecConfigToml {
+    fn from(exec_config: &ExecConfig) -> Self {
+        ExecConfigToml {
+            mint_installer_bytes: base16::encode_lower(&exec_config.mint_installer_bytes),
+            proof_of_stake_installer_bytes: base16::encode_lower(
+                &exec_config.proof_of_stake_installer_bytes,
+            ),
+            standard_payment_installer_bytes: base16::encode_lower(
+                &exec_config.standard_payment_installer_bytes,
+            ),
+            gas_price: exec_config.gas_price,
+            transfer_cost: exec_config.transfer_cost,
+            max_effects_size: exec_config.max_effects_size,
+            max_value_size: exec_config.max_value_size,
+            max_named_key_length: exec_config.max_named_key_length,
+            inflation_rate: exec_config.inflation_rate,
+            slash_rate: exec_config.slash_rate,
+            unbonding_delay: exec_config.unbonding_delay,
+            nonces_enabled: exec_config.nonces_enabled,
+            min_bond: exec_config.min_bond,
+            max_bond: exec_config.max_bond,
+            max_validator_slots: exec_config.max_validator_slots,
+            wasm_costs: exec_config.wasm_costs.into(),
+            host_function_costs: exec_config.host_function_costs,
+            accounts: exec_config.accounts.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<ExecConfigToml> for ExecConfig {
+    type Error = GenesisConfigTomlError;
+
+    fn try_from(exec_config_toml: ExecConfigToml) -> Result<Self, Self::Error> {
+        let accounts = exec_config_toml
+            .accounts
+            .into_iter()
+            .map(GenesisAccount::try_from)
+            .collect::<Result<_, _>>()?;
+        let mut exec_config = ExecConfig::new(
+            decode_bytes(
+                "mint_installer_bytes",
+                &exec_config_toml.mint_installer_bytes,
+            )?,
+            decode_bytes(
+                "proof_of_stake_installer_bytes",
+                &exec_config_toml.proof_of_stake_installer_bytes,
+            )?,
+            decode_bytes(
+                "standard_payment_installer_bytes",
+                &exec_config_toml.standard_payment_installer_bytes,
+            )?,
+            accounts,
+            exec_config_toml.wasm_costs.into(),
+            exec_config_toml.gas_price,
+            exec_config_toml.inflation_rate,
+            exec_config_toml.slash_rate,
+            exec_config_toml.unbonding_delay,
+        );
+        exec_config.set_host_function_costs(exec_config_toml.host_function_costs);
+        exec_config.set_transfer_cost(exec_config_toml.transfer_cost);
+        exec_config.set_max_effects_size(exec_config_toml.max_effects_size);
+        exec_config.set_max_value_size(exec_config_toml.max_value_size);
+        exec_config.set_max_named_key_length(exec_config_toml.max_named_key_length);
+        exec_config.set_nonces_enabled(exec_config_toml.nonces_enabled);
+        exec_config.set_min_bond(exec_config_toml.min_bond);
+        exec_config.set_max_bond(exec_config_toml.max_bond);
+        exec_config.set_max_validator_slots(exec_config_toml.max_validator_slots);
+        Ok(exec_config)
+    }
+}
+
+impl From<WasmCosts> for WasmCostsToml {
+    fn from(wasm_costs: WasmCosts) -> Self {
+        WasmCostsToml {
+            regular: wasm_costs.regular,
+            div: wasm_costs.div,
+            mul: wasm_costs.mul,
+            mem: wasm_costs.mem,
+            initial_mem: wasm_costs.initial_mem,
+            grow_mem: wasm_costs.grow_mem,
+            memcpy: wasm_costs.memcpy,
+            max_stack_height: wasm_costs.max_stack_height,
+            opcodes_mul: wasm_costs.opcodes_mul,
+            opcodes_div: wasm_costs.opcodes_div,
+            verify_ed25519: wasm_costs.verify_ed25519,
+            verify_secp256k1: wasm_costs.verify_secp256k1,
+        }
+    }
+}
+
+impl From<WasmCostsToml> for WasmCosts {
+    fn from(wasm_costs_toml: WasmCostsToml) -> Self {
+        WasmCosts {
+            regular: wasm_costs_toml.regular,
+            div: wasm_costs_toml.div,
+            mul: wasm_costs_toml.mul,
+            mem: wasm_costs_toml.mem,
+            initial_mem: wasm_costs_toml.initial_mem,
+            grow_mem: wasm_costs_toml.grow_mem,
+            memcpy: wasm_costs_toml.memcpy,
+            max_stack_height: wasm_costs_toml.max_stack_height,
+            opcodes_mul: wasm_costs_toml.opcodes_mul,
+            opcodes_div: wasm_costs_toml.opcodes_div,
+            verify_ed25519: wasm_costs_toml.verify_ed25519,
+            verify_secp256k1: wasm_costs_toml.verify_secp256k1,
+        }
+    }
+}
+
+impl From<&GenesisAccount> for GenesisAccountToml {
+    fn from(genesis_account: &GenesisAccount) -> Self {
+        GenesisAccountToml {
+            account_hash: base16::encode_lower(&genesis_account.account_hash.value()),
+            balance: genesis_account.balance.value().to_string(),
+            bonded_amount: genesis_account.bonded_amount.value().to_string(),
+            vesting_schedule: genesis_account.vesting_schedule.map(|vesting_schedule| {
+                VestingScheduleToml {
+                    start_millis: vesting_schedule.start().as_millis(),
+                    cliff_millis: vesting_schedule.cliff().as_millis() as u64,
+                    duration_millis: vesting_schedule.duration().as_millis() as u64,
+                    locked_amount: vesting_schedule.locked_amount().to_string(),
+                }
+            }),
+        }
+    }
+}
+
+impl TryFrom<GenesisAccountToml> for GenesisAccount {
+    type Error = GenesisConfigTomlError;
+
+    fn try_from(genesis_account_toml: GenesisAccountToml) -> Result<Self, Self::Error> {
+        let account_hash_bytes = decode_bytes("account_hash", &genesis_account_toml.account_hash)?;
+        let account_hash = AccountHash::try_from(account_hash_bytes.as_slice()).map_err(|_| {
+            GenesisConfigTomlError::invalid_field("account_hash", "expected 32 bytes")
+        })?;
+        let balance = decode_amount("balance", &genesis_account_toml.balance)?;
+        let bonded_amount = decode_amount("bonded_amount", &genesis_account_toml.bonded_amount)?;
+        let mut genesis_account =
+            GenesisAccount::new(account_hash, Motes::new(balance), Motes::new(bonded_amount));
+        if let Some(vesting_schedule_toml) = genesis_account_toml.vesting_schedule {
+            let locked_amount =
+                decode_amount("locked_amount", &vesting_schedule_toml.locked_amount)?;
+            genesis_account = genesis_account.with_vesting_schedule(VestingSchedule::new(
+                BlockTime::new(vesting_schedule_toml.start_millis),
+                Duration::from_millis(vesting_schedule_toml.cliff_millis),
+                Duration::from_millis(vesting_schedule_toml.duration_millis),
+                locked_amount,
+            ));
+        }
+        Ok(genesis_account)
+    }
+}
+
+fn decode_bytes(field: &'static str, base16: &str) -> Result<Vec<u8>, GenesisConfigTomlError> {
+    base16::decode(base16).map_err(|error| GenesisConfigTomlError::invalid_field(field, error))
+}
+
+fn decode_amount(field: &'static str, decimal: &str) -> Result<U512, GenesisConfigTomlError> {
+    U512::from_dec_str(decimal)
+        .map_err(|error| GenesisConfigTomlError::invalid_field(field, format!("{:?}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::host_function_costs::HostFunctionCost;
+
+    use super::*;
+    use crate::engine_state::genesis::GenesisConfigBuilder;
+
+    fn genesis_config() -> GenesisConfig {
+        let vesting_schedule = VestingSchedule::new(
+            BlockTime::new(1_000),
+            Duration::from_secs(60),
+            Duration::from_secs(3_600),
+            U512::from(500),
+        );
+        let vested_account = GenesisAccount::new(
+            AccountHash::new([1; 32]),
+            Motes::new(U512::max_value()),
+            Motes::default(),
+        )
+        .with_vesting_schedule(vesting_schedule);
+
+        GenesisConfigBuilder::new()
+            .with_name("devnet")
+            .with_timestamp(1_234)
+            .with_protocol_version(ProtocolVersion::from_parts(1, 2, 3))
+            .with_account(vested_account)
+            .with_validator(AccountHash::new([2; 32]), Motes::new(U512::from(1_000_000)))
+            .with_mint_installer_bytes(vec![0, 97, 115, 109])
+            .with_proof_of_stake_installer_bytes(vec![1, 2])
+            .with_wasm_costs(WasmCosts {
+                regular: 1,
+                verify_secp256k1: 14,
+                ..WasmCosts::default()
+            })
+            .with_host_function_costs(HostFunctionCosts {
+                put_key: HostFunctionCost::new(7, 8),
+                ..HostFunctionCosts::default()
+            })
+            .with_transfer_cost(20_000)
+            .with_max_effects_size(64 * 1024)
+            .with_max_value_size(1_024)
+            .with_max_named_key_length(16)
+            .with_inflation_rate(1_000)
+            .with_nonces_enabled(true)
+            .with_max_bond(u64::from(u32::max_value()))
+            .build()
+    }
+
+    #[test]
+    fn should_round_trip_through_toml() {
+        let genesis_config = genesis_config();
+        let toml = genesis_config.to_toml().expect("should write TOML");
+        let parsed = GenesisConfig::from_toml(&toml).expect("should parse TOML");
+        assert_eq!(parsed, genesis_config);
+    }
+
+    #[test]
+    fn should_parse_toml_without_optional_fields() {
+        let mut toml = genesis_config().to_toml().expect("should write TOML");
+        toml = toml
+            .lines()
+            .filter(|line| {
+                !line.starts_with("nonces_enabled")
+                    && !line.starts_with("max_bond")
+                    && !line.starts_with("transfer_cost")
+                    && !line.starts_with("max_effects_size")
+                    && !line.starts_with("max_value_size")
+                    && !line.starts_with("max_named_key_length")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parsed = GenesisConfig::from_toml(&toml).expect("should parse TOML");
+        assert!(!parsed.ee_config().nonces_enabled());
+        assert_eq!(parsed.ee_config().max_bond(), 0);
+        assert_eq!(parsed.ee_config().transfer_cost(), DEFAULT_TRANSFER_COST);
+        assert_eq!(
+            parsed.ee_config().max_effects_size(),
+            DEFAULT_MAX_EFFECTS_SIZE
+        );
+        assert_eq!(parsed.ee_config().max_value_size(), DEFAULT_MAX_VALUE_SIZE);
+        assert_eq!(
+            parsed.ee_config().max_named_key_length(),
+            DEFAULT_MAX_NAMED_KEY_LENGTH
+        );
+    }
+
+    #[test]
+    fn should_default_missing_host_function_costs() {
+        let mut toml = genesis_config().to_toml().expect("should write TOML");
+        let host_function_costs_start = toml
+            .find("[exec_config.host_function_costs")
+            .expect("should have host function costs");
+        let accounts_start = toml
+            .find("[[exec_config.accounts]]")
+            .expect("should have accounts");
+        toml.replace_range(
+            host_function_costs_start..accounts_start,
+            "[exec_config.host_function_costs.get_caller]\nfixed = 1\nper_byte = 2\n\n",
+        );
+
+        let parsed = GenesisConfig::from_toml(&toml).expect("should parse TOML");
+        let expected = HostFunctionCosts {
+            get_caller: HostFunctionCost::new(1, 2),
+            ..HostFunctionCosts::default()
+        };
+        assert_eq!(parsed.ee_config().host_function_costs(), expected);
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_toml() {
+        let toml = genesis_config().to_toml().expect("should write TOML");
+
+        let unknown_field = toml.replace("inflation_rate", "inflation");
+        assert!(matches!(
+            GenesisConfig::from_toml(&unknown_field),
+            Err(GenesisConfigTomlError::Parse(_))
+        ));
+
+        let bad_version = toml.replace("\"1.2.3\"", "\"1.2\"");
+        assert!(matches!(
+            GenesisConfig::from_toml(&bad_version),
+            Err(GenesisConfigTomlError::InvalidField {
+                field: "protocol_version",
+                ..
+            })
+        ));
+
+        let bad_balance = toml.replace("balance = \"0\"", "balance = \"-1\"");
+        assert!(matches!(
+            GenesisConfig::from_toml(&bad_balance),
+            Err(GenesisConfigTomlError::InvalidField {
+                field: "balance", ..
+            })
+        ));
+    }
+}
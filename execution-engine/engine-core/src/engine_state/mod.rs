@@ -1,5 +1,8 @@
+pub mod balance;
+pub mod bonding_event;
 pub mod deploy_item;
 pub mod engine_config;
+pub mod era_end;
 mod error;
 pub mod executable_deploy_item;
 pub mod execute_request;
@@ -9,14 +12,20 @@ pub mod genesis;
 pub mod op;
 pub mod query;
 pub mod run_genesis_request;
+pub mod scheduled_calls;
+pub mod slash;
+pub mod step;
 pub mod system_contract_cache;
 mod transfer;
 pub mod upgrade;
 pub mod utils;
+pub mod validator_weights;
 
 use std::{
     cell::RefCell,
+    cmp,
     collections::{BTreeMap, BTreeSet, HashMap},
+    convert::TryFrom,
     rc::Rc,
 };
 
@@ -28,26 +37,32 @@ use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
     gas::Gas,
+    logging, metrics,
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::Transform,
+    TypeMismatch,
 };
 use engine_storage::{
-    global_state::{CommitResult, StateProvider, StateReader},
+    global_state::{CommitResult, PruneResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
+    trie::Trie,
 };
 use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
 use types::{
-    account::AccountHash,
-    bytesrepr::{self, ToBytes},
-    contracts::{NamedKeys, ENTRY_POINT_NAME_INSTALL, UPGRADE_ENTRY_POINT_NAME},
+    account::{AccountHash, SYSTEM_ACCOUNT},
+    bytesrepr::{self, FromBytes, ToBytes},
+    contracts::{
+        NamedKeys, DEFAULT_ENTRY_POINT_NAME, ENTRY_POINT_NAME_INSTALL, UPGRADE_ENTRY_POINT_NAME,
+    },
     runtime_args,
+    scheduled_call::{SCHEDULER_NAME, SCHEDULER_PURSE_KEY, SCHEDULER_QUEUE_KEY},
     system_contract_errors::mint,
     system_contract_type::PROOF_OF_STAKE,
-    AccessRights, BlockTime, Contract, ContractHash, ContractPackage, ContractPackageHash,
-    ContractVersionKey, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    U512,
+    AccessRights, BlockTime, CLTyped, CLValue, CallStackElement, Contract, ContractHash,
+    ContractPackage, ContractPackageHash, ContractVersionKey, EntryPoint, EntryPointType, Key,
+    Phase, ProtocolVersion, RuntimeArgs, ScheduledCall, URef, U512,
 };
 
 pub use self::{
@@ -57,39 +72,59 @@ pub use self::{
 };
 use crate::{
     engine_state::{
-        deploy_item::DeployItem,
+        balance::{BalanceRequest, BalanceResult},
+        bonding_event::BondingEvent,
+        deploy_item::{DeployItem, EXECUTED_DEPLOY_RETENTION_MILLIS},
+        era_end::{EraEndRequest, EraEndResult},
         error::Error::MissingSystemContract,
         executable_deploy_item::ExecutableDeployItem,
         execute_request::ExecuteRequest,
+        execution_effect::ExecutionEffect,
         execution_result::{ExecutionResult, ForcedTransferResult},
         genesis::{
             ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
+            POS_STAKES,
         },
         query::{QueryRequest, QueryResult},
+        scheduled_calls::{
+            ScheduledCallsRequest, ScheduledCallsResult, MAX_SCHEDULED_CALLS_PER_BLOCK,
+        },
+        slash::{SlashConfig, SlashResult},
+        step::{StepConfig, StepResult},
         system_contract_cache::SystemContractCache,
         transfer::TransferTargetMode,
-        upgrade::{UpgradeConfig, UpgradeResult},
+        upgrade::{
+            self, Migration, MigrationRegistry, TrackingCopyMigrationContext, UpgradeConfig,
+            UpgradeResult,
+        },
+        validator_weights::{ValidatorWeightsRequest, ValidatorWeightsResult},
     },
     execution::{
         self, AddressGenerator, AddressGeneratorBuilder, DirectSystemContractCall, Executor,
     },
-    tracking_copy::{TrackingCopy, TrackingCopyExt},
+    resolvers::host_functions,
+    tracking_copy::{AddResult, BlockReadCache, TrackingCopy, TrackingCopyExt},
 };
 
 // TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
-// TBD gas * CONV_RATE = motes
 pub const MAX_PAYMENT: u64 = 10_000_000;
+/// Default gas price, i.e. the number of motes paid per unit of gas, used when a genesis config
+/// doesn't specify one.  The gas price in effect is stored in the protocol data.
 pub const CONV_RATE: u64 = 10;
 
-pub const SYSTEM_ACCOUNT_ADDR: AccountHash = AccountHash::new([0u8; 32]);
+pub const SYSTEM_ACCOUNT_ADDR: AccountHash = SYSTEM_ACCOUNT;
 
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
 const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE: &str = "purse";
+const ARG_VESTING_SCHEDULE: &str = "vesting_schedule";
 
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
+    executor: Executor,
+    migrations: MigrationRegistry,
     state: S,
 }
 
@@ -99,6 +134,7 @@ pub enum GetModuleResult {
         module: Module,
         contract_package: ContractPackage,
         entry_point: EntryPoint,
+        call_stack_element: CallStackElement,
     },
     Contract {
         // Contract hash
@@ -107,6 +143,7 @@ pub enum GetModuleResult {
         contract: Contract,
         contract_package: ContractPackage,
         entry_point: EntryPoint,
+        call_stack_element: CallStackElement,
     },
 }
 
@@ -117,6 +154,18 @@ impl GetModuleResult {
             GetModuleResult::Contract { module, .. } => module,
         }
     }
+
+    /// Returns the bottom element of the call stack when executing the module.
+    pub fn call_stack_element(&self) -> CallStackElement {
+        match self {
+            GetModuleResult::Session {
+                call_stack_element, ..
+            }
+            | GetModuleResult::Contract {
+                call_stack_element, ..
+            } => *call_stack_element,
+        }
+    }
 }
 
 impl<S> EngineState<S>
@@ -126,9 +175,13 @@ where
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
         let system_contract_cache = Default::default();
+        let executor = Executor::new(config);
+        let migrations = upgrade::builtin_migrations();
         EngineState {
             config,
             system_contract_cache,
+            executor,
+            migrations,
             state,
         }
     }
@@ -137,6 +190,10 @@ where
         &self.config
     }
 
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -147,6 +204,18 @@ where
         }
     }
 
+    /// Returns a preprocessor which instruments modules with `wasm_costs` and only accepts imports
+    /// of the host functions available under `protocol_version`.
+    pub fn preprocessor(
+        &self,
+        wasm_costs: WasmCosts,
+        protocol_version: ProtocolVersion,
+    ) -> Preprocessor {
+        Preprocessor::new(wasm_costs)
+            .with_validation_config(self.config.wasm_validation_config())
+            .with_host_functions(host_functions::names(protocol_version))
+    }
+
     pub fn get_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -166,14 +235,18 @@ where
         ee_config: &ExecConfig,
     ) -> Result<GenesisResult, Error> {
         // Preliminaries
-        let executor = Executor::new(self.config);
+        let executor = &self.executor;
         let blocktime = BlockTime::new(GENESIS_INITIAL_BLOCKTIME);
         let gas_limit = Gas::new(std::u64::MAX.into());
         let phase = Phase::System;
 
         let initial_root_hash = self.state.empty_root();
         let wasm_costs = ee_config.wasm_costs();
-        let preprocessor = Preprocessor::new(wasm_costs);
+        let gas_price = ee_config.gas_price();
+        if gas_price == 0 {
+            return Err(Error::InvalidGasPrice);
+        }
+        let preprocessor = self.preprocessor(wasm_costs, protocol_version);
 
         // Spec #3: Create "virtual system account" object.
         let mut virtual_system_account = {
@@ -274,6 +347,12 @@ where
             let args = runtime_args! {
                 "mint_contract_package_hash" => mint_package_hash,
                 "genesis_validators" => bonded_validators,
+                "inflation_rate" => ee_config.inflation_rate(),
+                "slash_rate" => ee_config.slash_rate(),
+                "unbonding_delay" => ee_config.unbonding_delay(),
+                "min_bond" => ee_config.min_bond(),
+                "max_bond" => ee_config.max_bond(),
+                "max_validator_slots" => ee_config.max_validator_slots(),
             };
             let authorization_keys: BTreeSet<AccountHash> = BTreeSet::new();
 
@@ -303,9 +382,11 @@ where
         // specification.
         let protocol_data = ProtocolData::partial_without_standard_payment(
             wasm_costs,
+            gas_price,
             mint_hash,
             proof_of_stake_hash,
-        );
+        )
+        .with_host_function_costs(ee_config.host_function_costs());
 
         let standard_payment_hash: ContractHash = {
             let standard_payment_installer_bytes =
@@ -352,15 +433,29 @@ where
         // Spec #2: Associate given CostTable with given ProtocolVersion.
         let protocol_data = ProtocolData::new(
             wasm_costs,
+            gas_price,
             mint_hash,
             proof_of_stake_hash,
             standard_payment_hash,
-        );
+        )
+        .with_nonces_enabled(ee_config.nonces_enabled())
+        .with_host_function_costs(ee_config.host_function_costs())
+        .with_transfer_cost(ee_config.transfer_cost())
+        .with_max_effects_size(ee_config.max_effects_size())
+        .with_max_value_size(ee_config.max_value_size())
+        .with_max_named_key_length(ee_config.max_named_key_length());
 
         self.state
             .put_protocol_data(protocol_version, &protocol_data)
             .map_err(Into::into)?;
 
+        // Register the system contracts by name, so that contracts can look them up without
+        // dedicated host functions.
+        tracking_copy
+            .borrow_mut()
+            .register_system_contracts(correlation_id, protocol_data.system_contract_registry())
+            .map_err(Error::Exec)?;
+
         //
         // NOTE: The following stanzas deviate from the implementation strategy described in the
         // original specification.
@@ -407,7 +502,7 @@ where
             for (account, named_keys) in accounts.into_iter() {
                 let module = module.clone();
                 let args = runtime_args! {
-                    ARG_AMOUNT => account.balance().value(),
+                    ARG_AMOUNT => account.balance(),
                 };
                 let tracking_copy_exec = Rc::clone(&tracking_copy);
                 let tracking_copy_write = Rc::clone(&tracking_copy);
@@ -427,7 +522,7 @@ where
                 };
                 let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
-                let mint_result: Result<URef, mint::Error> = {
+                let main_purse: URef = {
                     // ...call the Mint's "mint" endpoint to create purse with tokens...
                     let (_instance, mut runtime) = executor.create_runtime(
                         module,
@@ -451,7 +546,7 @@ where
                         system_contract_cache,
                     )?;
 
-                    runtime
+                    let main_purse = runtime
                         .call_versioned_contract(
                             mint_package_hash,
                             Some(1),
@@ -459,15 +554,35 @@ where
                             args,
                         )?
                         .into_t::<Result<URef, mint::Error>>()
-                        .expect("should convert")
+                        .expect("should convert")?;
+
+                    // ...lock part of it if the account has a vesting schedule...
+                    if let Some(vesting_schedule) = account.vesting_schedule() {
+                        let args = runtime_args! {
+                            ARG_PURSE => main_purse,
+                            ARG_VESTING_SCHEDULE => vesting_schedule,
+                        };
+                        runtime
+                            .call_versioned_contract(
+                                mint_package_hash,
+                                Some(1),
+                                "set_vesting_schedule".to_string(),
+                                args,
+                            )?
+                            .into_t::<Result<(), mint::Error>>()
+                            .expect("should convert")?;
+                    }
+
+                    main_purse
                 };
 
                 // ...and write that account to global state...
                 let key = Key::Account(account_hash);
-                let value = {
-                    let main_purse = mint_result?;
-                    StoredValue::Account(Account::create(account_hash, named_keys, main_purse))
-                };
+                let mut new_account = Account::create(account_hash, named_keys, main_purse);
+                if protocol_data.nonces_enabled() {
+                    new_account.set_nonce(Some(0));
+                }
+                let value = StoredValue::Account(new_account);
 
                 tracking_copy_write.borrow_mut().write(key, value);
             }
@@ -535,13 +650,51 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
-        // 3.1.2.2 persist wasm CostTable
+        let new_gas_price = match upgrade_config.gas_price() {
+            Some(0) => return Err(Error::InvalidGasPrice),
+            Some(new_gas_price) => new_gas_price,
+            None => current_protocol_data.gas_price(),
+        };
+
+        let new_host_function_costs = match upgrade_config.host_function_costs() {
+            Some(new_host_function_costs) => new_host_function_costs,
+            None => *current_protocol_data.host_function_costs(),
+        };
+
+        let new_transfer_cost = match upgrade_config.transfer_cost() {
+            Some(new_transfer_cost) => new_transfer_cost,
+            None => current_protocol_data.transfer_cost(),
+        };
+
+        let new_max_effects_size = match upgrade_config.max_effects_size() {
+            Some(new_max_effects_size) => new_max_effects_size,
+            None => current_protocol_data.max_effects_size(),
+        };
+
+        let new_max_value_size = match upgrade_config.max_value_size() {
+            Some(new_max_value_size) => new_max_value_size,
+            None => current_protocol_data.max_value_size(),
+        };
+
+        let new_max_named_key_length = match upgrade_config.max_named_key_length() {
+            Some(new_max_named_key_length) => new_max_named_key_length,
+            None => current_protocol_data.max_named_key_length(),
+        };
+
+        // 3.1.2.2 persist wasm and host function CostTables
         let mut new_protocol_data = ProtocolData::new(
             new_wasm_costs,
+            new_gas_price,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
-        );
+        )
+        .with_nonces_enabled(current_protocol_data.nonces_enabled())
+        .with_host_function_costs(new_host_function_costs)
+        .with_transfer_cost(new_transfer_cost)
+        .with_max_effects_size(new_max_effects_size)
+        .with_max_value_size(new_max_value_size)
+        .with_max_named_key_length(new_max_named_key_length);
 
         self.state
             .put_protocol_data(new_protocol_version, &new_protocol_data)
@@ -559,12 +712,6 @@ where
             Some(bytes) => {
                 // 3.1.2.3 execute upgrade installer if one is provided
 
-                // preprocess installer module
-                let upgrade_installer_module = {
-                    let preprocessor = Preprocessor::new(new_wasm_costs);
-                    preprocessor.preprocess(bytes)?
-                };
-
                 // currently there are no expected args for an upgrade installer but args are
                 // supported
                 let args = match upgrade_config.upgrade_installer_args() {
@@ -574,67 +721,15 @@ where
                     None => RuntimeArgs::new(),
                 };
 
-                // execute as system account
-                let mut system_account = {
-                    let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
-                    match tracking_copy.borrow_mut().read(correlation_id, &key) {
-                        Ok(Some(StoredValue::Account(account))) => account,
-                        Ok(_) => panic!("system account must exist"),
-                        Err(error) => return Err(Error::Exec(error.into())),
-                    }
-                };
-
-                let authorization_keys = {
-                    let mut ret = BTreeSet::new();
-                    ret.insert(SYSTEM_ACCOUNT_ADDR);
-                    ret
-                };
-
-                let blocktime = BlockTime::default();
-
-                let deploy_hash = {
-                    // seeds address generator w/ protocol version
-                    let bytes: Vec<u8> = upgrade_config
-                        .new_protocol_version()
-                        .value()
-                        .into_bytes()?
-                        .to_vec();
-                    Blake2bHash::new(&bytes).into()
-                };
-
-                // upgrade has no gas limit; approximating with MAX
-                let gas_limit = Gas::new(std::u64::MAX.into());
-                let phase = Phase::System;
-                let hash_address_generator = {
-                    let generator = AddressGenerator::new(&pre_state_hash.value(), phase);
-                    Rc::new(RefCell::new(generator))
-                };
-                let uref_address_generator = {
-                    let generator = AddressGenerator::new(&pre_state_hash.value(), phase);
-                    Rc::new(RefCell::new(generator))
-                };
-                let tracking_copy = Rc::clone(&tracking_copy);
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
-
-                let executor = Executor::new(self.config);
-
-                let result: BTreeMap<ContractHash, ContractHash> = executor.exec_wasm_direct(
-                    upgrade_installer_module,
+                let result: BTreeMap<ContractHash, ContractHash> = self.exec_upgrade_wasm(
+                    correlation_id,
+                    &tracking_copy,
+                    bytes,
                     UPGRADE_ENTRY_POINT_NAME,
                     args,
-                    &mut system_account,
-                    authorization_keys,
-                    blocktime,
-                    deploy_hash,
-                    gas_limit,
-                    hash_address_generator,
-                    uref_address_generator,
                     new_protocol_version,
-                    correlation_id,
-                    Rc::clone(&tracking_copy),
-                    phase,
                     new_protocol_data,
-                    system_contract_cache,
+                    &pre_state_hash.value(),
                 )?;
 
                 if !new_protocol_data.update_from(result) {
@@ -647,6 +742,77 @@ where
             }
         }
 
+        // Keep the registry entries of the built-in system contracts in step with any upgrades
+        // performed by the installer.  Entries for other system contracts are added by the
+        // installer itself via `system::register_system_contract`.
+        tracking_copy
+            .borrow_mut()
+            .register_system_contracts(correlation_id, new_protocol_data.system_contract_registry())
+            .map_err(Error::Exec)?;
+
+        // The migrations registered for the versions being moved past run first, followed by
+        // those bundled with this upgrade.
+        let migrations = self
+            .migrations
+            .migrations(current_protocol_version, new_protocol_version)
+            .chain(
+                upgrade_config
+                    .migrations()
+                    .iter()
+                    .map(|migration| (new_protocol_version, migration)),
+            );
+        for (protocol_version, migration) in migrations {
+            let marker_key = migration.marker_key(protocol_version);
+            let already_migrated = tracking_copy
+                .borrow_mut()
+                .read(correlation_id, &marker_key)
+                .map_err(|error| Error::Exec(error.into()))?
+                .is_some();
+            if already_migrated {
+                continue;
+            }
+
+            // Addresses created by a migration are generated from a seed of its own, so that they
+            // can't collide with those generated by the installer or by other migrations.
+            let mut address_seed = pre_state_hash.value().to_vec();
+            address_seed.extend_from_slice(migration.name().as_bytes());
+
+            match migration {
+                Migration::Wasm {
+                    module_bytes,
+                    args,
+                    ..
+                } => {
+                    self.exec_upgrade_wasm::<()>(
+                        correlation_id,
+                        &tracking_copy,
+                        module_bytes,
+                        DEFAULT_ENTRY_POINT_NAME,
+                        args.clone(),
+                        new_protocol_version,
+                        new_protocol_data,
+                        &address_seed,
+                    )?;
+                }
+                Migration::Native { migrate, .. } => {
+                    let mut tracking_copy = tracking_copy.borrow_mut();
+                    let mut context = TrackingCopyMigrationContext::new(
+                        correlation_id,
+                        &mut *tracking_copy,
+                        &new_protocol_data,
+                        &address_seed,
+                    );
+                    migrate(&mut context).map_err(Error::Exec)?;
+                }
+            }
+
+            let marker = CLValue::from_t(migration.name().to_string())
+                .map_err(|error| Error::Exec(error.into()))?;
+            tracking_copy
+                .borrow_mut()
+                .write(marker_key, StoredValue::CLValue(marker));
+        }
+
         let effects = tracking_copy.borrow().effect();
 
         // commit
@@ -663,6 +829,643 @@ where
         Ok(UpgradeResult::from_commit_result(commit_result, effects))
     }
 
+    /// Runs `module_bytes` from `entry_point_name` as the system account, as part of the upgrade to
+    /// `protocol_version`, leaving its effects in `tracking_copy`.
+    ///
+    /// The addresses of URefs and contracts created by the Wasm are generated from `address_seed`.
+    #[allow(clippy::too_many_arguments)]
+    fn exec_upgrade_wasm<T: FromBytes + CLTyped>(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        module_bytes: &[u8],
+        entry_point_name: &str,
+        args: RuntimeArgs,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        address_seed: &[u8],
+    ) -> Result<T, Error> {
+        let module = {
+            let preprocessor = self.preprocessor(*protocol_data.wasm_costs(), protocol_version);
+            preprocessor.preprocess(module_bytes)?
+        };
+
+        // execute as system account
+        let mut system_account = {
+            let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+            match tracking_copy.borrow_mut().read(correlation_id, &key) {
+                Ok(Some(StoredValue::Account(account))) => account,
+                Ok(_) => panic!("system account must exist"),
+                Err(error) => return Err(Error::Exec(error.into())),
+            }
+        };
+
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(SYSTEM_ACCOUNT_ADDR);
+            ret
+        };
+
+        let blocktime = BlockTime::default();
+
+        let deploy_hash = {
+            // seeds address generator w/ protocol version
+            let bytes: Vec<u8> = protocol_version.value().into_bytes()?.to_vec();
+            Blake2bHash::new(&bytes).into()
+        };
+
+        // upgrade has no gas limit; approximating with MAX
+        let gas_limit = Gas::new(std::u64::MAX.into());
+        let phase = Phase::System;
+        let hash_address_generator = {
+            let generator = AddressGenerator::new(address_seed, phase);
+            Rc::new(RefCell::new(generator))
+        };
+        let uref_address_generator = {
+            let generator = AddressGenerator::new(address_seed, phase);
+            Rc::new(RefCell::new(generator))
+        };
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        self.executor.exec_wasm_direct(
+            module,
+            entry_point_name,
+            args,
+            &mut system_account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            hash_address_generator,
+            uref_address_generator,
+            protocol_version,
+            correlation_id,
+            Rc::clone(tracking_copy),
+            phase,
+            protocol_data,
+            system_contract_cache,
+        )
+    }
+
+    /// Slashes the given validators by calling the proof-of-stake contract's `slash` entry point
+    /// as the system account, then commits the resulting effects.
+    ///
+    /// If slashing any of the validators fails, nothing is committed and the error is returned.
+    pub fn commit_slash(
+        &self,
+        correlation_id: CorrelationId,
+        slash_config: SlashConfig,
+    ) -> Result<SlashResult, Error> {
+        const ARG_VALIDATOR: &str = "validator";
+
+        let calls = slash_config
+            .validators()
+            .iter()
+            .map(|validator| {
+                let args = runtime_args! {
+                    ARG_VALIDATOR => *validator,
+                };
+                (DirectSystemContractCall::Slash, args)
+            })
+            .collect();
+
+        match self.commit_proof_of_stake_system_calls(
+            correlation_id,
+            slash_config.pre_state_hash(),
+            slash_config.protocol_version(),
+            BlockTime::default(),
+            calls,
+        )? {
+            Some((commit_result, effects)) => {
+                Ok(SlashResult::from_commit_result(commit_result, effects))
+            }
+            None => Ok(SlashResult::RootNotFound),
+        }
+    }
+
+    /// Processes the matured bonding and unbonding requests at an era boundary by calling the
+    /// proof-of-stake contract's `step` entry point as the system account, then commits the
+    /// resulting effects.
+    pub fn commit_step(
+        &self,
+        correlation_id: CorrelationId,
+        step_config: StepConfig,
+    ) -> Result<StepResult, Error> {
+        let calls = vec![(DirectSystemContractCall::Step, RuntimeArgs::new())];
+
+        match self.commit_proof_of_stake_system_calls(
+            correlation_id,
+            step_config.pre_state_hash(),
+            step_config.protocol_version(),
+            step_config.blocktime(),
+            calls,
+        )? {
+            Some((commit_result, effects)) => {
+                Ok(StepResult::from_commit_result(commit_result, effects))
+            }
+            None => Ok(StepResult::RootNotFound),
+        }
+    }
+
+    /// Runs the proof-of-stake bookkeeping due at the end of an era as the system account, then
+    /// commits the resulting effects.
+    ///
+    /// The era's rewards are distributed to the validators bonded during it before the matured
+    /// bonding and unbonding requests are processed, which rotates the validator set for the next
+    /// era.  If either step fails, nothing is committed and the error is returned.
+    pub fn run_era_end(
+        &self,
+        correlation_id: CorrelationId,
+        era_end_request: EraEndRequest,
+    ) -> Result<EraEndResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        debug!("running end of era {}", era_end_request.era_id());
+
+        let calls = vec![
+            (
+                DirectSystemContractCall::DistributeRewards,
+                RuntimeArgs::new(),
+            ),
+            (DirectSystemContractCall::Step, RuntimeArgs::new()),
+        ];
+
+        let (commit_result, effect) = match self.commit_proof_of_stake_system_calls(
+            correlation_id,
+            era_end_request.pre_state_hash(),
+            era_end_request.protocol_version(),
+            era_end_request.blocktime(),
+            calls,
+        )? {
+            Some(result) => result,
+            None => return Ok(EraEndResult::RootNotFound),
+        };
+
+        match commit_result {
+            CommitResult::Success { state_root, .. } => {
+                let bonded_validators = self.get_bonded_validators(
+                    correlation_id,
+                    era_end_request.protocol_version(),
+                    state_root,
+                )?;
+                Ok(EraEndResult::Success {
+                    post_state_hash: state_root,
+                    effect,
+                    bonded_validators,
+                })
+            }
+            CommitResult::RootNotFound => Ok(EraEndResult::RootNotFound),
+            CommitResult::KeyNotFound(key) => Ok(EraEndResult::KeyNotFound(key)),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                Ok(EraEndResult::TypeMismatch(type_mismatch))
+            }
+            CommitResult::Serialization(error) => Ok(EraEndResult::Serialization(error)),
+        }
+    }
+
+    /// Makes the calls queued with the scheduler system contract which are due at the request's
+    /// block time, in the order of their activation times.
+    ///
+    /// The matured calls are first removed from the queue, up to [`MAX_SCHEDULED_CALLS_PER_BLOCK`]
+    /// of them.  Each call then runs during the system phase on behalf of the account which
+    /// scheduled it, with the gas its payment buys up to the request's per-call gas limit, and its
+    /// effects are committed on their own: a call which fails has no effect on global state other
+    /// than paying for its gas, and doesn't prevent the following calls.  Nothing is done if no
+    /// scheduler is registered.
+    pub fn run_scheduled_calls(
+        &self,
+        correlation_id: CorrelationId,
+        scheduled_calls_request: ScheduledCallsRequest,
+    ) -> Result<ScheduledCallsResult, Error> {
+        let pre_state_hash = scheduled_calls_request.pre_state_hash();
+        let protocol_version = scheduled_calls_request.protocol_version();
+        let blocktime = scheduled_calls_request.blocktime();
+
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(ScheduledCallsResult::RootNotFound),
+        };
+
+        let no_calls = ScheduledCallsResult::Success {
+            post_state_hash: pre_state_hash,
+            results: Vec::new(),
+        };
+
+        let scheduler_hash = match tracking_copy
+            .borrow_mut()
+            .read_system_contract_registry(correlation_id)?
+            .get(SCHEDULER_NAME)
+        {
+            Some(scheduler_hash) => *scheduler_hash,
+            None => return Ok(no_calls),
+        };
+
+        let scheduler_named_keys = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, scheduler_hash)?
+            .take_named_keys();
+        let scheduler_key = |name: &str| {
+            scheduler_named_keys
+                .get(name)
+                .cloned()
+                .ok_or_else(|| execution::Error::NamedKeyNotFound(name.to_string()))
+        };
+        let queue_key = scheduler_key(SCHEDULER_QUEUE_KEY)?;
+        let scheduler_purse_key = scheduler_key(SCHEDULER_PURSE_KEY)?;
+        let queue: Vec<ScheduledCall> =
+            match tracking_copy.borrow_mut().read(correlation_id, &queue_key) {
+                Ok(Some(StoredValue::CLValue(cl_value))) => {
+                    cl_value.into_t().map_err(execution::Error::CLValue)?
+                }
+                Ok(_) => return Err(execution::Error::KeyNotFound(queue_key).into()),
+                Err(error) => return Err(Error::Exec(error.into())),
+            };
+
+        let (mut matured, mut pending): (Vec<ScheduledCall>, Vec<ScheduledCall>) = queue
+            .into_iter()
+            .partition(|scheduled_call| scheduled_call.is_matured(blocktime));
+        if matured.is_empty() {
+            return Ok(no_calls);
+        }
+        if matured.len() > MAX_SCHEDULED_CALLS_PER_BLOCK {
+            // The deferred calls are due before any pending call, so stay at the queue's front
+            let deferred = matured.split_off(MAX_SCHEDULED_CALLS_PER_BLOCK);
+            pending.splice(0..0, deferred);
+        }
+
+        let pending = CLValue::from_t(pending).map_err(execution::Error::CLValue)?;
+        tracking_copy
+            .borrow_mut()
+            .write(queue_key, StoredValue::CLValue(pending));
+        let effects = tracking_copy.borrow().effect();
+        let mut post_state_hash = match self
+            .state
+            .commit(correlation_id, pre_state_hash, effects.transforms)
+            .map_err(Into::into)?
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            commit_result => {
+                return Ok(ScheduledCallsResult::from_failed_commit(commit_result)
+                    .expect("should be a failed commit"))
+            }
+        };
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::Exec(error.into())),
+        };
+        let preprocessor = self.preprocessor(*protocol_data.wasm_costs(), protocol_version);
+        let gas_price = protocol_data.gas_price();
+
+        let mut results = Vec::with_capacity(matured.len());
+        for (index, scheduled_call) in matured.into_iter().enumerate() {
+            let tracking_copy = match self.tracking_copy(post_state_hash)? {
+                Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+                None => return Ok(ScheduledCallsResult::RootNotFound),
+            };
+
+            let gas_limit = {
+                let prepaid_gas = Gas::from_motes(Motes::new(scheduled_call.payment()), gas_price)
+                    .unwrap_or_default();
+                cmp::min(prepaid_gas, scheduled_calls_request.call_gas_limit())
+            };
+
+            // seeds address generators w/ the state hash and the position of the call
+            let deploy_hash = {
+                let mut seed = post_state_hash.to_vec();
+                seed.extend_from_slice(&(index as u64).to_le_bytes());
+                Blake2bHash::new(&seed).value()
+            };
+
+            let result = self.run_scheduled_call(
+                correlation_id,
+                &scheduled_call,
+                &preprocessor,
+                protocol_version,
+                protocol_data,
+                blocktime,
+                deploy_hash,
+                gas_limit,
+                Rc::clone(&tracking_copy),
+            );
+
+            // A failed call's effects are discarded, but its gas is paid for all the same
+            let tracking_copy = if result.is_success() {
+                tracking_copy
+            } else {
+                warn!(
+                    "scheduled call to {} of contract {} failed",
+                    scheduled_call.entry_point(),
+                    base16::encode_lower(&scheduled_call.contract_hash())
+                );
+                match self.tracking_copy(post_state_hash)? {
+                    Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+                    None => return Ok(ScheduledCallsResult::RootNotFound),
+                }
+            };
+            self.pay_for_scheduled_call(
+                correlation_id,
+                &scheduled_call,
+                result.cost(),
+                gas_price,
+                scheduler_purse_key,
+                protocol_data.proof_of_stake(),
+                Rc::clone(&tracking_copy),
+            )?;
+
+            let effects = tracking_copy.borrow().effect();
+            post_state_hash = match self
+                .state
+                .commit(correlation_id, post_state_hash, effects.transforms)
+                .map_err(Into::into)?
+            {
+                CommitResult::Success { state_root, .. } => state_root,
+                commit_result => {
+                    return Ok(ScheduledCallsResult::from_failed_commit(commit_result)
+                        .expect("should be a failed commit"))
+                }
+            };
+            results.push(result);
+        }
+
+        Ok(ScheduledCallsResult::Success {
+            post_state_hash,
+            results,
+        })
+    }
+
+    /// Pays for the gas used by a scheduled call out of the payment held for it in the scheduler's
+    /// purse.  The cost of the gas goes to the proof of stake rewards purse and the rest of the
+    /// payment is refunded to the main purse of the account on whose behalf the call was made, or
+    /// also goes to the rewards purse if that account no longer exists.
+    #[allow(clippy::too_many_arguments)]
+    fn pay_for_scheduled_call(
+        &self,
+        correlation_id: CorrelationId,
+        scheduled_call: &ScheduledCall,
+        cost: Gas,
+        gas_price: u64,
+        scheduler_purse_key: Key,
+        proof_of_stake_hash: ContractHash,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> Result<(), Error> {
+        let mut tracking_copy = tracking_copy.borrow_mut();
+
+        let payment = Motes::new(scheduled_call.payment());
+        let used = Motes::from_gas(cost, gas_price).map_or(payment, |used| cmp::min(used, payment));
+        let refund_purse = tracking_copy
+            .get_account(correlation_id, scheduled_call.account_hash())
+            .ok()
+            .map(|account| account.main_purse());
+        let (used, refund) = match refund_purse {
+            Some(_) => (used, payment - used),
+            None => (payment, Motes::zero()),
+        };
+
+        let scheduler_balance_key =
+            tracking_copy.get_purse_balance_key(correlation_id, scheduler_purse_key)?;
+        let scheduler_balance = tracking_copy
+            .get_purse_balance(correlation_id, scheduler_balance_key)?
+            .checked_sub(payment)
+            .ok_or(Error::InsufficientPayment)?;
+        // from_t for U512 is assumed to never panic
+        tracking_copy.write(
+            scheduler_balance_key,
+            StoredValue::CLValue(CLValue::from_t(scheduler_balance.value()).unwrap()),
+        );
+
+        let rewards_purse_key = tracking_copy
+            .get_contract(correlation_id, proof_of_stake_hash)?
+            .named_keys()
+            .get(POS_REWARDS_PURSE)
+            .cloned()
+            .ok_or(Error::Deploy)?;
+        let mut credits = vec![(rewards_purse_key, used)];
+        if let Some(refund_purse) = refund_purse {
+            credits.push((Key::URef(refund_purse), refund));
+        }
+        for (purse_key, amount) in credits {
+            let balance_key = tracking_copy.get_purse_balance_key(correlation_id, purse_key)?;
+            let amount = StoredValue::CLValue(CLValue::from_t(amount.value()).unwrap());
+            match tracking_copy.add(correlation_id, balance_key, amount) {
+                Ok(AddResult::Success) => (),
+                Ok(AddResult::KeyNotFound(key)) => {
+                    return Err(execution::Error::KeyNotFound(key).into());
+                }
+                Ok(AddResult::TypeMismatch(type_mismatch)) => {
+                    return Err(execution::Error::TypeMismatch(type_mismatch).into());
+                }
+                Ok(AddResult::Serialization(error)) => return Err(Error::Serialization(error)),
+                Err(error) => return Err(Error::Exec(error.into())),
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a single matured scheduled call on `tracking_copy` without committing its effects.
+    #[allow(clippy::too_many_arguments)]
+    fn run_scheduled_call(
+        &self,
+        correlation_id: CorrelationId,
+        scheduled_call: &ScheduledCall,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> ExecutionResult {
+        let account_hash = scheduled_call.account_hash();
+        let account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, account_hash)
+        {
+            Ok(account) => account,
+            Err(_) => return ExecutionResult::precondition_failure(Error::Authorization),
+        };
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(account_hash);
+            ret
+        };
+
+        let deploy_item = ExecutableDeployItem::StoredContractByHash {
+            hash: scheduled_call.contract_hash(),
+            entry_point: scheduled_call.entry_point().to_string(),
+            args: Vec::new(),
+        };
+        let (module, base_key, mut named_keys, contract_package, entry_point, call_stack_element) =
+            match self.get_module(
+                Rc::clone(&tracking_copy),
+                &deploy_item,
+                &account,
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            ) {
+                Ok(GetModuleResult::Contract {
+                    module,
+                    base_key,
+                    contract,
+                    contract_package,
+                    entry_point,
+                    call_stack_element,
+                }) => (
+                    module,
+                    base_key,
+                    contract.named_keys().clone(),
+                    contract_package,
+                    entry_point,
+                    call_stack_element,
+                ),
+                // Stored session code would run in the context of the account, which the
+                // scheduling contract had no access to
+                Ok(GetModuleResult::Session { .. }) => {
+                    return ExecutionResult::precondition_failure(Error::Exec(
+                        execution::Error::InvalidContext,
+                    ))
+                }
+                Err(error) => return ExecutionResult::precondition_failure(error),
+            };
+
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+        self.executor.exec(
+            module,
+            entry_point,
+            scheduled_call.args().clone(),
+            base_key,
+            &account,
+            &mut named_keys,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::System,
+            protocol_data,
+            system_contract_cache,
+            &contract_package,
+            call_stack_element,
+        )
+    }
+
+    /// Calls the given proof-of-stake entry points in order as the system account during the
+    /// system phase, then commits their combined effects.
+    ///
+    /// Returns `None` if `pre_state_hash` is not found. If any of the calls fails, nothing is
+    /// committed and the error is returned.
+    fn commit_proof_of_stake_system_calls(
+        &self,
+        correlation_id: CorrelationId,
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        calls: Vec<(DirectSystemContractCall, RuntimeArgs)>,
+    ) -> Result<Option<(CommitResult, ExecutionEffect)>, Error> {
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(None),
+        };
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => {
+                return Err(Error::InvalidProtocolVersion(protocol_version));
+            }
+            Err(error) => {
+                return Err(Error::Exec(error.into()));
+            }
+        };
+
+        let system_account = {
+            let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+            match tracking_copy.borrow_mut().read(correlation_id, &key) {
+                Ok(Some(StoredValue::Account(account))) => account,
+                Ok(_) => panic!("system account must exist"),
+                Err(error) => return Err(Error::Exec(error.into())),
+            }
+        };
+
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(SYSTEM_ACCOUNT_ADDR);
+            ret
+        };
+
+        let preprocessor = self.preprocessor(*protocol_data.wasm_costs(), protocol_version);
+        let proof_of_stake_hash = protocol_data.proof_of_stake();
+        let proof_of_stake_module = {
+            let proof_of_stake_contract = tracking_copy
+                .borrow_mut()
+                .get_contract(correlation_id, proof_of_stake_hash)?;
+            tracking_copy.borrow_mut().get_system_module(
+                correlation_id,
+                proof_of_stake_contract.contract_wasm_hash(),
+                self.config.use_system_contracts(),
+                &preprocessor,
+            )?
+        };
+
+        let executor = &self.executor;
+        // system calls have no gas limit; approximating with MAX
+        let gas_limit = Gas::new(std::u64::MAX.into());
+        // seeds address generators w/ pre state hash
+        let deploy_hash = pre_state_hash.value();
+
+        for (direct_system_contract_call, args) in calls {
+            // The PoS keys may have been changed by a previous call, so we need to look them up
+            // again from the tracking copy
+            let mut proof_of_stake_keys = tracking_copy
+                .borrow_mut()
+                .get_contract(correlation_id, proof_of_stake_hash)?
+                .named_keys()
+                .to_owned();
+            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+            let (_, result): (Option<()>, ExecutionResult) = executor.exec_system_contract(
+                direct_system_contract_call,
+                proof_of_stake_module.clone(),
+                args,
+                &mut proof_of_stake_keys,
+                Default::default(),
+                Key::from(proof_of_stake_hash),
+                &system_account,
+                authorization_keys.clone(),
+                blocktime,
+                deploy_hash,
+                gas_limit,
+                protocol_version,
+                correlation_id,
+                Rc::clone(&tracking_copy),
+                Phase::System,
+                protocol_data,
+                system_contract_cache,
+            );
+
+            if let Some(error) = result.take_error() {
+                return Err(error);
+            }
+        }
+
+        let effects = tracking_copy.borrow().effect();
+
+        let commit_result = self
+            .state
+            .commit(
+                correlation_id,
+                pre_state_hash,
+                effects.transforms.to_owned(),
+            )
+            .map_err(Into::into)?;
+
+        Ok(Some((commit_result, effects)))
+    }
+
     pub fn tracking_copy(
         &self,
         hash: Blake2bHash,
@@ -683,12 +1486,79 @@ where
             None => return Ok(QueryResult::RootNotFound),
         };
 
-        let tracking_copy = tracking_copy.borrow();
-
-        Ok(tracking_copy
-            .query(correlation_id, query_request.key(), query_request.path())
-            .map_err(|err| Error::Exec(err.into()))?
-            .into())
+        let tracking_copy = tracking_copy.borrow();
+
+        Ok(tracking_copy
+            .query(correlation_id, query_request.key(), query_request.path())
+            .map_err(|err| Error::Exec(err.into()))?
+            .into())
+    }
+
+    /// Resolves the main purse of the requested account and reads its balance, returning it along
+    /// with proofs of every value read on the way.
+    pub fn get_account_balance(
+        &self,
+        correlation_id: CorrelationId,
+        balance_request: BalanceRequest,
+    ) -> Result<BalanceResult, Error> {
+        let tracking_copy = match self.tracking_copy(balance_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(BalanceResult::RootNotFound),
+        };
+        let reader = tracking_copy.reader();
+        let read_with_proof = |key: Key| {
+            reader
+                .read_with_proof(correlation_id, &key)
+                .map_err(|err| Error::Exec(err.into()))
+        };
+        let into_cl_value = |stored_value: &StoredValue| {
+            CLValue::try_from(stored_value.clone())
+                .map_err(|error| Error::Exec(execution::Error::TypeMismatch(error)))
+        };
+
+        let account_key = Key::Account(balance_request.account_hash());
+        let account_proof = match read_with_proof(account_key)? {
+            Some(proof) => proof,
+            None => {
+                let msg = format!("Account not found: {}", account_key.to_formatted_string());
+                return Ok(BalanceResult::ValueNotFound(msg));
+            }
+        };
+        let main_purse = match account_proof.value() {
+            StoredValue::Account(account) => account.main_purse(),
+            other => {
+                let type_mismatch = TypeMismatch::new("Account".to_string(), other.type_name());
+                return Err(Error::Exec(execution::Error::TypeMismatch(type_mismatch)));
+            }
+        };
+
+        let balance_mapping_key = Key::Hash(main_purse.addr());
+        let balance_mapping_proof = match read_with_proof(balance_mapping_key)? {
+            Some(proof) => proof,
+            None => {
+                let msg = format!("Balance mapping not found for purse: {}", main_purse);
+                return Ok(BalanceResult::ValueNotFound(msg));
+            }
+        };
+        let balance_key: Key = into_cl_value(balance_mapping_proof.value())?
+            .into_t()
+            .map_err(execution::Error::from)?;
+
+        let balance_proof = match read_with_proof(balance_key)? {
+            Some(proof) => proof,
+            None => {
+                let msg = format!("Balance not found for purse: {}", main_purse);
+                return Ok(BalanceResult::ValueNotFound(msg));
+            }
+        };
+        let motes: U512 = into_cl_value(balance_proof.value())?
+            .into_t()
+            .map_err(execution::Error::from)?;
+
+        Ok(BalanceResult::Success {
+            motes,
+            proofs: vec![account_proof, balance_mapping_proof, balance_proof],
+        })
     }
 
     pub fn run_execute(
@@ -701,37 +1571,108 @@ where
             .wasm_costs(exec_request.protocol_version)
             .unwrap()
             .unwrap();
-        let executor = Executor::new(self.config);
-        let preprocessor = Preprocessor::new(wasm_costs);
+        let traced_executor;
+        let executor = if exec_request.estimate_gas {
+            traced_executor = self.executor.with_gas_estimation();
+            &traced_executor
+        } else if exec_request.enable_execution_trace {
+            traced_executor = self.executor.with_execution_trace();
+            &traced_executor
+        } else {
+            &self.executor
+        };
+        let preprocessor = self.preprocessor(wasm_costs, exec_request.protocol_version);
+        // All the deploys are executed against the parent state, so what one of them reads can be
+        // served to the rest from this cache.
+        let block_cache = Rc::new(BlockReadCache::new());
 
         let mut results = Vec::new();
 
         for deploy_item in exec_request.take_deploys() {
+            let _log_context = deploy_item.as_ref().ok().map(|deploy_item| {
+                logging::set_deploy_context(
+                    &deploy_item.deploy_hash,
+                    deploy_item.address,
+                    exec_request.protocol_version,
+                )
+            });
+            let mut deploy_hash = None;
+            let mut nonce_account = None;
             let result = match deploy_item {
                 Err(exec_result) => Ok(exec_result),
-                Ok(deploy_item) => match deploy_item.session {
-                    ExecutableDeployItem::Transfer { .. } => self.transfer(
-                        correlation_id,
-                        &executor,
-                        &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
-                        deploy_item,
-                    ),
-                    _ => self.deploy(
-                        correlation_id,
-                        &executor,
-                        &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
-                        deploy_item,
-                    ),
-                },
+                Ok(deploy_item) => {
+                    let check_result = self
+                        .validate_deploy_header(
+                            correlation_id,
+                            exec_request.parent_state_hash,
+                            exec_request.block_time,
+                            &deploy_item,
+                        )
+                        .and_then(|()| {
+                            self.check_deploy_nonce(
+                                correlation_id,
+                                exec_request.parent_state_hash,
+                                &deploy_item,
+                            )
+                        });
+                    match check_result {
+                        Ok(account) => nonce_account = account,
+                        Err(error) => {
+                            results.push(ExecutionResult::precondition_failure(error));
+                            continue;
+                        }
+                    }
+                    deploy_hash = Some(deploy_item.deploy_hash);
+                    match deploy_item.session {
+                        ExecutableDeployItem::Transfer { .. } => self.transfer(
+                            correlation_id,
+                            executor,
+                            &preprocessor,
+                            exec_request.protocol_version,
+                            exec_request.parent_state_hash,
+                            BlockTime::new(exec_request.block_time),
+                            deploy_item,
+                            &block_cache,
+                        ),
+                        _ => self.deploy(
+                            correlation_id,
+                            executor,
+                            &preprocessor,
+                            exec_request.protocol_version,
+                            exec_request.parent_state_hash,
+                            BlockTime::new(exec_request.block_time),
+                            deploy_item,
+                            &block_cache,
+                        ),
+                    }
+                }
             };
             match result {
-                Ok(result) => results.push(result),
+                Ok(result) => {
+                    if result.has_precondition_failure() {
+                        results.push(result);
+                        continue;
+                    }
+                    metrics::record_deploy_executed(result.cost());
+                    let result = match deploy_hash {
+                        Some(deploy_hash) => {
+                            result.with_executed_deploy(&deploy_hash, exec_request.block_time)
+                        }
+                        None => result,
+                    };
+                    let result = match nonce_account {
+                        Some(account) => {
+                            result
+                                .with_incremented_nonce(account)
+                                .unwrap_or_else(|error| {
+                                    ExecutionResult::precondition_failure(Error::Exec(error))
+                                })
+                        }
+                        None => result,
+                    };
+                    block_cache.invalidate(result.effect());
+                    results.push(result)
+                }
                 Err(error) => {
                     return Err(error);
                 }
@@ -741,6 +1682,82 @@ where
         Ok(results)
     }
 
+    /// Checks that the deploy hasn't expired by `block_time` and that all of its dependencies
+    /// have been executed in the state identified by `prestate_hash`, no longer than
+    /// [`EXECUTED_DEPLOY_RETENTION_MILLIS`] ago.
+    ///
+    /// A missing pre-state is not reported here; it is left for execution to surface.
+    fn validate_deploy_header(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        block_time: u64,
+        deploy_item: &DeployItem,
+    ) -> Result<(), Error> {
+        if let Some(expiry) = deploy_item.expiry() {
+            if block_time > expiry {
+                return Err(Error::DeployExpired { expiry, block_time });
+            }
+        }
+
+        if deploy_item.dependencies.is_empty() {
+            return Ok(());
+        }
+
+        let mut tracking_copy = match self.tracking_copy(prestate_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(()),
+        };
+        for dependency in &deploy_item.dependencies {
+            let key = deploy_item::executed_deploy_key(dependency);
+            let executed_at = match tracking_copy
+                .get(correlation_id, &key)
+                .map_err(|err| Error::Exec(err.into()))?
+            {
+                Some(StoredValue::CLValue(cl_value)) => cl_value.into_t::<u64>().ok(),
+                _ => None,
+            };
+            let is_live = executed_at.map_or(false, |executed_at| {
+                block_time.saturating_sub(executed_at) <= EXECUTED_DEPLOY_RETENTION_MILLIS
+            });
+            if !is_live {
+                return Err(Error::MissingDependency(Blake2bHash::from(*dependency)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the deploy's nonce against its account's in the state identified by
+    /// `prestate_hash`, returning the account if replay protection is enabled for it.
+    ///
+    /// A missing pre-state or account is not reported here; it is left for execution to surface.
+    fn check_deploy_nonce(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        deploy_item: &DeployItem,
+    ) -> Result<Option<Account>, Error> {
+        let mut tracking_copy = match self.tracking_copy(prestate_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+        let account = match tracking_copy
+            .get(correlation_id, &Key::Account(deploy_item.address))
+            .map_err(|err| Error::Exec(err.into()))?
+        {
+            Some(StoredValue::Account(account)) => account,
+            _ => return Ok(None),
+        };
+        match account.nonce() {
+            None => Ok(None),
+            Some(expected) if expected == deploy_item.nonce => Ok(Some(account)),
+            Some(expected) => Err(Error::InvalidNonce {
+                expected,
+                actual: deploy_item.nonce,
+            }),
+        }
+    }
+
     pub fn get_module(
         &self,
         tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
@@ -750,13 +1767,16 @@ where
         preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
     ) -> Result<GetModuleResult, error::Error> {
-        let (contract_package, contract, base_key) = match deploy_item {
+        let (contract_package, contract, contract_hash, base_key) = match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
-                let module = preprocessor.preprocess(&module_bytes)?;
+                let module =
+                    self.executor
+                        .preprocess(preprocessor, &module_bytes, *protocol_version)?;
                 return Ok(GetModuleResult::Session {
                     module,
                     contract_package: ContractPackage::default(),
                     entry_point: EntryPoint::default(),
+                    call_stack_element: CallStackElement::session(account.account_hash()),
                 });
             }
             ExecutableDeployItem::StoredContractByHash { .. }
@@ -779,7 +1799,13 @@ where
                     .borrow_mut()
                     .get_contract_package(correlation_id, contract.contract_package_hash())?;
 
-                (contract_package, contract, stored_contract_key)
+                let contract_hash = stored_contract_key.into_seed();
+                (
+                    contract_package,
+                    contract,
+                    contract_hash,
+                    stored_contract_key,
+                )
             }
             ExecutableDeployItem::StoredVersionedContractByName { version, .. }
             | ExecutableDeployItem::StoredVersionedContractByHash { version, .. } => {
@@ -819,7 +1845,12 @@ where
                     .borrow_mut()
                     .get_contract(correlation_id, contract_hash)?;
 
-                (contract_package, contract, contract_package_key)
+                (
+                    contract_package,
+                    contract,
+                    contract_hash,
+                    contract_package_key,
+                )
             }
             ExecutableDeployItem::Transfer { .. } => {
                 return Err(error::Error::InvalidDeployItemVariant(String::from(
@@ -828,6 +1859,12 @@ where
             }
         };
 
+        if contract.is_disabled() {
+            return Err(error::Error::Exec(execution::Error::ContractDisabled(
+                contract_hash,
+            )));
+        }
+
         let entry_point_name = deploy_item.entry_point_name();
 
         let entry_point = contract
@@ -843,11 +1880,17 @@ where
 
         let module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
 
+        let contract_package_hash = contract.contract_package_hash();
         match entry_point.entry_point_type() {
             EntryPointType::Session => Ok(GetModuleResult::Session {
                 module,
                 contract_package,
                 entry_point,
+                call_stack_element: CallStackElement::stored_session(
+                    account.account_hash(),
+                    contract_package_hash,
+                    contract_hash,
+                ),
             }),
             EntryPointType::Contract => Ok(GetModuleResult::Contract {
                 module,
@@ -855,6 +1898,10 @@ where
                 contract,
                 contract_package,
                 entry_point,
+                call_stack_element: CallStackElement::stored_contract(
+                    contract_package_hash,
+                    contract_hash,
+                ),
             }),
         }
     }
@@ -919,6 +1966,93 @@ where
         Ok(account)
     }
 
+    /// Moves any funds left in the proof of stake payment purse into its rewards purse.
+    ///
+    /// Finalization drains the payment purse at the end of every deploy, but funds can still reach
+    /// it in between, e.g. through a transfer to a stored reference of the purse.  Sweeping them
+    /// before payment code runs ensures that a deploy is only paid for by what its own payment code
+    /// deposits.
+    fn drain_payment_purse(
+        &self,
+        correlation_id: CorrelationId,
+        proof_of_stake_contract: &Contract,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> Result<(), Error> {
+        let purse_balance_key = |name: &str| -> Result<Key, Error> {
+            let purse_key = proof_of_stake_contract
+                .named_keys()
+                .get(name)
+                .ok_or(Error::Deploy)?;
+            tracking_copy
+                .borrow_mut()
+                .get_purse_balance_key(correlation_id, *purse_key)
+                .map_err(Into::into)
+        };
+
+        let payment_purse_balance_key = purse_balance_key(POS_PAYMENT_PURSE)?;
+        let leftover_balance = tracking_copy
+            .borrow_mut()
+            .get_purse_balance(correlation_id, payment_purse_balance_key)?
+            .value();
+        if leftover_balance.is_zero() {
+            return Ok(());
+        }
+        let rewards_purse_balance_key = purse_balance_key(POS_REWARDS_PURSE)?;
+
+        // from_t for U512 is assumed to never panic
+        let zero_balance = StoredValue::CLValue(CLValue::from_t(U512::zero()).unwrap());
+        let leftover_balance = StoredValue::CLValue(CLValue::from_t(leftover_balance).unwrap());
+
+        let mut tracking_copy = tracking_copy.borrow_mut();
+        tracking_copy.write(payment_purse_balance_key, zero_balance);
+        match tracking_copy.add(correlation_id, rewards_purse_balance_key, leftover_balance) {
+            Ok(AddResult::Success) => Ok(()),
+            Ok(AddResult::KeyNotFound(key)) => Err(execution::Error::KeyNotFound(key).into()),
+            Ok(AddResult::TypeMismatch(type_mismatch)) => {
+                Err(execution::Error::TypeMismatch(type_mismatch).into())
+            }
+            Ok(AddResult::Serialization(error)) => Err(Error::Serialization(error)),
+            Err(error) => Err(Error::Exec(error.into())),
+        }
+    }
+
+    /// Moves the fee of a native transfer from its source purse into the proof of stake rewards
+    /// purse, where finalization puts the payment of a deploy.
+    fn charge_transfer_fee(
+        &self,
+        correlation_id: CorrelationId,
+        proof_of_stake_contract: &Contract,
+        source_balance_key: Key,
+        source_balance: Motes,
+        transfer_fee: Motes,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> Result<(), Error> {
+        let rewards_purse_key = proof_of_stake_contract
+            .named_keys()
+            .get(POS_REWARDS_PURSE)
+            .ok_or(Error::Deploy)?;
+        let rewards_purse_balance_key = tracking_copy
+            .borrow_mut()
+            .get_purse_balance_key(correlation_id, *rewards_purse_key)?;
+
+        // from_t for U512 is assumed to never panic
+        let new_source_balance =
+            StoredValue::CLValue(CLValue::from_t((source_balance - transfer_fee).value()).unwrap());
+        let transfer_fee = StoredValue::CLValue(CLValue::from_t(transfer_fee.value()).unwrap());
+
+        let mut tracking_copy = tracking_copy.borrow_mut();
+        tracking_copy.write(source_balance_key, new_source_balance);
+        match tracking_copy.add(correlation_id, rewards_purse_balance_key, transfer_fee) {
+            Ok(AddResult::Success) => Ok(()),
+            Ok(AddResult::KeyNotFound(key)) => Err(execution::Error::KeyNotFound(key).into()),
+            Ok(AddResult::TypeMismatch(type_mismatch)) => {
+                Err(execution::Error::TypeMismatch(type_mismatch).into())
+            }
+            Ok(AddResult::Serialization(error)) => Err(Error::Serialization(error)),
+            Err(error) => Err(Error::Exec(error.into())),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn transfer(
         &self,
@@ -929,6 +2063,7 @@ where
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
         deploy_item: DeployItem,
+        block_cache: &Rc<BlockReadCache>,
     ) -> Result<ExecutionResult, RootNotFound> {
         let protocol_data = match self.state.get_protocol_data(protocol_version) {
             Ok(Some(protocol_data)) => protocol_data,
@@ -946,7 +2081,9 @@ where
         let tracking_copy = match self.tracking_copy(prestate_hash) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             Ok(None) => return Err(RootNotFound::new(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(
+                tracking_copy.with_block_cache(Rc::clone(block_cache)),
+            )),
         };
 
         let base_key = Key::Account(deploy_item.address);
@@ -998,10 +2135,22 @@ where
             }
         };
 
+        let proof_of_stake_contract = match tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, protocol_data.proof_of_stake())
+        {
+            Ok(contract) => contract,
+            Err(error) => {
+                return Ok(ExecutionResult::precondition_failure(error.into()));
+            }
+        };
+
         let mut named_keys = mint_contract.named_keys().to_owned();
         let mut extra_keys: Vec<Key> = vec![];
         let base_key = Key::from(protocol_data.mint());
-        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+        // The mint calls are metered against the transfer cost, which is all the transfer pays for
+        let transfer_cost = Gas::new(U512::from(protocol_data.transfer_cost()));
+        let gas_limit = transfer_cost;
 
         let input_runtime_args = match deploy_item.session.into_runtime_args() {
             Ok(runtime_args) => runtime_args,
@@ -1009,6 +2158,50 @@ where
         };
 
         let mut runtime_args_builder = TransferRuntimeArgsBuilder::new(input_runtime_args);
+
+        // The fee is taken from the purse the transfer is made from, so a transfer which can't pay
+        // for itself is rejected before it has any effects
+        let source_balance_key = match runtime_args_builder
+            .resolve_source_uref(&account, correlation_id, Rc::clone(&tracking_copy))
+            .and_then(|source_uref| {
+                tracking_copy
+                    .borrow_mut()
+                    .get_purse_balance_key(correlation_id, source_uref.into())
+                    .map_err(Into::into)
+            }) {
+            Ok(source_balance_key) => source_balance_key,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+        };
+
+        let source_balance = match tracking_copy
+            .borrow_mut()
+            .get_purse_balance(correlation_id, source_balance_key)
+        {
+            Ok(balance) => balance,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        };
+
+        // The product of two u64 values always fits in a U512
+        let transfer_fee = Motes::from_gas(transfer_cost, protocol_data.gas_price())
+            .expect("transfer fee should fit in a U512");
+
+        if source_balance < transfer_fee {
+            return Ok(ExecutionResult::precondition_failure(
+                Error::InsufficientPayment,
+            ));
+        }
+
+        if let Err(error) = self.charge_transfer_fee(
+            correlation_id,
+            &proof_of_stake_contract,
+            source_balance_key,
+            source_balance,
+            transfer_fee,
+            Rc::clone(&tracking_copy),
+        ) {
+            return Ok(ExecutionResult::precondition_failure(error));
+        }
+
         match runtime_args_builder.transfer_target_mode(correlation_id, Rc::clone(&tracking_copy)) {
             Ok(mode) => match mode {
                 TransferTargetMode::Unknown | TransferTargetMode::PurseExists(_) => { /* noop */ }
@@ -1035,8 +2228,11 @@ where
                         );
                     match maybe_uref {
                         Some(main_purse) => {
-                            let new_account =
+                            let mut new_account =
                                 Account::create(public_key, Default::default(), main_purse);
+                            if protocol_data.nonces_enabled() {
+                                new_account.set_nonce(Some(0));
+                            }
                             extra_keys.push(Key::from(main_purse));
                             // write new account
                             tracking_copy
@@ -1044,7 +2240,7 @@ where
                                 .write(Key::Account(public_key), StoredValue::Account(new_account))
                         }
                         None => {
-                            return Ok(execution_result);
+                            return Ok(execution_result.with_cost(transfer_cost));
                         }
                     }
                 }
@@ -1052,8 +2248,9 @@ where
             Err(error) => {
                 return Ok(ExecutionResult::Failure {
                     error,
-                    effect: Default::default(),
-                    cost: Gas::default(),
+                    effect: tracking_copy.borrow().effect(),
+                    cost: transfer_cost,
+                    trace: Vec::new(),
                 });
             }
         }
@@ -1064,8 +2261,9 @@ where
                 Err(error) => {
                     return Ok(ExecutionResult::Failure {
                         error,
-                        effect: Default::default(),
-                        cost: Gas::default(),
+                        effect: tracking_copy.borrow().effect(),
+                        cost: transfer_cost,
+                        trace: Vec::new(),
                     });
                 }
             };
@@ -1091,7 +2289,7 @@ where
                 SystemContractCache::clone(&self.system_contract_cache),
             );
 
-        Ok(execution_result)
+        Ok(execution_result.with_cost(transfer_cost))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1104,6 +2302,7 @@ where
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
         deploy_item: DeployItem,
+        block_cache: &Rc<BlockReadCache>,
     ) -> Result<ExecutionResult, RootNotFound> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
@@ -1121,6 +2320,7 @@ where
                 )));
             }
         };
+        let gas_price = protocol_data.gas_price();
 
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
@@ -1128,7 +2328,11 @@ where
         let tracking_copy = match self.tracking_copy(prestate_hash) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             Ok(None) => return Err(RootNotFound::new(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(
+                tracking_copy
+                    .with_max_effects_size(protocol_data.max_effects_size() as usize)
+                    .with_block_cache(Rc::clone(block_cache)),
+            )),
         };
 
         let base_key = Key::Account(deploy_item.address);
@@ -1245,10 +2449,40 @@ where
                 .insert(proof_of_stake_hash, proof_of_stake_module.clone());
         }
 
-        // Get account main purse balance key
+        let module_bytes_is_empty = match payment {
+            ExecutableDeployItem::ModuleBytes {
+                ref module_bytes, ..
+            } => module_bytes.is_empty(),
+            _ => false,
+        };
+
+        // Standard payment is funded from the account's main purse, or from the purse named by its
+        // "source" argument, which the account must hold with write access
+        let payment_source_purse: URef = if module_bytes_is_empty {
+            let payment_args = match payment.clone().into_runtime_args() {
+                Ok(args) => args,
+                Err(e) => {
+                    let exec_err: crate::execution::Error = e.into();
+                    warn!("Unable to deserialize arguments: {:?}", exec_err);
+                    return Ok(ExecutionResult::precondition_failure(exec_err.into()));
+                }
+            };
+            match TransferRuntimeArgsBuilder::new(payment_args).resolve_source_uref(
+                &account,
+                correlation_id,
+                Rc::clone(&tracking_copy),
+            ) {
+                Ok(uref) => uref,
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            }
+        } else {
+            account.main_purse()
+        };
+
+        // Get payment source purse balance key
         // validation_spec_5: account main purse minimum balance
-        let account_main_purse_balance_key: Key = {
-            let account_key = Key::URef(account.main_purse());
+        let payment_source_balance_key: Key = {
+            let account_key = Key::URef(payment_source_purse);
             match tracking_copy
                 .borrow_mut()
                 .get_purse_balance_key(correlation_id, account_key)
@@ -1260,11 +2494,11 @@ where
             }
         };
 
-        // Get account main purse balance to enforce precondition and in case of forced
+        // Get payment source purse balance to enforce precondition and in case of forced
         // transfer validation_spec_5: account main purse minimum balance
-        let account_main_purse_balance: Motes = match tracking_copy
+        let payment_source_balance: Motes = match tracking_copy
             .borrow_mut()
-            .get_purse_balance(correlation_id, account_main_purse_balance_key)
+            .get_purse_balance(correlation_id, payment_source_balance_key)
         {
             Ok(balance) => balance,
             Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
@@ -1272,14 +2506,24 @@ where
 
         let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
 
-        // Enforce minimum main purse balance validation
+        // Enforce minimum payment source purse balance validation
         // validation_spec_5: account main purse minimum balance
-        if account_main_purse_balance < max_payment_cost {
+        if payment_source_balance < max_payment_cost {
             return Ok(ExecutionResult::precondition_failure(
                 Error::InsufficientPayment,
             ));
         }
 
+        // Start from an empty payment purse, so that funds which reached it outside of a deploy's
+        // payment can't pay for this one
+        if let Err(error) = self.drain_payment_purse(
+            correlation_id,
+            &proof_of_stake_contract,
+            Rc::clone(&tracking_copy),
+        ) {
+            return Ok(ExecutionResult::precondition_failure(error));
+        }
+
         // Finalization is executed by system account (currently genesis account)
         // payment_code_spec_5: system executes finalization
         let system_account = Account::new(
@@ -1297,14 +2541,7 @@ where
         let payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
             // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
-
-            let module_bytes_is_empty = match payment {
-                ExecutableDeployItem::ModuleBytes {
-                    ref module_bytes, ..
-                } => module_bytes.is_empty(),
-                _ => false,
-            };
+            let pay_gas_limit = Gas::from_motes(max_payment_cost, gas_price).unwrap_or_default();
 
             // Create payment code module from bytes
             // validation_spec_1: valid wasm bytes
@@ -1331,6 +2568,7 @@ where
                     module,
                     contract_package: ContractPackage::default(),
                     entry_point: EntryPoint::default(),
+                    call_stack_element: CallStackElement::session(account.account_hash()),
                 })
             } else {
                 self.get_module(
@@ -1358,17 +2596,20 @@ where
                 mut payment_named_keys,
                 payment_package,
                 payment_entry_point,
+                payment_call_stack_element,
             ) = match payment_module {
                 GetModuleResult::Session {
                     module,
                     contract_package,
                     entry_point,
+                    call_stack_element,
                 } => (
                     module,
                     base_key,
                     account.named_keys().clone(),
                     contract_package,
                     entry_point,
+                    call_stack_element,
                 ),
                 GetModuleResult::Contract {
                     module,
@@ -1376,12 +2617,14 @@ where
                     contract,
                     contract_package,
                     entry_point,
+                    call_stack_element,
                 } => (
                     module,
                     base_key,
                     contract.named_keys().clone(),
                     contract_package,
                     entry_point,
+                    call_stack_element,
                 ),
             };
 
@@ -1415,6 +2658,7 @@ where
                     protocol_data,
                     system_contract_cache,
                     &payment_package,
+                    payment_call_stack_element,
                 )
             } else {
                 // use host side standard payment
@@ -1460,11 +2704,13 @@ where
                     Ok(()) => ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        trace: Vec::new(),
                     },
                     Err(error) => ExecutionResult::Failure {
                         error: error.into(),
                         effect: effects_snapshot,
                         cost: runtime.context().gas_counter(),
+                        trace: Vec::new(),
                     },
                 }
             }
@@ -1505,7 +2751,9 @@ where
             }
         };
 
-        if let Some(forced_transfer) = payment_result.check_forced_transfer(payment_purse_balance) {
+        if let Some(forced_transfer) =
+            payment_result.check_forced_transfer(payment_purse_balance, gas_price)
+        {
             // Get rewards purse balance key
             // payment_code_spec_6: system contract validity
             let rewards_purse_balance_key: Key = {
@@ -1537,8 +2785,9 @@ where
             return Ok(ExecutionResult::new_payment_code_error(
                 error,
                 max_payment_cost,
-                account_main_purse_balance,
-                account_main_purse_balance_key,
+                gas_price,
+                payment_source_balance,
+                payment_source_balance_key,
                 rewards_purse_balance_key,
             ));
         }
@@ -1555,17 +2804,20 @@ where
             mut session_named_keys,
             session_package,
             session_entry_point,
+            session_call_stack_element,
         ) = match session_module {
             GetModuleResult::Session {
                 module,
                 contract_package,
                 entry_point,
+                call_stack_element,
             } => (
                 module,
                 base_key,
                 account.named_keys().clone(),
                 contract_package,
                 entry_point,
+                call_stack_element,
             ),
             GetModuleResult::Contract {
                 module,
@@ -1573,12 +2825,14 @@ where
                 contract,
                 contract_package,
                 entry_point,
+                call_stack_element,
             } => (
                 module,
                 base_key,
                 contract.named_keys().clone(),
                 contract_package,
                 entry_point,
+                call_stack_element,
             ),
         };
 
@@ -1595,7 +2849,15 @@ where
             // payment code execution) * conv_rate, yes session
             // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
             // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
+            //
+            // When estimating, session code may use whatever the account could pay for instead,
+            // so that its exact cost is found even if the deploy's payment would not cover it.
+            let session_gas_budget = if executor.estimate_gas() {
+                payment_source_balance
+            } else {
+                payment_purse_balance
+            };
+            let session_gas_limit: Gas = Gas::from_motes(session_gas_budget, gas_price)
                 .unwrap_or_default()
                 - payment_result_cost;
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
@@ -1618,6 +2880,7 @@ where
                 protocol_data,
                 system_contract_cache,
                 &session_package,
+                session_call_stack_element,
             )
         };
         debug!("Session result: {:?}", session_result);
@@ -1641,11 +2904,18 @@ where
 
             let proof_of_stake_args = {
                 //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
-                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
+                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), gas_price).expect("motes overflow");
+                // An estimate may cost more than the payment purse holds.  Its effects are not meant
+                // to be committed, so only charge what's there to let finalization succeed.
+                let finalize_cost_motes = if executor.estimate_gas() {
+                    cmp::min(finalize_cost_motes, payment_purse_balance)
+                } else {
+                    finalize_cost_motes
+                };
                 const ARG_AMOUNT: &str = "amount";
                 const ARG_ACCOUNT_KEY: &str = "account";
                 runtime_args! {
-                    ARG_AMOUNT => finalize_cost_motes.value(),
+                    ARG_AMOUNT => finalize_cost_motes,
                     ARG_ACCOUNT_KEY => account_public_key,
                 }
             };
@@ -1722,19 +2992,107 @@ where
         }
     }
 
-    /// Calculates bonded validators at `root_hash` state.
+    /// Applies each of the `batch` of effects on top of its pre-state hash, in order, like
+    /// [`apply_effect`](Self::apply_effect) but letting global state commit them together, e.g.
+    /// within a single LMDB transaction when replaying blocks.
     ///
-    /// Should only be called with a valid root hash after a successful call to
-    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
-    fn get_bonded_validators(
+    /// A pre-state hash may be the post-state hash of an earlier entry of the batch.  Committing
+    /// stops at the first entry which fails, so the returned results run up to and including that
+    /// failure.
+    pub fn commit_batch(
         &self,
         correlation_id: CorrelationId,
         protocol_version: ProtocolVersion,
-        root_hash: Blake2bHash,
-    ) -> Result<HashMap<AccountHash, U512>, Error>
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .commit_batch(correlation_id, batch)?
+            .into_iter()
+            .map(|commit_result| match commit_result {
+                CommitResult::Success { state_root, .. } => {
+                    let bonded_validators =
+                        self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
+                    Ok(CommitResult::Success {
+                        state_root,
+                        bonded_validators,
+                    })
+                }
+                commit_result => Ok(commit_result),
+            })
+            .collect()
+    }
+
+    /// Removes all global state which is not reachable from one of the `retained_roots`.
+    pub fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .prune(correlation_id, retained_roots)
+            .map_err(Into::into)
+    }
+
+    /// Reads the trie stored under `trie_key`, if any.
+    pub fn read_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .read_trie(correlation_id, trie_key)
+            .map_err(Into::into)
+    }
+
+    /// Stores `trie` in global state under its hash, returning that hash.
+    pub fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie: &Trie<Key, StoredValue>,
+    ) -> Result<Blake2bHash, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .put_trie(correlation_id, trie)
+            .map_err(Into::into)
+    }
+
+    /// Returns the hashes of the tries reachable from `trie_keys` which are missing from global
+    /// state.
+    pub fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .missing_trie_keys(correlation_id, trie_keys)
+            .map_err(Into::into)
+    }
+
+    /// Returns the weights of the validators bonded in the proof-of-stake contract at the
+    /// requested state.
+    pub fn read_validator_weights(
+        &self,
+        correlation_id: CorrelationId,
+        validator_weights_request: ValidatorWeightsRequest,
+    ) -> Result<ValidatorWeightsResult, Error>
     where
         Error: From<S::Error>,
     {
+        let protocol_version = validator_weights_request.protocol_version();
         let protocol_data = match self.state.get_protocol_data(protocol_version)? {
             Some(protocol_data) => protocol_data,
             None => return Err(Error::InvalidProtocolVersion(protocol_version)),
@@ -1742,9 +3100,12 @@ where
 
         let proof_of_stake_key = protocol_data.proof_of_stake().into();
 
-        let reader = match self.state.checkout(root_hash)? {
+        let reader = match self
+            .state
+            .checkout(validator_weights_request.state_hash())?
+        {
             Some(reader) => reader,
-            None => panic!("get_bonded_validators called with an invalid root hash"),
+            None => return Ok(ValidatorWeightsResult::RootNotFound),
         };
 
         let contract = match reader.read(correlation_id, &proof_of_stake_key)? {
@@ -1752,12 +3113,113 @@ where
             _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
         };
 
-        let bonded_validators = contract
-            .named_keys()
-            .keys()
-            .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
-            .collect::<HashMap<AccountHash, U512>>();
+        let validator_weights = match contract.named_keys().get(POS_STAKES) {
+            Some(stakes_key) => match reader.read(correlation_id, &stakes_key.normalize())? {
+                Some(StoredValue::CLValue(cl_value)) => cl_value
+                    .into_t()
+                    .map_err(|error| Error::Exec(error.into()))?,
+                Some(other) => {
+                    let type_mismatch = TypeMismatch::new("CLValue".to_string(), other.type_name());
+                    return Err(Error::Exec(execution::Error::TypeMismatch(type_mismatch)));
+                }
+                None => return Err(Error::Exec(execution::Error::KeyNotFound(*stakes_key))),
+            },
+            // States predating the upgrade which migrated the stakes still have them encoded in
+            // the contract's named keys.
+            None => contract
+                .named_keys()
+                .keys()
+                .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
+                .collect(),
+        };
+
+        Ok(ValidatorWeightsResult::Success { validator_weights })
+    }
+
+    /// Returns the changes to the validators' stakes made by each of the given execution results,
+    /// all of which were produced on top of `pre_state_hash`.
+    ///
+    /// States predating the upgrade which migrated the stakes yield no events.
+    pub fn get_bonding_events(
+        &self,
+        correlation_id: CorrelationId,
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        execution_results: &[ExecutionResult],
+    ) -> Result<Vec<Vec<BondingEvent>>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let no_events = || execution_results.iter().map(|_| Vec::new()).collect();
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let proof_of_stake_key = protocol_data.proof_of_stake().into();
+
+        let reader = match self.state.checkout(pre_state_hash)? {
+            Some(reader) => reader,
+            None => return Ok(no_events()),
+        };
+
+        let contract = match reader.read(correlation_id, &proof_of_stake_key)? {
+            Some(StoredValue::Contract(contract)) => contract,
+            _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
+        };
+
+        let stakes_key = match contract.named_keys().get(POS_STAKES) {
+            Some(stakes_key) => stakes_key.normalize(),
+            None => return Ok(no_events()),
+        };
+
+        let validator_weights_request =
+            ValidatorWeightsRequest::new(pre_state_hash, protocol_version);
+        let pre_stakes =
+            match self.read_validator_weights(correlation_id, validator_weights_request)? {
+                ValidatorWeightsResult::Success { validator_weights } => validator_weights,
+                ValidatorWeightsResult::RootNotFound => return Ok(no_events()),
+            };
+
+        execution_results
+            .iter()
+            .map(
+                |execution_result| match execution_result.effect().transforms.get(&stakes_key) {
+                    Some(Transform::Write(StoredValue::CLValue(cl_value))) => {
+                        let post_stakes: BTreeMap<AccountHash, U512> = cl_value
+                            .clone()
+                            .into_t()
+                            .map_err(|error| Error::Exec(error.into()))?;
+                        Ok(BondingEvent::from_stakes(&pre_stakes, &post_stakes))
+                    }
+                    _ => Ok(Vec::new()),
+                },
+            )
+            .collect()
+    }
 
-        Ok(bonded_validators)
+    /// Calculates bonded validators at `root_hash` state.
+    ///
+    /// Should only be called with a valid root hash after a successful call to
+    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
+    fn get_bonded_validators(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        root_hash: Blake2bHash,
+    ) -> Result<HashMap<AccountHash, U512>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let validator_weights_request = ValidatorWeightsRequest::new(root_hash, protocol_version);
+        match self.read_validator_weights(correlation_id, validator_weights_request)? {
+            ValidatorWeightsResult::Success { validator_weights } => {
+                Ok(validator_weights.into_iter().collect())
+            }
+            ValidatorWeightsResult::RootNotFound => {
+                panic!("get_bonded_validators called with an invalid root hash")
+            }
+        }
     }
 }
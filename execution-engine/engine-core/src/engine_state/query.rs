@@ -1,4 +1,5 @@
 use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_storage::trie::merkle_proof::TrieMerkleProof;
 use types::Key;
 
 use crate::tracking_copy::TrackingCopyQueryResult;
@@ -7,7 +8,10 @@ pub enum QueryResult {
     RootNotFound,
     ValueNotFound(String),
     CircularReference(String),
-    Success(StoredValue),
+    Success {
+        value: StoredValue,
+        proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -46,7 +50,9 @@ impl From<TrackingCopyQueryResult> for QueryResult {
             TrackingCopyQueryResult::CircularReference(message) => {
                 QueryResult::CircularReference(message)
             }
-            TrackingCopyQueryResult::Success(value) => QueryResult::Success(value),
+            TrackingCopyQueryResult::Success { value, proofs } => {
+                QueryResult::Success { value, proofs }
+            }
         }
     }
 }
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use engine_shared::{gas::Gas, newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{bytesrepr, BlockTime, Key, ProtocolVersion};
+
+use crate::engine_state::execution_result::ExecutionResult;
+
+/// The maximum number of scheduled calls made for a single block.  Matured calls beyond it stay in
+/// the queue and are made for the following blocks.
+pub const MAX_SCHEDULED_CALLS_PER_BLOCK: usize = 100;
+
+pub enum ScheduledCallsResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        /// The results of the calls which matured, in the order they were made.
+        results: Vec<ExecutionResult>,
+    },
+}
+
+impl fmt::Display for ScheduledCallsResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ScheduledCallsResult::RootNotFound => write!(f, "Root not found"),
+            ScheduledCallsResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            ScheduledCallsResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            ScheduledCallsResult::Serialization(error) => {
+                write!(f, "Serialization error: {:?}", error)
+            }
+            ScheduledCallsResult::Success {
+                post_state_hash,
+                results,
+            } => write!(f, "Success: {} {:?}", post_state_hash, results),
+        }
+    }
+}
+
+impl ScheduledCallsResult {
+    /// Returns the result corresponding to a commit which didn't succeed, or `None` if it did.
+    pub(crate) fn from_failed_commit(commit_result: CommitResult) -> Option<Self> {
+        match commit_result {
+            CommitResult::Success { .. } => None,
+            CommitResult::RootNotFound => Some(ScheduledCallsResult::RootNotFound),
+            CommitResult::KeyNotFound(key) => Some(ScheduledCallsResult::KeyNotFound(key)),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                Some(ScheduledCallsResult::TypeMismatch(type_mismatch))
+            }
+            CommitResult::Serialization(error) => Some(ScheduledCallsResult::Serialization(error)),
+        }
+    }
+}
+
+/// A request to make the calls queued with the scheduler which are due at a block's time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledCallsRequest {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    blocktime: BlockTime,
+    call_gas_limit: Gas,
+}
+
+impl ScheduledCallsRequest {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        call_gas_limit: Gas,
+    ) -> Self {
+        ScheduledCallsRequest {
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+            call_gas_limit,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+
+    /// Returns the gas limit of each call, which is further limited by the gas its payment buys.
+    pub fn call_gas_limit(&self) -> Gas {
+        self.call_gas_limit
+    }
+}
@@ -0,0 +1,96 @@
+use std::{collections::HashMap, fmt};
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+pub enum SlashResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+        /// The stakes of the validators still bonded after slashing.
+        bonded_validators: HashMap<AccountHash, U512>,
+    },
+}
+
+impl fmt::Display for SlashResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SlashResult::RootNotFound => write!(f, "Root not found"),
+            SlashResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            SlashResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            SlashResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            SlashResult::Success {
+                post_state_hash,
+                effect,
+                bonded_validators,
+            } => write!(
+                f,
+                "Success: {} {:?} {:?}",
+                post_state_hash, effect, bonded_validators
+            ),
+        }
+    }
+}
+
+impl SlashResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => SlashResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => SlashResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => SlashResult::TypeMismatch(type_mismatch),
+            CommitResult::Serialization(error) => SlashResult::Serialization(error),
+            CommitResult::Success {
+                state_root,
+                bonded_validators,
+            } => SlashResult::Success {
+                post_state_hash: state_root,
+                effect,
+                bonded_validators,
+            },
+        }
+    }
+}
+
+/// A request to slash equivocating validators, executed by the system account against the
+/// proof-of-stake contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashConfig {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    validators: Vec<AccountHash>,
+}
+
+impl SlashConfig {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        validators: Vec<AccountHash>,
+    ) -> Self {
+        SlashConfig {
+            pre_state_hash,
+            protocol_version,
+            validators,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn validators(&self) -> &[AccountHash] {
+        self.validators.as_slice()
+    }
+}
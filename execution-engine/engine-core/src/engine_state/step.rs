@@ -0,0 +1,85 @@
+use std::fmt;
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{bytesrepr, BlockTime, Key, ProtocolVersion};
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+pub enum StepResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for StepResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            StepResult::RootNotFound => write!(f, "Root not found"),
+            StepResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            StepResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            StepResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            StepResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl StepResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => StepResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => StepResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => StepResult::TypeMismatch(type_mismatch),
+            CommitResult::Serialization(error) => StepResult::Serialization(error),
+            CommitResult::Success { state_root, .. } => StepResult::Success {
+                post_state_hash: state_root,
+                effect,
+            },
+        }
+    }
+}
+
+/// A request to process the matured bonding and unbonding requests of the proof-of-stake contract
+/// at an era boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepConfig {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    blocktime: BlockTime,
+}
+
+impl StepConfig {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+    ) -> Self {
+        StepConfig {
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+}
@@ -50,7 +50,7 @@ impl TransferRuntimeArgsBuilder {
             .is_ok()
     }
 
-    fn resolve_source_uref<R>(
+    pub fn resolve_source_uref<R>(
         &self,
         account: &Account,
         correlation_id: CorrelationId,
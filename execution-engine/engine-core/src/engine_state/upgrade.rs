@@ -1,14 +1,292 @@
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
-use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
-use engine_storage::global_state::CommitResult;
+use engine_shared::{
+    host_function_costs::HostFunctionCosts,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    TypeMismatch,
+};
+use engine_storage::{
+    global_state::{CommitResult, StateReader},
+    protocol_data::ProtocolData,
+};
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{bytesrepr, Key, ProtocolVersion};
+use types::{
+    account::AccountHash, bytesrepr, contracts::NamedKeys, AccessRights, CLValue, Key,
+    ProtocolVersion, RuntimeArgs, URef, U512,
+};
 
-use crate::engine_state::execution_effect::ExecutionEffect;
+use crate::{
+    engine_state::{execution_effect::ExecutionEffect, genesis::POS_STAKES, utils},
+    execution::{self, AddressGenerator, AddressGeneratorBuilder},
+    tracking_copy::TrackingCopy,
+};
 
 pub type ActivationPoint = u64;
 
+/// A state migration compiled into the engine.  It fails the upgrade by returning an error.
+pub type NativeMigration = fn(&mut dyn MigrationContext) -> Result<(), execution::Error>;
+
+/// The view of global state given to a [`NativeMigration`].
+pub trait MigrationContext {
+    /// Returns the protocol data of the version being upgraded to.
+    fn protocol_data(&self) -> &ProtocolData;
+
+    fn read(&mut self, key: &Key) -> Result<Option<StoredValue>, execution::Error>;
+
+    fn write(&mut self, key: Key, value: StoredValue);
+
+    /// Returns a fresh address, generated from the pre-state hash and the migration's name.
+    fn new_address(&mut self) -> [u8; 32];
+}
+
+pub(crate) struct TrackingCopyMigrationContext<'a, R> {
+    correlation_id: CorrelationId,
+    tracking_copy: &'a mut TrackingCopy<R>,
+    protocol_data: &'a ProtocolData,
+    address_generator: AddressGenerator,
+}
+
+impl<'a, R> TrackingCopyMigrationContext<'a, R> {
+    pub(crate) fn new(
+        correlation_id: CorrelationId,
+        tracking_copy: &'a mut TrackingCopy<R>,
+        protocol_data: &'a ProtocolData,
+        address_seed: &[u8],
+    ) -> Self {
+        let address_generator = AddressGeneratorBuilder::new()
+            .seed_with(address_seed)
+            .build();
+        TrackingCopyMigrationContext {
+            correlation_id,
+            tracking_copy,
+            protocol_data,
+            address_generator,
+        }
+    }
+}
+
+impl<'a, R> MigrationContext for TrackingCopyMigrationContext<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn protocol_data(&self) -> &ProtocolData {
+        self.protocol_data
+    }
+
+    fn read(&mut self, key: &Key) -> Result<Option<StoredValue>, execution::Error> {
+        self.tracking_copy
+            .read(self.correlation_id, key)
+            .map_err(Into::into)
+    }
+
+    fn write(&mut self, key: Key, value: StoredValue) {
+        self.tracking_copy.write(key, value)
+    }
+
+    fn new_address(&mut self) -> [u8; 32] {
+        self.address_generator.create_address()
+    }
+}
+
+/// A one-off transformation of global state bundled with an upgrade.
+///
+/// Migrations run in order after the upgrade installer.  Each one runs only once per chain: its
+/// completion is recorded in global state under [`Migration::marker_key`], and an upgrade finding
+/// the marker already present skips it.
+#[derive(Clone)]
+pub enum Migration {
+    /// Wasm run as the system account from its `call` entry point.
+    Wasm {
+        name: String,
+        module_bytes: Vec<u8>,
+        args: RuntimeArgs,
+    },
+    /// A function compiled into the engine.
+    Native {
+        name: String,
+        migrate: NativeMigration,
+    },
+}
+
+impl Migration {
+    pub fn wasm(name: &str, module_bytes: Vec<u8>, args: RuntimeArgs) -> Self {
+        Migration::Wasm {
+            name: name.to_string(),
+            module_bytes,
+            args,
+        }
+    }
+
+    pub fn native(name: &str, migrate: NativeMigration) -> Self {
+        Migration::Native {
+            name: name.to_string(),
+            migrate,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Migration::Wasm { name, .. } | Migration::Native { name, .. } => name,
+        }
+    }
+
+    /// Returns the key recording that this migration has run as part of the upgrade to
+    /// `protocol_version`.
+    pub fn marker_key(&self, protocol_version: ProtocolVersion) -> Key {
+        let mut seed = b"migration".to_vec();
+        seed.extend_from_slice(&protocol_version.value().major.to_le_bytes());
+        seed.extend_from_slice(&protocol_version.value().minor.to_le_bytes());
+        seed.extend_from_slice(&protocol_version.value().patch.to_le_bytes());
+        seed.extend_from_slice(self.name().as_bytes());
+        Key::Migration(Blake2bHash::new(&seed).value())
+    }
+}
+
+// `NativeMigration` is a higher-ranked function pointer, for which `Debug` and `PartialEq` can't be
+// derived.
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Migration::Wasm {
+                name,
+                module_bytes,
+                args,
+            } => f
+                .debug_struct("Wasm")
+                .field("name", name)
+                .field("module_bytes", &format!("[{} bytes]", module_bytes.len()))
+                .field("args", args)
+                .finish(),
+            Migration::Native { name, .. } => f.debug_struct("Native").field("name", name).finish(),
+        }
+    }
+}
+
+impl PartialEq for Migration {
+    fn eq(&self, other: &Migration) -> bool {
+        match (self, other) {
+            (
+                Migration::Wasm {
+                    name,
+                    module_bytes,
+                    args,
+                },
+                Migration::Wasm {
+                    name: other_name,
+                    module_bytes: other_module_bytes,
+                    args: other_args,
+                },
+            ) => name == other_name && module_bytes == other_module_bytes && args == other_args,
+            (
+                Migration::Native { name, migrate },
+                Migration::Native {
+                    name: other_name,
+                    migrate: other_migrate,
+                },
+            ) => name == other_name && *migrate as usize == *other_migrate as usize,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Migration {}
+
+/// The migrations compiled into the engine, by the protocol version whose upgrade introduces them.
+///
+/// An upgrade runs the migrations of every version it moves past, in order of version, before
+/// those given in its [`UpgradeConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationRegistry(BTreeMap<ProtocolVersion, Vec<Migration>>);
+
+impl MigrationRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `migration` to those run, in order, by upgrades reaching `protocol_version`.
+    pub fn with_migration(
+        mut self,
+        protocol_version: ProtocolVersion,
+        migration: Migration,
+    ) -> Self {
+        self.0.entry(protocol_version).or_default().push(migration);
+        self
+    }
+
+    /// Returns the migrations registered for the versions after `current_protocol_version` up to
+    /// and including `new_protocol_version`, each with the version it's registered for.
+    pub fn migrations(
+        &self,
+        current_protocol_version: ProtocolVersion,
+        new_protocol_version: ProtocolVersion,
+    ) -> impl Iterator<Item = (ProtocolVersion, &Migration)> {
+        self.0
+            .iter()
+            .filter(move |(protocol_version, _)| {
+                current_protocol_version < **protocol_version
+                    && **protocol_version <= new_protocol_version
+            })
+            .flat_map(|(protocol_version, migrations)| {
+                migrations
+                    .iter()
+                    .map(move |migration| (*protocol_version, migration))
+            })
+    }
+}
+
+/// Returns the registry of the migrations shipped with this engine.
+pub fn builtin_migrations() -> MigrationRegistry {
+    MigrationRegistry::new().with_migration(
+        ProtocolVersion::V1_1_0,
+        Migration::native(POS_STAKES, migrate_pos_stakes),
+    )
+}
+
+/// Moves the stakes of the proof-of-stake contract from its legacy `v_<account hash>_<stake>`
+/// named keys into a map stored under a new URef, which is added to the contract's named keys as
+/// [`POS_STAKES`].  Does nothing if the stakes were already migrated.
+fn migrate_pos_stakes(context: &mut dyn MigrationContext) -> Result<(), execution::Error> {
+    let proof_of_stake_key: Key = context.protocol_data().proof_of_stake().into();
+    let mut contract = match context.read(&proof_of_stake_key)? {
+        Some(StoredValue::Contract(contract)) => contract,
+        Some(other) => {
+            return Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "ContractHeader".to_string(),
+                other.type_name(),
+            )));
+        }
+        None => return Err(execution::Error::KeyNotFound(proof_of_stake_key)),
+    };
+    if contract.named_keys().contains_key(POS_STAKES) {
+        return Ok(());
+    }
+
+    let legacy_stakes: Vec<(String, (AccountHash, U512))> = contract
+        .named_keys()
+        .keys()
+        .filter_map(|name| {
+            utils::pos_validator_key_name_to_tuple(name).map(|stake| (name.clone(), stake))
+        })
+        .collect();
+    let mut stakes = BTreeMap::new();
+    for (name, (validator, stake)) in legacy_stakes {
+        contract.remove_named_key(&name);
+        stakes.insert(validator, stake);
+    }
+
+    let stakes_uref = URef::new(context.new_address(), AccessRights::READ_ADD_WRITE);
+    let cl_value = CLValue::from_t(stakes)?;
+    context.write(Key::URef(stakes_uref), StoredValue::CLValue(cl_value));
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(POS_STAKES.to_string(), Key::URef(stakes_uref));
+    contract.named_keys_append(&mut named_keys);
+    context.write(proof_of_stake_key, StoredValue::Contract(contract));
+    Ok(())
+}
+
 pub enum UpgradeResult {
     RootNotFound,
     KeyNotFound(Key),
@@ -60,10 +338,18 @@ pub struct UpgradeConfig {
     upgrade_installer_args: Option<Vec<u8>>,
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
+    host_function_costs: Option<HostFunctionCosts>,
+    transfer_cost: Option<u64>,
+    max_effects_size: Option<u64>,
+    max_value_size: Option<u64>,
+    max_named_key_length: Option<u64>,
+    gas_price: Option<u64>,
     activation_point: Option<ActivationPoint>,
+    migrations: Vec<Migration>,
 }
 
 impl UpgradeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pre_state_hash: Blake2bHash,
         current_protocol_version: ProtocolVersion,
@@ -71,6 +357,12 @@ impl UpgradeConfig {
         upgrade_installer_args: Option<Vec<u8>>,
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
+        host_function_costs: Option<HostFunctionCosts>,
+        transfer_cost: Option<u64>,
+        max_effects_size: Option<u64>,
+        max_value_size: Option<u64>,
+        max_named_key_length: Option<u64>,
+        gas_price: Option<u64>,
         activation_point: Option<ActivationPoint>,
     ) -> Self {
         UpgradeConfig {
@@ -80,10 +372,23 @@ impl UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            host_function_costs,
+            transfer_cost,
+            max_effects_size,
+            max_value_size,
+            max_named_key_length,
+            gas_price,
             activation_point,
+            migrations: Vec::new(),
         }
     }
 
+    /// Adds `migration` to those run, in order, when upgrading.
+    pub fn with_migration(mut self, migration: Migration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
     pub fn pre_state_hash(&self) -> Blake2bHash {
         self.pre_state_hash
     }
@@ -110,7 +415,164 @@ impl UpgradeConfig {
         self.wasm_costs
     }
 
+    /// Returns the costs of the host functions replacing the current ones, if any.
+    pub fn host_function_costs(&self) -> Option<HostFunctionCosts> {
+        self.host_function_costs
+    }
+
+    /// Returns the gas charged for a native transfer replacing the current one, if any.
+    pub fn transfer_cost(&self) -> Option<u64> {
+        self.transfer_cost
+    }
+
+    /// Returns the maximum total size, in bytes, of the transforms a deploy may produce replacing
+    /// the current one, if any.
+    pub fn max_effects_size(&self) -> Option<u64> {
+        self.max_effects_size
+    }
+
+    /// Returns the maximum size, in bytes of its serialized form, of a value a contract may write
+    /// replacing the current one, if any.
+    pub fn max_value_size(&self) -> Option<u64> {
+        self.max_value_size
+    }
+
+    /// Returns the maximum length, in bytes, of the name a contract may give a named key replacing
+    /// the current one, if any.
+    pub fn max_named_key_length(&self) -> Option<u64> {
+        self.max_named_key_length
+    }
+
+    pub fn gas_price(&self) -> Option<u64> {
+        self.gas_price
+    }
+
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
+
+    pub fn migrations(&self) -> &[Migration] {
+        self.migrations.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+    use engine_storage::{
+        global_state::{in_memory::InMemoryGlobalState, StateProvider},
+        protocol_data::ProtocolData,
+    };
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+    use types::{
+        account::AccountHash, contracts::NamedKeys, AccessRights, CLValue, Contract, EntryPoints,
+        Key, ProtocolVersion, URef, U512,
+    };
+
+    use super::{
+        migrate_pos_stakes, Migration, MigrationContext, MigrationRegistry,
+        TrackingCopyMigrationContext,
+    };
+    use crate::{engine_state::genesis::POS_STAKES, execution, tracking_copy::TrackingCopy};
+
+    fn noop(_context: &mut dyn MigrationContext) -> Result<(), execution::Error> {
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_migrations_of_versions_moved_past() {
+        let v1_0_1 = ProtocolVersion::from_parts(1, 0, 1);
+        let v2_0_0 = ProtocolVersion::from_parts(2, 0, 0);
+        let registry = MigrationRegistry::new()
+            .with_migration(ProtocolVersion::V1_1_0, Migration::native("b", noop))
+            .with_migration(v1_0_1, Migration::native("a", noop))
+            .with_migration(ProtocolVersion::V1_1_0, Migration::native("c", noop))
+            .with_migration(v2_0_0, Migration::native("d", noop));
+
+        let names = |current, new| {
+            registry
+                .migrations(current, new)
+                .map(|(protocol_version, migration)| (protocol_version, migration.name()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(ProtocolVersion::V1_0_0, ProtocolVersion::V1_1_0),
+            vec![
+                (v1_0_1, "a"),
+                (ProtocolVersion::V1_1_0, "b"),
+                (ProtocolVersion::V1_1_0, "c"),
+            ]
+        );
+        assert_eq!(names(ProtocolVersion::V1_1_0, v2_0_0), vec![(v2_0_0, "d")]);
+        assert!(names(v2_0_0, ProtocolVersion::from_parts(2, 0, 1)).is_empty());
+    }
+
+    #[test]
+    fn should_migrate_legacy_pos_stakes() {
+        let correlation_id = CorrelationId::new();
+        let validator_1 = AccountHash::new([1; 32]);
+        let validator_2 = AccountHash::new([2; 32]);
+        let legacy_name = |validator: AccountHash, stake: u64| {
+            format!("v_{}_{}", base16::encode_lower(&validator.value()), stake)
+        };
+        let other_name = "pos_bonding_purse".to_string();
+        let other_key = Key::URef(URef::new([3; 32], AccessRights::READ_ADD_WRITE));
+
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(legacy_name(validator_1, 10), Key::Hash([0; 32]));
+        named_keys.insert(legacy_name(validator_2, 20), Key::Hash([0; 32]));
+        named_keys.insert(other_name.clone(), other_key);
+        let contract = StoredValue::Contract(Contract::new(
+            [2; 32],
+            [3; 32],
+            named_keys,
+            EntryPoints::default(),
+            ProtocolVersion::V1_0_0,
+        ));
+        let pos_hash = [4; 32];
+        let protocol_data = ProtocolData::new(WasmCosts::default(), 1, [0; 32], pos_hash, [0; 32]);
+
+        let (gs, root_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &[(Key::Hash(pos_hash), contract)])
+                .unwrap();
+        let view = gs.checkout(root_hash).unwrap().unwrap();
+        let mut tracking_copy = TrackingCopy::new(view);
+
+        let mut context = TrackingCopyMigrationContext::new(
+            correlation_id,
+            &mut tracking_copy,
+            &protocol_data,
+            b"seed",
+        );
+        migrate_pos_stakes(&mut context).expect("should migrate stakes");
+
+        let stakes_key = match context.read(&Key::Hash(pos_hash)).unwrap() {
+            Some(StoredValue::Contract(contract)) => {
+                let stakes_key = *contract
+                    .named_keys()
+                    .get(POS_STAKES)
+                    .expect("should have stakes");
+                let mut expected_named_keys = NamedKeys::new();
+                expected_named_keys.insert(other_name.clone(), other_key);
+                expected_named_keys.insert(POS_STAKES.to_string(), stakes_key);
+                assert_eq!(contract.named_keys(), &expected_named_keys);
+                stakes_key
+            }
+            other => panic!("expected contract, got {:?}", other),
+        };
+
+        let mut expected_stakes = BTreeMap::new();
+        expected_stakes.insert(validator_1, U512::from(10));
+        expected_stakes.insert(validator_2, U512::from(20));
+        let expected_value = StoredValue::CLValue(CLValue::from_t(expected_stakes).unwrap());
+        assert_eq!(context.read(&stakes_key).unwrap(), Some(expected_value));
+
+        // Migrating again leaves the contract untouched.
+        let contract_before = context.read(&Key::Hash(pos_hash)).unwrap();
+        migrate_pos_stakes(&mut context).expect("should skip migration");
+        assert_eq!(context.read(&Key::Hash(pos_hash)).unwrap(), contract_before);
+    }
 }
@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::AccountHash, ProtocolVersion, U512};
+
+pub enum ValidatorWeightsResult {
+    RootNotFound,
+    Success {
+        /// The stakes of the bonded validators, including any stake delegated to them.
+        validator_weights: BTreeMap<AccountHash, U512>,
+    },
+}
+
+/// A request for the weights of the validators bonded in the proof-of-stake contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorWeightsRequest {
+    state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+}
+
+impl ValidatorWeightsRequest {
+    pub fn new(state_hash: Blake2bHash, protocol_version: ProtocolVersion) -> Self {
+        ValidatorWeightsRequest {
+            state_hash,
+            protocol_version,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
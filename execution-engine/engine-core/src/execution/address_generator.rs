@@ -28,6 +28,11 @@ impl AddressGenerator {
         self.0.fill_bytes(&mut buff);
         buff
     }
+
+    /// Fills `dest` with the next bytes of the underlying pseudo-random stream.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
 }
 
 /// A builder for [`AddressGenerator`].
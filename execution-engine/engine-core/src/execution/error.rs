@@ -1,10 +1,13 @@
 use failure::Fail;
 use parity_wasm::elements;
+use wasmi::TrapKind;
 
 use engine_shared::TypeMismatch;
 use types::{
-    account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
-    bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError,
+    account::{
+        AddKeyFailure, RemoveKeyFailure, SetMainPurseFailure, SetThresholdFailure, UpdateKeyFailure,
+    },
+    bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError, ContractHash,
     ContractPackageHash, ContractVersionKey, Key, URef,
 };
 
@@ -56,6 +59,8 @@ pub enum Error {
     #[fail(display = "{}", _0)]
     SetThresholdFailure(SetThresholdFailure),
     #[fail(display = "{}", _0)]
+    SetMainPurseFailure(SetMainPurseFailure),
+    #[fail(display = "{}", _0)]
     SystemContract(system_contract_errors::Error),
     #[fail(display = "Deployment authorization failure")]
     DeploymentAuthorizationFailure,
@@ -89,6 +94,24 @@ pub enum Error {
         expected, actual
     )]
     InvalidKeyLength { expected: usize, actual: usize },
+    #[fail(display = "Read-only call attempted to modify global state")]
+    ReadOnlyViolation,
+    #[fail(display = "Contract call depth limit of {} exceeded", _0)]
+    CallDepthLimit(usize),
+    #[fail(display = "Wasm stack height limit exceeded")]
+    StackOverflow,
+    #[fail(display = "Reentrant call to non-reentrant contract")]
+    ReentrantCall(ContractHash),
+    #[fail(display = "Contract is disabled")]
+    ContractDisabled(ContractHash),
+    #[fail(display = "Execution effects exceeded the limit of {} bytes", _0)]
+    EffectsLimit(usize),
+    #[fail(display = "Value of {} bytes exceeds the limit of {} bytes", size, max)]
+    ValueTooLarge { size: usize, max: usize },
+    #[fail(display = "Named key name of {} bytes exceeds the limit of {} bytes", length, max)]
+    NamedKeyTooLong { length: usize, max: usize },
+    #[fail(display = "Memory range of {} bytes at {} is out of bounds", size, ptr)]
+    MemoryOutOfBounds { ptr: u32, size: u32 },
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -115,7 +138,12 @@ impl From<wasmi::Error> for Error {
             .and_then(|host_error| host_error.downcast_ref::<Error>())
         {
             Some(error) => error.clone(),
-            None => Error::Interpreter(error.into()),
+            None => match error {
+                wasmi::Error::Trap(ref trap) if matches!(trap.kind(), TrapKind::StackOverflow) => {
+                    Error::StackOverflow
+                }
+                _ => Error::Interpreter(error.into()),
+            },
         }
     }
 }
@@ -168,6 +196,12 @@ impl From<SetThresholdFailure> for Error {
     }
 }
 
+impl From<SetMainPurseFailure> for Error {
+    fn from(err: SetMainPurseFailure) -> Self {
+        Error::SetMainPurseFailure(err)
+    }
+}
+
 impl From<system_contract_errors::Error> for Error {
     fn from(error: system_contract_errors::Error) -> Self {
         Error::SystemContract(error)
@@ -2,16 +2,20 @@ use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 
 use log::warn;
 use parity_wasm::elements::Module;
-use wasmi::ModuleRef;
 
 use engine_shared::{
-    account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+    account::Account,
+    gas::Gas,
+    logging,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::{PreprocessingError, Preprocessor};
 use types::{
     account::AccountHash, bytesrepr::FromBytes, contracts::NamedKeys, AccessRights, BlockTime,
-    CLTyped, CLValue, ContractPackage, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion,
-    RuntimeArgs,
+    CLTyped, CLValue, CallStackElement, ContractPackage, EntryPoint, EntryPointType, Key, Phase,
+    ProtocolVersion, RuntimeArgs,
 };
 
 use crate::{
@@ -19,7 +23,9 @@ use crate::{
         execution_effect::ExecutionEffect, execution_result::ExecutionResult,
         system_contract_cache::SystemContractCache, EngineConfig,
     },
-    execution::{address_generator::AddressGenerator, Error},
+    execution::{
+        address_generator::AddressGenerator, DefaultWasmInstance, Error, ModuleCache, WasmInstance,
+    },
     runtime::{
         extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
         Runtime,
@@ -51,11 +57,15 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: Default::default(),
                     cost: $cost,
+                    trace: Vec::new(),
                 };
             }
         }
     };
     ($fn:expr, $cost:expr, $effect:expr) => {
+        on_fail_charge!($fn, $cost, $effect, Vec::new())
+    };
+    ($fn:expr, $cost:expr, $effect:expr, $trace:expr) => {
         match $fn {
             Ok(res) => res,
             Err(e) => {
@@ -65,26 +75,90 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: $effect,
                     cost: $cost,
+                    trace: $trace,
                 };
             }
         }
     };
 }
 
+#[derive(Debug)]
 pub struct Executor {
     config: EngineConfig,
+    module_cache: ModuleCache,
+    estimate_gas: bool,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        let module_cache = ModuleCache::new(
+            config.module_cache_max_entries(),
+            config.module_cache_max_size(),
+        );
+        Executor {
+            config,
+            module_cache,
+            estimate_gas: false,
+        }
     }
 
     pub fn config(&self) -> EngineConfig {
         self.config
     }
 
+    /// Returns an executor sharing this one's module cache, which records the host functions
+    /// invoked during execution.
+    pub fn with_execution_trace(&self) -> Executor {
+        Executor {
+            config: self.config.with_enable_execution_trace(true),
+            module_cache: self.module_cache.clone(),
+            estimate_gas: self.estimate_gas,
+        }
+    }
+
+    /// Returns an executor sharing this one's module cache, for which deploys are run with gas
+    /// limits bounded only by what the account could pay, and which records the host functions
+    /// invoked so that the gas consumed can be broken down.
+    pub fn with_gas_estimation(&self) -> Executor {
+        Executor {
+            estimate_gas: true,
+            ..self.with_execution_trace()
+        }
+    }
+
+    /// Returns whether deploys are being run to estimate their gas cost.
+    pub fn estimate_gas(&self) -> bool {
+        self.estimate_gas
+    }
+
+    /// Returns the cache of modules preprocessed by [`Executor::preprocess`].
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
+
+    /// Preprocesses `module_bytes` for execution under `protocol_version`, reusing the module from
+    /// an earlier call with the same bytes, wasm costs and protocol version if it is still cached.
+    pub fn preprocess(
+        &self,
+        preprocessor: &Preprocessor,
+        module_bytes: &[u8],
+        protocol_version: ProtocolVersion,
+    ) -> Result<Module, PreprocessingError> {
+        let key = (
+            Blake2bHash::new(module_bytes),
+            *preprocessor.wasm_costs(),
+            protocol_version,
+        );
+        if let Some(module) = self.module_cache.get(&key) {
+            return Ok(module);
+        }
+        let module = preprocessor.preprocess(module_bytes)?;
+        self.module_cache
+            .insert(key, module.clone(), module_bytes.len());
+        Ok(module)
+    }
+
     pub fn exec<R>(
         &self,
         module: Module,
@@ -104,17 +178,23 @@ impl Executor {
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
         contract_package: &ContractPackage,
+        call_stack_element: CallStackElement,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
+        let _log_context = logging::set_phase(phase);
+
         let entry_point_name = entry_point.name();
         let entry_point_type = entry_point.entry_point_type();
         let entry_point_access = entry_point.access();
 
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(module.clone(), protocol_version));
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            module.clone(),
+            protocol_version,
+            self.config.max_memory_pages()
+        ));
 
         let access_rights = {
             let keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -156,7 +236,14 @@ impl Executor {
             protocol_data,
         );
 
-        let mut runtime = Runtime::new(self.config, system_contract_cache, memory, module, context);
+        let mut runtime = Runtime::new(
+            self.config,
+            system_contract_cache,
+            memory,
+            module,
+            context,
+            vec![call_stack_element],
+        );
 
         let accounts_access_rights = {
             let keys: Vec<Key> = account.named_keys().values().cloned().collect();
@@ -182,6 +269,7 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            trace: Vec::new(),
                         };
                     }
                     Err(error) => {
@@ -189,6 +277,7 @@ impl Executor {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            trace: Vec::new(),
                         };
                     }
                 }
@@ -204,6 +293,7 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            trace: Vec::new(),
                         };
                     }
                     Err(error) => {
@@ -211,6 +301,7 @@ impl Executor {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            trace: Vec::new(),
                         };
                     }
                 }
@@ -218,14 +309,16 @@ impl Executor {
         }
 
         on_fail_charge!(
-            instance.invoke_export(entry_point_name, &[], &mut runtime),
+            instance.invoke(entry_point_name, &mut runtime),
             runtime.context().gas_counter(),
-            effects_snapshot
+            effects_snapshot,
+            runtime.trace()
         );
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
-            cost: runtime.context().gas_counter(),
+            cost: runtime.context().gas_counter_less_storage_refund(),
+            trace: runtime.trace(),
         }
     }
 
@@ -254,8 +347,13 @@ impl Executor {
         R::Error: Into<Error>,
         T: FromBytes + CLTyped,
     {
+        let _log_context = logging::set_phase(phase);
+
         match direct_system_contract_call {
-            DirectSystemContractCall::FinalizePayment => {
+            DirectSystemContractCall::FinalizePayment
+            | DirectSystemContractCall::Slash
+            | DirectSystemContractCall::Step
+            | DirectSystemContractCall::DistributeRewards => {
                 if protocol_data.proof_of_stake() != base_key.into_seed() {
                     panic!(
                         "{} should only be called with the proof of stake contract",
@@ -314,6 +412,7 @@ impl Executor {
                     effect: effect_snapshot.clone(),
                     cost: gas_counter,
                     error: e.into(),
+                    trace: Vec::new(),
                 }
                 .take_without_ret::<T>();
             })
@@ -334,30 +433,23 @@ impl Executor {
         }
 
         let (maybe_ret, maybe_error, revert_effect): (Option<T>, Option<Error>, bool) = {
-            match instance.invoke_export(
-                direct_system_contract_call.entry_point_name(),
-                &[],
-                &mut runtime,
-            ) {
-                Err(error) => match error.as_host_error() {
-                    Some(host_error) => match host_error.downcast_ref::<Error>().unwrap() {
-                        Error::Ret(ref ret_urefs) => match runtime.take_host_buffer() {
-                            Some(result) => match result.into_t() {
-                                Ok(ret) => {
-                                    let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
-                                        extract_access_rights_from_urefs(ret_urefs.clone());
-                                    runtime.access_rights_extend(ret_urefs_map);
-
-                                    (Some(ret), None, false)
-                                }
-                                Err(error) => (None, Some(Error::CLValue(error)), false),
-                            },
-                            None => (None, Some(Error::ExpectedReturnValue), false),
+            match instance.invoke(direct_system_contract_call.entry_point_name(), &mut runtime) {
+                Err(error) => match error {
+                    Error::Ret(ret_urefs) => match runtime.take_host_buffer() {
+                        Some(result) => match result.into_t() {
+                            Ok(ret) => {
+                                let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
+                                    extract_access_rights_from_urefs(ret_urefs);
+                                runtime.access_rights_extend(ret_urefs_map);
+
+                                (Some(ret), None, false)
+                            }
+                            Err(error) => (None, Some(Error::CLValue(error)), false),
                         },
-                        Error::Revert(api_error) => (None, Some(Error::Revert(*api_error)), true),
-                        error => (None, Some(error.clone()), true),
+                        None => (None, Some(Error::ExpectedReturnValue), false),
                     },
-                    None => (None, Some(Error::Interpreter(error.into())), false),
+                    Error::Revert(api_error) => (None, Some(Error::Revert(api_error)), true),
+                    error => (None, Some(error), true),
                 },
                 Ok(_) => {
                     match runtime.take_host_buffer() {
@@ -373,7 +465,10 @@ impl Executor {
 
         let runtime_context = runtime.context();
 
-        let cost = runtime_context.gas_counter();
+        let cost = match maybe_error {
+            Some(_) => runtime_context.gas_counter(),
+            None => runtime_context.gas_counter_less_storage_refund(),
+        };
 
         let effect = if revert_effect {
             effect_snapshot
@@ -381,13 +476,20 @@ impl Executor {
             runtime_context.effect()
         };
 
+        let trace = runtime.trace();
+
         let execution_result = match maybe_error {
             Some(error) => ExecutionResult::Failure {
                 error: error.into(),
                 effect,
                 cost,
+                trace,
+            },
+            None => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
             },
-            None => ExecutionResult::Success { effect, cost },
         };
 
         match maybe_ret {
@@ -422,6 +524,8 @@ impl Executor {
         R::Error: Into<Error>,
         T: FromBytes + CLTyped,
     {
+        let _log_context = logging::set_phase(phase);
+
         let mut named_keys: NamedKeys = account.named_keys().clone();
         let base_key = account.account_hash().into();
 
@@ -447,12 +551,11 @@ impl Executor {
             system_contract_cache,
         )?;
 
-        let error: wasmi::Error = match instance.invoke_export(entry_point_name, &[], &mut runtime)
-        {
+        let error: Error = match instance.invoke(entry_point_name, &mut runtime) {
             Err(error) => error,
             Ok(_) => {
                 // This duplicates the behavior of runtime sub_call.
-                // If `instance.invoke_export` returns `Ok` and the `host_buffer` is `None`, the
+                // If `instance.invoke` returns `Ok` and the `host_buffer` is `None`, the
                 // contract's execution succeeded but did not explicitly call `runtime::ret()`.
                 // Treat as though the execution returned the unit type `()` as per Rust
                 // functions which don't specify a return value.
@@ -463,16 +566,11 @@ impl Executor {
             }
         };
 
-        let return_value: CLValue = match error
-            .as_host_error()
-            .and_then(|host_error| host_error.downcast_ref::<Error>())
-        {
-            Some(Error::Ret(_)) => runtime
+        let return_value: CLValue = match error {
+            Error::Ret(_) => runtime
                 .take_host_buffer()
                 .ok_or(Error::ExpectedReturnValue)?,
-            Some(Error::Revert(code)) => return Err(Error::Revert(*code)),
-            Some(error) => return Err(error.clone()),
-            _ => return Err(Error::Interpreter(error.into())),
+            error => return Err(error),
         };
 
         let ret = return_value.into_t()?;
@@ -501,7 +599,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
-    ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
+    ) -> Result<(DefaultWasmInstance, Runtime<'a, R>), Error>
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
@@ -535,7 +633,11 @@ impl Executor {
             protocol_data,
         );
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (instance, memory) = instance_and_memory(
+            module.clone(),
+            protocol_version,
+            self.config.max_memory_pages(),
+        )?;
 
         let runtime = Runtime::new(
             self.config,
@@ -543,6 +645,7 @@ impl Executor {
             memory,
             module,
             runtime_context,
+            vec![CallStackElement::session(account.account_hash())],
         );
 
         Ok((instance, runtime))
@@ -553,6 +656,9 @@ pub enum DirectSystemContractCall {
     FinalizePayment,
     CreatePurse,
     Transfer,
+    Slash,
+    Step,
+    DistributeRewards,
 }
 
 impl DirectSystemContractCall {
@@ -561,6 +667,9 @@ impl DirectSystemContractCall {
             DirectSystemContractCall::FinalizePayment => "finalize_payment",
             DirectSystemContractCall::CreatePurse => "create",
             DirectSystemContractCall::Transfer => "transfer",
+            DirectSystemContractCall::Slash => "slash",
+            DirectSystemContractCall::Step => "step",
+            DirectSystemContractCall::DistributeRewards => "distribute_rewards",
         }
     }
 
@@ -580,7 +689,10 @@ impl DirectSystemContractCall {
     {
         let entry_point_name = self.entry_point_name();
         let result = match self {
-            DirectSystemContractCall::FinalizePayment => runtime.call_host_proof_of_stake(
+            DirectSystemContractCall::FinalizePayment
+            | DirectSystemContractCall::Slash
+            | DirectSystemContractCall::Step
+            | DirectSystemContractCall::DistributeRewards => runtime.call_host_proof_of_stake(
                 protocol_version,
                 entry_point_name,
                 named_keys,
@@ -602,12 +714,14 @@ impl DirectSystemContractCall {
                 Ok(ret) => ExecutionResult::Success {
                     effect: runtime.context().effect(),
                     cost: runtime.context().gas_counter(),
+                    trace: Vec::new(),
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
                     error: Error::CLValue(error).into(),
                     effect: execution_effect,
                     cost: runtime.context().gas_counter(),
+                    trace: Vec::new(),
                 }
                 .take_without_ret(),
             },
@@ -615,6 +729,7 @@ impl DirectSystemContractCall {
                 error: error.into(),
                 effect: execution_effect,
                 cost: runtime.context().gas_counter(),
+                trace: Vec::new(),
             }
             .take_without_ret(),
         }
@@ -2,11 +2,20 @@ mod address_generator;
 mod error;
 #[macro_use]
 mod executor;
+mod module_cache;
 #[cfg(test)]
 mod tests;
+mod trace;
+mod wasm_engine;
 
 pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
     error::Error,
     executor::{DirectSystemContractCall, Executor},
+    module_cache::{ModuleCache, ModuleCacheKey},
+    trace::HostCall,
+    wasm_engine::{
+        nth_arg, DefaultWasmEngine, DefaultWasmInstance, DefaultWasmMemory, FromWasmValue,
+        HostFunctions, WasmEngine, WasmInstance, WasmMemory, WasmValue, WasmiEngine,
+    },
 };
@@ -0,0 +1,185 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+use parity_wasm::elements::Module;
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::ProtocolVersion;
+
+/// Identifies a preprocessed module by the hash of its original bytes, the costs its gas metering
+/// was instrumented with and the protocol version whose host functions its imports were checked
+/// against.
+pub type ModuleCacheKey = (Blake2bHash, WasmCosts, ProtocolVersion);
+
+#[derive(Debug)]
+struct CachedModule {
+    module: Module,
+    size: usize,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    modules: LinkedHashMap<ModuleCacheKey, CachedModule>,
+    total_size: usize,
+}
+
+/// A least-recently-used cache of preprocessed modules.
+///
+/// The cache holds at most `max_entries` modules, whose original bytes add up to at most
+/// `max_size` bytes.  Clones share the same underlying cache.
+#[derive(Clone, Debug)]
+pub struct ModuleCache {
+    max_entries: usize,
+    max_size: usize,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl ModuleCache {
+    pub fn new(max_entries: usize, max_size: usize) -> Self {
+        ModuleCache {
+            max_entries,
+            max_size,
+            inner: Default::default(),
+        }
+    }
+
+    /// Returns a clone of the module cached under `key`, marking it as the most recently used.
+    pub fn get(&self, key: &ModuleCacheKey) -> Option<Module> {
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .modules
+            .get_refresh(key)
+            .map(|cached_module| cached_module.module.clone())
+    }
+
+    /// Inserts `module`, preprocessed from `size` bytes, under `key`.
+    ///
+    /// The least recently used modules are evicted until the cache is back within its limits.  A
+    /// module which alone exceeds `max_size` is not cached.
+    pub fn insert(&self, key: ModuleCacheKey, module: Module, size: usize) {
+        if self.max_entries == 0 || size > self.max_size {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(replaced) = inner.modules.insert(key, CachedModule { module, size }) {
+            inner.total_size -= replaced.size;
+        }
+        inner.total_size += size;
+        while inner.modules.len() > self.max_entries || inner.total_size > self.max_size {
+            match inner.modules.pop_front() {
+                Some((_, evicted)) => inner.total_size -= evicted.size,
+                None => break,
+            }
+        }
+    }
+
+    /// Returns the number of cached modules.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().modules.len()
+    }
+
+    /// Returns `true` if no modules are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{Module, ModuleNameSubsection, NameSection, Section};
+
+    use engine_shared::newtypes::Blake2bHash;
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+    use types::ProtocolVersion;
+
+    use super::{ModuleCache, ModuleCacheKey};
+
+    fn key(byte: u8) -> ModuleCacheKey {
+        (
+            Blake2bHash::new(&[byte]),
+            WasmCosts::default(),
+            ProtocolVersion::V1_0_0,
+        )
+    }
+
+    fn named_module(name: &str) -> Module {
+        let section = NameSection::new(Some(ModuleNameSubsection::new(name)), None, None);
+        Module::new(vec![Section::Name(section)])
+    }
+
+    #[test]
+    fn should_get_inserted_module() {
+        let cache = ModuleCache::new(2, 100);
+        let module = named_module("a");
+
+        cache.insert(key(1), module.clone(), 10);
+
+        assert_eq!(cache.get(&key(1)), Some(module));
+        assert_eq!(cache.get(&key(2)), None);
+    }
+
+    #[test]
+    fn should_distinguish_wasm_costs() {
+        let cache = ModuleCache::new(2, 100);
+        cache.insert(key(1), named_module("a"), 10);
+
+        let (hash, wasm_costs, protocol_version) = key(1);
+        let other_costs = WasmCosts {
+            regular: wasm_costs.regular + 1,
+            ..wasm_costs
+        };
+
+        assert_eq!(cache.get(&(hash, other_costs, protocol_version)), None);
+    }
+
+    #[test]
+    fn should_distinguish_protocol_versions() {
+        let cache = ModuleCache::new(2, 100);
+        cache.insert(key(1), named_module("a"), 10);
+
+        let (hash, wasm_costs, _) = key(1);
+        let other_version = ProtocolVersion::from_parts(1, 1, 0);
+
+        assert_eq!(cache.get(&(hash, wasm_costs, other_version)), None);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = ModuleCache::new(2, 100);
+        cache.insert(key(1), named_module("a"), 10);
+        cache.insert(key(2), named_module("b"), 10);
+
+        // Using the first module makes the second the least recently used one.
+        assert!(cache.get(&key(1)).is_some());
+        cache.insert(key(3), named_module("c"), 10);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(2)).is_none());
+        assert!(cache.get(&key(3)).is_some());
+    }
+
+    #[test]
+    fn should_evict_until_within_max_size() {
+        let cache = ModuleCache::new(10, 100);
+        cache.insert(key(1), named_module("a"), 40);
+        cache.insert(key(2), named_module("b"), 40);
+        cache.insert(key(3), named_module("c"), 40);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(1)).is_none());
+
+        // Too large to be cached at all.
+        cache.insert(key(4), named_module("d"), 101);
+        assert!(cache.get(&key(4)).is_none());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_max_entries() {
+        let cache = ModuleCache::new(0, 100);
+        cache.insert(key(1), named_module("a"), 10);
+        assert!(cache.is_empty());
+    }
+}
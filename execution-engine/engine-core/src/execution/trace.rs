@@ -0,0 +1,73 @@
+use engine_shared::gas::Gas;
+use types::Phase;
+
+use super::{Error, WasmValue};
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// A host function invoked by Wasm code, recorded when execution tracing is enabled.
+#[derive(Clone, Debug)]
+pub struct HostCall {
+    function: FunctionIndex,
+    phase: Phase,
+    call_depth: usize,
+    args: Vec<WasmValue>,
+    gas: Gas,
+    error: Option<Error>,
+}
+
+impl HostCall {
+    pub(crate) fn new(
+        function: FunctionIndex,
+        phase: Phase,
+        call_depth: usize,
+        args: Vec<WasmValue>,
+    ) -> Self {
+        HostCall {
+            function,
+            phase,
+            call_depth,
+            args,
+            gas: Gas::default(),
+            error: None,
+        }
+    }
+
+    /// Records the outcome of the call once the host function has returned.
+    pub(crate) fn complete(&mut self, gas: Gas, error: Option<&Error>) {
+        self.gas = gas;
+        self.error = error.cloned();
+    }
+
+    /// Returns the host function which was invoked.
+    pub fn function(&self) -> FunctionIndex {
+        self.function
+    }
+
+    /// Returns the phase of the deploy's execution in which the host function was invoked.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns the depth of the contract call the host function was invoked from, where session
+    /// code runs at depth zero.
+    pub fn call_depth(&self) -> usize {
+        self.call_depth
+    }
+
+    /// Returns the arguments passed by the Wasm code.
+    pub fn args(&self) -> &[WasmValue] {
+        &self.args
+    }
+
+    /// Returns the gas charged by the call, including that of any contract it called.
+    pub fn gas(&self) -> Gas {
+        self.gas
+    }
+
+    /// Returns the error the call trapped with, if any.
+    ///
+    /// Note that `ret` always traps with [`Error::Ret`], as this is how it unwinds the caller.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+}
@@ -0,0 +1,427 @@
+//! The backend which instantiates and runs preprocessed Wasm modules.
+//!
+//! The runtime only sees the backend through the traits of this module: it reads and writes the
+//! module's linear memory through a [`WasmMemory`] and handles calls of imported functions as
+//! [`HostFunctions`], exchanging [`WasmValue`]s.  A backend adapts these to its own types.
+
+use std::convert::TryFrom;
+
+use parity_wasm::elements::Module;
+use wasmi::{
+    memory_units::Bytes, Externals, ImportsBuilder, MemoryInstance, MemoryRef, ModuleInstance,
+    ModuleRef, RuntimeArgs, RuntimeValue, Trap,
+};
+
+use types::ProtocolVersion;
+
+use crate::{
+    execution::Error,
+    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
+};
+
+/// The backend used by the executor and runtime.
+pub type DefaultWasmEngine = WasmiEngine;
+
+/// An instance of a module created by the [`DefaultWasmEngine`].
+pub type DefaultWasmInstance = <DefaultWasmEngine as WasmEngine>::Instance;
+
+/// The memory of an instance created by the [`DefaultWasmEngine`].
+pub type DefaultWasmMemory = <DefaultWasmEngine as WasmEngine>::Memory;
+
+/// A value passed between a module and the host.  Modules may not use floating-point
+/// instructions, so only integers are exchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WasmValue {
+    I32(i32),
+    I64(i64),
+}
+
+/// Conversion of a [`WasmValue`] into the type a host function expects as its argument.
+pub trait FromWasmValue: Sized {
+    /// Returns `None` if `value` is of the wrong Wasm type.
+    fn from_wasm_value(value: WasmValue) -> Option<Self>;
+}
+
+impl FromWasmValue for i32 {
+    fn from_wasm_value(value: WasmValue) -> Option<Self> {
+        match value {
+            WasmValue::I32(value) => Some(value),
+            WasmValue::I64(_) => None,
+        }
+    }
+}
+
+impl FromWasmValue for u32 {
+    fn from_wasm_value(value: WasmValue) -> Option<Self> {
+        i32::from_wasm_value(value).map(|value| value as u32)
+    }
+}
+
+impl FromWasmValue for u8 {
+    fn from_wasm_value(value: WasmValue) -> Option<Self> {
+        i32::from_wasm_value(value).map(|value| value as u8)
+    }
+}
+
+impl FromWasmValue for i64 {
+    fn from_wasm_value(value: WasmValue) -> Option<Self> {
+        match value {
+            WasmValue::I64(value) => Some(value),
+            WasmValue::I32(_) => None,
+        }
+    }
+}
+
+impl FromWasmValue for u64 {
+    fn from_wasm_value(value: WasmValue) -> Option<Self> {
+        i64::from_wasm_value(value).map(|value| value as u64)
+    }
+}
+
+/// Returns the `index`th of the arguments `args` passed to a host function.
+pub fn nth_arg<T: FromWasmValue>(args: &[WasmValue], index: usize) -> Result<T, Error> {
+    args.get(index)
+        .copied()
+        .and_then(T::from_wasm_value)
+        .ok_or_else(|| Error::Interpreter(format!("Invalid host function argument {}", index)))
+}
+
+/// The linear memory of an instantiated module.
+pub trait WasmMemory: Clone {
+    /// Returns the `size` bytes starting at `offset`.
+    fn get(&self, offset: u32, size: usize) -> Result<Vec<u8>, Error>;
+
+    /// Writes `bytes` starting at `offset`.
+    fn set(&self, offset: u32, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Returns the current size of the memory in bytes.
+    fn size(&self) -> u64;
+}
+
+/// The functions a module imports from the host, dispatched by the index they were resolved to.
+pub trait HostFunctions {
+    /// Calls the host function `index` with `args`.  An error aborts execution of the module.
+    fn call_host_function(
+        &mut self,
+        index: usize,
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error>;
+}
+
+/// A backend able to instantiate preprocessed Wasm modules.
+pub trait WasmEngine {
+    type Instance: WasmInstance;
+    type Memory: WasmMemory;
+
+    /// Instantiates `module`, resolving its imports against the host functions of
+    /// `protocol_version`, and returns the instance along with the memory it imports.
+    fn instantiate(
+        &self,
+        module: Module,
+        protocol_version: ProtocolVersion,
+        max_memory_pages: u32,
+    ) -> Result<(Self::Instance, Self::Memory), Error>;
+}
+
+/// An instantiated Wasm module.
+pub trait WasmInstance {
+    /// Invokes the exported function `name`, which takes no arguments, with calls of imported
+    /// functions handled by `host_functions`.
+    fn invoke<H: HostFunctions>(
+        &self,
+        name: &str,
+        host_functions: &mut H,
+    ) -> Result<Option<WasmValue>, Error>;
+}
+
+/// The `wasmi` interpreter.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WasmiEngine;
+
+impl WasmEngine for WasmiEngine {
+    type Instance = ModuleRef;
+    type Memory = MemoryRef;
+
+    fn instantiate(
+        &self,
+        module: Module,
+        protocol_version: ProtocolVersion,
+        max_memory_pages: u32,
+    ) -> Result<(ModuleRef, MemoryRef), Error> {
+        let module = wasmi::Module::from_parity_wasm_module(module)?;
+        let resolver = create_module_resolver(protocol_version, max_memory_pages)?;
+        let mut imports = ImportsBuilder::new();
+        imports.push_resolver("env", &resolver);
+        let not_started_module = ModuleInstance::new(&module, &imports)?;
+        if not_started_module.has_start() {
+            return Err(Error::UnsupportedWasmStart);
+        }
+        let instance = not_started_module.not_started_instance().clone();
+        let memory = resolver.memory_ref()?;
+        Ok((instance, memory))
+    }
+}
+
+impl WasmInstance for ModuleRef {
+    fn invoke<H: HostFunctions>(
+        &self,
+        name: &str,
+        host_functions: &mut H,
+    ) -> Result<Option<WasmValue>, Error> {
+        let result = self.invoke_export(name, &[], &mut WasmiExternals(host_functions))?;
+        result.map(WasmValue::try_from).transpose()
+    }
+}
+
+impl WasmMemory for MemoryRef {
+    fn get(&self, offset: u32, size: usize) -> Result<Vec<u8>, Error> {
+        MemoryInstance::get(self, offset, size).map_err(Into::into)
+    }
+
+    fn set(&self, offset: u32, bytes: &[u8]) -> Result<(), Error> {
+        MemoryInstance::set(self, offset, bytes).map_err(Into::into)
+    }
+
+    fn size(&self) -> u64 {
+        Bytes::from(self.current_size()).0 as u64
+    }
+}
+
+impl TryFrom<RuntimeValue> for WasmValue {
+    type Error = Error;
+
+    fn try_from(value: RuntimeValue) -> Result<Self, Error> {
+        match value {
+            RuntimeValue::I32(value) => Ok(WasmValue::I32(value)),
+            RuntimeValue::I64(value) => Ok(WasmValue::I64(value)),
+            RuntimeValue::F32(_) | RuntimeValue::F64(_) => Err(Error::Interpreter(format!(
+                "Unsupported floating-point value {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl From<WasmValue> for RuntimeValue {
+    fn from(value: WasmValue) -> Self {
+        match value {
+            WasmValue::I32(value) => RuntimeValue::I32(value),
+            WasmValue::I64(value) => RuntimeValue::I64(value),
+        }
+    }
+}
+
+/// Adapts [`HostFunctions`] to the `wasmi` interface for calls of imported functions.
+struct WasmiExternals<'a, H>(&'a mut H);
+
+impl<'a, H: HostFunctions> Externals for WasmiExternals<'a, H> {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        let args = args
+            .as_ref()
+            .iter()
+            .copied()
+            .map(WasmValue::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let result = self.0.call_host_function(index, &args)?;
+        Ok(result.map(RuntimeValue::from))
+    }
+}
+
+/// Conformance tests every backend has to pass: running the same module has to charge the same
+/// gas, leave the same memory and end with the same result as under the reference `wasmi`
+/// interpreter.  [`conformance_tests`] runs them for each backend.
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use parity_wasm::{
+        builder,
+        elements::{BlockType, Instruction, Instructions, Local, ValueType},
+    };
+
+    use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor, MEM_PAGES};
+
+    use super::*;
+    use crate::resolvers::v1_function_index::FunctionIndex;
+
+    const ENTRY_POINT: &str = "call";
+    const MEMORY_CHECKED: usize = 8;
+
+    /// The gas charged, the result and the start of the memory after running a module.
+    type Outcome = (u64, Result<(), String>, Vec<u8>);
+
+    /// Handles the host functions injected by the preprocessor, counting the gas charged.
+    #[derive(Default)]
+    struct GasCounter {
+        gas: u64,
+    }
+
+    impl HostFunctions for GasCounter {
+        fn call_host_function(
+            &mut self,
+            index: usize,
+            args: &[WasmValue],
+        ) -> Result<Option<WasmValue>, Error> {
+            match FunctionIndex::try_from(index).expect("should be a host function") {
+                FunctionIndex::GasFuncIndex => {
+                    let amount: u32 = nth_arg(args, 0)?;
+                    self.gas += u64::from(amount);
+                    Ok(None)
+                }
+                FunctionIndex::StackOverflowFuncIndex => Err(Error::StackOverflow),
+                function_index => panic!("unexpected host function {:?}", function_index),
+            }
+        }
+    }
+
+    /// Builds a module exporting [`ENTRY_POINT`] with the given body, which may call itself as
+    /// function 0.
+    fn module(locals: Vec<Local>, instructions: Vec<Instruction>) -> Module {
+        builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_locals(locals)
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .memory()
+            .with_min(1)
+            .build()
+            .export()
+            .field(ENTRY_POINT)
+            .internal()
+            .func(0)
+            .build()
+            .build()
+    }
+
+    fn run<W: WasmEngine>(engine: &W, module: &Module) -> Outcome {
+        let module_bytes = parity_wasm::serialize(module.clone()).expect("should serialize");
+        let module = Preprocessor::new(WasmCosts::default())
+            .preprocess(&module_bytes)
+            .expect("should preprocess");
+        let (instance, memory) = engine
+            .instantiate(module, ProtocolVersion::V1_0_0, MEM_PAGES)
+            .expect("should instantiate");
+
+        let mut gas_counter = GasCounter::default();
+        let result = instance
+            .invoke(ENTRY_POINT, &mut gas_counter)
+            .map(|_| ())
+            .map_err(|error| error.to_string());
+        assert!(memory.size() >= MEMORY_CHECKED as u64);
+        let memory = memory.get(0, MEMORY_CHECKED).expect("should read memory");
+        (gas_counter.gas, result, memory)
+    }
+
+    fn assert_conforms<W: WasmEngine>(engine: &W, module: Module) -> Outcome {
+        let expected = run(&WasmiEngine, &module);
+        let actual = run(engine, &module);
+        assert_eq!(actual, expected);
+        actual
+    }
+
+    /// Counts down from 100 in a loop.
+    fn check_metered_loop<W: WasmEngine>(engine: &W) {
+        let (gas, result, _memory) = assert_conforms(
+            engine,
+            module(
+                vec![Local::new(1, ValueType::I32)],
+                vec![
+                    Instruction::I32Const(100),
+                    Instruction::SetLocal(0),
+                    Instruction::Loop(BlockType::NoResult),
+                    Instruction::GetLocal(0),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::TeeLocal(0),
+                    Instruction::BrIf(0),
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            ),
+        );
+        assert!(gas > 0);
+        assert_eq!(result, Ok(()));
+    }
+
+    /// Stores a value in memory, which the host reads back afterwards.
+    fn check_memory_store<W: WasmEngine>(engine: &W) {
+        let (_gas, result, memory) = assert_conforms(
+            engine,
+            module(
+                vec![],
+                vec![
+                    Instruction::I32Const(4),
+                    Instruction::I32Const(0x0403_0201),
+                    Instruction::I32Store(2, 0),
+                    Instruction::End,
+                ],
+            ),
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(memory, vec![0, 0, 0, 0, 1, 2, 3, 4]);
+    }
+
+    /// Traps on `unreachable`.
+    fn check_trap<W: WasmEngine>(engine: &W) {
+        let (_gas, result, _memory) = assert_conforms(
+            engine,
+            module(vec![], vec![Instruction::Unreachable, Instruction::End]),
+        );
+        assert!(result.is_err());
+    }
+
+    /// Recurses until the stack height limit is exceeded.
+    fn check_stack_overflow<W: WasmEngine>(engine: &W) {
+        let (_gas, result, _memory) = assert_conforms(
+            engine,
+            module(
+                vec![Local::new(16, ValueType::I64)],
+                vec![Instruction::Call(0), Instruction::End],
+            ),
+        );
+        assert_eq!(result, Err(Error::StackOverflow.to_string()));
+    }
+
+    /// Generates a module of conformance tests for each of the given backends.
+    macro_rules! conformance_tests {
+        ($($name:ident: $engine:expr),* $(,)?) => {
+            $(
+                mod $name {
+                    use super::*;
+
+                    #[test]
+                    fn should_meter_loop() {
+                        check_metered_loop(&$engine);
+                    }
+
+                    #[test]
+                    fn should_store_to_memory() {
+                        check_memory_store(&$engine);
+                    }
+
+                    #[test]
+                    fn should_trap() {
+                        check_trap(&$engine);
+                    }
+
+                    #[test]
+                    fn should_report_stack_overflow() {
+                        check_stack_overflow(&$engine);
+                    }
+                }
+            )*
+        };
+    }
+
+    conformance_tests! {
+        wasmi_engine: WasmiEngine,
+    }
+}
@@ -0,0 +1,515 @@
+//! The host functions which Wasm modules can import from the `env` module, by protocol version.
+
+use std::collections::BTreeSet;
+
+use wasmi::{Signature, ValueType};
+
+use types::ProtocolVersion;
+
+use super::v1_function_index::FunctionIndex;
+
+/// A host function, taking only `i32` arguments.
+#[derive(Debug, Clone, Copy)]
+pub struct HostFunction {
+    name: &'static str,
+    param_count: usize,
+    return_type: Option<ValueType>,
+    index: FunctionIndex,
+    since: ProtocolVersion,
+}
+
+impl HostFunction {
+    /// Creates a host function available from protocol version `since` on.
+    const fn new(
+        name: &'static str,
+        param_count: usize,
+        return_type: Option<ValueType>,
+        index: FunctionIndex,
+        since: ProtocolVersion,
+    ) -> Self {
+        HostFunction {
+            name,
+            param_count,
+            return_type,
+            index,
+            since,
+        }
+    }
+
+    /// Creates a host function available from protocol version 1.0.0 on.
+    const fn v1(
+        name: &'static str,
+        param_count: usize,
+        return_type: Option<ValueType>,
+        index: FunctionIndex,
+    ) -> Self {
+        HostFunction::new(
+            name,
+            param_count,
+            return_type,
+            index,
+            ProtocolVersion::V1_0_0,
+        )
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn signature(&self) -> Signature {
+        Signature::new(vec![ValueType::I32; self.param_count], self.return_type)
+    }
+
+    pub fn index(&self) -> FunctionIndex {
+        self.index
+    }
+
+    /// Returns the first protocol version under which the host function can be imported.
+    pub fn since(&self) -> ProtocolVersion {
+        self.since
+    }
+}
+
+/// Every host function, including those only available under later protocol versions.
+///
+/// A host function added by a new protocol version gets that version as its `since`, so that
+/// modules deployed under earlier versions can't link against it.
+static HOST_FUNCTIONS: &[HostFunction] = &[
+    HostFunction::v1(
+        "read_value",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::ReadFuncIndex,
+    ),
+    HostFunction::v1(
+        "read_value_local",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::ReadLocalFuncIndex,
+    ),
+    HostFunction::v1(
+        "load_named_keys",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::LoadNamedKeysFuncIndex,
+    ),
+    HostFunction::v1("write", 4, None, FunctionIndex::WriteFuncIndex),
+    HostFunction::v1("write_local", 4, None, FunctionIndex::WriteLocalFuncIndex),
+    HostFunction::v1("add", 4, None, FunctionIndex::AddFuncIndex),
+    HostFunction::new(
+        "counter_inc",
+        3,
+        None,
+        FunctionIndex::CounterIncIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::v1("new_uref", 3, None, FunctionIndex::NewFuncIndex),
+    HostFunction::v1("ret", 2, None, FunctionIndex::RetFuncIndex),
+    HostFunction::v1(
+        "get_key",
+        5,
+        Some(ValueType::I32),
+        FunctionIndex::GetKeyFuncIndex,
+    ),
+    HostFunction::v1(
+        "has_key",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::HasKeyFuncIndex,
+    ),
+    HostFunction::v1("put_key", 4, None, FunctionIndex::PutKeyFuncIndex),
+    HostFunction::v1("gas", 1, None, FunctionIndex::GasFuncIndex),
+    HostFunction::v1(
+        "stack_overflow",
+        0,
+        None,
+        FunctionIndex::StackOverflowFuncIndex,
+    ),
+    HostFunction::v1(
+        "is_valid_uref",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::IsValidURefFnIndex,
+    ),
+    HostFunction::v1("revert", 1, None, FunctionIndex::RevertFuncIndex),
+    HostFunction::v1(
+        "add_associated_key",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::AddAssociatedKeyFuncIndex,
+    ),
+    HostFunction::v1(
+        "remove_associated_key",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::RemoveAssociatedKeyFuncIndex,
+    ),
+    HostFunction::v1(
+        "update_associated_key",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::UpdateAssociatedKeyFuncIndex,
+    ),
+    HostFunction::v1(
+        "set_action_threshold",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::SetActionThresholdFuncIndex,
+    ),
+    HostFunction::v1("remove_key", 2, None, FunctionIndex::RemoveKeyFuncIndex),
+    HostFunction::v1(
+        "get_caller",
+        1,
+        Some(ValueType::I32),
+        FunctionIndex::GetCallerIndex,
+    ),
+    HostFunction::v1("get_blocktime", 1, None, FunctionIndex::GetBlocktimeIndex),
+    HostFunction::v1(
+        "create_purse",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::CreatePurseIndex,
+    ),
+    HostFunction::v1(
+        "transfer_to_account",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::TransferToAccountIndex,
+    ),
+    HostFunction::v1(
+        "transfer_from_purse_to_account",
+        6,
+        Some(ValueType::I32),
+        FunctionIndex::TransferFromPurseToAccountIndex,
+    ),
+    HostFunction::new(
+        "transfer_from_purse_to_accounts",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::TransferFromPurseToAccountsIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::v1(
+        "transfer_from_purse_to_purse",
+        6,
+        Some(ValueType::I32),
+        FunctionIndex::TransferFromPurseToPurseIndex,
+    ),
+    HostFunction::v1(
+        "get_balance",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::GetBalanceIndex,
+    ),
+    HostFunction::v1("get_phase", 1, None, FunctionIndex::GetPhaseIndex),
+    HostFunction::v1(
+        "get_system_contract",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::GetSystemContractIndex,
+    ),
+    HostFunction::v1("get_main_purse", 1, None, FunctionIndex::GetMainPurseIndex),
+    HostFunction::v1(
+        "read_host_buffer",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::ReadHostBufferIndex,
+    ),
+    HostFunction::v1(
+        "create_contract_package_at_hash",
+        2,
+        None,
+        FunctionIndex::CreateContractPackageAtHash,
+    ),
+    HostFunction::v1(
+        "create_contract_user_group",
+        8,
+        Some(ValueType::I32),
+        FunctionIndex::CreateContractUserGroup,
+    ),
+    HostFunction::v1(
+        "add_contract_version",
+        10,
+        Some(ValueType::I32),
+        FunctionIndex::AddContractVersion,
+    ),
+    HostFunction::v1(
+        "disable_contract_version",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::DisableContractVersion,
+    ),
+    HostFunction::new(
+        "disable_contract",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::DisableContractIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "set_non_reentrant",
+        5,
+        Some(ValueType::I32),
+        FunctionIndex::SetNonReentrantIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::v1(
+        "call_contract",
+        7,
+        Some(ValueType::I32),
+        FunctionIndex::CallContractFuncIndex,
+    ),
+    HostFunction::v1(
+        "call_versioned_contract",
+        9,
+        Some(ValueType::I32),
+        FunctionIndex::CallVersionedContract,
+    ),
+    HostFunction::v1(
+        "get_named_arg_size",
+        3,
+        Some(ValueType::I32),
+        FunctionIndex::GetRuntimeArgsizeIndex,
+    ),
+    HostFunction::v1(
+        "get_named_arg",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::GetRuntimeArgIndex,
+    ),
+    HostFunction::v1(
+        "remove_contract_user_group",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::RemoveContractUserGroupIndex,
+    ),
+    HostFunction::v1(
+        "provision_contract_user_group_uref",
+        5,
+        Some(ValueType::I32),
+        FunctionIndex::ExtendContractUserGroupURefsIndex,
+    ),
+    HostFunction::v1(
+        "remove_contract_user_group_urefs",
+        6,
+        Some(ValueType::I32),
+        FunctionIndex::RemoveContractUserGroupURefsIndex,
+    ),
+    HostFunction::new(
+        "random_bytes",
+        2,
+        None,
+        FunctionIndex::RandomBytesIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "u512_mul_div",
+        7,
+        Some(ValueType::I32),
+        FunctionIndex::U512MulDivIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "emit_event",
+        4,
+        None,
+        FunctionIndex::EmitEventIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "dictionary_new",
+        1,
+        None,
+        FunctionIndex::DictionaryNewIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "dictionary_get",
+        5,
+        Some(ValueType::I32),
+        FunctionIndex::DictionaryGetIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "dictionary_put",
+        6,
+        None,
+        FunctionIndex::DictionaryPutIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "blake2b",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::Blake2bIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "keccak256",
+        4,
+        Some(ValueType::I32),
+        FunctionIndex::Keccak256Index,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "verify_ed25519",
+        6,
+        Some(ValueType::I32),
+        FunctionIndex::VerifyEd25519Index,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "verify_secp256k1",
+        6,
+        Some(ValueType::I32),
+        FunctionIndex::VerifySecp256k1Index,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "ret_without_transfer",
+        2,
+        None,
+        FunctionIndex::RetWithoutTransferIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "call_contract_read_only",
+        7,
+        Some(ValueType::I32),
+        FunctionIndex::CallContractReadOnlyFuncIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "load_named_keys_paged",
+        5,
+        Some(ValueType::I32),
+        FunctionIndex::LoadNamedKeysPagedFuncIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "set_main_purse",
+        2,
+        Some(ValueType::I32),
+        FunctionIndex::SetMainPurseIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "register_system_contract",
+        4,
+        None,
+        FunctionIndex::RegisterSystemContractIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "get_gas_price",
+        1,
+        None,
+        FunctionIndex::GetGasPriceIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "get_call_stack",
+        1,
+        Some(ValueType::I32),
+        FunctionIndex::GetCallStackIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "get_deploy_hash",
+        1,
+        None,
+        FunctionIndex::GetDeployHashIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "is_system_caller",
+        0,
+        Some(ValueType::I32),
+        FunctionIndex::IsSystemCallerIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "write_and_return_old",
+        5,
+        Some(ValueType::I32),
+        FunctionIndex::WriteAndReturnOldFuncIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "compare_and_swap",
+        6,
+        Some(ValueType::I32),
+        FunctionIndex::CompareAndSwapFuncIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    HostFunction::new(
+        "delete",
+        2,
+        None,
+        FunctionIndex::DeleteFuncIndex,
+        ProtocolVersion::V1_1_0,
+    ),
+    #[cfg(feature = "test-support")]
+    HostFunction::v1("print", 2, None, FunctionIndex::PrintIndex),
+];
+
+/// Returns the host function named `name` if it is available under `protocol_version`.
+pub fn find(name: &str, protocol_version: ProtocolVersion) -> Option<&'static HostFunction> {
+    HOST_FUNCTIONS
+        .iter()
+        .find(|host_function| host_function.name == name && host_function.since <= protocol_version)
+}
+
+/// Returns the names of the host functions available under `protocol_version`.
+pub fn names(protocol_version: ProtocolVersion) -> BTreeSet<String> {
+    HOST_FUNCTIONS
+        .iter()
+        .filter(|host_function| host_function.since <= protocol_version)
+        .map(|host_function| host_function.name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use types::ProtocolVersion;
+
+    use super::{find, names, HOST_FUNCTIONS};
+
+    #[test]
+    fn should_have_unique_names_and_indices() {
+        let names: BTreeSet<&str> = HOST_FUNCTIONS
+            .iter()
+            .map(|host_function| host_function.name)
+            .collect();
+        assert_eq!(names.len(), HOST_FUNCTIONS.len());
+
+        let indices: BTreeSet<usize> = HOST_FUNCTIONS
+            .iter()
+            .map(|host_function| host_function.index.into())
+            .collect();
+        assert_eq!(indices.len(), HOST_FUNCTIONS.len());
+    }
+
+    #[test]
+    fn should_only_find_host_functions_available_under_protocol_version() {
+        assert!(find("read_value", ProtocolVersion::V1_0_0).is_some());
+        assert!(find("read_value", ProtocolVersion::from_parts(2, 0, 0)).is_some());
+        assert!(find("read_value", ProtocolVersion::default()).is_none());
+        assert!(find("no_such_function", ProtocolVersion::V1_0_0).is_none());
+
+        assert!(names(ProtocolVersion::V1_0_0).contains("read_value"));
+        assert!(names(ProtocolVersion::default()).is_empty());
+    }
+
+    #[test]
+    fn should_only_find_host_functions_from_the_protocol_version_introducing_them() {
+        assert!(find("random_bytes", ProtocolVersion::V1_0_0).is_none());
+        assert!(find("random_bytes", ProtocolVersion::V1_1_0).is_some());
+
+        assert!(!names(ProtocolVersion::V1_0_0).contains("random_bytes"));
+        assert!(names(ProtocolVersion::V1_1_0).contains("random_bytes"));
+        assert!(names(ProtocolVersion::V1_1_0).is_superset(&names(ProtocolVersion::V1_0_0)));
+    }
+}
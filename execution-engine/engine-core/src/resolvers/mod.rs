@@ -1,4 +1,5 @@
 pub mod error;
+pub mod host_functions;
 pub mod memory_resolver;
 pub mod v1_function_index;
 mod v1_resolver;
@@ -13,22 +14,29 @@ use crate::resolvers::memory_resolver::MemoryResolver;
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
+/// * `max_memory_pages` Maximum number of 64 KiB pages the module's memory may be allocated with.
 pub fn create_module_resolver(
     protocol_version: ProtocolVersion,
+    max_memory_pages: u32,
 ) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            protocol_version,
+            max_memory_pages,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
 #[test]
 fn resolve_invalid_module() {
-    assert!(create_module_resolver(ProtocolVersion::default()).is_err());
+    assert!(
+        create_module_resolver(ProtocolVersion::default(), engine_wasm_prep::MEM_PAGES).is_err()
+    );
 }
 
 #[test]
 fn protocol_version_1_always_resolves() {
-    assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
+    assert!(create_module_resolver(ProtocolVersion::V1_0_0, engine_wasm_prep::MEM_PAGES).is_ok());
 }
@@ -42,13 +42,39 @@ pub enum FunctionIndex {
     DisableContractVersion,
     CallVersionedContract,
     CreateContractUserGroup,
-    #[cfg(feature = "test-support")]
     PrintIndex,
     GetRuntimeArgsizeIndex,
     GetRuntimeArgIndex,
     RemoveContractUserGroupIndex,
     ExtendContractUserGroupURefsIndex,
     RemoveContractUserGroupURefsIndex,
+    RandomBytesIndex,
+    EmitEventIndex,
+    DictionaryNewIndex,
+    DictionaryGetIndex,
+    DictionaryPutIndex,
+    Blake2bIndex,
+    Keccak256Index,
+    VerifyEd25519Index,
+    VerifySecp256k1Index,
+    RetWithoutTransferIndex,
+    CallContractReadOnlyFuncIndex,
+    LoadNamedKeysPagedFuncIndex,
+    SetMainPurseIndex,
+    RegisterSystemContractIndex,
+    GetGasPriceIndex,
+    WriteAndReturnOldFuncIndex,
+    CompareAndSwapFuncIndex,
+    StackOverflowFuncIndex,
+    IsSystemCallerIndex,
+    GetDeployHashIndex,
+    GetCallStackIndex,
+    SetNonReentrantIndex,
+    CounterIncIndex,
+    U512MulDivIndex,
+    DisableContractIndex,
+    TransferFromPurseToAccountsIndex,
+    DeleteFuncIndex,
 }
 
 impl Into<usize> for FunctionIndex {
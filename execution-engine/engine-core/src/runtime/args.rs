@@ -1,201 +1,201 @@
-use wasmi::{FromRuntimeValue, RuntimeArgs, Trap};
+use crate::execution::{nth_arg, Error, FromWasmValue, WasmValue};
 
 pub trait Args
 where
     Self: Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap>;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error>;
 }
 
 impl Args for u32 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        args.nth_checked(0)
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        nth_arg(args, 0)
     }
 }
 
 impl Args for usize {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: u32 = args.nth_checked(0)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: u32 = nth_arg(args, 0)?;
         Ok(a0 as usize)
     }
 }
 
 impl<T1, T2> Args for (T1, T2)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
         Ok((a0, a1))
     }
 }
 
 impl<T1, T2, T3> Args for (T1, T2, T3)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
         Ok((a0, a1, a2))
     }
 }
 
 impl<T1, T2, T3, T4> Args for (T1, T2, T3, T4)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
         Ok((a0, a1, a2, a3))
     }
 }
 
 impl<T1, T2, T3, T4, T5> Args for (T1, T2, T3, T4, T5)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
         Ok((a0, a1, a2, a3, a4))
     }
 }
 
 impl<T1, T2, T3, T4, T5, T6> Args for (T1, T2, T3, T4, T5, T6)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
-    T6: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
+    T6: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
-        let a5: T6 = args.nth_checked(5)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
+        let a5: T6 = nth_arg(args, 5)?;
         Ok((a0, a1, a2, a3, a4, a5))
     }
 }
 
 impl<T1, T2, T3, T4, T5, T6, T7> Args for (T1, T2, T3, T4, T5, T6, T7)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
-    T6: FromRuntimeValue + Sized,
-    T7: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
+    T6: FromWasmValue + Sized,
+    T7: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
-        let a5: T6 = args.nth_checked(5)?;
-        let a6: T7 = args.nth_checked(6)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
+        let a5: T6 = nth_arg(args, 5)?;
+        let a6: T7 = nth_arg(args, 6)?;
         Ok((a0, a1, a2, a3, a4, a5, a6))
     }
 }
 
 impl<T1, T2, T3, T4, T5, T6, T7, T8> Args for (T1, T2, T3, T4, T5, T6, T7, T8)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
-    T6: FromRuntimeValue + Sized,
-    T7: FromRuntimeValue + Sized,
-    T8: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
+    T6: FromWasmValue + Sized,
+    T7: FromWasmValue + Sized,
+    T8: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
-        let a5: T6 = args.nth_checked(5)?;
-        let a6: T7 = args.nth_checked(6)?;
-        let a7: T8 = args.nth_checked(7)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
+        let a5: T6 = nth_arg(args, 5)?;
+        let a6: T7 = nth_arg(args, 6)?;
+        let a7: T8 = nth_arg(args, 7)?;
         Ok((a0, a1, a2, a3, a4, a5, a6, a7))
     }
 }
 
 impl<T1, T2, T3, T4, T5, T6, T7, T8, T9> Args for (T1, T2, T3, T4, T5, T6, T7, T8, T9)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
-    T6: FromRuntimeValue + Sized,
-    T7: FromRuntimeValue + Sized,
-    T8: FromRuntimeValue + Sized,
-    T9: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
+    T6: FromWasmValue + Sized,
+    T7: FromWasmValue + Sized,
+    T8: FromWasmValue + Sized,
+    T9: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
-        let a5: T6 = args.nth_checked(5)?;
-        let a6: T7 = args.nth_checked(6)?;
-        let a7: T8 = args.nth_checked(7)?;
-        let a8: T9 = args.nth_checked(8)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
+        let a5: T6 = nth_arg(args, 5)?;
+        let a6: T7 = nth_arg(args, 6)?;
+        let a7: T8 = nth_arg(args, 7)?;
+        let a8: T9 = nth_arg(args, 8)?;
         Ok((a0, a1, a2, a3, a4, a5, a6, a7, a8))
     }
 }
 
 impl<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10> Args for (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
-    T6: FromRuntimeValue + Sized,
-    T7: FromRuntimeValue + Sized,
-    T8: FromRuntimeValue + Sized,
-    T9: FromRuntimeValue + Sized,
-    T10: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
+    T6: FromWasmValue + Sized,
+    T7: FromWasmValue + Sized,
+    T8: FromWasmValue + Sized,
+    T9: FromWasmValue + Sized,
+    T10: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
-        let a5: T6 = args.nth_checked(5)?;
-        let a6: T7 = args.nth_checked(6)?;
-        let a7: T8 = args.nth_checked(7)?;
-        let a8: T9 = args.nth_checked(8)?;
-        let a9: T10 = args.nth_checked(9)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
+        let a5: T6 = nth_arg(args, 5)?;
+        let a6: T7 = nth_arg(args, 6)?;
+        let a7: T8 = nth_arg(args, 7)?;
+        let a8: T9 = nth_arg(args, 8)?;
+        let a9: T10 = nth_arg(args, 9)?;
         Ok((a0, a1, a2, a3, a4, a5, a6, a7, a8, a9))
     }
 }
@@ -203,30 +203,30 @@ where
 impl<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11> Args
     for (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11)
 where
-    T1: FromRuntimeValue + Sized,
-    T2: FromRuntimeValue + Sized,
-    T3: FromRuntimeValue + Sized,
-    T4: FromRuntimeValue + Sized,
-    T5: FromRuntimeValue + Sized,
-    T6: FromRuntimeValue + Sized,
-    T7: FromRuntimeValue + Sized,
-    T8: FromRuntimeValue + Sized,
-    T9: FromRuntimeValue + Sized,
-    T10: FromRuntimeValue + Sized,
-    T11: FromRuntimeValue + Sized,
+    T1: FromWasmValue + Sized,
+    T2: FromWasmValue + Sized,
+    T3: FromWasmValue + Sized,
+    T4: FromWasmValue + Sized,
+    T5: FromWasmValue + Sized,
+    T6: FromWasmValue + Sized,
+    T7: FromWasmValue + Sized,
+    T8: FromWasmValue + Sized,
+    T9: FromWasmValue + Sized,
+    T10: FromWasmValue + Sized,
+    T11: FromWasmValue + Sized,
 {
-    fn parse(args: RuntimeArgs) -> Result<Self, Trap> {
-        let a0: T1 = args.nth_checked(0)?;
-        let a1: T2 = args.nth_checked(1)?;
-        let a2: T3 = args.nth_checked(2)?;
-        let a3: T4 = args.nth_checked(3)?;
-        let a4: T5 = args.nth_checked(4)?;
-        let a5: T6 = args.nth_checked(5)?;
-        let a6: T7 = args.nth_checked(6)?;
-        let a7: T8 = args.nth_checked(7)?;
-        let a8: T9 = args.nth_checked(8)?;
-        let a9: T10 = args.nth_checked(9)?;
-        let a10: T11 = args.nth_checked(10)?;
+    fn parse(args: &[WasmValue]) -> Result<Self, Error> {
+        let a0: T1 = nth_arg(args, 0)?;
+        let a1: T2 = nth_arg(args, 1)?;
+        let a2: T3 = nth_arg(args, 2)?;
+        let a3: T4 = nth_arg(args, 3)?;
+        let a4: T5 = nth_arg(args, 4)?;
+        let a5: T6 = nth_arg(args, 5)?;
+        let a6: T7 = nth_arg(args, 6)?;
+        let a7: T8 = nth_arg(args, 7)?;
+        let a8: T9 = nth_arg(args, 8)?;
+        let a9: T10 = nth_arg(args, 9)?;
+        let a10: T11 = nth_arg(args, 10)?;
         Ok((a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10))
     }
 }
@@ -0,0 +1,118 @@
+//! Cryptographic primitives exposed to contracts as host functions.
+
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Length in bytes of the digests produced by [`blake2b`] and [`keccak256`].
+pub const DIGEST_LENGTH: usize = 32;
+
+/// Computes a 32-byte blake2b digest of `data`.
+pub fn blake2b(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut ret = [0u8; DIGEST_LENGTH];
+    let mut hasher = VarBlake2b::new(DIGEST_LENGTH).unwrap();
+    hasher.input(data);
+    hasher.variable_result(|hash| ret.clone_from_slice(hash));
+    ret
+}
+
+/// Computes a keccak-256 digest of `data`.
+pub fn keccak256(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut ret = [0u8; DIGEST_LENGTH];
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    hasher.finalize(&mut ret);
+    ret
+}
+
+/// Verifies an Ed25519 `signature` of `message` against `public_key`.
+///
+/// Malformed signatures or public keys are treated as failed verifications.
+pub fn verify_ed25519(message: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+    let public_key = match ed25519_dalek::PublicKey::from_bytes(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match ed25519_dalek::Signature::from_bytes(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    public_key.verify(message, &signature).is_ok()
+}
+
+/// Verifies a compact (64-byte) secp256k1 `signature` of the 32-byte `message_digest` against a
+/// compressed or uncompressed `public_key`.
+///
+/// Malformed digests, signatures or public keys are treated as failed verifications.
+pub fn verify_secp256k1(message_digest: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+    let message = match secp256k1::Message::parse_slice(message_digest) {
+        Ok(message) => message,
+        Err(_) => return false,
+    };
+    let signature = match secp256k1::Signature::parse_slice(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let public_key = match secp256k1::PublicKey::parse_slice(public_key, None) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    secp256k1::verify(&message, &signature, &public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_known_keccak256_digest() {
+        // keccak256("") as used by Ethereum
+        let expected = [
+            0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+            0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+            0x5d, 0x85, 0xa4, 0x70,
+        ];
+        assert_eq!(keccak256(&[]), expected);
+    }
+
+    #[test]
+    fn should_compute_distinct_digests() {
+        assert_ne!(blake2b(b"a"), blake2b(b"b"));
+        assert_ne!(blake2b(b"a"), keccak256(b"a"));
+    }
+
+    #[test]
+    fn should_reject_malformed_ed25519_inputs() {
+        assert!(!verify_ed25519(b"message", &[0u8; 3], &[0u8; 32]));
+        assert!(!verify_ed25519(b"message", &[0u8; 64], &[0u8; 3]));
+    }
+
+    #[test]
+    fn should_verify_ed25519_signature() {
+        // Test vector 2 from RFC 8032, section 7.1
+        let public_key = [
+            0x3d, 0x40, 0x17, 0xc3, 0xe8, 0x43, 0x89, 0x5a, 0x92, 0xb7, 0x0a, 0xa7, 0x4d, 0x1b,
+            0x7e, 0xbc, 0x9c, 0x98, 0x2c, 0xcf, 0x2e, 0xc4, 0x96, 0x8c, 0xc0, 0xcd, 0x55, 0xf1,
+            0x2a, 0xf4, 0x66, 0x0c,
+        ];
+        let message = [0x72];
+        let signature = [
+            0x92, 0xa0, 0x09, 0xa9, 0xf0, 0xd4, 0xca, 0xb8, 0x72, 0x0e, 0x82, 0x0b, 0x5f, 0x64,
+            0x25, 0x40, 0xa2, 0xb2, 0x7b, 0x54, 0x16, 0x50, 0x3f, 0x8f, 0xb3, 0x76, 0x22, 0x23,
+            0xeb, 0xdb, 0x69, 0xda, 0x08, 0x5a, 0xc1, 0xe4, 0x3e, 0x15, 0x99, 0x6e, 0x45, 0x8f,
+            0x36, 0x13, 0xd0, 0xf1, 0x1d, 0x8c, 0x38, 0x7b, 0x2e, 0xae, 0xb4, 0x30, 0x2a, 0xee,
+            0xb0, 0x0d, 0x29, 0x16, 0x12, 0xbb, 0x0c, 0x00,
+        ];
+        assert!(verify_ed25519(&message, &signature, &public_key));
+        assert!(!verify_ed25519(&[0x73], &signature, &public_key));
+    }
+
+    #[test]
+    fn should_reject_malformed_secp256k1_inputs() {
+        assert!(!verify_secp256k1(&[0u8; 31], &[0u8; 64], &[0u8; 33]));
+        assert!(!verify_secp256k1(&[1u8; 32], &[0u8; 63], &[0u8; 33]));
+        assert!(!verify_secp256k1(&[1u8; 32], &[1u8; 64], &[0u8; 2]));
+    }
+}
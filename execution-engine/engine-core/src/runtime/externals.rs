@@ -1,6 +1,4 @@
-use std::{collections::BTreeSet, convert::TryFrom};
-
-use wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
+use std::{cell::RefCell, cmp, collections::BTreeSet, convert::TryFrom, rc::Rc};
 
 use types::{
     account::AccountHash,
@@ -14,28 +12,82 @@ use engine_shared::{gas::Gas, stored_value::StoredValue};
 use engine_storage::global_state::StateReader;
 
 use super::{args::Args, scoped_instrumenter::ScopedInstrumenter, Error, Runtime};
-use crate::resolvers::v1_function_index::FunctionIndex;
+use crate::{
+    execution::{HostCall, HostFunctions, WasmMemory, WasmValue},
+    resolvers::v1_function_index::FunctionIndex,
+};
 
-impl<'a, R> Externals for Runtime<'a, R>
+impl<'a, R> HostFunctions for Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<Error>,
 {
-    fn invoke_index(
+    fn call_host_function(
         &mut self,
         index: usize,
-        args: RuntimeArgs,
-    ) -> Result<Option<RuntimeValue>, Trap> {
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        let result = match self.trace.clone() {
+            Some(trace) => self.invoke_traced_host_function(trace, func, args),
+            None => self.invoke_host_function(func, args),
+        };
+        // Only host functions can record effects, so checking after each one aborts execution
+        // before the effects can grow much beyond the limit.
+        if self.context.is_effects_limit_exceeded() {
+            let max_effects_size = self.context.protocol_data().max_effects_size();
+            return Err(Error::EffectsLimit(max_effects_size as usize));
+        }
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn invoke_traced_host_function(
+        &mut self,
+        trace: Rc<RefCell<Vec<HostCall>>>,
+        func: FunctionIndex,
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
+        // The call is recorded before invoking it, so that it precedes any host calls made by a
+        // contract it calls.
+        let position = {
+            let mut trace = trace.borrow_mut();
+            trace.push(HostCall::new(
+                func,
+                self.context.phase(),
+                self.call_depth,
+                args.to_vec(),
+            ));
+            trace.len() - 1
+        };
+        let gas_before = self.context.gas_counter();
+        let result = self.invoke_host_function(func, args);
+        let gas = self.context.gas_counter() - gas_before;
+        trace.borrow_mut()[position].complete(gas, result.as_ref().err());
+        result
+    }
+
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
         let mut scoped_instrumenter = ScopedInstrumenter::new(func);
+        let host_function_costs = *self.context.protocol_data().host_function_costs();
         match func {
             FunctionIndex::ReadFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
                 // args(1) = size of key in Wasm memory
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.read_value, &[key_size])?;
                 let ret = self.read(key_ptr, key_size, output_size_ptr)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::ReadLocalFuncIndex => {
@@ -43,21 +95,46 @@ where
                 // args(1) = size of key in Wasm memory
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr): (_, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.read_value_local, &[key_size])?;
                 scoped_instrumenter.add_property("key_size", key_size);
                 let ret = self.read_local(key_ptr, key_size, output_size_ptr)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::LoadNamedKeysFuncIndex => {
                 // args(0) = pointer to amount of keys (output)
                 // args(1) = pointer to amount of serialized bytes (output)
                 let (total_keys_ptr, result_size_ptr) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.load_named_keys, &[])?;
                 let ret = self.load_named_keys(
                     total_keys_ptr,
                     result_size_ptr,
                     &mut scoped_instrumenter,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::LoadNamedKeysPagedFuncIndex => {
+                // args(0) = pointer to the serialized name the page starts after, if any
+                // args(1) = size of the serialized name
+                // args(2) = maximum amount of keys in the page
+                // args(3) = pointer to amount of keys in the page (output)
+                // args(4) = pointer to amount of serialized bytes (output)
+                let (start_after_ptr, start_after_size, limit, page_keys_ptr, result_size_ptr) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.load_named_keys_paged,
+                    &[start_after_size],
+                )?;
+                let ret = self.load_named_keys_paged(
+                    start_after_ptr,
+                    start_after_size,
+                    limit,
+                    page_keys_ptr,
+                    result_size_ptr,
+                    &mut scoped_instrumenter,
+                )?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::WriteFuncIndex => {
@@ -66,11 +143,75 @@ where
                 // args(2) = pointer to value
                 // args(3) = size of value
                 let (key_ptr, key_size, value_ptr, value_size): (_, _, _, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.write, &[key_size, value_size])?;
                 scoped_instrumenter.add_property("value_size", value_size);
                 self.write(key_ptr, key_size, value_ptr, value_size)?;
                 Ok(None)
             }
 
+            FunctionIndex::DeleteFuncIndex => {
+                // args(0) = pointer to uref in Wasm memory
+                // args(1) = size of uref
+                let (uref_ptr, uref_size) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.delete, &[uref_size])?;
+                self.delete(uref_ptr, uref_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::WriteAndReturnOldFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                // args(2) = pointer to value
+                // args(3) = size of value
+                // args(4) = pointer to output size of the old value (output param)
+                let (key_ptr, key_size, value_ptr, value_size, output_size_ptr): (_, _, _, u32, _) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.write_and_return_old,
+                    &[key_size, value_size],
+                )?;
+                scoped_instrumenter.add_property("value_size", value_size);
+                let ret = self.write_and_return_old(
+                    key_ptr,
+                    key_size,
+                    value_ptr,
+                    value_size,
+                    output_size_ptr,
+                )?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CompareAndSwapFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                // args(2) = pointer to expected value
+                // args(3) = size of expected value
+                // args(4) = pointer to new value
+                // args(5) = size of new value
+                let (key_ptr, key_size, expected_ptr, expected_size, new_ptr, new_size): (
+                    _,
+                    _,
+                    _,
+                    u32,
+                    _,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.compare_and_swap,
+                    &[key_size, expected_size, new_size],
+                )?;
+                scoped_instrumenter.add_property("value_size", new_size);
+                let swapped = self.compare_and_swap(
+                    key_ptr,
+                    key_size,
+                    expected_ptr,
+                    expected_size,
+                    new_ptr,
+                    new_size,
+                )?;
+                Ok(Some(WasmValue::I32(i32::from(swapped))))
+            }
+
             FunctionIndex::WriteLocalFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
                 // args(1) = size of key
@@ -78,6 +219,10 @@ where
                 // args(3) = size of value
                 let (key_bytes_ptr, key_bytes_size, value_ptr, value_size): (_, u32, _, u32) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.write_local,
+                    &[key_bytes_size, value_size],
+                )?;
                 scoped_instrumenter.add_property("key_bytes_size", key_bytes_size);
                 scoped_instrumenter.add_property("value_size", value_size);
                 self.write_local(key_bytes_ptr, key_bytes_size, value_ptr, value_size)?;
@@ -90,15 +235,27 @@ where
                 // args(2) = pointer to value
                 // args(3) = size of value
                 let (key_ptr, key_size, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.add, &[key_size, value_size])?;
                 self.add(key_ptr, key_size, value_ptr, value_size)?;
                 Ok(None)
             }
 
+            FunctionIndex::CounterIncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                // args(2) = pointer to output
+                let (key_ptr, key_size, value_ptr) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.counter_inc, &[key_size])?;
+                self.counter_inc(key_ptr, key_size, value_ptr)?;
+                Ok(None)
+            }
+
             FunctionIndex::NewFuncIndex => {
                 // args(0) = pointer to uref destination in Wasm memory
                 // args(1) = pointer to initial value
                 // args(2) = size of initial value
                 let (uref_ptr, value_ptr, value_size): (_, _, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.new_uref, &[value_size])?;
                 scoped_instrumenter.add_property("value_size", value_size);
                 self.new_uref(uref_ptr, value_ptr, value_size)?;
                 Ok(None)
@@ -108,8 +265,28 @@ where
                 // args(0) = pointer to value
                 // args(1) = size of value
                 let (value_ptr, value_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.ret, &[value_size])?;
                 scoped_instrumenter.add_property("value_size", value_size);
-                Err(self.ret(value_ptr, value_size as usize, &mut scoped_instrumenter))
+                Err(self.ret(
+                    value_ptr,
+                    value_size as usize,
+                    true,
+                    &mut scoped_instrumenter,
+                ))
+            }
+
+            FunctionIndex::RetWithoutTransferIndex => {
+                // args(0) = pointer to value
+                // args(1) = size of value
+                let (value_ptr, value_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.ret, &[value_size])?;
+                scoped_instrumenter.add_property("value_size", value_size);
+                Err(self.ret(
+                    value_ptr,
+                    value_size as usize,
+                    false,
+                    &mut scoped_instrumenter,
+                ))
             }
 
             FunctionIndex::GetKeyFuncIndex => {
@@ -125,6 +302,7 @@ where
                     u32,
                     u32,
                 ) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_key, &[name_size])?;
                 scoped_instrumenter.add_property("name_size", name_size);
                 let ret = self.load_key(
                     name_ptr,
@@ -133,16 +311,17 @@ where
                     output_size as usize,
                     bytes_written,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::HasKeyFuncIndex => {
                 // args(0) = pointer to key name in Wasm memory
                 // args(1) = size of key name
                 let (name_ptr, name_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.has_key, &[name_size])?;
                 scoped_instrumenter.add_property("name_size", name_size);
                 let result = self.has_key(name_ptr, name_size)?;
-                Ok(Some(RuntimeValue::I32(result)))
+                Ok(Some(WasmValue::I32(result)))
             }
 
             FunctionIndex::PutKeyFuncIndex => {
@@ -151,6 +330,10 @@ where
                 // args(2) = pointer to key in Wasm memory
                 // args(3) = size of key
                 let (name_ptr, name_size, key_ptr, key_size): (_, u32, _, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.put_key,
+                    &[name_size, key_size],
+                )?;
                 scoped_instrumenter.add_property("name_size", name_size);
                 self.put_key(name_ptr, name_size, key_ptr, key_size)?;
                 Ok(None)
@@ -160,6 +343,7 @@ where
                 // args(0) = pointer to key name in Wasm memory
                 // args(1) = size of key name
                 let (name_ptr, name_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.remove_key, &[name_size])?;
                 scoped_instrumenter.add_property("name_size", name_size);
                 self.remove_key(name_ptr, name_size)?;
                 Ok(None)
@@ -168,29 +352,238 @@ where
             FunctionIndex::GetCallerIndex => {
                 // args(0) = pointer where a size of serialized bytes will be stored
                 let output_size = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_caller, &[])?;
                 let ret = self.get_caller(output_size)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetCallStackIndex => {
+                // args(0) = pointer where a size of serialized bytes will be stored
+                let output_size = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_call_stack, &[])?;
+                let ret = self.get_call_stack(output_size)?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::IsSystemCallerIndex => {
+                self.charge_host_function_call(host_function_costs.is_system_caller, &[])?;
+                Ok(Some(WasmValue::I32(i32::from(
+                    self.context.is_system_caller(),
+                ))))
             }
 
             FunctionIndex::GetBlocktimeIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_blocktime, &[])?;
                 self.get_blocktime(dest_ptr)?;
                 Ok(None)
             }
 
+            FunctionIndex::GetGasPriceIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_gas_price, &[])?;
+                self.get_gas_price(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetDeployHashIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_deploy_hash, &[])?;
+                self.get_deploy_hash(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::RandomBytesIndex => {
+                // args(0) = pointer to Wasm memory where to write
+                // args(1) = number of bytes to write
+                let (dest_ptr, dest_size): (u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.random_bytes, &[dest_size])?;
+                scoped_instrumenter.add_property("dest_size", dest_size.to_string());
+                self.random_bytes(dest_ptr, dest_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::U512MulDivIndex => {
+                // args(0) = pointer to serialized multiplicand
+                // args(1) = size of serialized multiplicand
+                // args(2) = pointer to serialized numerator
+                // args(3) = size of serialized numerator
+                // args(4) = pointer to serialized denominator
+                // args(5) = size of serialized denominator
+                // args(6) = pointer to Wasm memory where to write the 64-byte result
+                let (a_ptr, a_size, b_ptr, b_size, c_ptr, c_size, dest_ptr) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.u512_mul_div, &[])?;
+                let ret =
+                    self.u512_mul_div(a_ptr, a_size, b_ptr, b_size, c_ptr, c_size, dest_ptr)?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to serialized event name
+                // args(1) = size of serialized event name
+                // args(2) = pointer to serialized event data
+                // args(3) = size of serialized event data
+                let (name_ptr, name_size, data_ptr, data_size): (_, u32, _, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.emit_event,
+                    &[name_size, data_size],
+                )?;
+                scoped_instrumenter.add_property("name_size", name_size.to_string());
+                scoped_instrumenter.add_property("data_size", data_size.to_string());
+                self.emit_event(name_ptr, name_size, data_ptr, data_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::DictionaryNewIndex => {
+                // args(0) = pointer to array for return value
+                let uref_ptr = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.dictionary_new, &[])?;
+                self.dictionary_new(uref_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::DictionaryGetIndex => {
+                // args(0) = pointer to serialized dictionary seed uref
+                // args(1) = size of serialized dictionary seed uref
+                // args(2) = pointer to item key bytes
+                // args(3) = size of item key bytes
+                // args(4) = pointer to output size (output param)
+                let (uref_ptr, uref_size, key_ptr, key_size, output_size_ptr): (_, _, _, u32, _) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.dictionary_get,
+                    &[uref_size, key_size],
+                )?;
+                scoped_instrumenter.add_property("key_size", key_size.to_string());
+                let ret =
+                    self.dictionary_get(uref_ptr, uref_size, key_ptr, key_size, output_size_ptr)?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::DictionaryPutIndex => {
+                // args(0) = pointer to serialized dictionary seed uref
+                // args(1) = size of serialized dictionary seed uref
+                // args(2) = pointer to item key bytes
+                // args(3) = size of item key bytes
+                // args(4) = pointer to serialized value
+                // args(5) = size of serialized value
+                let (uref_ptr, uref_size, key_ptr, key_size, value_ptr, value_size): (
+                    _,
+                    _,
+                    _,
+                    u32,
+                    _,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.dictionary_put,
+                    &[uref_size, key_size, value_size],
+                )?;
+                scoped_instrumenter.add_property("key_size", key_size.to_string());
+                scoped_instrumenter.add_property("value_size", value_size.to_string());
+                self.dictionary_put(
+                    uref_ptr, uref_size, key_ptr, key_size, value_ptr, value_size,
+                )?;
+                Ok(None)
+            }
+
+            FunctionIndex::Blake2bIndex => {
+                // args(0) = pointer to input bytes
+                // args(1) = size of input bytes
+                // args(2) = pointer to output digest
+                // args(3) = size of output digest
+                let (in_ptr, in_size, out_ptr, out_size): (_, u32, _, _) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.blake2b, &[in_size])?;
+                scoped_instrumenter.add_property("in_size", in_size.to_string());
+                let ret = self.blake2b(in_ptr, in_size, out_ptr, out_size)?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::Keccak256Index => {
+                // args(0) = pointer to input bytes
+                // args(1) = size of input bytes
+                // args(2) = pointer to output digest
+                // args(3) = size of output digest
+                let (in_ptr, in_size, out_ptr, out_size): (_, u32, _, _) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.keccak256, &[in_size])?;
+                scoped_instrumenter.add_property("in_size", in_size.to_string());
+                let ret = self.keccak256(in_ptr, in_size, out_ptr, out_size)?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::VerifyEd25519Index => {
+                // args(0) = pointer to message bytes
+                // args(1) = size of message bytes
+                // args(2) = pointer to signature bytes
+                // args(3) = size of signature bytes
+                // args(4) = pointer to public key bytes
+                // args(5) = size of public key bytes
+                let (
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                ): (_, u32, _, _, _, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("message_size", message_size.to_string());
+                let verified = self.verify_ed25519(
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                )?;
+                Ok(Some(WasmValue::I32(i32::from(verified))))
+            }
+
+            FunctionIndex::VerifySecp256k1Index => {
+                // args(0) = pointer to message bytes
+                // args(1) = size of message bytes
+                // args(2) = pointer to signature bytes
+                // args(3) = size of signature bytes
+                // args(4) = pointer to public key bytes
+                // args(5) = size of public key bytes
+                let (
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                ): (_, u32, _, _, _, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("message_size", message_size.to_string());
+                let verified = self.verify_secp256k1(
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                )?;
+                Ok(Some(WasmValue::I32(i32::from(verified))))
+            }
+
             FunctionIndex::GasFuncIndex => {
                 let gas_arg: u32 = Args::parse(args)?;
                 self.gas(Gas::new(gas_arg.into()))?;
                 Ok(None)
             }
 
+            FunctionIndex::StackOverflowFuncIndex => Err(Error::StackOverflow),
+
             FunctionIndex::IsValidURefFnIndex => {
                 // args(0) = pointer to value to validate
                 // args(1) = size of value
                 let (uref_ptr, uref_size) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.is_valid_uref, &[uref_size])?;
 
-                Ok(Some(RuntimeValue::I32(i32::from(
+                Ok(Some(WasmValue::I32(i32::from(
                     self.is_valid_uref(uref_ptr, uref_size)?,
                 ))))
             }
@@ -198,6 +591,7 @@ where
             FunctionIndex::RevertFuncIndex => {
                 // args(0) = status u32
                 let status = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.revert, &[])?;
 
                 Err(self.revert(status))
             }
@@ -208,21 +602,29 @@ where
                 // args(2) = weight of the key
                 let (account_hash_ptr, account_hash_size, weight_value): (u32, u32, u8) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.add_associated_key,
+                    &[account_hash_size],
+                )?;
                 let value = self.add_associated_key(
                     account_hash_ptr,
                     account_hash_size as usize,
                     weight_value,
                 )?;
-                Ok(Some(RuntimeValue::I32(value)))
+                Ok(Some(WasmValue::I32(value)))
             }
 
             FunctionIndex::RemoveAssociatedKeyFuncIndex => {
                 // args(0) = pointer to array of bytes of an account hash
                 // args(1) = size of an account hash
                 let (account_hash_ptr, account_hash_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.remove_associated_key,
+                    &[account_hash_size],
+                )?;
                 let value =
                     self.remove_associated_key(account_hash_ptr, account_hash_size as usize)?;
-                Ok(Some(RuntimeValue::I32(value)))
+                Ok(Some(WasmValue::I32(value)))
             }
 
             FunctionIndex::UpdateAssociatedKeyFuncIndex => {
@@ -231,33 +633,66 @@ where
                 // args(2) = weight of the key
                 let (account_hash_ptr, account_hash_size, weight_value): (u32, u32, u8) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.update_associated_key,
+                    &[account_hash_size],
+                )?;
                 let value = self.update_associated_key(
                     account_hash_ptr,
                     account_hash_size as usize,
                     weight_value,
                 )?;
-                Ok(Some(RuntimeValue::I32(value)))
+                Ok(Some(WasmValue::I32(value)))
             }
 
             FunctionIndex::SetActionThresholdFuncIndex => {
                 // args(0) = action type
                 // args(1) = new threshold
                 let (action_type_value, threshold_value): (u32, u8) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.set_action_threshold, &[])?;
                 let value = self.set_action_threshold(action_type_value, threshold_value)?;
-                Ok(Some(RuntimeValue::I32(value)))
+                Ok(Some(WasmValue::I32(value)))
+            }
+
+            FunctionIndex::SetMainPurseIndex => {
+                // args(0) = pointer to purse in Wasm memory
+                // args(1) = size of purse
+                let (purse_ptr, purse_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.set_main_purse, &[])?;
+                let value = self.set_main_purse(purse_ptr, purse_size)?;
+                Ok(Some(WasmValue::I32(value)))
+            }
+
+            FunctionIndex::RegisterSystemContractIndex => {
+                // args(0) = pointer to name in Wasm memory
+                // args(1) = size of name
+                // args(2) = pointer to contract hash in Wasm memory
+                // args(3) = size of contract hash
+                let (name_ptr, name_size, contract_hash_ptr, contract_hash_size) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.register_system_contract,
+                    &[name_size, contract_hash_size],
+                )?;
+                self.register_system_contract(
+                    name_ptr,
+                    name_size,
+                    contract_hash_ptr,
+                    contract_hash_size,
+                )?;
+                Ok(None)
             }
 
             FunctionIndex::CreatePurseIndex => {
                 // args(0) = pointer to array for return value
                 // args(1) = length of array for return value
                 let (dest_ptr, dest_size): (u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.create_purse, &[])?;
                 let purse = self.create_purse()?;
                 let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
                 assert_eq!(dest_size, purse_bytes.len() as u32);
-                self.memory
-                    .set(dest_ptr, &purse_bytes)
-                    .map_err(|e| Error::Interpreter(e.into()))?;
-                Ok(Some(RuntimeValue::I32(0)))
+                self.memory.set(dest_ptr, &purse_bytes)?;
+                Ok(Some(WasmValue::I32(0)))
             }
 
             FunctionIndex::TransferToAccountIndex => {
@@ -267,6 +702,10 @@ where
                 // args(3) = length of array of bytes of an amount
                 let (key_ptr, key_size, amount_ptr, amount_size): (u32, u32, u32, u32) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.transfer_to_account,
+                    &[key_size, amount_size],
+                )?;
                 let account_hash: AccountHash = {
                     let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
                     bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
@@ -276,7 +715,7 @@ where
                     bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
                 };
                 let ret = self.transfer_to_account(account_hash, amount)?;
-                Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
+                Ok(Some(WasmValue::I32(TransferredTo::i32_from(ret))))
             }
 
             FunctionIndex::TransferFromPurseToAccountIndex => {
@@ -294,6 +733,10 @@ where
                     u32,
                     u32,
                 ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.transfer_from_purse_to_account,
+                    &[source_size, key_size, amount_size],
+                )?;
 
                 let source_purse = {
                     let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
@@ -309,7 +752,33 @@ where
                 };
                 let ret =
                     self.transfer_from_purse_to_account(source_purse, account_hash, amount)?;
-                Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
+                Ok(Some(WasmValue::I32(TransferredTo::i32_from(ret))))
+            }
+
+            FunctionIndex::TransferFromPurseToAccountsIndex => {
+                // args(0) = pointer to array of bytes in Wasm memory of a source purse
+                // args(1) = length of array of bytes in Wasm memory of a source purse
+                // args(2) = pointer to array of bytes in Wasm memory of the transfers
+                // args(3) = length of array of bytes in Wasm memory of the transfers
+                let (source_ptr, source_size, transfers_ptr, transfers_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.transfer_from_purse_to_accounts,
+                    &[source_size, transfers_size],
+                )?;
+
+                let source_purse = self.t_from_mem(source_ptr, source_size)?;
+                let transfers: Vec<(AccountHash, U512)> =
+                    self.t_from_mem(transfers_ptr, transfers_size)?;
+                // The first recipient is covered by the charge above.
+                for _ in 1..transfers.len() {
+                    self.charge_host_function_call(
+                        host_function_costs.transfer_from_purse_to_accounts,
+                        &[],
+                    )?;
+                }
+
+                let ret = self.transfer_from_purse_to_accounts(source_purse, transfers)?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::TransferFromPurseToPurseIndex => {
@@ -321,6 +790,10 @@ where
                 // args(5) = length of array of bytes in Wasm memory of an amount
                 let (source_ptr, source_size, target_ptr, target_size, amount_ptr, amount_size) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.transfer_from_purse_to_purse,
+                    &[source_size, target_size, amount_size],
+                )?;
                 let ret = self.transfer_from_purse_to_purse(
                     source_ptr,
                     source_size,
@@ -329,7 +802,7 @@ where
                     amount_ptr,
                     amount_size,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::GetBalanceIndex => {
@@ -337,13 +810,15 @@ where
                 // args(1) = length of purse
                 // args(2) = pointer to output size (output)
                 let (ptr, ptr_size, output_size_ptr): (_, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_balance, &[ptr_size])?;
                 let ret = self.get_balance_host_buffer(ptr, ptr_size as usize, output_size_ptr)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::GetPhaseIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_phase, &[])?;
                 self.get_phase(dest_ptr)?;
                 Ok(None)
             }
@@ -353,13 +828,15 @@ where
                 // args(1) = dest pointer for storing serialized result
                 // args(2) = dest pointer size
                 let (system_contract_index, dest_ptr, dest_size) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_system_contract, &[])?;
                 let ret = self.get_system_contract(system_contract_index, dest_ptr, dest_size)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::GetMainPurseIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_main_purse, &[])?;
                 self.get_main_purse(dest_ptr)?;
                 Ok(None)
             }
@@ -367,15 +844,26 @@ where
             FunctionIndex::ReadHostBufferIndex => {
                 // args(0) = pointer to Wasm memory where to write size.
                 let (dest_ptr, dest_size, bytes_written_ptr): (_, u32, _) = Args::parse(args)?;
+                // Only the bytes actually copied are charged for, so that a contract can pass a
+                // destination larger than the host buffer without paying for the excess.
+                let bytes_to_copy = cmp::min(dest_size, self.host_buffer_size());
+                self.charge_host_function_call(
+                    host_function_costs.read_host_buffer,
+                    &[bytes_to_copy],
+                )?;
                 scoped_instrumenter.add_property("dest_size", dest_size);
                 let ret = self.read_host_buffer(dest_ptr, dest_size as usize, bytes_written_ptr)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::CreateContractPackageAtHash => {
                 // args(0) = pointer to wasm memory where to write 32-byte Hash address
                 // args(1) = pointer to wasm memory where to write 32-byte access key address
                 let (hash_dest_ptr, access_dest_ptr) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.create_contract_package_at_hash,
+                    &[],
+                )?;
                 let (hash_addr, access_addr) = self.create_contract_package_at_hash()?;
                 self.function_address(hash_addr, hash_dest_ptr)?;
                 self.function_address(access_addr, access_dest_ptr)?;
@@ -401,6 +889,10 @@ where
                     existing_urefs_size,
                     output_size_ptr,
                 ): (_, _, _, u32, _, _, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.create_contract_user_group,
+                    &[package_key_size, label_size, existing_urefs_size],
+                )?;
                 scoped_instrumenter
                     .add_property("existing_urefs_size", existing_urefs_size.to_string());
                 scoped_instrumenter.add_property("label_size", label_size.to_string());
@@ -418,7 +910,7 @@ where
                     existing_urefs,
                     output_size_ptr,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::AddContractVersion => {
@@ -443,6 +935,14 @@ where
                     output_size,
                     bytes_written_ptr,
                 ): (u32, u32, u32, u32, u32, u32, u32, u32, u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.add_contract_version,
+                    &[
+                        contract_package_hash_size,
+                        entry_points_size,
+                        named_keys_size,
+                    ],
+                )?;
 
                 scoped_instrumenter
                     .add_property("entry_points_size", entry_points_size.to_string());
@@ -462,7 +962,7 @@ where
                     bytes_written_ptr,
                     version_ptr,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::DisableContractVersion => {
@@ -472,13 +972,63 @@ where
                 // args(3) = size of contract hash in wasm memory
                 let (package_key_ptr, package_key_size, contract_hash_ptr, contract_hash_size) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.disable_contract_version,
+                    &[package_key_size, contract_hash_size],
+                )?;
 
                 let contract_package_hash = self.t_from_mem(package_key_ptr, package_key_size)?;
                 let contract_hash = self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
 
                 let result = self.disable_contract_version(contract_package_hash, contract_hash)?;
 
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(result))))
+            }
+
+            FunctionIndex::DisableContractIndex => {
+                // args(0) = pointer to contract hash in wasm memory
+                // args(1) = size of contract hash in wasm memory
+                let (contract_hash_ptr, contract_hash_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.disable_contract,
+                    &[contract_hash_size],
+                )?;
+
+                let contract_hash = self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+
+                let result = self.disable_contract(contract_hash)?;
+
+                Ok(Some(WasmValue::I32(api_error::i32_from(result))))
+            }
+
+            FunctionIndex::SetNonReentrantIndex => {
+                // args(0) = pointer to package hash in wasm memory
+                // args(1) = size of package hash in wasm memory
+                // args(2) = pointer to contract hash in wasm memory
+                // args(3) = size of contract hash in wasm memory
+                // args(4) = non-zero if the contract should be non-reentrant
+                let (
+                    package_key_ptr,
+                    package_key_size,
+                    contract_hash_ptr,
+                    contract_hash_size,
+                    non_reentrant,
+                ): (_, _, _, _, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.set_non_reentrant,
+                    &[package_key_size, contract_hash_size],
+                )?;
+
+                let contract_package_hash = self.t_from_mem(package_key_ptr, package_key_size)?;
+                let contract_hash = self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+
+                let result = self.set_non_reentrant(
+                    contract_package_hash,
+                    contract_hash,
+                    non_reentrant != 0,
+                )?;
+
+                Ok(Some(WasmValue::I32(api_error::i32_from(result))))
             }
 
             FunctionIndex::CallContractFuncIndex => {
@@ -498,6 +1048,10 @@ where
                     args_size,
                     result_size_ptr,
                 ): (_, _, _, u32, _, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.call_contract,
+                    &[contract_hash_size, entry_point_name_size, args_size],
+                )?;
                 scoped_instrumenter
                     .add_property("entry_point_name_size", entry_point_name_size.to_string());
                 scoped_instrumenter.add_property("args_size", args_size.to_string());
@@ -518,7 +1072,7 @@ where
                     result_size_ptr,
                     &mut scoped_instrumenter,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::CallVersionedContract => {
@@ -542,6 +1096,15 @@ where
                     args_size,
                     result_size_ptr,
                 ): (_, _, _, _, _, u32, _, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.call_versioned_contract,
+                    &[
+                        contract_package_hash_size,
+                        contract_package_size,
+                        entry_point_name_size,
+                        args_size,
+                    ],
+                )?;
 
                 scoped_instrumenter
                     .add_property("entry_point_name_size", entry_point_name_size.to_string());
@@ -566,12 +1129,58 @@ where
                     result_size_ptr,
                     &mut scoped_instrumenter,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CallContractReadOnlyFuncIndex => {
+                // args(0) = pointer to contract hash where contract is at in global state
+                // args(1) = size of contract hash
+                // args(2) = pointer to entry point
+                // args(3) = size of entry point
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to result size (output)
+                let (
+                    contract_hash_ptr,
+                    contract_hash_size,
+                    entry_point_name_ptr,
+                    entry_point_name_size,
+                    args_ptr,
+                    args_size,
+                    result_size_ptr,
+                ): (_, _, _, u32, _, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.call_contract_read_only,
+                    &[contract_hash_size, entry_point_name_size, args_size],
+                )?;
+                scoped_instrumenter
+                    .add_property("entry_point_name_size", entry_point_name_size.to_string());
+                scoped_instrumenter.add_property("args_size", args_size.to_string());
+
+                let contract_hash: ContractHash =
+                    self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+                let entry_point_name: String =
+                    self.t_from_mem(entry_point_name_ptr, entry_point_name_size)?;
+                let args_bytes: Vec<u8> = {
+                    let args_size: u32 = args_size;
+                    self.bytes_from_mem(args_ptr, args_size as usize)?
+                };
+
+                let ret = self.call_contract_read_only_host_buffer(
+                    contract_hash,
+                    &entry_point_name,
+                    args_bytes,
+                    result_size_ptr,
+                    &mut scoped_instrumenter,
+                )?;
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
-            #[cfg(feature = "test-support")]
             FunctionIndex::PrintIndex => {
+                // args(0) = pointer to the text in Wasm memory
+                // args(1) = size of the text in Wasm memory
                 let (text_ptr, text_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.print, &[text_size])?;
                 scoped_instrumenter.add_property("text_size", text_size);
                 self.print(text_ptr, text_size)?;
                 Ok(None)
@@ -582,9 +1191,13 @@ where
                 // args(1) = size of name of the host runtime arg
                 // args(2) = pointer to a argument size (output)
                 let (name_ptr, name_size, size_ptr): (u32, u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.get_named_arg_size,
+                    &[name_size],
+                )?;
                 scoped_instrumenter.add_property("name_size", name_size.to_string());
                 let ret = self.get_named_arg_size(name_ptr, name_size as usize, size_ptr)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::GetRuntimeArgIndex => {
@@ -594,11 +1207,12 @@ where
                 // args(3) = size of available data under output pointer
                 let (name_ptr, name_size, dest_ptr, dest_size): (u32, u32, u32, u32) =
                     Args::parse(args)?;
+                self.charge_host_function_call(host_function_costs.get_named_arg, &[name_size])?;
                 scoped_instrumenter.add_property("name_size", name_size.to_string());
                 scoped_instrumenter.add_property("dest_size", dest_size.to_string());
                 let ret =
                     self.get_named_arg(name_ptr, name_size as usize, dest_ptr, dest_size as usize)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::RemoveContractUserGroupIndex => {
@@ -608,12 +1222,16 @@ where
                 // args(3) = size of serialized group label
                 let (package_key_ptr, package_key_size, label_ptr, label_size): (_, _, _, u32) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.remove_contract_user_group,
+                    &[package_key_size, label_size],
+                )?;
                 scoped_instrumenter.add_property("label_size", label_size.to_string());
                 let package_key = self.t_from_mem(package_key_ptr, package_key_size)?;
                 let label: Group = self.t_from_mem(label_ptr, label_size)?;
 
                 let ret = self.remove_contract_user_group(package_key, label)?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::ExtendContractUserGroupURefsIndex => {
@@ -629,6 +1247,10 @@ where
                     u32,
                     _,
                 ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.provision_contract_user_group_uref,
+                    &[package_size, label_size],
+                )?;
                 scoped_instrumenter.add_property("label_size", label_size.to_string());
                 let ret = self.provision_contract_user_group_uref(
                     package_ptr,
@@ -637,7 +1259,7 @@ where
                     label_size,
                     value_size_ptr,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
 
             FunctionIndex::RemoveContractUserGroupURefsIndex => {
@@ -655,6 +1277,10 @@ where
                     _,
                     u32,
                 ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    host_function_costs.remove_contract_user_group_urefs,
+                    &[package_size, label_size, urefs_size],
+                )?;
                 scoped_instrumenter.add_property("label_size", label_size.to_string());
                 scoped_instrumenter.add_property("urefs_size", urefs_size.to_string());
                 let ret = self.remove_contract_user_group_urefs(
@@ -665,7 +1291,7 @@ where
                     urefs_ptr,
                     urefs_size,
                 )?;
-                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+                Ok(Some(WasmValue::I32(api_error::i32_from(ret))))
             }
         }
     }
@@ -5,7 +5,7 @@ use types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint::Error,
-    CLTyped, CLValue, Key, URef,
+    BlockTime, CLTyped, CLValue, Key, URef,
 };
 
 use crate::{execution, runtime_context::RuntimeContext};
@@ -23,6 +23,14 @@ where
         // TODO: update RuntimeProvider to better handle errors
         self.put_key(name.to_string(), key).expect("should put key")
     }
+
+    fn get_key(&self, name: &str) -> Option<Key> {
+        self.named_keys_get(name).cloned()
+    }
+
+    fn get_block_time(&self) -> BlockTime {
+        self.get_blocktime()
+    }
 }
 
 // TODO: update Mint + StorageProvider to better handle errors
@@ -1,4 +1,5 @@
 mod args;
+mod crypto;
 mod externals;
 mod mint_internal;
 mod proof_of_stake_internal;
@@ -6,51 +7,65 @@ mod scoped_instrumenter;
 mod standard_payment_internal;
 
 use std::{
+    cell::RefCell,
     cmp,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    ops::Bound,
+    rc::Rc,
 };
 
 use itertools::Itertools;
+use log::info;
 use parity_wasm::elements::Module;
-use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
-use ::mint::Mint;
-use engine_shared::{account::Account, gas::Gas, stored_value::StoredValue};
+use ::mint::{Mint, VestingSchedule};
+use engine_shared::{
+    account::Account, gas::Gas, host_function_costs::HostFunctionCost, stored_value::StoredValue,
+};
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
-    account::{AccountHash, ActionType, Weight},
+    account::{AccountHash, ActionType, SetMainPurseFailure, Weight},
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{
         self, Contract, ContractPackage, EntryPoint, EntryPointAccess, EntryPoints, Group,
     },
     runtime_args, system_contract_errors,
     system_contract_errors::mint,
-    AccessRights, ApiError, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash,
-    ContractVersionKey, ContractWasm, EntryPointType, Key, ProtocolVersion, RuntimeArgs,
-    SystemContractType, TransferResult, TransferredTo, URef, U128, U256, U512,
+    AccessRights, ApiError, CLType, CLTyped, CLValue, CallStackElement, ContractHash,
+    ContractPackageHash, ContractVersionKey, ContractWasm, EntryPointType, Key, Motes,
+    ProtocolVersion, RuntimeArgs, SystemContractType, TransferResult, TransferredTo, URef, U128,
+    U256, U512,
 };
 
 use crate::{
     engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
-    execution::Error,
-    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
+    execution::{
+        DefaultWasmEngine, DefaultWasmInstance, DefaultWasmMemory, Error, HostCall, WasmEngine,
+        WasmInstance, WasmMemory,
+    },
     runtime_context::{self, RuntimeContext},
     Address,
 };
-use contracts::{ContractVersion, ContractVersions, DisabledVersions, Groups, NamedKeys};
+use contracts::{
+    ContractVersion, ContractVersions, DisabledVersions, Groups, NamedKeys,
+    MAX_NAMED_KEYS_PAGE_SIZE,
+};
 use scoped_instrumenter::ScopedInstrumenter;
 
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
     config: EngineConfig,
-    memory: MemoryRef,
+    memory: DefaultWasmMemory,
     module: Module,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    call_depth: usize,
+    call_stack: Vec<CallStackElement>,
+    trace: Option<Rc<RefCell<Vec<HostCall>>>>,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -72,18 +87,9 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
-) -> Result<(ModuleRef, MemoryRef), Error> {
-    let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
-    let resolver = create_module_resolver(protocol_version)?;
-    let mut imports = ImportsBuilder::new();
-    imports.push_resolver("env", &resolver);
-    let not_started_module = ModuleInstance::new(&module, &imports)?;
-    if not_started_module.has_start() {
-        return Err(Error::UnsupportedWasmStart);
-    }
-    let instance = not_started_module.not_started_instance().clone();
-    let memory = resolver.memory_ref()?;
-    Ok((instance, memory))
+    max_memory_pages: u32,
+) -> Result<(DefaultWasmInstance, DefaultWasmMemory), Error> {
+    DefaultWasmEngine::default().instantiate(parity_module, protocol_version, max_memory_pages)
 }
 
 /// Turns `key` into a `([u8; 32], AccessRights)` tuple.
@@ -95,6 +101,10 @@ pub fn key_to_tuple(key: Key) -> Option<([u8; 32], AccessRights)> {
         Key::URef(uref) => Some((uref.addr(), uref.access_rights())),
         Key::Account(_) => None,
         Key::Hash(_) => None,
+        Key::SystemRegistry => None,
+        Key::Dictionary(_) => None,
+        Key::ExecutedDeploy(_) => None,
+        Key::Migration(_) => None,
     }
 }
 
@@ -1352,10 +1362,16 @@ where
     pub fn new(
         config: EngineConfig,
         system_contract_cache: SystemContractCache,
-        memory: MemoryRef,
+        memory: DefaultWasmMemory,
         module: Module,
         context: RuntimeContext<'a, R>,
+        call_stack: Vec<CallStackElement>,
     ) -> Self {
+        let trace = if config.enable_execution_trace() {
+            Some(Default::default())
+        } else {
+            None
+        };
         Runtime {
             config,
             system_contract_cache,
@@ -1363,10 +1379,22 @@ where
             module,
             host_buffer: None,
             context,
+            call_depth: 0,
+            call_stack,
+            trace,
         }
     }
 
-    pub fn memory(&self) -> &MemoryRef {
+    /// Returns the host functions invoked so far, including those invoked by called contracts, or
+    /// an empty trace if execution tracing is disabled.
+    pub fn trace(&self) -> Vec<HostCall> {
+        self.trace
+            .as_ref()
+            .map(|trace| trace.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    pub fn memory(&self) -> &DefaultWasmMemory {
         &self.memory
     }
 
@@ -1400,16 +1428,48 @@ where
         }
     }
 
-    fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
+    fn gas(&mut self, amount: Gas) -> Result<(), Error> {
         if self.charge_gas(amount) {
             Ok(())
         } else {
-            Err(Error::GasLimit.into())
+            Err(Error::GasLimit)
         }
     }
 
+    /// Charges the cost of a host function call whose inputs have the given sizes in bytes.
+    fn charge_host_function_call(
+        &mut self,
+        cost: HostFunctionCost,
+        input_sizes: &[u32],
+    ) -> Result<(), Error> {
+        self.gas(cost.calculate_gas_cost(input_sizes))
+    }
+
+    /// Returns the size in bytes of the value currently stored under `key`, or zero if there is
+    /// none.
+    fn stored_value_size(&mut self, key: &Key) -> Result<usize, Error> {
+        let maybe_stored_value = self.context.read_gs_direct(key)?;
+        Ok(maybe_stored_value.map_or(0, |stored_value| stored_value.serialized_length()))
+    }
+
+    /// Charges for the storage taken up by replacing a value of `previous_size` bytes with one of
+    /// `new_size` bytes, or refunds part of that charge if the new value is the smaller one.
+    ///
+    /// Refunds are accumulated rather than taken off the gas counter, and only deducted from the
+    /// cost of the execution once it has succeeded.
+    fn charge_storage(&mut self, previous_size: usize, new_size: usize) -> Result<(), Error> {
+        let storage_costs = self.context.protocol_data().host_function_costs().storage;
+        if new_size >= previous_size {
+            return self.gas(storage_costs.calculate_write_cost(new_size - previous_size));
+        }
+        let refund = storage_costs.calculate_refund(previous_size - new_size);
+        let storage_refund = self.context.storage_refund() + refund;
+        self.context.set_storage_refund(storage_refund);
+        Ok(())
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
-        self.memory.get(ptr, size).map_err(Into::into)
+        self.memory.get(ptr, size)
     }
 
     fn t_from_mem<T: FromBytes>(&self, ptr: u32, size: u32) -> Result<T, Error> {
@@ -1423,6 +1483,28 @@ where
         bytesrepr::deserialize(bytes).map_err(Into::into)
     }
 
+    /// Reads `CLValue` (defined as `cl_value_ptr` and `cl_value_size` tuple) from Wasm memory,
+    /// failing if it's too large to be written to global state.
+    fn writable_cl_value_from_mem(
+        &mut self,
+        cl_value_ptr: u32,
+        cl_value_size: u32,
+    ) -> Result<CLValue, Error> {
+        let cl_value = self.cl_value_from_mem(cl_value_ptr, cl_value_size)?;
+        self.check_value_size(&cl_value)?;
+        Ok(cl_value)
+    }
+
+    /// Fails with `Error::ValueTooLarge` if `cl_value` is too large to be written to global state.
+    fn check_value_size(&self, cl_value: &CLValue) -> Result<(), Error> {
+        let size = cl_value.serialized_length();
+        let max = self.context.protocol_data().max_value_size() as usize;
+        if size > max {
+            return Err(Error::ValueTooLarge { size, max });
+        }
+        Ok(())
+    }
+
     /// Reads `CLValue` (defined as `cl_value_ptr` and `cl_value_size` tuple) from Wasm memory.
     fn cl_value_from_mem(
         &mut self,
@@ -1433,9 +1515,9 @@ where
         bytesrepr::deserialize(bytes).map_err(Into::into)
     }
 
-    fn string_from_mem(&self, ptr: u32, size: u32) -> Result<String, Trap> {
+    fn string_from_mem(&self, ptr: u32, size: u32) -> Result<String, Error> {
         let bytes = self.bytes_from_mem(ptr, size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(|e| Error::BytesRepr(e).into())
+        bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)
     }
 
     fn get_module_from_entry_points(
@@ -1469,7 +1551,7 @@ where
         }
     }
 
-    fn is_valid_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<bool, Trap> {
+    fn is_valid_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<bool, Error> {
         let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
         let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
         Ok(self.context.validate_uref(&uref).is_ok())
@@ -1483,7 +1565,7 @@ where
         output_ptr: u32,
         output_size: usize,
         bytes_written_ptr: u32,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         let name = self.string_from_mem(name_ptr, name_size)?;
 
         // Get a key and serialize it
@@ -1503,21 +1585,17 @@ where
         }
 
         // Set serialized Key bytes into the output buffer
-        if let Err(error) = self.memory.set(output_ptr, &key_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
-        }
+        self.memory.set(output_ptr, &key_bytes)?;
 
         // For all practical purposes following cast is assumed to be safe
         let bytes_size = key_bytes.len() as u32;
         let size_bytes = bytes_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(bytes_written_ptr, &size_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
-        }
+        self.memory.set(bytes_written_ptr, &size_bytes)?;
 
         Ok(Ok(()))
     }
 
-    fn has_key(&mut self, name_ptr: u32, name_size: u32) -> Result<i32, Trap> {
+    fn has_key(&mut self, name_ptr: u32, name_size: u32) -> Result<i32, Error> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         if self.context.named_keys_contains_key(&name) {
             Ok(0)
@@ -1532,30 +1610,35 @@ where
         name_size: u32,
         key_ptr: u32,
         key_size: u32,
-    ) -> Result<(), Trap> {
+    ) -> Result<(), Error> {
         let name = self.string_from_mem(name_ptr, name_size)?;
+        let max = self.context.protocol_data().max_named_key_length() as usize;
+        if name.len() > max {
+            return Err(Error::NamedKeyTooLong {
+                length: name.len(),
+                max,
+            });
+        }
         let key = self.key_from_mem(key_ptr, key_size)?;
-        self.context.put_key(name, key).map_err(Into::into)
+        self.context.put_key(name, key)
     }
 
-    fn remove_key(&mut self, name_ptr: u32, name_size: u32) -> Result<(), Trap> {
+    fn remove_key(&mut self, name_ptr: u32, name_size: u32) -> Result<(), Error> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         self.context.remove_key(&name)?;
         Ok(())
     }
 
     /// Writes runtime context's account main purse to [dest_ptr] in the Wasm memory.
-    fn get_main_purse(&mut self, dest_ptr: u32) -> Result<(), Trap> {
+    fn get_main_purse(&mut self, dest_ptr: u32) -> Result<(), Error> {
         let purse = self.context.get_main_purse()?;
         let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
-        self.memory
-            .set(dest_ptr, &purse_bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &purse_bytes)
     }
 
     /// Writes caller (deploy) account public key to [dest_ptr] in the Wasm
     /// memory.
-    fn get_caller(&mut self, output_size: u32) -> Result<Result<(), ApiError>, Trap> {
+    fn get_caller(&mut self, output_size: u32) -> Result<Result<(), ApiError>, Error> {
         if !self.can_write_to_host_buffer() {
             // Exit early if the host buffer is already occupied
             return Ok(Err(ApiError::HostBufferFull));
@@ -1570,47 +1653,232 @@ where
 
         // Write output
         let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(output_size, &output_size_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+        self.memory.set(output_size, &output_size_bytes)?;
+        Ok(Ok(()))
+    }
+
+    /// Writes the serialized call stack to the host buffer, and its size to `output_size` in the
+    /// Wasm memory.
+    fn get_call_stack(&mut self, output_size: u32) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
         }
+        let value = CLValue::from_t(self.call_stack.clone()).map_err(Error::CLValue)?;
+        let value_size = value.inner_bytes().len();
+
+        if let Err(error) = self.write_host_buffer(value) {
+            return Ok(Err(error));
+        }
+
+        let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        self.memory.set(output_size, &output_size_bytes)?;
         Ok(Ok(()))
     }
 
     /// Writes runtime context's phase to [dest_ptr] in the Wasm memory.
-    fn get_phase(&mut self, dest_ptr: u32) -> Result<(), Trap> {
+    fn get_phase(&mut self, dest_ptr: u32) -> Result<(), Error> {
         let phase = self.context.phase();
         let bytes = phase.into_bytes().map_err(Error::BytesRepr)?;
-        self.memory
-            .set(dest_ptr, &bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &bytes)
+    }
+
+    /// Writes `dest_size` deterministic pseudo-random bytes to [dest_ptr] in Wasm memory.
+    ///
+    /// See [`RuntimeContext::random_bytes`] for the seed derivation.
+    ///
+    /// The destination range is checked against the current memory size before any bytes are
+    /// generated, so a guest can't make the host allocate more than it could ever receive.
+    fn random_bytes(&mut self, dest_ptr: u32, dest_size: u32) -> Result<(), Error> {
+        if u64::from(dest_ptr) + u64::from(dest_size) > self.memory.size() {
+            return Err(Error::MemoryOutOfBounds {
+                ptr: dest_ptr,
+                size: dest_size,
+            });
+        }
+        let bytes = self.context.random_bytes(dest_size as usize)?;
+        self.memory.set(dest_ptr, &bytes)
+    }
+
+    /// Computes `a * b / c` for the serialized `U512`s in Wasm memory with a 1024-bit
+    /// intermediate, and writes the result to `dest_ptr` as 64 little-endian bytes.
+    ///
+    /// Returns `ApiError::None` if `c` is zero or the result doesn't fit in a `U512`.
+    #[allow(clippy::too_many_arguments)]
+    fn u512_mul_div(
+        &mut self,
+        a_ptr: u32,
+        a_size: u32,
+        b_ptr: u32,
+        b_size: u32,
+        c_ptr: u32,
+        c_size: u32,
+        dest_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let a: U512 = self.t_from_mem(a_ptr, a_size)?;
+        let b: U512 = self.t_from_mem(b_ptr, b_size)?;
+        let c: U512 = self.t_from_mem(c_ptr, c_size)?;
+
+        let result = match a.checked_mul_div(b, c) {
+            Some(result) => result,
+            None => return Ok(Err(ApiError::None)),
+        };
+
+        let mut bytes = [0u8; 64];
+        result.to_little_endian(&mut bytes); // Wasm is little-endian
+        self.memory.set(dest_ptr, &bytes)?;
+        Ok(Ok(()))
+    }
+
+    /// Hashes the input bytes with `hash_fn` and writes the digest to `out_ptr` in Wasm memory.
+    fn hash_host(
+        &mut self,
+        hash_fn: fn(&[u8]) -> [u8; crypto::DIGEST_LENGTH],
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if (out_size as usize) < crypto::DIGEST_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+
+        let input = self.bytes_from_mem(in_ptr, in_size as usize)?;
+        let digest = hash_fn(&input);
+        self.memory.set(out_ptr, &digest)?;
+        Ok(Ok(()))
+    }
+
+    /// Writes the blake2b-256 digest of the input bytes to `out_ptr` in Wasm memory.
+    fn blake2b(
+        &mut self,
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        self.hash_host(crypto::blake2b, in_ptr, in_size, out_ptr, out_size)
+    }
+
+    /// Writes the keccak-256 digest of the input bytes to `out_ptr` in Wasm memory.
+    fn keccak256(
+        &mut self,
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        self.hash_host(crypto::keccak256, in_ptr, in_size, out_ptr, out_size)
+    }
+
+    /// Verifies a signature with `verify_fn` after charging `cost`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_host(
+        &mut self,
+        verify_fn: fn(&[u8], &[u8], &[u8]) -> bool,
+        cost: u32,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        public_key_ptr: u32,
+        public_key_size: u32,
+    ) -> Result<bool, Error> {
+        self.gas(Gas::new(cost.into()))?;
+
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature = self.bytes_from_mem(signature_ptr, signature_size as usize)?;
+        let public_key = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+        Ok(verify_fn(&message, &signature, &public_key))
+    }
+
+    /// Verifies an Ed25519 signature of a message.
+    fn verify_ed25519(
+        &mut self,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        public_key_ptr: u32,
+        public_key_size: u32,
+    ) -> Result<bool, Error> {
+        let cost = self.context.protocol_data().wasm_costs().verify_ed25519;
+        self.verify_host(
+            crypto::verify_ed25519,
+            cost,
+            message_ptr,
+            message_size,
+            signature_ptr,
+            signature_size,
+            public_key_ptr,
+            public_key_size,
+        )
+    }
+
+    /// Verifies a secp256k1 signature of a 32-byte message digest.
+    fn verify_secp256k1(
+        &mut self,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        public_key_ptr: u32,
+        public_key_size: u32,
+    ) -> Result<bool, Error> {
+        let cost = self.context.protocol_data().wasm_costs().verify_secp256k1;
+        self.verify_host(
+            crypto::verify_secp256k1,
+            cost,
+            message_ptr,
+            message_size,
+            signature_ptr,
+            signature_size,
+            public_key_ptr,
+            public_key_size,
+        )
     }
 
     /// Writes current blocktime to [dest_ptr] in Wasm memory.
-    fn get_blocktime(&self, dest_ptr: u32) -> Result<(), Trap> {
+    fn get_blocktime(&self, dest_ptr: u32) -> Result<(), Error> {
         let blocktime = self
             .context
             .get_blocktime()
             .into_bytes()
             .map_err(Error::BytesRepr)?;
-        self.memory
-            .set(dest_ptr, &blocktime)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &blocktime)
+    }
+
+    /// Writes the 32-byte hash of the deploy being executed into the Wasm memory.
+    fn get_deploy_hash(&self, dest_ptr: u32) -> Result<(), Error> {
+        self.memory.set(dest_ptr, &self.context.get_deploy_hash())
+    }
+
+    /// Writes the gas price currently in effect (in motes per unit of gas) into the Wasm memory.
+    fn get_gas_price(&self, dest_ptr: u32) -> Result<(), Error> {
+        let gas_price = self
+            .context
+            .protocol_data()
+            .gas_price()
+            .into_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory.set(dest_ptr, &gas_price)
     }
 
     /// Return some bytes from the memory and terminate the current `sub_call`. Note that the return
-    /// type is `Trap`, indicating that this function will always kill the current Wasm instance.
+    /// type is `Error`, indicating that this function will always kill the current Wasm instance.
+    ///
+    /// If `transfer_urefs` is `true`, the `URef`s embedded in the returned value are granted to the
+    /// caller.
     fn ret(
         &mut self,
         value_ptr: u32,
         value_size: usize,
+        transfer_urefs: bool,
         scoped_instrumenter: &mut ScopedInstrumenter,
-    ) -> Trap {
+    ) -> Error {
         const UREF_COUNT: &str = "uref_count";
         self.host_buffer = None;
-        let mem_get = self
-            .memory
-            .get(value_ptr, value_size)
-            .map_err(|e| Error::Interpreter(e.into()));
+        let mem_get = self.memory.get(value_ptr, value_size);
         match mem_get {
             Ok(buf) => {
                 // Set the result field in the runtime and return the proper element of the `Error`
@@ -1618,23 +1886,23 @@ where
                 self.host_buffer = bytesrepr::deserialize(buf).ok();
 
                 let urefs = match &self.host_buffer {
-                    Some(buf) => extract_urefs(buf),
-                    None => Ok(vec![]),
+                    Some(buf) if transfer_urefs => extract_urefs(buf),
+                    _ => Ok(vec![]),
                 };
                 match urefs {
                     Ok(urefs) => {
                         scoped_instrumenter.add_property(UREF_COUNT, urefs.len());
-                        Error::Ret(urefs).into()
+                        Error::Ret(urefs)
                     }
                     Err(e) => {
                         scoped_instrumenter.add_property(UREF_COUNT, 0);
-                        e.into()
+                        e
                     }
                 }
             }
             Err(e) => {
                 scoped_instrumenter.add_property(UREF_COUNT, 0);
-                e.into()
+                e
             }
         }
     }
@@ -1676,6 +1944,15 @@ where
         const METHOD_CREATE: &str = "create";
         const METHOD_BALANCE: &str = "balance";
         const METHOD_TRANSFER: &str = "transfer";
+        const METHOD_TRANSFER_BATCH: &str = "transfer_batch";
+        const METHOD_BURN: &str = "burn";
+        const METHOD_READ_TOTAL_SUPPLY: &str = "read_total_supply";
+        const METHOD_SET_VESTING_SCHEDULE: &str = "set_vesting_schedule";
+        const METHOD_CREATE_TOKEN: &str = "create_token";
+        const METHOD_CREATE_TOKEN_PURSE: &str = "create_token_purse";
+        const METHOD_TOKEN_BALANCE: &str = "token_balance";
+        const METHOD_TOKEN_TRANSFER: &str = "token_transfer";
+        const METHOD_READ_TOKEN_TOTAL_SUPPLY: &str = "read_token_total_supply";
 
         let state = self.context.state();
         let access_rights = {
@@ -1720,15 +1997,17 @@ where
         );
 
         let ret: CLValue = match entry_point_name {
-            // Type: `fn mint(amount: U512) -> Result<URef, Error>`
+            // Type: `fn mint(amount: Motes) -> Result<URef, Error>`
             METHOD_MINT => {
-                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let amount: Motes = Self::get_named_argument(&runtime_args, "amount")?;
                 let result: Result<URef, mint::Error> = mint_context.mint(amount);
                 CLValue::from_t(result)?
             }
             // Type: `fn create() -> URef`
             METHOD_CREATE => {
-                let uref = mint_context.mint(U512::zero()).map_err(Self::reverter)?;
+                let uref = mint_context
+                    .mint(Motes::new(U512::zero()))
+                    .map_err(Self::reverter)?;
                 CLValue::from_t(uref).map_err(Self::reverter)?
             }
             // Type: `fn balance(purse: URef) -> Option<U512>`
@@ -1746,6 +2025,82 @@ where
                 let result: Result<(), mint::Error> = mint_context.transfer(source, target, amount);
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
+            // Type: `fn transfer_batch(source: URef, transfers: Vec<(URef, U512)>) -> Result<(),
+            // Error>`
+            METHOD_TRANSFER_BATCH => {
+                let source: URef = Self::get_named_argument(&runtime_args, "source")?;
+                let transfers: Vec<(URef, U512)> =
+                    Self::get_named_argument(&runtime_args, "transfers")?;
+                let result: Result<(), mint::Error> =
+                    mint_context.transfer_batch(source, transfers);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn burn(purse: URef, amount: U512) -> Result<(), Error>`
+            METHOD_BURN => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> = mint_context.burn(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn read_total_supply() -> U512`
+            METHOD_READ_TOTAL_SUPPLY => {
+                let total_supply: U512 = mint_context.total_supply().map_err(Self::reverter)?;
+                CLValue::from_t(total_supply).map_err(Self::reverter)?
+            }
+            // Type: `fn set_vesting_schedule(purse: URef, vesting_schedule: VestingSchedule) ->
+            // Result<(), Error>`
+            METHOD_SET_VESTING_SCHEDULE => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let vesting_schedule: VestingSchedule =
+                    Self::get_named_argument(&runtime_args, "vesting_schedule")?;
+                let result: Result<(), mint::Error> =
+                    mint_context.set_vesting_schedule(purse, vesting_schedule);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn create_token(token_id: String, initial_supply: U512) -> Result<URef,
+            // Error>`
+            METHOD_CREATE_TOKEN => {
+                let id: String = Self::get_named_argument(&runtime_args, "token_id")?;
+                let initial_supply: U512 =
+                    Self::get_named_argument(&runtime_args, "initial_supply")?;
+                let result: Result<URef, mint::Error> =
+                    mint_context.create_token(id, initial_supply);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn create_token_purse(token_id: String) -> Result<URef, Error>`
+            METHOD_CREATE_TOKEN_PURSE => {
+                let id: String = Self::get_named_argument(&runtime_args, "token_id")?;
+                let result: Result<URef, mint::Error> = mint_context.create_token_purse(id);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn token_balance(token_id: String, purse: URef) -> Option<U512>`
+            METHOD_TOKEN_BALANCE => {
+                let id: String = Self::get_named_argument(&runtime_args, "token_id")?;
+                let uref: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let maybe_balance: Option<U512> = mint_context
+                    .token_balance(id, uref)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(maybe_balance).map_err(Self::reverter)?
+            }
+            // Type: `fn token_transfer(token_id: String, source: URef, target: URef, amount: U512)
+            // -> Result<(), Error>`
+            METHOD_TOKEN_TRANSFER => {
+                let id: String = Self::get_named_argument(&runtime_args, "token_id")?;
+                let source: URef = Self::get_named_argument(&runtime_args, "source")?;
+                let target: URef = Self::get_named_argument(&runtime_args, "target")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> =
+                    mint_context.token_transfer(id, source, target, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn read_token_total_supply(token_id: String) -> U512`
+            METHOD_READ_TOKEN_TOTAL_SUPPLY => {
+                let id: String = Self::get_named_argument(&runtime_args, "token_id")?;
+                let total_supply: U512 = mint_context
+                    .token_total_supply(id)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(total_supply).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1764,12 +2119,19 @@ where
     ) -> Result<CLValue, Error> {
         const METHOD_BOND: &str = "bond";
         const METHOD_UNBOND: &str = "unbond";
+        const METHOD_DELEGATE: &str = "delegate";
+        const METHOD_UNDELEGATE: &str = "undelegate";
         const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
         const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
         const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
         const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+        const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+        const METHOD_SLASH: &str = "slash";
+        const METHOD_WITHDRAW_UNBONDED: &str = "withdraw_unbonded";
+        const METHOD_STEP: &str = "step";
         const ARG_AMOUNT: &str = "amount";
         const ARG_PURSE: &str = "purse";
+        const ARG_VALIDATOR: &str = "validator";
 
         let state = self.context.state();
         let access_rights = {
@@ -1813,12 +2175,14 @@ where
             protocol_data,
         );
 
+        // The proof-of-stake contract is run natively here, and can't inspect its call stack.
         let mut runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
+            self.call_stack.clone(),
         );
 
         let ret: CLValue = match entry_point_name {
@@ -1849,6 +2213,48 @@ where
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_WITHDRAW_UNBONDED => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let account: AccountHash = runtime.context.get_caller();
+                runtime.withdraw_unbonded(account).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_DELEGATE => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let delegator: AccountHash = runtime.context.get_caller();
+                let validator: AccountHash =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR)?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, ARG_AMOUNT)?;
+                let source_uref: URef = Self::get_named_argument(&runtime_args, ARG_PURSE)?;
+                runtime
+                    .delegate(delegator, validator, amount, source_uref)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_UNDELEGATE => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let delegator: AccountHash = runtime.context.get_caller();
+                let validator: AccountHash =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR)?;
+                let maybe_amount: Option<U512> =
+                    Self::get_named_argument(&runtime_args, ARG_AMOUNT)?;
+                runtime
+                    .undelegate(delegator, validator, maybe_amount)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             METHOD_GET_PAYMENT_PURSE => {
                 let rights_controlled_purse =
                     runtime.get_payment_purse().map_err(Self::reverter)?;
@@ -1864,13 +2270,27 @@ where
                 CLValue::from_t(maybe_purse).map_err(Self::reverter)?
             }
             METHOD_FINALIZE_PAYMENT => {
-                let amount_spent: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let amount_spent: Motes = Self::get_named_argument(&runtime_args, "amount")?;
                 let account: AccountHash = Self::get_named_argument(&runtime_args, "account")?;
                 runtime
                     .finalize_payment(amount_spent, account)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_DISTRIBUTE_REWARDS => {
+                runtime.distribute_rewards().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_SLASH => {
+                let validator: AccountHash =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR)?;
+                runtime.slash(validator).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_STEP => {
+                runtime.step().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1881,10 +2301,20 @@ where
 
     pub fn call_host_standard_payment(&mut self) -> Result<(), Error> {
         let amount: U512 = Self::get_named_argument(&self.context.args(), "amount")?;
-        self.pay(amount).map_err(Self::reverter)
+        let maybe_source: Option<URef> = if self.context.args().get("source").is_some() {
+            Some(Self::get_named_argument(&self.context.args(), "source")?)
+        } else {
+            None
+        };
+        match maybe_source {
+            Some(source) => self.pay_from(source, amount),
+            None => self.pay(amount),
+        }
+        .map_err(Self::reverter)
     }
 
-    /// Calls contract living under a `key`, with supplied `args`.
+    /// Calls contract living under a `key`, with supplied `args`. This function also checks the
+    /// args conform with the types given in the contract header.
     pub fn call_contract(
         &mut self,
         contract_hash: ContractHash,
@@ -1908,11 +2338,22 @@ where
             .cloned()
             .ok_or_else(|| Error::NoSuchMethod(entry_point_name.to_owned()))?;
 
+        // Only group-restricted entry points need the package; public ones skip the extra read.
+        if let EntryPointAccess::Groups(_) = entry_point.access() {
+            let contract_package: ContractPackage = self
+                .context
+                .read_gs_typed(&contract.contract_package_hash().into())?;
+            self.validate_entry_point_access(&contract_package, entry_point.access())?;
+        }
+
+        Self::validate_entry_point_args(&entry_point, &args)?;
+
         let context_key = self.get_context_key_for_contract_call(contract_hash, &entry_point)?;
 
         self.execute_contract(
             key,
             context_key,
+            contract_hash,
             contract,
             args,
             entry_point,
@@ -1920,6 +2361,28 @@ where
         )
     }
 
+    /// Calls the contract living at `contract_hash` like [`Self::call_contract`], discarding any
+    /// change it makes to global state. If the callee attempts a write, the call fails with
+    /// [`Error::ReadOnlyViolation`].
+    pub fn call_contract_read_only(
+        &mut self,
+        contract_hash: ContractHash,
+        entry_point_name: &str,
+        args: RuntimeArgs,
+    ) -> Result<CLValue, Error> {
+        let snapshot = self.context.state().borrow().snapshot();
+        let result = self.call_contract(contract_hash, entry_point_name, args);
+        let state = self.context.state();
+        let mut state = state.borrow_mut();
+        let has_changes = state.has_changes_since(&snapshot);
+        state.restore(snapshot);
+        let value = result?;
+        if has_changes {
+            return Err(Error::ReadOnlyViolation);
+        }
+        Ok(value)
+    }
+
     /// Calls `version` of the contract living at `key`, invoking `method` with
     /// supplied `args`. This function also checks the args conform with the
     /// types given in the contract header.
@@ -1978,23 +2441,14 @@ where
 
         self.validate_entry_point_access(&contract_package, entry_point.access())?;
 
-        for (expected, found) in entry_point
-            .args()
-            .iter()
-            .map(|a| a.cl_type())
-            .cloned()
-            .zip(args.to_values().into_iter().map(|v| v.cl_type()).cloned())
-        {
-            if expected != found {
-                return Err(Error::type_mismatch(expected, found));
-            }
-        }
+        Self::validate_entry_point_args(&entry_point, &args)?;
 
         let context_key = self.get_context_key_for_contract_call(contract_hash, &entry_point)?;
 
         self.execute_contract(
             context_key,
             context_key,
+            contract_hash,
             contract,
             args,
             entry_point,
@@ -2030,15 +2484,52 @@ where
         self.context.access_rights_extend(access_rights)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn execute_contract(
         &mut self,
         key: Key,
         base_key: Key,
+        contract_hash: ContractHash,
         contract: Contract,
         args: RuntimeArgs,
         entry_point: EntryPoint,
         protocol_version: ProtocolVersion,
     ) -> Result<CLValue, Error> {
+        let call_depth = self.call_depth + 1;
+        if call_depth > self.config.max_call_depth() {
+            return Err(Error::CallDepthLimit(self.config.max_call_depth()));
+        }
+
+        if contract.is_disabled() {
+            return Err(Error::ContractDisabled(contract_hash));
+        }
+
+        if contract.is_non_reentrant()
+            && self
+                .call_stack
+                .iter()
+                .any(|element| element.contract_hash() == Some(&contract_hash))
+        {
+            return Err(Error::ReentrantCall(contract_hash));
+        }
+
+        let call_stack = {
+            let contract_package_hash = contract.contract_package_hash();
+            let element = match entry_point.entry_point_type() {
+                EntryPointType::Session => CallStackElement::stored_session(
+                    self.context.account().account_hash(),
+                    contract_package_hash,
+                    contract_hash,
+                ),
+                EntryPointType::Contract => {
+                    CallStackElement::stored_contract(contract_package_hash, contract_hash)
+                }
+            };
+            let mut call_stack = self.call_stack.clone();
+            call_stack.push(element);
+            call_stack
+        };
+
         // Check for major version compatibility before calling
         if !contract.is_compatible_protocol_version(protocol_version) {
             return Err(Error::IncompatibleProtocolMajorVersion {
@@ -2112,7 +2603,11 @@ where
 
         let entry_point_name = entry_point.name();
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (instance, memory) = instance_and_memory(
+            module.clone(),
+            protocol_version,
+            self.config.max_memory_pages(),
+        )?;
 
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -2156,14 +2651,21 @@ where
             module,
             host_buffer,
             context,
+            call_depth,
+            call_stack,
+            trace: self.trace.clone(),
         };
 
-        let result = instance.invoke_export(entry_point_name, &[], &mut runtime);
+        let result = instance.invoke(entry_point_name, &mut runtime);
 
         // The `runtime`'s context was initialized with our counter from before the call and any gas
         // charged by the sub-call was added to its counter - so let's copy the correct value of the
         // counter from there to our counter
         self.context.set_gas_counter(runtime.context.gas_counter());
+        // Likewise, the sub-call started without any storage refund, so add the one it accrued to
+        // ours
+        let storage_refund = self.context.storage_refund() + runtime.context.storage_refund();
+        self.context.set_storage_refund(storage_refund);
 
         let error = match result {
             Err(error) => error,
@@ -2183,32 +2685,27 @@ where
             }
         };
 
-        if let Some(host_error) = error.as_host_error() {
+        match error {
             // If the "error" was in fact a trap caused by calling `ret` then
             // this is normal operation and we should return the value captured
             // in the Runtime result field.
-            let downcasted_error = host_error.downcast_ref::<Error>().unwrap();
-            match downcasted_error {
-                Error::Ret(ref ret_urefs) => {
-                    // insert extra urefs returned from call
-                    let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
-                        extract_access_rights_from_urefs(ret_urefs.clone());
-                    self.context.access_rights_extend(ret_urefs_map);
-                    // if ret has not set host_buffer consider it programmer error
-                    if self.context.entry_point_type() == EntryPointType::Session
-                        && runtime.context.entry_point_type() == EntryPointType::Session
-                    {
-                        // Overwrites parent's named keys with child's new named key but only when
-                        // running session code
-                        *self.context.named_keys_mut() = runtime.context.named_keys().clone();
-                    }
-                    return runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue);
+            Error::Ret(ret_urefs) => {
+                // insert extra urefs returned from call
+                let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
+                    extract_access_rights_from_urefs(ret_urefs);
+                self.context.access_rights_extend(ret_urefs_map);
+                // if ret has not set host_buffer consider it programmer error
+                if self.context.entry_point_type() == EntryPointType::Session
+                    && runtime.context.entry_point_type() == EntryPointType::Session
+                {
+                    // Overwrites parent's named keys with child's new named key but only when
+                    // running session code
+                    *self.context.named_keys_mut() = runtime.context.named_keys().clone();
                 }
-                error => return Err(error.clone()),
+                runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue)
             }
+            error => Err(error),
         }
-
-        Err(Error::Interpreter(error.into()))
     }
 
     fn call_contract_host_buffer(
@@ -2230,6 +2727,25 @@ where
         self.manage_call_contract_host_buffer(result_size_ptr, result)
     }
 
+    fn call_contract_read_only_host_buffer(
+        &mut self,
+        contract_hash: ContractHash,
+        entry_point_name: &str,
+        args_bytes: Vec<u8>,
+        result_size_ptr: u32,
+        scoped_instrumenter: &mut ScopedInstrumenter,
+    ) -> Result<Result<(), ApiError>, Error> {
+        // Exit early if the host buffer is already occupied
+        if let Err(err) = self.check_host_buffer() {
+            return Ok(Err(err));
+        }
+        let args: RuntimeArgs = bytesrepr::deserialize(args_bytes)?;
+        scoped_instrumenter.pause();
+        let result = self.call_contract_read_only(contract_hash, entry_point_name, args)?;
+        scoped_instrumenter.unpause();
+        self.manage_call_contract_host_buffer(result_size_ptr, result)
+    }
+
     fn call_versioned_contract_host_buffer(
         &mut self,
         contract_package_hash: ContractPackageHash,
@@ -2278,9 +2794,7 @@ where
         }
 
         let result_size_bytes = result_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(result_size_ptr, &result_size_bytes) {
-            return Err(Error::Interpreter(error.into()));
-        }
+        self.memory.set(result_size_ptr, &result_size_bytes)?;
 
         Ok(Ok(()))
     }
@@ -2290,7 +2804,7 @@ where
         total_keys_ptr: u32,
         result_size_ptr: u32,
         scoped_instrumenter: &mut ScopedInstrumenter,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         scoped_instrumenter.add_property(
             "names_total_length",
             self.context
@@ -2307,9 +2821,7 @@ where
 
         let total_keys = self.context.named_keys().len() as u32;
         let total_keys_bytes = total_keys.to_le_bytes();
-        if let Err(error) = self.memory.set(total_keys_ptr, &total_keys_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
-        }
+        self.memory.set(total_keys_ptr, &total_keys_bytes)?;
 
         if total_keys == 0 {
             // No need to do anything else, we leave host buffer empty.
@@ -2325,10 +2837,77 @@ where
         }
 
         let length_bytes = length.to_le_bytes();
-        if let Err(error) = self.memory.set(result_size_ptr, &length_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+        self.memory.set(result_size_ptr, &length_bytes)?;
+
+        Ok(Ok(()))
+    }
+
+    /// Writes up to `limit` named keys of the current context, capped at
+    /// [`MAX_NAMED_KEYS_PAGE_SIZE`], to the host buffer.  The page starts after the name read from
+    /// `start_after_ptr` in name order, or at the first key if that name is `None`.
+    ///
+    /// The serialized page is charged per byte, on top of the cost charged for the call.
+    fn load_named_keys_paged(
+        &mut self,
+        start_after_ptr: u32,
+        start_after_size: u32,
+        limit: u32,
+        page_keys_ptr: u32,
+        result_size_ptr: u32,
+        scoped_instrumenter: &mut ScopedInstrumenter,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
         }
 
+        let start_after: Option<String> = self.t_from_mem(start_after_ptr, start_after_size)?;
+        let start = match start_after {
+            Some(name) => Bound::Excluded(name),
+            None => Bound::Unbounded,
+        };
+        let limit = limit.min(MAX_NAMED_KEYS_PAGE_SIZE);
+
+        let page: NamedKeys = self
+            .context
+            .named_keys()
+            .range((start, Bound::Unbounded))
+            .take(limit as usize)
+            .map(|(name, key)| (name.clone(), *key))
+            .collect();
+
+        scoped_instrumenter.add_property(
+            "names_total_length",
+            page.keys().map(|name| name.len()).sum::<usize>(),
+        );
+
+        let page_keys = page.len() as u32;
+        let page_keys_bytes = page_keys.to_le_bytes();
+        self.memory.set(page_keys_ptr, &page_keys_bytes)?;
+
+        if page_keys == 0 {
+            // No need to do anything else, we leave host buffer empty.
+            return Ok(Ok(()));
+        }
+
+        let page = CLValue::from_t(page).map_err(Error::CLValue)?;
+
+        let length = page.inner_bytes().len() as u32;
+        let per_byte_cost = self
+            .context
+            .protocol_data()
+            .host_function_costs()
+            .load_named_keys_paged
+            .per_byte;
+        self.charge_host_function_call(HostFunctionCost::new(0, per_byte_cost), &[length])?;
+
+        if let Err(error) = self.write_host_buffer(page) {
+            return Ok(Err(error));
+        }
+
+        let length_bytes = length.to_le_bytes();
+        self.memory.set(result_size_ptr, &length_bytes)?;
+
         Ok(Ok(()))
     }
 
@@ -2416,9 +2995,7 @@ where
         }
         // Write return value size to output location
         let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(output_size_ptr, &output_size_bytes) {
-            return Err(Error::Interpreter(error.into()));
-        }
+        self.memory.set(output_size_ptr, &output_size_bytes)?;
 
         // Write updated package to the global state
         self.context.state().borrow_mut().write(
@@ -2507,22 +3084,16 @@ where
             }
 
             // Set serialized Key bytes into the output buffer
-            if let Err(error) = self.memory.set(output_ptr, &key_bytes) {
-                return Err(Error::Interpreter(error.into()));
-            }
+            self.memory.set(output_ptr, &key_bytes)?;
 
             // Following cast is assumed to be safe
             let bytes_size = key_bytes.len() as u32;
             let size_bytes = bytes_size.to_le_bytes(); // Wasm is little-endian
-            if let Err(error) = self.memory.set(bytes_written_ptr, &size_bytes) {
-                return Err(Error::Interpreter(error.into()));
-            }
+            self.memory.set(bytes_written_ptr, &size_bytes)?;
 
             let version_value: u32 = insert_contract_result.contract_version();
             let version_bytes = version_value.to_le_bytes();
-            if let Err(error) = self.memory.set(version_ptr, &version_bytes) {
-                return Err(Error::Interpreter(error.into()));
-            }
+            self.memory.set(version_ptr, &version_bytes)?;
         }
 
         Ok(Ok(()))
@@ -2552,22 +3123,88 @@ where
         Ok(Ok(()))
     }
 
+    /// Retires the given contract, leaving a tombstone which keeps its named keys but can no longer
+    /// be called, and disables its version in the contract package. Requires access to the
+    /// contract package.
+    fn disable_contract(
+        &mut self,
+        contract_hash: ContractHash,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_key = Key::Hash(contract_hash);
+        let mut contract: Contract = self.context.read_gs_typed(&contract_key)?;
+
+        let contract_package_hash = contract.contract_package_hash();
+        let mut contract_package: ContractPackage = self
+            .context
+            .get_validated_contract_package(contract_package_hash)?;
+
+        if let Err(err) = contract_package.disable_contract_version(contract_hash) {
+            return Ok(Err(err.into()));
+        }
+        contract.disable();
+
+        let state = self.context.state();
+        let mut state = state.borrow_mut();
+        state.write(
+            contract_package_hash.into(),
+            StoredValue::ContractPackage(contract_package),
+        );
+        state.write(contract_key, StoredValue::Contract(contract));
+
+        Ok(Ok(()))
+    }
+
+    /// Sets whether the given version of a contract rejects calls made while it is already on the
+    /// call stack. Requires access to the contract package.
+    fn set_non_reentrant(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        contract_hash: ContractHash,
+        non_reentrant: bool,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_package_key = contract_package_hash.into();
+        self.context.validate_key(&contract_package_key)?;
+
+        let contract_package: ContractPackage = self
+            .context
+            .get_validated_contract_package(contract_package_hash)?;
+
+        if !contract_package
+            .versions()
+            .values()
+            .any(|version_hash| *version_hash == contract_hash)
+        {
+            return Ok(Err(contracts::Error::ContractNotFound.into()));
+        }
+
+        let contract_key = Key::Hash(contract_hash);
+        let mut contract: Contract = self.context.read_gs_typed(&contract_key)?;
+        contract.set_non_reentrant(non_reentrant);
+
+        self.context
+            .state()
+            .borrow_mut()
+            .write(contract_key, StoredValue::Contract(contract));
+
+        Ok(Ok(()))
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
-    fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
-        self.memory
-            .set(dest_ptr, &hash_bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+    fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Error> {
+        self.memory.set(dest_ptr, &hash_bytes)
     }
 
     /// Generates new unforgable reference and adds it to the context's
     /// access_rights set.
-    fn new_uref(&mut self, uref_ptr: u32, value_ptr: u32, value_size: u32) -> Result<(), Trap> {
-        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?; // read initial value from memory
-        let uref = self.context.new_uref(StoredValue::CLValue(cl_value))?;
-        self.memory
-            .set(uref_ptr, &uref.into_bytes().map_err(Error::BytesRepr)?)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+    fn new_uref(&mut self, uref_ptr: u32, value_ptr: u32, value_size: u32) -> Result<(), Error> {
+        // read initial value from memory
+        let cl_value = self.writable_cl_value_from_mem(value_ptr, value_size)?;
+        let value = StoredValue::CLValue(cl_value);
+        // a new uref has no previous value
+        self.charge_storage(0, value.serialized_length())?;
+        let uref = self.context.new_uref(value)?;
+        self.memory.set(uref_ptr, &uref.into_bytes().map_err(Error::BytesRepr)?)
     }
 
     /// Writes `value` under `key` in GlobalState.
@@ -2577,12 +3214,98 @@ where
         key_size: u32,
         value_ptr: u32,
         value_size: u32,
-    ) -> Result<(), Trap> {
+    ) -> Result<(), Error> {
         let key = self.key_from_mem(key_ptr, key_size)?;
-        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
-        self.context
-            .write_gs(key, StoredValue::CLValue(cl_value))
-            .map_err(Into::into)
+        let cl_value = self.writable_cl_value_from_mem(value_ptr, value_size)?;
+        let value = StoredValue::CLValue(cl_value);
+        let previous_size = self.stored_value_size(&key)?;
+        let new_size = value.serialized_length();
+        self.context.write_gs(key, value)?;
+        self.charge_storage(previous_size, new_size)
+    }
+
+    /// Deletes the value under `uref` from GlobalState, refunding part of the storage charge for
+    /// it.
+    fn delete(&mut self, uref_ptr: u32, uref_size: u32) -> Result<(), Error> {
+        let uref: URef = self.t_from_mem(uref_ptr, uref_size)?;
+        let key = Key::URef(uref);
+        let previous_size = self.stored_value_size(&key)?;
+        self.context.delete_gs(key)?;
+        self.charge_storage(previous_size, 0)
+    }
+
+    /// Writes `value` under `key` like `write`, and buffers the value previously stored under
+    /// `key` in the host so that the contract doesn't have to read it separately beforehand.
+    ///
+    /// Returns `ApiError::ValueNotFound` if there was no previous value, in which case the new
+    /// value is still written.
+    fn write_and_return_old(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        let cl_value = self.writable_cl_value_from_mem(value_ptr, value_size)?;
+        let value = StoredValue::CLValue(cl_value);
+        let (old_value, previous_size) = match self.context.read_gs(&key)? {
+            Some(stored_value) => {
+                let previous_size = stored_value.serialized_length();
+                let old_value = CLValue::try_from(stored_value).map_err(Error::TypeMismatch)?;
+                (Some(old_value), previous_size)
+            }
+            None => (None, 0),
+        };
+        let new_size = value.serialized_length();
+        self.context.write_gs(key, value)?;
+        self.charge_storage(previous_size, new_size)?;
+
+        let old_value = match old_value {
+            Some(old_value) => old_value,
+            None => return Ok(Err(ApiError::ValueNotFound)),
+        };
+        let old_value_size = old_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buffer(old_value) {
+            return Ok(Err(error));
+        }
+
+        let old_value_size_bytes = old_value_size.to_le_bytes(); // Wasm is little-endian
+        self.memory.set(output_size_ptr, &old_value_size_bytes)?;
+
+        Ok(Ok(()))
+    }
+
+    /// Writes the new value under `key` only if the value currently stored there equals the
+    /// expected one, returning whether the write happened.
+    fn compare_and_swap(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        expected_ptr: u32,
+        expected_size: u32,
+        new_ptr: u32,
+        new_size: u32,
+    ) -> Result<bool, Error> {
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        let expected = self.cl_value_from_mem(expected_ptr, expected_size)?;
+        let new = self.writable_cl_value_from_mem(new_ptr, new_size)?;
+        let expected = StoredValue::CLValue(expected);
+        let new = StoredValue::CLValue(new);
+        // if the swap happens, the value it replaces is the expected one
+        let previous_size = expected.serialized_length();
+        let new_size = new.serialized_length();
+        let swapped = self.context.compare_and_swap_gs(key, expected, new)?;
+        if swapped {
+            self.charge_storage(previous_size, new_size)?;
+        }
+        Ok(swapped)
     }
 
     /// Writes `value` under a key derived from `key` in the "local cluster" of
@@ -2593,12 +3316,16 @@ where
         key_size: u32,
         value_ptr: u32,
         value_size: u32,
-    ) -> Result<(), Trap> {
+    ) -> Result<(), Error> {
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
-        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
-        self.context
-            .write_ls(&key_bytes, cl_value)
-            .map_err(Into::into)
+        let cl_value = self.writable_cl_value_from_mem(value_ptr, value_size)?;
+        let new_size = StoredValue::CLValue(cl_value.clone()).serialized_length();
+        let previous_size = match self.context.read_ls(&key_bytes)? {
+            Some(old_value) => StoredValue::CLValue(old_value).serialized_length(),
+            None => 0,
+        };
+        self.context.write_ls(&key_bytes, cl_value)?;
+        self.charge_storage(previous_size, new_size)
     }
 
     /// Adds `value` to the cell that `key` points at.
@@ -2608,12 +3335,50 @@ where
         key_size: u32,
         value_ptr: u32,
         value_size: u32,
-    ) -> Result<(), Trap> {
+    ) -> Result<(), Error> {
         let key = self.key_from_mem(key_ptr, key_size)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
-        self.context
-            .add_gs(key, StoredValue::CLValue(cl_value))
-            .map_err(Into::into)
+        if let CLType::List(_) = cl_value.cl_type() {
+            return self.append(key, cl_value);
+        }
+        self.context.add_gs(key, StoredValue::CLValue(cl_value))
+    }
+
+    /// Appends the list `items` to the one under `key`, failing if the resulting list is too large
+    /// to be written to global state, and charges for the storage taken up by the appended items.
+    fn append(&mut self, key: Key, items: CLValue) -> Result<(), Error> {
+        let previous_size = self
+            .context
+            .get_gs_direct(&key)?
+            .map_or(0, |stored_value| stored_value.serialized_length());
+        self.context.add_gs(key, StoredValue::CLValue(items))?;
+        let list = match self.context.get_gs_direct(&key)? {
+            Some(StoredValue::CLValue(list)) => list,
+            Some(_) | None => return Err(Error::KeyNotFound(key)),
+        };
+        self.check_value_size(&list)?;
+        let new_size = StoredValue::CLValue(list).serialized_length();
+        // the length prefix of the list is already paid for, so only the appended items are charged
+        self.charge_storage(previous_size, new_size)
+    }
+
+    /// Adds one to the `u64` counter under the key specified by `key_ptr` and `key_size`, and
+    /// writes the incremented value to `value_ptr` in the Wasm memory.
+    ///
+    /// The counter is only ever changed by a `Transform::AddUInt64`.  As the new value is returned,
+    /// the key is also recorded as read, so deploys incrementing the same counter conflict rather
+    /// than being handed the same value.
+    fn counter_inc(&mut self, key_ptr: u32, key_size: u32, value_ptr: u32) -> Result<(), Error> {
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        let one = CLValue::from_t(1u64).map_err(Error::CLValue)?;
+        self.context.add_gs(key, StoredValue::CLValue(one))?;
+
+        let value: u64 = match self.context.read_gs(&key)? {
+            Some(StoredValue::CLValue(cl_value)) => cl_value.into_t().map_err(Error::CLValue)?,
+            Some(_) | None => return Err(Error::KeyNotFound(key)),
+        };
+
+        self.memory.set(value_ptr, &value.to_le_bytes()) // Wasm is little-endian
     }
 
     /// Reads value from the GS living under key specified by `key_ptr` and
@@ -2626,7 +3391,7 @@ where
         key_ptr: u32,
         key_size: u32,
         output_size_ptr: u32,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         if !self.can_write_to_host_buffer() {
             // Exit early if the host buffer is already occupied
             return Ok(Err(ApiError::HostBufferFull));
@@ -2644,9 +3409,7 @@ where
         }
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
-        }
+        self.memory.set(output_size_ptr, &value_bytes)?;
 
         Ok(Ok(()))
     }
@@ -2658,7 +3421,7 @@ where
         key_ptr: u32,
         key_size: u32,
         output_size_ptr: u32,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         if !self.can_write_to_host_buffer() {
             // Exit early if the host buffer is already occupied
             return Ok(Err(ApiError::HostBufferFull));
@@ -2677,16 +3440,74 @@ where
         }
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+        self.memory.set(output_size_ptr, &value_bytes)?;
+
+        Ok(Ok(()))
+    }
+
+    /// Creates a new dictionary seed [`URef`] and writes it to `uref_ptr` in Wasm memory.
+    fn dictionary_new(&mut self, uref_ptr: u32) -> Result<(), Error> {
+        let uref = self.context.new_unit_uref()?;
+        self.memory.set(uref_ptr, &uref.into_bytes().map_err(Error::BytesRepr)?)
+    }
+
+    /// Reads the dictionary item under the given item key into the host buffer, writing its size
+    /// to `output_size_ptr`.
+    fn dictionary_get(
+        &mut self,
+        uref_ptr: u32,
+        uref_size: u32,
+        key_ptr: u32,
+        key_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
         }
 
+        let seed: URef = self.t_from_mem(uref_ptr, uref_size)?;
+        let item_key = self.bytes_from_mem(key_ptr, key_size as usize)?;
+
+        let cl_value = match self.context.dictionary_get(seed, &item_key)? {
+            Some(cl_value) => cl_value,
+            None => return Ok(Err(ApiError::ValueNotFound)),
+        };
+
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buffer(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        self.memory.set(output_size_ptr, &value_bytes)?;
+
         Ok(Ok(()))
     }
 
+    /// Writes a value under the given item key in the dictionary identified by a seed [`URef`].
+    fn dictionary_put(
+        &mut self,
+        uref_ptr: u32,
+        uref_size: u32,
+        key_ptr: u32,
+        key_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Error> {
+        let seed: URef = self.t_from_mem(uref_ptr, uref_size)?;
+        let item_key = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        let cl_value = self.writable_cl_value_from_mem(value_ptr, value_size)?;
+        let new_size = StoredValue::CLValue(cl_value.clone()).serialized_length();
+        let dictionary_key = runtime_context::dictionary_item_key(seed, &item_key);
+        let previous_size = self.stored_value_size(&dictionary_key)?;
+        self.context.dictionary_put(seed, &item_key, cl_value)?;
+        self.charge_storage(previous_size, new_size)
+    }
+
     /// Reverts contract execution with a status specified.
-    fn revert(&mut self, status: u32) -> Trap {
-        Error::Revert(status.into()).into()
+    fn revert(&mut self, status: u32) -> Error {
+        Error::Revert(status.into())
     }
 
     fn add_associated_key(
@@ -2694,7 +3515,7 @@ where
         account_hash_ptr: u32,
         account_hash_size: usize,
         weight_value: u8,
-    ) -> Result<i32, Trap> {
+    ) -> Result<i32, Error> {
         let account_hash = {
             // Account hash as serialized bytes
             let source_serialized = self.bytes_from_mem(account_hash_ptr, account_hash_size)?;
@@ -2712,8 +3533,8 @@ where
             // are greater than the first one, so it's safe to assume `0` is success,
             // and any error is greater than 0.
             Err(Error::AddKeyFailure(e)) => Ok(e as i32),
-            // Any other variant just pass as `Trap`
-            Err(e) => Err(e.into()),
+            // Any other variant just pass as `Error`
+            Err(e) => Err(e),
         }
     }
 
@@ -2721,7 +3542,7 @@ where
         &mut self,
         account_hash_ptr: u32,
         account_hash_size: usize,
-    ) -> Result<i32, Trap> {
+    ) -> Result<i32, Error> {
         let account_hash = {
             // Account hash as serialized bytes
             let source_serialized = self.bytes_from_mem(account_hash_ptr, account_hash_size)?;
@@ -2733,7 +3554,7 @@ where
         match self.context.remove_associated_key(account_hash) {
             Ok(_) => Ok(0),
             Err(Error::RemoveKeyFailure(e)) => Ok(e as i32),
-            Err(e) => Err(e.into()),
+            Err(e) => Err(e),
         }
     }
 
@@ -2742,7 +3563,7 @@ where
         account_hash_ptr: u32,
         account_hash_size: usize,
         weight_value: u8,
-    ) -> Result<i32, Trap> {
+    ) -> Result<i32, Error> {
         let account_hash = {
             // Account hash as serialized bytes
             let source_serialized = self.bytes_from_mem(account_hash_ptr, account_hash_size)?;
@@ -2760,8 +3581,8 @@ where
             // are greater than the first one, so it's safe to assume `0` is success,
             // and any error is greater than 0.
             Err(Error::UpdateKeyFailure(e)) => Ok(e as i32),
-            // Any other variant just pass as `Trap`
-            Err(e) => Err(e.into()),
+            // Any other variant just pass as `Error`
+            Err(e) => Err(e),
         }
     }
 
@@ -2769,20 +3590,48 @@ where
         &mut self,
         action_type_value: u32,
         threshold_value: u8,
-    ) -> Result<i32, Trap> {
+    ) -> Result<i32, Error> {
         match ActionType::try_from(action_type_value) {
             Ok(action_type) => {
                 let threshold = Weight::new(threshold_value);
                 match self.context.set_action_threshold(action_type, threshold) {
                     Ok(_) => Ok(0),
                     Err(Error::SetThresholdFailure(e)) => Ok(e as i32),
-                    Err(e) => Err(e.into()),
+                    Err(e) => Err(e),
                 }
             }
-            Err(_) => Err(Trap::new(TrapKind::Unreachable)),
+            Err(_) => Err(Error::Interpreter(format!(
+                "Invalid action type {}",
+                action_type_value
+            ))),
         }
     }
 
+    fn set_main_purse(&mut self, purse_ptr: u32, purse_size: u32) -> Result<i32, Error> {
+        let purse: URef = self.t_from_mem(purse_ptr, purse_size)?;
+        // Only purses known to the mint can become the main purse
+        if self.get_balance(purse)?.is_none() {
+            return Ok(SetMainPurseFailure::InvalidPurse as i32);
+        }
+        match self.context.set_main_purse(purse) {
+            Ok(_) => Ok(0),
+            Err(Error::SetMainPurseFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn register_system_contract(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        contract_hash_ptr: u32,
+        contract_hash_size: u32,
+    ) -> Result<(), Error> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let contract_hash: ContractHash = self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+        self.context.register_system_contract(name, contract_hash)
+    }
+
     /// Looks up the public mint contract key in the context's protocol data.
     ///
     /// Returned URef is already attenuated depending on the calling account.
@@ -2841,6 +3690,27 @@ where
         Ok(result.map_err(system_contract_errors::Error::from)?)
     }
 
+    /// Calls the "transfer_batch" method on the mint contract at the given mint
+    /// contract key
+    fn mint_transfer_batch(
+        &mut self,
+        mint_contract_hash: ContractHash,
+        source: URef,
+        transfers: Vec<(URef, U512)>,
+    ) -> Result<(), Error> {
+        const ARG_SOURCE: &str = "source";
+        const ARG_TRANSFERS: &str = "transfers";
+
+        let args_values: RuntimeArgs = runtime_args! {
+            ARG_SOURCE => source,
+            ARG_TRANSFERS => transfers,
+        };
+
+        let result = self.call_contract(mint_contract_hash, "transfer_batch", args_values)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
     /// Creates a new account at a given public key, transferring a given amount
     /// of motes from the given source purse to the new account's purse.
     fn transfer_to_new_account(
@@ -2867,7 +3737,10 @@ where
 
         match self.mint_transfer(mint_contract_hash, source, target_purse, amount) {
             Ok(_) => {
-                let account = Account::create(target, Default::default(), target_purse);
+                let mut account = Account::create(target, Default::default(), target_purse);
+                if self.context.protocol_data().nonces_enabled() {
+                    account.set_nonce(Some(0));
+                }
                 self.context.write_account(target_key, account)?;
                 Ok(Ok(TransferredTo::NewAccount))
             }
@@ -2937,6 +3810,72 @@ where
         }
     }
 
+    /// Transfers motes from `source` purse to each of the `transfers` target accounts in a single
+    /// call to the mint, creating any accounts which don't exist yet.  Either every transfer
+    /// succeeds or none do.
+    fn transfer_from_purse_to_accounts(
+        &mut self,
+        source: URef,
+        transfers: Vec<(AccountHash, U512)>,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let mint_contract_hash = self.get_mint_contract();
+
+        // A precondition check that verifies that the source purse has enough funds to cover
+        // every transfer, so that no purses are created for a batch which can't succeed.
+        let total = transfers
+            .iter()
+            .try_fold(U512::zero(), |total, (_, amount)| {
+                total.checked_add(*amount)
+            });
+        match total {
+            Some(total) if total <= self.get_balance(source)?.unwrap_or_default() => (),
+            _ => return Ok(Err(ApiError::Transfer)),
+        }
+
+        let mut mint_transfers = Vec::with_capacity(transfers.len());
+        let mut new_accounts: Vec<(AccountHash, URef)> = Vec::new();
+        for (target, amount) in transfers {
+            // The same new account may be the target of several transfers in the batch.
+            if let Some((_, target_purse)) = new_accounts.iter().find(|(hash, _)| *hash == target) {
+                mint_transfers.push((*target_purse, amount));
+                continue;
+            }
+            let target_key = Key::Account(target);
+            match self.context.read_account(&target_key)? {
+                None => {
+                    let target_purse = self.mint_create(mint_contract_hash)?;
+                    new_accounts.push((target, target_purse));
+                    mint_transfers.push((target_purse, amount));
+                }
+                Some(StoredValue::Account(account)) => {
+                    let target_purse = account.main_purse_add_only();
+                    if source == target_purse {
+                        continue;
+                    }
+                    self.context.insert_uref(target_purse);
+                    mint_transfers.push((target_purse, amount));
+                }
+                Some(_) => return Err(Error::AccountNotFound(target_key)),
+            }
+        }
+
+        if self
+            .mint_transfer_batch(mint_contract_hash, source, mint_transfers)
+            .is_err()
+        {
+            return Ok(Err(ApiError::Transfer));
+        }
+
+        for (target, target_purse) in new_accounts {
+            let mut account = Account::create(target, Default::default(), target_purse);
+            if self.context.protocol_data().nonces_enabled() {
+                account.set_nonce(Some(0));
+            }
+            self.context.write_account(Key::Account(target), account)?;
+        }
+        Ok(Ok(()))
+    }
+
     /// Transfers `amount` of motes from `source` purse to `target` purse.
     fn transfer_from_purse_to_purse(
         &mut self,
@@ -3040,9 +3979,7 @@ where
         }
 
         let balance_size_bytes = balance_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(output_size_ptr, &balance_size_bytes) {
-            return Err(Error::Interpreter(error.into()));
-        }
+        self.memory.set(output_size_ptr, &balance_size_bytes)?;
 
         Ok(Ok(()))
     }
@@ -3052,7 +3989,7 @@ where
         system_contract_index: u32,
         dest_ptr: u32,
         _dest_size: u32,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         let contract_hash: ContractHash = match SystemContractType::try_from(system_contract_index)
         {
             Ok(SystemContractType::Mint) => self.get_mint_contract(),
@@ -3063,7 +4000,7 @@ where
 
         match self.memory.set(dest_ptr, &contract_hash) {
             Ok(_) => Ok(Ok(())),
-            Err(error) => Err(Error::Interpreter(error.into()).into()),
+            Err(error) => Err(error),
         }
     }
 
@@ -3072,6 +4009,15 @@ where
         self.host_buffer.take()
     }
 
+    /// Returns the number of bytes which a read of the host buffer would copy, or 0 if it's empty.
+    fn host_buffer_size(&self) -> u32 {
+        self.host_buffer
+            .as_ref()
+            .map(|cl_value| cl_value.inner_bytes().len())
+            .map(|size| u32::try_from(size).unwrap_or(u32::max_value()))
+            .unwrap_or_default()
+    }
+
     /// Checks if a write to host buffer can happen.
     ///
     /// This will check if the host buffer is empty.
@@ -3109,24 +4055,39 @@ where
         // Slice data, so if `dest_size` is larger than host_buffer size, it will take host_buffer
         // as whole.
         let sliced_buf = &serialized_value[..cmp::min(dest_size, serialized_value.len())];
-        if let Err(error) = self.memory.set(dest_ptr, sliced_buf) {
-            return Err(Error::Interpreter(error.into()));
-        }
+        self.memory.set(dest_ptr, sliced_buf)?;
 
         let bytes_written = sliced_buf.len() as u32;
         let bytes_written_data = bytes_written.to_le_bytes();
 
-        if let Err(error) = self.memory.set(bytes_written_ptr, &bytes_written_data) {
-            return Err(Error::Interpreter(error.into()));
-        }
+        self.memory.set(bytes_written_ptr, &bytes_written_data)?;
 
         Ok(Ok(()))
     }
 
-    #[cfg(feature = "test-support")]
-    fn print(&mut self, text_ptr: u32, text_size: u32) -> Result<(), Trap> {
+    /// Emits an event with the given name and data on behalf of the current context.
+    fn emit_event(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        data_ptr: u32,
+        data_size: u32,
+    ) -> Result<(), Error> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let data = self.cl_value_from_mem(data_ptr, data_size)?;
+        self.context.emit_event(name, data);
+        Ok(())
+    }
+
+    /// Forwards debug output of a contract to the engine log if contract debugging is enabled.
+    ///
+    /// The text is read and validated whether or not it is logged, so that a deploy has the same
+    /// outcome on every node.
+    fn print(&mut self, text_ptr: u32, text_size: u32) -> Result<(), Error> {
         let text = self.string_from_mem(text_ptr, text_size)?;
-        println!("{}", text);
+        if self.config.enable_contract_debug() {
+            info!("contract debug: {}", text);
+        }
         Ok(())
     }
 
@@ -3135,7 +4096,7 @@ where
         name_ptr: u32,
         name_size: usize,
         size_ptr: u32,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         let name_bytes = self.bytes_from_mem(name_ptr, name_size)?;
         let name = String::from_utf8_lossy(&name_bytes);
 
@@ -3149,9 +4110,7 @@ where
 
         let arg_size_bytes = arg_size.to_le_bytes(); // Wasm is little-endian
 
-        if let Err(e) = self.memory.set(size_ptr, &arg_size_bytes) {
-            return Err(Error::Interpreter(e.into()).into());
-        }
+        self.memory.set(size_ptr, &arg_size_bytes)?;
 
         Ok(Ok(()))
     }
@@ -3162,7 +4121,7 @@ where
         name_size: usize,
         output_ptr: u32,
         output_size: usize,
-    ) -> Result<Result<(), ApiError>, Trap> {
+    ) -> Result<Result<(), ApiError>, Error> {
         let name_bytes = self.bytes_from_mem(name_ptr, name_size)?;
         let name = String::from_utf8_lossy(&name_bytes);
 
@@ -3179,7 +4138,7 @@ where
             .memory
             .set(output_ptr, &arg.inner_bytes()[..output_size])
         {
-            return Err(Error::Interpreter(e.into()).into());
+            return Err(e);
         }
 
         Ok(Ok(()))
@@ -3195,6 +4154,28 @@ where
         })
     }
 
+    /// Checks that each of `args` declared by `entry_point` has the declared type.
+    ///
+    /// Arguments are matched by name.  Parameters declared as [`CLType::Any`] accept a value of
+    /// any type, and missing arguments are left for the callee to report when it reads them.
+    fn validate_entry_point_args(
+        entry_point: &EntryPoint,
+        args: &RuntimeArgs,
+    ) -> Result<(), Error> {
+        for parameter in entry_point.args() {
+            let expected = parameter.cl_type();
+            if *expected == CLType::Any {
+                continue;
+            }
+            if let Some(found) = args.get(parameter.name()).map(CLValue::cl_type) {
+                if expected != found {
+                    return Err(Error::type_mismatch(expected.clone(), found.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Remove a user group from access to a contract
     fn remove_contract_user_group(
         &mut self,
@@ -3299,9 +4280,7 @@ where
         }
         // Write return value size to output location
         let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
-        if let Err(error) = self.memory.set(output_size_ptr, &output_size_bytes) {
-            return Err(Error::Interpreter(error.into()));
-        }
+        self.memory.set(output_size_ptr, &output_size_bytes)?;
 
         // Write updated package to the global state
         self.context.state().borrow_mut().write(
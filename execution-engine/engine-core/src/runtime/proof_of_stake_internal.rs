@@ -1,19 +1,27 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    fmt::Write,
-};
+use std::collections::{BTreeMap, BTreeSet};
 
 use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    Delegations, DelegationsProvider, MintProvider, ProofOfStake, Queue, QueueProvider,
+    RewardsProvider, RuntimeProvider, SlashingProvider, Stakes, StakesProvider,
 };
 use types::{
-    account::AccountHash, bytesrepr::ToBytes, system_contract_errors::pos::Error, ApiError,
-    BlockTime, CLValue, Key, Phase, TransferredTo, URef, U512,
+    account::AccountHash,
+    bytesrepr::ToBytes,
+    runtime_args,
+    system_contract_errors::{mint, pos::Error},
+    ApiError, BlockTime, CLValue, Key, Phase, RuntimeArgs, TransferredTo, URef, U512,
 };
 
-use crate::{execution, runtime::Runtime};
+use crate::{
+    engine_state::genesis::{
+        POS_INFLATION_RATE, POS_MAX_BOND, POS_MAX_VALIDATOR_SLOTS, POS_MIN_BOND, POS_SLASH_RATE,
+        POS_STAKES, POS_UNBONDING_DELAY,
+    },
+    execution,
+    runtime::Runtime,
+};
 
 const BONDING_KEY: [u8; 32] = {
     let mut result = [0; 32];
@@ -63,6 +71,38 @@ where
     fn balance(&mut self, purse: URef) -> Option<U512> {
         self.get_balance(purse).expect("should get balance")
     }
+
+    fn mint(&mut self, amount: U512) -> Result<URef, ()> {
+        const METHOD_MINT: &str = "mint";
+        const ARG_AMOUNT: &str = "amount";
+
+        let mint_contract_hash = self.get_mint_contract();
+        let args = runtime_args! {
+            ARG_AMOUNT => amount,
+        };
+        let result = self
+            .call_contract(mint_contract_hash, METHOD_MINT, args)
+            .map_err(|_| ())?;
+        let result: Result<URef, mint::Error> = result.into_t().map_err(|_| ())?;
+        result.map_err(|_| ())
+    }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        const METHOD_BURN: &str = "burn";
+        const ARG_PURSE: &str = "purse";
+        const ARG_AMOUNT: &str = "amount";
+
+        let mint_contract_hash = self.get_mint_contract();
+        let args = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+        let result = self
+            .call_contract(mint_contract_hash, METHOD_BURN, args)
+            .map_err(|_| ())?;
+        let result: Result<(), mint::Error> = result.into_t().map_err(|_| ())?;
+        result.map_err(|_| ())
+    }
 }
 
 // TODO: Update QueueProvider to better handle errors
@@ -102,6 +142,21 @@ where
             .write_ls(&key, value)
             .expect("should write local state")
     }
+
+    fn read_unbonding_delay(&mut self) -> Result<u64, Error> {
+        let uref = match self.context.named_keys_get(POS_UNBONDING_DELAY) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::UnbondingDelayKeyUnexpectedType),
+            None => return Err(Error::UnbondingDelayNotFound),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::UnbondingDelayNotFound)?;
+        cl_value
+            .into_t()
+            .map_err(|_| Error::UnbondingDelayKeyUnexpectedType)
+    }
 }
 
 // TODO: Update RuntimeProvider to better handle errors
@@ -142,30 +197,19 @@ where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<execution::Error>,
 {
-    fn read(&self) -> Result<Stakes, Error> {
-        let mut stakes = BTreeMap::new();
-        for (name, _) in self.context.named_keys().iter() {
-            let mut split_name = name.split('_');
-            if Some("v") != split_name.next() {
-                continue;
-            }
-            let hex_key = split_name
-                .next()
-                .ok_or(Error::StakesKeyDeserializationFailed)?;
-            if hex_key.len() != 64 {
-                return Err(Error::StakesKeyDeserializationFailed);
-            }
-            let mut key_bytes = [0u8; 32];
-            let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
-                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            debug_assert!(_bytes_written == key_bytes.len());
-            let pub_key = AccountHash::new(key_bytes);
-            let balance = split_name
-                .next()
-                .and_then(|b| U512::from_dec_str(b).ok())
-                .ok_or(Error::StakesDeserializationFailed)?;
-            stakes.insert(pub_key, balance);
-        }
+    fn read(&mut self) -> Result<Stakes, Error> {
+        let uref = match self.context.named_keys_get(POS_STAKES) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::StakesKeyDeserializationFailed),
+            None => return Err(Error::StakesNotFound),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::StakesNotFound)?;
+        let stakes: BTreeMap<AccountHash, U512> = cl_value
+            .into_t()
+            .map_err(|_| Error::StakesDeserializationFailed)?;
         if stakes.is_empty() {
             return Err(Error::StakesNotFound);
         }
@@ -173,26 +217,78 @@ where
     }
 
     fn write(&mut self, stakes: &Stakes) {
-        // Encode the stakes as a set of uref names.
-        let mut new_urefs: BTreeSet<String> = stakes
-            .0
-            .iter()
-            .map(|(pub_key, balance)| {
-                let key_bytes = pub_key.value();
-                let mut hex_key = String::with_capacity(64);
-                for byte in &key_bytes[..32] {
-                    write!(hex_key, "{:02x}", byte).expect("Writing to a string cannot fail");
-                }
-                let mut uref = String::new();
-                uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
-                    .expect("Writing to a string cannot fail");
-                uref
-            })
-            .collect();
+        let uref = match self.context.named_keys_get(POS_STAKES) {
+            Some(Key::URef(uref)) => *uref,
+            _ => panic!("should have stakes uref"),
+        };
+        let value = CLValue::from_t(stakes.0.clone()).expect("should convert");
+        self.context
+            .write_gs(Key::URef(uref), StoredValue::CLValue(value))
+            .expect("should write stakes")
+    }
+
+    fn read_min_bond(&mut self) -> Result<u64, Error> {
+        let uref = match self.context.named_keys_get(POS_MIN_BOND) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::MinBondKeyUnexpectedType),
+            None => return Ok(0),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::MinBondNotFound)?;
+        cl_value
+            .into_t()
+            .map_err(|_| Error::MinBondKeyUnexpectedType)
+    }
+
+    fn read_max_bond(&mut self) -> Result<u64, Error> {
+        let uref = match self.context.named_keys_get(POS_MAX_BOND) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::MaxBondKeyUnexpectedType),
+            None => return Ok(0),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::MaxBondNotFound)?;
+        cl_value
+            .into_t()
+            .map_err(|_| Error::MaxBondKeyUnexpectedType)
+    }
+
+    fn read_max_validator_slots(&mut self) -> Result<u32, Error> {
+        let uref = match self.context.named_keys_get(POS_MAX_VALIDATOR_SLOTS) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::ValidatorSlotsKeyUnexpectedType),
+            None => return Ok(0),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::ValidatorSlotsNotFound)?;
+        cl_value
+            .into_t()
+            .map_err(|_| Error::ValidatorSlotsKeyUnexpectedType)
+    }
+}
+
+impl<'a, R> DelegationsProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_delegations(&self) -> Result<Delegations, Error> {
+        Delegations::from_strings(self.context.named_keys().keys())
+    }
+
+    fn write_delegations(&mut self, delegations: &Delegations) {
+        // Encode the delegations as a set of uref names.
+        let mut new_urefs: BTreeSet<String> = delegations.strings().collect();
         // Remove and add urefs to update the contract's known urefs accordingly.
         let mut removes = Vec::new();
         for (name, _) in self.context.named_keys().iter() {
-            if name.starts_with("v_") && !new_urefs.remove(name) {
+            if Delegations::is_delegation_string(name) && !new_urefs.remove(name) {
                 removes.push(name.to_owned())
             }
         }
@@ -207,6 +303,48 @@ where
     }
 }
 
+impl<'a, R> RewardsProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_inflation_rate(&mut self) -> Result<u64, Error> {
+        let uref = match self.context.named_keys_get(POS_INFLATION_RATE) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::InflationRateKeyUnexpectedType),
+            None => return Err(Error::InflationRateNotFound),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::InflationRateNotFound)?;
+        cl_value
+            .into_t()
+            .map_err(|_| Error::InflationRateKeyUnexpectedType)
+    }
+}
+
+impl<'a, R> SlashingProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_slash_rate(&mut self) -> Result<u64, Error> {
+        let uref = match self.context.named_keys_get(POS_SLASH_RATE) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(Error::SlashRateKeyUnexpectedType),
+            None => return Err(Error::SlashRateNotFound),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&Key::URef(uref))
+            .map_err(|_| Error::SlashRateNotFound)?;
+        cl_value
+            .into_t()
+            .map_err(|_| Error::SlashRateKeyUnexpectedType)
+    }
+}
+
 impl<'a, R> ProofOfStake for Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
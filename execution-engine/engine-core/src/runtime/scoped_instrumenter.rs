@@ -86,12 +86,13 @@ impl Drop for ScopedInstrumenter {
     fn drop(&mut self) {
         let duration = self.duration();
         let host_function = match self.function_index {
-            FunctionIndex::GasFuncIndex => return,
+            FunctionIndex::GasFuncIndex | FunctionIndex::StackOverflowFuncIndex => return,
             FunctionIndex::WriteFuncIndex => "host_function_write",
             FunctionIndex::WriteLocalFuncIndex => "host_function_write_local",
             FunctionIndex::ReadFuncIndex => "host_function_read_value",
             FunctionIndex::ReadLocalFuncIndex => "host_function_read_value_local",
             FunctionIndex::AddFuncIndex => "host_function_add",
+            FunctionIndex::CounterIncIndex => "host_function_counter_inc",
             FunctionIndex::NewFuncIndex => "host_function_new_uref",
             FunctionIndex::RetFuncIndex => "host_function_ret",
             FunctionIndex::CallContractFuncIndex => "host_function_call_contract",
@@ -113,6 +114,9 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::TransferFromPurseToAccountIndex => {
                 "host_function_transfer_from_purse_to_account"
             }
+            FunctionIndex::TransferFromPurseToAccountsIndex => {
+                "host_function_transfer_from_purse_to_accounts"
+            }
             FunctionIndex::TransferFromPurseToPurseIndex => {
                 "host_function_transfer_from_purse_to_purse"
             }
@@ -128,7 +132,6 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::DisableContractVersion => "host_remove_contract_version",
             FunctionIndex::CallVersionedContract => "host_call_versioned_contract",
             FunctionIndex::CreateContractUserGroup => "create_contract_user_group",
-            #[cfg(feature = "test-support")]
             FunctionIndex::PrintIndex => "host_function_print",
             FunctionIndex::GetRuntimeArgsizeIndex => "host_get_named_arg_size",
             FunctionIndex::GetRuntimeArgIndex => "host_get_named_arg",
@@ -139,6 +142,30 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::RemoveContractUserGroupURefsIndex => {
                 "host_remove_contract_user_group_urefs"
             }
+            FunctionIndex::RandomBytesIndex => "host_function_random_bytes",
+            FunctionIndex::U512MulDivIndex => "host_function_u512_mul_div",
+            FunctionIndex::EmitEventIndex => "host_function_emit_event",
+            FunctionIndex::DictionaryNewIndex => "host_function_dictionary_new",
+            FunctionIndex::DictionaryGetIndex => "host_function_dictionary_get",
+            FunctionIndex::DictionaryPutIndex => "host_function_dictionary_put",
+            FunctionIndex::Blake2bIndex => "host_function_blake2b",
+            FunctionIndex::Keccak256Index => "host_function_keccak256",
+            FunctionIndex::VerifyEd25519Index => "host_function_verify_ed25519",
+            FunctionIndex::VerifySecp256k1Index => "host_function_verify_secp256k1",
+            FunctionIndex::RetWithoutTransferIndex => "host_function_ret_without_transfer",
+            FunctionIndex::CallContractReadOnlyFuncIndex => "host_function_call_contract_read_only",
+            FunctionIndex::LoadNamedKeysPagedFuncIndex => "host_function_load_named_keys_paged",
+            FunctionIndex::SetMainPurseIndex => "host_function_set_main_purse",
+            FunctionIndex::RegisterSystemContractIndex => "host_function_register_system_contract",
+            FunctionIndex::GetGasPriceIndex => "host_function_get_gas_price",
+            FunctionIndex::WriteAndReturnOldFuncIndex => "host_function_write_and_return_old",
+            FunctionIndex::CompareAndSwapFuncIndex => "host_function_compare_and_swap",
+            FunctionIndex::DeleteFuncIndex => "host_function_delete",
+            FunctionIndex::IsSystemCallerIndex => "host_function_is_system_caller",
+            FunctionIndex::GetDeployHashIndex => "host_function_get_deploy_hash",
+            FunctionIndex::GetCallStackIndex => "host_function_get_call_stack",
+            FunctionIndex::SetNonReentrantIndex => "host_function_set_non_reentrant",
+            FunctionIndex::DisableContractIndex => "host_function_disable_contract",
         };
 
         let mut properties = mem::take(&mut self.properties);
@@ -1,5 +1,6 @@
 use std::{
     cell::RefCell,
+    cmp,
     collections::{BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::Debug,
@@ -12,25 +13,30 @@ use blake2::{
 };
 
 use engine_shared::{
-    account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+    account::Account, gas::Gas, host_function_costs::MAX_STORAGE_REFUND_QUOTIENT,
+    newtypes::CorrelationId, stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetMainPurseFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr,
     contracts::NamedKeys,
-    AccessRights, BlockTime, CLType, CLValue, Contract, ContractPackage, ContractPackageHash,
-    EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    KEY_HASH_LENGTH,
+    system_contract_type::SystemContractRegistry,
+    AccessRights, BlockTime, CLType, CLValue, Contract, ContractHash, ContractPackage,
+    ContractPackageHash, EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion,
+    RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
-    engine_state::execution_effect::ExecutionEffect,
-    execution::{AddressGenerator, Error},
-    tracking_copy::{AddResult, TrackingCopy},
+    engine_state::{
+        execution_effect::{ContractEvent, ExecutionEffect},
+        SYSTEM_ACCOUNT_ADDR,
+    },
+    execution::{AddressGenerator, AddressGeneratorBuilder, Error},
+    tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     Address,
 };
 
@@ -54,6 +60,21 @@ pub(crate) fn uref_has_access_rights(
     }
 }
 
+/// Domain separator for dictionary item addresses.
+const DICTIONARY_ITEM_PREFIX: &[u8] = b"dictionary";
+
+/// Derives the global state key of a dictionary item as the blake2b hash of the dictionary seed
+/// address and the item key.
+pub fn dictionary_item_key(seed: URef, item_key: &[u8]) -> Key {
+    let mut hasher = VarBlake2b::new(KEY_HASH_LENGTH).unwrap();
+    hasher.input(DICTIONARY_ITEM_PREFIX);
+    hasher.input(seed.addr());
+    hasher.input(item_key);
+    let mut hash_bytes = [0; KEY_HASH_LENGTH];
+    hasher.variable_result(|hash| hash_bytes.clone_from_slice(hash));
+    Key::Dictionary(hash_bytes)
+}
+
 pub fn validate_entry_point_access_with(
     contract_package: &ContractPackage,
     access: &EntryPointAccess,
@@ -99,6 +120,8 @@ pub struct RuntimeContext<'a, R> {
     deploy_hash: [u8; KEY_HASH_LENGTH],
     gas_limit: Gas,
     gas_counter: Gas,
+    // Gas refunded for the storage freed so far, deducted only once execution has finished
+    storage_refund: Gas,
     hash_address_generator: Rc<RefCell<AddressGenerator>>,
     uref_address_generator: Rc<RefCell<AddressGenerator>>,
     protocol_version: ProtocolVersion,
@@ -147,6 +170,7 @@ where
             base_key,
             gas_limit,
             gas_counter,
+            storage_refund: Gas::default(),
             hash_address_generator,
             uref_address_generator,
             protocol_version,
@@ -230,6 +254,10 @@ where
                 self.named_keys.remove(name);
                 self.remove_key_from_contract(contract_hash, contract, name)
             }
+            Key::SystemRegistry
+            | Key::Dictionary(_)
+            | Key::ExecutedDeploy(_)
+            | Key::Migration(_) => Err(Error::InvalidContext),
         }
     }
 
@@ -237,6 +265,20 @@ where
         self.account.account_hash()
     }
 
+    /// Returns `true` if the deploy is being run by the reserved system account.
+    pub fn is_system_caller(&self) -> bool {
+        self.get_caller() == SYSTEM_ACCOUNT_ADDR
+    }
+
+    /// Returns an error unless the deploy is being run by the system account during the system
+    /// phase, as required by the privileged host functions.
+    fn check_system_privileges(&self) -> Result<(), Error> {
+        if self.phase != Phase::System || !self.is_system_caller() {
+            return Err(Error::InvalidContext);
+        }
+        Ok(())
+    }
+
     pub fn get_blocktime(&self) -> BlockTime {
         self.blocktime
     }
@@ -281,6 +323,25 @@ where
         self.gas_counter = new_gas_counter;
     }
 
+    /// Returns the gas refunded so far for the storage freed by the execution.  Unlike gas
+    /// charges, it isn't applied to the gas counter as it accrues.
+    pub fn storage_refund(&self) -> Gas {
+        self.storage_refund
+    }
+
+    pub fn set_storage_refund(&mut self, new_storage_refund: Gas) {
+        self.storage_refund = new_storage_refund;
+    }
+
+    /// Returns the gas counter less the storage refund, which is what a finished execution costs.
+    ///
+    /// The refund is capped at the gas counter divided by [`MAX_STORAGE_REFUND_QUOTIENT`].
+    pub fn gas_counter_less_storage_refund(&self) -> Gas {
+        let max_storage_refund =
+            self.gas_counter / Gas::new(U512::from(MAX_STORAGE_REFUND_QUOTIENT));
+        self.gas_counter - cmp::min(self.storage_refund, max_storage_refund)
+    }
+
     pub fn base_key(&self) -> Key {
         self.base_key
     }
@@ -308,6 +369,24 @@ where
         Ok(hash_bytes)
     }
 
+    /// Generates `count` deterministic pseudo-random bytes.
+    ///
+    /// The seed is the blake2b hash of the next output of the hash address generator (itself
+    /// seeded with the deploy hash and the current phase) followed by the serialized blocktime.
+    /// Every validator replaying the same deploy in the same block therefore observes the same
+    /// sequence, while consecutive calls within a deploy yield distinct bytes.
+    pub fn random_bytes(&mut self, count: usize) -> Result<Vec<u8>, Error> {
+        let pre_seed_bytes = self.hash_address_generator.borrow_mut().create_address();
+        let blocktime_bytes = bytesrepr::ToBytes::to_bytes(&self.blocktime)?;
+        let mut generator = AddressGeneratorBuilder::new()
+            .seed_with(&pre_seed_bytes)
+            .seed_with(&blocktime_bytes)
+            .build();
+        let mut bytes = vec![0u8; count];
+        generator.fill_bytes(&mut bytes);
+        Ok(bytes)
+    }
+
     pub fn new_uref(&mut self, value: StoredValue) -> Result<URef, Error> {
         let uref = {
             let addr = self.uref_address_generator.borrow_mut().create_address();
@@ -374,6 +453,52 @@ where
         Ok(())
     }
 
+    /// Reads the dictionary item stored under `item_key` in the dictionary identified by `seed`.
+    ///
+    /// Requires read access to `seed`.
+    pub fn dictionary_get(
+        &mut self,
+        seed: URef,
+        item_key: &[u8],
+    ) -> Result<Option<CLValue>, Error> {
+        let seed_key = Key::URef(seed);
+        self.validate_readable(&seed_key)?;
+        self.validate_key(&seed_key)?;
+
+        let key = dictionary_item_key(seed, item_key);
+        let maybe_stored_value = self
+            .tracking_copy
+            .borrow_mut()
+            .read(self.correlation_id, &key)
+            .map_err(Into::into)?;
+
+        match maybe_stored_value {
+            Some(stored_value) => Ok(Some(stored_value.try_into().map_err(Error::TypeMismatch)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Writes `cl_value` under `item_key` in the dictionary identified by `seed`.
+    ///
+    /// Requires write access to `seed`.
+    pub fn dictionary_put(
+        &mut self,
+        seed: URef,
+        item_key: &[u8],
+        cl_value: CLValue,
+    ) -> Result<(), Error> {
+        let seed_key = Key::URef(seed);
+        self.validate_writeable(&seed_key)?;
+        self.validate_key(&seed_key)?;
+
+        let value = StoredValue::CLValue(cl_value);
+        self.validate_value(&value)?;
+
+        let key = dictionary_item_key(seed, item_key);
+        self.tracking_copy.borrow_mut().write(key, value);
+        Ok(())
+    }
+
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
@@ -392,6 +517,17 @@ where
             .map_err(Into::into)
     }
 
+    /// Reads the value under `key` without recording the read in the execution effect, so that the
+    /// host can inspect a value without changing how the deploy conflicts with others.
+    ///
+    /// DO NOT EXPOSE THIS VIA THE FFI
+    pub fn get_gs_direct(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
+        self.tracking_copy
+            .borrow_mut()
+            .get(self.correlation_id, &key.normalize())
+            .map_err(Into::into)
+    }
+
     /// This method is a wrapper over `read_gs` in the sense that it extracts the type held by a
     /// `StoredValue` stored in the global state in a type safe manner.
     ///
@@ -422,6 +558,32 @@ where
         Ok(())
     }
 
+    /// Deletes the value under `key`.  `key` must be writeable.
+    pub fn delete_gs(&mut self, key: Key) -> Result<(), Error> {
+        self.validate_writeable(&key)?;
+        self.validate_key(&key)?;
+        self.tracking_copy.borrow_mut().delete(key);
+        Ok(())
+    }
+
+    /// Writes `new` under `key` if the value currently under it equals `expected`, returning
+    /// whether the write happened.  `key` must be both readable and writeable.
+    pub fn compare_and_swap_gs(
+        &mut self,
+        key: Key,
+        expected: StoredValue,
+        new: StoredValue,
+    ) -> Result<bool, Error> {
+        self.validate_readable(&key)?;
+        self.validate_writeable(&key)?;
+        self.validate_key(&key)?;
+        self.validate_value(&new)?;
+        self.tracking_copy
+            .borrow_mut()
+            .compare_and_swap(self.correlation_id, &key, &expected, new)
+            .map_err(Into::into)
+    }
+
     pub fn read_account(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         if let Key::Account(_) = key {
             self.validate_key(key)?;
@@ -484,6 +646,17 @@ where
         self.tracking_copy.borrow_mut().effect()
     }
 
+    /// Returns `true` if the effects recorded so far exceed the configured limit.
+    pub fn is_effects_limit_exceeded(&self) -> bool {
+        self.tracking_copy.borrow().is_effects_limit_exceeded()
+    }
+
+    /// Records an event emitted by the entity identified by the current base key.
+    pub fn emit_event(&mut self, name: String, data: CLValue) {
+        let event = ContractEvent::new(self.base_key, name, data);
+        self.tracking_copy.borrow_mut().emit_event(event);
+    }
+
     /// Validates whether keys used in the `value` are not forged.
     fn validate_value(&self, value: &StoredValue) -> Result<(), Error> {
         match value {
@@ -617,8 +790,9 @@ where
     pub fn is_readable(&self, key: &Key) -> bool {
         match key {
             Key::Account(_) => &self.base_key() == key,
-            Key::Hash(_) => true,
+            Key::Hash(_) | Key::SystemRegistry => true,
             Key::URef(uref) => uref.is_readable(),
+            Key::Dictionary(_) | Key::ExecutedDeploy(_) | Key::Migration(_) => false,
         }
     }
 
@@ -627,13 +801,22 @@ where
         match key {
             Key::Account(_) | Key::Hash(_) => &self.base_key() == key,
             Key::URef(uref) => uref.is_addable(),
+            Key::SystemRegistry
+            | Key::Dictionary(_)
+            | Key::ExecutedDeploy(_)
+            | Key::Migration(_) => false,
         }
     }
 
     /// Tests whether writing to `key` is valid.
     pub fn is_writeable(&self, key: &Key) -> bool {
         match key {
-            Key::Account(_) | Key::Hash(_) => false,
+            Key::Account(_)
+            | Key::Hash(_)
+            | Key::SystemRegistry
+            | Key::Dictionary(_)
+            | Key::ExecutedDeploy(_)
+            | Key::Migration(_) => false,
             Key::URef(uref) => uref.is_writeable(),
         }
     }
@@ -814,6 +997,65 @@ where
         Ok(())
     }
 
+    /// Replaces the main purse of the account in the current context with `purse`.
+    pub fn set_main_purse(&mut self, purse: URef) -> Result<(), Error> {
+        // Check permission to modify the account
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(SetMainPurseFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(SetMainPurseFailure::PermissionDenied.into());
+        }
+
+        // The account must keep full control over its main purse
+        if !purse.access_rights().contains(AccessRights::READ_ADD_WRITE)
+            || self.validate_uref(&purse).is_err()
+        {
+            return Err(SetMainPurseFailure::InvalidPurse.into());
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().account_hash());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+        account.set_main_purse(purse);
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.tracking_copy.borrow_mut().write(key, account_value);
+
+        Ok(())
+    }
+
+    /// Registers the contract under `contract_hash` in the system contract registry as `name`.
+    ///
+    /// Only the system account may register system contracts, and only during the system phase
+    /// (i.e. from within an upgrade installer).
+    pub fn register_system_contract(
+        &mut self,
+        name: String,
+        contract_hash: ContractHash,
+    ) -> Result<(), Error> {
+        self.check_system_privileges()?;
+
+        // Only existing contracts can be registered
+        let _contract: Contract = self.read_gs_typed(&Key::Hash(contract_hash))?;
+
+        let mut entries = SystemContractRegistry::new();
+        entries.insert(name, contract_hash);
+        self.tracking_copy
+            .borrow_mut()
+            .register_system_contracts(self.correlation_id, entries)
+    }
+
     pub fn protocol_data(&self) -> ProtocolData {
         self.protocol_data
     }
@@ -5,6 +5,7 @@ use std::{
     rc::Rc,
 };
 
+use assert_matches::assert_matches;
 use rand::RngCore;
 
 use engine_shared::{
@@ -22,13 +23,14 @@ use engine_storage::global_state::{
 use types::{
     account::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, Weight,
+        SYSTEM_ACCOUNT,
     },
     contracts::NamedKeys,
     AccessRights, BlockTime, CLValue, Contract, EntryPointType, EntryPoints, Key, Phase,
-    ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH,
+    ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
-use super::{Address, Error, RuntimeContext};
+use super::{dictionary_item_key, Address, Error, RuntimeContext};
 use crate::{
     execution::AddressGenerator, runtime::extract_access_rights_from_keys,
     tracking_copy::TrackingCopy,
@@ -488,6 +490,31 @@ fn uref_key_writeable_invalid() {
     assert_invalid_access(query_result, AccessRights::WRITE);
 }
 
+#[test]
+fn uref_key_deletable_valid() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key]);
+    let query_result = test(access_rights, |mut rc| {
+        rc.write_gs(
+            uref_key,
+            StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+        )?;
+        rc.delete_gs(uref_key)?;
+        rc.read_gs(&uref_key)
+    });
+    assert_eq!(query_result.expect("should delete"), None);
+}
+
+#[test]
+fn uref_key_deletable_invalid() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key]);
+    let query_result = test(access_rights, |mut rc| rc.delete_gs(uref_key));
+    assert_invalid_access(query_result, AccessRights::WRITE);
+}
+
 #[test]
 fn uref_key_addable_valid() {
     let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
@@ -557,6 +584,34 @@ fn hash_key_addable_invalid() {
     assert!(query_result.is_err())
 }
 
+#[test]
+fn dictionary_key_not_accessible_directly() {
+    // dictionary items can only be reached through the seed URef
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let seed_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let seed = seed_key.into_uref().unwrap();
+    let item_key = dictionary_item_key(seed, b"item");
+    let query = |runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context
+            .validate_readable(&item_key)
+            .and(runtime_context.validate_writeable(&item_key))
+    };
+    let query_result = test(HashMap::new(), query);
+    assert!(query_result.is_err())
+}
+
+#[test]
+fn dictionary_put_requires_write_access_to_seed() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let seed_key = create_uref(&mut rng, AccessRights::READ);
+    let access_rights = extract_access_rights_from_keys(vec![seed_key]);
+    let query_result = test(access_rights, |mut rc| {
+        let seed = seed_key.into_uref().unwrap();
+        rc.dictionary_put(seed, b"item", CLValue::from_t(1_i32).unwrap())
+    });
+    assert_invalid_access(query_result, AccessRights::WRITE);
+}
+
 #[test]
 fn manage_associated_keys() {
     // Testing a valid case only - successfuly added a key, and successfuly removed,
@@ -831,3 +886,49 @@ fn validate_valid_purse_of_an_account() {
     let purse = URef::new([53; 32], AccessRights::READ_ADD_WRITE);
     assert!(runtime_context.validate_uref(&purse).is_err());
 }
+
+#[test]
+fn should_only_grant_system_privileges_to_system_account() {
+    let deploy_hash = [1u8; 32];
+    for (account_hash, is_system_caller) in
+        vec![(SYSTEM_ACCOUNT, true), (AccountHash::new([1u8; 32]), false)]
+    {
+        let (base_key, account) = mock_account(account_hash);
+        let mut named_keys = NamedKeys::new();
+        let hash_address_generator = AddressGenerator::new(&deploy_hash, Phase::Session);
+        let uref_address_generator = AddressGenerator::new(&deploy_hash, Phase::Session);
+        let runtime_context = mock_runtime_context(
+            &account,
+            base_key,
+            &mut named_keys,
+            HashMap::new(),
+            hash_address_generator,
+            uref_address_generator,
+        );
+
+        assert_eq!(runtime_context.is_system_caller(), is_system_caller);
+        // Privileged host functions also require the system phase.
+        assert_matches!(
+            runtime_context.check_system_privileges(),
+            Err(Error::InvalidContext)
+        );
+    }
+}
+
+#[test]
+fn should_cap_storage_refund() {
+    let result = test(HashMap::new(), |mut runtime_context| {
+        runtime_context.set_gas_counter(Gas::new(U512::from(1_000)));
+        runtime_context.set_storage_refund(Gas::new(U512::from(100)));
+        let uncapped = runtime_context.gas_counter_less_storage_refund();
+
+        // The refund is capped at a fifth of the gas counter.
+        runtime_context.set_storage_refund(Gas::new(U512::from(500)));
+        let capped = runtime_context.gas_counter_less_storage_refund();
+
+        Ok((uncapped, capped))
+    });
+    let (uncapped, capped) = result.expect("should compute cost");
+    assert_eq!(uncapped, Gas::new(U512::from(900)));
+    assert_eq!(capped, Gas::new(U512::from(800)));
+}
@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, mem};
 
-use engine_shared::{account::Account, stored_value::StoredValue};
+use engine_shared::{account::Account, stored_value::StoredValue, transform::Transform};
 use types::{bytesrepr::ToBytes, ContractWasm, Key};
 
 /// Returns byte size of the element - both heap size and stack size.
@@ -55,6 +55,25 @@ impl ByteSize for StoredValue {
     }
 }
 
+impl ByteSize for Transform {
+    fn byte_size(&self) -> usize {
+        mem::size_of::<Self>()
+            + match self {
+                Transform::Write(value) => value.byte_size() - mem::size_of::<StoredValue>(),
+                Transform::AddKeys(named_keys) => named_keys.heap_size(),
+                Transform::AddList(cl_value) => cl_value.serialized_length(),
+                Transform::Identity
+                | Transform::AddInt32(_)
+                | Transform::AddUInt64(_)
+                | Transform::AddUInt128(_)
+                | Transform::AddUInt256(_)
+                | Transform::AddUInt512(_)
+                | Transform::Delete
+                | Transform::Failure(_) => 0,
+            }
+    }
+}
+
 /// Returns heap size of the value.
 /// Note it's different from [ByteSize] that returns both heap and stack size.
 pub trait HeapSizeOf {
@@ -106,8 +125,10 @@ impl HeapSizeOf for String {
 mod tests {
     use std::{collections::BTreeMap, mem};
 
+    use engine_shared::{stored_value::StoredValue, transform::Transform};
+    use types::{CLValue, Key};
+
     use super::ByteSize;
-    use types::Key;
 
     fn assert_byte_size<T: ByteSize>(el: T, expected: usize) {
         assert_eq!(el.byte_size(), expected)
@@ -131,4 +152,15 @@ mod tests {
         let map: BTreeMap<Key, String> = v.into_iter().collect();
         assert_byte_size(map, it_size);
     }
+
+    #[test]
+    fn byte_size_of_transform() {
+        let value = StoredValue::CLValue(CLValue::from_t(vec![0u8; 100]).unwrap());
+        let transform = Transform::Write(value.clone());
+        assert_eq!(
+            transform.byte_size(),
+            mem::size_of::<Transform>() + value.byte_size() - mem::size_of::<StoredValue>()
+        );
+        assert_byte_size(Transform::AddUInt64(1), mem::size_of::<Transform>());
+    }
 }
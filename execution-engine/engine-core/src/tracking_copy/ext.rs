@@ -7,8 +7,8 @@ use engine_shared::{
 use engine_storage::global_state::StateReader;
 use engine_wasm_prep::Preprocessor;
 use types::{
-    account::AccountHash, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash,
-    ContractWasm, ContractWasmHash, Key, U512,
+    account::AccountHash, system_contract_type::SystemContractRegistry, CLValue, Contract,
+    ContractHash, ContractPackage, ContractPackageHash, ContractWasm, ContractWasmHash, Key, U512,
 };
 
 use crate::{execution, tracking_copy::TrackingCopy};
@@ -73,6 +73,20 @@ pub trait TrackingCopyExt<R> {
         use_system_contracts: bool,
         preprocessor: &Preprocessor,
     ) -> Result<Module, Self::Error>;
+
+    /// Reads the system contract registry, returning an empty registry if none has been stored.
+    fn read_system_contract_registry(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<SystemContractRegistry, Self::Error>;
+
+    /// Adds the given entries to the system contract registry, replacing any existing entries
+    /// with the same names.
+    fn register_system_contracts(
+        &mut self,
+        correlation_id: CorrelationId,
+        entries: SystemContractRegistry,
+    ) -> Result<(), Self::Error>;
 }
 
 impl<R> TrackingCopyExt<R> for TrackingCopy<R>
@@ -240,4 +254,34 @@ where
             Err(error) => Err(error.into()),
         }
     }
+
+    fn read_system_contract_registry(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<SystemContractRegistry, Self::Error> {
+        match self
+            .read(correlation_id, &Key::SystemRegistry)
+            .map_err(Into::into)?
+        {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                Ok(cl_value.into_t()?)
+            }
+            None => Ok(SystemContractRegistry::new()),
+        }
+    }
+
+    fn register_system_contracts(
+        &mut self,
+        correlation_id: CorrelationId,
+        entries: SystemContractRegistry,
+    ) -> Result<(), Self::Error> {
+        let mut registry = self.read_system_contract_registry(correlation_id)?;
+        registry.extend(entries);
+        let cl_value = CLValue::from_t(registry)?;
+        self.write(Key::SystemRegistry, StoredValue::CLValue(cl_value));
+        Ok(())
+    }
 }
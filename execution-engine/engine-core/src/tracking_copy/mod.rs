@@ -5,31 +5,45 @@ pub(self) mod meter;
 mod tests;
 
 use std::{
+    cell::RefCell,
     collections::{HashMap, HashSet, VecDeque},
     convert::From,
-    iter,
+    iter, mem,
+    rc::Rc,
 };
 
 use linked_hash_map::LinkedHashMap;
 
 use engine_shared::{
     additive_map::AdditiveMap,
+    metrics,
     newtypes::CorrelationId,
     stored_value::StoredValue,
     transform::{self, Transform},
     TypeMismatch,
 };
-use engine_storage::global_state::StateReader;
+use engine_storage::{global_state::StateReader, trie::merkle_proof::TrieMerkleProof};
 use types::{bytesrepr, CLType, CLValueError, Key};
 
-use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
+use crate::engine_state::{
+    execution_effect::{ContractEvent, ExecutionEffect},
+    op::Op,
+};
 
 pub use self::ext::TrackingCopyExt;
-use self::meter::{heap_meter::HeapSize, Meter};
+use self::{
+    byte_size::ByteSize,
+    meter::{heap_meter::HeapSize, Meter},
+};
 
 #[derive(Debug)]
 pub enum TrackingCopyQueryResult {
-    Success(StoredValue),
+    /// The value found at the end of the path, along with a proof of inclusion for each key
+    /// visited along the way, starting with the base key.
+    Success {
+        value: StoredValue,
+        proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+    },
     ValueNotFound(String),
     CircularReference(String),
 }
@@ -75,15 +89,15 @@ impl Query {
 
     fn into_circular_ref_result(self) -> TrackingCopyQueryResult {
         let msg = format!(
-            "{:?} has formed a circular reference at path: {}",
-            self.current_key,
+            "{} has formed a circular reference at path: {}",
+            self.current_key.to_formatted_string(),
             self.current_path()
         );
         TrackingCopyQueryResult::CircularReference(msg)
     }
 
     fn current_path(&self) -> String {
-        let mut path = format!("{:?}", self.base_key);
+        let mut path = self.base_key.to_formatted_string();
         for name in &self.visited_names {
             path.push_str("/");
             path.push_str(name);
@@ -99,7 +113,8 @@ pub struct TrackingCopyCache<M> {
     max_cache_size: usize,
     current_cache_size: usize,
     reads_cached: LinkedHashMap<Key, StoredValue>,
-    muts_cached: HashMap<Key, StoredValue>,
+    /// Written or added values, with `None` for a deleted one.
+    muts_cached: HashMap<Key, Option<StoredValue>>,
     meter: M,
 }
 
@@ -134,26 +149,112 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
         }
     }
 
+    /// Removes the value under `key` from Read cache.
+    pub fn remove_read(&mut self, key: &Key) {
+        if let Some(value) = self.reads_cached.remove(key) {
+            self.current_cache_size -= Meter::measure(&self.meter, key, &value);
+        }
+    }
+
     /// Inserts `key` and `value` pair to Write/Add cache.
     pub fn insert_write(&mut self, key: Key, value: StoredValue) {
-        self.muts_cached.insert(key, value);
+        self.muts_cached.insert(key, Some(value));
+    }
+
+    /// Marks the value under `key` as deleted in the Write/Add cache.
+    pub fn insert_delete(&mut self, key: Key) {
+        self.muts_cached.insert(key, None);
+    }
+
+    /// Returns `true` if the value under `key` has been deleted.
+    pub fn is_deleted(&self, key: &Key) -> bool {
+        self.muts_cached.get(key).map_or(false, Option::is_none)
     }
 
     /// Gets value from `key` in the cache.
+    ///
+    /// A deleted value isn't cached, so callers should check [`Self::is_deleted`] before reading
+    /// past the cache.
     pub fn get(&mut self, key: &Key) -> Option<&StoredValue> {
-        if let Some(value) = self.muts_cached.get(&key) {
-            return Some(value);
+        if let Some(maybe_value) = self.muts_cached.get(&key) {
+            return maybe_value.as_ref();
         };
 
         self.reads_cached.get_refresh(key).map(|v| &*v)
     }
 }
 
+/// The size in bytes above which least-recently-used values are evicted from a [`BlockReadCache`].
+const BLOCK_READ_CACHE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Values read from the pre-state of a block, shared by the `TrackingCopy`s of the block's deploys
+/// so that hot keys such as the system contracts are read from global state only once per block.
+///
+/// Values under keys written by any of the block's deploys are evicted and never cached again.
+pub struct BlockReadCache {
+    cache: RefCell<TrackingCopyCache<HeapSize>>,
+    written_keys: RefCell<HashSet<Key>>,
+}
+
+impl BlockReadCache {
+    pub fn new() -> Self {
+        BlockReadCache {
+            cache: RefCell::new(TrackingCopyCache::new(BLOCK_READ_CACHE_SIZE, HeapSize)),
+            written_keys: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Returns the cached value under `key`, if any.
+    pub fn get(&self, key: &Key) -> Option<StoredValue> {
+        self.cache.borrow_mut().get(key).cloned()
+    }
+
+    /// Caches `value`, read from the block's pre-state under `key`, unless the block has written
+    /// to `key`.
+    pub fn insert(&self, key: Key, value: StoredValue) {
+        if !self.written_keys.borrow().contains(&key) {
+            self.cache.borrow_mut().insert_read(key, value);
+        }
+    }
+
+    /// Evicts the values under the keys which `effect` writes, adds to or deletes.
+    pub fn invalidate(&self, effect: &ExecutionEffect) {
+        let mut cache = self.cache.borrow_mut();
+        let mut written_keys = self.written_keys.borrow_mut();
+        for (key, transform) in effect.transforms.iter() {
+            if *transform != Transform::Identity && written_keys.insert(*key) {
+                cache.remove_read(key);
+            }
+        }
+    }
+}
+
+impl Default for BlockReadCache {
+    fn default() -> Self {
+        BlockReadCache::new()
+    }
+}
+
+/// The changes held by a `TrackingCopy` at a point of execution, used to roll back anything
+/// recorded after it was taken.
+pub struct TrackingCopySnapshot {
+    muts_cached: HashMap<Key, Option<StoredValue>>,
+    ops: AdditiveMap<Key, Op>,
+    fns: AdditiveMap<Key, Transform>,
+    events_count: usize,
+    effects_size: usize,
+}
+
 pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    events: Vec<ContractEvent>,
+    /// The total size in bytes of the transforms in `fns`.
+    effects_size: usize,
+    max_effects_size: usize,
+    block_cache: Option<Rc<BlockReadCache>>,
 }
 
 #[derive(Debug)]
@@ -187,9 +288,46 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            events: Vec::new(),
+            effects_size: 0,
+            max_effects_size: usize::max_value(),
+            block_cache: None,
         }
     }
 
+    /// Consults `block_cache` for values missing from this `TrackingCopy`'s own cache before
+    /// reading them from global state, and shares the values read with it.  `block_cache` must
+    /// hold values read from the same pre-state as this `TrackingCopy`'s reader.
+    pub fn with_block_cache(mut self, block_cache: Rc<BlockReadCache>) -> Self {
+        self.block_cache = Some(block_cache);
+        self
+    }
+
+    /// Sets the size in bytes which the transforms recorded by this `TrackingCopy` may grow to
+    /// before [`Self::is_effects_limit_exceeded`] returns `true`.  Forks inherit the limit.
+    pub fn with_max_effects_size(mut self, max_effects_size: usize) -> Self {
+        self.max_effects_size = max_effects_size;
+        self
+    }
+
+    /// Returns the total size in bytes of the transforms recorded so far.
+    pub fn effects_size(&self) -> usize {
+        self.effects_size
+    }
+
+    /// Returns `true` if the transforms recorded so far are larger than the limit set by
+    /// [`Self::with_max_effects_size`].
+    pub fn is_effects_limit_exceeded(&self) -> bool {
+        self.effects_size > self.max_effects_size
+    }
+
+    fn record_transform(&mut self, key: Key, transform: Transform) {
+        let old_size = self.fns.get(&key).map_or(0, ByteSize::byte_size);
+        self.fns.insert_add(key, transform);
+        let new_size = self.fns.get(&key).map_or(0, ByteSize::byte_size);
+        self.effects_size = self.effects_size - old_size + new_size;
+    }
+
     pub fn reader(&self) -> &R {
         &self.reader
     }
@@ -207,7 +345,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// forking, however we recognize this is sub-optimal and will revisit
     /// in the future.
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
-        TrackingCopy::new(self)
+        TrackingCopy::new(self).with_max_effects_size(self.max_effects_size)
     }
 
     pub fn get(
@@ -216,10 +354,28 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         if let Some(value) = self.cache.get(key) {
+            metrics::record_cache_lookup(true);
             return Ok(Some(value.to_owned()));
         }
+        if self.cache.is_deleted(key) {
+            metrics::record_cache_lookup(true);
+            return Ok(None);
+        }
+        if let Some(value) = self
+            .block_cache
+            .as_ref()
+            .and_then(|block_cache| block_cache.get(key))
+        {
+            metrics::record_cache_lookup(true);
+            self.cache.insert_read(*key, value.to_owned());
+            return Ok(Some(value));
+        }
+        metrics::record_cache_lookup(false);
         if let Some(value) = self.reader.read(correlation_id, key)? {
             self.cache.insert_read(*key, value.to_owned());
+            if let Some(block_cache) = self.block_cache.as_ref() {
+                block_cache.insert(*key, value.to_owned());
+            }
             Ok(Some(value))
         } else {
             Ok(None)
@@ -234,7 +390,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         let normalized_key = key.normalize();
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
             self.ops.insert_add(normalized_key, Op::Read);
-            self.fns.insert_add(normalized_key, Transform::Identity);
+            self.record_transform(normalized_key, Transform::Identity);
             Ok(Some(value))
         } else {
             Ok(None)
@@ -245,7 +401,35 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         let normalized_key = key.normalize();
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
-        self.fns.insert_add(normalized_key, Transform::Write(value));
+        self.record_transform(normalized_key, Transform::Write(value));
+    }
+
+    /// Deletes the value under `key`, so that reading it afterwards finds nothing.
+    pub fn delete(&mut self, key: Key) {
+        let normalized_key = key.normalize();
+        self.cache.insert_delete(normalized_key);
+        self.ops.insert_add(normalized_key, Op::Write);
+        self.record_transform(normalized_key, Transform::Delete);
+    }
+
+    /// Writes `new` under `key` only if the value currently under it equals `expected`, and returns
+    /// whether the write happened.  A missing value never equals `expected`.
+    ///
+    /// The read of the current value is recorded even if no write happens.
+    pub fn compare_and_swap(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        expected: &StoredValue,
+        new: StoredValue,
+    ) -> Result<bool, R::Error> {
+        match self.read(correlation_id, key)? {
+            Some(ref current) if current == expected => {
+                self.write(*key, new);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 
     /// Ok(None) represents missing key to which we want to "add" some value.
@@ -267,7 +451,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         let type_name = value.type_name();
         let mismatch = || {
             Ok(AddResult::TypeMismatch(TypeMismatch::new(
-                "I32, U64, U128, U256, U512 or (String, Key) tuple".to_string(),
+                "I32, U64, U128, U256, U512, List or (String, Key) tuple".to_string(),
                 type_name,
             )))
         };
@@ -294,6 +478,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                     Ok(value) => Transform::AddUInt512(value),
                     Err(error) => return Ok(AddResult::from(error)),
                 },
+                CLType::List(_) => Transform::AddList(cl_value),
                 _ => {
                     if *cl_value.cl_type() == types::named_key_type() {
                         match cl_value.into_t() {
@@ -315,7 +500,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
             Ok(new_value) => {
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
-                self.fns.insert_add(normalized_key, transform);
+                self.record_transform(normalized_key, transform);
                 Ok(AddResult::Success)
             }
             Err(transform::Error::TypeMismatch(type_mismatch)) => {
@@ -325,8 +510,50 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Records an event emitted by a contract.  Events are returned as part of [`Self::effect`].
+    pub fn emit_event(&mut self, event: ContractEvent) {
+        self.events.push(event);
+    }
+
     pub fn effect(&self) -> ExecutionEffect {
-        ExecutionEffect::new(self.ops.clone(), self.fns.clone())
+        ExecutionEffect::new(self.ops.clone(), self.fns.clone()).with_events(self.events.clone())
+    }
+
+    /// Captures the changes made so far, so that later ones can be discarded with
+    /// [`Self::restore`].
+    pub fn snapshot(&self) -> TrackingCopySnapshot {
+        TrackingCopySnapshot {
+            muts_cached: self.cache.muts_cached.clone(),
+            ops: self.ops.clone(),
+            fns: self.fns.clone(),
+            events_count: self.events.len(),
+            effects_size: self.effects_size,
+        }
+    }
+
+    /// Returns `true` if a write, an add or an event was recorded after `snapshot` was taken.
+    pub fn has_changes_since(&self, snapshot: &TrackingCopySnapshot) -> bool {
+        self.events.len() != snapshot.events_count
+            || self.fns.iter().any(|(key, transform)| {
+                *transform != Transform::Identity && snapshot.fns.get(key) != Some(transform)
+            })
+    }
+
+    /// Discards every change recorded after `snapshot` was taken.
+    ///
+    /// Keys accessed after `snapshot` are still recorded as reads, since the outcome of the
+    /// execution may depend on them.
+    pub fn restore(&mut self, snapshot: TrackingCopySnapshot) {
+        self.cache.muts_cached = snapshot.muts_cached;
+        let ops = mem::replace(&mut self.ops, snapshot.ops);
+        for (key, op) in ops {
+            if self.ops.get(&key) != Some(&op) {
+                self.ops.insert_add(key, Op::Read);
+            }
+        }
+        self.fns = snapshot.fns;
+        self.events.truncate(snapshot.events_count);
+        self.effects_size = snapshot.effects_size;
     }
 
     /// Calling `query()` avoids calling into `self.cache`, so this will not return any values
@@ -343,20 +570,31 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         path: &[String],
     ) -> Result<TrackingCopyQueryResult, R::Error> {
         let mut query = Query::new(base_key, path);
+        let mut proofs = Vec::new();
 
         loop {
             if !query.visited_keys.insert(query.current_key) {
                 return Ok(query.into_circular_ref_result());
             }
-            let stored_value = match self.reader.read(correlation_id, &query.current_key)? {
+            let stored_value = match self
+                .reader
+                .read_with_proof(correlation_id, &query.current_key)?
+            {
                 None => {
                     return Ok(query.into_not_found_result("Failed to find base key"));
                 }
-                Some(stored_value) => stored_value,
+                Some(proof) => {
+                    let stored_value = proof.value().clone();
+                    proofs.push(proof);
+                    stored_value
+                }
             };
 
             if query.unvisited_names.is_empty() {
-                return Ok(TrackingCopyQueryResult::Success(stored_value));
+                return Ok(TrackingCopyQueryResult::Success {
+                    value: stored_value,
+                    proofs,
+                });
             }
 
             match stored_value {
@@ -417,8 +655,8 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
-        if let Some(value) = self.cache.muts_cached.get(key) {
-            return Ok(Some(value.to_owned()));
+        if let Some(maybe_value) = self.cache.muts_cached.get(key) {
+            return Ok(maybe_value.to_owned());
         }
         if let Some(value) = self.reader.read(correlation_id, key)? {
             Ok(Some(value))
@@ -426,4 +664,14 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
             Ok(None)
         }
     }
+
+    /// Proofs can only be given for values in the underlying state, so any cached mutations are
+    /// ignored.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        self.reader.read_with_proof(correlation_id, key)
+    }
 }
@@ -9,7 +9,10 @@ use engine_shared::{
     stored_value::{gens::stored_value_arb, StoredValue},
     transform::Transform,
 };
-use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
+    trie::merkle_proof::TrieMerkleProof,
+};
 use types::{
     account::{AccountHash, Weight, ACCOUNT_HASH_LENGTH},
     contracts::NamedKeys,
@@ -18,7 +21,8 @@ use types::{
 };
 
 use super::{
-    meter::count_meter::Count, AddResult, TrackingCopy, TrackingCopyCache, TrackingCopyQueryResult,
+    byte_size::ByteSize, meter::count_meter::Count, AddResult, BlockReadCache, TrackingCopy,
+    TrackingCopyCache, TrackingCopyQueryResult,
 };
 use crate::engine_state::op::Op;
 
@@ -58,6 +62,15 @@ impl StateReader<Key, StoredValue> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let maybe_value = self.read(correlation_id, key)?;
+        Ok(maybe_value.map(|value| TrieMerkleProof::new(*key, value, Vec::new())))
+    }
 }
 
 #[test]
@@ -143,6 +156,190 @@ fn tracking_copy_write() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_delete() {
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+    let correlation_id = CorrelationId::new();
+
+    // the value is initially read from the DB
+    assert!(tc.read(correlation_id, &k).unwrap().is_some());
+    assert_eq!(counter.get(), 1);
+
+    // delete creates a Transform and an Op
+    tc.delete(k);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Delete));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Write));
+
+    // reading the deleted value finds nothing, without querying the DB
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    assert_eq!(counter.get(), 1);
+
+    // writing again brings the value back
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    tc.write(k, one.clone());
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one.clone()));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(one)));
+    assert_eq!(counter.get(), 1);
+}
+
+#[test]
+fn tracking_copy_block_cache_shares_reads() {
+    let counter = Rc::new(Cell::new(0));
+    let block_cache = Rc::new(BlockReadCache::new());
+    let k = Key::Hash([0u8; 32]);
+    let correlation_id = CorrelationId::new();
+
+    let mut tc = TrackingCopy::new(CountingDb::new(Rc::clone(&counter)))
+        .with_block_cache(Rc::clone(&block_cache));
+    let value = tc.read(correlation_id, &k).unwrap();
+    assert_eq!(counter.get(), 1);
+
+    // another tracking copy of the same block is served from the block cache
+    let mut tc = TrackingCopy::new(CountingDb::new(Rc::clone(&counter)))
+        .with_block_cache(Rc::clone(&block_cache));
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), value);
+    assert_eq!(counter.get(), 1);
+    // its reads are recorded like any other
+    assert_eq!(tc.ops.get(&k), Some(&Op::Read));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Identity));
+}
+
+#[test]
+fn tracking_copy_block_cache_invalidated_by_writes() {
+    let counter = Rc::new(Cell::new(0));
+    let block_cache = Rc::new(BlockReadCache::new());
+    let read_key = Key::Hash([0u8; 32]);
+    let written_key = Key::Hash([1u8; 32]);
+    let correlation_id = CorrelationId::new();
+
+    let mut tc = TrackingCopy::new(CountingDb::new(Rc::clone(&counter)))
+        .with_block_cache(Rc::clone(&block_cache));
+    tc.read(correlation_id, &read_key).unwrap();
+    tc.read(correlation_id, &written_key).unwrap();
+    tc.write(written_key, StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()));
+    assert_eq!(counter.get(), 2);
+    block_cache.invalidate(&tc.effect());
+
+    // a key which was only read is still cached, but a written one is read from the DB again, and
+    // isn't cached anew
+    let mut tc = TrackingCopy::new(CountingDb::new(Rc::clone(&counter)))
+        .with_block_cache(Rc::clone(&block_cache));
+    tc.read(correlation_id, &read_key).unwrap();
+    assert_eq!(counter.get(), 2);
+    tc.read(correlation_id, &written_key).unwrap();
+    assert_eq!(counter.get(), 3);
+    assert_eq!(block_cache.get(&written_key), None);
+}
+
+#[test]
+fn tracking_copy_compare_and_swap() {
+    let correlation_id = CorrelationId::new();
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+    let db = CountingDb::new_init(zero.clone());
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    // mismatch leaves the value untouched, but records the read
+    assert!(!tc
+        .compare_and_swap(correlation_id, &k, &one, two.clone())
+        .unwrap());
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Identity));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Read));
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(zero.clone()));
+
+    // match writes the new value
+    assert!(tc
+        .compare_and_swap(correlation_id, &k, &zero, one.clone())
+        .unwrap());
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(one.clone())));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Write));
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one.clone()));
+
+    // the swapped-out value no longer matches
+    assert!(!tc.compare_and_swap(correlation_id, &k, &zero, two).unwrap());
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one));
+}
+
+#[test]
+fn tracking_copy_restore_snapshot() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+
+    tc.write(k1, one.clone());
+    let snapshot = tc.snapshot();
+
+    // reads don't count as changes
+    let _ = tc.read(correlation_id, &k1).unwrap();
+    let _ = tc.read(correlation_id, &k2).unwrap();
+    assert!(!tc.has_changes_since(&snapshot));
+
+    tc.write(k1, two.clone());
+    tc.write(k2, two);
+    assert!(tc.has_changes_since(&snapshot));
+
+    // restoring discards the writes made after the snapshot, but keeps earlier ones
+    tc.restore(snapshot);
+    assert_eq!(tc.fns.len(), 1);
+    assert_eq!(tc.fns.get(&k1), Some(&Transform::Write(one.clone())));
+    assert_eq!(tc.ops.get(&k1), Some(&Op::Write));
+    assert_eq!(tc.read(correlation_id, &k1).unwrap(), Some(one));
+
+    // keys accessed after the snapshot are still recorded as reads
+    assert_eq!(tc.ops.get(&k2), Some(&Op::Read));
+}
+
+#[test]
+fn tracking_copy_effects_size() {
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let small = StoredValue::CLValue(CLValue::from_t(vec![0u8; 10]).unwrap());
+    let large = StoredValue::CLValue(CLValue::from_t(vec![0u8; 1_000]).unwrap());
+    let small_size = Transform::Write(small.clone()).byte_size();
+    let large_size = Transform::Write(large.clone()).byte_size();
+    let mut tc = TrackingCopy::new(db).with_max_effects_size(large_size);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+
+    tc.write(k1, large.clone());
+    assert_eq!(tc.effects_size(), large_size);
+    assert!(!tc.is_effects_limit_exceeded());
+
+    // overwriting a key replaces its transform, so only the latest value counts
+    tc.write(k1, small.clone());
+    assert_eq!(tc.effects_size(), small_size);
+
+    let snapshot = tc.snapshot();
+    tc.write(k2, large);
+    assert_eq!(tc.effects_size(), small_size + large_size);
+    assert!(tc.is_effects_limit_exceeded());
+
+    tc.restore(snapshot);
+    assert_eq!(tc.effects_size(), small_size);
+    assert!(!tc.is_effects_limit_exceeded());
+
+    // forks inherit the limit, but not the effects
+    let mut fork = tc.fork();
+    assert_eq!(fork.effects_size(), 0);
+    fork.write(k2, small);
+    fork.write(
+        k1,
+        StoredValue::CLValue(CLValue::from_t(vec![0u8; 2_000]).unwrap()),
+    );
+    assert!(fork.is_effects_limit_exceeded());
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
@@ -229,6 +426,44 @@ fn tracking_copy_add_named_key() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Add));
 }
 
+#[test]
+fn tracking_copy_add_list() {
+    let correlation_id = CorrelationId::new();
+    let k = Key::Hash([0u8; 32]);
+    let list = |items: Vec<u64>| CLValue::from_t(items).unwrap();
+    let db = CountingDb::new_init(StoredValue::CLValue(list(vec![1, 2])));
+    let mut tc = TrackingCopy::new(db);
+
+    // appending a list of a different item type should fail
+    let failed_add = tc.add(
+        correlation_id,
+        k,
+        StoredValue::CLValue(CLValue::from_t(vec![3_i32]).unwrap()),
+    );
+    assert_matches!(failed_add, Ok(AddResult::TypeMismatch(_)));
+    assert_eq!(tc.ops.is_empty(), true);
+    assert_eq!(tc.fns.is_empty(), true);
+
+    // appending a list of the same type works
+    let add = tc.add(correlation_id, k, StoredValue::CLValue(list(vec![3])));
+    assert_matches!(add, Ok(AddResult::Success));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::AddList(list(vec![3]))));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Add));
+
+    // appending again extends both the transform and the cached value
+    let add = tc.add(correlation_id, k, StoredValue::CLValue(list(vec![4, 5])));
+    assert_matches!(add, Ok(AddResult::Success));
+    assert_eq!(tc.fns.len(), 1);
+    assert_eq!(
+        tc.fns.get(&k),
+        Some(&Transform::AddList(list(vec![3, 4, 5])))
+    );
+    assert_eq!(
+        tc.read(correlation_id, &k).unwrap(),
+        Some(StoredValue::CLValue(list(vec![1, 2, 3, 4, 5])))
+    );
+}
+
 #[test]
 fn tracking_copy_rw() {
     let correlation_id = CorrelationId::new();
@@ -293,8 +528,10 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let tc = TrackingCopy::new(view);
         let empty_path = Vec::new();
-        if let Ok(TrackingCopyQueryResult::Success(result)) = tc.query(correlation_id, k, &empty_path) {
-            assert_eq!(v, result);
+        if let Ok(TrackingCopyQueryResult::Success { value, proofs }) = tc.query(correlation_id, k, &empty_path) {
+            assert_eq!(v, value);
+            assert_eq!(proofs.len(), 1);
+            assert_eq!(proofs[0].compute_state_hash().unwrap(), root_hash);
         } else {
             panic!("Query failed when it should not have!");
         }
@@ -333,8 +570,12 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let tc = TrackingCopy::new(view);
         let path = vec!(name.clone());
-        if let Ok(TrackingCopyQueryResult::Success(result)) = tc.query(correlation_id, contract_key, &path) {
-            assert_eq!(v, result);
+        if let Ok(TrackingCopyQueryResult::Success { value, proofs }) = tc.query(correlation_id, contract_key, &path) {
+            assert_eq!(v, value);
+            assert_eq!(proofs.len(), 2);
+            for proof in proofs {
+                assert_eq!(proof.compute_state_hash().unwrap(), root_hash);
+            }
         } else {
             panic!("Query failed when it should not have!");
         }
@@ -375,8 +616,12 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let tc = TrackingCopy::new(view);
         let path = vec!(name.clone());
-        if let Ok(TrackingCopyQueryResult::Success(result)) = tc.query(correlation_id, account_key, &path) {
-            assert_eq!(v, result);
+        if let Ok(TrackingCopyQueryResult::Success { value, proofs }) = tc.query(correlation_id, account_key, &path) {
+            assert_eq!(v, value);
+            assert_eq!(proofs.len(), 2);
+            for proof in proofs {
+                assert_eq!(proof.compute_state_hash().unwrap(), root_hash);
+            }
         } else {
             panic!("Query failed when it should not have!");
         }
@@ -435,8 +680,13 @@ proptest! {
         let path = vec!(contract_name, state_name);
 
         let result =  tc.query(correlation_id, account_key, &path);
-        if let Ok(TrackingCopyQueryResult::Success(result)) = result {
-            assert_eq!(v, result);
+        if let Ok(TrackingCopyQueryResult::Success { value, proofs }) = result {
+            assert_eq!(v, value);
+            let proof_keys: Vec<Key> = proofs.iter().map(|proof| *proof.key()).collect();
+            assert_eq!(proof_keys, vec![account_key, contract_key, k.normalize()]);
+            for proof in proofs {
+                assert_eq!(proof.compute_state_hash().unwrap(), root_hash);
+            }
         } else {
             panic!("Query failed when it should not have!");
         }
@@ -527,7 +777,11 @@ fn query_for_circular_references_should_fail() {
     if let Ok(TrackingCopyQueryResult::CircularReference(msg)) =
         tracking_copy.query(correlation_id, contract_key, &path)
     {
-        let expected_path_msg = format!("at path: {:?}/{}", contract_key, path[0]);
+        let expected_path_msg = format!(
+            "at path: {}/{}",
+            contract_key.to_formatted_string(),
+            path[0]
+        );
         assert!(msg.contains(&expected_path_msg));
     } else {
         panic!("Query didn't fail with a circular reference error");
@@ -538,7 +792,11 @@ fn query_for_circular_references_should_fail() {
     if let Ok(TrackingCopyQueryResult::CircularReference(msg)) =
         tracking_copy.query(correlation_id, contract_key, &path)
     {
-        let expected_path_msg = format!("at path: {:?}/{}", contract_key, path[0]);
+        let expected_path_msg = format!(
+            "at path: {}/{}",
+            contract_key.to_formatted_string(),
+            path[0]
+        );
         assert!(msg.contains(&expected_path_msg));
     } else {
         panic!("Query didn't fail with a circular reference error");
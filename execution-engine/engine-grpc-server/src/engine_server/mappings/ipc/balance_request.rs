@@ -0,0 +1,35 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::balance::BalanceRequest;
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::account::AccountHash;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::BalanceRequest> for BalanceRequest {
+    type Error = MappingError;
+
+    fn try_from(balance_request: ipc::BalanceRequest) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = balance_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let account_hash = {
+            let public_key_hash = balance_request.get_public_key_hash();
+            AccountHash::try_from(public_key_hash)
+                .map_err(|_| MappingError::invalid_account_hash_length(public_key_hash.len()))?
+        };
+
+        Ok(BalanceRequest::new(state_hash, account_hash))
+    }
+}
@@ -0,0 +1,38 @@
+use engine_core::engine_state::bonding_event::BondingEvent;
+
+use crate::engine_server::ipc;
+
+impl From<(usize, BondingEvent)> for ipc::BondingEvent {
+    fn from((deploy_index, bonding_event): (usize, BondingEvent)) -> Self {
+        let mut pb_bonding_event = ipc::BondingEvent::new();
+        pb_bonding_event.set_deploy_index(deploy_index as u32);
+        let bond = (bonding_event.validator(), bonding_event.amount()).into();
+        match bonding_event {
+            BondingEvent::Bond { .. } => pb_bonding_event.set_bond(bond),
+            BondingEvent::Unbond { .. } => pb_bonding_event.set_unbond(bond),
+        }
+        pb_bonding_event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{account::AccountHash, U512};
+
+    use super::*;
+
+    #[test]
+    fn should_map_unbond() {
+        let validator = AccountHash::new([1; 32]);
+        let amount = U512::from(100);
+        let pb_bonding_event: ipc::BondingEvent =
+            (3, BondingEvent::Unbond { validator, amount }).into();
+
+        assert_eq!(pb_bonding_event.get_deploy_index(), 3);
+        assert!(!pb_bonding_event.has_bond());
+        assert_eq!(
+            pb_bonding_event.get_unbond().get_validator_account_hash(),
+            validator.as_bytes()
+        );
+    }
+}
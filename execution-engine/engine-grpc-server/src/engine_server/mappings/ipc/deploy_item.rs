@@ -3,7 +3,7 @@ use std::{
     convert::{TryFrom, TryInto},
 };
 
-use engine_core::engine_state::deploy_item::DeployItem;
+use engine_core::{engine_state::deploy_item::DeployItem, DeployHash};
 use types::account::AccountHash;
 
 use crate::engine_server::{ipc, mappings::MappingError};
@@ -42,14 +42,28 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             MappingError::invalid_deploy_hash_length(pb_deploy_item.deploy_hash.len())
         })?;
 
-        Ok(DeployItem::new(
+        let dependencies = pb_deploy_item
+            .get_dependencies()
+            .iter()
+            .map(|raw: &Vec<u8>| {
+                raw.as_slice()
+                    .try_into()
+                    .map_err(|_| MappingError::invalid_deploy_hash_length(raw.len()))
+            })
+            .collect::<Result<Vec<DeployHash>, Self::Error>>()?;
+
+        Ok(DeployItem {
             address,
             session,
             payment,
             gas_price,
             authorization_keys,
             deploy_hash,
-        ))
+            timestamp: pb_deploy_item.get_timestamp(),
+            ttl_millis: pb_deploy_item.get_ttl_millis(),
+            dependencies,
+            nonce: pb_deploy_item.get_nonce(),
+        })
     }
 }
 
@@ -68,6 +82,16 @@ impl From<DeployItem> for ipc::DeployItem {
                 .collect(),
         );
         result.set_deploy_hash(deploy_item.deploy_hash.to_vec());
+        result.set_timestamp(deploy_item.timestamp);
+        result.set_ttl_millis(deploy_item.ttl_millis);
+        result.set_dependencies(
+            deploy_item
+                .dependencies
+                .iter()
+                .map(|dependency| dependency.to_vec())
+                .collect(),
+        );
+        result.set_nonce(deploy_item.nonce);
         result
     }
 }
@@ -5,18 +5,21 @@ use engine_core::{
     },
     execution::Error as ExecutionError,
 };
-use engine_shared::gas::Gas;
+use engine_shared::{gas::Gas, newtypes::Blake2bHash};
 
 use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
 
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
         match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+            ExecutionResult::Success { effect, cost, .. } => {
+                detail::execution_success(effect, cost)
+            }
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                ..
             } => (error, effect, cost).into(),
         }
     }
@@ -32,6 +35,7 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::InvalidAccountHashLength { .. }
             | error @ EngineStateError::InvalidProtocolVersion { .. }
             | error @ EngineStateError::InvalidUpgradeConfig
+            | error @ EngineStateError::InvalidGasPrice
             | error @ EngineStateError::WasmPreprocessing(_)
             | error @ EngineStateError::WasmSerialization(_)
             | error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
@@ -41,6 +45,15 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::InvalidUpgradeResult => {
                 detail::precondition_error(error.to_string())
             }
+            EngineStateError::DeployExpired { expiry, block_time } => {
+                detail::deploy_expired_error(expiry, block_time)
+            }
+            EngineStateError::MissingDependency(deploy_hash) => {
+                detail::missing_dependency_error(deploy_hash)
+            }
+            EngineStateError::InvalidNonce { expected, actual } => {
+                detail::invalid_nonce_error(expected, actual)
+            }
             EngineStateError::Storage(storage_error) => {
                 detail::execution_error(storage_error, effect, cost)
             }
@@ -75,7 +88,7 @@ impl From<(ExecutionError, ExecutionEffect, Gas)> for DeployResult {
 }
 
 mod detail {
-    use super::{DeployError_OutOfGasError, DeployResult, ExecutionEffect, Gas};
+    use super::{Blake2bHash, DeployError_OutOfGasError, DeployResult, ExecutionEffect, Gas};
 
     /// Constructs an instance of `DeployResult` with no error set, i.e. a successful
     /// result.
@@ -91,6 +104,36 @@ mod detail {
         pb_deploy_result
     }
 
+    /// Constructs an instance of `DeployResult` with an error set to
+    /// `DeployResult_DeployExpired`.
+    pub(super) fn deploy_expired_error(expiry: u64, block_time: u64) -> DeployResult {
+        let mut pb_deploy_result = DeployResult::new();
+        let pb_deploy_expired = pb_deploy_result.mut_deploy_expired();
+        pb_deploy_expired.set_expiry(expiry);
+        pb_deploy_expired.set_block_time(block_time);
+        pb_deploy_result
+    }
+
+    /// Constructs an instance of `DeployResult` with an error set to
+    /// `DeployResult_MissingDependency`.
+    pub(super) fn missing_dependency_error(deploy_hash: Blake2bHash) -> DeployResult {
+        let mut pb_deploy_result = DeployResult::new();
+        pb_deploy_result
+            .mut_missing_dependency()
+            .set_deploy_hash(deploy_hash.to_vec());
+        pb_deploy_result
+    }
+
+    /// Constructs an instance of `DeployResult` with an error set to
+    /// `DeployResult_InvalidNonce`.
+    pub(super) fn invalid_nonce_error(expected: u64, actual: u64) -> DeployResult {
+        let mut pb_deploy_result = DeployResult::new();
+        let pb_invalid_nonce = pb_deploy_result.mut_invalid_nonce();
+        pb_invalid_nonce.set_expected(expected);
+        pb_invalid_nonce.set_actual(actual);
+        pb_deploy_result
+    }
+
     /// Constructs an instance of `DeployResult` with an error set to
     /// `ProtobufExecutionError`.
     pub(super) fn execution_error<T: ToString>(
@@ -165,6 +208,7 @@ mod tests {
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            trace: Vec::new(),
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -190,6 +234,7 @@ mod tests {
             error: error.into(),
             effect: Default::default(),
             cost: expected_cost,
+            trace: Vec::new(),
         };
         let mut ipc_deploy_result: DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -224,6 +269,34 @@ mod tests {
         assert_eq!(test_cost(cost, forged_ref_error), cost);
     }
 
+    #[test]
+    fn deploy_header_errors_map_to_dedicated_variants() {
+        let deploy_result = |error| -> DeployResult {
+            (error, ExecutionEffect::default(), Gas::default()).into()
+        };
+
+        let expired = deploy_result(EngineStateError::DeployExpired {
+            expiry: 10,
+            block_time: 11,
+        });
+        assert_eq!(expired.get_deploy_expired().get_expiry(), 10);
+        assert_eq!(expired.get_deploy_expired().get_block_time(), 11);
+
+        let dependency = Blake2bHash::new(b"dependency");
+        let missing = deploy_result(EngineStateError::MissingDependency(dependency));
+        assert_eq!(
+            missing.get_missing_dependency().get_deploy_hash(),
+            dependency.to_vec().as_slice()
+        );
+
+        let invalid_nonce = deploy_result(EngineStateError::InvalidNonce {
+            expected: 2,
+            actual: 1,
+        });
+        assert_eq!(invalid_nonce.get_invalid_nonce().get_expected(), 2);
+        assert_eq!(invalid_nonce.get_invalid_nonce().get_actual(), 1);
+    }
+
     #[test]
     fn revert_error_maps_to_execution_error() {
         let expected_revert = ApiError::UnexpectedContractRefVariant;
@@ -233,6 +306,7 @@ mod tests {
             error: EngineStateError::Exec(revert_error),
             effect: Default::default(),
             cost: Gas::new(amount),
+            trace: Vec::new(),
         };
         let mut ipc_result: DeployResult = exec_result.into();
         assert!(
@@ -0,0 +1,38 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::era_end::EraEndRequest;
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::BlockTime;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::EraEndRequest> for EraEndRequest {
+    type Error = MappingError;
+
+    fn try_from(mut era_end_request: ipc::EraEndRequest) -> Result<Self, Self::Error> {
+        let pre_state_hash = {
+            let parent_state_hash = era_end_request.get_parent_state_hash();
+            let length = parent_state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            parent_state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let protocol_version = era_end_request.take_protocol_version().into();
+        let era_id = era_end_request.get_era_id();
+        let blocktime = BlockTime::new(era_end_request.get_block_time());
+
+        Ok(EraEndRequest::new(
+            pre_state_hash,
+            protocol_version,
+            era_id,
+            blocktime,
+        ))
+    }
+}
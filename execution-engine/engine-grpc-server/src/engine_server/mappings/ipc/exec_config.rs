@@ -1,6 +1,14 @@
 use std::convert::{TryFrom, TryInto};
 
-use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount};
+use engine_core::engine_state::{
+    genesis::{ExecConfig, GenesisAccount},
+    CONV_RATE,
+};
+use engine_shared::host_function_costs::HostFunctionCosts;
+use engine_storage::protocol_data::{
+    DEFAULT_MAX_EFFECTS_SIZE, DEFAULT_MAX_NAMED_KEY_LENGTH, DEFAULT_MAX_VALUE_SIZE,
+    DEFAULT_TRANSFER_COST,
+};
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -15,17 +23,60 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
             .into_iter()
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
-        let wasm_costs = pb_exec_config.take_costs().take_wasm().into();
+        let mut pb_costs = pb_exec_config.take_costs();
+        let wasm_costs = pb_costs.take_wasm().into();
+        let host_function_costs = if pb_costs.has_host_function_costs() {
+            pb_costs.take_host_function_costs().try_into()?
+        } else {
+            HostFunctionCosts::default()
+        };
+        let transfer_cost = match pb_costs.get_transfer_cost() {
+            0 => DEFAULT_TRANSFER_COST,
+            transfer_cost => transfer_cost,
+        };
+        let max_effects_size = match pb_exec_config.get_max_effects_size() {
+            0 => DEFAULT_MAX_EFFECTS_SIZE,
+            max_effects_size => max_effects_size,
+        };
+        let max_value_size = match pb_exec_config.get_max_value_size() {
+            0 => DEFAULT_MAX_VALUE_SIZE,
+            max_value_size => max_value_size,
+        };
+        let max_named_key_length = match pb_exec_config.get_max_named_key_length() {
+            0 => DEFAULT_MAX_NAMED_KEY_LENGTH,
+            max_named_key_length => max_named_key_length,
+        };
         let mint_initializer_bytes = pb_exec_config.take_mint_installer();
         let proof_of_stake_initializer_bytes = pb_exec_config.take_pos_installer();
         let standard_payment_installer_bytes = pb_exec_config.take_standard_payment_installer();
-        Ok(ExecConfig::new(
+        let inflation_rate = pb_exec_config.get_inflation_rate();
+        let slash_rate = pb_exec_config.get_slash_rate();
+        let unbonding_delay = pb_exec_config.get_unbonding_delay();
+        let gas_price = match pb_exec_config.get_gas_price() {
+            0 => CONV_RATE,
+            gas_price => gas_price,
+        };
+        let mut exec_config = ExecConfig::new(
             mint_initializer_bytes,
             proof_of_stake_initializer_bytes,
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
-        ))
+            gas_price,
+            inflation_rate,
+            slash_rate,
+            unbonding_delay,
+        );
+        exec_config.set_host_function_costs(host_function_costs);
+        exec_config.set_transfer_cost(transfer_cost);
+        exec_config.set_max_effects_size(max_effects_size);
+        exec_config.set_max_value_size(max_value_size);
+        exec_config.set_max_named_key_length(max_named_key_length);
+        exec_config.set_nonces_enabled(pb_exec_config.get_enable_nonces());
+        exec_config.set_min_bond(pb_exec_config.get_min_bond());
+        exec_config.set_max_bond(pb_exec_config.get_max_bond());
+        exec_config.set_max_validator_slots(pb_exec_config.get_max_validator_slots());
+        Ok(exec_config)
     }
 }
 
@@ -50,6 +101,23 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
             .mut_costs()
             .set_wasm(exec_config.wasm_costs().into());
         pb_exec_config
+            .mut_costs()
+            .set_host_function_costs(exec_config.host_function_costs().into());
+        pb_exec_config
+            .mut_costs()
+            .set_transfer_cost(exec_config.transfer_cost());
+        pb_exec_config.set_inflation_rate(exec_config.inflation_rate());
+        pb_exec_config.set_slash_rate(exec_config.slash_rate());
+        pb_exec_config.set_unbonding_delay(exec_config.unbonding_delay());
+        pb_exec_config.set_gas_price(exec_config.gas_price());
+        pb_exec_config.set_enable_nonces(exec_config.nonces_enabled());
+        pb_exec_config.set_min_bond(exec_config.min_bond());
+        pb_exec_config.set_max_bond(exec_config.max_bond());
+        pb_exec_config.set_max_validator_slots(exec_config.max_validator_slots());
+        pb_exec_config.set_max_effects_size(exec_config.max_effects_size());
+        pb_exec_config.set_max_value_size(exec_config.max_value_size());
+        pb_exec_config.set_max_named_key_length(exec_config.max_named_key_length());
+        pb_exec_config
     }
 }
 
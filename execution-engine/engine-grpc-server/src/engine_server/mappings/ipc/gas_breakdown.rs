@@ -0,0 +1,12 @@
+use engine_core::engine_state::execution_result::GasBreakdown;
+
+use crate::engine_server::ipc;
+
+impl From<GasBreakdown> for ipc::GasBreakdown {
+    fn from(gas_breakdown: GasBreakdown) -> Self {
+        let mut pb_gas_breakdown = ipc::GasBreakdown::new();
+        pb_gas_breakdown.set_opcodes(gas_breakdown.opcodes().value().into());
+        pb_gas_breakdown.set_host_functions(gas_breakdown.host_functions().value().into());
+        pb_gas_breakdown
+    }
+}
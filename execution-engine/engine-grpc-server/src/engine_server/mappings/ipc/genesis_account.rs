@@ -1,11 +1,18 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    time::Duration,
+};
 
-use engine_core::engine_state::genesis::GenesisAccount;
+use engine_core::engine_state::genesis::{GenesisAccount, VestingSchedule};
 use engine_shared::motes::Motes;
-use types::account::AccountHash;
+use types::{account::AccountHash, BlockTime};
 
 use crate::engine_server::{
-    ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisAccount, mappings::MappingError,
+    ipc::{
+        ChainSpec_GenesisConfig_ExecConfig_GenesisAccount,
+        ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule,
+    },
+    mappings::MappingError,
 };
 
 impl From<GenesisAccount> for ChainSpec_GenesisConfig_ExecConfig_GenesisAccount {
@@ -15,6 +22,9 @@ impl From<GenesisAccount> for ChainSpec_GenesisConfig_ExecConfig_GenesisAccount
         pb_genesis_account.public_key_hash = genesis_account.account_hash().as_bytes().to_vec();
         pb_genesis_account.set_balance(genesis_account.balance().value().into());
         pb_genesis_account.set_bonded_amount(genesis_account.bonded_amount().value().into());
+        if let Some(vesting_schedule) = genesis_account.vesting_schedule() {
+            pb_genesis_account.set_vesting_schedule(vesting_schedule.into());
+        }
 
         pb_genesis_account
     }
@@ -39,7 +49,44 @@ impl TryFrom<ChainSpec_GenesisConfig_ExecConfig_GenesisAccount> for GenesisAccou
             .take_bonded_amount()
             .try_into()
             .map(Motes::new)?;
-        Ok(GenesisAccount::new(account_hash, balance, bonded_amount))
+        let mut genesis_account = GenesisAccount::new(account_hash, balance, bonded_amount);
+        if pb_genesis_account.has_vesting_schedule() {
+            let vesting_schedule = pb_genesis_account.take_vesting_schedule().try_into()?;
+            genesis_account = genesis_account.with_vesting_schedule(vesting_schedule);
+        }
+        Ok(genesis_account)
+    }
+}
+
+impl From<VestingSchedule> for ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule {
+    fn from(vesting_schedule: VestingSchedule) -> Self {
+        let mut pb_vesting_schedule =
+            ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule::new();
+
+        pb_vesting_schedule.set_start(vesting_schedule.start().as_millis());
+        pb_vesting_schedule.set_cliff_millis(vesting_schedule.cliff().as_millis() as u64);
+        pb_vesting_schedule.set_duration_millis(vesting_schedule.duration().as_millis() as u64);
+        pb_vesting_schedule.set_locked_amount(vesting_schedule.locked_amount().into());
+
+        pb_vesting_schedule
+    }
+}
+
+impl TryFrom<ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule>
+    for VestingSchedule
+{
+    type Error = MappingError;
+
+    fn try_from(
+        mut pb_vesting_schedule: ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule,
+    ) -> Result<Self, Self::Error> {
+        let locked_amount = pb_vesting_schedule.take_locked_amount().try_into()?;
+        Ok(VestingSchedule::new(
+            BlockTime::from_millis(pb_vesting_schedule.start),
+            Duration::from_millis(pb_vesting_schedule.cliff_millis),
+            Duration::from_millis(pb_vesting_schedule.duration_millis),
+            locked_amount,
+        ))
     }
 }
 
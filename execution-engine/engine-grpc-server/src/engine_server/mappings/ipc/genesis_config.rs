@@ -35,6 +35,10 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
 
 #[cfg(test)]
 mod tests {
+    use engine_core::engine_state::genesis::GenesisConfigBuilder;
+    use engine_shared::motes::Motes;
+    use types::{account::AccountHash, U512};
+
     use super::*;
     use crate::engine_server::mappings::test_utils;
 
@@ -43,4 +47,18 @@ mod tests {
         let genesis_config = rand::random();
         test_utils::protobuf_round_trip::<GenesisConfig, ChainSpec_GenesisConfig>(genesis_config);
     }
+
+    #[test]
+    fn should_map_genesis_config_parsed_from_toml() {
+        let genesis_config = GenesisConfigBuilder::new()
+            .with_name("devnet")
+            .with_validator(AccountHash::new([1; 32]), Motes::new(U512::from(1_000)))
+            .with_mint_installer_bytes(vec![0, 97, 115, 109])
+            .with_max_validator_slots(3)
+            .build();
+        let toml = genesis_config.to_toml().expect("should write TOML");
+        let parsed = GenesisConfig::from_toml(&toml).expect("should parse TOML");
+        assert_eq!(parsed, genesis_config);
+        test_utils::protobuf_round_trip::<GenesisConfig, ChainSpec_GenesisConfig>(parsed);
+    }
 }
@@ -0,0 +1,273 @@
+use std::convert::TryFrom;
+
+use engine_shared::host_function_costs::{HostFunctionCost, HostFunctionCosts, StorageCosts};
+
+use crate::engine_server::{
+    ipc::{
+        ChainSpec_CostTable_HostFunctionCost, ChainSpec_CostTable_HostFunctionCosts,
+        ChainSpec_CostTable_StorageCosts,
+    },
+    mappings::ParsingError,
+};
+
+impl From<HostFunctionCost> for ChainSpec_CostTable_HostFunctionCost {
+    fn from(host_function_cost: HostFunctionCost) -> Self {
+        let mut pb_host_function_cost = ChainSpec_CostTable_HostFunctionCost::new();
+        pb_host_function_cost.set_fixed(host_function_cost.fixed);
+        pb_host_function_cost.set_per_byte(host_function_cost.per_byte);
+        pb_host_function_cost
+    }
+}
+
+impl From<ChainSpec_CostTable_HostFunctionCost> for HostFunctionCost {
+    fn from(pb_host_function_cost: ChainSpec_CostTable_HostFunctionCost) -> Self {
+        HostFunctionCost::new(
+            pb_host_function_cost.get_fixed(),
+            pb_host_function_cost.get_per_byte(),
+        )
+    }
+}
+
+impl From<StorageCosts> for ChainSpec_CostTable_StorageCosts {
+    fn from(storage_costs: StorageCosts) -> Self {
+        let mut pb_storage_costs = ChainSpec_CostTable_StorageCosts::new();
+        pb_storage_costs.set_gas_per_byte(storage_costs.gas_per_byte);
+        pb_storage_costs.set_refund_percent(storage_costs.refund_percent.into());
+        pb_storage_costs
+    }
+}
+
+impl TryFrom<ChainSpec_CostTable_StorageCosts> for StorageCosts {
+    type Error = ParsingError;
+
+    fn try_from(pb_storage_costs: ChainSpec_CostTable_StorageCosts) -> Result<Self, Self::Error> {
+        let refund_percent = u8::try_from(pb_storage_costs.get_refund_percent()).map_err(|_| {
+            ParsingError(format!(
+                "Storage refund percent {} doesn't fit in a u8",
+                pb_storage_costs.get_refund_percent()
+            ))
+        })?;
+        Ok(StorageCosts::new(
+            pb_storage_costs.get_gas_per_byte(),
+            refund_percent,
+        ))
+    }
+}
+
+impl From<HostFunctionCosts> for ChainSpec_CostTable_HostFunctionCosts {
+    fn from(host_function_costs: HostFunctionCosts) -> Self {
+        let mut pb_host_function_costs = ChainSpec_CostTable_HostFunctionCosts::new();
+        pb_host_function_costs.set_read_value(host_function_costs.read_value.into());
+        pb_host_function_costs.set_read_value_local(host_function_costs.read_value_local.into());
+        pb_host_function_costs.set_write(host_function_costs.write.into());
+        pb_host_function_costs.set_write_local(host_function_costs.write_local.into());
+        pb_host_function_costs
+            .set_write_and_return_old(host_function_costs.write_and_return_old.into());
+        pb_host_function_costs.set_compare_and_swap(host_function_costs.compare_and_swap.into());
+        pb_host_function_costs.set_delete(host_function_costs.delete.into());
+        pb_host_function_costs.set_add(host_function_costs.add.into());
+        pb_host_function_costs.set_counter_inc(host_function_costs.counter_inc.into());
+        pb_host_function_costs.set_new_uref(host_function_costs.new_uref.into());
+        pb_host_function_costs.set_load_named_keys(host_function_costs.load_named_keys.into());
+        pb_host_function_costs
+            .set_load_named_keys_paged(host_function_costs.load_named_keys_paged.into());
+        pb_host_function_costs.set_ret(host_function_costs.ret.into());
+        pb_host_function_costs.set_get_key(host_function_costs.get_key.into());
+        pb_host_function_costs.set_has_key(host_function_costs.has_key.into());
+        pb_host_function_costs.set_put_key(host_function_costs.put_key.into());
+        pb_host_function_costs.set_remove_key(host_function_costs.remove_key.into());
+        pb_host_function_costs.set_get_caller(host_function_costs.get_caller.into());
+        pb_host_function_costs.set_is_system_caller(host_function_costs.is_system_caller.into());
+        pb_host_function_costs.set_get_call_stack(host_function_costs.get_call_stack.into());
+        pb_host_function_costs.set_get_blocktime(host_function_costs.get_blocktime.into());
+        pb_host_function_costs.set_get_gas_price(host_function_costs.get_gas_price.into());
+        pb_host_function_costs.set_get_deploy_hash(host_function_costs.get_deploy_hash.into());
+        pb_host_function_costs.set_random_bytes(host_function_costs.random_bytes.into());
+        pb_host_function_costs.set_u512_mul_div(host_function_costs.u512_mul_div.into());
+        pb_host_function_costs.set_blake2b(host_function_costs.blake2b.into());
+        pb_host_function_costs.set_keccak256(host_function_costs.keccak256.into());
+        pb_host_function_costs.set_emit_event(host_function_costs.emit_event.into());
+        pb_host_function_costs.set_dictionary_new(host_function_costs.dictionary_new.into());
+        pb_host_function_costs.set_dictionary_get(host_function_costs.dictionary_get.into());
+        pb_host_function_costs.set_dictionary_put(host_function_costs.dictionary_put.into());
+        pb_host_function_costs.set_is_valid_uref(host_function_costs.is_valid_uref.into());
+        pb_host_function_costs.set_revert(host_function_costs.revert.into());
+        pb_host_function_costs
+            .set_add_associated_key(host_function_costs.add_associated_key.into());
+        pb_host_function_costs
+            .set_remove_associated_key(host_function_costs.remove_associated_key.into());
+        pb_host_function_costs
+            .set_update_associated_key(host_function_costs.update_associated_key.into());
+        pb_host_function_costs
+            .set_set_action_threshold(host_function_costs.set_action_threshold.into());
+        pb_host_function_costs.set_set_main_purse(host_function_costs.set_main_purse.into());
+        pb_host_function_costs
+            .set_register_system_contract(host_function_costs.register_system_contract.into());
+        pb_host_function_costs.set_create_purse(host_function_costs.create_purse.into());
+        pb_host_function_costs
+            .set_transfer_to_account(host_function_costs.transfer_to_account.into());
+        pb_host_function_costs.set_transfer_from_purse_to_account(
+            host_function_costs.transfer_from_purse_to_account.into(),
+        );
+        pb_host_function_costs.set_transfer_from_purse_to_accounts(
+            host_function_costs.transfer_from_purse_to_accounts.into(),
+        );
+        pb_host_function_costs.set_transfer_from_purse_to_purse(
+            host_function_costs.transfer_from_purse_to_purse.into(),
+        );
+        pb_host_function_costs.set_get_balance(host_function_costs.get_balance.into());
+        pb_host_function_costs.set_get_phase(host_function_costs.get_phase.into());
+        pb_host_function_costs
+            .set_get_system_contract(host_function_costs.get_system_contract.into());
+        pb_host_function_costs.set_get_main_purse(host_function_costs.get_main_purse.into());
+        pb_host_function_costs.set_read_host_buffer(host_function_costs.read_host_buffer.into());
+        pb_host_function_costs.set_create_contract_package_at_hash(
+            host_function_costs.create_contract_package_at_hash.into(),
+        );
+        pb_host_function_costs
+            .set_create_contract_user_group(host_function_costs.create_contract_user_group.into());
+        pb_host_function_costs
+            .set_add_contract_version(host_function_costs.add_contract_version.into());
+        pb_host_function_costs
+            .set_disable_contract_version(host_function_costs.disable_contract_version.into());
+        pb_host_function_costs.set_disable_contract(host_function_costs.disable_contract.into());
+        pb_host_function_costs.set_set_non_reentrant(host_function_costs.set_non_reentrant.into());
+        pb_host_function_costs.set_call_contract(host_function_costs.call_contract.into());
+        pb_host_function_costs
+            .set_call_contract_read_only(host_function_costs.call_contract_read_only.into());
+        pb_host_function_costs
+            .set_call_versioned_contract(host_function_costs.call_versioned_contract.into());
+        pb_host_function_costs.set_print(host_function_costs.print.into());
+        pb_host_function_costs
+            .set_get_named_arg_size(host_function_costs.get_named_arg_size.into());
+        pb_host_function_costs.set_get_named_arg(host_function_costs.get_named_arg.into());
+        pb_host_function_costs
+            .set_remove_contract_user_group(host_function_costs.remove_contract_user_group.into());
+        pb_host_function_costs.set_provision_contract_user_group_uref(
+            host_function_costs
+                .provision_contract_user_group_uref
+                .into(),
+        );
+        pb_host_function_costs.set_remove_contract_user_group_urefs(
+            host_function_costs.remove_contract_user_group_urefs.into(),
+        );
+        pb_host_function_costs.set_storage(host_function_costs.storage.into());
+        pb_host_function_costs
+    }
+}
+
+impl TryFrom<ChainSpec_CostTable_HostFunctionCosts> for HostFunctionCosts {
+    type Error = ParsingError;
+
+    fn try_from(
+        mut pb_host_function_costs: ChainSpec_CostTable_HostFunctionCosts,
+    ) -> Result<Self, Self::Error> {
+        Ok(HostFunctionCosts {
+            read_value: pb_host_function_costs.take_read_value().into(),
+            read_value_local: pb_host_function_costs.take_read_value_local().into(),
+            write: pb_host_function_costs.take_write().into(),
+            write_local: pb_host_function_costs.take_write_local().into(),
+            write_and_return_old: pb_host_function_costs.take_write_and_return_old().into(),
+            compare_and_swap: pb_host_function_costs.take_compare_and_swap().into(),
+            delete: pb_host_function_costs.take_delete().into(),
+            add: pb_host_function_costs.take_add().into(),
+            counter_inc: pb_host_function_costs.take_counter_inc().into(),
+            new_uref: pb_host_function_costs.take_new_uref().into(),
+            load_named_keys: pb_host_function_costs.take_load_named_keys().into(),
+            load_named_keys_paged: pb_host_function_costs.take_load_named_keys_paged().into(),
+            ret: pb_host_function_costs.take_ret().into(),
+            get_key: pb_host_function_costs.take_get_key().into(),
+            has_key: pb_host_function_costs.take_has_key().into(),
+            put_key: pb_host_function_costs.take_put_key().into(),
+            remove_key: pb_host_function_costs.take_remove_key().into(),
+            get_caller: pb_host_function_costs.take_get_caller().into(),
+            is_system_caller: pb_host_function_costs.take_is_system_caller().into(),
+            get_call_stack: pb_host_function_costs.take_get_call_stack().into(),
+            get_blocktime: pb_host_function_costs.take_get_blocktime().into(),
+            get_gas_price: pb_host_function_costs.take_get_gas_price().into(),
+            get_deploy_hash: pb_host_function_costs.take_get_deploy_hash().into(),
+            random_bytes: pb_host_function_costs.take_random_bytes().into(),
+            u512_mul_div: pb_host_function_costs.take_u512_mul_div().into(),
+            blake2b: pb_host_function_costs.take_blake2b().into(),
+            keccak256: pb_host_function_costs.take_keccak256().into(),
+            emit_event: pb_host_function_costs.take_emit_event().into(),
+            dictionary_new: pb_host_function_costs.take_dictionary_new().into(),
+            dictionary_get: pb_host_function_costs.take_dictionary_get().into(),
+            dictionary_put: pb_host_function_costs.take_dictionary_put().into(),
+            is_valid_uref: pb_host_function_costs.take_is_valid_uref().into(),
+            revert: pb_host_function_costs.take_revert().into(),
+            add_associated_key: pb_host_function_costs.take_add_associated_key().into(),
+            remove_associated_key: pb_host_function_costs.take_remove_associated_key().into(),
+            update_associated_key: pb_host_function_costs.take_update_associated_key().into(),
+            set_action_threshold: pb_host_function_costs.take_set_action_threshold().into(),
+            set_main_purse: pb_host_function_costs.take_set_main_purse().into(),
+            register_system_contract: pb_host_function_costs
+                .take_register_system_contract()
+                .into(),
+            create_purse: pb_host_function_costs.take_create_purse().into(),
+            transfer_to_account: pb_host_function_costs.take_transfer_to_account().into(),
+            transfer_from_purse_to_account: pb_host_function_costs
+                .take_transfer_from_purse_to_account()
+                .into(),
+            transfer_from_purse_to_accounts: pb_host_function_costs
+                .take_transfer_from_purse_to_accounts()
+                .into(),
+            transfer_from_purse_to_purse: pb_host_function_costs
+                .take_transfer_from_purse_to_purse()
+                .into(),
+            get_balance: pb_host_function_costs.take_get_balance().into(),
+            get_phase: pb_host_function_costs.take_get_phase().into(),
+            get_system_contract: pb_host_function_costs.take_get_system_contract().into(),
+            get_main_purse: pb_host_function_costs.take_get_main_purse().into(),
+            read_host_buffer: pb_host_function_costs.take_read_host_buffer().into(),
+            create_contract_package_at_hash: pb_host_function_costs
+                .take_create_contract_package_at_hash()
+                .into(),
+            create_contract_user_group: pb_host_function_costs
+                .take_create_contract_user_group()
+                .into(),
+            add_contract_version: pb_host_function_costs.take_add_contract_version().into(),
+            disable_contract_version: pb_host_function_costs
+                .take_disable_contract_version()
+                .into(),
+            disable_contract: pb_host_function_costs.take_disable_contract().into(),
+            set_non_reentrant: pb_host_function_costs.take_set_non_reentrant().into(),
+            call_contract: pb_host_function_costs.take_call_contract().into(),
+            call_contract_read_only: pb_host_function_costs.take_call_contract_read_only().into(),
+            call_versioned_contract: pb_host_function_costs.take_call_versioned_contract().into(),
+            print: pb_host_function_costs.take_print().into(),
+            get_named_arg_size: pb_host_function_costs.take_get_named_arg_size().into(),
+            get_named_arg: pb_host_function_costs.take_get_named_arg().into(),
+            remove_contract_user_group: pb_host_function_costs
+                .take_remove_contract_user_group()
+                .into(),
+            provision_contract_user_group_uref: pb_host_function_costs
+                .take_provision_contract_user_group_uref()
+                .into(),
+            remove_contract_user_group_urefs: pb_host_function_costs
+                .take_remove_contract_user_group_urefs()
+                .into(),
+            storage: StorageCosts::try_from(pb_host_function_costs.take_storage())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::host_function_costs::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(host_function_costs in gens::host_function_costs_arb()) {
+            test_utils::protobuf_round_trip::<
+                HostFunctionCosts,
+                ChainSpec_CostTable_HostFunctionCosts,
+            >(host_function_costs);
+        }
+    }
+}
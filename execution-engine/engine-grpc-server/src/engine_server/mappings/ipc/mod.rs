@@ -1,16 +1,25 @@
 //! Functions for converting between CasperLabs types and their Protobuf equivalents which are
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
+mod balance_request;
 mod bond;
+mod bonding_event;
 mod deploy_item;
 mod deploy_result;
+mod era_end_request;
 mod exec_config;
 mod executable_deploy_item;
 mod execute_request;
 mod execution_effect;
+mod gas_breakdown;
 mod genesis_account;
 mod genesis_config;
+mod host_function_costs;
 mod query_request;
 mod run_genesis_request;
+mod scheduled_calls_request;
+mod slash_request;
+mod speculative_exec_request;
 mod upgrade_request;
+mod validator_weights_request;
 mod wasm_costs;
@@ -0,0 +1,40 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::scheduled_calls::ScheduledCallsRequest;
+use engine_shared::{gas::Gas, newtypes::BLAKE2B_DIGEST_LENGTH};
+use types::{BlockTime, U512};
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::ScheduledCallsRequest> for ScheduledCallsRequest {
+    type Error = MappingError;
+
+    fn try_from(
+        mut scheduled_calls_request: ipc::ScheduledCallsRequest,
+    ) -> Result<Self, Self::Error> {
+        let pre_state_hash = {
+            let parent_state_hash = scheduled_calls_request.get_parent_state_hash();
+            let length = parent_state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            parent_state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let protocol_version = scheduled_calls_request.take_protocol_version().into();
+        let blocktime = BlockTime::new(scheduled_calls_request.get_block_time());
+        let call_gas_limit = Gas::new(U512::from(scheduled_calls_request.get_call_gas_limit()));
+
+        Ok(ScheduledCallsRequest::new(
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+            call_gas_limit,
+        ))
+    }
+}
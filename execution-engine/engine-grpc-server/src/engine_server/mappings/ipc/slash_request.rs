@@ -0,0 +1,43 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::slash::SlashConfig;
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::account::AccountHash;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::SlashRequest> for SlashConfig {
+    type Error = MappingError;
+
+    fn try_from(mut slash_request: ipc::SlashRequest) -> Result<Self, Self::Error> {
+        let pre_state_hash = {
+            let parent_state_hash = slash_request.get_parent_state_hash();
+            let length = parent_state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            parent_state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let protocol_version = slash_request.take_protocol_version().into();
+
+        // The amount slashed is decided by the proof-of-stake contract, so only the validator ids
+        // are taken from the request.
+        let validators = slash_request
+            .get_slashes()
+            .iter()
+            .map(|slash| {
+                let validator_id = slash.get_validator_id();
+                AccountHash::try_from(validator_id)
+                    .map_err(|_| MappingError::invalid_account_hash_length(validator_id.len()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SlashConfig::new(pre_state_hash, protocol_version, validators))
+    }
+}
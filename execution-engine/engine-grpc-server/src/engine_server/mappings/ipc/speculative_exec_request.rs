@@ -0,0 +1,50 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::{
+    execute_request::ExecuteRequest, execution_result::ExecutionResult,
+};
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+/// Maps to an `ExecuteRequest` holding the single deploy to be executed.
+impl TryFrom<ipc::SpeculativeExecRequest> for ExecuteRequest {
+    type Error = ipc::SpeculativeExecResponse;
+
+    fn try_from(mut request: ipc::SpeculativeExecRequest) -> Result<Self, Self::Error> {
+        let parent_state_hash = {
+            let parent_state_hash = request.take_parent_state_hash();
+            let length = parent_state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                let mut result = ipc::SpeculativeExecResponse::new();
+                result.mut_missing_parent().set_hash(parent_state_hash);
+                return Err(result);
+            }
+            parent_state_hash.as_slice().try_into().map_err(|_| {
+                let mut result = ipc::SpeculativeExecResponse::new();
+                result
+                    .mut_missing_parent()
+                    .set_hash(parent_state_hash.clone());
+                result
+            })?
+        };
+
+        let block_time = request.get_block_time();
+
+        let deploy = request
+            .take_deploy()
+            .try_into()
+            .map_err(|err: MappingError| ExecutionResult::precondition_failure(err.into()));
+
+        let protocol_version = request.take_protocol_version().into();
+
+        let mut exec_request = ExecuteRequest::new(
+            parent_state_hash,
+            block_time,
+            vec![deploy],
+            protocol_version,
+        );
+        exec_request.estimate_gas = request.get_estimate_gas();
+        Ok(exec_request)
+    }
+}
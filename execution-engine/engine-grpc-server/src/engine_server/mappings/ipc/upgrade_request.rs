@@ -30,10 +30,41 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
                 (bytes, args)
             };
 
-        let wasm_costs = if !upgrade_point.has_new_costs() {
-            None
+        let (wasm_costs, host_function_costs, transfer_cost) = if !upgrade_point.has_new_costs() {
+            (None, None, None)
         } else {
-            Some(upgrade_point.mut_new_costs().take_wasm().into())
+            let new_costs = upgrade_point.mut_new_costs();
+            let wasm_costs = if !new_costs.has_wasm() {
+                None
+            } else {
+                Some(new_costs.take_wasm().into())
+            };
+            let host_function_costs = if !new_costs.has_host_function_costs() {
+                None
+            } else {
+                Some(new_costs.take_host_function_costs().try_into()?)
+            };
+            let transfer_cost = match new_costs.get_transfer_cost() {
+                0 => None,
+                transfer_cost => Some(transfer_cost),
+            };
+            (wasm_costs, host_function_costs, transfer_cost)
+        };
+        let gas_price = match upgrade_point.get_new_gas_price() {
+            0 => None,
+            gas_price => Some(gas_price),
+        };
+        let max_effects_size = match upgrade_point.get_new_max_effects_size() {
+            0 => None,
+            max_effects_size => Some(max_effects_size),
+        };
+        let max_value_size = match upgrade_point.get_new_max_value_size() {
+            0 => None,
+            max_value_size => Some(max_value_size),
+        };
+        let max_named_key_length = match upgrade_point.get_new_max_named_key_length() {
+            0 => None,
+            max_named_key_length => Some(max_named_key_length),
         };
         let activation_point = if !upgrade_point.has_activation_point() {
             None
@@ -48,6 +79,12 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            host_function_costs,
+            transfer_cost,
+            max_effects_size,
+            max_value_size,
+            max_named_key_length,
+            gas_price,
             activation_point,
         ))
     }
@@ -0,0 +1,32 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::validator_weights::ValidatorWeightsRequest;
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::ValidatorWeightsRequest> for ValidatorWeightsRequest {
+    type Error = MappingError;
+
+    fn try_from(
+        mut validator_weights_request: ipc::ValidatorWeightsRequest,
+    ) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = validator_weights_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let protocol_version = validator_weights_request.take_protocol_version().into();
+
+        Ok(ValidatorWeightsRequest::new(state_hash, protocol_version))
+    }
+}
@@ -1,4 +1,6 @@
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::wasm_costs::{
+    WasmCosts, DEFAULT_VERIFY_ED25519_COST, DEFAULT_VERIFY_SECP256K1_COST,
+};
 
 use crate::engine_server::ipc::ChainSpec_CostTable_WasmCosts;
 
@@ -15,6 +17,8 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            verify_ed25519: wasm_costs.verify_ed25519,
+            verify_secp256k1: wasm_costs.verify_secp256k1,
             ..Default::default()
         }
     }
@@ -22,6 +26,15 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
 
 impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
     fn from(pb_wasm_costs: ChainSpec_CostTable_WasmCosts) -> Self {
+        // Signature verification mustn't be free when a cost table doesn't specify its costs.
+        let verify_ed25519 = match pb_wasm_costs.verify_ed25519 {
+            0 => DEFAULT_VERIFY_ED25519_COST,
+            verify_ed25519 => verify_ed25519,
+        };
+        let verify_secp256k1 = match pb_wasm_costs.verify_secp256k1 {
+            0 => DEFAULT_VERIFY_SECP256K1_COST,
+            verify_secp256k1 => verify_secp256k1,
+        };
         WasmCosts {
             regular: pb_wasm_costs.regular,
             div: pb_wasm_costs.div,
@@ -33,13 +46,15 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
+            verify_ed25519,
+            verify_secp256k1,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use proptest::proptest;
+    use proptest::{prop_assume, proptest};
 
     use engine_wasm_prep::wasm_costs::gens;
 
@@ -49,7 +64,16 @@ mod tests {
     proptest! {
         #[test]
         fn round_trip(wasm_costs in gens::wasm_costs_arb()) {
+            // zero verification costs are read as the defaults
+            prop_assume!(wasm_costs.verify_ed25519 != 0 && wasm_costs.verify_secp256k1 != 0);
             test_utils::protobuf_round_trip::<WasmCosts, ChainSpec_CostTable_WasmCosts>(wasm_costs);
         }
     }
+
+    #[test]
+    fn should_default_unset_verification_costs() {
+        let wasm_costs = WasmCosts::from(ChainSpec_CostTable_WasmCosts::new());
+        assert_eq!(wasm_costs.verify_ed25519, DEFAULT_VERIFY_ED25519_COST);
+        assert_eq!(wasm_costs.verify_secp256k1, DEFAULT_VERIFY_SECP256K1_COST);
+    }
 }
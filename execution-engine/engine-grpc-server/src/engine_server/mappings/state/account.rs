@@ -37,6 +37,10 @@ impl From<Account> for state::Account {
             pb_action_thresholds.set_key_management_threshold(key_management)
         }
 
+        if let Some(nonce) = account.nonce() {
+            pb_account.mut_nonce().set_value(nonce);
+        }
+
         pb_account
     }
 }
@@ -91,13 +95,19 @@ impl TryFrom<state::Account> for Account {
             .map_err(ParsingError::from)?
         };
 
-        let account = Account::new(
+        let mut account = Account::new(
             AccountHash::new(account_hash),
             named_keys.into_inner(),
             main_purse,
             associated_keys,
             action_thresholds,
         );
+        account.set_nonce(
+            pb_account
+                .nonce
+                .into_option()
+                .map(|pb_nonce| pb_nonce.value),
+        );
         Ok(account)
     }
 }
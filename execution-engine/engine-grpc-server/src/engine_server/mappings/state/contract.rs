@@ -9,6 +9,8 @@ use std::convert::{TryFrom, TryInto};
 
 impl From<Contract> for state::Contract {
     fn from(contract: Contract) -> Self {
+        let non_reentrant = contract.is_non_reentrant();
+        let disabled = contract.is_disabled();
         let (contract_package_hash, contract_wasm_hash, named_keys, entry_points, protocol_version) =
             contract.into();
         let mut pb_contract = state::Contract::new();
@@ -23,6 +25,8 @@ impl From<Contract> for state::Contract {
         pb_contract.set_named_keys(named_keys.into());
         pb_contract.set_entry_points(entry_points.into());
         pb_contract.set_protocol_version(protocol_version.into());
+        pb_contract.set_non_reentrant(non_reentrant);
+        pb_contract.set_disabled(disabled);
         pb_contract
     }
 }
@@ -55,13 +59,18 @@ impl TryFrom<state::Contract> for Contract {
             entry_points.add_entry_point(entry_point.try_into()?);
         }
 
-        Ok(Contract::new(
+        let mut contract = Contract::new(
             contract_package_hash,
             contract_wasm_hash,
             named_keys,
             entry_points,
             value.take_protocol_version().try_into()?,
-        ))
+        );
+        contract.set_non_reentrant(value.non_reentrant);
+        if value.disabled {
+            contract.disable();
+        }
+        Ok(contract)
     }
 }
 
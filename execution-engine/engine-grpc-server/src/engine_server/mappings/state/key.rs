@@ -4,7 +4,10 @@ use types::{account::AccountHash, Key};
 
 use crate::engine_server::{
     mappings::{self, ParsingError},
-    state::{self, Key_Address, Key_Hash, Key_oneof_value},
+    state::{
+        self, Key_Address, Key_Dictionary, Key_ExecutedDeploy, Key_Hash, Key_Migration,
+        Key_SystemRegistry, Key_oneof_value,
+    },
 };
 
 impl From<Key> for state::Key {
@@ -24,6 +27,24 @@ impl From<Key> for state::Key {
             Key::URef(uref) => {
                 pb_key.set_uref(uref.into());
             }
+            Key::SystemRegistry => {
+                pb_key.set_system_registry(Key_SystemRegistry::new());
+            }
+            Key::Dictionary(addr) => {
+                let mut pb_dictionary = Key_Dictionary::new();
+                pb_dictionary.set_addr(addr.to_vec());
+                pb_key.set_dictionary(pb_dictionary);
+            }
+            Key::ExecutedDeploy(deploy_hash) => {
+                let mut pb_executed_deploy = Key_ExecutedDeploy::new();
+                pb_executed_deploy.set_deploy_hash(deploy_hash.to_vec());
+                pb_key.set_executed_deploy(pb_executed_deploy);
+            }
+            Key::Migration(addr) => {
+                let mut pb_migration = Key_Migration::new();
+                pb_migration.set_addr(addr.to_vec());
+                pb_key.set_migration(pb_migration);
+            }
         }
         pb_key
     }
@@ -50,6 +71,22 @@ impl TryFrom<state::Key> for Key {
                 let uref = pb_uref.try_into()?;
                 Key::URef(uref)
             }
+            Key_oneof_value::system_registry(_) => Key::SystemRegistry,
+            Key_oneof_value::dictionary(pb_dictionary) => {
+                let addr = mappings::vec_to_array(pb_dictionary.addr, "Protobuf Key::Dictionary")?;
+                Key::Dictionary(addr)
+            }
+            Key_oneof_value::executed_deploy(pb_executed_deploy) => {
+                let deploy_hash = mappings::vec_to_array(
+                    pb_executed_deploy.deploy_hash,
+                    "Protobuf Key::ExecutedDeploy",
+                )?;
+                Key::ExecutedDeploy(deploy_hash)
+            }
+            Key_oneof_value::migration(pb_migration) => {
+                let addr = mappings::vec_to_array(pb_migration.addr, "Protobuf Key::Migration")?;
+                Key::Migration(addr)
+            }
         };
         Ok(key)
     }
@@ -42,6 +42,12 @@ impl From<Transform> for transforms::Transform {
             Transform::AddUInt512(uint512) => {
                 pb_transform.mut_add_big_int().set_value(uint512.into());
             }
+            Transform::AddList(cl_value) => {
+                pb_transform.mut_add_list().set_value(cl_value.into());
+            }
+            Transform::Delete => {
+                pb_transform.set_delete(Default::default());
+            }
         };
         pb_transform
     }
@@ -91,6 +97,11 @@ impl TryFrom<transforms::Transform> for Transform {
                     }
                 }
             }
+            Transform_oneof_transform_instance::add_list(mut pb_add_list) => {
+                let cl_value: CLValue = pb_add_list.take_value().try_into()?;
+                Transform::AddList(cl_value)
+            }
+            Transform_oneof_transform_instance::delete(_) => Transform::Delete,
             Transform_oneof_transform_instance::write(mut pb_write) => {
                 let value = StoredValue::try_from(pb_write.take_value())?;
                 Transform::Write(value)
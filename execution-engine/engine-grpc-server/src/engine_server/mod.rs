@@ -17,6 +17,7 @@ include!(concat!(
 pub mod mappings;
 
 use std::{
+    cmp,
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
     fmt::Debug,
@@ -26,48 +27,95 @@ use std::{
     time::Instant,
 };
 
-use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
+use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse, StreamingResponse};
 use log::{info, warn, Level};
 
 use engine_core::engine_state::{
+    balance::{BalanceRequest, BalanceResult},
+    era_end::{EraEndRequest, EraEndResult},
     execute_request::ExecuteRequest,
     genesis::GenesisResult,
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
+    scheduled_calls::{ScheduledCallsRequest, ScheduledCallsResult},
+    slash::{SlashConfig, SlashResult},
     upgrade::{UpgradeConfig, UpgradeResult},
+    validator_weights::{ValidatorWeightsRequest, ValidatorWeightsResult},
     EngineState, Error as EngineError,
 };
 use engine_shared::{
+    additive_map::AdditiveMap,
     logging::{self, log_duration},
     newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_storage::{
+    global_state::{CommitResult, PruneResult, StateProvider},
+    trie::Trie,
+};
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
 };
-use engine_storage::global_state::{CommitResult, StateProvider};
-use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use self::{
     ipc::{
-        BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, DistributeRewardsRequest,
-        DistributeRewardsResponse, ExecuteResponse, GenesisResponse, QueryResponse, SlashRequest,
-        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        BalanceResponse, BidStateRequest, BidStateResponse, CommitBatchRequest, CommitBatchResponse,
+        CommitRequest, CommitResponse, EraEndResponse, ExecuteResponse, GenesisResponse,
+        GetTrieChunkRequest, GetTrieChunkResponse, MissingTrieKeysRequest, MissingTrieKeysResponse,
+        PruneRequest, PruneResponse, PutTrieRequest, PutTrieResponse, QueryResponse,
+        ScheduledCallsResponse, SlashRequest, SlashResponse, SpeculativeExecRequest,
+        SpeculativeExecResponse, StreamExecutionEffectsResponse, UpgradeRequest, UpgradeResponse,
+        ValidatorWeights, ValidatorWeightsResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
     mappings::{ParsingError, TransformMap},
+    transforms::TransformEntry,
 };
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
+const METRIC_DURATION_COMMIT_BATCH: &str = "commit_batch_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_STREAM_EXEC: &str = "stream_exec_duration";
+const METRIC_DURATION_SPECULATIVE_EXEC: &str = "speculative_exec_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_GET_BALANCE: &str = "get_balance_duration";
+const METRIC_DURATION_GET_VALIDATOR_WEIGHTS: &str = "get_validator_weights_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_RUN_ERA_END: &str = "run_era_end_duration";
+const METRIC_DURATION_SLASH: &str = "slash_duration";
+const METRIC_DURATION_RUN_SCHEDULED_CALLS: &str = "run_scheduled_calls_duration";
+const METRIC_DURATION_PRUNE: &str = "prune_duration";
+const METRIC_DURATION_GET_TRIE_CHUNK: &str = "get_trie_chunk_duration";
+const METRIC_DURATION_PUT_TRIE: &str = "put_trie_duration";
+const METRIC_DURATION_MISSING_TRIE_KEYS: &str = "missing_trie_keys_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
+const TAG_RESPONSE_COMMIT_BATCH: &str = "commit_batch_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_STREAM_EXEC: &str = "stream_exec_response";
+const TAG_RESPONSE_SPECULATIVE_EXEC: &str = "speculative_exec_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_GET_BALANCE: &str = "get_balance_response";
+const TAG_RESPONSE_GET_VALIDATOR_WEIGHTS: &str = "get_validator_weights_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_RUN_ERA_END: &str = "run_era_end_response";
+const TAG_RESPONSE_SLASH: &str = "slash_response";
+const TAG_RESPONSE_RUN_SCHEDULED_CALLS: &str = "run_scheduled_calls_response";
+const TAG_RESPONSE_PRUNE: &str = "prune_response";
+const TAG_RESPONSE_GET_TRIE_CHUNK: &str = "get_trie_chunk_response";
+const TAG_RESPONSE_PUT_TRIE: &str = "put_trie_response";
+const TAG_RESPONSE_MISSING_TRIE_KEYS: &str = "missing_trie_keys_response";
 
 const UNIMPLEMENTED: &str = "unimplemented";
 
+/// The size of the chunks serialized tries are split into by `get_trie_chunk`.  Half the node's
+/// default maximum gRPC message size, leaving room for the rest of the response.
+const TRIE_CHUNK_SIZE: usize = 2 * 1024 * 1024;
+
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
 // Idea is that Engine will represent the core of the execution engine project.
@@ -109,18 +157,27 @@ where
         let result = self.run_query(correlation_id, request);
 
         let response = match result {
-            Ok(QueryResult::Success(value)) => {
+            Ok(QueryResult::Success { value, proofs }) => {
                 let mut result = ipc::QueryResponse::new();
-                match value.to_bytes() {
-                    Ok(serialized_value) => {
+                let serialized_proofs: Result<Vec<Vec<u8>>, _> =
+                    proofs.iter().map(ToBytes::to_bytes).collect();
+                match (value.to_bytes(), serialized_proofs) {
+                    (Ok(serialized_value), Ok(serialized_proofs)) => {
                         info!("query successful; correlation_id: {}", correlation_id);
                         result.set_success(serialized_value);
+                        result.set_merkle_proofs(serialized_proofs.into());
                     }
-                    Err(error_msg) => {
+                    (Err(error_msg), _) => {
                         let log_message = format!("Failed to serialize StoredValue: {}", error_msg);
                         warn!("{}", log_message);
                         result.set_failure(log_message);
                     }
+                    (_, Err(error_msg)) => {
+                        let log_message =
+                            format!("Failed to serialize TrieMerkleProof: {}", error_msg);
+                        warn!("{}", log_message);
+                        result.set_failure(log_message);
+                    }
                 }
                 result
             }
@@ -162,6 +219,76 @@ where
         SingleResponse::completed(response)
     }
 
+    fn get_balance(
+        &self,
+        _request_options: RequestOptions,
+        balance_request: ipc::BalanceRequest,
+    ) -> SingleResponse<BalanceResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: BalanceRequest = match balance_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = BalanceResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_BALANCE,
+                    TAG_RESPONSE_GET_BALANCE,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let mut response = BalanceResponse::new();
+        match self.get_account_balance(correlation_id, request) {
+            Ok(BalanceResult::Success { motes, proofs }) => {
+                let serialized_proofs: Result<Vec<Vec<u8>>, _> =
+                    proofs.iter().map(ToBytes::to_bytes).collect();
+                match serialized_proofs {
+                    Ok(serialized_proofs) => {
+                        info!("get_balance successful; correlation_id: {}", correlation_id);
+                        response.set_success(motes.into());
+                        response.set_merkle_proofs(serialized_proofs.into());
+                    }
+                    Err(error_msg) => {
+                        let log_message =
+                            format!("Failed to serialize TrieMerkleProof: {}", error_msg);
+                        warn!("{}", log_message);
+                        response.set_failure(log_message);
+                    }
+                }
+            }
+            Ok(BalanceResult::ValueNotFound(msg)) => {
+                info!("{}", msg);
+                response.set_failure(msg);
+            }
+            Ok(BalanceResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                response.set_failure(log_message.to_string());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_BALANCE,
+            TAG_RESPONSE_GET_BALANCE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn execute(
         &self,
         _request_options: RequestOptions,
@@ -178,6 +305,8 @@ where
         };
 
         let mut exec_response = ExecuteResponse::new();
+        let pre_state_hash = exec_request.parent_state_hash;
+        let protocol_version = exec_request.protocol_version;
 
         let results = match self.run_execute(correlation_id, exec_request) {
             Ok(results) => results,
@@ -194,6 +323,31 @@ where
             }
         };
 
+        let bonding_events = match self.get_bonding_events(
+            correlation_id,
+            pre_state_hash,
+            protocol_version,
+            &results,
+        ) {
+            Ok(bonding_events) => bonding_events,
+            Err(error) => {
+                warn!("failed to detect bonding events: {:?}", error);
+                Vec::new()
+            }
+        };
+        let protobuf_bonding_events_iter =
+            bonding_events
+                .into_iter()
+                .enumerate()
+                .flat_map(|(deploy_index, events)| {
+                    events.into_iter().map(move |bonding_event| {
+                        ipc::BondingEvent::from((deploy_index, bonding_event))
+                    })
+                });
+        exec_response
+            .mut_success()
+            .set_bonding_events(FromIterator::from_iter(protobuf_bonding_events_iter));
+
         let protobuf_results_iter = results.into_iter().map(Into::into);
         exec_response
             .mut_success()
@@ -207,112 +361,219 @@ where
         SingleResponse::completed(exec_response)
     }
 
-    fn commit(
+    fn stream_execution_effects(
         &self,
         _request_options: RequestOptions,
-        mut commit_request: CommitRequest,
-    ) -> SingleResponse<CommitResponse> {
+        exec_request: ipc::ExecuteRequest,
+    ) -> StreamingResponse<StreamExecutionEffectsResponse> {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
-        // TODO
-        let protocol_version = {
-            let protocol_version = commit_request.take_protocol_version().into();
-            if protocol_version < DEFAULT_PROTOCOL_VERSION {
-                DEFAULT_PROTOCOL_VERSION
-            } else {
-                protocol_version
+        // Deploys which fail to parse still yield a result, so the hashes line up with the results.
+        let deploy_hashes: Vec<Vec<u8>> = exec_request
+            .get_deploys()
+            .iter()
+            .map(|deploy_item| deploy_item.get_deploy_hash().to_vec())
+            .collect();
+
+        let exec_request: ExecuteRequest = match exec_request.try_into() {
+            Ok(ret) => ret,
+            Err(mut err) => {
+                let mut response = StreamExecutionEffectsResponse::new();
+                response.set_missing_parent(err.take_missing_parent());
+                return StreamingResponse::completed(vec![response]);
             }
         };
 
-        // Acquire pre-state hash
-        let pre_state_hash: Blake2bHash = match commit_request.get_prestate_hash().try_into() {
-            Err(_) => {
-                let error_message = "Could not parse pre-state hash".to_string();
-                warn!("{}", error_message);
-                let mut commit_response = CommitResponse::new();
-                commit_response
-                    .mut_failed_transform()
-                    .set_message(error_message);
-                return SingleResponse::completed(commit_response);
+        let responses = match self.run_execute(correlation_id, exec_request) {
+            Ok(results) => results
+                .iter()
+                .zip(deploy_hashes)
+                .enumerate()
+                .map(|(deploy_index, (result, deploy_hash))| {
+                    let transform_map: Vec<TransformEntry> = result
+                        .effect()
+                        .transforms
+                        .iter()
+                        .map(|(key, transform)| (*key, transform.clone()).into())
+                        .collect();
+                    let mut response = StreamExecutionEffectsResponse::new();
+                    let deploy_effects = response.mut_deploy_effects();
+                    deploy_effects.set_deploy_index(deploy_index as u32);
+                    deploy_effects.set_deploy_hash(deploy_hash);
+                    deploy_effects.set_transform_map(transform_map.into());
+                    response
+                })
+                .collect(),
+            Err(error) => {
+                info!("deploy results error: RootNotFound");
+                let mut response = StreamExecutionEffectsResponse::new();
+                response.mut_missing_parent().set_hash(error.to_vec());
+                vec![response]
             }
-            Ok(hash) => hash,
         };
 
-        // Acquire commit transforms
-        let transforms = match TransformMap::try_from(commit_request.take_effects().into_vec()) {
-            Err(ParsingError(error_message)) => {
-                warn!("{}", error_message);
-                let mut commit_response = CommitResponse::new();
-                commit_response
-                    .mut_failed_transform()
-                    .set_message(error_message);
-                return SingleResponse::completed(commit_response);
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_STREAM_EXEC,
+            TAG_RESPONSE_STREAM_EXEC,
+            start.elapsed(),
+        );
+        StreamingResponse::completed(responses)
+    }
+
+    fn speculative_exec(
+        &self,
+        _request_options: RequestOptions,
+        speculative_exec_request: SpeculativeExecRequest,
+    ) -> SingleResponse<SpeculativeExecResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let estimate_gas = speculative_exec_request.get_estimate_gas();
+        let exec_request: ExecuteRequest = match speculative_exec_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                return SingleResponse::completed(err);
             }
-            Ok(transforms) => transforms.into_inner(),
         };
 
-        // "Apply" effects to global state
-        let commit_response = {
-            let mut ret = CommitResponse::new();
-
-            match self.apply_effect(correlation_id, protocol_version, pre_state_hash, transforms) {
-                Ok(CommitResult::Success {
-                    state_root,
-                    bonded_validators,
-                }) => {
-                    let properties = {
-                        let mut tmp = BTreeMap::new();
-                        tmp.insert("post-state-hash", format!("{:?}", state_root));
-                        tmp.insert("success", true.to_string());
-                        tmp
-                    };
-                    logging::log_details(
-                        Level::Info,
-                        "effects applied; new state hash is: {post-state-hash}".to_owned(),
-                        properties,
-                    );
-
-                    let bonds = bonded_validators.into_iter().map(Into::into).collect();
-                    let commit_result = ret.mut_success();
-                    commit_result.set_poststate_hash(state_root.to_vec());
-                    commit_result.set_bonded_validators(bonds);
-                }
-                Ok(CommitResult::RootNotFound) => {
-                    warn!("RootNotFound");
-                    ret.mut_missing_prestate().set_hash(pre_state_hash.to_vec());
+        let mut response = SpeculativeExecResponse::new();
+        match self.run_execute(correlation_id, exec_request) {
+            Ok(mut results) => {
+                let result = results
+                    .pop()
+                    .expect("should have one result per deploy in the request");
+                if estimate_gas && !result.has_precondition_failure() {
+                    response.set_gas_breakdown(result.gas_breakdown().into());
                 }
-                Ok(CommitResult::KeyNotFound(key)) => {
-                    warn!("{:?} not found", key);
-                    ret.set_key_not_found(key.into());
+                response.set_success(result.into());
+            }
+            Err(error) => {
+                info!("speculative exec error: RootNotFound");
+                response.mut_missing_parent().set_hash(error.to_vec());
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_SPECULATIVE_EXEC,
+            TAG_RESPONSE_SPECULATIVE_EXEC,
+            start.elapsed(),
+        );
+        SingleResponse::completed(response)
+    }
+
+    fn commit(
+        &self,
+        _request_options: RequestOptions,
+        mut commit_request: CommitRequest,
+    ) -> SingleResponse<CommitResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let commit_response = match parse_commit_request(&mut commit_request) {
+            Ok((protocol_version, pre_state_hash, transforms)) => {
+                // "Apply" effects to global state
+                let commit_result = self.apply_effect(
+                    correlation_id,
+                    protocol_version,
+                    pre_state_hash,
+                    transforms,
+                );
+                commit_response(pre_state_hash, commit_result)
+            }
+            Err(commit_response) => commit_response,
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_COMMIT,
+            TAG_RESPONSE_COMMIT,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(commit_response)
+    }
+
+    fn commit_batch(
+        &self,
+        _request_options: RequestOptions,
+        mut commit_batch_request: CommitBatchRequest,
+    ) -> SingleResponse<CommitBatchResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        // Parse the commits up to the first one which fails to parse, whose response then ends
+        // the batch.
+        let mut commits = Vec::new();
+        let mut maybe_parsing_failure = None;
+        for mut commit_request in commit_batch_request.take_commits().into_iter() {
+            match parse_commit_request(&mut commit_request) {
+                Ok(commit) => commits.push(commit),
+                Err(commit_response) => {
+                    maybe_parsing_failure = Some(commit_response);
+                    break;
                 }
-                Ok(CommitResult::TypeMismatch(type_mismatch)) => {
-                    warn!("{:?}", type_mismatch);
-                    ret.set_type_mismatch(type_mismatch.into());
+            }
+        }
+
+        // Commits of different protocol versions are committed as separate batches, as the bonded
+        // validators of each post state depend on the version.
+        let mut responses = Vec::with_capacity(commits.len());
+        let mut is_success = true;
+        let mut commits = commits.into_iter().peekable();
+        while let Some((protocol_version, pre_state_hash, transforms)) = commits.next() {
+            let mut batch = vec![(pre_state_hash, transforms)];
+            while let Some((next_protocol_version, _, _)) = commits.peek() {
+                if *next_protocol_version != protocol_version {
+                    break;
                 }
-                Ok(CommitResult::Serialization(error)) => {
-                    warn!("{:?}", error);
-                    ret.mut_failed_transform()
-                        .set_message(format!("{:?}", error));
+                let (_, pre_state_hash, transforms) = commits.next().unwrap();
+                batch.push((pre_state_hash, transforms));
+            }
+            let pre_state_hashes: Vec<Blake2bHash> = batch.iter().map(|(hash, _)| *hash).collect();
+
+            match self.commit_batch(correlation_id, protocol_version, batch) {
+                Ok(commit_results) => {
+                    is_success = commit_results.len() == pre_state_hashes.len()
+                        && commit_results.iter().all(|commit_result| {
+                            matches!(commit_result, CommitResult::Success { .. })
+                        });
+                    responses.extend(
+                        pre_state_hashes
+                            .into_iter()
+                            .zip(commit_results)
+                            .map(|(pre_state_hash, commit_result)| {
+                                commit_response(pre_state_hash, Ok(commit_result))
+                            }),
+                    );
                 }
+                // None of this batch was committed.
                 Err(error) => {
-                    warn!("State error {:?} when applying transforms", error);
-                    ret.mut_failed_transform()
-                        .set_message(format!("{:?}", error));
+                    is_success = false;
+                    responses.push(commit_response(pre_state_hash, Err(error)));
                 }
             }
+            if !is_success {
+                break;
+            }
+        }
+        if is_success {
+            responses.extend(maybe_parsing_failure);
+        }
 
-            ret
-        };
+        let mut commit_batch_response = CommitBatchResponse::new();
+        commit_batch_response.set_responses(responses.into());
 
         log_duration(
             correlation_id,
-            METRIC_DURATION_COMMIT,
-            TAG_RESPONSE_COMMIT,
+            METRIC_DURATION_COMMIT_BATCH,
+            TAG_RESPONSE_COMMIT_BATCH,
             start.elapsed(),
         );
 
-        SingleResponse::completed(commit_response)
+        SingleResponse::completed(commit_batch_response)
     }
 
     fn run_genesis(
@@ -461,29 +722,597 @@ where
         SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
     }
 
-    fn distribute_rewards(
+    fn get_validator_weights(
         &self,
         _request_options: RequestOptions,
-        _distribute_rewards_request: DistributeRewardsRequest,
-    ) -> SingleResponse<DistributeRewardsResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
+        validator_weights_request: ipc::ValidatorWeightsRequest,
+    ) -> SingleResponse<ValidatorWeightsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: ValidatorWeightsRequest = match validator_weights_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ValidatorWeightsResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_VALIDATOR_WEIGHTS,
+                    TAG_RESPONSE_GET_VALIDATOR_WEIGHTS,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+        let state_hash = request.state_hash();
+
+        let mut response = ValidatorWeightsResponse::new();
+        match self.read_validator_weights(correlation_id, request) {
+            Ok(ValidatorWeightsResult::Success { validator_weights }) => {
+                info!(
+                    "get_validator_weights successful; correlation_id: {}",
+                    correlation_id
+                );
+                let mut pb_validator_weights = ValidatorWeights::new();
+                pb_validator_weights
+                    .set_bonds(validator_weights.into_iter().map(Into::into).collect());
+                response.set_success(pb_validator_weights);
+            }
+            Ok(ValidatorWeightsResult::RootNotFound) => {
+                info!("Root not found");
+                response.mut_missing_parent().set_hash(state_hash.to_vec());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_VALIDATOR_WEIGHTS,
+            TAG_RESPONSE_GET_VALIDATOR_WEIGHTS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
     }
 
     fn slash(
         &self,
         _request_options: RequestOptions,
-        _slash_request: SlashRequest,
+        slash_request: SlashRequest,
     ) -> SingleResponse<SlashResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let slash_config: SlashConfig = match slash_request.try_into() {
+            Ok(slash_config) => slash_config,
+            Err(error) => {
+                let err_msg = error.to_string();
+                warn!("{}", err_msg);
+
+                let mut slash_response = SlashResponse::new();
+                slash_response.mut_error().set_message(err_msg);
+                return SingleResponse::completed(slash_response);
+            }
+        };
+        let pre_state_hash = slash_config.pre_state_hash();
+
+        let mut slash_response = SlashResponse::new();
+        match self.commit_slash(correlation_id, slash_config) {
+            Ok(SlashResult::Success {
+                post_state_hash,
+                bonded_validators,
+                ..
+            }) => {
+                info!("slash successful: {}", post_state_hash);
+
+                let bonds = bonded_validators.into_iter().map(Into::into).collect();
+                let commit_result = slash_response.mut_success();
+                commit_result.set_poststate_hash(post_state_hash.to_vec());
+                commit_result.set_bonded_validators(bonds);
+            }
+            Ok(SlashResult::RootNotFound) => {
+                warn!("RootNotFound");
+                slash_response
+                    .mut_missing_parent()
+                    .set_hash(pre_state_hash.to_vec());
+            }
+            Ok(slash_result) => {
+                let err_msg = slash_result.to_string();
+                warn!("{}", err_msg);
+                slash_response.mut_error().set_message(err_msg);
+            }
+            Err(error) => {
+                let err_msg = error.to_string();
+                warn!("{}", err_msg);
+                slash_response.mut_error().set_message(err_msg);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_SLASH,
+            TAG_RESPONSE_SLASH,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(slash_response)
     }
 
-    fn unbond_payout(
+    fn run_era_end(
         &self,
         _request_options: RequestOptions,
-        _unbond_payout_request: UnbondPayoutRequest,
-    ) -> SingleResponse<UnbondPayoutResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
+        era_end_request: ipc::EraEndRequest,
+    ) -> SingleResponse<EraEndResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let era_end_request: EraEndRequest = match era_end_request.try_into() {
+            Ok(era_end_request) => era_end_request,
+            Err(error) => {
+                let err_msg = error.to_string();
+                warn!("{}", err_msg);
+
+                let mut era_end_response = EraEndResponse::new();
+                era_end_response.mut_error().set_message(err_msg);
+                return SingleResponse::completed(era_end_response);
+            }
+        };
+        let pre_state_hash = era_end_request.pre_state_hash();
+        let era_id = era_end_request.era_id();
+
+        let mut era_end_response = EraEndResponse::new();
+        match self.run_era_end(correlation_id, era_end_request) {
+            Ok(EraEndResult::Success {
+                post_state_hash,
+                effect,
+                bonded_validators,
+            }) => {
+                info!(
+                    "run_era_end successful for era {}: {}",
+                    era_id, post_state_hash
+                );
+
+                let bonds = bonded_validators.into_iter().map(Into::into).collect();
+                let era_end_result = era_end_response.mut_success();
+                era_end_result.set_post_state_hash(post_state_hash.to_vec());
+                era_end_result.set_effect(effect.into());
+                era_end_result.set_bonded_validators(bonds);
+            }
+            Ok(EraEndResult::RootNotFound) => {
+                warn!("RootNotFound");
+                era_end_response
+                    .mut_missing_parent()
+                    .set_hash(pre_state_hash.to_vec());
+            }
+            Ok(era_end_result) => {
+                let err_msg = era_end_result.to_string();
+                warn!("{}", err_msg);
+                era_end_response.mut_error().set_message(err_msg);
+            }
+            Err(error) => {
+                let err_msg = error.to_string();
+                warn!("{}", err_msg);
+                era_end_response.mut_error().set_message(err_msg);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_RUN_ERA_END,
+            TAG_RESPONSE_RUN_ERA_END,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(era_end_response)
+    }
+
+    fn run_scheduled_calls(
+        &self,
+        _request_options: RequestOptions,
+        scheduled_calls_request: ipc::ScheduledCallsRequest,
+    ) -> SingleResponse<ScheduledCallsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let scheduled_calls_request: ScheduledCallsRequest =
+            match scheduled_calls_request.try_into() {
+                Ok(scheduled_calls_request) => scheduled_calls_request,
+                Err(error) => {
+                    let err_msg = error.to_string();
+                    warn!("{}", err_msg);
+
+                    let mut scheduled_calls_response = ScheduledCallsResponse::new();
+                    scheduled_calls_response.mut_error().set_message(err_msg);
+                    return SingleResponse::completed(scheduled_calls_response);
+                }
+            };
+        let pre_state_hash = scheduled_calls_request.pre_state_hash();
+
+        let mut scheduled_calls_response = ScheduledCallsResponse::new();
+        match self.run_scheduled_calls(correlation_id, scheduled_calls_request) {
+            Ok(ScheduledCallsResult::Success {
+                post_state_hash,
+                results,
+            }) => {
+                info!(
+                    "run_scheduled_calls successful with {} calls: {}",
+                    results.len(),
+                    post_state_hash
+                );
+
+                let call_results = results.into_iter().map(Into::into).collect();
+                let scheduled_calls_result = scheduled_calls_response.mut_success();
+                scheduled_calls_result.set_post_state_hash(post_state_hash.to_vec());
+                scheduled_calls_result.set_call_results(call_results);
+            }
+            Ok(ScheduledCallsResult::RootNotFound) => {
+                warn!("RootNotFound");
+                scheduled_calls_response
+                    .mut_missing_parent()
+                    .set_hash(pre_state_hash.to_vec());
+            }
+            Ok(scheduled_calls_result) => {
+                let err_msg = scheduled_calls_result.to_string();
+                warn!("{}", err_msg);
+                scheduled_calls_response.mut_error().set_message(err_msg);
+            }
+            Err(error) => {
+                let err_msg = error.to_string();
+                warn!("{}", err_msg);
+                scheduled_calls_response.mut_error().set_message(err_msg);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_RUN_SCHEDULED_CALLS,
+            TAG_RESPONSE_RUN_SCHEDULED_CALLS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(scheduled_calls_response)
     }
+
+    fn prune(
+        &self,
+        _request_options: RequestOptions,
+        prune_request: PruneRequest,
+    ) -> SingleResponse<PruneResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut retained_roots: Vec<Blake2bHash> = Vec::new();
+        for state_hash in prune_request.get_retained_state_hashes() {
+            match state_hash.as_slice().try_into() {
+                Ok(hash) => retained_roots.push(hash),
+                Err(_) => {
+                    let error_message = "Could not parse retained state hash".to_string();
+                    warn!("{}", error_message);
+                    let mut prune_response = PruneResponse::new();
+                    prune_response.mut_error().set_message(error_message);
+                    return SingleResponse::completed(prune_response);
+                }
+            }
+        }
+
+        let mut prune_response = PruneResponse::new();
+
+        match self.prune(correlation_id, &retained_roots) {
+            Ok(PruneResult::Success { pruned, retained }) => {
+                info!(
+                    "pruned {} trie nodes from global state, retained {}",
+                    pruned, retained
+                );
+                let prune_result = prune_response.mut_success();
+                prune_result.set_pruned_count(pruned as u64);
+                prune_result.set_retained_count(retained as u64);
+            }
+            Ok(PruneResult::RootNotFound(root)) => {
+                warn!("RootNotFound");
+                prune_response.mut_missing_state().set_hash(root.to_vec());
+            }
+            Err(error) => {
+                warn!("State error {:?} when pruning global state", error);
+                prune_response
+                    .mut_error()
+                    .set_message(format!("{:?}", error));
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PRUNE,
+            TAG_RESPONSE_PRUNE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(prune_response)
+    }
+
+    fn get_trie_chunk(
+        &self,
+        _request_options: RequestOptions,
+        get_trie_chunk_request: GetTrieChunkRequest,
+    ) -> SingleResponse<GetTrieChunkResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut get_trie_chunk_response = GetTrieChunkResponse::new();
+
+        let trie_key: Blake2bHash = match get_trie_chunk_request.get_root_hash().try_into() {
+            Ok(trie_key) => trie_key,
+            Err(_) => {
+                let error_message = "Could not parse trie hash".to_string();
+                warn!("{}", error_message);
+                get_trie_chunk_response
+                    .mut_error()
+                    .set_message(error_message);
+                return SingleResponse::completed(get_trie_chunk_response);
+            }
+        };
+        let chunk_id = get_trie_chunk_request.get_chunk_id();
+
+        match self.read_trie(correlation_id, &trie_key) {
+            Ok(Some(trie)) => match trie.to_bytes() {
+                Ok(trie_bytes) => match trie_chunk(&trie_bytes, chunk_id) {
+                    Some((chunk_count, data)) => {
+                        let trie_chunk = get_trie_chunk_response.mut_chunk();
+                        trie_chunk.set_chunk_id(chunk_id);
+                        trie_chunk.set_chunk_count(chunk_count);
+                        trie_chunk.set_data(data.to_vec());
+                    }
+                    None => {
+                        let error_message = format!("Trie {} has no chunk {}", trie_key, chunk_id);
+                        warn!("{}", error_message);
+                        get_trie_chunk_response
+                            .mut_error()
+                            .set_message(error_message);
+                    }
+                },
+                Err(error) => {
+                    let error_message = format!("Failed to serialize trie: {}", error);
+                    warn!("{}", error_message);
+                    get_trie_chunk_response
+                        .mut_error()
+                        .set_message(error_message);
+                }
+            },
+            Ok(None) => {
+                info!("trie not found: {}", trie_key);
+                get_trie_chunk_response
+                    .mut_missing_trie()
+                    .set_hash(trie_key.to_vec());
+            }
+            Err(error) => {
+                warn!("State error {:?} when reading trie", error);
+                get_trie_chunk_response
+                    .mut_error()
+                    .set_message(format!("{:?}", error));
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_TRIE_CHUNK,
+            TAG_RESPONSE_GET_TRIE_CHUNK,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(get_trie_chunk_response)
+    }
+
+    fn put_trie(
+        &self,
+        _request_options: RequestOptions,
+        put_trie_request: PutTrieRequest,
+    ) -> SingleResponse<PutTrieResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut put_trie_response = PutTrieResponse::new();
+
+        let trie: Trie<Key, StoredValue> =
+            match bytesrepr::deserialize(put_trie_request.get_trie().to_vec()) {
+                Ok(trie) => trie,
+                Err(error) => {
+                    let error_message = format!("Could not parse trie: {}", error);
+                    warn!("{}", error_message);
+                    put_trie_response.mut_error().set_message(error_message);
+                    return SingleResponse::completed(put_trie_response);
+                }
+            };
+
+        match self.put_trie(correlation_id, &trie) {
+            Ok(trie_hash) => put_trie_response.set_trie_hash(trie_hash.to_vec()),
+            Err(error) => {
+                warn!("State error {:?} when putting trie", error);
+                put_trie_response
+                    .mut_error()
+                    .set_message(format!("{:?}", error));
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PUT_TRIE,
+            TAG_RESPONSE_PUT_TRIE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(put_trie_response)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        _request_options: RequestOptions,
+        missing_trie_keys_request: MissingTrieKeysRequest,
+    ) -> SingleResponse<MissingTrieKeysResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut missing_trie_keys_response = MissingTrieKeysResponse::new();
+
+        let mut trie_keys: Vec<Blake2bHash> = Vec::new();
+        for trie_key in missing_trie_keys_request.get_trie_keys() {
+            match trie_key.as_slice().try_into() {
+                Ok(hash) => trie_keys.push(hash),
+                Err(_) => {
+                    let error_message = "Could not parse trie hash".to_string();
+                    warn!("{}", error_message);
+                    missing_trie_keys_response
+                        .mut_error()
+                        .set_message(error_message);
+                    return SingleResponse::completed(missing_trie_keys_response);
+                }
+            }
+        }
+
+        match self.missing_trie_keys(correlation_id, trie_keys) {
+            Ok(missing_trie_keys) => {
+                let missing_trie_keys: Vec<Vec<u8>> =
+                    missing_trie_keys.iter().map(Blake2bHash::to_vec).collect();
+                missing_trie_keys_response
+                    .mut_success()
+                    .set_missing_trie_keys(missing_trie_keys.into());
+            }
+            Err(error) => {
+                warn!("State error {:?} when finding missing tries", error);
+                missing_trie_keys_response
+                    .mut_error()
+                    .set_message(format!("{:?}", error));
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_MISSING_TRIE_KEYS,
+            TAG_RESPONSE_MISSING_TRIE_KEYS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(missing_trie_keys_response)
+    }
+}
+
+/// Returns the number of chunks `trie_bytes` is split into and the chunk at `chunk_id`, or `None`
+/// if there is no such chunk.  An empty input still consists of a single, empty chunk.
+fn trie_chunk(trie_bytes: &[u8], chunk_id: u32) -> Option<(u32, &[u8])> {
+    let chunk_count = cmp::max(
+        1,
+        (trie_bytes.len() + TRIE_CHUNK_SIZE - 1) / TRIE_CHUNK_SIZE,
+    );
+    let chunk_id = chunk_id as usize;
+    if chunk_id >= chunk_count {
+        return None;
+    }
+    let chunk_start = chunk_id * TRIE_CHUNK_SIZE;
+    let chunk_end = cmp::min(trie_bytes.len(), chunk_start + TRIE_CHUNK_SIZE);
+    Some((chunk_count as u32, &trie_bytes[chunk_start..chunk_end]))
+}
+
+/// Parses the protocol version, pre-state hash and effects of `commit_request`, or returns the
+/// response to send back if any of them is invalid.
+fn parse_commit_request(
+    commit_request: &mut CommitRequest,
+) -> Result<(ProtocolVersion, Blake2bHash, AdditiveMap<Key, Transform>), CommitResponse> {
+    // TODO
+    let protocol_version = {
+        let protocol_version = commit_request.take_protocol_version().into();
+        if protocol_version < DEFAULT_PROTOCOL_VERSION {
+            DEFAULT_PROTOCOL_VERSION
+        } else {
+            protocol_version
+        }
+    };
+
+    // Acquire pre-state hash
+    let pre_state_hash: Blake2bHash = match commit_request.get_prestate_hash().try_into() {
+        Err(_) => {
+            let error_message = "Could not parse pre-state hash".to_string();
+            warn!("{}", error_message);
+            let mut commit_response = CommitResponse::new();
+            commit_response
+                .mut_failed_transform()
+                .set_message(error_message);
+            return Err(commit_response);
+        }
+        Ok(hash) => hash,
+    };
+
+    // Acquire commit transforms
+    let transforms = match TransformMap::try_from(commit_request.take_effects().into_vec()) {
+        Err(ParsingError(error_message)) => {
+            warn!("{}", error_message);
+            let mut commit_response = CommitResponse::new();
+            commit_response
+                .mut_failed_transform()
+                .set_message(error_message);
+            return Err(commit_response);
+        }
+        Ok(transforms) => transforms.into_inner(),
+    };
+
+    Ok((protocol_version, pre_state_hash, transforms))
+}
+
+/// Converts the result of committing effects on top of `pre_state_hash` into a response.
+fn commit_response(
+    pre_state_hash: Blake2bHash,
+    commit_result: Result<CommitResult, EngineError>,
+) -> CommitResponse {
+    let mut ret = CommitResponse::new();
+
+    match commit_result {
+        Ok(CommitResult::Success {
+            state_root,
+            bonded_validators,
+        }) => {
+            let properties = {
+                let mut tmp = BTreeMap::new();
+                tmp.insert("post-state-hash", format!("{:?}", state_root));
+                tmp.insert("success", true.to_string());
+                tmp
+            };
+            logging::log_details(
+                Level::Info,
+                "effects applied; new state hash is: {post-state-hash}".to_owned(),
+                properties,
+            );
+
+            let bonds = bonded_validators.into_iter().map(Into::into).collect();
+            let commit_result = ret.mut_success();
+            commit_result.set_poststate_hash(state_root.to_vec());
+            commit_result.set_bonded_validators(bonds);
+        }
+        Ok(CommitResult::RootNotFound) => {
+            warn!("RootNotFound");
+            ret.mut_missing_prestate().set_hash(pre_state_hash.to_vec());
+        }
+        Ok(CommitResult::KeyNotFound(key)) => {
+            warn!("{:?} not found", key);
+            ret.set_key_not_found(key.into());
+        }
+        Ok(CommitResult::TypeMismatch(type_mismatch)) => {
+            warn!("{:?}", type_mismatch);
+            ret.set_type_mismatch(type_mismatch.into());
+        }
+        Ok(CommitResult::Serialization(error)) => {
+            warn!("{:?}", error);
+            ret.mut_failed_transform().set_message(format!("{:?}", error));
+        }
+        Err(error) => {
+            warn!("State error {:?} when applying transforms", error);
+            ret.mut_failed_transform().set_message(format!("{:?}", error));
+        }
+    }
+
+    ret
 }
 
 // Helper method which returns single DeployResult that is set to be a
@@ -1 +1,2 @@
 pub mod engine_server;
+pub mod metrics_endpoint;
@@ -22,12 +22,13 @@ use engine_shared::{
     socket,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
-    trie_store::lmdb::LmdbTrieStore,
+    global_state::{lmdb::LmdbGlobalState, rocksdb::RocksDbGlobalState},
+    protocol_data_store::{lmdb::LmdbProtocolDataStore, rocksdb::RocksDbProtocolDataStore},
+    transaction_source::{lmdb::LmdbEnvironment, rocksdb::RocksDbEnvironment},
+    trie_store::{lmdb::LmdbTrieStore, rocksdb::RocksDbTrieStore},
 };
 
-use casperlabs_engine_grpc_server::engine_server;
-use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use casperlabs_engine_grpc_server::{engine_server, metrics_endpoint};
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
@@ -48,6 +49,24 @@ const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
 const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
 const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
+const ROCKSDB_DIR: &str = "rocksdb";
+const ROCKSDB_ENVIRONMENT_EXPECT: &str = "Could not create RocksDbEnvironment";
+const ROCKSDB_GLOBAL_STATE_EXPECT: &str = "Could not create RocksDbGlobalState";
+const ROCKSDB_MIGRATION_EXPECT: &str = "Could not migrate global state from LMDB to RocksDB";
+
+// storage
+const ARG_STORAGE: &str = "storage";
+const ARG_STORAGE_VALUE: &str = "BACKEND";
+const ARG_STORAGE_HELP: &str = "Sets the storage backend for global state";
+const STORAGE_LMDB: &str = "lmdb";
+const STORAGE_ROCKSDB: &str = "rocksdb";
+
+// migrate from lmdb
+const ARG_MIGRATE_FROM_LMDB: &str = "migrate-from-lmdb";
+const ARG_MIGRATE_FROM_LMDB_HELP: &str =
+    "Copies the LMDB global state in the data directory into RocksDB before starting.  Requires \
+     --storage rocksdb";
+const ARG_MIGRATE_FROM_LMDB_EXPECT: &str = "--migrate-from-lmdb requires --storage rocksdb";
 
 // pages / lmdb
 const ARG_PAGES: &str = "pages";
@@ -80,13 +99,20 @@ const LOG_LEVEL_TRACE: &str = "trace";
 // metrics
 const ARG_LOG_METRICS: &str = "log-metrics";
 const ARG_LOG_METRICS_HELP: &str = "Enables logging of metrics regardless of log-level setting";
+const ARG_METRICS_ADDRESS: &str = "metrics-address";
+const ARG_METRICS_ADDRESS_VALUE: &str = "ADDRESS";
+const ARG_METRICS_ADDRESS_HELP: &str =
+    "Serves execution metrics for Prometheus at http://ADDRESS/metrics, e.g. 127.0.0.1:9102";
+const METRICS_ENDPOINT_EXPECT: &str = "failed to start metrics endpoint";
 
 // log style
 const ARG_LOG_STYLE: &str = "log-style";
 const ARG_LOG_STYLE_VALUE: &str = "STYLE";
-const ARG_LOG_STYLE_HELP: &str = "Sets logging style to structured or human-readable";
+const ARG_LOG_STYLE_HELP: &str =
+    "Sets logging style to structured, human-readable or JSON (one object per line)";
 const LOG_STYLE_STRUCTURED: &str = "structured";
 const LOG_STYLE_HUMAN_READABLE: &str = "human";
+const LOG_STYLE_JSON: &str = "json";
 
 // thread count
 const ARG_THREAD_COUNT: &str = "threads";
@@ -107,6 +133,11 @@ const ARG_ENABLE_BONDING: &str = "enable-bonding";
 const ARG_ENABLE_BONDING_SHORT: &str = "b";
 const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
 
+// contract debugging
+const ARG_ENABLE_CONTRACT_DEBUG: &str = "enable-contract-debug";
+const ARG_ENABLE_CONTRACT_DEBUG_HELP: &str =
+    "Write debug output of contracts to the log.  Not meant for production use";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -135,10 +166,21 @@ fn main() {
 
     let engine_config: EngineConfig = get_engine_config(&arg_matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
+    let storage = get_storage(&arg_matches);
+
+    let _server = get_grpc_server(
+        &socket,
+        data_dir,
+        map_size,
+        thread_count,
+        engine_config,
+        storage,
+    );
 
     log_listening_message(&socket);
 
+    start_metrics_endpoint(&arg_matches);
+
     let interval = Duration::from_secs(RUNNABLE_CHECK_INTERVAL_SECONDS);
 
     let runnable = get_sigint_handle();
@@ -191,6 +233,14 @@ fn get_args() -> ArgMatches<'static> {
                 .takes_value(false)
                 .help(ARG_LOG_METRICS_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_METRICS_ADDRESS)
+                .required(false)
+                .long(ARG_METRICS_ADDRESS)
+                .takes_value(true)
+                .value_name(ARG_METRICS_ADDRESS_VALUE)
+                .help(ARG_METRICS_ADDRESS_HELP),
+        )
         .arg(
             Arg::with_name(ARG_LOG_STYLE)
                 .required(false)
@@ -198,6 +248,7 @@ fn get_args() -> ArgMatches<'static> {
                 .takes_value(true)
                 .possible_value(LOG_STYLE_STRUCTURED)
                 .possible_value(LOG_STYLE_HUMAN_READABLE)
+                .possible_value(LOG_STYLE_JSON)
                 .default_value(LOG_STYLE_STRUCTURED)
                 .value_name(ARG_LOG_STYLE_VALUE)
                 .help(ARG_LOG_STYLE_HELP),
@@ -218,6 +269,24 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_STORAGE)
+                .required(false)
+                .long(ARG_STORAGE)
+                .takes_value(true)
+                .possible_value(STORAGE_LMDB)
+                .possible_value(STORAGE_ROCKSDB)
+                .default_value(STORAGE_LMDB)
+                .value_name(ARG_STORAGE_VALUE)
+                .help(ARG_STORAGE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MIGRATE_FROM_LMDB)
+                .required(false)
+                .long(ARG_MIGRATE_FROM_LMDB)
+                .takes_value(false)
+                .help(ARG_MIGRATE_FROM_LMDB_HELP),
+        )
         .arg(
             Arg::with_name(ARG_THREAD_COUNT)
                 .short(ARG_THREAD_COUNT_SHORT)
@@ -239,6 +308,11 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_ENABLE_BONDING)
                 .help(ARG_ENABLE_BONDING_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_ENABLE_CONTRACT_DEBUG)
+                .long(ARG_ENABLE_CONTRACT_DEBUG)
+                .help(ARG_ENABLE_CONTRACT_DEBUG_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
@@ -248,6 +322,17 @@ fn get_args() -> ArgMatches<'static> {
         .get_matches()
 }
 
+/// Starts the metrics endpoint if the metrics-address argument was given
+fn start_metrics_endpoint(arg_matches: &ArgMatches) {
+    if let Some(address) = arg_matches.value_of(ARG_METRICS_ADDRESS) {
+        let local_addr = metrics_endpoint::start(address).expect(METRICS_ENDPOINT_EXPECT);
+        info!(
+            "metrics endpoint is listening on http://{}/metrics",
+            local_addr
+        );
+    }
+}
+
 /// Gets SIGINT handle to allow clean exit
 fn get_sigint_handle() -> Arc<AtomicBool> {
     let handle = Arc::new(AtomicBool::new(true));
@@ -299,14 +384,36 @@ fn get_thread_count(arg_matches: &ArgMatches) -> usize {
         .expect(ARG_THREAD_COUNT_EXPECT)
 }
 
+/// Selects the storage backend for global state.
+enum Storage {
+    Lmdb,
+    RocksDb { migrate_from_lmdb: bool },
+}
+
+/// Parses storage and migrate-from-lmdb arguments
+fn get_storage(arg_matches: &ArgMatches) -> Storage {
+    let migrate_from_lmdb = arg_matches.is_present(ARG_MIGRATE_FROM_LMDB);
+    match arg_matches
+        .value_of(ARG_STORAGE)
+        .expect("should have default value if not explicitly set")
+    {
+        STORAGE_LMDB if migrate_from_lmdb => panic!("{}", ARG_MIGRATE_FROM_LMDB_EXPECT),
+        STORAGE_LMDB => Storage::Lmdb,
+        STORAGE_ROCKSDB => Storage::RocksDb { migrate_from_lmdb },
+        _ => unreachable!("should validate storage arg to match one of the options"),
+    }
+}
+
 /// Returns an [`EngineConfig`].
 fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
     let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
     let enable_bonding = arg_matches.is_present(ARG_ENABLE_BONDING);
+    let enable_contract_debug = arg_matches.is_present(ARG_ENABLE_CONTRACT_DEBUG);
     EngineConfig::new()
         .with_use_system_contracts(use_system_contracts)
         .with_enable_bonding(enable_bonding)
+        .with_enable_contract_debug(enable_contract_debug)
 }
 
 /// Builds and returns a gRPC server.
@@ -316,20 +423,26 @@ fn get_grpc_server(
     map_size: usize,
     thread_count: usize,
     engine_config: EngineConfig,
+    storage: Storage,
 ) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
+    let server_builder = match storage {
+        Storage::Lmdb => {
+            let global_state = get_lmdb_global_state(data_dir, map_size);
+            let engine_state = EngineState::new(global_state, engine_config);
+            engine_server::new(socket.as_str(), thread_count, engine_state)
+        }
+        Storage::RocksDb { migrate_from_lmdb } => {
+            let global_state = get_rocksdb_global_state(data_dir, map_size, migrate_from_lmdb);
+            let engine_state = EngineState::new(global_state, engine_config);
+            engine_server::new(socket.as_str(), thread_count, engine_state)
+        }
+    };
 
-    engine_server::new(socket.as_str(), thread_count, engine_state)
-        .build()
-        .expect(SERVER_START_EXPECT)
+    server_builder.build().expect(SERVER_START_EXPECT)
 }
 
-/// Builds and returns engine global state
-fn get_engine_state(
-    data_dir: PathBuf,
-    map_size: usize,
-    engine_config: EngineConfig,
-) -> EngineState<LmdbGlobalState> {
+/// Builds and returns LMDB-backed global state
+fn get_lmdb_global_state(data_dir: PathBuf, map_size: usize) -> LmdbGlobalState {
     let environment = {
         let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
@@ -347,10 +460,44 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+    LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(LMDB_GLOBAL_STATE_EXPECT)
+}
+
+/// Builds and returns RocksDB-backed global state, optionally migrating the LMDB global state in
+/// the data directory into it first
+fn get_rocksdb_global_state(
+    data_dir: PathBuf,
+    map_size: usize,
+    migrate_from_lmdb: bool,
+) -> RocksDbGlobalState {
+    let rocksdb_dir = data_dir.join(ROCKSDB_DIR);
+
+    let environment = {
+        let ret = RocksDbEnvironment::new(&rocksdb_dir).expect(ROCKSDB_ENVIRONMENT_EXPECT);
+        Arc::new(ret)
+    };
+
+    let trie_store = Arc::new(RocksDbTrieStore::new(&environment, None));
+
+    let protocol_data_store = Arc::new(RocksDbProtocolDataStore::new(&environment, None));
+
+    let global_state = RocksDbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(ROCKSDB_GLOBAL_STATE_EXPECT);
+
+    if migrate_from_lmdb {
+        info!("migrating global state from LMDB to RocksDB");
+        let lmdb_global_state = get_lmdb_global_state(data_dir, map_size);
+        let copied = global_state
+            .migrate_from_lmdb(&lmdb_global_state)
+            .expect(ROCKSDB_MIGRATION_EXPECT);
+        info!(
+            "migrated {} global state entries from LMDB to RocksDB",
+            copied
+        );
+    }
 
-    EngineState::new(global_state, engine_config)
+    global_state
 }
 
 /// Builds and returns log settings
@@ -372,6 +519,7 @@ fn get_log_settings(arg_matches: &ArgMatches) -> Settings {
 
     let style = match arg_matches.value_of(ARG_LOG_STYLE) {
         Some(LOG_STYLE_HUMAN_READABLE) => Style::HumanReadable,
+        Some(LOG_STYLE_JSON) => Style::Json,
         _ => Style::Structured,
     };
 
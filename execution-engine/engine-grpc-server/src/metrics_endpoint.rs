@@ -0,0 +1,102 @@
+//! A minimal HTTP endpoint serving the engine's execution statistics for Prometheus to scrape.
+//!
+//! Only `GET /metrics` is answered; every other request gets a `404`.  Requests are handled one at
+//! a time on a dedicated thread, which is ample for a scraper polling every few seconds.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::Duration,
+};
+
+use log::warn;
+
+use engine_shared::metrics;
+
+const THREAD_NAME: &str = "metrics-endpoint";
+const METRICS_PATH: &str = "/metrics";
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Binds to `address` and serves metrics from a background thread.
+///
+/// Returns the address actually bound, which differs from `address` if it specified port 0.
+pub fn start<A: ToSocketAddrs>(address: A) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(address)?;
+    let local_addr = listener.local_addr()?;
+    thread::Builder::new()
+        .name(THREAD_NAME.to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(handle_connection);
+                if let Err(error) = result {
+                    warn!("metrics endpoint failed to handle request: {}", error);
+                }
+            }
+        })?;
+    Ok(local_addr)
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers so the client isn't reset while still sending them.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(METRICS_PATH)) => {
+            http_response("200 OK", metrics::CONTENT_TYPE, &metrics::render())
+        }
+        _ => http_response("404 Not Found", "text/plain", "Not Found\n"),
+    };
+
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).expect("should connect");
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).expect("should write");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .expect("should read response");
+        response
+    }
+
+    #[test]
+    fn should_serve_metrics() {
+        let address = start("127.0.0.1:0").expect("should start endpoint");
+
+        let response = get(address, METRICS_PATH);
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(metrics::CONTENT_TYPE));
+        assert!(response.contains("casperlabs_engine_deploys_executed_total"));
+
+        let response = get(address, "/");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
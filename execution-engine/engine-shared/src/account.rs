@@ -23,6 +23,8 @@ pub struct Account {
     main_purse: URef,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
+    /// The nonce expected of the account's next deploy, if replay protection is enabled for it.
+    nonce: Option<u64>,
 }
 
 impl Account {
@@ -39,6 +41,7 @@ impl Account {
             main_purse,
             associated_keys,
             action_thresholds,
+            nonce: None,
         }
     }
 
@@ -74,6 +77,10 @@ impl Account {
         self.main_purse
     }
 
+    pub fn set_main_purse(&mut self, main_purse: URef) {
+        self.main_purse = main_purse;
+    }
+
     /// Returns an [`AccessRights::ADD`]-only version of the [`URef`].
     pub fn main_purse_add_only(&self) -> URef {
         URef::new(self.main_purse.addr(), AccessRights::ADD)
@@ -87,6 +94,25 @@ impl Account {
         &self.action_thresholds
     }
 
+    /// Returns the nonce expected of the account's next deploy, or `None` if replay protection
+    /// isn't enabled for the account.
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    /// Sets the nonce expected of the account's next deploy.  `None` disables replay protection
+    /// for the account.
+    pub fn set_nonce(&mut self, nonce: Option<u64>) {
+        self.nonce = nonce;
+    }
+
+    /// Advances the nonce expected of the account's next deploy, if replay protection is enabled.
+    pub fn increment_nonce(&mut self) {
+        if let Some(nonce) = self.nonce.as_mut() {
+            *nonce = nonce.saturating_add(1);
+        }
+    }
+
     pub fn add_associated_key(
         &mut self,
         account_hash: AccountHash,
@@ -215,15 +241,26 @@ impl ToBytes for Account {
         result.append(&mut self.main_purse.to_bytes()?);
         result.append(&mut self.associated_keys.to_bytes()?);
         result.append(&mut self.action_thresholds.to_bytes()?);
+        // Omitted when unset, so that accounts stored before the nonce was added re-encode to the
+        // same bytes.
+        if self.nonce.is_some() {
+            result.append(&mut self.nonce.to_bytes()?);
+        }
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
+        let nonce_length = if self.nonce.is_some() {
+            self.nonce.serialized_length()
+        } else {
+            0
+        };
         self.account_hash.serialized_length()
             + self.named_keys.serialized_length()
             + self.main_purse.serialized_length()
             + self.associated_keys.serialized_length()
             + self.action_thresholds.serialized_length()
+            + nonce_length
     }
 }
 
@@ -234,6 +271,7 @@ impl FromBytes for Account {
         let (main_purse, rem) = URef::from_bytes(rem)?;
         let (associated_keys, rem) = AssociatedKeys::from_bytes(rem)?;
         let (action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
+        let (nonce, rem) = bytesrepr::from_trailing_bytes(rem, None)?;
         Ok((
             Account {
                 account_hash,
@@ -241,6 +279,7 @@ impl FromBytes for Account {
                 main_purse,
                 associated_keys,
                 action_thresholds,
+                nonce,
             },
             rem,
         ))
@@ -267,15 +306,18 @@ pub mod gens {
             purse in uref_arb(),
             thresholds in action_thresholds_arb(),
             mut associated_keys in associated_keys_arb(MAX_ASSOCIATED_KEYS - 1),
+            nonce in proptest::option::of(proptest::num::u64::ANY),
         ) -> Account {
                 associated_keys.add_key(account_hash, Weight::new(1)).unwrap();
-                Account::new(
+                let mut account = Account::new(
                     account_hash,
                     urefs,
                     purse,
                     associated_keys,
                     thresholds,
-                )
+                );
+                account.set_nonce(nonce);
+                account
         }
     }
 }
@@ -651,4 +693,54 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    #[test]
+    fn should_increment_nonce_only_if_set() {
+        let mut account = Account::create(
+            AccountHash::new([0u8; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        assert_eq!(account.nonce(), None);
+
+        account.increment_nonce();
+        assert_eq!(account.nonce(), None);
+
+        account.set_nonce(Some(0));
+        account.increment_nonce();
+        assert_eq!(account.nonce(), Some(1));
+    }
+
+    #[test]
+    fn should_round_trip_legacy_layout_of_account_without_nonce() {
+        let account = Account::create(
+            AccountHash::new([0u8; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        // the encoding of accounts stored before the nonce was added
+        let mut legacy_bytes = vec![];
+        legacy_bytes.append(&mut account.account_hash().to_bytes().unwrap());
+        legacy_bytes.append(&mut account.named_keys().to_bytes().unwrap());
+        legacy_bytes.append(&mut account.main_purse().to_bytes().unwrap());
+        legacy_bytes.append(&mut account.associated_keys.to_bytes().unwrap());
+        legacy_bytes.append(&mut account.action_thresholds().to_bytes().unwrap());
+
+        let (decoded, rem) = Account::from_bytes(&legacy_bytes).expect("should deserialize");
+        assert_eq!(decoded, account);
+        assert!(rem.is_empty());
+        assert_eq!(decoded.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(decoded.serialized_length(), legacy_bytes.len());
+    }
+
+    #[test]
+    fn should_round_trip_account_with_nonce() {
+        let mut account = Account::create(
+            AccountHash::new([0u8; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        account.set_nonce(Some(0));
+        bytesrepr::test_serialization_roundtrip(&account);
+    }
 }
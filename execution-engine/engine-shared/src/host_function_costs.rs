@@ -0,0 +1,605 @@
+//! Gas costs of the host functions exposed to contracts.
+//!
+//! The signature verification host functions (`verify_ed25519` and `verify_secp256k1`) are
+//! charged according to `WasmCosts`, and `gas` is the metering call itself, so none of them appear
+//! here.
+//!
+//! On top of the cost of the call, writes to global state are charged for the storage they take
+//! up according to `StorageCosts`.
+//!
+//! Like `WasmCosts`, the costs are part of a protocol version's `ProtocolData`: they are set at
+//! genesis and may be replaced by an upgrade.
+
+use std::cmp;
+
+use serde::{Deserialize, Serialize};
+
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    U512,
+};
+
+use crate::gas::Gas;
+
+/// The storage refund of an execution is capped at the gas it used divided by this quotient, so that
+/// freeing storage can discount an execution but never pay for it.
+pub const MAX_STORAGE_REFUND_QUOTIENT: u64 = 5;
+
+const NUM_HOST_FUNCTIONS: usize = 64;
+const HOST_FUNCTION_COST_SERIALIZED_LENGTH: usize = 2 * U32_SERIALIZED_LENGTH;
+const STORAGE_COSTS_SERIALIZED_LENGTH: usize = U32_SERIALIZED_LENGTH + U8_SERIALIZED_LENGTH;
+pub const HOST_FUNCTION_COSTS_SERIALIZED_LENGTH: usize =
+    NUM_HOST_FUNCTIONS * HOST_FUNCTION_COST_SERIALIZED_LENGTH + STORAGE_COSTS_SERIALIZED_LENGTH;
+
+/// The cost of a single call to a host function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostFunctionCost {
+    /// Cost charged for every call.
+    pub fixed: u32,
+    /// Cost charged per byte of input read from Wasm memory.
+    pub per_byte: u32,
+}
+
+impl HostFunctionCost {
+    pub const fn new(fixed: u32, per_byte: u32) -> Self {
+        HostFunctionCost { fixed, per_byte }
+    }
+
+    /// Returns the gas charged for a call whose inputs have the given sizes in bytes.
+    pub fn calculate_gas_cost(self, input_sizes: &[u32]) -> Gas {
+        let input_size = input_sizes
+            .iter()
+            .fold(U512::zero(), |sum, size| sum + U512::from(*size));
+        Gas::new(U512::from(self.fixed) + U512::from(self.per_byte) * input_size)
+    }
+}
+
+/// The cost of the persistent storage taken up by values written to global state.
+///
+/// A write is charged for the bytes by which it grows the value stored under its key, and refunded
+/// a portion of that charge for the bytes by which it shrinks it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageCosts {
+    /// Cost charged per byte added to global state.
+    pub gas_per_byte: u32,
+    /// Percentage of `gas_per_byte` refunded per byte removed from global state.
+    pub refund_percent: u8,
+}
+
+impl StorageCosts {
+    pub const fn new(gas_per_byte: u32, refund_percent: u8) -> Self {
+        StorageCosts {
+            gas_per_byte,
+            refund_percent,
+        }
+    }
+
+    /// Returns the gas charged for adding the given number of bytes to global state.
+    pub fn calculate_write_cost(self, added_bytes: usize) -> Gas {
+        Gas::new(U512::from(self.gas_per_byte) * U512::from(added_bytes))
+    }
+
+    /// Returns the gas refunded for removing the given number of bytes from global state.
+    pub fn calculate_refund(self, removed_bytes: usize) -> Gas {
+        let refund_percent = cmp::min(self.refund_percent, 100);
+        Gas::new(
+            U512::from(self.gas_per_byte) * U512::from(removed_bytes) * U512::from(refund_percent)
+                / U512::from(100),
+        )
+    }
+}
+
+impl Default for StorageCosts {
+    fn default() -> Self {
+        StorageCosts::new(10, 50)
+    }
+}
+
+/// The cost schedule of the host functions, with one entry per function of the contract API.
+///
+/// When deserialized with serde, missing entries take their default costs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HostFunctionCosts {
+    pub read_value: HostFunctionCost,
+    pub read_value_local: HostFunctionCost,
+    pub write: HostFunctionCost,
+    pub write_local: HostFunctionCost,
+    pub write_and_return_old: HostFunctionCost,
+    pub compare_and_swap: HostFunctionCost,
+    pub delete: HostFunctionCost,
+    pub add: HostFunctionCost,
+    pub counter_inc: HostFunctionCost,
+    pub new_uref: HostFunctionCost,
+    pub load_named_keys: HostFunctionCost,
+    /// The per byte cost is also charged for each byte of the page returned.
+    pub load_named_keys_paged: HostFunctionCost,
+    /// Also charged for `ret_without_transfer`.
+    pub ret: HostFunctionCost,
+    pub get_key: HostFunctionCost,
+    pub has_key: HostFunctionCost,
+    pub put_key: HostFunctionCost,
+    pub remove_key: HostFunctionCost,
+    pub get_caller: HostFunctionCost,
+    pub is_system_caller: HostFunctionCost,
+    pub get_call_stack: HostFunctionCost,
+    pub get_blocktime: HostFunctionCost,
+    pub get_gas_price: HostFunctionCost,
+    pub get_deploy_hash: HostFunctionCost,
+    pub random_bytes: HostFunctionCost,
+    pub u512_mul_div: HostFunctionCost,
+    pub blake2b: HostFunctionCost,
+    pub keccak256: HostFunctionCost,
+    pub emit_event: HostFunctionCost,
+    pub dictionary_new: HostFunctionCost,
+    pub dictionary_get: HostFunctionCost,
+    pub dictionary_put: HostFunctionCost,
+    pub is_valid_uref: HostFunctionCost,
+    pub revert: HostFunctionCost,
+    pub add_associated_key: HostFunctionCost,
+    pub remove_associated_key: HostFunctionCost,
+    pub update_associated_key: HostFunctionCost,
+    pub set_action_threshold: HostFunctionCost,
+    pub set_main_purse: HostFunctionCost,
+    pub register_system_contract: HostFunctionCost,
+    pub create_purse: HostFunctionCost,
+    pub transfer_to_account: HostFunctionCost,
+    pub transfer_from_purse_to_account: HostFunctionCost,
+    pub transfer_from_purse_to_accounts: HostFunctionCost,
+    pub transfer_from_purse_to_purse: HostFunctionCost,
+    pub get_balance: HostFunctionCost,
+    pub get_phase: HostFunctionCost,
+    pub get_system_contract: HostFunctionCost,
+    pub get_main_purse: HostFunctionCost,
+    pub read_host_buffer: HostFunctionCost,
+    pub create_contract_package_at_hash: HostFunctionCost,
+    pub create_contract_user_group: HostFunctionCost,
+    pub add_contract_version: HostFunctionCost,
+    pub disable_contract_version: HostFunctionCost,
+    pub disable_contract: HostFunctionCost,
+    pub set_non_reentrant: HostFunctionCost,
+    pub call_contract: HostFunctionCost,
+    pub call_contract_read_only: HostFunctionCost,
+    pub call_versioned_contract: HostFunctionCost,
+    pub print: HostFunctionCost,
+    pub get_named_arg_size: HostFunctionCost,
+    pub get_named_arg: HostFunctionCost,
+    pub remove_contract_user_group: HostFunctionCost,
+    pub provision_contract_user_group_uref: HostFunctionCost,
+    pub remove_contract_user_group_urefs: HostFunctionCost,
+    /// Charged in addition to the above for the values written to global state.
+    pub storage: StorageCosts,
+}
+
+impl Default for HostFunctionCosts {
+    fn default() -> Self {
+        HostFunctionCosts {
+            read_value: HostFunctionCost::new(1_000, 1),
+            read_value_local: HostFunctionCost::new(1_000, 1),
+            write: HostFunctionCost::new(10_000, 10),
+            write_local: HostFunctionCost::new(10_000, 10),
+            write_and_return_old: HostFunctionCost::new(11_000, 10),
+            compare_and_swap: HostFunctionCost::new(11_000, 10),
+            delete: HostFunctionCost::new(10_000, 0),
+            add: HostFunctionCost::new(10_000, 10),
+            counter_inc: HostFunctionCost::new(10_000, 0),
+            new_uref: HostFunctionCost::new(10_000, 10),
+            load_named_keys: HostFunctionCost::new(1_000, 0),
+            load_named_keys_paged: HostFunctionCost::new(1_000, 1),
+            ret: HostFunctionCost::new(100, 1),
+            get_key: HostFunctionCost::new(200, 1),
+            has_key: HostFunctionCost::new(200, 1),
+            put_key: HostFunctionCost::new(1_000, 1),
+            remove_key: HostFunctionCost::new(1_000, 1),
+            get_caller: HostFunctionCost::new(100, 0),
+            is_system_caller: HostFunctionCost::new(100, 0),
+            get_call_stack: HostFunctionCost::new(100, 0),
+            get_blocktime: HostFunctionCost::new(100, 0),
+            get_gas_price: HostFunctionCost::new(100, 0),
+            get_deploy_hash: HostFunctionCost::new(100, 0),
+            random_bytes: HostFunctionCost::new(200, 1),
+            u512_mul_div: HostFunctionCost::new(1_000, 0),
+            blake2b: HostFunctionCost::new(200, 1),
+            keccak256: HostFunctionCost::new(200, 1),
+            emit_event: HostFunctionCost::new(1_000, 10),
+            dictionary_new: HostFunctionCost::new(10_000, 0),
+            dictionary_get: HostFunctionCost::new(1_000, 1),
+            dictionary_put: HostFunctionCost::new(10_000, 10),
+            is_valid_uref: HostFunctionCost::new(100, 0),
+            revert: HostFunctionCost::new(100, 0),
+            add_associated_key: HostFunctionCost::new(10_000, 0),
+            remove_associated_key: HostFunctionCost::new(10_000, 0),
+            update_associated_key: HostFunctionCost::new(10_000, 0),
+            set_action_threshold: HostFunctionCost::new(10_000, 0),
+            set_main_purse: HostFunctionCost::new(10_000, 0),
+            register_system_contract: HostFunctionCost::new(10_000, 0),
+            create_purse: HostFunctionCost::new(20_000, 0),
+            transfer_to_account: HostFunctionCost::new(20_000, 0),
+            transfer_from_purse_to_account: HostFunctionCost::new(20_000, 0),
+            transfer_from_purse_to_accounts: HostFunctionCost::new(10_000, 0),
+            transfer_from_purse_to_purse: HostFunctionCost::new(20_000, 0),
+            get_balance: HostFunctionCost::new(1_000, 0),
+            get_phase: HostFunctionCost::new(100, 0),
+            get_system_contract: HostFunctionCost::new(100, 0),
+            get_main_purse: HostFunctionCost::new(100, 0),
+            read_host_buffer: HostFunctionCost::new(100, 1),
+            create_contract_package_at_hash: HostFunctionCost::new(20_000, 0),
+            create_contract_user_group: HostFunctionCost::new(20_000, 10),
+            add_contract_version: HostFunctionCost::new(20_000, 10),
+            disable_contract_version: HostFunctionCost::new(10_000, 0),
+            disable_contract: HostFunctionCost::new(10_000, 0),
+            set_non_reentrant: HostFunctionCost::new(10_000, 0),
+            call_contract: HostFunctionCost::new(5_000, 1),
+            call_contract_read_only: HostFunctionCost::new(5_000, 1),
+            call_versioned_contract: HostFunctionCost::new(5_000, 1),
+            print: HostFunctionCost::new(100, 1),
+            get_named_arg_size: HostFunctionCost::new(100, 1),
+            get_named_arg: HostFunctionCost::new(100, 1),
+            remove_contract_user_group: HostFunctionCost::new(10_000, 1),
+            provision_contract_user_group_uref: HostFunctionCost::new(10_000, 1),
+            remove_contract_user_group_urefs: HostFunctionCost::new(10_000, 1),
+            storage: StorageCosts::default(),
+        }
+    }
+}
+
+impl ToBytes for HostFunctionCost {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.fixed.to_bytes()?);
+        ret.append(&mut self.per_byte.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        HOST_FUNCTION_COST_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for HostFunctionCost {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (fixed, rem) = u32::from_bytes(bytes)?;
+        let (per_byte, rem) = u32::from_bytes(rem)?;
+        Ok((HostFunctionCost { fixed, per_byte }, rem))
+    }
+}
+
+impl ToBytes for StorageCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.gas_per_byte.to_bytes()?);
+        ret.append(&mut self.refund_percent.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        STORAGE_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for StorageCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (gas_per_byte, rem) = u32::from_bytes(bytes)?;
+        let (refund_percent, rem) = u8::from_bytes(rem)?;
+        Ok((
+            StorageCosts {
+                gas_per_byte,
+                refund_percent,
+            },
+            rem,
+        ))
+    }
+}
+
+impl ToBytes for HostFunctionCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.read_value.to_bytes()?);
+        ret.append(&mut self.read_value_local.to_bytes()?);
+        ret.append(&mut self.write.to_bytes()?);
+        ret.append(&mut self.write_local.to_bytes()?);
+        ret.append(&mut self.write_and_return_old.to_bytes()?);
+        ret.append(&mut self.compare_and_swap.to_bytes()?);
+        ret.append(&mut self.delete.to_bytes()?);
+        ret.append(&mut self.add.to_bytes()?);
+        ret.append(&mut self.counter_inc.to_bytes()?);
+        ret.append(&mut self.new_uref.to_bytes()?);
+        ret.append(&mut self.load_named_keys.to_bytes()?);
+        ret.append(&mut self.load_named_keys_paged.to_bytes()?);
+        ret.append(&mut self.ret.to_bytes()?);
+        ret.append(&mut self.get_key.to_bytes()?);
+        ret.append(&mut self.has_key.to_bytes()?);
+        ret.append(&mut self.put_key.to_bytes()?);
+        ret.append(&mut self.remove_key.to_bytes()?);
+        ret.append(&mut self.get_caller.to_bytes()?);
+        ret.append(&mut self.is_system_caller.to_bytes()?);
+        ret.append(&mut self.get_call_stack.to_bytes()?);
+        ret.append(&mut self.get_blocktime.to_bytes()?);
+        ret.append(&mut self.get_gas_price.to_bytes()?);
+        ret.append(&mut self.get_deploy_hash.to_bytes()?);
+        ret.append(&mut self.random_bytes.to_bytes()?);
+        ret.append(&mut self.u512_mul_div.to_bytes()?);
+        ret.append(&mut self.blake2b.to_bytes()?);
+        ret.append(&mut self.keccak256.to_bytes()?);
+        ret.append(&mut self.emit_event.to_bytes()?);
+        ret.append(&mut self.dictionary_new.to_bytes()?);
+        ret.append(&mut self.dictionary_get.to_bytes()?);
+        ret.append(&mut self.dictionary_put.to_bytes()?);
+        ret.append(&mut self.is_valid_uref.to_bytes()?);
+        ret.append(&mut self.revert.to_bytes()?);
+        ret.append(&mut self.add_associated_key.to_bytes()?);
+        ret.append(&mut self.remove_associated_key.to_bytes()?);
+        ret.append(&mut self.update_associated_key.to_bytes()?);
+        ret.append(&mut self.set_action_threshold.to_bytes()?);
+        ret.append(&mut self.set_main_purse.to_bytes()?);
+        ret.append(&mut self.register_system_contract.to_bytes()?);
+        ret.append(&mut self.create_purse.to_bytes()?);
+        ret.append(&mut self.transfer_to_account.to_bytes()?);
+        ret.append(&mut self.transfer_from_purse_to_account.to_bytes()?);
+        ret.append(&mut self.transfer_from_purse_to_accounts.to_bytes()?);
+        ret.append(&mut self.transfer_from_purse_to_purse.to_bytes()?);
+        ret.append(&mut self.get_balance.to_bytes()?);
+        ret.append(&mut self.get_phase.to_bytes()?);
+        ret.append(&mut self.get_system_contract.to_bytes()?);
+        ret.append(&mut self.get_main_purse.to_bytes()?);
+        ret.append(&mut self.read_host_buffer.to_bytes()?);
+        ret.append(&mut self.create_contract_package_at_hash.to_bytes()?);
+        ret.append(&mut self.create_contract_user_group.to_bytes()?);
+        ret.append(&mut self.add_contract_version.to_bytes()?);
+        ret.append(&mut self.disable_contract_version.to_bytes()?);
+        ret.append(&mut self.disable_contract.to_bytes()?);
+        ret.append(&mut self.set_non_reentrant.to_bytes()?);
+        ret.append(&mut self.call_contract.to_bytes()?);
+        ret.append(&mut self.call_contract_read_only.to_bytes()?);
+        ret.append(&mut self.call_versioned_contract.to_bytes()?);
+        ret.append(&mut self.print.to_bytes()?);
+        ret.append(&mut self.get_named_arg_size.to_bytes()?);
+        ret.append(&mut self.get_named_arg.to_bytes()?);
+        ret.append(&mut self.remove_contract_user_group.to_bytes()?);
+        ret.append(&mut self.provision_contract_user_group_uref.to_bytes()?);
+        ret.append(&mut self.remove_contract_user_group_urefs.to_bytes()?);
+        ret.append(&mut self.storage.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        HOST_FUNCTION_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let rem = bytes;
+        let (read_value, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (read_value_local, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (write, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (write_local, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (write_and_return_old, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (compare_and_swap, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (delete, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (add, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (counter_inc, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (new_uref, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (load_named_keys, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (load_named_keys_paged, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (ret, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (has_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (put_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (remove_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_caller, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (is_system_caller, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_call_stack, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_blocktime, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_gas_price, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_deploy_hash, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (random_bytes, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (u512_mul_div, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (blake2b, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (keccak256, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (emit_event, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (dictionary_new, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (dictionary_get, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (dictionary_put, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (is_valid_uref, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (revert, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (add_associated_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (remove_associated_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (update_associated_key, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (set_action_threshold, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (set_main_purse, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (register_system_contract, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (create_purse, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (transfer_to_account, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (transfer_from_purse_to_account, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (transfer_from_purse_to_accounts, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (transfer_from_purse_to_purse, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_balance, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_phase, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_system_contract, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_main_purse, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (read_host_buffer, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (create_contract_package_at_hash, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (create_contract_user_group, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (add_contract_version, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (disable_contract_version, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (disable_contract, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (set_non_reentrant, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (call_contract, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (call_contract_read_only, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (call_versioned_contract, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (print, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_named_arg_size, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (get_named_arg, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (remove_contract_user_group, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (provision_contract_user_group_uref, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (remove_contract_user_group_urefs, rem) = HostFunctionCost::from_bytes(rem)?;
+        let (storage, rem) = StorageCosts::from_bytes(rem)?;
+        let host_function_costs = HostFunctionCosts {
+            read_value,
+            read_value_local,
+            write,
+            write_local,
+            write_and_return_old,
+            compare_and_swap,
+            delete,
+            add,
+            counter_inc,
+            new_uref,
+            load_named_keys,
+            load_named_keys_paged,
+            ret,
+            get_key,
+            has_key,
+            put_key,
+            remove_key,
+            get_caller,
+            is_system_caller,
+            get_call_stack,
+            get_blocktime,
+            get_gas_price,
+            get_deploy_hash,
+            random_bytes,
+            u512_mul_div,
+            blake2b,
+            keccak256,
+            emit_event,
+            dictionary_new,
+            dictionary_get,
+            dictionary_put,
+            is_valid_uref,
+            revert,
+            add_associated_key,
+            remove_associated_key,
+            update_associated_key,
+            set_action_threshold,
+            set_main_purse,
+            register_system_contract,
+            create_purse,
+            transfer_to_account,
+            transfer_from_purse_to_account,
+            transfer_from_purse_to_accounts,
+            transfer_from_purse_to_purse,
+            get_balance,
+            get_phase,
+            get_system_contract,
+            get_main_purse,
+            read_host_buffer,
+            create_contract_package_at_hash,
+            create_contract_user_group,
+            add_contract_version,
+            disable_contract_version,
+            disable_contract,
+            set_non_reentrant,
+            call_contract,
+            call_contract_read_only,
+            call_versioned_contract,
+            print,
+            get_named_arg_size,
+            get_named_arg,
+            remove_contract_user_group,
+            provision_contract_user_group_uref,
+            remove_contract_user_group_urefs,
+            storage,
+        };
+        Ok((host_function_costs, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection, num, prelude::*};
+
+    use types::bytesrepr;
+
+    use super::{HostFunctionCosts, HOST_FUNCTION_COSTS_SERIALIZED_LENGTH};
+
+    /// Generates arbitrary costs from their serialized form, since there are too many of them to
+    /// compose individually.
+    pub fn host_function_costs_arb() -> impl Strategy<Value = HostFunctionCosts> {
+        collection::vec(num::u8::ANY, HOST_FUNCTION_COSTS_SERIALIZED_LENGTH).prop_map(|bytes| {
+            bytesrepr::deserialize(bytes).expect("should deserialize host function costs")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::{bytesrepr, U512};
+
+    use super::{gens, HostFunctionCost, HostFunctionCosts, StorageCosts};
+    use crate::gas::Gas;
+
+    #[test]
+    fn should_charge_fixed_cost_without_input() {
+        let cost = HostFunctionCost::new(100, 10);
+        assert_eq!(cost.calculate_gas_cost(&[]), Gas::new(U512::from(100)));
+        assert_eq!(cost.calculate_gas_cost(&[0, 0]), Gas::new(U512::from(100)));
+    }
+
+    #[test]
+    fn should_charge_per_byte_of_all_inputs() {
+        let cost = HostFunctionCost::new(100, 10);
+        assert_eq!(
+            cost.calculate_gas_cost(&[3, 4]),
+            Gas::new(U512::from(100 + 10 * 7))
+        );
+    }
+
+    #[test]
+    fn should_not_overflow() {
+        let cost = HostFunctionCost::new(u32::max_value(), u32::max_value());
+        let expected = U512::from(u32::max_value())
+            + U512::from(u32::max_value()) * U512::from(u32::max_value()) * 2;
+        assert_eq!(
+            cost.calculate_gas_cost(&[u32::max_value(), u32::max_value()]),
+            Gas::new(expected)
+        );
+    }
+
+    #[test]
+    fn should_charge_storage_per_byte_added() {
+        let costs = StorageCosts::new(10, 50);
+        assert_eq!(costs.calculate_write_cost(0), Gas::new(U512::zero()));
+        assert_eq!(costs.calculate_write_cost(7), Gas::new(U512::from(70)));
+    }
+
+    #[test]
+    fn should_refund_percentage_of_storage_cost_per_byte_removed() {
+        let costs = StorageCosts::new(10, 50);
+        assert_eq!(costs.calculate_refund(7), Gas::new(U512::from(35)));
+        assert_eq!(
+            StorageCosts::new(10, 0).calculate_refund(7),
+            Gas::new(U512::zero())
+        );
+    }
+
+    #[test]
+    fn should_not_refund_more_than_storage_cost() {
+        let costs = StorageCosts::new(10, 200);
+        assert_eq!(costs.calculate_refund(7), costs.calculate_write_cost(7));
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let host_function_costs = HostFunctionCosts {
+            put_key: HostFunctionCost::new(1, 2),
+            storage: StorageCosts::new(3, 4),
+            ..HostFunctionCosts::default()
+        };
+        bytesrepr::test_serialization_roundtrip(&HostFunctionCosts::default());
+        bytesrepr::test_serialization_roundtrip(&host_function_costs);
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            host_function_costs in gens::host_function_costs_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&host_function_costs);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+
+use types::{account::AccountHash, Phase, ProtocolVersion};
+
+use crate::logging::structured_message::MessageProperties;
+
+const DEPLOY_HASH_KEY: &str = "deploy_hash";
+const ACCOUNT_KEY: &str = "account";
+const PROTOCOL_VERSION_KEY: &str = "protocol_version";
+const PHASE_KEY: &str = "phase";
+
+thread_local! {
+    static CONTEXT: RefCell<LogContext> = RefCell::new(LogContext::default());
+}
+
+/// Details of the deploy being executed on the current thread, attached as properties to every
+/// log record emitted while they are set.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct LogContext {
+    deploy_hash: Option<String>,
+    account: Option<String>,
+    protocol_version: Option<String>,
+    phase: Option<String>,
+}
+
+/// Restores the previous logging context of the current thread when dropped.
+#[must_use = "the context is cleared as soon as the guard is dropped"]
+pub struct LogContextGuard {
+    previous: LogContext,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.previous);
+        CONTEXT.with(|context| *context.borrow_mut() = previous);
+    }
+}
+
+/// Attaches the given deploy details to all log records emitted on the current thread until the
+/// returned guard is dropped.
+///
+/// Any phase set previously is cleared.
+pub fn set_deploy_context(
+    deploy_hash: &[u8],
+    account: AccountHash,
+    protocol_version: ProtocolVersion,
+) -> LogContextGuard {
+    update(|_| LogContext {
+        deploy_hash: Some(base16::encode_lower(deploy_hash)),
+        account: Some(base16::encode_lower(&account.value())),
+        protocol_version: Some(protocol_version.to_string()),
+        phase: None,
+    })
+}
+
+/// Attaches the given execution phase to all log records emitted on the current thread until the
+/// returned guard is dropped.
+pub fn set_phase(phase: Phase) -> LogContextGuard {
+    update(|context| LogContext {
+        phase: Some(format!("{:?}", phase)),
+        ..context
+    })
+}
+
+fn update<F: FnOnce(LogContext) -> LogContext>(f: F) -> LogContextGuard {
+    CONTEXT.with(|context| {
+        let previous = context.borrow().clone();
+        *context.borrow_mut() = f(previous.clone());
+        LogContextGuard { previous }
+    })
+}
+
+/// Inserts the properties of the current thread's logging context into `properties`.
+pub(crate) fn add_properties(properties: &mut MessageProperties) {
+    CONTEXT.with(|context| {
+        let context = context.borrow();
+        let entries = [
+            (DEPLOY_HASH_KEY, &context.deploy_hash),
+            (ACCOUNT_KEY, &context.account),
+            (PROTOCOL_VERSION_KEY, &context.protocol_version),
+            (PHASE_KEY, &context.phase),
+        ];
+        for (key, value) in entries.iter() {
+            if let Some(value) = value {
+                properties.insert(key.to_string(), value.clone());
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    fn current() -> LogContext {
+        CONTEXT.with(|context| context.borrow().clone())
+    }
+
+    #[test]
+    fn should_set_and_restore_context() {
+        let account = AccountHash::new([1; 32]);
+        {
+            let _deploy_guard = set_deploy_context(&[2; 32], account, ProtocolVersion::V1_0_0);
+            assert_eq!(current().deploy_hash, Some("02".repeat(32)));
+            assert_eq!(current().account, Some("01".repeat(32)));
+            assert_eq!(current().protocol_version, Some("1.0.0".to_string()));
+            assert_eq!(current().phase, None);
+
+            {
+                let _phase_guard = set_phase(Phase::Session);
+                assert_eq!(current().phase, Some("Session".to_string()));
+                assert_eq!(current().deploy_hash, Some("02".repeat(32)));
+            }
+
+            assert_eq!(current().phase, None);
+            assert_eq!(current().deploy_hash, Some("02".repeat(32)));
+        }
+
+        assert_eq!(current(), LogContext::default());
+    }
+
+    #[test]
+    fn should_add_only_set_context_properties() {
+        let _phase_guard = set_phase(Phase::Payment);
+
+        let mut properties = MessageProperties::default();
+        add_properties(&mut properties);
+
+        let json = utils::jsonify(&properties, false);
+        assert!(json.contains(r#""phase":"Payment""#));
+        assert!(!json.contains(DEPLOY_HASH_KEY));
+    }
+}
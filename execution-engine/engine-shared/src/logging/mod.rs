@@ -1,5 +1,6 @@
 //! A logger implementation which outputs log messages from CasperLabs crates to the terminal.
 
+mod context;
 mod settings;
 mod structured_message;
 mod terminal_logger;
@@ -13,6 +14,7 @@ use log::{self, Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
 
 pub use self::terminal_logger::TerminalLogger;
 use crate::newtypes::CorrelationId;
+pub use context::{set_deploy_context, set_phase, LogContextGuard};
 pub use settings::{Settings, Style};
 
 #[doc(hidden)]
@@ -33,7 +33,7 @@ impl Settings {
         self
     }
 
-    /// Sets the logging style to structured or human-readable.
+    /// Sets the logging style to structured, human-readable or JSON.
     pub fn with_style(mut self, value: Style) -> Self {
         self.style = value;
         self
@@ -60,4 +60,6 @@ pub enum Style {
     Structured,
     /// Human-readable log-messages.
     HumanReadable,
+    /// One JSON object per log-message, for ingestion by log aggregators.
+    Json,
 }
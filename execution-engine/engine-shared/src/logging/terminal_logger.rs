@@ -2,9 +2,13 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 
 use log::{Level, LevelFilter, Log, Metadata, Record};
 
-use crate::logging::{
-    structured_message::{MessageId, MessageProperties, StructuredMessage, TimestampRfc3999},
-    Settings, Style, CASPERLABS_METADATA_TARGET, DEFAULT_MESSAGE_KEY, METRIC_METADATA_TARGET,
+use crate::{
+    logging::{
+        context,
+        structured_message::{MessageId, MessageProperties, StructuredMessage, TimestampRfc3999},
+        Settings, Style, CASPERLABS_METADATA_TARGET, DEFAULT_MESSAGE_KEY, METRIC_METADATA_TARGET,
+    },
+    utils,
 };
 
 #[doc(hidden)]
@@ -33,9 +37,10 @@ impl TerminalLogger {
 
         let mut properties = MessageProperties::default();
         let _ = record.key_values().visit(&mut properties);
+        context::add_properties(&mut properties);
 
         let log_line = match self.style {
-            Style::Structured => {
+            Style::Structured | Style::Json => {
                 if record.key_values().count() == 0 {
                     properties.insert(
                         DEFAULT_MESSAGE_KEY.to_string(),
@@ -50,7 +55,10 @@ impl TerminalLogger {
                     message_id,
                     properties,
                 );
-                format!("{}", structured_message)
+                match self.style {
+                    Style::Json => utils::jsonify(&structured_message, false),
+                    _ => format!("{}", structured_message),
+                }
             }
             Style::HumanReadable => {
                 let formatted_properties = properties.get_formatted_message();
@@ -0,0 +1,309 @@
+//! Process-wide execution statistics, rendered in the Prometheus text exposition format.
+//!
+//! The free functions record into a single global registry which is cheap to update from any
+//! thread; [`render`] produces the body served from the engine's `/metrics` endpoint.
+
+use std::{
+    fmt::{self, Write},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+
+use types::U512;
+
+use crate::gas::Gas;
+
+/// The content type of the text produced by [`render`].
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+const DEPLOYS_EXECUTED: &str = "casperlabs_engine_deploys_executed_total";
+const DEPLOYS_EXECUTED_HELP: &str = "Number of deploys executed.";
+const GAS_CONSUMED: &str = "casperlabs_engine_gas_consumed_total";
+const GAS_CONSUMED_HELP: &str = "Gas consumed by executed deploys.";
+const TRIE_READS: &str = "casperlabs_engine_trie_reads_total";
+const TRIE_READS_HELP: &str = "Number of reads from the global state trie.";
+const TRIE_WRITES: &str = "casperlabs_engine_trie_writes_total";
+const TRIE_WRITES_HELP: &str = "Number of writes to the global state trie.";
+const CACHE_HITS: &str = "casperlabs_engine_tracking_copy_cache_hits_total";
+const CACHE_HITS_HELP: &str = "Number of tracking copy lookups served from its cache.";
+const CACHE_MISSES: &str = "casperlabs_engine_tracking_copy_cache_misses_total";
+const CACHE_MISSES_HELP: &str = "Number of tracking copy lookups which fell through to storage.";
+const CACHE_HIT_RATIO: &str = "casperlabs_engine_tracking_copy_cache_hit_ratio";
+const CACHE_HIT_RATIO_HELP: &str = "Fraction of tracking copy lookups served from its cache.";
+const COMMIT_DURATION: &str = "casperlabs_engine_commit_duration_seconds";
+const COMMIT_DURATION_HELP: &str = "Time taken to commit effects to global state.";
+
+/// Upper bounds in seconds of the commit latency histogram buckets.
+const COMMIT_DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+lazy_static! {
+    static ref METRICS: Metrics = Metrics::new();
+}
+
+/// Records the execution of a deploy which consumed `gas`.
+///
+/// Gas beyond `u64::MAX` is clamped, as counters are held as `u64`s.
+pub fn record_deploy_executed(gas: Gas) {
+    let gas = gas.value();
+    let gas = if gas > U512::from(u64::max_value()) {
+        u64::max_value()
+    } else {
+        gas.as_u64()
+    };
+    METRICS.record_deploy_executed(gas)
+}
+
+/// Records a read from the global state trie.
+pub fn record_trie_read() {
+    METRICS.trie_reads.inc()
+}
+
+/// Records a write to the global state trie.
+pub fn record_trie_write() {
+    METRICS.trie_writes.inc()
+}
+
+/// Records a tracking copy lookup, and whether it was served from the cache.
+pub fn record_cache_lookup(hit: bool) {
+    METRICS.record_cache_lookup(hit)
+}
+
+/// Records the time taken to commit a set of effects to global state.
+pub fn record_commit(duration: Duration) {
+    METRICS.commit_duration.observe(duration)
+}
+
+/// Renders the current values of all metrics in the Prometheus text exposition format.
+pub fn render() -> String {
+    METRICS.render()
+}
+
+#[derive(Default)]
+struct Counter(AtomicU64);
+
+impl Counter {
+    fn inc(&self) {
+        self.add(1)
+    }
+
+    fn add(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A histogram of durations.  Bucket counts are cumulative, as Prometheus expects them.
+struct DurationHistogram {
+    bounds: &'static [f64],
+    buckets: Vec<Counter>,
+    count: Counter,
+    sum_nanos: Counter,
+}
+
+impl DurationHistogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        DurationHistogram {
+            bounds,
+            buckets: bounds.iter().map(|_| Counter::default()).collect(),
+            count: Counter::default(),
+            sum_nanos: Counter::default(),
+        }
+    }
+
+    fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (bound, bucket) in self.bounds.iter().zip(&self.buckets) {
+            if seconds <= *bound {
+                bucket.inc();
+            }
+        }
+        self.count.inc();
+        let nanos = duration.as_nanos();
+        self.sum_nanos.add(if nanos > u128::from(u64::max_value()) {
+            u64::max_value()
+        } else {
+            nanos as u64
+        });
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) -> fmt::Result {
+        write_header(out, name, help, "histogram")?;
+        for (bound, bucket) in self.bounds.iter().zip(&self.buckets) {
+            writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, bucket.get())?;
+        }
+        let count = self.count.get();
+        writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, count)?;
+        let sum = Duration::from_nanos(self.sum_nanos.get()).as_secs_f64();
+        writeln!(out, "{}_sum {}", name, sum)?;
+        writeln!(out, "{}_count {}", name, count)
+    }
+}
+
+struct Metrics {
+    deploys_executed: Counter,
+    gas_consumed: Counter,
+    trie_reads: Counter,
+    trie_writes: Counter,
+    cache_hits: Counter,
+    cache_misses: Counter,
+    commit_duration: DurationHistogram,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Metrics {
+            deploys_executed: Counter::default(),
+            gas_consumed: Counter::default(),
+            trie_reads: Counter::default(),
+            trie_writes: Counter::default(),
+            cache_hits: Counter::default(),
+            cache_misses: Counter::default(),
+            commit_duration: DurationHistogram::new(&COMMIT_DURATION_BUCKETS),
+        }
+    }
+
+    fn record_deploy_executed(&self, gas: u64) {
+        self.deploys_executed.inc();
+        self.gas_consumed.add(gas);
+    }
+
+    fn record_cache_lookup(&self, hit: bool) {
+        if hit {
+            self.cache_hits.inc()
+        } else {
+            self.cache_misses.inc()
+        }
+    }
+
+    fn cache_hit_ratio(&self) -> f64 {
+        let hits = self.cache_hits.get();
+        let lookups = hits + self.cache_misses.get();
+        if lookups == 0 {
+            0.0
+        } else {
+            hits as f64 / lookups as f64
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        self.write_all(&mut out)
+            .expect("writing to a String should not fail");
+        out
+    }
+
+    fn write_all(&self, out: &mut String) -> fmt::Result {
+        write_counter(
+            out,
+            DEPLOYS_EXECUTED,
+            DEPLOYS_EXECUTED_HELP,
+            &self.deploys_executed,
+        )?;
+        write_counter(out, GAS_CONSUMED, GAS_CONSUMED_HELP, &self.gas_consumed)?;
+        write_counter(out, TRIE_READS, TRIE_READS_HELP, &self.trie_reads)?;
+        write_counter(out, TRIE_WRITES, TRIE_WRITES_HELP, &self.trie_writes)?;
+        write_counter(out, CACHE_HITS, CACHE_HITS_HELP, &self.cache_hits)?;
+        write_counter(out, CACHE_MISSES, CACHE_MISSES_HELP, &self.cache_misses)?;
+        write_header(out, CACHE_HIT_RATIO, CACHE_HIT_RATIO_HELP, "gauge")?;
+        writeln!(out, "{} {}", CACHE_HIT_RATIO, self.cache_hit_ratio())?;
+        self.commit_duration
+            .render(out, COMMIT_DURATION, COMMIT_DURATION_HELP)
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, metric_type: &str) -> fmt::Result {
+    writeln!(out, "# HELP {} {}", name, help)?;
+    writeln!(out, "# TYPE {} {}", name, metric_type)
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, counter: &Counter) -> fmt::Result {
+    write_header(out, name, help, "counter")?;
+    writeln!(out, "{} {}", name, counter.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(rendered: &str, name: &str) -> String {
+        rendered
+            .lines()
+            .find_map(|line| {
+                let mut parts = line.splitn(2, ' ');
+                if parts.next() == Some(name) {
+                    parts.next().map(str::to_string)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| panic!("should have sample for {}", name))
+    }
+
+    #[test]
+    fn should_render_counters() {
+        let metrics = Metrics::new();
+        metrics.record_deploy_executed(10);
+        metrics.record_deploy_executed(32);
+        metrics.trie_reads.inc();
+        metrics.trie_writes.add(3);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("# TYPE casperlabs_engine_deploys_executed_total counter"));
+        assert_eq!(sample(&rendered, DEPLOYS_EXECUTED), "2");
+        assert_eq!(sample(&rendered, GAS_CONSUMED), "42");
+        assert_eq!(sample(&rendered, TRIE_READS), "1");
+        assert_eq!(sample(&rendered, TRIE_WRITES), "3");
+    }
+
+    #[test]
+    fn should_render_cache_hit_ratio() {
+        let metrics = Metrics::new();
+        assert_eq!(sample(&metrics.render(), CACHE_HIT_RATIO), "0");
+
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(false);
+
+        let rendered = metrics.render();
+        assert_eq!(sample(&rendered, CACHE_HITS), "3");
+        assert_eq!(sample(&rendered, CACHE_MISSES), "1");
+        assert_eq!(sample(&rendered, CACHE_HIT_RATIO), "0.75");
+    }
+
+    #[test]
+    fn should_render_cumulative_histogram_buckets() {
+        let metrics = Metrics::new();
+        metrics.commit_duration.observe(Duration::from_millis(250));
+        metrics.commit_duration.observe(Duration::from_secs(2));
+        metrics.commit_duration.observe(Duration::from_secs(60));
+
+        let rendered = metrics.render();
+        let bucket = |le: &str| {
+            sample(
+                &rendered,
+                &format!("{}_bucket{{le=\"{}\"}}", COMMIT_DURATION, le),
+            )
+        };
+        assert_eq!(bucket("0.1"), "0");
+        assert_eq!(bucket("0.25"), "1");
+        assert_eq!(bucket("2.5"), "2");
+        assert_eq!(bucket("10"), "2");
+        assert_eq!(bucket("+Inf"), "3");
+        assert_eq!(
+            sample(&rendered, &format!("{}_count", COMMIT_DURATION)),
+            "3"
+        );
+        assert_eq!(
+            sample(&rendered, &format!("{}_sum", COMMIT_DURATION)),
+            "62.25"
+        );
+    }
+}
@@ -24,6 +24,8 @@ pub fn wasm_costs_mock() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        verify_ed25519: 10_000,
+        verify_secp256k1: 10_000,
     }
 }
 
@@ -39,5 +41,7 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        verify_ed25519: 0,
+        verify_secp256k1: 0,
     }
 }
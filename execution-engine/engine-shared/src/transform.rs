@@ -59,6 +59,12 @@ pub enum Transform {
     AddUInt256(U256),
     AddUInt512(U512),
     AddKeys(NamedKeys),
+    /// Appends the items of the contained `CLValue`, which must be of type `List(T)`, to a stored
+    /// list of the same type.
+    AddList(CLValue),
+    /// Removes the value stored under the key.  There is no value to apply it to, so global state
+    /// handles it separately from the other transforms.
+    Delete,
     Failure(Error),
 }
 
@@ -90,6 +96,7 @@ from_try_from_impl!(U128, AddUInt128);
 from_try_from_impl!(U256, AddUInt256);
 from_try_from_impl!(U512, AddUInt512);
 from_try_from_impl!(NamedKeys, AddKeys);
+from_try_from_impl!(CLValue, AddList);
 from_try_from_impl!(Error, Failure);
 
 /// Attempts a wrapping addition of `to_add` to `stored_value`, assuming `stored_value` is
@@ -135,7 +142,136 @@ where
     Ok(StoredValue::CLValue(CLValue::from_t(result)?))
 }
 
+/// Attempts to append the items of `to_append` to the list held in `stored_value`.  Both must be
+/// `CLValue`s of the same `List(T)` type.
+fn list_append(stored_value: StoredValue, to_append: CLValue) -> Result<StoredValue, Error> {
+    let cl_value = CLValue::try_from(stored_value)?;
+    let concatenated = concat_lists(cl_value, to_append)?;
+    Ok(StoredValue::CLValue(concatenated))
+}
+
+/// Concatenates two `CLValue` lists of the same type without deserializing their items.
+///
+/// A serialized list is a `u32` item count followed by the serialized items, so the result is the
+/// summed count followed by the items of `head` and then those of `tail`.
+fn concat_lists(head: CLValue, tail: CLValue) -> Result<CLValue, Error> {
+    match head.cl_type() {
+        CLType::List(_) if head.cl_type() == tail.cl_type() => (),
+        _ => {
+            let expected = format!("{:?}", tail.cl_type());
+            let found = format!("{:?}", head.cl_type());
+            return Err(TypeMismatch::new(expected, found).into());
+        }
+    }
+
+    let (cl_type, head_bytes) = head.destructure();
+    let (_, tail_bytes) = tail.destructure();
+    let (head_count, head_items) = u32::from_bytes(&head_bytes).map_err(Error::Serialization)?;
+    let (tail_count, tail_items) = u32::from_bytes(&tail_bytes).map_err(Error::Serialization)?;
+    let count = head_count
+        .checked_add(tail_count)
+        .ok_or(Error::Serialization(bytesrepr::Error::OutOfMemory))?;
+
+    let mut bytes = count.to_bytes().map_err(Error::Serialization)?;
+    bytes.reserve(head_items.len() + tail_items.len());
+    bytes.extend_from_slice(head_items);
+    bytes.extend_from_slice(tail_items);
+    Ok(CLValue::from_components(cl_type, bytes))
+}
+
+/// A disagreement between composing `Transform`s and applying them one at a time, as reported by
+/// [`Transform::check_composition_laws`].
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CompositionLawViolation {
+    /// Composing `transform` with `Identity` on one side gave `composed` rather than `transform`.
+    Identity {
+        transform: Transform,
+        composed: Transform,
+    },
+    /// Applying [`Transform::compose`] of `transforms` gave `composed` rather than `sequential`,
+    /// the result of applying them in turn.
+    Composition {
+        transforms: Vec<Transform>,
+        sequential: StoredValue,
+        composed: Result<StoredValue, Error>,
+    },
+    /// Composing `transforms` from the right and applying the result gave `right`, whereas
+    /// composing them from the left gave `left`.
+    Associativity {
+        transforms: Vec<Transform>,
+        left: Result<StoredValue, Error>,
+        right: Result<StoredValue, Error>,
+    },
+}
+
 impl Transform {
+    /// Combines `transforms` into a single `Transform` by adding them together in order.
+    pub fn compose<I: IntoIterator<Item = Transform>>(transforms: I) -> Transform {
+        transforms.into_iter().fold(Transform::Identity, Add::add)
+    }
+
+    /// Checks that combining `transforms` with `+` is consistent with applying them to `value` one
+    /// after another.
+    ///
+    /// `Identity` must leave every transform unchanged when added on either side.  If applying
+    /// `transforms` in turn to `value` succeeds, then applying their composition must give the same
+    /// result whether they are added from the left or from the right.  Sequences which fail part
+    /// way through are not otherwise constrained, since a later `Write` can mask the failure once
+    /// composed.
+    pub fn check_composition_laws(
+        value: &StoredValue,
+        transforms: &[Transform],
+    ) -> Result<(), CompositionLawViolation> {
+        for transform in transforms {
+            let left_identity = Transform::Identity + transform.clone();
+            let right_identity = transform.clone() + Transform::Identity;
+            for composed in [left_identity, right_identity].iter() {
+                if composed != transform {
+                    return Err(CompositionLawViolation::Identity {
+                        transform: transform.clone(),
+                        composed: composed.clone(),
+                    });
+                }
+            }
+        }
+
+        let sequential = transforms
+            .iter()
+            .cloned()
+            .try_fold(value.clone(), |value, transform| transform.apply(value));
+        let sequential = match sequential {
+            Ok(sequential) => sequential,
+            Err(_) => return Ok(()),
+        };
+
+        let left = Transform::compose(transforms.iter().cloned()).apply(value.clone());
+        if left.as_ref() != Ok(&sequential) {
+            return Err(CompositionLawViolation::Composition {
+                transforms: transforms.to_vec(),
+                sequential,
+                composed: left,
+            });
+        }
+
+        let right = transforms
+            .iter()
+            .rev()
+            .cloned()
+            .fold(Transform::Identity, |composed, transform| {
+                transform + composed
+            })
+            .apply(value.clone());
+        if right != left {
+            return Err(CompositionLawViolation::Associativity {
+                transforms: transforms.to_vec(),
+                left,
+                right,
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
             Transform::Identity => Ok(stored_value),
@@ -170,6 +306,12 @@ impl Transform {
                     Err(TypeMismatch::new(expected, found).into())
                 }
             },
+            Transform::AddList(to_append) => list_append(stored_value, to_append),
+            Transform::Delete => {
+                let expected = "a transform yielding a value".to_string();
+                let found = "Delete".to_string();
+                Err(TypeMismatch::new(expected, found).into())
+            }
             Transform::Failure(error) => Err(error),
         }
     }
@@ -216,6 +358,7 @@ impl Add for Transform {
             (Transform::Identity, b) => b,
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
+            (_, Transform::Delete) => Transform::Delete,
             (_, b @ Transform::Write(_)) => b,
             (Transform::Write(v), b) => {
                 // second transform changes value being written
@@ -224,6 +367,10 @@ impl Add for Transform {
                     Ok(new_value) => Transform::Write(new_value),
                 }
             }
+            // only a write can follow a delete, as there is no value left to add to
+            (Transform::Delete, b) => Transform::Failure(
+                TypeMismatch::new("Write".to_owned(), format!("{:?}", b)).into(),
+            ),
             (Transform::AddInt32(i), b) => match b {
                 Transform::AddInt32(j) => Transform::AddInt32(i.wrapping_add(j)),
                 Transform::AddUInt64(j) => Transform::AddUInt64(j.wrapping_add(i as u64)),
@@ -256,6 +403,15 @@ impl Add for Transform {
                     TypeMismatch::new("AddKeys".to_owned(), format!("{:?}", other)).into(),
                 ),
             },
+            (Transform::AddList(head), b) => match b {
+                Transform::AddList(tail) => match concat_lists(head, tail) {
+                    Ok(items) => Transform::AddList(items),
+                    Err(error) => Transform::Failure(error),
+                },
+                other => Transform::Failure(
+                    TypeMismatch::new("AddList".to_owned(), format!("{:?}", other)).into(),
+                ),
+            },
         }
     }
 }
@@ -279,10 +435,21 @@ impl Default for Transform {
 }
 
 pub mod gens {
+    use std::fmt::Debug;
+
     use proptest::{collection::vec, prelude::*};
 
+    use types::{
+        bytesrepr::ToBytes,
+        gens::{named_keys_arb, u128_arb, u256_arb, u512_arb},
+        CLTyped, CLValue,
+    };
+
     use super::Transform;
-    use crate::stored_value::gens::stored_value_arb;
+    use crate::{
+        account::gens::account_arb,
+        stored_value::{gens::stored_value_arb, StoredValue},
+    };
 
     pub fn transform_arb() -> impl Strategy<Value = Transform> {
         prop_oneof![
@@ -301,18 +468,115 @@ pub mod gens {
                 buf.copy_from_slice(&u);
                 Transform::AddUInt512(buf.into())
             }),
+            vec(any::<u64>(), 0..5).prop_map(|items| {
+                Transform::AddList(CLValue::from_t(items).expect("should create CLValue"))
+            }),
+            Just(Transform::Delete),
         ]
     }
+
+    /// Returns sequences of at most `max_len` arbitrary transforms, which in general can't all be
+    /// applied to any one value.
+    pub fn transforms_arb(max_len: usize) -> impl Strategy<Value = Vec<Transform>> {
+        vec(transform_arb(), 0..=max_len)
+    }
+
+    /// Returns a value along with at most `max_len` transforms which can be applied to it one after
+    /// another without error.
+    ///
+    /// Numeric transforms always have the same width as the value they apply to.
+    pub fn applicable_transforms_arb(
+        max_len: usize,
+    ) -> impl Strategy<Value = (StoredValue, Vec<Transform>)> {
+        prop_oneof![
+            numeric_transforms_arb(any::<i32>(), max_len),
+            numeric_transforms_arb(any::<u64>(), max_len),
+            numeric_transforms_arb(u128_arb(), max_len),
+            numeric_transforms_arb(u256_arb(), max_len),
+            numeric_transforms_arb(u512_arb(), max_len),
+            list_transforms_arb(max_len),
+            account_transforms_arb(max_len),
+            (
+                stored_value_arb(),
+                vec(
+                    prop_oneof![
+                        Just(Transform::Identity),
+                        stored_value_arb().prop_map(Transform::Write),
+                    ],
+                    0..=max_len
+                )
+            ),
+        ]
+    }
+
+    fn numeric_transforms_arb<T, S>(
+        number_arb: S,
+        max_len: usize,
+    ) -> impl Strategy<Value = (StoredValue, Vec<Transform>)>
+    where
+        T: CLTyped + ToBytes + Into<Transform> + Debug + Clone,
+        S: Strategy<Value = T> + Clone,
+    {
+        let transform = prop_oneof![
+            Just(Transform::Identity),
+            number_arb
+                .clone()
+                .prop_map(|number| Transform::Write(stored_number(number))),
+            number_arb.clone().prop_map(Into::<Transform>::into),
+        ];
+        (
+            number_arb.prop_map(stored_number),
+            vec(transform, 0..=max_len),
+        )
+    }
+
+    fn stored_number<T: CLTyped + ToBytes>(number: T) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(number).expect("should create CLValue"))
+    }
+
+    fn list_transforms_arb(max_len: usize) -> impl Strategy<Value = (StoredValue, Vec<Transform>)> {
+        let list_arb = || {
+            vec(any::<u64>(), 0..5)
+                .prop_map(|items| CLValue::from_t(items).expect("should create CLValue"))
+        };
+        let transform = prop_oneof![
+            Just(Transform::Identity),
+            list_arb().prop_map(|list| Transform::Write(StoredValue::CLValue(list))),
+            list_arb().prop_map(Transform::AddList),
+        ];
+        (
+            list_arb().prop_map(StoredValue::CLValue),
+            vec(transform, 0..=max_len),
+        )
+    }
+
+    fn account_transforms_arb(
+        max_len: usize,
+    ) -> impl Strategy<Value = (StoredValue, Vec<Transform>)> {
+        let transform = prop_oneof![
+            Just(Transform::Identity),
+            account_arb().prop_map(|account| Transform::Write(StoredValue::Account(account))),
+            named_keys_arb(3).prop_map(Transform::AddKeys),
+        ];
+        (
+            account_arb().prop_map(StoredValue::Account),
+            vec(transform, 0..=max_len),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use num::{Bounded, Num};
+    use proptest::prelude::*;
 
     use types::{account::AccountHash, AccessRights, ContractWasm, Key, URef, U128, U256, U512};
 
     use super::*;
-    use crate::account::{Account, ActionThresholds, AssociatedKeys};
+    use crate::{
+        account::{Account, ActionThresholds, AssociatedKeys},
+        stored_value::gens::stored_value_arb,
+    };
     use std::collections::BTreeMap;
 
     const ZERO_ARRAY: [u8; 32] = [0; 32];
@@ -746,4 +1010,133 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    fn list_value<T: CLTyped + ToBytes>(items: Vec<T>) -> CLValue {
+        CLValue::from_t(items).expect("should create CLValue")
+    }
+
+    #[test]
+    fn add_list_should_append_items() {
+        let stored = StoredValue::CLValue(list_value(vec![ONE_U64, MAX_U64]));
+        let transform = Transform::AddList(list_value(vec![ZERO_U64]));
+
+        let result = transform.apply(stored).expect("should append");
+        let items: Vec<u64> = CLValue::try_from(result)
+            .expect("should be CLValue")
+            .into_t()
+            .expect("should parse to Vec<u64>");
+        assert_eq!(items, vec![ONE_U64, MAX_U64, ZERO_U64]);
+    }
+
+    #[test]
+    fn add_list_should_append_to_empty_list() {
+        let stored = StoredValue::CLValue(list_value(Vec::<String>::new()));
+        let transform = Transform::AddList(list_value(vec![TEST_STR.to_string()]));
+
+        let result = transform.apply(stored).expect("should append");
+        let items: Vec<String> = CLValue::try_from(result)
+            .expect("should be CLValue")
+            .into_t()
+            .expect("should parse to Vec<String>");
+        assert_eq!(items, vec![TEST_STR.to_string()]);
+    }
+
+    #[test]
+    fn add_list_with_mismatched_types_should_fail() {
+        let transform = Transform::AddList(list_value(vec![ONE_U64]));
+
+        let other_list = StoredValue::CLValue(list_value(vec![ONE_U32]));
+        match transform.clone().apply(other_list) {
+            Err(Error::TypeMismatch(_)) => (),
+            other => panic!("expected TypeMismatch error, got {:?}", other),
+        }
+
+        let not_a_list = StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap());
+        match transform.apply(not_a_list) {
+            Err(Error::TypeMismatch(_)) => (),
+            other => panic!("expected TypeMismatch error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn add_list_transforms_should_combine() {
+        let first = Transform::AddList(list_value(vec![ZERO_U64]));
+        let second = Transform::AddList(list_value(vec![ONE_U64, MAX_U64]));
+        assert_eq!(
+            first + second,
+            Transform::AddList(list_value(vec![ZERO_U64, ONE_U64, MAX_U64]))
+        );
+
+        let write = Transform::Write(StoredValue::CLValue(list_value(vec![MAX_U64])));
+        let append = Transform::AddList(list_value(vec![ONE_U64]));
+        assert_eq!(
+            write + append,
+            Transform::Write(StoredValue::CLValue(list_value(vec![MAX_U64, ONE_U64])))
+        );
+
+        let mismatched = Transform::AddList(list_value(vec![ONE_U64])) + Transform::AddInt32(1);
+        assert!(matches!(
+            mismatched,
+            Transform::Failure(Error::TypeMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn delete_should_supersede_earlier_transforms() {
+        let write = Transform::Write(StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap()));
+        assert_eq!(write.clone() + Transform::Delete, Transform::Delete);
+        assert_eq!(
+            Transform::AddUInt64(ONE_U64) + Transform::Delete,
+            Transform::Delete
+        );
+        assert_eq!(Transform::Delete + write.clone(), write);
+        assert_eq!(Transform::Delete + Transform::Identity, Transform::Delete);
+
+        let added = Transform::Delete + Transform::AddUInt64(ONE_U64);
+        assert!(matches!(added, Transform::Failure(Error::TypeMismatch(_))));
+
+        let value = StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap());
+        assert!(Transform::Delete.apply(value).is_err());
+    }
+
+    #[test]
+    fn compose_should_add_transforms_in_order() {
+        assert_eq!(Transform::compose(vec![]), Transform::Identity);
+
+        let write = Transform::Write(StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap()));
+        let composed = Transform::compose(vec![Transform::AddUInt64(MAX_U64), write.clone()]);
+        assert_eq!(composed, write);
+
+        let composed = Transform::compose(vec![write, Transform::AddUInt64(ONE_U64)]);
+        let expected = Transform::Write(StoredValue::CLValue(CLValue::from_t(2u64).unwrap()));
+        assert_eq!(composed, expected);
+    }
+
+    #[test]
+    fn composition_laws_should_not_constrain_failing_sequences() {
+        let value = StoredValue::CLValue(CLValue::from_t(TEST_STR.to_string()).unwrap());
+        let write = Transform::Write(StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap()));
+        let transforms = vec![Transform::AddUInt64(ONE_U64), write];
+        assert_eq!(
+            Transform::check_composition_laws(&value, &transforms),
+            Ok(())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn applicable_transforms_should_obey_composition_laws(
+            (value, transforms) in gens::applicable_transforms_arb(5)
+        ) {
+            prop_assert_eq!(Transform::check_composition_laws(&value, &transforms), Ok(()));
+        }
+
+        #[test]
+        fn single_transforms_should_obey_composition_laws(
+            value in stored_value_arb(),
+            transform in gens::transform_arb()
+        ) {
+            prop_assert_eq!(Transform::check_composition_laws(&value, &[transform]), Ok(()));
+        }
+    }
 }
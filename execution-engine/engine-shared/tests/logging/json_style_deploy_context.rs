@@ -0,0 +1,56 @@
+use log::{Level, LevelFilter, Record};
+use serde_json::Value;
+
+use casperlabs_engine_shared::logging::{self, Settings, Style, TerminalLogger};
+use types::{account::AccountHash, Phase, ProtocolVersion};
+
+const LOG_MSG_TARGET: &str = "casperlabs_logging";
+const MESSAGE: &str = "json style test message";
+
+fn prepare_json_log_line() -> Value {
+    let settings = Settings::new(LevelFilter::Info).with_style(Style::Json);
+    let logger = TerminalLogger::new(&settings);
+    let log_line = logger
+        .prepare_log_line(
+            &Record::builder()
+                .target(LOG_MSG_TARGET)
+                .level(Level::Info)
+                .args(format_args!("{}", MESSAGE))
+                .build(),
+        )
+        .expect("should prepare log line");
+    serde_json::from_str(&log_line).expect("log line should be a single JSON object")
+}
+
+#[test]
+fn should_log_json_without_deploy_context() {
+    let log_line = prepare_json_log_line();
+
+    assert_eq!(log_line["log_level"], "Info");
+    assert_eq!(log_line["description"], MESSAGE);
+    assert_eq!(log_line["properties"]["deploy_hash"], Value::Null);
+    assert_eq!(log_line["properties"]["phase"], Value::Null);
+}
+
+#[test]
+fn should_log_json_with_deploy_context() {
+    let deploy_hash = [4u8; 32];
+    let account = AccountHash::new([3u8; 32]);
+
+    let log_line = {
+        let _deploy_context =
+            logging::set_deploy_context(&deploy_hash, account, ProtocolVersion::V1_0_0);
+        let _phase = logging::set_phase(Phase::Session);
+        prepare_json_log_line()
+    };
+
+    let properties = &log_line["properties"];
+    assert_eq!(properties["deploy_hash"], "04".repeat(32));
+    assert_eq!(properties["account"], "03".repeat(32));
+    assert_eq!(properties["protocol_version"], "1.0.0");
+    assert_eq!(properties["phase"], "Session");
+
+    // the context is dropped along with its guards
+    let log_line = prepare_json_log_line();
+    assert_eq!(log_line["properties"]["deploy_hash"], Value::Null);
+}
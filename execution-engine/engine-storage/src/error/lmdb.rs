@@ -6,17 +6,24 @@ use lmdb as lmdb_external;
 use types::bytesrepr;
 
 use super::in_memory;
+use crate::snapshot;
 
 #[derive(Debug, Clone, Fail, PartialEq, Eq)]
 pub enum Error {
     #[fail(display = "{}", _0)]
     Lmdb(#[fail(cause)] lmdb_external::Error),
 
+    #[fail(display = "RocksDB error: {}", _0)]
+    RocksDb(String),
+
     #[fail(display = "{}", _0)]
     BytesRepr(#[fail(cause)] bytesrepr::Error),
 
     #[fail(display = "Another thread panicked while holding a lock")]
     Poison,
+
+    #[fail(display = "{}", _0)]
+    Snapshot(#[fail(cause)] snapshot::Error),
 }
 
 impl wasmi::HostError for Error {}
@@ -27,6 +34,12 @@ impl From<lmdb_external::Error> for Error {
     }
 }
 
+impl From<rocksdb::Error> for Error {
+    fn from(error: rocksdb::Error) -> Self {
+        Error::RocksDb(error.to_string())
+    }
+}
+
 impl From<bytesrepr::Error> for Error {
     fn from(error: bytesrepr::Error) -> Self {
         Error::BytesRepr(error)
@@ -47,3 +60,12 @@ impl From<in_memory::Error> for Error {
         }
     }
 }
+
+impl From<snapshot::Error> for Error {
+    fn from(error: snapshot::Error) -> Self {
+        match error {
+            snapshot::Error::BytesRepr(error) => Error::BytesRepr(error),
+            error => Error::Snapshot(error),
+        }
+    }
+}
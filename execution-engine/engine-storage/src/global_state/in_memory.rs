@@ -10,7 +10,9 @@ use types::{Key, ProtocolVersion};
 
 use crate::{
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        commit, commit_batch, prune, CommitResult, PruneResult, StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
     store::Store,
@@ -18,10 +20,12 @@ use crate::{
         in_memory::{InMemoryEnvironment, InMemoryReadTransaction},
         Transaction, TransactionSource,
     },
-    trie::{operations::create_hashed_empty_trie, Trie},
+    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, ReadResult, WriteResult},
+        operations::{
+            self, missing_trie_keys, put_trie, read, read_with_proof, ReadResult, WriteResult,
+        },
     },
 };
 
@@ -137,6 +141,33 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -173,6 +204,19 @@ impl StateProvider for InMemoryGlobalState {
         Ok(commit_result)
     }
 
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error> {
+        commit_batch::<InMemoryEnvironment, InMemoryTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            batch,
+        )
+    }
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -197,6 +241,64 @@ impl StateProvider for InMemoryGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let mut retained_roots = retained_roots.to_vec();
+        retained_roots.push(self.empty_root_hash);
+        prune::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &retained_roots,
+        )
+    }
+
+    fn read_trie(
+        &self,
+        _correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, trie_key)?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie: &Trie<Key, StoredValue>,
+    ) -> Result<Blake2bHash, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let trie_hash = put_trie::<Key, StoredValue, _, InMemoryTrieStore, Self::Error>(
+            correlation_id,
+            &mut txn,
+            &self.trie_store,
+            trie,
+        )?;
+        txn.commit()?;
+        Ok(trie_hash)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let missing = missing_trie_keys::<Key, StoredValue, _, InMemoryTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            &self.trie_store,
+            trie_keys,
+        )?;
+        txn.commit()?;
+        Ok(missing)
+    }
 }
 
 #[cfg(test)]
@@ -343,6 +445,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_deletes_value() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let (state, root_hash) = create_test_state();
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(test_pairs[0].key, Transform::Delete);
+        let deleted_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let deleted_checkout = state.checkout(deleted_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            deleted_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            deleted_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        // The state is the same as if the deleted value had never been written.
+        let (_, expected_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[(test_pairs[1].key, test_pairs[1].value.clone())],
+        )
+        .unwrap();
+        assert_eq!(deleted_hash, expected_hash);
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        assert_eq!(
+            Some(test_pairs[0].value.clone()),
+            original_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn commit_ignores_delete_of_missing_value() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(create_test_pairs_updated()[2].key, Transform::Delete);
+        match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => assert_eq!(state_root, root_hash),
+            _ => panic!("commit failed"),
+        }
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -353,4 +512,91 @@ mod tests {
         let (_, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
         assert_eq!(expected_bytes, root_hash.to_vec())
     }
+
+    fn commit_test_pairs_updated(
+        state: &InMemoryGlobalState,
+        root_hash: Blake2bHash,
+    ) -> Blake2bHash {
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        match state
+            .commit(CorrelationId::new(), root_hash, effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        }
+    }
+
+    #[test]
+    fn prune_removes_state_which_is_not_retained() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let updated_hash = commit_test_pairs_updated(&state, root_hash);
+
+        match state.prune(correlation_id, &[updated_hash]).unwrap() {
+            PruneResult::Success { pruned, .. } => assert!(pruned > 0),
+            PruneResult::RootNotFound(root) => panic!("root not found: {}", root),
+        }
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root()).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs_updated().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn prune_keeps_all_retained_states() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let updated_hash = commit_test_pairs_updated(&state, root_hash);
+
+        state
+            .prune(correlation_id, &[root_hash, updated_hash])
+            .unwrap();
+
+        // Pruning again with the same roots has nothing left to remove.
+        let retained = match state
+            .prune(correlation_id, &[root_hash, updated_hash])
+            .unwrap()
+        {
+            PruneResult::Success {
+                pruned: 0,
+                retained,
+            } => retained,
+            result => panic!("unexpected prune result: {}", result),
+        };
+        assert!(retained > 0);
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert!(state.checkout(updated_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn prune_fails_if_unknown_hash_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        assert_eq!(
+            PruneResult::RootNotFound(fake_hash),
+            state.prune(correlation_id, &[fake_hash]).unwrap()
+        );
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
 }
@@ -10,15 +10,17 @@ use types::{Key, ProtocolVersion};
 
 use crate::{
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        commit, commit_batch, prune, CommitResult, PruneResult, StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     store::Store,
     transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-    trie::{operations::create_hashed_empty_trie, Trie},
+    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
     trie_store::{
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        operations::{missing_trie_keys, put_trie, read, read_with_proof, ReadResult},
     },
 };
 
@@ -98,6 +100,33 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for LmdbGlobalState {
@@ -133,6 +162,19 @@ impl StateProvider for LmdbGlobalState {
         Ok(commit_result)
     }
 
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error> {
+        commit_batch::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            batch,
+        )
+    }
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -157,6 +199,64 @@ impl StateProvider for LmdbGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let mut retained_roots = retained_roots.to_vec();
+        retained_roots.push(self.empty_root_hash);
+        prune::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &retained_roots,
+        )
+    }
+
+    fn read_trie(
+        &self,
+        _correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, trie_key)?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie: &Trie<Key, StoredValue>,
+    ) -> Result<Blake2bHash, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let trie_hash = put_trie::<Key, StoredValue, _, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &mut txn,
+            &self.trie_store,
+            trie,
+        )?;
+        txn.commit()?;
+        Ok(trie_hash)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let missing = missing_trie_keys::<Key, StoredValue, _, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            &self.trie_store,
+            trie_keys,
+        )?;
+        txn.commit()?;
+        Ok(missing)
+    }
 }
 
 #[cfg(test)]
@@ -339,4 +439,181 @@ mod tests {
                 .unwrap()
         );
     }
+
+    fn create_test_effects_chained() -> AdditiveMap<Key, Transform> {
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            create_test_pairs_updated()[2].key,
+            Transform::Write(StoredValue::CLValue(CLValue::from_t(4_i32).unwrap())),
+        );
+        effects.insert(create_test_pairs_updated()[0].key, Transform::Delete);
+        effects
+    }
+
+    #[test]
+    fn commit_batch_commits_chained_effects() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        // The post state hashes are the same as when committing one set of effects at a time.
+        let (expected_state, _) = create_test_state();
+        let first_hash = commit_test_pairs_updated(&expected_state, root_hash);
+        let second_hash = match expected_state
+            .commit(correlation_id, first_hash, create_test_effects_chained())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let first_effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        let batch = vec![
+            (root_hash, first_effects),
+            (first_hash, create_test_effects_chained()),
+        ];
+        let state_roots: Vec<Blake2bHash> = state
+            .commit_batch(correlation_id, batch)
+            .unwrap()
+            .into_iter()
+            .map(|commit_result| match commit_result {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            })
+            .collect();
+        assert_eq!(state_roots, vec![first_hash, second_hash]);
+
+        let first_checkout = state.checkout(first_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs_updated().iter().cloned() {
+            assert_eq!(Some(value), first_checkout.read(correlation_id, &key).unwrap());
+        }
+        let second_checkout = state.checkout(second_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            second_checkout
+                .read(correlation_id, &create_test_pairs_updated()[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(StoredValue::CLValue(CLValue::from_t(4_i32).unwrap())),
+            second_checkout
+                .read(correlation_id, &create_test_pairs_updated()[2].key)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn commit_batch_stops_at_first_failure() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let first_effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        let batch = vec![
+            (root_hash, first_effects),
+            (fake_hash, create_test_effects_chained()),
+            (root_hash, create_test_effects_chained()),
+        ];
+        let commit_results = state.commit_batch(correlation_id, batch).unwrap();
+        assert_eq!(commit_results.len(), 2);
+        assert!(matches!(commit_results[1], CommitResult::RootNotFound));
+
+        // The effects before the failure are committed nonetheless.
+        let first_hash = match commit_results[0] {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        assert!(state.checkout(first_hash).unwrap().is_some());
+    }
+
+    fn commit_test_pairs_updated(state: &LmdbGlobalState, root_hash: Blake2bHash) -> Blake2bHash {
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        match state
+            .commit(CorrelationId::new(), root_hash, effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        }
+    }
+
+    #[test]
+    fn prune_removes_state_which_is_not_retained() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let updated_hash = commit_test_pairs_updated(&state, root_hash);
+
+        match state.prune(correlation_id, &[updated_hash]).unwrap() {
+            PruneResult::Success { pruned, .. } => assert!(pruned > 0),
+            PruneResult::RootNotFound(root) => panic!("root not found: {}", root),
+        }
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root()).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs_updated().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn prune_keeps_all_retained_states() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let updated_hash = commit_test_pairs_updated(&state, root_hash);
+
+        state
+            .prune(correlation_id, &[root_hash, updated_hash])
+            .unwrap();
+
+        // Pruning again with the same roots has nothing left to remove.
+        let retained = match state
+            .prune(correlation_id, &[root_hash, updated_hash])
+            .unwrap()
+        {
+            PruneResult::Success {
+                pruned: 0,
+                retained,
+            } => retained,
+            result => panic!("unexpected prune result: {}", result),
+        };
+        assert!(retained > 0);
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert!(state.checkout(updated_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn prune_fails_if_unknown_hash_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        assert_eq!(
+            PruneResult::RootNotFound(fake_hash),
+            state.prune(correlation_id, &[fake_hash]).unwrap()
+        );
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
 }
@@ -1,11 +1,19 @@
 pub mod in_memory;
 pub mod lmdb;
-
-use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
+pub mod rocksdb;
+pub mod scratch;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::BuildHasher,
+    time::Instant,
+};
 
 use engine_shared::{
     additive_map::AdditiveMap,
     logging::{log_duration, log_metric},
+    metrics,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::{self, Transform},
@@ -15,10 +23,10 @@ use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
 
 use crate::{
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
-    trie::Trie,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    trie::{merkle_proof::TrieMerkleProof, Pointer, Trie},
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        operations::{delete, read, write, DeleteResult, ReadResult, WriteBatch, WriteResult},
         TrieStore,
     },
     GAUGE_METRIC_KEY,
@@ -26,10 +34,15 @@ use crate::{
 
 const GLOBAL_STATE_COMMIT_READS: &str = "global_state_commit_reads";
 const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
+const GLOBAL_STATE_COMMIT_PUTS: &str = "global_state_commit_puts";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
 const COMMIT: &str = "commit";
+const GLOBAL_STATE_PRUNE_PRUNED: &str = "global_state_prune_pruned";
+const GLOBAL_STATE_PRUNE_RETAINED: &str = "global_state_prune_retained";
+const GLOBAL_STATE_PRUNE_DURATION: &str = "global_state_prune_duration";
+const PRUNE: &str = "prune";
 
 /// A reader of state
 pub trait StateReader<K, V> {
@@ -38,6 +51,14 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns the state value from the corresponding key along with a proof of its inclusion in
+    /// the state
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error>;
 }
 
 #[derive(Debug)]
@@ -84,6 +105,23 @@ impl From<transform::Error> for CommitResult {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum PruneResult {
+    RootNotFound(Blake2bHash),
+    Success { pruned: usize, retained: usize },
+}
+
+impl fmt::Display for PruneResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            PruneResult::RootNotFound(root) => write!(f, "Root not found: {}", root),
+            PruneResult::Success { pruned, retained } => {
+                write!(f, "Success: pruned: {}, retained: {}", pruned, retained)
+            }
+        }
+    }
+}
+
 pub trait StateProvider {
     type Error;
     type Reader: StateReader<Key, StoredValue, Error = Self::Error>;
@@ -100,6 +138,28 @@ pub trait StateProvider {
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error>;
 
+    /// Commits each of the `batch` of effects on top of its prestate hash, in order, stopping at
+    /// the first one which fails.  A prestate hash may be the post state hash of an earlier entry.
+    ///
+    /// Returns the results up to and including the failure, if any.  The default implementation
+    /// commits the entries one at a time.
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error> {
+        let mut commit_results = Vec::with_capacity(batch.len());
+        for (prestate_hash, effects) in batch {
+            let commit_result = self.commit(correlation_id, prestate_hash, effects)?;
+            let is_success = matches!(commit_result, CommitResult::Success { .. });
+            commit_results.push(commit_result);
+            if !is_success {
+                break;
+            }
+        }
+        Ok(commit_results)
+    }
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -112,6 +172,36 @@ pub trait StateProvider {
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Removes every trie which is not reachable from one of the `retained_roots` (or from the
+    /// empty root), making all other post state hashes unavailable for checkout.
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error>;
+
+    /// Reads the trie stored under `trie_key`, if any.
+    fn read_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Self::Error>;
+
+    /// Stores `trie` under its hash, returning that hash.  The children of `trie` need not be
+    /// present.
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie: &Trie<Key, StoredValue>,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    /// Returns the hashes of the tries reachable from `trie_keys` which are missing from the store.
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error>;
 }
 
 pub fn commit<'a, R, S, H, E>(
@@ -129,9 +219,67 @@ where
     H: BuildHasher,
 {
     let mut txn = environment.create_read_write_txn()?;
+    let commit_result =
+        commit_effects::<_, _, _, E>(correlation_id, &mut txn, store, prestate_hash, effects)?;
+    if let CommitResult::Success { .. } = commit_result {
+        txn.commit()?;
+    }
+    Ok(commit_result)
+}
+
+/// Commits each of the `batch` of effects on top of its prestate hash, in order, within a single
+/// read-write transaction.
+///
+/// A prestate hash may be the post state hash of an earlier entry of the batch.  Committing stops
+/// at the first entry which fails, so the returned results run up to and including that failure;
+/// the entries before it are committed nonetheless.
+pub fn commit_batch<'a, R, S, H, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform, H>)>,
+) -> Result<Vec<CommitResult>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    H: BuildHasher,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let mut commit_results = Vec::with_capacity(batch.len());
+    for (prestate_hash, effects) in batch {
+        let commit_result =
+            commit_effects::<_, _, _, E>(correlation_id, &mut txn, store, prestate_hash, effects)?;
+        let is_success = matches!(commit_result, CommitResult::Success { .. });
+        commit_results.push(commit_result);
+        if !is_success {
+            break;
+        }
+    }
+    txn.commit()?;
+    Ok(commit_results)
+}
+
+/// Applies `effects` on top of `prestate_hash` within `txn`, which is left for the caller to
+/// commit.  Nothing is written to `txn` unless the result is a success.
+fn commit_effects<T, S, H, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    prestate_hash: Blake2bHash,
+    effects: AdditiveMap<Key, Transform, H>,
+) -> Result<CommitResult, E>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+    H: BuildHasher,
+{
     let mut state_root = prestate_hash;
 
-    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&*txn, &state_root)?;
 
     if maybe_root.is_none() {
         return Ok(CommitResult::RootNotFound);
@@ -141,8 +289,16 @@ where
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
 
+    // `Key`s are ordered like their serialized form, so sorting them clusters keys which share a
+    // trie path prefix and lets the batch serve the intermediate nodes from its cache.
+    let mut effects: Vec<(Key, Transform)> = effects.into_iter().collect();
+    effects.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    let batch = WriteBatch::new(store);
+
     for (key, transform) in effects.into_iter() {
-        let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
+        let read_result =
+            read::<_, _, _, _, E>(correlation_id, &*txn, &batch, &state_root, &key)?;
 
         log_duration(
             correlation_id,
@@ -155,9 +311,24 @@ where
 
         let value = match (read_result, transform) {
             (ReadResult::NotFound, Transform::Write(new_value)) => new_value,
+            // Nothing is stored under the key, as these effects both wrote and deleted it.
+            (ReadResult::NotFound, Transform::Delete) => continue,
             (ReadResult::NotFound, _) => {
                 return Ok(CommitResult::KeyNotFound(key));
             }
+            (ReadResult::Found(_), Transform::Delete) => {
+                let delete_result =
+                    delete::<_, _, _, _, E>(correlation_id, txn, &batch, &state_root, &key)?;
+                match delete_result {
+                    DeleteResult::Deleted(root_hash) => {
+                        state_root = root_hash;
+                        writes += 1;
+                    }
+                    DeleteResult::DoesNotExist => return Ok(CommitResult::KeyNotFound(key)),
+                    DeleteResult::RootNotFound => return Ok(CommitResult::RootNotFound),
+                }
+                continue;
+            }
             (ReadResult::Found(current_value), transform) => match transform.apply(current_value) {
                 Ok(updated_value) => updated_value,
                 Err(err) => return Ok(err.into()),
@@ -166,7 +337,7 @@ where
         };
 
         let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
+            write::<_, _, _, _, E>(correlation_id, txn, &batch, &state_root, &key, &value)?;
 
         log_duration(
             correlation_id,
@@ -185,13 +356,16 @@ where
         }
     }
 
-    txn.commit()?;
+    let puts = batch.flush(txn, &state_root)?;
+
+    let commit_duration = start.elapsed();
+    metrics::record_commit(commit_duration);
 
     log_duration(
         correlation_id,
         GLOBAL_STATE_COMMIT_DURATION,
         COMMIT,
-        start.elapsed(),
+        commit_duration,
     );
 
     log_metric(
@@ -210,6 +384,14 @@ where
         f64::from(writes),
     );
 
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_PUTS,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        puts as f64,
+    );
+
     let bonded_validators = Default::default();
 
     Ok(CommitResult::Success {
@@ -217,3 +399,93 @@ where
         bonded_validators,
     })
 }
+
+/// Deletes all tries from `store` which are not reachable from any of the `retained_roots`.
+///
+/// Reachable tries are marked and the rest swept within a single read-write transaction, so
+/// concurrent commits cannot add tries which would be swept before they are referenced.  If any of
+/// the `retained_roots` is missing from the store nothing is deleted.
+pub fn prune<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    retained_roots: &[Blake2bHash],
+) -> Result<PruneResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error>,
+{
+    let start = Instant::now();
+    let mut txn = environment.create_read_write_txn()?;
+
+    let mut reachable: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = Vec::new();
+
+    for root in retained_roots {
+        if store.get(&txn, root)?.is_none() {
+            return Ok(PruneResult::RootNotFound(*root));
+        }
+        to_visit.push(*root);
+    }
+
+    while let Some(hash) = to_visit.pop() {
+        if !reachable.insert(hash) {
+            continue;
+        }
+        let pointers: Vec<Pointer> = match store.get(&txn, &hash)? {
+            Some(Trie::Node { pointer_block }) => {
+                pointer_block[..].iter().flatten().copied().collect()
+            }
+            Some(Trie::Extension { pointer, .. }) => vec![pointer],
+            Some(Trie::Leaf { .. }) | None => continue,
+        };
+        for pointer in pointers {
+            match pointer {
+                // Leaves have no children, so there is no need to fetch them.
+                Pointer::LeafPointer(leaf_hash) => {
+                    reachable.insert(leaf_hash);
+                }
+                Pointer::NodePointer(node_hash) => to_visit.push(node_hash),
+            }
+        }
+    }
+
+    let mut pruned: usize = 0;
+    for hash in store.keys(&txn)? {
+        if !reachable.contains(&hash) {
+            store.delete(&mut txn, &hash)?;
+            pruned += 1;
+        }
+    }
+
+    txn.commit()?;
+
+    let retained = reachable.len();
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_DURATION,
+        PRUNE,
+        start.elapsed(),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_PRUNED,
+        PRUNE,
+        GAUGE_METRIC_KEY,
+        pruned as f64,
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_RETAINED,
+        PRUNE,
+        GAUGE_METRIC_KEY,
+        retained as f64,
+    );
+
+    Ok(PruneResult::Success { pruned, retained })
+}
@@ -0,0 +1,526 @@
+use std::{ops::Deref, sync::Arc};
+
+use lmdb::{Cursor, Transaction as _};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use types::{Key, ProtocolVersion};
+
+use crate::{
+    error,
+    global_state::{
+        commit, commit_batch, lmdb::LmdbGlobalState, prune, CommitResult, PruneResult,
+        StateProvider, StateReader,
+    },
+    protocol_data::ProtocolData,
+    protocol_data_store::rocksdb::RocksDbProtocolDataStore,
+    store::Store,
+    transaction_source::{
+        rocksdb::{RocksDbEnvironment, RocksDbReadTransaction},
+        Transaction, TransactionSource, Writable,
+    },
+    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
+    trie_store::{
+        operations::{missing_trie_keys, put_trie, read, read_with_proof, ReadResult},
+        rocksdb::RocksDbTrieStore,
+    },
+};
+
+/// The number of entries copied from LMDB per RocksDB write batch during a migration.
+const MIGRATION_BATCH_SIZE: usize = 10_000;
+
+pub struct RocksDbGlobalState {
+    pub environment: Arc<RocksDbEnvironment>,
+    pub trie_store: Arc<RocksDbTrieStore>,
+    pub protocol_data_store: Arc<RocksDbProtocolDataStore>,
+    pub empty_root_hash: Blake2bHash,
+}
+
+/// Represents a "view" of global state at a particular root hash.
+pub struct RocksDbGlobalStateView {
+    pub environment: Arc<RocksDbEnvironment>,
+    pub store: Arc<RocksDbTrieStore>,
+    pub root_hash: Blake2bHash,
+}
+
+impl RocksDbGlobalState {
+    /// Creates an empty state from an existing environment and trie_store.
+    pub fn empty(
+        environment: Arc<RocksDbEnvironment>,
+        trie_store: Arc<RocksDbTrieStore>,
+        protocol_data_store: Arc<RocksDbProtocolDataStore>,
+    ) -> Result<Self, error::Error> {
+        let root_hash: Blake2bHash = {
+            let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
+            let mut txn = environment.create_read_write_txn()?;
+            trie_store.put(&mut txn, &root_hash, &root)?;
+            txn.commit()?;
+            root_hash
+        };
+        Ok(RocksDbGlobalState::new(
+            environment,
+            trie_store,
+            protocol_data_store,
+            root_hash,
+        ))
+    }
+
+    /// Creates a state from an existing environment, store, and root_hash.
+    /// Intended to be used for testing.
+    pub(crate) fn new(
+        environment: Arc<RocksDbEnvironment>,
+        trie_store: Arc<RocksDbTrieStore>,
+        protocol_data_store: Arc<RocksDbProtocolDataStore>,
+        empty_root_hash: Blake2bHash,
+    ) -> Self {
+        RocksDbGlobalState {
+            environment,
+            trie_store,
+            protocol_data_store,
+            empty_root_hash,
+        }
+    }
+
+    /// Copies every trie and protocol data entry of an LMDB-backed global state into this one.
+    ///
+    /// Entries are copied as raw bytes, so all state roots and protocol versions available in
+    /// `source` are available here afterwards.  Returns the number of entries copied.
+    pub fn migrate_from_lmdb(&self, source: &LmdbGlobalState) -> Result<usize, error::Error> {
+        let source_dbs = [
+            Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(source.trie_store.deref()),
+            source.protocol_data_store.handle(),
+        ];
+        let target_handles = [
+            Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(self.trie_store.deref()),
+            self.protocol_data_store.handle(),
+        ];
+
+        let source_txn = source.environment.create_read_txn()?;
+        let mut copied = 0;
+
+        for (source_db, target_handle) in source_dbs.iter().zip(target_handles.iter()) {
+            let mut cursor = source_txn.open_ro_cursor(*source_db)?;
+            let mut target_txn = self.environment.create_read_write_txn()?;
+            for (key, value) in cursor.iter_start() {
+                target_txn.write(target_handle.to_owned(), key, value)?;
+                copied += 1;
+                if copied % MIGRATION_BATCH_SIZE == 0 {
+                    target_txn.commit()?;
+                    target_txn = self.environment.create_read_write_txn()?;
+                }
+            }
+            target_txn.commit()?;
+        }
+
+        Transaction::commit(source_txn)?;
+        Ok(copied)
+    }
+}
+
+impl StateReader<Key, StoredValue> for RocksDbGlobalStateView {
+    type Error = error::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret =
+            match read::<Key, StoredValue, RocksDbReadTransaction, RocksDbTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.store.deref(),
+                &self.root_hash,
+                key,
+            )? {
+                ReadResult::Found(value) => Some(value),
+                ReadResult::NotFound => None,
+                ReadResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+            };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            RocksDbReadTransaction,
+            RocksDbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProvider for RocksDbGlobalState {
+    type Error = error::Error;
+
+    type Reader = RocksDbGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| RocksDbGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        let commit_result = commit::<RocksDbEnvironment, RocksDbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+        )?;
+        Ok(commit_result)
+    }
+
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error> {
+        commit_batch::<RocksDbEnvironment, RocksDbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            batch,
+        )
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.protocol_data_store
+            .put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit()
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get(&txn, &protocol_version)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.empty_root_hash
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let mut retained_roots = retained_roots.to_vec();
+        retained_roots.push(self.empty_root_hash);
+        prune::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &retained_roots,
+        )
+    }
+
+    fn read_trie(
+        &self,
+        _correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, trie_key)?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie: &Trie<Key, StoredValue>,
+    ) -> Result<Blake2bHash, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let trie_hash = put_trie::<Key, StoredValue, _, RocksDbTrieStore, Self::Error>(
+            correlation_id,
+            &mut txn,
+            &self.trie_store,
+            trie,
+        )?;
+        txn.commit()?;
+        Ok(trie_hash)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let missing = missing_trie_keys::<Key, StoredValue, _, RocksDbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            &self.trie_store,
+            trie_keys,
+        )?;
+        txn.commit()?;
+        Ok(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lmdb::DatabaseFlags;
+    use tempfile::tempdir;
+
+    use types::{account::AccountHash, CLValue};
+
+    use crate::{
+        protocol_data_store::lmdb::LmdbProtocolDataStore,
+        transaction_source::lmdb::LmdbEnvironment,
+        trie_store::{
+            lmdb::LmdbTrieStore,
+            operations::{write, WriteResult},
+        },
+        TEST_MAP_SIZE,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct TestPair {
+        key: Key,
+        value: StoredValue,
+    }
+
+    fn create_test_pairs() -> [TestPair; 2] {
+        [
+            TestPair {
+                key: Key::Account(AccountHash::new([1_u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+            },
+            TestPair {
+                key: Key::Account(AccountHash::new([2_u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t(2_i32).unwrap()),
+            },
+        ]
+    }
+
+    fn create_test_pairs_updated() -> [TestPair; 3] {
+        [
+            TestPair {
+                key: Key::Account(AccountHash::new([1u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t("one".to_string()).unwrap()),
+            },
+            TestPair {
+                key: Key::Account(AccountHash::new([2u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t("two".to_string()).unwrap()),
+            },
+            TestPair {
+                key: Key::Account(AccountHash::new([3u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t(3_i32).unwrap()),
+            },
+        ]
+    }
+
+    fn create_empty_state(path: &std::path::Path) -> RocksDbGlobalState {
+        let environment = Arc::new(RocksDbEnvironment::new(&path.to_path_buf()).unwrap());
+        let trie_store = Arc::new(RocksDbTrieStore::new(&environment, None));
+        let protocol_data_store = Arc::new(RocksDbProtocolDataStore::new(&environment, None));
+        RocksDbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap()
+    }
+
+    fn create_test_state(path: &std::path::Path) -> (RocksDbGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let ret = create_empty_state(path);
+        let mut current_root = ret.empty_root_hash;
+        {
+            let mut txn = ret.environment.create_read_write_txn().unwrap();
+
+            for TestPair { key, value } in &create_test_pairs() {
+                match write::<_, _, _, RocksDbTrieStore, error::Error>(
+                    correlation_id,
+                    &mut txn,
+                    &ret.trie_store,
+                    &current_root,
+                    key,
+                    value,
+                )
+                .unwrap()
+                {
+                    WriteResult::Written(root_hash) => {
+                        current_root = root_hash;
+                    }
+                    WriteResult::AlreadyExists => (),
+                    WriteResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+                }
+            }
+
+            txn.commit().unwrap();
+        }
+        (ret, current_root)
+    }
+
+    fn create_test_effects(pairs: &[TestPair]) -> AdditiveMap<Key, Transform> {
+        let mut tmp = AdditiveMap::new();
+        for TestPair { key, value } in pairs {
+            tmp.insert(*key, Transform::Write(value.to_owned()));
+        }
+        tmp
+    }
+
+    #[test]
+    fn reads_from_a_checkout_return_expected_values() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let (state, root_hash) = create_test_state(temp_dir.path());
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn checkout_fails_if_unknown_hash_is_given() {
+        let temp_dir = tempdir().unwrap();
+        let (state, _) = create_test_state(temp_dir.path());
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        let result = state.checkout(fake_hash).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn commit_updates_state_and_original_state_stays_intact() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let temp_dir = tempdir().unwrap();
+        let (state, root_hash) = create_test_state(temp_dir.path());
+
+        let effects = create_test_effects(&test_pairs_updated);
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            None,
+            original_checkout
+                .read(correlation_id, &test_pairs_updated[2].key)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn state_persists_across_reopening() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let root_hash = {
+            let (_state, root_hash) = create_test_state(temp_dir.path());
+            root_hash
+        };
+
+        let state = create_empty_state(temp_dir.path());
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn migrate_from_lmdb_copies_state_and_protocol_data() {
+        let correlation_id = CorrelationId::new();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let protocol_data = ProtocolData::default();
+
+        let lmdb_dir = tempdir().unwrap();
+        let lmdb_state = {
+            let environment = Arc::new(
+                LmdbEnvironment::new(&lmdb_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap(),
+            );
+            let trie_store =
+                Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+            let protocol_data_store = Arc::new(
+                LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+            );
+            LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap()
+        };
+        lmdb_state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .unwrap();
+        let effects = create_test_effects(&create_test_pairs());
+        let root_hash = match lmdb_state
+            .commit(correlation_id, lmdb_state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let rocksdb_dir = tempdir().unwrap();
+        let rocksdb_state = create_empty_state(rocksdb_dir.path());
+        let copied = rocksdb_state.migrate_from_lmdb(&lmdb_state).unwrap();
+        assert!(copied > 0);
+
+        let checkout = rocksdb_state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert_eq!(
+            Some(protocol_data),
+            rocksdb_state.get_protocol_data(protocol_version).unwrap()
+        );
+    }
+}
@@ -0,0 +1,554 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use types::{Key, ProtocolVersion};
+
+use crate::{
+    error,
+    global_state::{
+        commit,
+        in_memory::{InMemoryGlobalState, InMemoryGlobalStateView},
+        CommitResult, PruneResult, StateProvider, StateReader,
+    },
+    protocol_data::ProtocolData,
+    transaction_source::in_memory::InMemoryEnvironment,
+    trie::{merkle_proof::TrieMerkleProof, Trie},
+    trie_store::in_memory::InMemoryTrieStore,
+};
+
+/// Prefix of the bytes hashed to derive the state hash of a pending state.
+const PENDING_STATE_HASH_PREFIX: &[u8] = b"scratch";
+
+/// Global state which keeps committed effects in memory without writing them to the trie store.
+///
+/// Committing effects doesn't compute a Merkle root: the returned state hash identifies a pending
+/// state, whose reads are served from the committed values on top of the last sealed state.
+/// [`ScratchGlobalState::seal`] writes a pending state to the trie store, returning the same root
+/// hash as committing the effects to an [`InMemoryGlobalState`] would have.
+///
+/// Intended for tests, where the Merkle root of most intermediate states is never looked at.
+pub struct ScratchGlobalState {
+    state: InMemoryGlobalState,
+    pending_states: Mutex<HashMap<Blake2bHash, Arc<PendingState>>>,
+    next_pending_state_id: AtomicU64,
+}
+
+/// Values committed on top of a sealed state.
+struct PendingState {
+    /// The pending state the values were committed on top of, if any.
+    parent: Option<Arc<PendingState>>,
+    /// The sealed state at the bottom of the chain of pending states.
+    sealed_root: Blake2bHash,
+    /// The committed values, with `None` for a deleted one.
+    values: BTreeMap<Key, Option<StoredValue>>,
+}
+
+impl PendingState {
+    /// Returns the value committed under `key` by this state or the ones it was committed on top
+    /// of, which is `Some(None)` if the value was deleted.
+    fn read(&self, key: &Key) -> Option<Option<&StoredValue>> {
+        let mut pending_state = Some(self);
+        while let Some(current) = pending_state {
+            if let Some(maybe_value) = current.values.get(key) {
+                return Some(maybe_value.as_ref());
+            }
+            pending_state = current.parent.as_deref();
+        }
+        None
+    }
+
+    /// Returns the effects which turn the sealed state into this one.
+    fn effects(&self) -> AdditiveMap<Key, Transform> {
+        let mut chain = vec![self];
+        while let Some(parent) = chain[chain.len() - 1].parent.as_deref() {
+            chain.push(parent);
+        }
+        let mut values = BTreeMap::new();
+        for pending_state in chain.into_iter().rev() {
+            values.extend(pending_state.values.iter());
+        }
+        values
+            .into_iter()
+            .map(|(key, maybe_value)| match maybe_value {
+                Some(value) => (*key, Transform::Write(value.clone())),
+                None => (*key, Transform::Delete),
+            })
+            .collect()
+    }
+}
+
+/// Writes `pending_state` to `trie_store`, returning the root hash of the resulting state.
+fn seal_pending_state(
+    environment: &InMemoryEnvironment,
+    trie_store: &InMemoryTrieStore,
+    correlation_id: CorrelationId,
+    pending_state: &PendingState,
+) -> Result<CommitResult, error::Error> {
+    commit::<InMemoryEnvironment, InMemoryTrieStore, _, error::Error>(
+        environment,
+        trie_store,
+        correlation_id,
+        pending_state.sealed_root,
+        pending_state.effects(),
+    )
+}
+
+/// Represents a "view" of scratch global state at a particular state hash.
+pub struct ScratchGlobalStateView {
+    pending_state: Option<Arc<PendingState>>,
+    sealed_view: InMemoryGlobalStateView,
+}
+
+impl ScratchGlobalState {
+    /// Creates an empty state.
+    pub fn empty() -> Result<Self, error::Error> {
+        Ok(ScratchGlobalState {
+            state: InMemoryGlobalState::empty()?,
+            pending_states: Mutex::new(HashMap::new()),
+            next_pending_state_id: AtomicU64::new(0),
+        })
+    }
+
+    fn pending_state(
+        &self,
+        state_hash: &Blake2bHash,
+    ) -> Result<Option<Arc<PendingState>>, error::Error> {
+        Ok(self.pending_states.lock()?.get(state_hash).cloned())
+    }
+
+    /// Writes the pending state identified by `state_hash` to the trie store.
+    ///
+    /// Returns [`CommitResult::Success`] with the Merkle root of the state, which is `state_hash`
+    /// itself if the state is already sealed.
+    pub fn seal(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<CommitResult, error::Error> {
+        match self.pending_state(&state_hash)? {
+            Some(pending_state) => seal_pending_state(
+                &self.state.environment,
+                &self.state.trie_store,
+                correlation_id,
+                &pending_state,
+            ),
+            None if self.state.checkout(state_hash)?.is_some() => Ok(CommitResult::Success {
+                state_root: state_hash,
+                bonded_validators: Default::default(),
+            }),
+            None => Ok(CommitResult::RootNotFound),
+        }
+    }
+}
+
+impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
+    type Error = error::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        if let Some(maybe_value) = self
+            .pending_state
+            .as_ref()
+            .and_then(|pending_state| pending_state.read(key))
+        {
+            return Ok(maybe_value.cloned());
+        }
+        self.sealed_view.read(correlation_id, key)
+    }
+
+    /// Proofs are only defined against a Merkle root, so a pending state is sealed first.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let pending_state = match &self.pending_state {
+            Some(pending_state) => pending_state,
+            None => return self.sealed_view.read_with_proof(correlation_id, key),
+        };
+        let root_hash = match seal_pending_state(
+            &self.sealed_view.environment,
+            &self.sealed_view.store,
+            correlation_id,
+            pending_state,
+        )? {
+            CommitResult::Success { state_root, .. } => state_root,
+            commit_result => panic!("ScratchGlobalState failed to seal: {}", commit_result),
+        };
+        let sealed_view = InMemoryGlobalStateView {
+            environment: Arc::clone(&self.sealed_view.environment),
+            store: Arc::clone(&self.sealed_view.store),
+            root_hash,
+        };
+        sealed_view.read_with_proof(correlation_id, key)
+    }
+}
+
+impl StateProvider for ScratchGlobalState {
+    type Error = error::Error;
+
+    type Reader = ScratchGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let pending_state = self.pending_state(&state_hash)?;
+        let sealed_root = pending_state
+            .as_ref()
+            .map_or(state_hash, |pending_state| pending_state.sealed_root);
+        let maybe_state =
+            self.state
+                .checkout(sealed_root)?
+                .map(|sealed_view| ScratchGlobalStateView {
+                    pending_state,
+                    sealed_view,
+                });
+        Ok(maybe_state)
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        let view = match self.checkout(prestate_hash)? {
+            Some(view) => view,
+            None => return Ok(CommitResult::RootNotFound),
+        };
+
+        let mut values = BTreeMap::new();
+        for (key, transform) in effects.into_iter() {
+            let maybe_value = match (view.read(correlation_id, &key)?, transform) {
+                (None, Transform::Write(new_value)) => Some(new_value),
+                (None, Transform::Delete) => continue,
+                (None, _) => return Ok(CommitResult::KeyNotFound(key)),
+                (Some(_), Transform::Delete) => None,
+                (Some(current_value), transform) => match transform.apply(current_value) {
+                    Ok(updated_value) => Some(updated_value),
+                    Err(error) => return Ok(error.into()),
+                },
+            };
+            values.insert(key, maybe_value);
+        }
+
+        if values.is_empty() {
+            return Ok(CommitResult::Success {
+                state_root: prestate_hash,
+                bonded_validators: Default::default(),
+            });
+        }
+
+        let pending_state = PendingState {
+            sealed_root: view.sealed_view.root_hash,
+            parent: view.pending_state,
+            values,
+        };
+        let state_root = {
+            let id = self.next_pending_state_id.fetch_add(1, Ordering::SeqCst);
+            let mut bytes = PENDING_STATE_HASH_PREFIX.to_vec();
+            bytes.extend_from_slice(&id.to_le_bytes());
+            Blake2bHash::new(&bytes)
+        };
+        self.pending_states
+            .lock()?
+            .insert(state_root, Arc::new(pending_state));
+
+        Ok(CommitResult::Success {
+            state_root,
+            bonded_validators: Default::default(),
+        })
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        self.state
+            .put_protocol_data(protocol_version, protocol_data)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        self.state.get_protocol_data(protocol_version)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.state.empty_root()
+    }
+
+    /// Pending states which aren't retained are dropped, while the sealed states the retained ones
+    /// were committed on top of are kept.
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let mut pending_states = self.pending_states.lock()?;
+        pending_states.retain(|state_hash, _| retained_roots.contains(state_hash));
+        let retained_roots: Vec<Blake2bHash> = retained_roots
+            .iter()
+            .map(|state_hash| {
+                pending_states
+                    .get(state_hash)
+                    .map_or(*state_hash, |pending_state| pending_state.sealed_root)
+            })
+            .collect();
+        self.state.prune(correlation_id, &retained_roots)
+    }
+
+    fn read_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Self::Error> {
+        self.state.read_trie(correlation_id, trie_key)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie: &Trie<Key, StoredValue>,
+    ) -> Result<Blake2bHash, Self::Error> {
+        self.state.put_trie(correlation_id, trie)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        self.state.missing_trie_keys(correlation_id, trie_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{account::AccountHash, CLValue};
+
+    use super::*;
+
+    fn account_key(byte: u8) -> Key {
+        Key::Account(AccountHash::new([byte; 32]))
+    }
+
+    fn cl_value<T: types::CLTyped + types::bytesrepr::ToBytes>(value: T) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(value).unwrap())
+    }
+
+    fn create_test_pairs() -> Vec<(Key, StoredValue)> {
+        vec![
+            (account_key(1), cl_value(1_i32)),
+            (account_key(2), cl_value(2_i32)),
+        ]
+    }
+
+    fn create_test_pairs_updated() -> Vec<(Key, StoredValue)> {
+        vec![
+            (account_key(1), cl_value("one".to_string())),
+            (account_key(3), cl_value(3_i32)),
+        ]
+    }
+
+    fn write_effects(pairs: Vec<(Key, StoredValue)>) -> AdditiveMap<Key, Transform> {
+        pairs
+            .into_iter()
+            .map(|(key, value)| (key, Transform::Write(value)))
+            .collect()
+    }
+
+    fn commit<S: StateProvider>(
+        state: &S,
+        root_hash: Blake2bHash,
+        pairs: Vec<(Key, StoredValue)>,
+    ) -> Blake2bHash
+    where
+        S::Error: std::fmt::Debug,
+    {
+        match state
+            .commit(CorrelationId::new(), root_hash, write_effects(pairs))
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            commit_result => panic!("commit failed: {}", commit_result),
+        }
+    }
+
+    fn seal(state: &ScratchGlobalState, state_hash: Blake2bHash) -> Blake2bHash {
+        match state.seal(CorrelationId::new(), state_hash).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            commit_result => panic!("seal failed: {}", commit_result),
+        }
+    }
+
+    #[test]
+    fn commit_does_not_write_tries() {
+        let correlation_id = CorrelationId::new();
+        let state = ScratchGlobalState::empty().unwrap();
+        let root_hash = commit(&state, state.empty_root(), create_test_pairs());
+
+        assert!(state
+            .read_trie(correlation_id, &root_hash)
+            .unwrap()
+            .is_none());
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for (key, value) in create_test_pairs() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn commit_updates_state_and_original_state_stays_intact() {
+        let correlation_id = CorrelationId::new();
+        let state = ScratchGlobalState::empty().unwrap();
+        let root_hash = commit(&state, state.empty_root(), create_test_pairs());
+        let updated_hash = commit(&state, root_hash, create_test_pairs_updated());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for (key, value) in create_test_pairs_updated() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            Some(cl_value(2_i32)),
+            updated_checkout
+                .read(correlation_id, &account_key(2))
+                .unwrap()
+        );
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        for (key, value) in create_test_pairs() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            None,
+            original_checkout
+                .read(correlation_id, &account_key(3))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn seal_returns_merkle_root_of_state() {
+        let correlation_id = CorrelationId::new();
+        let state = ScratchGlobalState::empty().unwrap();
+        let root_hash = commit(&state, state.empty_root(), create_test_pairs());
+        let updated_hash = commit(&state, root_hash, create_test_pairs_updated());
+
+        let (in_memory_state, expected_root_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &create_test_pairs()).unwrap();
+        let expected_updated_hash = commit(
+            &in_memory_state,
+            expected_root_hash,
+            create_test_pairs_updated(),
+        );
+
+        let sealed_hash = seal(&state, updated_hash);
+        assert_eq!(sealed_hash, expected_updated_hash);
+        assert!(state
+            .read_trie(correlation_id, &sealed_hash)
+            .unwrap()
+            .is_some());
+        assert_eq!(seal(&state, sealed_hash), sealed_hash);
+
+        let sealed_checkout = state.checkout(sealed_hash).unwrap().unwrap();
+        assert_eq!(
+            Some(cl_value(2_i32)),
+            sealed_checkout
+                .read(correlation_id, &account_key(2))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn deleted_value_is_not_read_and_not_sealed() {
+        let correlation_id = CorrelationId::new();
+        let state = ScratchGlobalState::empty().unwrap();
+        let root_hash = commit(&state, state.empty_root(), create_test_pairs());
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(account_key(1), Transform::Delete);
+        let deleted_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            commit_result => panic!("commit failed: {}", commit_result),
+        };
+
+        let checkout = state.checkout(deleted_hash).unwrap().unwrap();
+        assert_eq!(None, checkout.read(correlation_id, &account_key(1)).unwrap());
+        assert_eq!(
+            Some(cl_value(2_i32)),
+            checkout.read(correlation_id, &account_key(2)).unwrap()
+        );
+
+        let (_, expected_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &[(account_key(2), cl_value(2_i32))])
+                .unwrap();
+        assert_eq!(seal(&state, deleted_hash), expected_hash);
+    }
+
+    #[test]
+    fn read_with_proof_from_pending_state() {
+        let correlation_id = CorrelationId::new();
+        let state = ScratchGlobalState::empty().unwrap();
+        let root_hash = commit(&state, state.empty_root(), create_test_pairs());
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let proof = checkout
+            .read_with_proof(correlation_id, &account_key(1))
+            .unwrap()
+            .expect("should have proof");
+        assert_eq!(proof.value(), &cl_value(1_i32));
+        assert_eq!(proof.compute_state_hash().unwrap(), seal(&state, root_hash));
+    }
+
+    #[test]
+    fn checkout_and_seal_fail_if_unknown_hash_is_given() {
+        let state = ScratchGlobalState::empty().unwrap();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        assert!(state.checkout(fake_hash).unwrap().is_none());
+        match state.seal(CorrelationId::new(), fake_hash).unwrap() {
+            CommitResult::RootNotFound => (),
+            commit_result => panic!("unexpected seal result: {}", commit_result),
+        }
+    }
+
+    #[test]
+    fn prune_drops_pending_states_which_are_not_retained() {
+        let correlation_id = CorrelationId::new();
+        let state = ScratchGlobalState::empty().unwrap();
+        let root_hash = commit(&state, state.empty_root(), create_test_pairs());
+        let updated_hash = commit(&state, root_hash, create_test_pairs_updated());
+
+        state.prune(correlation_id, &[updated_hash]).unwrap();
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            Some(cl_value(2_i32)),
+            updated_checkout
+                .read(correlation_id, &account_key(2))
+                .unwrap()
+        );
+    }
+}
@@ -1,23 +1,64 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use engine_shared::host_function_costs::{
+    HostFunctionCosts, HOST_FUNCTION_COSTS_SERIALIZED_LENGTH,
+};
+use engine_wasm_prep::wasm_costs::{
+    WasmCosts, DEFAULT_VERIFY_ED25519_COST, DEFAULT_VERIFY_SECP256K1_COST,
+    LEGACY_WASM_COSTS_SERIALIZED_LENGTH, WASM_COSTS_SERIALIZED_LENGTH,
+};
 use std::collections::BTreeMap;
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
-    ContractHash, HashAddr, KEY_HASH_LENGTH,
+    bytesrepr::{self, FromBytes, ToBytes, BOOL_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH},
+    system_contract_type::SystemContractRegistry,
+    ContractHash, HashAddr, SystemContractType, KEY_HASH_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH + 3 * KEY_HASH_LENGTH;
+const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH
+    + U64_SERIALIZED_LENGTH
+    + 3 * KEY_HASH_LENGTH
+    + BOOL_SERIALIZED_LENGTH
+    + HOST_FUNCTION_COSTS_SERIALIZED_LENGTH
+    + U64_SERIALIZED_LENGTH
+    + U64_SERIALIZED_LENGTH
+    + U64_SERIALIZED_LENGTH
+    + U64_SERIALIZED_LENGTH;
 const DEFAULT_ADDRESS: [u8; 32] = [0; 32];
+/// The gas price charged before it was stored in the protocol data, and hence the gas price of
+/// protocol data stored by earlier versions.
+pub const LEGACY_GAS_PRICE: u64 = 10;
+/// The gas charged for a native transfer, unless the protocol data sets another cost.
+pub const DEFAULT_TRANSFER_COST: u64 = 10_000;
+/// The maximum total size, in bytes, of the transforms a deploy may produce, unless the protocol
+/// data sets another limit.
+pub const DEFAULT_MAX_EFFECTS_SIZE: u64 = 256 * 1024 * 1024;
+/// The maximum size, in bytes of its serialized form, of a value written by a contract, unless the
+/// protocol data sets another limit.
+pub const DEFAULT_MAX_VALUE_SIZE: u64 = 1024 * 1024;
+/// The maximum length, in bytes, of the name of a named key, unless the protocol data sets another
+/// limit.
+pub const DEFAULT_MAX_NAMED_KEY_LENGTH: u64 = 256;
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
+///
+/// The serialized form starts with the layout stored by earlier versions: the original [`WasmCosts`]
+/// fields followed by the system contract hashes.  Fields added since are appended after it and
+/// take their legacy values when missing, so that protocol data stored by earlier versions can
+/// still be read.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
+    gas_price: u64,
     mint: ContractHash,
     proof_of_stake: ContractHash,
     standard_payment: ContractHash,
+    nonces_enabled: bool,
+    host_function_costs: HostFunctionCosts,
+    transfer_cost: u64,
+    max_effects_size: u64,
+    max_value_size: u64,
+    max_named_key_length: u64,
 }
 
-/// Provides a default instance with non existing urefs and empty costs table.
+/// Provides a default instance with non existing urefs, empty costs table and zero gas price.
 ///
 /// Used in contexts where PoS or Mint contract is not ready yet, and pos, and
 /// mint installers are ran. For use with caution.
@@ -25,29 +66,84 @@ impl Default for ProtocolData {
     fn default() -> ProtocolData {
         ProtocolData {
             wasm_costs: WasmCosts::default(),
+            gas_price: 0,
             mint: DEFAULT_ADDRESS,
             proof_of_stake: DEFAULT_ADDRESS,
             standard_payment: DEFAULT_ADDRESS,
+            nonces_enabled: false,
+            host_function_costs: HostFunctionCosts::default(),
+            transfer_cost: 0,
+            max_effects_size: DEFAULT_MAX_EFFECTS_SIZE,
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+            max_named_key_length: DEFAULT_MAX_NAMED_KEY_LENGTH,
         }
     }
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
+    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value and gas price.
     pub fn new(
         wasm_costs: WasmCosts,
+        gas_price: u64,
         mint: ContractHash,
         proof_of_stake: ContractHash,
         standard_payment: ContractHash,
     ) -> Self {
         ProtocolData {
             wasm_costs,
+            gas_price,
             mint,
             proof_of_stake,
             standard_payment,
+            nonces_enabled: false,
+            host_function_costs: HostFunctionCosts::default(),
+            transfer_cost: DEFAULT_TRANSFER_COST,
+            max_effects_size: DEFAULT_MAX_EFFECTS_SIZE,
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+            max_named_key_length: DEFAULT_MAX_NAMED_KEY_LENGTH,
         }
     }
 
+    /// Returns a copy of the [`ProtocolData`] with per-account replay protection enabled or
+    /// disabled.
+    pub fn with_nonces_enabled(mut self, nonces_enabled: bool) -> Self {
+        self.nonces_enabled = nonces_enabled;
+        self
+    }
+
+    /// Returns a copy of the [`ProtocolData`] with the given costs of the host functions.
+    pub fn with_host_function_costs(mut self, host_function_costs: HostFunctionCosts) -> Self {
+        self.host_function_costs = host_function_costs;
+        self
+    }
+
+    /// Returns a copy of the [`ProtocolData`] with the given gas charged for a native transfer.
+    pub fn with_transfer_cost(mut self, transfer_cost: u64) -> Self {
+        self.transfer_cost = transfer_cost;
+        self
+    }
+
+    /// Returns a copy of the [`ProtocolData`] with the given maximum total size, in bytes, of the
+    /// transforms a deploy may produce.
+    pub fn with_max_effects_size(mut self, max_effects_size: u64) -> Self {
+        self.max_effects_size = max_effects_size;
+        self
+    }
+
+    /// Returns a copy of the [`ProtocolData`] with the given maximum size, in bytes of its
+    /// serialized form, of a value a contract may write.
+    pub fn with_max_value_size(mut self, max_value_size: u64) -> Self {
+        self.max_value_size = max_value_size;
+        self
+    }
+
+    /// Returns a copy of the [`ProtocolData`] with the given maximum length, in bytes, of the name
+    /// a contract may give a named key.
+    pub fn with_max_named_key_length(mut self, max_named_key_length: u64) -> Self {
+        self.max_named_key_length = max_named_key_length;
+        self
+    }
+
     /// Creates a new, partially-valid [`ProtocolData`] value where only the mint URef is known.
     ///
     /// Used during `commit_genesis` before all system contracts' URefs are known.
@@ -64,11 +160,13 @@ impl ProtocolData {
     /// Used during `commit_genesis` before all system contracts' URefs are known.
     pub fn partial_without_standard_payment(
         wasm_costs: WasmCosts,
+        gas_price: u64,
         mint: ContractHash,
         proof_of_stake: ContractHash,
     ) -> Self {
         ProtocolData {
             wasm_costs,
+            gas_price,
             mint,
             proof_of_stake,
             ..Default::default()
@@ -80,6 +178,44 @@ impl ProtocolData {
         &self.wasm_costs
     }
 
+    /// Gets the gas price, i.e. the number of motes paid per unit of gas.
+    pub fn gas_price(&self) -> u64 {
+        self.gas_price
+    }
+
+    /// Returns whether accounts are created with a nonce, which each of their deploys must
+    /// match.
+    pub fn nonces_enabled(&self) -> bool {
+        self.nonces_enabled
+    }
+
+    /// Gets the [`HostFunctionCosts`] value from a given [`ProtocolData`] value.
+    pub fn host_function_costs(&self) -> &HostFunctionCosts {
+        &self.host_function_costs
+    }
+
+    /// Gets the gas charged for a native transfer.
+    pub fn transfer_cost(&self) -> u64 {
+        self.transfer_cost
+    }
+
+    /// Gets the maximum total size, in bytes, of the transforms a deploy may produce.  A deploy
+    /// exceeding it fails rather than being committed.
+    pub fn max_effects_size(&self) -> u64 {
+        self.max_effects_size
+    }
+
+    /// Gets the maximum size, in bytes of its serialized form, of a value a contract may write to
+    /// global state.
+    pub fn max_value_size(&self) -> u64 {
+        self.max_value_size
+    }
+
+    /// Gets the maximum length, in bytes, of the name a contract may give a named key.
+    pub fn max_named_key_length(&self) -> u64 {
+        self.max_named_key_length
+    }
+
     pub fn mint(&self) -> ContractHash {
         self.mint
     }
@@ -107,6 +243,23 @@ impl ProtocolData {
         vec
     }
 
+    /// Returns the valid system contracts keyed by the names of their [`SystemContractType`]s,
+    /// suitable for storing in the system contract registry.
+    pub fn system_contract_registry(&self) -> SystemContractRegistry {
+        let entries = [
+            (SystemContractType::Mint, self.mint),
+            (SystemContractType::ProofOfStake, self.proof_of_stake),
+            (SystemContractType::StandardPayment, self.standard_payment),
+        ];
+        entries
+            .iter()
+            .filter(|(_, contract_hash)| *contract_hash != DEFAULT_ADDRESS)
+            .map(|(system_contract_type, contract_hash)| {
+                (system_contract_type.to_string(), *contract_hash)
+            })
+            .collect()
+    }
+
     pub fn update_from(&mut self, updates: BTreeMap<ContractHash, ContractHash>) -> bool {
         for (old_hash, new_hash) in updates {
             if old_hash == self.mint {
@@ -126,10 +279,21 @@ impl ProtocolData {
 impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
-        ret.append(&mut self.wasm_costs.to_bytes()?);
+        let wasm_costs = self.wasm_costs.to_bytes()?;
+        let (legacy_wasm_costs, signature_verification_costs) =
+            wasm_costs.split_at(LEGACY_WASM_COSTS_SERIALIZED_LENGTH);
+        ret.extend_from_slice(legacy_wasm_costs);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
+        ret.extend_from_slice(signature_verification_costs);
+        ret.append(&mut self.gas_price.to_bytes()?);
+        ret.append(&mut self.nonces_enabled.to_bytes()?);
+        ret.append(&mut self.host_function_costs.to_bytes()?);
+        ret.append(&mut self.transfer_cost.to_bytes()?);
+        ret.append(&mut self.max_effects_size.to_bytes()?);
+        ret.append(&mut self.max_value_size.to_bytes()?);
+        ret.append(&mut self.max_named_key_length.to_bytes()?);
         Ok(ret)
     }
 
@@ -140,17 +304,50 @@ impl ToBytes for ProtocolData {
 
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_costs, rem) = WasmCosts::from_bytes(bytes)?;
+        if bytes.len() < LEGACY_WASM_COSTS_SERIALIZED_LENGTH {
+            return Err(bytesrepr::Error::EarlyEndOfStream);
+        }
+        let (legacy_wasm_costs, rem) = bytes.split_at(LEGACY_WASM_COSTS_SERIALIZED_LENGTH);
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (proof_of_stake, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
+        // Signature verification mustn't be free under protocol data stored without its costs.
+        let (verify_ed25519, rem) =
+            bytesrepr::from_trailing_bytes(rem, DEFAULT_VERIFY_ED25519_COST)?;
+        let (verify_secp256k1, rem) =
+            bytesrepr::from_trailing_bytes(rem, DEFAULT_VERIFY_SECP256K1_COST)?;
+        let (gas_price, rem) = bytesrepr::from_trailing_bytes(rem, LEGACY_GAS_PRICE)?;
+        let (nonces_enabled, rem) = bytesrepr::from_trailing_bytes(rem, false)?;
+        let (host_function_costs, rem) =
+            bytesrepr::from_trailing_bytes(rem, HostFunctionCosts::default())?;
+        let (transfer_cost, rem) = bytesrepr::from_trailing_bytes(rem, DEFAULT_TRANSFER_COST)?;
+        let (max_effects_size, rem) =
+            bytesrepr::from_trailing_bytes(rem, DEFAULT_MAX_EFFECTS_SIZE)?;
+        let (max_value_size, rem) = bytesrepr::from_trailing_bytes(rem, DEFAULT_MAX_VALUE_SIZE)?;
+        let (max_named_key_length, rem) =
+            bytesrepr::from_trailing_bytes(rem, DEFAULT_MAX_NAMED_KEY_LENGTH)?;
+
+        let wasm_costs = {
+            let mut wasm_costs_bytes = Vec::with_capacity(WASM_COSTS_SERIALIZED_LENGTH);
+            wasm_costs_bytes.extend_from_slice(legacy_wasm_costs);
+            wasm_costs_bytes.append(&mut verify_ed25519.to_bytes()?);
+            wasm_costs_bytes.append(&mut verify_secp256k1.to_bytes()?);
+            bytesrepr::deserialize(wasm_costs_bytes)?
+        };
 
         Ok((
             ProtocolData {
                 wasm_costs,
+                gas_price,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                nonces_enabled,
+                host_function_costs,
+                transfer_cost,
+                max_effects_size,
+                max_value_size,
+                max_named_key_length,
             },
             rem,
         ))
@@ -161,6 +358,7 @@ impl FromBytes for ProtocolData {
 pub(crate) mod gens {
     use proptest::prop_compose;
 
+    use engine_shared::host_function_costs::gens as host_function_costs_gens;
     use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
     use types::gens;
 
@@ -169,15 +367,29 @@ pub(crate) mod gens {
     prop_compose! {
         pub fn protocol_data_arb()(
             wasm_costs in wasm_costs_gens::wasm_costs_arb(),
+            gas_price in proptest::num::u64::ANY,
             mint in gens::u8_slice_32(),
             proof_of_stake in gens::u8_slice_32(),
             standard_payment in gens::u8_slice_32(),
+            nonces_enabled in proptest::bool::ANY,
+            host_function_costs in host_function_costs_gens::host_function_costs_arb(),
+            transfer_cost in proptest::num::u64::ANY,
+            max_effects_size in proptest::num::u64::ANY,
+            max_value_size in proptest::num::u64::ANY,
+            max_named_key_length in proptest::num::u64::ANY,
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
+                gas_price,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                nonces_enabled,
+                host_function_costs,
+                transfer_cost,
+                max_effects_size,
+                max_value_size,
+                max_named_key_length,
             }
         }
     }
@@ -187,10 +399,21 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_wasm_prep::wasm_costs::WasmCosts;
-    use types::{bytesrepr, ContractHash};
+    use engine_shared::host_function_costs::{HostFunctionCost, HostFunctionCosts, StorageCosts};
+    use engine_wasm_prep::wasm_costs::{
+        WasmCosts, DEFAULT_VERIFY_ED25519_COST, DEFAULT_VERIFY_SECP256K1_COST,
+    };
+    use types::{
+        bytesrepr::{self, ToBytes},
+        ContractHash, SystemContractType,
+    };
 
-    use super::{gens, ProtocolData};
+    use super::{
+        gens, ProtocolData, DEFAULT_MAX_EFFECTS_SIZE, DEFAULT_MAX_NAMED_KEY_LENGTH,
+        DEFAULT_MAX_VALUE_SIZE, DEFAULT_TRANSFER_COST, LEGACY_GAS_PRICE,
+    };
+
+    const GAS_PRICE: u64 = 10;
 
     fn wasm_costs_mock() -> WasmCosts {
         WasmCosts {
@@ -204,6 +427,8 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            verify_ed25519: 10_000,
+            verify_secp256k1: 10_000,
         }
     }
 
@@ -219,6 +444,8 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            verify_ed25519: 0,
+            verify_secp256k1: 0,
         }
     }
 
@@ -231,6 +458,7 @@ mod tests {
             let standard_payment_reference = [3u8; 32];
             ProtocolData::new(
                 costs,
+                GAS_PRICE,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -243,10 +471,21 @@ mod tests {
             let standard_payment_reference = [2u8; 32];
             ProtocolData::new(
                 costs,
+                GAS_PRICE,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
             )
+            .with_nonces_enabled(true)
+            .with_host_function_costs(HostFunctionCosts {
+                put_key: HostFunctionCost::new(0, 0),
+                storage: StorageCosts::new(0, 0),
+                ..HostFunctionCosts::default()
+            })
+            .with_transfer_cost(0)
+            .with_max_effects_size(0)
+            .with_max_value_size(0)
+            .with_max_named_key_length(0)
         };
         bytesrepr::test_serialization_roundtrip(&mock);
         bytesrepr::test_serialization_roundtrip(&free);
@@ -261,6 +500,7 @@ mod tests {
             let costs = wasm_costs_mock();
             ProtocolData::new(
                 costs,
+                GAS_PRICE,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -291,6 +531,7 @@ mod tests {
             let costs = wasm_costs_mock();
             ProtocolData::new(
                 costs,
+                GAS_PRICE,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -308,6 +549,81 @@ mod tests {
         assert_eq!(actual[1], standard_payment_reference);
     }
 
+    #[test]
+    fn should_return_registry_of_valid_system_contracts() {
+        let mint_reference = [0u8; 32]; // <-- invalid addr
+        let proof_of_stake_reference = [2u8; 32];
+        let standard_payment_reference = [3u8; 32];
+        let protocol_data = ProtocolData::new(
+            wasm_costs_mock(),
+            GAS_PRICE,
+            mint_reference,
+            proof_of_stake_reference,
+            standard_payment_reference,
+        );
+
+        let registry = protocol_data.system_contract_registry();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            registry.get(&SystemContractType::ProofOfStake.to_string()),
+            Some(&proof_of_stake_reference)
+        );
+        assert_eq!(
+            registry.get(&SystemContractType::StandardPayment.to_string()),
+            Some(&standard_payment_reference)
+        );
+    }
+
+    #[test]
+    fn should_deserialize_legacy_layout() {
+        let wasm_costs = WasmCosts {
+            verify_ed25519: DEFAULT_VERIFY_ED25519_COST,
+            verify_secp256k1: DEFAULT_VERIFY_SECP256K1_COST,
+            ..wasm_costs_mock()
+        };
+        let mint_reference = [1u8; 32];
+        let proof_of_stake_reference = [2u8; 32];
+        let standard_payment_reference = [3u8; 32];
+
+        // Protocol data as stored before the signature verification costs, gas price, nonce flag,
+        // host function costs, transfer cost and size limits were added.
+        let mut legacy_bytes = vec![];
+        for cost in &[
+            wasm_costs.regular,
+            wasm_costs.div,
+            wasm_costs.mul,
+            wasm_costs.mem,
+            wasm_costs.initial_mem,
+            wasm_costs.grow_mem,
+            wasm_costs.memcpy,
+            wasm_costs.max_stack_height,
+            wasm_costs.opcodes_mul,
+            wasm_costs.opcodes_div,
+        ] {
+            legacy_bytes.append(&mut cost.to_bytes().unwrap());
+        }
+        legacy_bytes.extend_from_slice(&mint_reference);
+        legacy_bytes.extend_from_slice(&proof_of_stake_reference);
+        legacy_bytes.extend_from_slice(&standard_payment_reference);
+
+        let expected = ProtocolData::new(
+            wasm_costs,
+            LEGACY_GAS_PRICE,
+            mint_reference,
+            proof_of_stake_reference,
+            standard_payment_reference,
+        );
+        let actual: ProtocolData = bytesrepr::deserialize(legacy_bytes).unwrap();
+        assert_eq!(actual, expected);
+        assert!(!actual.nonces_enabled());
+        assert_eq!(*actual.host_function_costs(), HostFunctionCosts::default());
+        assert_eq!(actual.transfer_cost(), DEFAULT_TRANSFER_COST);
+        assert_eq!(actual.max_effects_size(), DEFAULT_MAX_EFFECTS_SIZE);
+        assert_eq!(actual.max_value_size(), DEFAULT_MAX_VALUE_SIZE);
+        assert_eq!(actual.max_named_key_length(), DEFAULT_MAX_NAMED_KEY_LENGTH);
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
@@ -0,0 +1,36 @@
+use types::ProtocolVersion;
+
+use crate::{
+    error,
+    protocol_data::ProtocolData,
+    protocol_data_store::{self, ProtocolDataStore},
+    store::Store,
+    transaction_source::rocksdb::RocksDbEnvironment,
+};
+
+/// A RocksDB-backed protocol data store.
+#[derive(Debug, Clone)]
+pub struct RocksDbProtocolDataStore {
+    name: String,
+}
+
+impl RocksDbProtocolDataStore {
+    pub fn new(_env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", protocol_data_store::NAME, name))
+            .unwrap_or_else(|| String::from(protocol_data_store::NAME));
+        RocksDbProtocolDataStore { name }
+    }
+}
+
+impl Store<ProtocolVersion, ProtocolData> for RocksDbProtocolDataStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.name.clone()
+    }
+}
+
+impl ProtocolDataStore for RocksDbProtocolDataStore {}
@@ -7,9 +7,14 @@ use types::{gens as gens_ext, ProtocolVersion};
 
 use crate::{
     protocol_data::{gens, ProtocolData},
-    protocol_data_store::{in_memory::InMemoryProtocolDataStore, lmdb::LmdbProtocolDataStore},
+    protocol_data_store::{
+        in_memory::InMemoryProtocolDataStore, lmdb::LmdbProtocolDataStore,
+        rocksdb::RocksDbProtocolDataStore,
+    },
     store::tests as store_tests,
-    transaction_source::{in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment},
+    transaction_source::{
+        in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment, rocksdb::RocksDbEnvironment,
+    },
     TEST_MAP_SIZE,
 };
 
@@ -43,6 +48,17 @@ fn lmdb_roundtrip_succeeds(inputs: BTreeMap<ProtocolVersion, ProtocolData>) -> b
     ret
 }
 
+fn rocksdb_roundtrip_succeeds(inputs: BTreeMap<ProtocolVersion, ProtocolData>) -> bool {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let env = RocksDbEnvironment::new(&tmp_dir.path().to_path_buf()).unwrap();
+    let store = RocksDbProtocolDataStore::new(&env, None);
+
+    let ret = store_tests::roundtrip_succeeds(&env, &store, inputs).unwrap();
+    drop(env);
+    tmp_dir.close().unwrap();
+    ret
+}
+
 proptest! {
     #[test]
     fn prop_in_memory_roundtrip_succeeds(
@@ -57,4 +73,11 @@ proptest! {
     ) {
         assert!(lmdb_roundtrip_succeeds(m))
     }
+
+    #[test]
+    fn prop_rocksdb_roundtrip_succeeds(
+        m in collection::btree_map(gens_ext::protocol_version_arb(), gens::protocol_data_arb(), get_range())
+    ) {
+        assert!(rocksdb_roundtrip_succeeds(m))
+    }
 }
@@ -0,0 +1,48 @@
+use std::io;
+
+use failure::Fail;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr;
+
+#[derive(Debug, Clone, Fail, PartialEq, Eq)]
+pub enum Error {
+    #[fail(display = "Snapshot I/O error: {}", _0)]
+    Io(String),
+
+    #[fail(display = "{}", _0)]
+    BytesRepr(#[fail(cause)] bytesrepr::Error),
+
+    #[fail(display = "Not a global state snapshot")]
+    InvalidMagic,
+
+    #[fail(display = "Unsupported snapshot format version: {}", _0)]
+    UnsupportedVersion(u8),
+
+    #[fail(display = "Snapshot chunk of {} bytes is too large", _0)]
+    ChunkTooLarge(usize),
+
+    #[fail(
+        display = "Snapshot trie hash mismatch: expected {}, computed {}",
+        expected, actual
+    )]
+    HashMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+
+    #[fail(display = "Trie {} is missing from the snapshot", _0)]
+    MissingTrie(Blake2bHash),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error.to_string())
+    }
+}
+
+impl From<bytesrepr::Error> for Error {
+    fn from(error: bytesrepr::Error) -> Self {
+        Error::BytesRepr(error)
+    }
+}
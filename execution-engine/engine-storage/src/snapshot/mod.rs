@@ -0,0 +1,244 @@
+//! Export and import of complete global state snapshots.
+//!
+//! A snapshot holds every trie reachable from a single state root.  Importing it into another store
+//! makes that state available for checkout without replaying the blocks which produced it.
+//!
+//! A snapshot consists of:
+//!
+//! * a header made up of [`MAGIC`], the format [`VERSION`] and the state root hash
+//! * a sequence of chunks, each a `u32` byte length followed by that many bytes holding a
+//!   serialized `Vec<(Blake2bHash, Vec<u8>)>` of trie hashes and serialized tries
+//! * a zero length marking the end of the snapshot
+//!
+//! All integers are little-endian, matching [`bytesrepr`].
+
+mod error;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    io::{self, Read, Write},
+    mem,
+};
+
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+pub use self::error::Error;
+use crate::{
+    store::Store,
+    transaction_source::{Readable, Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::TrieStore,
+};
+
+/// The bytes every snapshot starts with.
+pub const MAGIC: [u8; 8] = *b"CLSNAPSH";
+
+/// The version of the snapshot format written by [`export`].
+pub const VERSION: u8 = 1;
+
+/// The default number of tries written per chunk.
+pub const DEFAULT_CHUNK_SIZE: usize = 1024;
+
+const LENGTH_SERIALIZED_LENGTH: usize = 4;
+
+type Chunk = Vec<(Blake2bHash, Vec<u8>)>;
+
+/// Writes every trie reachable from `state_root` to `writer` as a snapshot, `chunk_size` tries per
+/// chunk.
+///
+/// All tries are read within a single read transaction.  Returns the number of tries written.
+pub fn export<'a, K, V, R, S, W, E>(
+    environment: &'a R,
+    store: &S,
+    state_root: &Blake2bHash,
+    writer: &mut W,
+    chunk_size: usize,
+) -> Result<usize, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    W: Write,
+    E: From<R::Error> + From<S::Error> + From<Error>,
+{
+    let chunk_size = chunk_size.max(1);
+    let txn = environment.create_read_txn()?;
+
+    write_header(writer, state_root)?;
+
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = vec![*state_root];
+    let mut chunk: Chunk = Vec::with_capacity(chunk_size);
+    let mut exported: usize = 0;
+
+    while let Some(hash) = to_visit.pop() {
+        if !visited.insert(hash) {
+            continue;
+        }
+        let trie: Trie<K, V> = match store.get(&txn, &hash)? {
+            Some(trie) => trie,
+            None => return Err(Error::MissingTrie(hash).into()),
+        };
+        to_visit.extend(child_hashes(&trie));
+        chunk.push((hash, trie.to_bytes().map_err(Error::from)?));
+        exported += 1;
+
+        if chunk.len() == chunk_size {
+            let full_chunk = mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+            write_chunk(writer, full_chunk)?;
+        }
+    }
+
+    if !chunk.is_empty() {
+        write_chunk(writer, chunk)?;
+    }
+    writer
+        .write_all(&[0; LENGTH_SERIALIZED_LENGTH])
+        .map_err(Error::from)?;
+    writer.flush().map_err(Error::from)?;
+
+    txn.commit()?;
+    Ok(exported)
+}
+
+/// Reads a snapshot from `reader` into `store`, returning its state root hash.
+///
+/// Every trie is checked against its hash, and the tries reachable from the state root are checked
+/// to all be present.  All writes happen in a single read-write transaction which is only
+/// committed if the snapshot is valid.
+pub fn import<'a, K, V, R, S, Rd, E>(
+    environment: &'a R,
+    store: &S,
+    reader: &mut Rd,
+) -> Result<Blake2bHash, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    Rd: Read,
+    E: From<R::Error> + From<S::Error> + From<Error>,
+{
+    let state_root = read_header(reader)?;
+    let mut txn = environment.create_read_write_txn()?;
+
+    while let Some(chunk) = read_chunk(reader)? {
+        for (hash, trie_bytes) in chunk {
+            let actual = Blake2bHash::new(&trie_bytes);
+            if actual != hash {
+                return Err(Error::HashMismatch {
+                    expected: hash,
+                    actual,
+                }
+                .into());
+            }
+            let trie: Trie<K, V> = bytesrepr::deserialize(trie_bytes).map_err(Error::from)?;
+            store.put(&mut txn, &hash, &trie)?;
+        }
+    }
+
+    // As each trie has been checked against its hash, the state is intact if nothing reachable
+    // from the root is missing.
+    check_complete::<K, V, _, _, E>(&txn, store, &state_root)?;
+
+    txn.commit()?;
+    Ok(state_root)
+}
+
+fn child_hashes<K, V>(trie: &Trie<K, V>) -> Vec<Blake2bHash> {
+    match trie {
+        Trie::Leaf { .. } => Vec::new(),
+        Trie::Node { pointer_block } => pointer_block[..]
+            .iter()
+            .flatten()
+            .map(|pointer| *pointer.hash())
+            .collect(),
+        Trie::Extension { pointer, .. } => vec![*pointer.hash()],
+    }
+}
+
+fn check_complete<K, V, T, S, E>(txn: &T, store: &S, state_root: &Blake2bHash) -> Result<(), E>
+where
+    K: FromBytes,
+    V: FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<Error>,
+{
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = vec![*state_root];
+
+    while let Some(hash) = to_visit.pop() {
+        if !visited.insert(hash) {
+            continue;
+        }
+        match store.get(txn, &hash)? {
+            Some(trie) => to_visit.extend(child_hashes(&trie)),
+            None => return Err(Error::MissingTrie(hash).into()),
+        }
+    }
+    Ok(())
+}
+
+fn write_header<W: Write>(writer: &mut W, state_root: &Blake2bHash) -> Result<(), Error> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    writer.write_all(&state_root.to_bytes()?)?;
+    Ok(())
+}
+
+fn read_header<Rd: Read>(reader: &mut Rd) -> Result<Blake2bHash, Error> {
+    let mut magic = [0u8; MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(Error::InvalidMagic);
+    }
+
+    let mut version = [0u8; 1];
+    reader.read_exact(&mut version)?;
+    if version[0] != VERSION {
+        return Err(Error::UnsupportedVersion(version[0]));
+    }
+
+    let mut state_root = [0u8; BLAKE2B_DIGEST_LENGTH];
+    reader.read_exact(&mut state_root)?;
+    Ok(state_root.into())
+}
+
+fn write_chunk<W: Write>(writer: &mut W, chunk: Chunk) -> Result<(), Error> {
+    let chunk_bytes = chunk.into_bytes()?;
+    let length =
+        u32::try_from(chunk_bytes.len()).map_err(|_| Error::ChunkTooLarge(chunk_bytes.len()))?;
+    writer.write_all(&length.to_le_bytes())?;
+    writer.write_all(&chunk_bytes)?;
+    Ok(())
+}
+
+/// Returns `None` once the end of the snapshot has been reached.
+fn read_chunk<Rd: Read>(reader: &mut Rd) -> Result<Option<Chunk>, Error> {
+    let mut length = [0u8; LENGTH_SERIALIZED_LENGTH];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length) as usize;
+    if length == 0 {
+        return Ok(None);
+    }
+
+    let mut chunk_bytes = Vec::new();
+    reader
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(&mut chunk_bytes)?;
+    if chunk_bytes.len() != length {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    let chunk = bytesrepr::deserialize(chunk_bytes)?;
+    Ok(Some(chunk))
+}
@@ -0,0 +1,239 @@
+use std::{convert::TryInto, sync::Arc};
+
+use lmdb::DatabaseFlags;
+use tempfile::tempdir;
+
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId, BLAKE2B_DIGEST_LENGTH},
+    stored_value::StoredValue,
+};
+use types::{account::AccountHash, bytesrepr::ToBytes, CLValue, Key};
+
+use super::{write_chunk, write_header, Error, DEFAULT_CHUNK_SIZE, MAGIC, VERSION};
+use crate::{
+    error,
+    global_state::{
+        in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider, StateReader,
+    },
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    store::Store,
+    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::{
+        in_memory::InMemoryTrieStore,
+        lmdb::LmdbTrieStore,
+        operations::{write, WriteResult},
+    },
+    TEST_MAP_SIZE,
+};
+
+const TEST_PAIRS_LENGTH: u8 = 16;
+
+fn create_test_pairs() -> Vec<(Key, StoredValue)> {
+    (0..TEST_PAIRS_LENGTH)
+        .map(|i| {
+            let key = Key::Account(AccountHash::new([i; 32]));
+            let value = StoredValue::CLValue(CLValue::from_t(i32::from(i)).unwrap());
+            (key, value)
+        })
+        .collect()
+}
+
+fn create_in_memory_state() -> (InMemoryGlobalState, Blake2bHash) {
+    InMemoryGlobalState::from_pairs(CorrelationId::new(), &create_test_pairs()).unwrap()
+}
+
+fn export_in_memory_state(
+    state: &InMemoryGlobalState,
+    state_root: &Blake2bHash,
+    chunk_size: usize,
+) -> Result<Vec<u8>, error::Error> {
+    let mut snapshot = Vec::new();
+    super::export::<Key, StoredValue, _, InMemoryTrieStore, _, error::Error>(
+        state.environment.as_ref(),
+        state.trie_store.as_ref(),
+        state_root,
+        &mut snapshot,
+        chunk_size,
+    )?;
+    Ok(snapshot)
+}
+
+fn import_in_memory_state(
+    state: &InMemoryGlobalState,
+    snapshot: &[u8],
+) -> Result<Blake2bHash, error::Error> {
+    super::import::<Key, StoredValue, _, InMemoryTrieStore, _, error::Error>(
+        state.environment.as_ref(),
+        state.trie_store.as_ref(),
+        &mut &snapshot[..],
+    )
+}
+
+fn check_test_pairs<S: StateProvider>(state: &S, state_root: Blake2bHash)
+where
+    S::Error: std::fmt::Debug,
+{
+    let correlation_id = CorrelationId::new();
+    let checkout = state
+        .checkout(state_root)
+        .unwrap()
+        .expect("should checkout");
+    for (key, value) in create_test_pairs() {
+        assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+    }
+}
+
+#[test]
+fn in_memory_snapshot_roundtrip_in_several_chunks() {
+    let (source, state_root) = create_in_memory_state();
+    let snapshot = export_in_memory_state(&source, &state_root, 3).unwrap();
+
+    let target = InMemoryGlobalState::empty().unwrap();
+    assert!(target.checkout(state_root).unwrap().is_none());
+
+    let imported_root = import_in_memory_state(&target, &snapshot).unwrap();
+    assert_eq!(imported_root, state_root);
+    check_test_pairs(&target, state_root);
+}
+
+#[test]
+fn lmdb_snapshot_imports_into_in_memory_state() {
+    let correlation_id = CorrelationId::new();
+    let temp_dir = tempdir().unwrap();
+    let environment =
+        Arc::new(LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+    let trie_store =
+        Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+    let protocol_data_store =
+        Arc::new(LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+    let source = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+
+    let mut state_root = source.empty_root_hash;
+    {
+        let mut txn = source.environment.create_read_write_txn().unwrap();
+        for (key, value) in create_test_pairs() {
+            let write_result = write::<_, _, _, LmdbTrieStore, error::Error>(
+                correlation_id,
+                &mut txn,
+                &source.trie_store,
+                &state_root,
+                &key,
+                &value,
+            )
+            .unwrap();
+            if let WriteResult::Written(root_hash) = write_result {
+                state_root = root_hash;
+            }
+        }
+        txn.commit().unwrap();
+    }
+
+    let mut snapshot = Vec::new();
+    let exported = super::export::<Key, StoredValue, _, LmdbTrieStore, _, error::Error>(
+        source.environment.as_ref(),
+        source.trie_store.as_ref(),
+        &state_root,
+        &mut snapshot,
+        DEFAULT_CHUNK_SIZE,
+    )
+    .unwrap();
+    assert!(exported > usize::from(TEST_PAIRS_LENGTH));
+
+    let target = InMemoryGlobalState::empty().unwrap();
+    let imported_root = import_in_memory_state(&target, &snapshot).unwrap();
+    assert_eq!(imported_root, state_root);
+    check_test_pairs(&target, state_root);
+}
+
+#[test]
+fn export_fails_if_unknown_root_is_given() {
+    let (source, _) = create_in_memory_state();
+    let fake_root: Blake2bHash = [1u8; 32].into();
+    assert_eq!(
+        export_in_memory_state(&source, &fake_root, DEFAULT_CHUNK_SIZE),
+        Err(error::Error::Snapshot(Error::MissingTrie(fake_root)))
+    );
+}
+
+#[test]
+fn import_rejects_invalid_header() {
+    let target = InMemoryGlobalState::empty().unwrap();
+
+    let mut snapshot = b"NOTASNAP".to_vec();
+    snapshot.push(VERSION);
+    assert_eq!(
+        import_in_memory_state(&target, &snapshot),
+        Err(error::Error::Snapshot(Error::InvalidMagic))
+    );
+
+    let mut snapshot = MAGIC.to_vec();
+    snapshot.push(VERSION + 1);
+    assert_eq!(
+        import_in_memory_state(&target, &snapshot),
+        Err(error::Error::Snapshot(Error::UnsupportedVersion(
+            VERSION + 1
+        )))
+    );
+}
+
+#[test]
+fn import_rejects_tampered_trie() {
+    let (source, state_root) = create_in_memory_state();
+    let root: Trie<Key, StoredValue> = {
+        let txn = source.environment.create_read_txn().unwrap();
+        source.trie_store.get(&txn, &state_root).unwrap().unwrap()
+    };
+    let tampered: Trie<Key, StoredValue> = Trie::Leaf {
+        key: Key::Account(AccountHash::new([42; 32])),
+        value: StoredValue::CLValue(CLValue::from_t(42).unwrap()),
+    };
+
+    let mut snapshot = Vec::new();
+    write_header(&mut snapshot, &state_root).unwrap();
+    write_chunk(
+        &mut snapshot,
+        vec![
+            (state_root, root.to_bytes().unwrap()),
+            (state_root, tampered.to_bytes().unwrap()),
+        ],
+    )
+    .unwrap();
+    snapshot.extend_from_slice(&[0; 4]);
+
+    let target = InMemoryGlobalState::empty().unwrap();
+    match import_in_memory_state(&target, &snapshot) {
+        Err(error::Error::Snapshot(Error::HashMismatch { expected, .. })) => {
+            assert_eq!(expected, state_root)
+        }
+        result => panic!("unexpected import result: {:?}", result),
+    }
+    assert!(target.checkout(state_root).unwrap().is_none());
+}
+
+#[test]
+fn import_rejects_incomplete_snapshot() {
+    let (source, state_root) = create_in_memory_state();
+    let snapshot = export_in_memory_state(&source, &state_root, 1).unwrap();
+
+    // With a single trie per chunk, dropping the last chunk leaves out a single trie.
+    let mut offset = MAGIC.len() + 1 + BLAKE2B_DIGEST_LENGTH;
+    let mut last_chunk_start = offset;
+    loop {
+        let length = u32::from_le_bytes(snapshot[offset..offset + 4].try_into().unwrap());
+        if length == 0 {
+            break;
+        }
+        last_chunk_start = offset;
+        offset += 4 + length as usize;
+    }
+    let mut truncated = snapshot[..last_chunk_start].to_vec();
+    truncated.extend_from_slice(&[0; 4]);
+
+    let target = InMemoryGlobalState::empty().unwrap();
+    match import_in_memory_state(&target, &truncated) {
+        Err(error::Error::Snapshot(Error::MissingTrie(_))) => (),
+        result => panic!("unexpected import result: {:?}", result),
+    }
+    assert!(target.checkout(state_root).unwrap().is_none());
+}
@@ -42,4 +42,27 @@ pub trait Store<K, V> {
         txn.write(handle, &key.to_bytes()?, &value.to_bytes()?)
             .map_err(Into::into)
     }
+
+    fn delete<T>(&self, txn: &mut T, key: &K) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        K: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        let handle = self.handle();
+        txn.delete(handle, &key.to_bytes()?).map_err(Into::into)
+    }
+
+    fn keys<T>(&self, txn: &T) -> Result<Vec<K>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        K: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        let handle = self.handle();
+        txn.keys(handle)?
+            .into_iter()
+            .map(|key_bytes| bytesrepr::deserialize(key_bytes).map_err(Into::into))
+            .collect()
+    }
 }
@@ -52,6 +52,14 @@ impl Readable for InMemoryReadTransaction {
         };
         Ok(sub_view.get(&key.to_vec()).cloned())
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        Ok(sub_view.keys().cloned().collect())
+    }
 }
 
 /// A read-write transaction for the in-memory trie store.
@@ -97,6 +105,14 @@ impl<'a> Readable for InMemoryReadWriteTransaction<'a> {
         };
         Ok(sub_view.get(&key.to_vec()).cloned())
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        Ok(sub_view.keys().cloned().collect())
+    }
 }
 
 impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
@@ -105,6 +121,13 @@ impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
         sub_view.insert(key.to_vec(), value.to_vec());
         Ok(())
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        if let Some(sub_view) = self.view.get_mut(&handle) {
+            sub_view.remove(key);
+        }
+        Ok(())
+    }
 }
 
 /// An environment for the in-memory trie store.
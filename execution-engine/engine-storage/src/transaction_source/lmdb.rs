@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{self, Cursor, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
 
 use crate::{
     error,
@@ -26,6 +26,11 @@ impl<'a> Readable for RoTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(self, handle)?;
+        Ok(cursor.iter_start().map(|(key, _)| key.to_vec()).collect())
+    }
 }
 
 impl<'a> Transaction for RwTransaction<'a> {
@@ -46,6 +51,11 @@ impl<'a> Readable for RwTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(self, handle)?;
+        Ok(cursor.iter_start().map(|(key, _)| key.to_vec()).collect())
+    }
 }
 
 impl<'a> Writable for RwTransaction<'a> {
@@ -53,6 +63,13 @@ impl<'a> Writable for RwTransaction<'a> {
         self.put(handle, &key, &value, WriteFlags::empty())
             .map_err(Into::into)
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// The environment for an LMDB-backed trie store.
@@ -1,5 +1,6 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod rocksdb;
 
 /// A transaction which can be committed or aborted.
 pub trait Transaction: Sized {
@@ -25,12 +26,20 @@ pub trait Transaction: Sized {
 pub trait Readable: Transaction {
     /// Returns the value from the corresponding key from a given [`Transaction::Handle`].
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Returns all of the keys stored in a given [`Transaction::Handle`].
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error>;
 }
 
 /// A transaction with the capability to write to a given [`Handle`](Transaction::Handle).
 pub trait Writable: Transaction {
     /// Inserts a key-value pair into a given [`Transaction::Handle`].
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
+
+    /// Removes a key and its value from a given [`Transaction::Handle`].
+    ///
+    /// Removing a key which is not present is not an error.
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error>;
 }
 
 /// A source of transactions e.g. values that implement [`Readable`]
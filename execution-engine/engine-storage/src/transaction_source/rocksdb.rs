@@ -0,0 +1,207 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+};
+
+use rocksdb::{Direction, IteratorMode, Options, Snapshot, WriteBatch, DB};
+
+use crate::{
+    error,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+};
+
+/// A marker for use in a mutex which represents the capability to perform a
+/// write transaction.
+struct WriteCapability;
+
+type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
+
+/// Returns the key under which `key` is stored in the database for the store identified by
+/// `handle`.
+///
+/// All stores share the database's default column family, so each store's keys are prefixed with
+/// its length-prefixed name to keep them apart.
+fn prefixed_key(handle: &str, key: &[u8]) -> Vec<u8> {
+    let mut ret = Vec::with_capacity(1 + handle.len() + key.len());
+    ret.push(handle.len() as u8);
+    ret.extend_from_slice(handle.as_bytes());
+    ret.extend_from_slice(key);
+    ret
+}
+
+/// Returns the prefix shared by every key of the store identified by `handle`.
+fn key_prefix(handle: &str) -> Vec<u8> {
+    prefixed_key(handle, &[])
+}
+
+/// Collects the keys yielded by a database iterator positioned at `prefix`, stripped of that
+/// prefix, stopping at the first key which belongs to a different store.
+fn collect_keys<I>(iter: I, prefix: &[u8]) -> Vec<Vec<u8>>
+where
+    I: Iterator<Item = (Box<[u8]>, Box<[u8]>)>,
+{
+    iter.take_while(|(key, _)| key.starts_with(prefix))
+        .map(|(key, _)| key[prefix.len()..].to_vec())
+        .collect()
+}
+
+/// A read transaction for a RocksDB-backed store.
+///
+/// Reads are served from a snapshot of the database taken when the transaction was created.
+pub struct RocksDbReadTransaction<'a> {
+    snapshot: Snapshot<'a>,
+}
+
+impl<'a> Transaction for RocksDbReadTransaction<'a> {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a> Readable for RocksDbReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let maybe_bytes = self.snapshot.get(prefixed_key(&handle, key))?;
+        Ok(maybe_bytes.map(|bytes| bytes.to_vec()))
+    }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let prefix = key_prefix(&handle);
+        let iter = self
+            .snapshot
+            .iterator(IteratorMode::From(&prefix, Direction::Forward));
+        Ok(collect_keys(iter, &prefix))
+    }
+}
+
+/// A read-write transaction for a RocksDB-backed store.
+///
+/// Writes and deletions are kept in memory until the transaction is committed, at which point they
+/// are applied atomically as a single [`WriteBatch`].  Only one read-write transaction can be open
+/// at a time.
+pub struct RocksDbReadWriteTransaction<'a> {
+    db: &'a DB,
+    /// Pending changes, where `None` marks a deleted key.
+    pending: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+    _write_lock: WriteLock<'a>,
+}
+
+impl<'a> Transaction for RocksDbReadWriteTransaction<'a> {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        let mut batch = WriteBatch::default();
+        for (key, maybe_value) in self.pending.iter() {
+            match maybe_value {
+                Some(value) => batch.put(key, value)?,
+                None => batch.delete(key)?,
+            }
+        }
+        self.db.write(batch).map_err(Into::into)
+    }
+}
+
+impl<'a> Readable for RocksDbReadWriteTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let key = prefixed_key(&handle, key);
+        if let Some(maybe_value) = self.pending.get(&key) {
+            return Ok(maybe_value.to_owned());
+        }
+        let maybe_bytes = self.db.get(key)?;
+        Ok(maybe_bytes.map(|bytes| bytes.to_vec()))
+    }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let prefix = key_prefix(&handle);
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(&prefix, Direction::Forward));
+        let mut keys: BTreeSet<Vec<u8>> = collect_keys(iter, &prefix).into_iter().collect();
+        for (key, maybe_value) in self.pending.range(prefix.clone()..) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            let key = key[prefix.len()..].to_vec();
+            match maybe_value {
+                Some(_) => keys.insert(key),
+                None => keys.remove(&key),
+            };
+        }
+        Ok(keys.into_iter().collect())
+    }
+}
+
+impl<'a> Writable for RocksDbReadWriteTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        self.pending
+            .insert(prefixed_key(&handle, key), Some(value.to_vec()));
+        Ok(())
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        self.pending.insert(prefixed_key(&handle, key), None);
+        Ok(())
+    }
+}
+
+/// The environment for a RocksDB-backed store.
+///
+/// Wraps [`rocksdb::DB`].
+pub struct RocksDbEnvironment {
+    path: PathBuf,
+    db: DB,
+    write_mutex: Mutex<WriteCapability>,
+}
+
+impl RocksDbEnvironment {
+    pub fn new(path: &PathBuf) -> Result<Self, error::Error> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        let db = DB::open(&options, path)?;
+        let path = path.to_owned();
+        let write_mutex = Mutex::new(WriteCapability);
+        Ok(RocksDbEnvironment {
+            path,
+            db,
+            write_mutex,
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+}
+
+impl<'a> TransactionSource<'a> for RocksDbEnvironment {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    type ReadTransaction = RocksDbReadTransaction<'a>;
+
+    type ReadWriteTransaction = RocksDbReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<RocksDbReadTransaction<'a>, Self::Error> {
+        let snapshot = self.db.snapshot();
+        Ok(RocksDbReadTransaction { snapshot })
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<RocksDbReadWriteTransaction<'a>, Self::Error> {
+        let _write_lock = self.write_mutex.lock()?;
+        Ok(RocksDbReadWriteTransaction {
+            db: &self.db,
+            pending: BTreeMap::new(),
+            _write_lock,
+        })
+    }
+}
@@ -0,0 +1,200 @@
+//! Merkle proofs of the inclusion of a key-value pair in a Merkle Trie.
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+use crate::trie::{Pointer, Trie, RADIX};
+
+const NODE_TAG: u8 = 0;
+const EXTENSION_TAG: u8 = 1;
+
+/// A step of a [`TrieMerkleProof`], describing a single trie on the path from a leaf to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieMerkleProofStep {
+    /// A [`Trie::Node`], minus the pointer at `hole_index` which leads towards the leaf.
+    Node {
+        hole_index: u8,
+        indexed_pointers_with_hole: Vec<(u8, Pointer)>,
+    },
+    /// A [`Trie::Extension`], minus the pointer which leads towards the leaf.
+    Extension { affix: Vec<u8> },
+}
+
+impl TrieMerkleProofStep {
+    /// Constructs a [`TrieMerkleProofStep::Node`] from the given pointers.
+    pub fn node(hole_index: u8, indexed_pointers_with_hole: Vec<(u8, Pointer)>) -> Self {
+        TrieMerkleProofStep::Node {
+            hole_index,
+            indexed_pointers_with_hole,
+        }
+    }
+
+    /// Constructs a [`TrieMerkleProofStep::Extension`] from the given affix.
+    pub fn extension(affix: Vec<u8>) -> Self {
+        TrieMerkleProofStep::Extension { affix }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            TrieMerkleProofStep::Node { .. } => NODE_TAG,
+            TrieMerkleProofStep::Extension { .. } => EXTENSION_TAG,
+        }
+    }
+}
+
+impl ToBytes for TrieMerkleProofStep {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.push(self.tag());
+        match self {
+            TrieMerkleProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                ret.append(&mut hole_index.to_bytes()?);
+                ret.append(&mut indexed_pointers_with_hole.to_bytes()?);
+            }
+            TrieMerkleProofStep::Extension { affix } => {
+                ret.append(&mut affix.to_bytes()?);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                TrieMerkleProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    hole_index.serialized_length() + indexed_pointers_with_hole.serialized_length()
+                }
+                TrieMerkleProofStep::Extension { affix } => affix.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for TrieMerkleProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            NODE_TAG => {
+                let (hole_index, rem) = u8::from_bytes(rem)?;
+                let (indexed_pointers_with_hole, rem) = Vec::<(u8, Pointer)>::from_bytes(rem)?;
+                Ok((
+                    TrieMerkleProofStep::node(hole_index, indexed_pointers_with_hole),
+                    rem,
+                ))
+            }
+            EXTENSION_TAG => {
+                let (affix, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((TrieMerkleProofStep::extension(affix), rem))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A proof that a key-value pair is stored in a Merkle Trie.
+///
+/// The proof steps are ordered from the parent of the leaf up to the root, so the state hash of the
+/// trie can be recomputed by hashing the leaf and then each step in turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    value: V,
+    proof_steps: Vec<TrieMerkleProofStep>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    /// Constructs a proof from a key-value pair and the steps leading from it to the root.
+    pub fn new(key: K, value: V, proof_steps: Vec<TrieMerkleProofStep>) -> Self {
+        TrieMerkleProof {
+            key,
+            value,
+            proof_steps,
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn take_value(self) -> V {
+        self.value
+    }
+
+    pub fn proof_steps(&self) -> &[TrieMerkleProofStep] {
+        &self.proof_steps
+    }
+}
+
+impl<K, V> TrieMerkleProof<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Recomputes the state hash of the trie this proof was taken from.
+    ///
+    /// The proof is valid for a given state root hash if and only if the result is equal to it.
+    pub fn compute_state_hash(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        let leaf: Trie<K, V> = Trie::leaf(self.key.clone(), self.value.clone());
+        let mut hash = Blake2bHash::new(&leaf.to_bytes()?);
+
+        for (step_index, proof_step) in self.proof_steps.iter().enumerate() {
+            let pointer = if step_index == 0 {
+                Pointer::LeafPointer(hash)
+            } else {
+                Pointer::NodePointer(hash)
+            };
+            let trie: Trie<K, V> = match proof_step {
+                TrieMerkleProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    let mut indexed_pointers: Vec<(usize, Pointer)> = indexed_pointers_with_hole
+                        .iter()
+                        .map(|(index, pointer)| (usize::from(*index), *pointer))
+                        .collect();
+                    assert!(usize::from(*hole_index) < RADIX, "hole index out of range");
+                    indexed_pointers.push((usize::from(*hole_index), pointer));
+                    Trie::node(&indexed_pointers)
+                }
+                TrieMerkleProofStep::Extension { affix } => Trie::extension(affix.clone(), pointer),
+            };
+            hash = Blake2bHash::new(&trie.to_bytes()?);
+        }
+
+        Ok(hash)
+    }
+}
+
+impl<K: ToBytes, V: ToBytes> ToBytes for TrieMerkleProof<K, V> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.key.to_bytes()?);
+        ret.append(&mut self.value.to_bytes()?);
+        ret.append(&mut self.proof_steps.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.value.serialized_length()
+            + self.proof_steps.serialized_length()
+    }
+}
+
+impl<K: FromBytes, V: FromBytes> FromBytes for TrieMerkleProof<K, V> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem) = K::from_bytes(bytes)?;
+        let (value, rem) = V::from_bytes(rem)?;
+        let (proof_steps, rem) = Vec::<TrieMerkleProofStep>::from_bytes(rem)?;
+        Ok((TrieMerkleProof::new(key, value, proof_steps), rem))
+    }
+}
@@ -5,6 +5,7 @@
 pub mod in_memory;
 pub mod lmdb;
 pub(crate) mod operations;
+pub mod rocksdb;
 #[cfg(test)]
 mod tests;
 
@@ -1,32 +1,51 @@
 #[cfg(test)]
 mod tests;
 
-use std::{cmp, collections::VecDeque, mem, time::Instant};
+use std::{
+    cell::RefCell,
+    cmp,
+    collections::{HashMap, HashSet, VecDeque},
+    mem,
+    time::Instant,
+};
 
 use engine_shared::{
     logging::{log_duration, log_metric},
+    metrics,
     newtypes::{Blake2bHash, CorrelationId},
 };
 use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
+    store::Store,
     transaction_source::{Readable, Writable},
-    trie::{self, Parents, Pointer, Trie, RADIX},
+    trie::{
+        self,
+        merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+        Parents, Pointer, Trie, RADIX,
+    },
     trie_store::TrieStore,
     GAUGE_METRIC_KEY,
 };
 
 const TRIE_STORE_READ_DURATION: &str = "trie_store_read_duration";
 const TRIE_STORE_READ_GETS: &str = "trie_store_read_gets";
+const TRIE_STORE_READ_WITH_PROOF_DURATION: &str = "trie_store_read_with_proof_duration";
+const TRIE_STORE_MISSING_TRIE_KEYS_DURATION: &str = "trie_store_missing_trie_keys_duration";
+const TRIE_STORE_MISSING_TRIE_KEYS_GETS: &str = "trie_store_missing_trie_keys_gets";
 const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
+const TRIE_STORE_DELETE_PUTS: &str = "trie_store_delete_puts";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
+const DELETE: &str = "delete";
 const PUT: &str = "put";
+const MISSING_TRIE_KEYS: &str = "missing_trie_keys";
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReadResult<V> {
@@ -51,6 +70,8 @@ where
     S::Error: From<T::Error>,
     E: From<S::Error> + From<types::bytesrepr::Error>,
 {
+    metrics::record_trie_read();
+
     let path: Vec<u8> = key.to_bytes()?;
 
     let mut depth: usize = 0;
@@ -199,6 +220,188 @@ where
     }
 }
 
+/// Returns a value from the corresponding key at a given root in a given store, along with a proof
+/// that it is stored under that root.
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadResult<TrieMerkleProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut depth: usize = 0;
+    let mut proof_steps: Vec<TrieMerkleProofStep> = Vec::new();
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+
+    let start = Instant::now();
+
+    let result = loop {
+        let pointer = match current {
+            Trie::Leaf {
+                key: leaf_key,
+                value: leaf_value,
+            } => {
+                if *key != leaf_key {
+                    break ReadResult::NotFound;
+                }
+                // Steps were collected from the root down, but a proof is verified from the leaf
+                // up.
+                proof_steps.reverse();
+                break ReadResult::Found(TrieMerkleProof::new(leaf_key, leaf_value, proof_steps));
+            }
+            Trie::Node { pointer_block } => {
+                let hole_index: u8 = {
+                    assert!(depth < path.len(), "depth must be < {}", path.len());
+                    path[depth]
+                };
+                let pointer = match pointer_block[usize::from(hole_index)] {
+                    Some(pointer) => pointer,
+                    None => break ReadResult::NotFound,
+                };
+                let indexed_pointers_with_hole = pointer_block[..]
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| *index != usize::from(hole_index))
+                    .filter_map(|(index, maybe_pointer)| {
+                        maybe_pointer.map(|pointer| (index as u8, pointer))
+                    })
+                    .collect();
+                proof_steps.push(TrieMerkleProofStep::node(
+                    hole_index,
+                    indexed_pointers_with_hole,
+                ));
+                depth += 1;
+                pointer
+            }
+            Trie::Extension { affix, pointer } => {
+                let sub_path = &path[depth..depth + affix.len()];
+                if sub_path != affix.as_slice() {
+                    break ReadResult::NotFound;
+                }
+                depth += affix.len();
+                proof_steps.push(TrieMerkleProofStep::extension(affix));
+                pointer
+            }
+        };
+        current = match store.get(txn, pointer.hash())? {
+            Some(next) => next,
+            None => panic!(
+                "No trie value at key: {:?} (reading from key: {:?})",
+                pointer.hash(),
+                key
+            ),
+        };
+    };
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_READ_WITH_PROOF_DURATION,
+        READ,
+        start.elapsed(),
+    );
+
+    Ok(result)
+}
+
+/// Returns the hashes of the tries which are needed to complete the tries under `trie_keys`, but
+/// are missing from the store.
+///
+/// Each of the `trie_keys` which is present is descended into, so the result holds every missing
+/// trie which is reachable from them through tries already in the store.  A state is complete once
+/// this returns no hashes for its root.
+pub fn missing_trie_keys<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    trie_keys: Vec<Blake2bHash>,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    K: FromBytes,
+    V: FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let mut missing: Vec<Blake2bHash> = Vec::new();
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = trie_keys;
+
+    while let Some(trie_key) = to_visit.pop() {
+        if !visited.insert(trie_key) {
+            continue;
+        }
+        get_counter += 1;
+        match store.get(txn, &trie_key)? {
+            Some(Trie::Leaf { .. }) => (),
+            Some(Trie::Node { pointer_block }) => to_visit.extend(
+                pointer_block[..]
+                    .iter()
+                    .flatten()
+                    .map(|pointer| *pointer.hash()),
+            ),
+            Some(Trie::Extension { pointer, .. }) => to_visit.push(*pointer.hash()),
+            None => missing.push(trie_key),
+        }
+    }
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_MISSING_TRIE_KEYS_DURATION,
+        MISSING_TRIE_KEYS,
+        start.elapsed(),
+    );
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_MISSING_TRIE_KEYS_GETS,
+        GET,
+        GAUGE_METRIC_KEY,
+        f64::from(get_counter),
+    );
+
+    Ok(missing)
+}
+
+/// Stores `trie` under its hash, returning that hash.
+///
+/// The children of `trie` need not be present yet, so a state can be assembled one trie at a time
+/// in any order.  Use [`missing_trie_keys`] to find out which tries are still needed.
+pub fn put_trie<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    trie: &Trie<K, V>,
+) -> Result<Blake2bHash, E>
+where
+    K: ToBytes,
+    V: ToBytes,
+    T: Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let trie_hash = Blake2bHash::new(&trie.to_bytes()?);
+    store.put(txn, &trie_hash, trie)?;
+    Ok(trie_hash)
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -634,6 +837,8 @@ where
     S::Error: From<T::Error>,
     E: From<S::Error> + From<types::bytesrepr::Error>,
 {
+    metrics::record_trie_write();
+
     let start = Instant::now();
     let mut put_counter: i32 = 0;
 
@@ -731,6 +936,290 @@ where
     }
 }
 
+/// Takes a node from which a leaf has just been removed, along with the parents of that node, and
+/// returns the tip and parents to rehash.
+///
+/// A node other than the root has at least two children, so if only one is left the node is
+/// removed: a remaining leaf is moved up to the nearest node above, and a remaining node is joined
+/// to it by an extension, merged with any extensions around the removed node.  This keeps the
+/// trie in exactly the shape that writing its leaves would have given it.
+#[allow(clippy::type_complexity)]
+fn collapse_node<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    pointer_block: Box<trie::PointerBlock>,
+    mut parents: Parents<K, V>,
+) -> Result<(Trie<K, V>, Parents<K, V>), E>
+where
+    K: ToBytes + FromBytes + Clone,
+    V: ToBytes + FromBytes + Clone,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let children: Vec<(usize, Pointer)> = pointer_block[..]
+        .iter()
+        .enumerate()
+        .filter_map(|(index, maybe_pointer)| maybe_pointer.map(|pointer| (index, pointer)))
+        .collect();
+    let (child_index, child_pointer) = match children[..] {
+        [child] if !parents.is_empty() => child,
+        _ => return Ok((Trie::Node { pointer_block }, parents)),
+    };
+
+    // An extension leading to the removed node becomes part of the path to its child.
+    let mut affix = match parents.pop() {
+        Some((_, Trie::Extension { affix, .. })) => affix,
+        Some(parent) => {
+            parents.push(parent);
+            Vec::new()
+        }
+        None => unreachable!(),
+    };
+
+    match child_pointer {
+        Pointer::LeafPointer(_) => {
+            let (index, parent) = parents.pop().expect("parents should not be empty");
+            let mut pointer_block = match parent {
+                Trie::Node { pointer_block } => pointer_block,
+                _ => panic!("An extension should have a node for its parent"),
+            };
+            pointer_block[index.into()] = Some(child_pointer);
+            Ok((Trie::Node { pointer_block }, parents))
+        }
+        Pointer::NodePointer(child_hash) => {
+            affix.push(child_index as u8);
+            let extension = match store.get(txn, &child_hash)? {
+                Some(Trie::Extension {
+                    affix: child_affix,
+                    pointer,
+                }) => {
+                    affix.extend(child_affix);
+                    Trie::extension(affix, pointer)
+                }
+                Some(Trie::Node { .. }) => Trie::extension(affix, child_pointer),
+                Some(Trie::Leaf { .. }) => panic!("A node pointer should not point to a leaf"),
+                None => panic!("No trie value at key: {:?}", child_hash),
+            };
+            Ok((extension, parents))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Removes the leaf under `key` from the trie at `root`.
+///
+/// The resulting trie is the same as the one given by writing all of the other leaves, so its root
+/// hash doesn't depend on whether `key` was ever present.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    metrics::record_trie_write();
+
+    let start = Instant::now();
+    let mut put_counter: i32 = 0;
+
+    let current_root = match store.get(txn, root)? {
+        None => return Ok(DeleteResult::RootNotFound),
+        Some(current_root) => current_root,
+    };
+    let path: Vec<u8> = key.to_bytes()?;
+    let TrieScan { tip, mut parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+    match tip {
+        Trie::Leaf { key: ref leaf_key, .. } if key == leaf_key => (),
+        _ => return Ok(DeleteResult::DoesNotExist),
+    }
+    let (leaf_index, mut pointer_block) = match parents.pop() {
+        Some((index, Trie::Node { pointer_block })) => (index, pointer_block),
+        _ => panic!("A leaf should have a node for its parent"),
+    };
+    pointer_block[leaf_index.into()] = None;
+
+    let (tip, parents) = collapse_node::<K, V, T, S, E>(txn, store, pointer_block, parents)?;
+    let mut root_hash = root.to_owned();
+    for (hash, element) in rehash(tip, parents)?.iter() {
+        put_counter += 1;
+        store.put(txn, hash, element)?;
+        root_hash = *hash;
+    }
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DELETE_PUTS,
+        PUT,
+        GAUGE_METRIC_KEY,
+        f64::from(put_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
+/// An in-memory overlay for a [`TrieStore`] which batches the tries produced by a series of
+/// [`write`]s to the same store.
+///
+/// Tries read from the underlying store are cached, so consecutive writes of keys sharing a path
+/// prefix don't read and deserialize the same intermediate nodes over and over.  Tries put through
+/// the batch are kept in memory until [`WriteBatch::flush`], which persists only the ones reachable
+/// from the final root: intermediate nodes superseded by later writes in the same batch are never
+/// written to the underlying store.
+pub struct WriteBatch<'a, K, V, S> {
+    store: &'a S,
+    cache: RefCell<HashMap<Blake2bHash, Trie<K, V>>>,
+    pending: RefCell<HashMap<Blake2bHash, Trie<K, V>>>,
+}
+
+impl<'a, K, V, S> WriteBatch<'a, K, V, S>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+    S: TrieStore<K, V>,
+{
+    pub fn new(store: &'a S) -> Self {
+        WriteBatch {
+            store,
+            cache: RefCell::new(HashMap::new()),
+            pending: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Writes the batched tries reachable from `root` to the underlying store, discarding the
+    /// rest.
+    ///
+    /// Returns the number of tries written.
+    pub fn flush<T>(self, txn: &mut T, root: &Blake2bHash) -> Result<usize, S::Error>
+    where
+        T: Writable<Handle = S::Handle>,
+        S::Error: From<T::Error>,
+    {
+        let mut pending = self.pending.into_inner();
+        let mut to_visit = vec![*root];
+        let mut put_counter = 0;
+
+        while let Some(hash) = to_visit.pop() {
+            // A trie which is not in the batch is already persisted, along with its descendants.
+            let trie = match pending.remove(&hash) {
+                Some(trie) => trie,
+                None => continue,
+            };
+            match trie {
+                Trie::Leaf { .. } => (),
+                Trie::Node { ref pointer_block } => to_visit.extend(
+                    pointer_block[..]
+                        .iter()
+                        .flatten()
+                        .map(|pointer| *pointer.hash()),
+                ),
+                Trie::Extension { ref pointer, .. } => to_visit.push(*pointer.hash()),
+            }
+            self.store.put(txn, &hash, &trie)?;
+            put_counter += 1;
+        }
+        Ok(put_counter)
+    }
+}
+
+impl<'a, K, V, S> Store<Blake2bHash, Trie<K, V>> for WriteBatch<'a, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: TrieStore<K, V>,
+{
+    type Error = S::Error;
+
+    type Handle = S::Handle;
+
+    fn handle(&self) -> Self::Handle {
+        self.store.handle()
+    }
+
+    fn get<T>(&self, txn: &T, key: &Blake2bHash) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(trie) = self.pending.borrow().get(key) {
+            return Ok(Some(trie.clone()));
+        }
+        if let Some(trie) = self.cache.borrow().get(key) {
+            return Ok(Some(trie.clone()));
+        }
+        let maybe_trie = self.store.get(txn, key)?;
+        if let Some(ref trie) = maybe_trie {
+            self.cache.borrow_mut().insert(*key, trie.clone());
+        }
+        Ok(maybe_trie)
+    }
+
+    fn put<T>(&self, _txn: &mut T, key: &Blake2bHash, value: &Trie<K, V>) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        self.pending.borrow_mut().insert(*key, value.clone());
+        Ok(())
+    }
+
+    fn delete<T>(&self, txn: &mut T, key: &Blake2bHash) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        self.pending.borrow_mut().remove(key);
+        self.cache.borrow_mut().remove(key);
+        self.store.delete(txn, key)
+    }
+
+    fn keys<T>(&self, txn: &T) -> Result<Vec<Blake2bHash>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Blake2bHash: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        let mut keys = self.store.keys(txn)?;
+        let pending = self.pending.borrow();
+        keys.retain(|key| !pending.contains_key(key));
+        keys.extend(pending.keys().copied());
+        Ok(keys)
+    }
+}
+
+impl<'a, K, V, S> TrieStore<K, V> for WriteBatch<'a, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: TrieStore<K, V>,
+{
+}
+
 enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
     /// Iterate normally
     Ok,
@@ -0,0 +1,217 @@
+use super::*;
+use crate::trie_store::operations::WriteBatch;
+
+fn write_leaves_batched<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    leaves: &[Trie<K, V>],
+) -> Result<(Blake2bHash, usize), E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut root_hash = root_hash.to_owned();
+    let mut txn = environment.create_read_write_txn()?;
+    let batch = WriteBatch::new(store);
+
+    for leaf in leaves.iter() {
+        if let Trie::Leaf { key, value } = leaf {
+            let write_result =
+                write::<_, _, _, _, E>(correlation_id, &mut txn, &batch, &root_hash, key, value)?;
+            match write_result {
+                WriteResult::Written(hash) => root_hash = hash,
+                WriteResult::AlreadyExists => (),
+                WriteResult::RootNotFound => panic!("write_leaves_batched given an invalid root"),
+            }
+        } else {
+            panic!("leaves should contain only leaves");
+        }
+    }
+
+    let puts = batch.flush(&mut txn, &root_hash)?;
+    txn.commit()?;
+    Ok((root_hash, puts))
+}
+
+fn final_root(results: Vec<WriteResult>) -> Blake2bHash {
+    match results.last() {
+        Some(WriteResult::Written(root_hash)) => *root_hash,
+        _ => panic!("last write should have been written"),
+    }
+}
+
+#[test]
+fn lmdb_batched_writes_match_sequential_writes() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+
+    let sequential_context = LmdbTestContext::new(&tries).unwrap();
+    let sequential_root = final_root(
+        write_leaves::<_, _, _, _, error::Error>(
+            correlation_id,
+            &sequential_context.environment,
+            &sequential_context.store,
+            &root_hash,
+            &TEST_LEAVES,
+        )
+        .unwrap(),
+    );
+
+    let batched_context = LmdbTestContext::new(&tries).unwrap();
+    let (batched_root, _) = write_leaves_batched::<_, _, _, _, error::Error>(
+        correlation_id,
+        &batched_context.environment,
+        &batched_context.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+
+    assert_eq!(batched_root, sequential_root);
+    check_leaves::<_, _, _, _, error::Error>(
+        correlation_id,
+        &batched_context.environment,
+        &batched_context.store,
+        &batched_root,
+        &TEST_LEAVES,
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_batched_writes_match_sequential_writes() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+
+    let sequential_context = InMemoryTestContext::new(&tries).unwrap();
+    let sequential_root = final_root(
+        write_leaves::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &sequential_context.environment,
+            &sequential_context.store,
+            &root_hash,
+            &TEST_LEAVES,
+        )
+        .unwrap(),
+    );
+
+    let batched_context = InMemoryTestContext::new(&tries).unwrap();
+    let (batched_root, _) = write_leaves_batched::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &batched_context.environment,
+        &batched_context.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+
+    assert_eq!(batched_root, sequential_root);
+    check_leaves::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &batched_context.environment,
+        &batched_context.store,
+        &batched_root,
+        &TEST_LEAVES,
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_batched_writes_only_persist_reachable_tries() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+
+    let sequential_context = InMemoryTestContext::new(&tries).unwrap();
+    let sequential_results = write_leaves::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &sequential_context.environment,
+        &sequential_context.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+    let intermediate_roots: Vec<Blake2bHash> = sequential_results[..TEST_LEAVES_LENGTH - 1]
+        .iter()
+        .map(|result| match result {
+            WriteResult::Written(root_hash) => *root_hash,
+            _ => panic!("write_leaves resulted in non-write"),
+        })
+        .collect();
+
+    let batched_context = InMemoryTestContext::new(&tries).unwrap();
+    let (batched_root, puts) = write_leaves_batched::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &batched_context.environment,
+        &batched_context.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+
+    let sequential_dump = sequential_context
+        .environment
+        .dump::<TestKey, TestValue>(None)
+        .unwrap();
+    let batched_dump = batched_context
+        .environment
+        .dump::<TestKey, TestValue>(None)
+        .unwrap();
+
+    // Everything the batch wrote was written by the sequential writes too, but not vice versa.
+    assert_eq!(batched_dump.len(), tries.len() + puts);
+    assert!(batched_dump.len() < sequential_dump.len());
+    for (hash, trie) in batched_dump.iter() {
+        assert_eq!(sequential_dump.get(hash), Some(trie));
+    }
+
+    // The roots of the intermediate states are superseded within the batch.
+    assert!(batched_dump.contains_key(&batched_root));
+    for intermediate_root in intermediate_roots.iter() {
+        assert!(!batched_dump.contains_key(intermediate_root));
+    }
+}
+
+#[test]
+fn in_memory_unflushed_batch_writes_nothing() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let new_root = {
+        let mut txn = context.environment.create_read_write_txn().unwrap();
+        let batch = WriteBatch::new(&context.store);
+        let new_root = match &TEST_LEAVES[0] {
+            Trie::Leaf { key, value } => match write::<_, _, _, _, in_memory::Error>(
+                correlation_id,
+                &mut txn,
+                &batch,
+                &root_hash,
+                key,
+                value,
+            )
+            .unwrap()
+            {
+                WriteResult::Written(hash) => hash,
+                _ => panic!("write should have been written"),
+            },
+            _ => panic!("leaves should contain only leaves"),
+        };
+        txn.commit().unwrap();
+        new_root
+    };
+
+    let dump = context
+        .environment
+        .dump::<TestKey, TestValue>(None)
+        .unwrap();
+    assert_eq!(dump.len(), tries.len());
+    assert!(!dump.contains_key(&new_root));
+}
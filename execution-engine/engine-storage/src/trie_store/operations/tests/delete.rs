@@ -0,0 +1,188 @@
+use std::slice;
+
+use super::*;
+use crate::trie_store::operations::{delete, DeleteResult};
+
+fn delete_leaves<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    leaves: &[Trie<K, V>],
+) -> Result<Vec<DeleteResult>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut results = Vec::new();
+    let mut root_hash = root_hash.to_owned();
+    let mut txn = environment.create_read_write_txn()?;
+
+    for leaf in leaves.iter() {
+        if let Trie::Leaf { key, .. } = leaf {
+            let delete_result =
+                delete::<K, V, _, _, E>(correlation_id, &mut txn, store, &root_hash, key)?;
+            if let DeleteResult::Deleted(hash) = delete_result {
+                root_hash = hash;
+            }
+            results.push(delete_result);
+        } else {
+            panic!("leaves should contain only leaves");
+        }
+    }
+    txn.commit()?;
+    Ok(results)
+}
+
+#[test]
+fn lmdb_deleting_last_leaf_of_n_leaf_trie_gives_n_minus_one_leaf_trie() {
+    for num_leaves in 1..TEST_TRIE_GENERATORS_LENGTH {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+        let (expected_root_hash, _) = TEST_TRIE_GENERATORS[num_leaves - 1]().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        let results = delete_leaves::<_, _, _, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &TEST_LEAVES[num_leaves - 1..num_leaves],
+        )
+        .unwrap();
+        assert_eq!(results, vec![DeleteResult::Deleted(expected_root_hash)]);
+
+        check_leaves::<_, _, _, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &expected_root_hash,
+            &TEST_LEAVES[..num_leaves - 1],
+            &TEST_LEAVES[num_leaves - 1..],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_deleting_last_leaf_of_n_leaf_trie_gives_n_minus_one_leaf_trie() {
+    for num_leaves in 1..TEST_TRIE_GENERATORS_LENGTH {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+        let (expected_root_hash, _) = TEST_TRIE_GENERATORS[num_leaves - 1]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        let results = delete_leaves::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &TEST_LEAVES[num_leaves - 1..num_leaves],
+        )
+        .unwrap();
+        assert_eq!(results, vec![DeleteResult::Deleted(expected_root_hash)]);
+
+        check_leaves::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &expected_root_hash,
+            &TEST_LEAVES[..num_leaves - 1],
+            &TEST_LEAVES[num_leaves - 1..],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_deleting_any_leaf_gives_trie_written_without_it() {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, empty_tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+
+    for (index, deleted) in TEST_LEAVES.iter().enumerate() {
+        let remaining: Vec<TestTrie> = TEST_LEAVES
+            .iter()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .map(|(_, leaf)| leaf.to_owned())
+            .collect();
+        let expected_context = InMemoryTestContext::new(&empty_tries).unwrap();
+        let expected_root_hash = match write_leaves::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &expected_context.environment,
+            &expected_context.store,
+            &empty_root_hash,
+            &remaining,
+        )
+        .unwrap()
+        .last()
+        {
+            Some(WriteResult::Written(hash)) => *hash,
+            _ => panic!("last write should have been written"),
+        };
+
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let results = delete_leaves::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            slice::from_ref(deleted),
+        )
+        .unwrap();
+        assert_eq!(results, vec![DeleteResult::Deleted(expected_root_hash)]);
+
+        check_leaves::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &expected_root_hash,
+            &remaining,
+            slice::from_ref(deleted),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_deleting_all_leaves_gives_empty_trie() {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, _) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let results = delete_leaves::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+    assert_eq!(
+        results.last(),
+        Some(&DeleteResult::Deleted(empty_root_hash))
+    );
+}
+
+#[test]
+fn in_memory_deleting_missing_leaf_does_nothing() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH - 1]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let results = delete_leaves::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        &TEST_LEAVES[TEST_LEAVES_LENGTH - 1..],
+    )
+    .unwrap();
+    assert_eq!(results, vec![DeleteResult::DoesNotExist]);
+}
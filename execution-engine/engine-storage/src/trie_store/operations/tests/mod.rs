@@ -1,7 +1,10 @@
+mod batch;
+mod delete;
 mod keys;
 mod proptests;
 mod read;
 mod scan;
+mod synchronize;
 mod write;
 
 use std::{collections::HashMap, convert};
@@ -1,4 +1,4 @@
-//! This module contains tests for [`StateReader::read`].
+//! This module contains tests for [`StateReader::read`] and [`StateReader::read_with_proof`].
 //!
 //! Our primary goal here is to test this functionality in isolation.
 //! Therefore, we manually construct test tries from a well-known set of
@@ -126,3 +126,126 @@ mod full_tries {
         }
     }
 }
+
+mod proofs {
+    //! Here we read every leaf of each of the "full" tries along with a proof
+    //! of its inclusion, and check that each proof hashes back up to the root
+    //! it was read from.
+
+    use super::*;
+    use crate::trie::merkle_proof::TrieMerkleProof;
+
+    fn check_proofs<'a, R, S, E>(
+        correlation_id: CorrelationId,
+        environment: &'a R,
+        store: &S,
+        root: &Blake2bHash,
+        present: &[TestTrie],
+        absent: &[TestTrie],
+    ) -> Result<(), E>
+    where
+        R: TransactionSource<'a, Handle = S::Handle>,
+        S: TrieStore<TestKey, TestValue>,
+        S::Error: From<R::Error>,
+        E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+    {
+        let txn: R::ReadTransaction = environment.create_read_txn()?;
+
+        for leaf in present {
+            let (key, value) = match leaf {
+                Trie::Leaf { key, value } => (key, value),
+                _ => panic!("leaves should only contain leaves"),
+            };
+            let proof: TrieMerkleProof<TestKey, TestValue> =
+                match operations::read_with_proof::<TestKey, TestValue, _, _, E>(
+                    correlation_id,
+                    &txn,
+                    store,
+                    root,
+                    key,
+                )? {
+                    ReadResult::Found(proof) => proof,
+                    _ => panic!("should find {:?}", key),
+                };
+            assert_eq!(proof.key(), key);
+            assert_eq!(proof.value(), value);
+            assert_eq!(proof.compute_state_hash()?, *root);
+
+            let deserialized: TrieMerkleProof<TestKey, TestValue> =
+                bytesrepr::deserialize(proof.to_bytes()?)?;
+            assert_eq!(proof, deserialized);
+        }
+
+        for leaf in absent {
+            if let Trie::Leaf { key, value } = leaf {
+                let maybe_proof = operations::read_with_proof::<TestKey, TestValue, _, _, E>(
+                    correlation_id,
+                    &txn,
+                    store,
+                    root,
+                    key,
+                )?;
+                if let ReadResult::Found(proof) = maybe_proof {
+                    assert_ne!(proof.value(), value);
+                }
+            }
+        }
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    #[test]
+    fn lmdb_proofs_from_n_leaf_full_trie_hash_to_root() {
+        let correlation_id = CorrelationId::new();
+        let context = LmdbTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+        let mut states: Vec<Blake2bHash> = Vec::new();
+
+        for (state_index, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let (root_hash, tries) = generator().unwrap();
+            context.update(&tries).unwrap();
+            states.push(root_hash);
+
+            for (num_leaves, state) in states[..state_index].iter().enumerate() {
+                let test_leaves = TEST_LEAVES;
+                let (used, unused) = test_leaves.split_at(num_leaves);
+                check_proofs::<_, _, error::Error>(
+                    correlation_id,
+                    &context.environment,
+                    &context.store,
+                    state,
+                    used,
+                    unused,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn in_memory_proofs_from_n_leaf_full_trie_hash_to_root() {
+        let correlation_id = CorrelationId::new();
+        let context = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+        let mut states: Vec<Blake2bHash> = Vec::new();
+
+        for (state_index, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let (root_hash, tries) = generator().unwrap();
+            context.update(&tries).unwrap();
+            states.push(root_hash);
+
+            for (num_leaves, state) in states[..state_index].iter().enumerate() {
+                let test_leaves = TEST_LEAVES;
+                let (used, unused) = test_leaves.split_at(num_leaves);
+                check_proofs::<_, _, in_memory::Error>(
+                    correlation_id,
+                    &context.environment,
+                    &context.store,
+                    state,
+                    used,
+                    unused,
+                )
+                .unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::*;
+use crate::trie_store::operations::{missing_trie_keys, put_trie};
+
+/// Copies the state under `root` from the source to the target store one trie at a time, the way a
+/// syncing node pulls it from a peer.  Returns the number of tries copied.
+fn synchronize<'a, 'b, K, V, R1, S1, R2, S2, E>(
+    correlation_id: CorrelationId,
+    source_environment: &'a R1,
+    source_store: &S1,
+    target_environment: &'b R2,
+    target_store: &S2,
+    root: &Blake2bHash,
+) -> Result<usize, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    R1: TransactionSource<'a, Handle = S1::Handle>,
+    S1: TrieStore<K, V>,
+    S1::Error: From<R1::Error>,
+    R2: TransactionSource<'b, Handle = S2::Handle>,
+    S2: TrieStore<K, V>,
+    S2::Error: From<R2::Error>,
+    E: From<R1::Error>
+        + From<S1::Error>
+        + From<R2::Error>
+        + From<S2::Error>
+        + From<types::bytesrepr::Error>,
+{
+    let mut copied: usize = 0;
+    let mut trie_keys = vec![*root];
+
+    loop {
+        trie_keys = {
+            let txn = target_environment.create_read_txn()?;
+            let missing =
+                missing_trie_keys::<K, V, _, _, E>(correlation_id, &txn, target_store, trie_keys)?;
+            txn.commit()?;
+            missing
+        };
+        if trie_keys.is_empty() {
+            return Ok(copied);
+        }
+
+        for trie_key in trie_keys.iter() {
+            let trie: Trie<K, V> = {
+                let txn = source_environment.create_read_txn()?;
+                let trie = source_store
+                    .get(&txn, trie_key)?
+                    .expect("source should contain every trie");
+                txn.commit()?;
+                trie
+            };
+            let mut txn = target_environment.create_read_write_txn()?;
+            let trie_hash =
+                put_trie::<K, V, _, _, E>(correlation_id, &mut txn, target_store, &trie)?;
+            txn.commit()?;
+            assert_eq!(trie_hash, *trie_key);
+            copied += 1;
+        }
+    }
+}
+
+#[test]
+fn in_memory_synchronize_copies_every_trie() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS_LENGTH - 1]().unwrap();
+    let source = InMemoryTestContext::new(&tries).unwrap();
+    let target = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+
+    let copied = synchronize::<TestKey, TestValue, _, _, _, _, in_memory::Error>(
+        correlation_id,
+        &source.environment,
+        &source.store,
+        &target.environment,
+        &target.store,
+        &root_hash,
+    )
+    .unwrap();
+    assert_eq!(copied, tries.len());
+
+    let txn = target.environment.create_read_txn().unwrap();
+    let leaves_exist = check_leaves_exist::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &target.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+    assert!(leaves_exist.into_iter().all(convert::identity));
+    txn.commit().unwrap();
+}
+
+#[test]
+fn lmdb_synchronize_copies_every_trie() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS_LENGTH - 1]().unwrap();
+    let source = LmdbTestContext::new(&tries).unwrap();
+    let target = LmdbTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+
+    let copied = synchronize::<TestKey, TestValue, _, _, _, _, error::Error>(
+        correlation_id,
+        &source.environment,
+        &source.store,
+        &target.environment,
+        &target.store,
+        &root_hash,
+    )
+    .unwrap();
+    assert_eq!(copied, tries.len());
+
+    let txn = target.environment.create_read_txn().unwrap();
+    let leaves_exist = check_leaves_exist::<_, _, _, _, error::Error>(
+        correlation_id,
+        &txn,
+        &target.store,
+        &root_hash,
+        &TEST_LEAVES,
+    )
+    .unwrap();
+    assert!(leaves_exist.into_iter().all(convert::identity));
+    txn.commit().unwrap();
+}
+
+#[test]
+fn missing_trie_keys_of_complete_state_is_empty() {
+    let correlation_id = CorrelationId::new();
+    for generator in &TEST_TRIE_GENERATORS {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let txn = context.environment.create_read_txn().unwrap();
+        let missing = missing_trie_keys::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &txn,
+            &context.store,
+            vec![root_hash],
+        )
+        .unwrap();
+        assert!(missing.is_empty());
+        txn.commit().unwrap();
+    }
+}
+
+#[test]
+fn missing_trie_keys_lists_children_of_lone_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS_LENGTH - 1]().unwrap();
+    let root = tries
+        .iter()
+        .find(|hashed_trie| hashed_trie.hash == root_hash)
+        .cloned()
+        .unwrap();
+    let context = InMemoryTestContext::new(&[root.clone()]).unwrap();
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let mut missing = missing_trie_keys::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        vec![root_hash],
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    let mut expected: Vec<Blake2bHash> = match root.trie {
+        Trie::Node { pointer_block } => pointer_block[..]
+            .iter()
+            .flatten()
+            .map(|pointer| *pointer.hash())
+            .collect(),
+        Trie::Extension { pointer, .. } => vec![*pointer.hash()],
+        Trie::Leaf { .. } => vec![],
+    };
+    missing.sort();
+    expected.sort();
+    assert_eq!(missing, expected);
+}
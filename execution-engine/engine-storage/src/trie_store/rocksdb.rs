@@ -0,0 +1,41 @@
+//! A RocksDB-backed trie store.
+//!
+//! Used the same way as the [lmdb](../lmdb/index.html#usage) trie store, with a
+//! [`RocksDbEnvironment`] as the source of transactions.
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error,
+    store::Store,
+    transaction_source::rocksdb::RocksDbEnvironment,
+    trie::Trie,
+    trie_store::{self, TrieStore},
+};
+
+/// A RocksDB-backed trie store.
+#[derive(Debug, Clone)]
+pub struct RocksDbTrieStore {
+    name: String,
+}
+
+impl RocksDbTrieStore {
+    pub fn new(_env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", trie_store::NAME, name))
+            .unwrap_or_else(|| String::from(trie_store::NAME));
+        RocksDbTrieStore { name }
+    }
+}
+
+impl<K, V> Store<Blake2bHash, Trie<K, V>> for RocksDbTrieStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.name.clone()
+    }
+}
+
+impl<K, V> TrieStore<K, V> for RocksDbTrieStore {}
@@ -19,6 +19,10 @@ struct DeployItemData {
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<AccountHash>,
     pub deploy_hash: DeployHash,
+    pub timestamp: u64,
+    pub ttl_millis: u32,
+    pub dependencies: Vec<DeployHash>,
+    pub nonce: u64,
 }
 
 pub struct DeployItemBuilder {
@@ -215,6 +219,22 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_ttl(mut self, timestamp: u64, ttl_millis: u32) -> Self {
+        self.deploy_item.timestamp = timestamp;
+        self.deploy_item.ttl_millis = ttl_millis;
+        self
+    }
+
+    pub fn with_dependencies(mut self, dependencies: &[DeployHash]) -> Self {
+        self.deploy_item.dependencies = dependencies.to_vec();
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.deploy_item.nonce = nonce;
+        self
+    }
+
     pub fn build(self) -> DeployItem {
         DeployItem {
             address: self
@@ -232,6 +252,10 @@ impl DeployItemBuilder {
             gas_price: self.deploy_item.gas_price,
             authorization_keys: self.deploy_item.authorization_keys,
             deploy_hash: self.deploy_item.deploy_hash,
+            timestamp: self.deploy_item.timestamp,
+            ttl_millis: self.deploy_item.ttl_millis,
+            dependencies: self.deploy_item.dependencies,
+            nonce: self.deploy_item.nonce,
         }
     }
 
@@ -5,7 +5,7 @@ use engine_core::{
         executable_deploy_item::ExecutableDeployItem, execution_effect::ExecutionEffect,
         EngineConfig, EngineState,
     },
-    execution::{self, AddressGenerator},
+    execution::{self, AddressGenerator, WasmInstance},
     runtime::{self, Runtime},
     runtime_context::RuntimeContext,
 };
@@ -15,10 +15,12 @@ use engine_storage::{global_state::StateProvider, protocol_data::ProtocolData};
 use engine_wasm_prep::Preprocessor;
 use types::{
     account::AccountHash, bytesrepr::FromBytes, BlockTime, CLTyped, EntryPointType, Key, Phase,
-    ProtocolVersion, RuntimeArgs, URef, U512,
+    RuntimeArgs, URef, U512,
 };
 
-use crate::internal::{utils, WasmTestBuilder, DEFAULT_WASM_COSTS};
+use crate::internal::{
+    utils, WasmTestBuilder, DEFAULT_GAS_PRICE, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+};
 
 /// This function allows executing the contract stored in the given `wasm_file`, while capturing the
 /// output. It is essentially the same functionality as `Executor::exec`, but the return value of
@@ -66,7 +68,7 @@ where
         Rc::new(RefCell::new(fn_store_id))
     };
     let gas_limit = Gas::new(U512::from(std::u64::MAX));
-    let protocol_version = ProtocolVersion::V1_0_0;
+    let protocol_version = *DEFAULT_PROTOCOL_VERSION;
     let correlation_id = CorrelationId::new();
     let base_key = Key::Account(address);
 
@@ -85,7 +87,13 @@ where
         let mint = builder.get_mint_contract_hash();
         let pos = builder.get_mint_contract_hash();
         let standard_payment = builder.get_standard_payment_contract_hash();
-        ProtocolData::new(*DEFAULT_WASM_COSTS, mint, pos, standard_payment)
+        ProtocolData::new(
+            *DEFAULT_WASM_COSTS,
+            DEFAULT_GAS_PRICE,
+            mint,
+            pos,
+            standard_payment,
+        )
     };
 
     let context = RuntimeContext::new(
@@ -130,43 +138,37 @@ where
         )
         .expect("should get wasm module");
 
-    let (instance, memory) =
-        runtime::instance_and_memory(parity_module.clone().take_module(), protocol_version)
-            .expect("should be able to make wasm instance from module");
+    let (instance, memory) = runtime::instance_and_memory(
+        parity_module.clone().take_module(),
+        protocol_version,
+        config.max_memory_pages(),
+    )
+    .expect("should be able to make wasm instance from module");
 
+    let call_stack = vec![parity_module.call_stack_element()];
     let mut runtime = Runtime::new(
         config,
         Default::default(),
         memory,
         parity_module.take_module(),
         context,
+        call_stack,
     );
 
-    match instance.invoke_export(entry_point_name, &[], &mut runtime) {
+    match instance.invoke(entry_point_name, &mut runtime) {
         Ok(_) => None,
-        Err(e) => {
-            if let Some(host_error) = e.as_host_error() {
-                // `ret` Trap is a success; downcast and attempt to extract result
-                let downcasted_error = host_error.downcast_ref::<execution::Error>().unwrap();
-                match downcasted_error {
-                    execution::Error::Ret(ref ret_urefs) => {
-                        let effect = runtime.context().effect();
-                        let urefs = ret_urefs.clone();
-
-                        let value: T = runtime
-                            .take_host_buffer()
-                            .expect("should have return value in the host_buffer")
-                            .into_t()
-                            .expect("should deserialize return value");
-
-                        Some((value, urefs, effect))
-                    }
-
-                    _ => None,
-                }
-            } else {
-                None
-            }
+        // `ret` error is a success; attempt to extract result
+        Err(execution::Error::Ret(urefs)) => {
+            let effect = runtime.context().effect();
+
+            let value: T = runtime
+                .take_host_buffer()
+                .expect("should have return value in the host_buffer")
+                .into_t()
+                .expect("should deserialize return value");
+
+            Some((value, urefs, effect))
         }
+        Err(_) => None,
     }
 }
@@ -8,7 +8,9 @@ use types::{
     RuntimeArgs,
 };
 
-use crate::internal::{DeployItemBuilder, DEFAULT_BLOCK_TIME, DEFAULT_PAYMENT};
+use crate::internal::{
+    DeployItemBuilder, DEFAULT_BLOCK_TIME, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+};
 
 const ARG_AMOUNT: &str = "amount";
 
@@ -118,7 +120,7 @@ impl Default for ExecuteRequestBuilder {
     fn default() -> Self {
         let mut execute_request: ExecuteRequest = Default::default();
         execute_request.block_time = DEFAULT_BLOCK_TIME;
-        execute_request.protocol_version = ProtocolVersion::V1_0_0;
+        execute_request.protocol_version = *DEFAULT_PROTOCOL_VERSION;
         ExecuteRequestBuilder { execute_request }
     }
 }
@@ -0,0 +1,235 @@
+//! Regression tracking of gas costs against tables checked in next to the tests.
+//!
+//! Each entry of a table maps the name of a snapshot to the gas cost recorded for it, one entry per
+//! line.  Gas costs depend on the features the tests are built with, so each build keeps its own
+//! table, see [`gas_snapshots_file`].  See the `gas_snapshot!` macro for how entries are checked
+//! and updated.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+    sync::Mutex,
+};
+
+use lazy_static::lazy_static;
+
+use engine_shared::gas::Gas;
+use types::U512;
+
+/// Stem of the table file names, relative to the manifest directory of the crate under test.
+pub const GAS_SNAPSHOTS_FILE_STEM: &str = "gas_snapshots";
+
+/// Environment variable which, when set, makes missing and drifted snapshots be recorded rather
+/// than fail.
+pub const UPDATE_GAS_SNAPSHOTS_ENV: &str = "UPDATE_GAS_SNAPSHOTS";
+
+/// The features changing gas costs, which each get their own tables.
+const TABLE_FEATURES: [(&str, bool); 3] = [
+    ("enable-bonding", cfg!(feature = "enable-bonding")),
+    ("use-as-wasm", cfg!(feature = "use-as-wasm")),
+    ("use-system-contracts", cfg!(feature = "use-system-contracts")),
+];
+
+/// Percentage by which a gas cost may differ from its snapshot before the check fails.
+pub const DEFAULT_THRESHOLD_PERCENT: u64 = 1;
+
+lazy_static! {
+    /// Serializes access to the table between tests running on concurrent threads.
+    static ref TABLE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Returns the name of the table file for the features this crate is built with, e.g.
+/// `gas_snapshots.txt` without any of them, and
+/// `gas_snapshots.enable-bonding.use-system-contracts.txt` with both `enable-bonding` and
+/// `use-system-contracts`.
+pub fn gas_snapshots_file() -> String {
+    let mut file_name = GAS_SNAPSHOTS_FILE_STEM.to_string();
+    for (feature, _) in TABLE_FEATURES.iter().filter(|(_, enabled)| *enabled) {
+        file_name.push('.');
+        file_name.push_str(feature);
+    }
+    file_name.push_str(".txt");
+    file_name
+}
+
+fn read_table(path: &Path) -> BTreeMap<String, U512> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return BTreeMap::new(),
+    };
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next().expect("should have snapshot name");
+            let gas = parts
+                .next()
+                .and_then(|gas| U512::from_dec_str(gas).ok())
+                .unwrap_or_else(|| panic!("invalid gas snapshot entry {:?} in {:?}", line, path));
+            (name.to_string(), gas)
+        })
+        .collect()
+}
+
+fn write_table(path: &Path, table: &BTreeMap<String, U512>) {
+    let contents: String = table
+        .iter()
+        .map(|(name, gas)| format!("{} {}\n", name, gas))
+        .collect();
+    fs::write(path, contents)
+        .unwrap_or_else(|error| panic!("should write gas snapshots to {:?}: {}", path, error));
+}
+
+/// Checks `gas` against the snapshot recorded under `name` in the table for this build in
+/// `table_dir`.
+///
+/// A missing snapshot, or one which differs from `gas` by more than `threshold_percent`, fails the
+/// check, unless [`UPDATE_GAS_SNAPSHOTS_ENV`] is set, in which case it is recorded.  Smaller
+/// differences are accepted without touching the table.
+pub fn check_gas_snapshot(table_dir: &Path, name: &str, gas: Gas, threshold_percent: u64) {
+    let path = table_dir.join(gas_snapshots_file());
+    let update = std::env::var_os(UPDATE_GAS_SNAPSHOTS_ENV).is_some();
+    check_table(&path, name, gas, threshold_percent, update);
+}
+
+fn check_table(path: &Path, name: &str, gas: Gas, threshold_percent: u64, update: bool) {
+    let _guard = TABLE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let mut table = read_table(path);
+    let gas = gas.value();
+
+    match table.get(name) {
+        Some(recorded) => {
+            let drift = if gas > *recorded {
+                gas - *recorded
+            } else {
+                *recorded - gas
+            };
+            let allowed = *recorded * U512::from(threshold_percent) / U512::from(100);
+            if drift <= allowed {
+                return;
+            }
+            if !update {
+                panic!(
+                    "gas cost of {} drifted from {} to {}, more than {}%; rerun with {}=1 to \
+                     accept the new cost",
+                    name, recorded, gas, threshold_percent, UPDATE_GAS_SNAPSHOTS_ENV
+                );
+            }
+        }
+        None if !update => panic!(
+            "no gas snapshot of {} in {:?}; rerun with {}=1 to record its cost of {}",
+            name, path, UPDATE_GAS_SNAPSHOTS_ENV, gas
+        ),
+        None => (),
+    }
+
+    table.insert(name.to_string(), gas);
+    write_table(path, &table);
+}
+
+/// Checks a gas cost against the snapshot table of the calling crate for this build.
+///
+/// The snapshot is named after the calling module and `$name`.  The optional third argument
+/// overrides the `DEFAULT_THRESHOLD_PERCENT` by which the cost may drift.
+///
+/// ```ignore
+/// builder.exec(exec_request).expect_success().commit();
+/// gas_snapshot!("transfer", builder.last_exec_gas_cost());
+/// ```
+#[macro_export]
+macro_rules! gas_snapshot {
+    ($name:expr, $gas:expr) => {
+        $crate::gas_snapshot!(
+            $name,
+            $gas,
+            $crate::internal::gas_snapshot::DEFAULT_THRESHOLD_PERCENT
+        )
+    };
+    ($name:expr, $gas:expr, $threshold_percent:expr) => {
+        $crate::internal::gas_snapshot::check_gas_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")),
+            &format!("{}::{}", module_path!(), $name),
+            $gas,
+            $threshold_percent,
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use engine_shared::gas::Gas;
+    use types::U512;
+
+    use super::{check_table, read_table};
+
+    fn table_path(test_name: &str) -> PathBuf {
+        let dir =
+            env::temp_dir().join(format!("gas_snapshot_{}_{}", test_name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("should create table dir");
+        dir.join("gas_snapshots.txt")
+    }
+
+    fn gas(value: u64) -> Gas {
+        Gas::new(U512::from(value))
+    }
+
+    #[test]
+    fn should_record_missing_snapshot_when_updating() {
+        let path = table_path("record");
+
+        check_table(&path, "b", gas(200), 1, true);
+        check_table(&path, "a", gas(100), 1, true);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "a 100\nb 200\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "no gas snapshot of b")]
+    fn should_fail_on_missing_snapshot() {
+        let path = table_path("missing");
+        check_table(&path, "a", gas(100), 1, true);
+
+        check_table(&path, "b", gas(200), 1, false);
+    }
+
+    #[test]
+    fn should_accept_drift_within_threshold() {
+        let path = table_path("within");
+        check_table(&path, "a", gas(1000), 1, true);
+
+        check_table(&path, "a", gas(1010), 1, false);
+        check_table(&path, "a", gas(990), 1, false);
+
+        let table = read_table(&path);
+        assert_eq!(table.get("a"), Some(&U512::from(1000)));
+    }
+
+    #[test]
+    #[should_panic(expected = "gas cost of a drifted from 1000 to 1011")]
+    fn should_fail_on_drift_beyond_threshold() {
+        let path = table_path("beyond");
+        check_table(&path, "a", gas(1000), 1, true);
+
+        check_table(&path, "a", gas(1011), 1, false);
+    }
+
+    #[test]
+    fn should_overwrite_drift_beyond_threshold_when_updating() {
+        let path = table_path("overwrite");
+        check_table(&path, "a", gas(1000), 1, true);
+
+        check_table(&path, "a", gas(1011), 1, true);
+
+        let table = read_table(&path);
+        assert_eq!(table.get("a"), Some(&U512::from(1011)));
+    }
+}
@@ -2,6 +2,8 @@ mod additive_map_diff;
 mod deploy_item_builder;
 pub mod exec_with_return;
 mod execute_request_builder;
+pub mod gas_snapshot;
+mod test_clock;
 mod upgrade_request_builder;
 pub mod utils;
 mod wasm_test_builder;
@@ -12,6 +14,7 @@ use num_traits::identities::Zero;
 use engine_core::engine_state::{
     genesis::{ExecConfig, GenesisAccount, GenesisConfig},
     run_genesis_request::RunGenesisRequest,
+    CONV_RATE,
 };
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, test_utils};
 use engine_wasm_prep::wasm_costs::WasmCosts;
@@ -21,9 +24,11 @@ use super::{DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE};
 pub use additive_map_diff::AdditiveMapDiff;
 pub use deploy_item_builder::DeployItemBuilder;
 pub use execute_request_builder::ExecuteRequestBuilder;
+pub use test_clock::TestClock;
 pub use upgrade_request_builder::UpgradeRequestBuilder;
 pub use wasm_test_builder::{
-    InMemoryWasmTestBuilder, LmdbWasmTestBuilder, WasmTestBuilder, WasmTestResult,
+    InMemoryWasmTestBuilder, LmdbWasmTestBuilder, ScratchWasmTestBuilder, WasmTestBuilder,
+    WasmTestResult,
 };
 
 pub const MINT_INSTALL_CONTRACT: &str = "mint_install.wasm";
@@ -33,6 +38,10 @@ pub const STANDARD_PAYMENT_INSTALL_CONTRACT: &str = "standard_payment_install.wa
 pub const DEFAULT_CHAIN_NAME: &str = "gerald";
 pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 0;
 pub const DEFAULT_BLOCK_TIME: u64 = 0;
+pub const DEFAULT_INFLATION_RATE: u64 = 1_000;
+pub const DEFAULT_SLASH_RATE: u64 = 100_000;
+pub const DEFAULT_UNBONDING_DELAY: u64 = 0;
+pub const DEFAULT_GAS_PRICE: u64 = CONV_RATE;
 pub const MOCKED_ACCOUNT_ADDRESS: AccountHash = AccountHash::new([48u8; 32]);
 
 pub const DEFAULT_ACCOUNT_KEY: AccountHash = DEFAULT_ACCOUNT_ADDR;
@@ -51,7 +60,7 @@ lazy_static! {
         ret.push(genesis_account);
         ret
     };
-    pub static ref DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+    pub static ref DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
     pub static ref DEFAULT_PAYMENT: U512 = 100_000_000.into();
     pub static ref DEFAULT_WASM_COSTS: WasmCosts = test_utils::wasm_costs_mock();
     pub static ref DEFAULT_EXEC_CONFIG: ExecConfig = {
@@ -69,6 +78,10 @@ lazy_static! {
             standard_payment_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            DEFAULT_GAS_PRICE,
+            DEFAULT_INFLATION_RATE,
+            DEFAULT_SLASH_RATE,
+            DEFAULT_UNBONDING_DELAY,
         )
     };
     pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = {
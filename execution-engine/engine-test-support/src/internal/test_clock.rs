@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use types::BlockTime;
+
+/// A block time which only moves when a test tells it to.
+///
+/// Once a [`WasmTestBuilder`](super::WasmTestBuilder) has a clock, every exec request it runs has
+/// its block time set to the clock's current time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestClock {
+    now: BlockTime,
+}
+
+impl TestClock {
+    /// Creates a clock showing `start`.
+    pub fn new(start: BlockTime) -> Self {
+        TestClock { now: start }
+    }
+
+    /// Returns the current time.
+    pub fn now(&self) -> BlockTime {
+        self.now
+    }
+
+    /// Moves the clock forward by `duration`, truncated to whole milliseconds.
+    pub fn advance(&mut self, duration: Duration) {
+        self.now = self.now + duration;
+    }
+
+    /// Moves the clock forward to `now`.
+    ///
+    /// Panics if `now` is earlier than the current time, since block time never goes backwards.
+    pub fn set(&mut self, now: BlockTime) {
+        assert!(
+            now >= self.now,
+            "should not move clock back from {:?} to {:?}",
+            self.now,
+            now
+        );
+        self.now = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_advance() {
+        let mut clock = TestClock::new(BlockTime::new(1_000));
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(clock.now(), BlockTime::new(3_000));
+        clock.advance(Duration::from_micros(1_500));
+        assert_eq!(clock.now(), BlockTime::new(3_001));
+    }
+
+    #[test]
+    fn should_set() {
+        let mut clock = TestClock::new(BlockTime::new(1_000));
+        clock.set(BlockTime::new(1_000));
+        clock.set(BlockTime::new(5_000));
+        assert_eq!(clock.now(), BlockTime::new(5_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_not_set_earlier_time() {
+        let mut clock = TestClock::new(BlockTime::new(1_000));
+        clock.set(BlockTime::new(999));
+    }
+}
@@ -1,10 +1,11 @@
 use engine_grpc_server::engine_server::{
     ipc::{
-        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-        DeployCode, UpgradeRequest,
+        ChainSpec_ActivationPoint, ChainSpec_CostTable, ChainSpec_CostTable_WasmCosts,
+        ChainSpec_UpgradePoint, DeployCode, UpgradeRequest,
     },
     state,
 };
+use engine_shared::host_function_costs::HostFunctionCosts;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use types::ProtocolVersion;
 
@@ -14,6 +15,11 @@ pub struct UpgradeRequestBuilder {
     new_protocol_version: state::ProtocolVersion,
     upgrade_installer: DeployCode,
     new_costs: Option<ChainSpec_CostTable_WasmCosts>,
+    new_host_function_costs: Option<HostFunctionCosts>,
+    new_gas_price: u64,
+    new_max_effects_size: u64,
+    new_max_value_size: u64,
+    new_max_named_key_length: u64,
     activation_point: ChainSpec_ActivationPoint,
 }
 
@@ -54,10 +60,37 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_verify_ed25519(wasm_costs.verify_ed25519);
+        new_costs.set_verify_secp256k1(wasm_costs.verify_secp256k1);
         self.new_costs = Some(new_costs);
         self
     }
 
+    pub fn with_new_host_function_costs(mut self, host_function_costs: HostFunctionCosts) -> Self {
+        self.new_host_function_costs = Some(host_function_costs);
+        self
+    }
+
+    pub fn with_new_gas_price(mut self, gas_price: u64) -> Self {
+        self.new_gas_price = gas_price;
+        self
+    }
+
+    pub fn with_new_max_effects_size(mut self, max_effects_size: u64) -> Self {
+        self.new_max_effects_size = max_effects_size;
+        self
+    }
+
+    pub fn with_new_max_value_size(mut self, max_value_size: u64) -> Self {
+        self.new_max_value_size = max_value_size;
+        self
+    }
+
+    pub fn with_new_max_named_key_length(mut self, max_named_key_length: u64) -> Self {
+        self.new_max_named_key_length = max_named_key_length;
+        self
+    }
+
     pub fn with_activation_point(mut self, rank: u64) -> Self {
         self.activation_point = {
             let mut ret = ChainSpec_ActivationPoint::new();
@@ -70,15 +103,20 @@ impl UpgradeRequestBuilder {
     pub fn build(self) -> UpgradeRequest {
         let mut upgrade_point = ChainSpec_UpgradePoint::new();
         upgrade_point.set_activation_point(self.activation_point);
-        match self.new_costs {
-            None => {}
-            Some(new_costs) => {
-                let mut cost_table =
-                    engine_grpc_server::engine_server::ipc::ChainSpec_CostTable::new();
+        if self.new_costs.is_some() || self.new_host_function_costs.is_some() {
+            let mut cost_table = ChainSpec_CostTable::new();
+            if let Some(new_costs) = self.new_costs {
                 cost_table.set_wasm(new_costs);
-                upgrade_point.set_new_costs(cost_table);
             }
+            if let Some(new_host_function_costs) = self.new_host_function_costs {
+                cost_table.set_host_function_costs(new_host_function_costs.into());
+            }
+            upgrade_point.set_new_costs(cost_table);
         }
+        upgrade_point.set_new_gas_price(self.new_gas_price);
+        upgrade_point.set_new_max_effects_size(self.new_max_effects_size);
+        upgrade_point.set_new_max_value_size(self.new_max_value_size);
+        upgrade_point.set_new_max_named_key_length(self.new_max_named_key_length);
         upgrade_point.set_protocol_version(self.new_protocol_version);
         upgrade_point.set_upgrade_installer(self.upgrade_installer);
 
@@ -97,6 +135,11 @@ impl Default for UpgradeRequestBuilder {
             new_protocol_version: Default::default(),
             upgrade_installer: Default::default(),
             new_costs: None,
+            new_host_function_costs: None,
+            new_gas_price: 0,
+            new_max_effects_size: 0,
+            new_max_value_size: 0,
+            new_max_named_key_length: 0,
             activation_point: Default::default(),
         }
     }
@@ -8,7 +8,7 @@ use lazy_static::lazy_static;
 
 use engine_core::engine_state::{
     execution_result::ExecutionResult,
-    genesis::{ExecConfig, GenesisAccount, GenesisConfig},
+    genesis::{ExecConfig, GenesisAccount, GenesisConfig, GenesisConfigBuilder},
     run_genesis_request::RunGenesisRequest,
     Error,
 };
@@ -19,8 +19,9 @@ use engine_shared::{
 use types::Key;
 
 use crate::internal::{
-    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP,
-    DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    DEFAULT_CHAIN_NAME, DEFAULT_GAS_PRICE, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP,
+    DEFAULT_INFLATION_RATE, DEFAULT_PROTOCOL_VERSION, DEFAULT_SLASH_RATE, DEFAULT_UNBONDING_DELAY,
+    DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
     STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
 
@@ -104,27 +105,32 @@ pub fn read_wasm_file_bytes<T: AsRef<Path>>(contract_file: T) -> Vec<u8> {
     panic!("{}\n", error_msg);
 }
 
+/// Returns a builder preset with the default test chain settings, the system contract installers
+/// and `accounts`, to which further settings can be applied.
+pub fn create_genesis_config_builder(accounts: Vec<GenesisAccount>) -> GenesisConfigBuilder {
+    GenesisConfigBuilder::new()
+        .with_name(DEFAULT_CHAIN_NAME)
+        .with_timestamp(DEFAULT_GENESIS_TIMESTAMP)
+        .with_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_accounts(accounts)
+        .with_mint_installer_bytes(read_wasm_file_bytes(MINT_INSTALL_CONTRACT))
+        .with_proof_of_stake_installer_bytes(read_wasm_file_bytes(POS_INSTALL_CONTRACT))
+        .with_standard_payment_installer_bytes(read_wasm_file_bytes(
+            STANDARD_PAYMENT_INSTALL_CONTRACT,
+        ))
+        .with_wasm_costs(*DEFAULT_WASM_COSTS)
+        .with_gas_price(DEFAULT_GAS_PRICE)
+        .with_inflation_rate(DEFAULT_INFLATION_RATE)
+        .with_slash_rate(DEFAULT_SLASH_RATE)
+        .with_unbonding_delay(DEFAULT_UNBONDING_DELAY)
+}
+
 pub fn create_exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
-    let mint_installer_bytes = read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
-    let proof_of_stake_installer_bytes = read_wasm_file_bytes(POS_INSTALL_CONTRACT);
-    let standard_payment_installer_bytes = read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
-    let wasm_costs = *DEFAULT_WASM_COSTS;
-    ExecConfig::new(
-        mint_installer_bytes,
-        proof_of_stake_installer_bytes,
-        standard_payment_installer_bytes,
-        accounts,
-        wasm_costs,
-    )
+    create_genesis_config(accounts).take_ee_config()
 }
 
 pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
-    let name = DEFAULT_CHAIN_NAME.to_string();
-    let timestamp = DEFAULT_GENESIS_TIMESTAMP;
-    let protocol_version = *DEFAULT_PROTOCOL_VERSION;
-    let exec_config = create_exec_config(accounts);
-
-    GenesisConfig::new(name, timestamp, protocol_version, exec_config)
+    create_genesis_config_builder(accounts).build()
 }
 
 pub fn create_run_genesis_request(accounts: Vec<GenesisAccount>) -> RunGenesisRequest {
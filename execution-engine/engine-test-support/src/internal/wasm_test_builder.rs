@@ -1,11 +1,12 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::{TryFrom, TryInto},
     ffi::OsStr,
     fs,
     path::PathBuf,
     rc::Rc,
     sync::Arc,
+    time::Duration,
 };
 
 use grpc::RequestOptions;
@@ -14,15 +15,22 @@ use log::LevelFilter;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult,
-        run_genesis_request::RunGenesisRequest, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        era_end::{EraEndRequest, EraEndResult},
+        execute_request::ExecuteRequest,
+        execution_result::ExecutionResult,
+        run_genesis_request::RunGenesisRequest,
+        scheduled_calls::{ScheduledCallsRequest, ScheduledCallsResult},
+        slash::{SlashConfig, SlashResult},
+        step::{StepConfig, StepResult},
+        upgrade::{Migration, UpgradeConfig, UpgradeResult},
+        EngineConfig, EngineState, Error as EngineError, SYSTEM_ACCOUNT_ADDR,
     },
-    execution,
+    execution::{self, HostCall},
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        BalanceRequest, CommitRequest, CommitResponse, GenesisResponse, QueryRequest,
+        UpgradeRequest, UpgradeResponse, ValidatorWeightsRequest,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
@@ -39,18 +47,24 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
-    global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
+    global_state::{
+        in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, scratch::ScratchGlobalState,
+        CommitResult, StateProvider,
+    },
+    protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     transaction_source::lmdb::LmdbEnvironment,
+    trie::merkle_proof::TrieMerkleProof,
     trie_store::lmdb::LmdbTrieStore,
 };
 use types::{
     account::AccountHash,
-    bytesrepr::{self},
-    CLValue, Contract, ContractHash, ContractWasm, Key, URef, U512,
+    bytesrepr::{self, FromBytes},
+    BlockTime, CLTyped, CLValue, Contract, ContractHash, ContractWasm, Key, ProtocolVersion, URef,
+    U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{utils, TestClock, UpgradeRequestBuilder, DEFAULT_BLOCK_TIME};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -63,6 +77,8 @@ const GLOBAL_STATE_DIR: &str = "global_state";
 
 pub type InMemoryWasmTestBuilder = WasmTestBuilder<InMemoryGlobalState>;
 pub type LmdbWasmTestBuilder = WasmTestBuilder<LmdbGlobalState>;
+/// A builder whose commits skip Merkle hashing until [`ScratchWasmTestBuilder::seal`] is called.
+pub type ScratchWasmTestBuilder = WasmTestBuilder<ScratchGlobalState>;
 
 /// Builder for simple WASM test
 pub struct WasmTestBuilder<S> {
@@ -70,7 +86,13 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_responses: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Protocol versions of the exec calls, i.e. `exec_protocol_versions[0]` is the version the
+    /// first exec call ran under etc.
+    exec_protocol_versions: Vec<ProtocolVersion>,
     upgrade_responses: Vec<UpgradeResponse>,
+    /// Protocol versions the state was set up with, starting with the genesis version and followed
+    /// by the version of each successful upgrade
+    protocol_versions: Vec<ProtocolVersion>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
     /// Cached transform maps after subsequent successful runs i.e. `transforms[0]` is for first
@@ -87,6 +109,8 @@ pub struct WasmTestBuilder<S> {
     pos_contract_hash: Option<ContractHash>,
     /// Standard payment contract key
     standard_payment_hash: Option<ContractHash>,
+    /// Block time applied to every exec request, once a test has started the clock
+    clock: Option<TestClock>,
 }
 
 impl<S> WasmTestBuilder<S> {
@@ -94,22 +118,20 @@ impl<S> WasmTestBuilder<S> {
         let log_settings = Settings::new(LevelFilter::Error).with_style(Style::HumanReadable);
         let _ = logging::initialize(log_settings);
     }
-}
 
-impl Default for InMemoryWasmTestBuilder {
-    fn default() -> Self {
-        Self::initialize_logging();
-        let engine_config = EngineConfig::new()
+    fn default_engine_config() -> EngineConfig {
+        EngineConfig::new()
             .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
-            .with_enable_bonding(cfg!(feature = "enable-bonding"));
-
-        let global_state = InMemoryGlobalState::empty().expect("should create global state");
-        let engine_state = EngineState::new(global_state, engine_config);
+            .with_enable_bonding(cfg!(feature = "enable-bonding"))
+    }
 
+    fn with_engine_state(engine_state: EngineState<S>) -> Self {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_protocol_versions: Vec::new(),
             upgrade_responses: Vec::new(),
+            protocol_versions: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
             transforms: Vec::new(),
@@ -119,10 +141,23 @@ impl Default for InMemoryWasmTestBuilder {
             mint_contract_hash: None,
             pos_contract_hash: None,
             standard_payment_hash: None,
+            clock: None,
         }
     }
 }
 
+impl Default for InMemoryWasmTestBuilder {
+    fn default() -> Self {
+        Self::new_with_config(Self::default_engine_config())
+    }
+}
+
+impl Default for ScratchWasmTestBuilder {
+    fn default() -> Self {
+        Self::new_with_config(Self::default_engine_config())
+    }
+}
+
 // TODO: Deriving `Clone` for `WasmTestBuilder<S>` doesn't work correctly (unsure why), so
 // implemented by hand here.  Try to derive in the future with a different compiler version.
 impl<S> Clone for WasmTestBuilder<S> {
@@ -130,7 +165,9 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_responses: self.exec_responses.clone(),
+            exec_protocol_versions: self.exec_protocol_versions.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
+            protocol_versions: self.protocol_versions.clone(),
             genesis_hash: self.genesis_hash.clone(),
             post_state_hash: self.post_state_hash.clone(),
             transforms: self.transforms.clone(),
@@ -140,6 +177,7 @@ impl<S> Clone for WasmTestBuilder<S> {
             mint_contract_hash: self.mint_contract_hash,
             pos_contract_hash: self.pos_contract_hash,
             standard_payment_hash: self.standard_payment_hash,
+            clock: self.clock,
         }
     }
 }
@@ -170,6 +208,44 @@ impl InMemoryWasmTestBuilder {
             ..Default::default()
         }
     }
+
+    /// Returns a builder with empty global state which executes deploys using `engine_config`.
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
+        Self::initialize_logging();
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        Self::with_engine_state(EngineState::new(global_state, engine_config))
+    }
+}
+
+impl ScratchWasmTestBuilder {
+    /// Returns a builder with empty global state which executes deploys using `engine_config`.
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
+        Self::initialize_logging();
+        let global_state = ScratchGlobalState::empty().expect("should create global state");
+        Self::with_engine_state(EngineState::new(global_state, engine_config))
+    }
+
+    /// Writes the current post state to the trie store, replacing the post state hash with the
+    /// state's Merkle root.
+    pub fn seal(&mut self) -> &mut Self {
+        let state_hash = self
+            .post_state_hash
+            .clone()
+            .expect("should have post state hash");
+        let state_hash = Blake2bHash::try_from(state_hash.as_slice()).expect("should be a hash");
+        match self
+            .engine_state
+            .state()
+            .seal(CorrelationId::new(), state_hash)
+            .expect("should seal")
+        {
+            CommitResult::Success { state_root, .. } => {
+                self.post_state_hash = Some(state_root.to_vec())
+            }
+            commit_result => panic!("failed to seal post state: {}", commit_result),
+        }
+        self
+    }
 }
 
 impl LmdbWasmTestBuilder {
@@ -198,7 +274,9 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_protocol_versions: Vec::new(),
             upgrade_responses: Vec::new(),
+            protocol_versions: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
             transforms: Vec::new(),
@@ -208,6 +286,7 @@ impl LmdbWasmTestBuilder {
             mint_contract_hash: None,
             pos_contract_hash: None,
             standard_payment_hash: None,
+            clock: None,
         }
     }
 
@@ -230,6 +309,7 @@ impl LmdbWasmTestBuilder {
         builder.bonded_validators = result.0.bonded_validators.clone();
         builder.mint_contract_hash = result.0.mint_contract_hash;
         builder.pos_contract_hash = result.0.pos_contract_hash;
+        builder.protocol_versions = result.0.protocol_versions.clone();
         builder
     }
 
@@ -259,7 +339,9 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_protocol_versions: Vec::new(),
             upgrade_responses: Vec::new(),
+            protocol_versions: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
             transforms: Vec::new(),
@@ -269,6 +351,7 @@ impl LmdbWasmTestBuilder {
             mint_contract_hash: None,
             pos_contract_hash: None,
             standard_payment_hash: None,
+            clock: None,
         }
     }
 
@@ -295,7 +378,9 @@ where
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
+            exec_protocol_versions: Vec::new(),
             upgrade_responses: Vec::new(),
+            protocol_versions: result.0.protocol_versions,
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
             transforms: Vec::new(),
@@ -304,6 +389,7 @@ where
             mint_contract_hash: result.0.mint_contract_hash,
             pos_contract_hash: result.0.pos_contract_hash,
             standard_payment_hash: result.0.standard_payment_hash,
+            clock: result.0.clock,
             genesis_transforms: result.0.genesis_transforms,
         }
     }
@@ -351,6 +437,7 @@ where
         self.standard_payment_hash = Some(protocol_data.standard_payment());
         self.genesis_account = Some(genesis_account);
         self.genesis_transforms = Some(transforms);
+        self.protocol_versions = vec![run_genesis_request.protocol_version()];
         self
     }
 
@@ -380,7 +467,137 @@ where
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
+    /// Queries the current post state for the value under `base_key` at `path`, expecting it to be
+    /// a [`CLValue`] holding a `T`.
+    ///
+    /// Panics if there is no such value or it isn't a `T`.
+    pub fn query_expect<T: CLTyped + FromBytes>(&self, base_key: Key, path: &[&str]) -> T {
+        self.query(None, base_key, path)
+            .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
+            .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "should get value of type {:?} under {} at {:?}: {}",
+                    T::cl_type(),
+                    base_key,
+                    path,
+                    error
+                )
+            })
+    }
+
+    /// Like [`WasmTestBuilder::query`], but also returns the Merkle proofs of every value read
+    /// while following `path`.
+    pub fn query_with_proof(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+        base_key: Key,
+        path: &[&str],
+    ) -> Result<(StoredValue, Vec<TrieMerkleProof<Key, StoredValue>>), String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let path_vec: Vec<String> = path.iter().map(|s| String::from(*s)).collect();
+
+        let query_request = create_query_request(post_state, base_key, path_vec);
+
+        let mut query_response = self
+            .engine_state
+            .query(RequestOptions::new(), query_request)
+            .wait_drop_metadata()
+            .expect("should get query response");
+
+        if query_response.has_failure() {
+            return Err(query_response.take_failure());
+        }
+        let value = bytesrepr::deserialize(query_response.take_success())
+            .map_err(|err| format!("{}", err))?;
+        let proofs = query_response
+            .take_merkle_proofs()
+            .into_iter()
+            .map(|proof_bytes| {
+                bytesrepr::deserialize(proof_bytes).map_err(|err| format!("{}", err))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((value, proofs))
+    }
+
+    /// Returns the balance of the main purse of the given account, along with the Merkle proofs of
+    /// the account, of the purse's balance mapping and of the balance.
+    pub fn get_balance_with_proof(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+        account_hash: AccountHash,
+    ) -> Result<(U512, Vec<TrieMerkleProof<Key, StoredValue>>), String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let mut balance_request = BalanceRequest::new();
+        balance_request.set_state_hash(post_state);
+        balance_request.set_public_key_hash(account_hash.value().to_vec());
+
+        let mut balance_response = self
+            .engine_state
+            .get_balance(RequestOptions::new(), balance_request)
+            .wait_drop_metadata()
+            .expect("should get balance response");
+
+        if balance_response.has_failure() {
+            return Err(balance_response.take_failure());
+        }
+        let motes =
+            U512::try_from(balance_response.take_success()).map_err(|err| format!("{:?}", err))?;
+        let proofs = balance_response
+            .take_merkle_proofs()
+            .into_iter()
+            .map(|proof_bytes| {
+                bytesrepr::deserialize(proof_bytes).map_err(|err| format!("{}", err))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((motes, proofs))
+    }
+
+    /// Returns the weights of the bonded validators as reported by the `get_validator_weights`
+    /// endpoint, under the latest protocol version.
+    pub fn get_validator_weights(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+    ) -> Result<BTreeMap<AccountHash, U512>, String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let mut validator_weights_request = ValidatorWeightsRequest::new();
+        validator_weights_request.set_state_hash(post_state);
+        validator_weights_request.set_protocol_version(self.get_protocol_version().into());
+
+        let mut validator_weights_response = self
+            .engine_state
+            .get_validator_weights(RequestOptions::new(), validator_weights_request)
+            .wait_drop_metadata()
+            .expect("should get validator weights response");
+
+        if validator_weights_response.has_failure() {
+            return Err(validator_weights_response.take_failure());
+        }
+        if validator_weights_response.has_missing_parent() {
+            return Err("Root not found".to_string());
+        }
+        validator_weights_response
+            .take_success()
+            .take_bonds()
+            .into_iter()
+            .map(|bond| bond.try_into().map_err(|err| format!("{:?}", err)))
+            .collect()
+    }
+
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+        let protocol_version = exec_request.protocol_version;
+        if let Some(clock) = self.clock {
+            exec_request.block_time = clock.now().into();
+        }
         let exec_request = {
             let hash = self
                 .post_state_hash
@@ -404,9 +621,16 @@ where
         );
         self.exec_responses
             .push(exec_response.unwrap().into_iter().map(Rc::new).collect());
+        self.exec_protocol_versions.push(protocol_version);
         self
     }
 
+    /// Like `exec`, but records the host functions invoked by each deploy.  See `last_exec_trace`.
+    pub fn exec_with_trace(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+        exec_request.enable_execution_trace = true;
+        self.exec(exec_request)
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self
@@ -486,6 +710,280 @@ where
         self
     }
 
+    /// Upgrades the latest post-state from the current protocol version to the new protocol
+    /// version set on `upgrade_request_builder`, and expects the upgrade to succeed.
+    ///
+    /// The pre-state hash and current protocol version of the request are filled in by the builder,
+    /// so consecutive upgrades can be chained without tracking either.  The system contract hashes
+    /// are refreshed from the protocol data of the new version.
+    pub fn upgrade_with(&mut self, upgrade_request_builder: UpgradeRequestBuilder) -> &mut Self {
+        let mut upgrade_request = upgrade_request_builder
+            .with_current_protocol_version(self.get_protocol_version())
+            .build();
+        let new_protocol_version: ProtocolVersion = upgrade_request
+            .get_upgrade_point()
+            .get_protocol_version()
+            .clone()
+            .into();
+
+        self.upgrade_with_upgrade_request(&mut upgrade_request);
+
+        let upgrade_response = self
+            .upgrade_responses
+            .last()
+            .expect("should have upgrade response");
+        if !upgrade_response.has_success() {
+            panic!(
+                "Expected upgrade to {} to succeed but received a failure instead: {:?}",
+                new_protocol_version, upgrade_response
+            );
+        }
+
+        let protocol_data = self
+            .get_protocol_data(new_protocol_version)
+            .expect("should have protocol data for the new protocol version");
+        self.mint_contract_hash = Some(protocol_data.mint());
+        self.pos_contract_hash = Some(protocol_data.proof_of_stake());
+        self.standard_payment_hash = Some(protocol_data.standard_payment());
+        self.protocol_versions.push(new_protocol_version);
+        self
+    }
+
+    /// Upgrades the latest post-state from the current protocol version to `new_protocol_version`
+    /// without an upgrade installer, running `migrations` in order, and expects the upgrade to
+    /// succeed.
+    ///
+    /// State migrations are not carried by the protobuf `UpgradeRequest`, so the upgrade is
+    /// committed directly against the engine state.
+    pub fn upgrade_with_migrations(
+        &mut self,
+        new_protocol_version: ProtocolVersion,
+        migrations: Vec<Migration>,
+    ) -> &mut Self {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should convert to Blake2bHash");
+        let upgrade_config = migrations.into_iter().fold(
+            UpgradeConfig::new(
+                pre_state_hash,
+                self.get_protocol_version(),
+                new_protocol_version,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            UpgradeConfig::with_migration,
+        );
+        let upgrade_result = self
+            .engine_state
+            .commit_upgrade(CorrelationId::new(), upgrade_config)
+            .expect("should upgrade");
+
+        match upgrade_result {
+            UpgradeResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            error => panic!("upgrade failure: {}", error),
+        }
+
+        let protocol_data = self
+            .get_protocol_data(new_protocol_version)
+            .expect("should have protocol data for the new protocol version");
+        self.mint_contract_hash = Some(protocol_data.mint());
+        self.pos_contract_hash = Some(protocol_data.proof_of_stake());
+        self.standard_payment_hash = Some(protocol_data.standard_payment());
+        self.protocol_versions.push(new_protocol_version);
+        self
+    }
+
+    /// Slashes the given validators as the system account and commits the effects.
+    pub fn slash(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        validators: Vec<AccountHash>,
+    ) -> &mut Self {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should convert to Blake2bHash");
+        let slash_config = SlashConfig::new(pre_state_hash, protocol_version, validators);
+        let slash_result = self
+            .engine_state
+            .commit_slash(CorrelationId::new(), slash_config)
+            .expect("should slash");
+
+        match slash_result {
+            SlashResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            error => panic!("slash failure: {}", error),
+        }
+        self
+    }
+
+    /// Processes the matured PoS bonding and unbonding requests as the system account and commits
+    /// the effects.
+    pub fn step(&mut self, protocol_version: ProtocolVersion, blocktime: BlockTime) -> &mut Self {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should convert to Blake2bHash");
+        let step_config = StepConfig::new(pre_state_hash, protocol_version, blocktime);
+        let step_result = self
+            .engine_state
+            .commit_step(CorrelationId::new(), step_config)
+            .expect("should step");
+
+        match step_result {
+            StepResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            error => panic!("step failure: {}", error),
+        }
+        self
+    }
+
+    /// Like `step`, but at the current time of the builder's clock.
+    pub fn step_now(&mut self, protocol_version: ProtocolVersion) -> &mut Self {
+        let blocktime = self.get_block_time().expect("should have started clock");
+        self.step(protocol_version, blocktime)
+    }
+
+    /// Starts the builder's clock at `start`.  From then on, the block time of every exec request
+    /// is overridden with the clock's current time.
+    pub fn start_clock(&mut self, start: BlockTime) -> &mut Self {
+        self.clock = Some(TestClock::new(start));
+        self
+    }
+
+    /// Moves the builder's clock forward by `duration`, starting it at `DEFAULT_BLOCK_TIME` first
+    /// if required.
+    pub fn advance_time(&mut self, duration: Duration) -> &mut Self {
+        self.clock
+            .get_or_insert_with(|| TestClock::new(BlockTime::new(DEFAULT_BLOCK_TIME)))
+            .advance(duration);
+        self
+    }
+
+    /// Moves the builder's clock forward to `block_time`, starting it first if required.
+    pub fn set_block_time(&mut self, block_time: BlockTime) -> &mut Self {
+        self.clock
+            .get_or_insert_with(|| TestClock::new(block_time))
+            .set(block_time);
+        self
+    }
+
+    /// Returns the current time of the builder's clock, if it has been started.
+    pub fn get_block_time(&self) -> Option<BlockTime> {
+        self.clock.map(|clock| clock.now())
+    }
+
+    /// Runs the PoS bookkeeping due at the end of era `era_id` as the system account and commits
+    /// the effects.  The resulting validator weights are appended to those returned by
+    /// `get_bonded_validators`.
+    pub fn run_era_end(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        era_id: u64,
+        blocktime: BlockTime,
+    ) -> &mut Self
+    where
+        EngineError: From<S::Error>,
+    {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should convert to Blake2bHash");
+        let era_end_request =
+            EraEndRequest::new(pre_state_hash, protocol_version, era_id, blocktime);
+        let era_end_result = self
+            .engine_state
+            .run_era_end(CorrelationId::new(), era_end_request)
+            .expect("should run era end");
+
+        match era_end_result {
+            EraEndResult::Success {
+                post_state_hash,
+                bonded_validators,
+                ..
+            } => {
+                self.post_state_hash = Some(post_state_hash.to_vec());
+                self.bonded_validators.push(bonded_validators);
+            }
+            error => panic!("era end failure: {}", error),
+        }
+        self
+    }
+
+    /// Makes the calls queued with the scheduler which are due at `blocktime`, each limited to
+    /// `call_gas_limit`, and commits the effects of those which succeed.  Returns the results of
+    /// the calls in the order they were made.
+    pub fn run_scheduled_calls(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        call_gas_limit: Gas,
+    ) -> Vec<ExecutionResult> {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should convert to Blake2bHash");
+        let scheduled_calls_request =
+            ScheduledCallsRequest::new(pre_state_hash, protocol_version, blocktime, call_gas_limit);
+        let scheduled_calls_result = self
+            .engine_state
+            .run_scheduled_calls(CorrelationId::new(), scheduled_calls_request)
+            .expect("should run scheduled calls");
+
+        match scheduled_calls_result {
+            ScheduledCallsResult::Success {
+                post_state_hash,
+                results,
+            } => {
+                self.post_state_hash = Some(post_state_hash.to_vec());
+                results
+            }
+            error => panic!("scheduled calls failure: {}", error),
+        }
+    }
+
+    /// Expects the last exec call to have run under `protocol_version`.
+    pub fn expect_protocol_version(&mut self, protocol_version: ProtocolVersion) -> &mut Self {
+        let exec_protocol_version = self
+            .exec_protocol_versions
+            .last()
+            .expect("Expected to be called after run()");
+        assert_eq!(
+            *exec_protocol_version, protocol_version,
+            "Expected exec to run under protocol version {} but it ran under {}",
+            protocol_version, exec_protocol_version
+        );
+        self
+    }
+
     /// Expects a successful run and caches transformations
     pub fn expect_success(&mut self) -> &mut Self {
         // Check first result, as only first result is interesting for a simple test
@@ -583,6 +1081,32 @@ where
         self.exec_responses.len()
     }
 
+    /// Returns the protocol version the exec call at `index` ran under.
+    pub fn get_exec_protocol_version(&self, index: usize) -> Option<ProtocolVersion> {
+        self.exec_protocol_versions.get(index).copied()
+    }
+
+    /// Returns the protocol version of the latest successful upgrade, or of genesis if there were
+    /// no upgrades.
+    pub fn get_protocol_version(&self) -> ProtocolVersion {
+        *self
+            .protocol_versions
+            .last()
+            .expect("should have run genesis")
+    }
+
+    /// Returns the protocol versions the state was set up with, starting with the genesis version.
+    pub fn get_protocol_versions(&self) -> &[ProtocolVersion] {
+        &self.protocol_versions
+    }
+
+    /// Returns the protocol data stored for `protocol_version`.
+    pub fn get_protocol_data(&self, protocol_version: ProtocolVersion) -> Option<ProtocolData> {
+        self.engine_state
+            .get_protocol_data(protocol_version)
+            .expect("should read protocol data")
+    }
+
     pub fn get_upgrade_response(&self, index: usize) -> Option<&UpgradeResponse> {
         self.upgrade_responses.get(index)
     }
@@ -605,16 +1129,8 @@ where
         let purse_addr = purse.addr();
         let balance_mapping_key = Key::Hash(purse_addr);
 
-        let base_key = self
-            .query(None, balance_mapping_key, &[])
-            .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
-            .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
-            .expect("should find balance uref");
-
-        self.query(None, base_key, &[])
-            .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
-            .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
-            .expect("should parse balance into a U512")
+        let base_key: Key = self.query_expect(balance_mapping_key, &[]);
+        self.query_expect(base_key, &[])
     }
 
     pub fn get_account(&self, account_hash: AccountHash) -> Option<Account> {
@@ -667,6 +1183,28 @@ where
         exec_result.cost()
     }
 
+    /// Returns the host functions invoked by the first deploy of the last exec call.  Empty unless
+    /// it was run with `exec_with_trace` or tracing is enabled in the `EngineConfig`.
+    pub fn last_exec_trace(&self) -> &[HostCall] {
+        let exec_response = self
+            .exec_responses
+            .last()
+            .expect("Expected to be called after run()");
+        let exec_result = exec_response.get(0).expect("should have result");
+        exec_result.trace()
+    }
+
+    /// Expects the first deploy of the last exec call to have cost exactly `expected`.
+    pub fn expect_gas_cost(&mut self, expected: Gas) -> &mut Self {
+        let gas_cost = self.last_exec_gas_cost();
+        assert_eq!(
+            gas_cost, expected,
+            "Expected exec to cost {} gas but it cost {}",
+            expected, gas_cost
+        );
+        self
+    }
+
     pub fn exec_error_message(&self, index: usize) -> Option<String> {
         let response = self.get_exec_response(index)?;
         Some(utils::get_error_message(response))
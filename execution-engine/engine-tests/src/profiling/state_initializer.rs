@@ -12,7 +12,8 @@ use engine_core::engine_state::{
 use engine_test_support::{
     internal::{
         utils, DeployItemBuilder, ExecuteRequestBuilder, LmdbWasmTestBuilder, ARG_AMOUNT,
-        DEFAULT_ACCOUNTS, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_ACCOUNTS, DEFAULT_GAS_PRICE, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_INFLATION_RATE,
+        DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION, DEFAULT_SLASH_RATE, DEFAULT_UNBONDING_DELAY,
         DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
         STANDARD_PAYMENT_INSTALL_CONTRACT,
     },
@@ -80,6 +81,10 @@ fn main() {
         standard_payment_installer_bytes,
         DEFAULT_ACCOUNTS.clone(),
         *DEFAULT_WASM_COSTS,
+        DEFAULT_GAS_PRICE,
+        DEFAULT_INFLATION_RATE,
+        DEFAULT_SLASH_RATE,
+        DEFAULT_UNBONDING_DELAY,
     );
     let run_genesis_request = RunGenesisRequest::new(
         *DEFAULT_GENESIS_CONFIG_HASH,
@@ -0,0 +1,92 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{EngineConfig, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ContractHash, RuntimeArgs};
+
+const CONTRACT_RECURSIVE_CALL: &str = "recursive_call.wasm";
+const CONTRACT_KEY: &str = "recursive_call";
+const METHOD_RECURSE: &str = "recurse";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_DEPTH: &str = "depth";
+const MAX_CALL_DEPTH: usize = 5;
+
+fn setup() -> (InMemoryWasmTestBuilder, ContractHash) {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_max_call_depth(MAX_CALL_DEPTH);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_CALL,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let contract_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(CONTRACT_KEY)
+        .expect("should have contract key")
+        .into_hash()
+        .expect("should be hash");
+
+    (builder, contract_hash)
+}
+
+fn recurse(builder: &mut InMemoryWasmTestBuilder, contract_hash: ContractHash, depth: u32) {
+    let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+        DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        METHOD_RECURSE,
+        runtime_args! {
+            ARG_CONTRACT_HASH => contract_hash,
+            ARG_DEPTH => depth,
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_allow_calls_up_to_max_call_depth() {
+    let (mut builder, contract_hash) = setup();
+    recurse(&mut builder, contract_hash, MAX_CALL_DEPTH as u32);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_fail_calls_beyond_max_call_depth() {
+    let (mut builder, contract_hash) = setup();
+    recurse(&mut builder, contract_hash, MAX_CALL_DEPTH as u32 + 1);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::CallDepthLimit(max_call_depth))
+            if *max_call_depth == MAX_CALL_DEPTH
+    );
+}
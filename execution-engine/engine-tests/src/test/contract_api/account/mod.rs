@@ -2,3 +2,4 @@ mod associated_keys;
 mod authorized_keys;
 mod key_management_thresholds;
 mod named_keys;
+mod set_main_purse;
@@ -0,0 +1,65 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_SET_MAIN_PURSE: &str = "set_main_purse.wasm";
+const ARG_STAGE: &str = "stage";
+const NEW_PURSE_KEY: &str = "new_purse";
+
+#[ignore]
+#[test]
+fn should_set_main_purse() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SET_MAIN_PURSE,
+        runtime_args! { ARG_STAGE => String::from("rotate") },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let new_purse = account
+        .named_keys()
+        .get(NEW_PURSE_KEY)
+        .expect("should have new purse")
+        .into_uref()
+        .expect("should be uref");
+    assert_eq!(account.main_purse(), new_purse);
+}
+
+#[ignore]
+#[test]
+fn should_not_set_main_purse_to_invalid_purse() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SET_MAIN_PURSE,
+        runtime_args! { ARG_STAGE => String::from("test-invalid-purse") },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let original_main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    assert_eq!(main_purse, original_main_purse);
+}
@@ -0,0 +1,96 @@
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::AccountHash, runtime_args, ApiError, RuntimeArgs, U512};
+
+const CONTRACT_BATCH_TRANSFER: &str = "batch_transfer.wasm";
+const ARG_TRANSFERS: &str = "transfers";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+
+fn batch_transfer_request(transfers: Vec<(AccountHash, U512)>) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_BATCH_TRANSFER,
+        runtime_args! { ARG_TRANSFERS => transfers },
+    )
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder, account_hash: AccountHash) -> U512 {
+    let account = builder
+        .get_account(account_hash)
+        .expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_batch_transfer_to_new_and_existing_accounts() {
+    let first_amount = *DEFAULT_PAYMENT;
+    let second_amount = U512::from(42);
+
+    let exec_request_1 = batch_transfer_request(vec![(ACCOUNT_1_ADDR, first_amount)]).build();
+    // Account 2 is the target of two transfers before it exists, and account 1 already exists.
+    let exec_request_2 = batch_transfer_request(vec![
+        (ACCOUNT_1_ADDR, second_amount),
+        (ACCOUNT_2_ADDR, second_amount),
+        (ACCOUNT_2_ADDR, second_amount),
+    ])
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2)
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        main_purse_balance(&builder, ACCOUNT_1_ADDR),
+        first_amount + second_amount
+    );
+    assert_eq!(
+        main_purse_balance(&builder, ACCOUNT_2_ADDR),
+        second_amount * 2
+    );
+    assert_eq!(
+        main_purse_balance(&builder, DEFAULT_ACCOUNT_ADDR),
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)
+            - *DEFAULT_PAYMENT * 2
+            - first_amount
+            - second_amount * 3
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_apply_any_transfer_when_batch_exceeds_balance() {
+    let exec_request = batch_transfer_request(vec![
+        (ACCOUNT_1_ADDR, U512::from(1)),
+        (ACCOUNT_2_ADDR, U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)),
+    ])
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let error_msg = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_msg.contains(&format!("{:?}", ApiError::Transfer)),
+        error_msg
+    );
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_none());
+}
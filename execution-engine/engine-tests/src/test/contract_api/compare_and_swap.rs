@@ -0,0 +1,37 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, RuntimeArgs};
+
+const CONTRACT_COMPARE_AND_SWAP: &str = "compare_and_swap.wasm";
+const VALUE_NAME: &str = "value";
+
+#[ignore]
+#[test]
+fn should_only_swap_matching_value() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COMPARE_AND_SWAP,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let key: Key = *account
+        .named_keys()
+        .get(VALUE_NAME)
+        .expect("should have value key");
+    let value: u64 = builder.query_expect(key, &[]);
+
+    assert_eq!(value, 3);
+}
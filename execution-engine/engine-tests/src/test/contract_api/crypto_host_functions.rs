@@ -0,0 +1,91 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, runtime_args, CLTyped, Key, RuntimeArgs};
+
+const CONTRACT_CRYPTO_HOST_FUNCTIONS: &str = "crypto_host_functions.wasm";
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const BLAKE2B_KEY: &str = "blake2b";
+const KECCAK256_KEY: &str = "keccak256";
+const ED25519_VALID_KEY: &str = "ed25519_valid";
+
+// Test vector 2 from RFC 8032, section 7.1
+const ED25519_MESSAGE: [u8; 1] = [0x72];
+const ED25519_PUBLIC_KEY: [u8; 32] = [
+    0x3d, 0x40, 0x17, 0xc3, 0xe8, 0x43, 0x89, 0x5a, 0x92, 0xb7, 0x0a, 0xa7, 0x4d, 0x1b, 0x7e, 0xbc,
+    0x9c, 0x98, 0x2c, 0xcf, 0x2e, 0xc4, 0x96, 0x8c, 0xc0, 0xcd, 0x55, 0xf1, 0x2a, 0xf4, 0x66, 0x0c,
+];
+const ED25519_SIGNATURE: [u8; 64] = [
+    0x92, 0xa0, 0x09, 0xa9, 0xf0, 0xd4, 0xca, 0xb8, 0x72, 0x0e, 0x82, 0x0b, 0x5f, 0x64, 0x25, 0x40,
+    0xa2, 0xb2, 0x7b, 0x54, 0x16, 0x50, 0x3f, 0x8f, 0xb3, 0x76, 0x22, 0x23, 0xeb, 0xdb, 0x69, 0xda,
+    0x08, 0x5a, 0xc1, 0xe4, 0x3e, 0x15, 0x99, 0x6e, 0x45, 0x8f, 0x36, 0x13, 0xd0, 0xf1, 0x1d, 0x8c,
+    0x38, 0x7b, 0x2e, 0xae, 0xb4, 0x30, 0x2a, 0xee, 0xb0, 0x0d, 0x29, 0x16, 0x12, 0xbb, 0x0c, 0x00,
+];
+
+fn run_crypto_host_functions(message: &[u8]) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO_HOST_FUNCTIONS,
+        runtime_args! {
+            ARG_MESSAGE => message.to_vec(),
+            ARG_SIGNATURE => ED25519_SIGNATURE.to_vec(),
+            ARG_PUBLIC_KEY => ED25519_PUBLIC_KEY.to_vec(),
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn query_named_value<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+}
+
+#[ignore]
+#[test]
+fn should_compute_digests_on_host() {
+    let builder = run_crypto_host_functions(&[]);
+
+    let blake2b: Vec<u8> = query_named_value(&builder, BLAKE2B_KEY);
+    let keccak256: Vec<u8> = query_named_value(&builder, KECCAK256_KEY);
+
+    let expected_blake2b = vec![
+        0x0e, 0x57, 0x51, 0xc0, 0x26, 0xe5, 0x43, 0xb2, 0xe8, 0xab, 0x2e, 0xb0, 0x60, 0x99, 0xda,
+        0xa1, 0xd1, 0xe5, 0xdf, 0x47, 0x77, 0x8f, 0x77, 0x87, 0xfa, 0xab, 0x45, 0xcd, 0xf1, 0x2f,
+        0xe3, 0xa8,
+    ];
+    let expected_keccak256 = vec![
+        0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+        0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+        0xa4, 0x70,
+    ];
+    assert_eq!(blake2b, expected_blake2b);
+    assert_eq!(keccak256, expected_keccak256);
+}
+
+#[ignore]
+#[test]
+fn should_verify_valid_ed25519_signature() {
+    let builder = run_crypto_host_functions(&ED25519_MESSAGE);
+
+    let valid: bool = query_named_value(&builder, ED25519_VALID_KEY);
+    assert!(valid);
+}
+
+#[ignore]
+#[test]
+fn should_reject_ed25519_signature_of_other_message() {
+    let builder = run_crypto_host_functions(b"other message");
+
+    let valid: bool = query_named_value(&builder, ED25519_VALID_KEY);
+    assert!(!valid);
+}
@@ -0,0 +1,67 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, AccessRights, Key, RuntimeArgs};
+
+const CONTRACT_DELETE_UREF: &str = "delete_uref.wasm";
+const ARG_METHOD: &str = "method";
+const METHOD_DELETE: &str = "delete";
+const METHOD_DELETE_READ_ONLY: &str = "delete_read_only";
+const VALUE_NAME: &str = "value";
+
+fn run(method: &str) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DELETE_UREF,
+        runtime_args! { ARG_METHOD => method },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_not_read_deleted_value() {
+    let mut builder = run(METHOD_DELETE);
+    builder.expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let key: Key = *account
+        .named_keys()
+        .get(VALUE_NAME)
+        .expect("should have value key");
+
+    assert!(builder.query(None, key, &[]).is_err());
+}
+
+#[ignore]
+#[test]
+fn should_not_delete_without_write_access() {
+    let builder = run(METHOD_DELETE_READ_ONLY);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::InvalidAccess {
+            required: AccessRights::WRITE
+        })
+    );
+}
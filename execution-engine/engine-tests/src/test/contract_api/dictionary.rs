@@ -0,0 +1,43 @@
+use engine_core::runtime_context::dictionary_item_key;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::ToBytes, Key, RuntimeArgs, U512};
+
+const CONTRACT_DICTIONARY: &str = "dictionary.wasm";
+const DICTIONARY_NAME: &str = "dictionary";
+const ITEM_KEY_1: &str = "alice";
+
+#[ignore]
+#[test]
+fn should_put_and_get_dictionary_items() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DICTIONARY,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let seed_uref = account
+        .named_keys()
+        .get(DICTIONARY_NAME)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have dictionary seed uref");
+
+    let item_key = dictionary_item_key(seed_uref, &ITEM_KEY_1.to_bytes().unwrap());
+    let value: U512 = builder.query_expect(item_key, &[]);
+
+    assert_eq!(value, U512::from(200));
+}
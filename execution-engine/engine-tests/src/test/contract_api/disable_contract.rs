@@ -0,0 +1,121 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ContractHash, Key, RuntimeArgs};
+
+const CONTRACT_DISABLE_CONTRACT: &str = "disable_contract.wasm";
+const METHOD_PING: &str = "ping";
+const ARG_ACTION: &str = "action";
+const ACTION_INSTALL: &str = "install";
+const ACTION_DISABLE: &str = "disable";
+const ACTION_CALL: &str = "call";
+const CONTRACT_KEY: &str = "disable_contract";
+const AUDIT_KEY: &str = "audit";
+const AUDIT_VALUE: u64 = 42;
+
+fn run_action(builder: &mut InMemoryWasmTestBuilder, action: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DISABLE_CONTRACT,
+        runtime_args! { ARG_ACTION => action },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn setup() -> (InMemoryWasmTestBuilder, ContractHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    run_action(&mut builder, ACTION_INSTALL);
+    builder.expect_success();
+    run_action(&mut builder, ACTION_CALL);
+    builder.expect_success();
+    run_action(&mut builder, ACTION_DISABLE);
+    builder.expect_success();
+
+    let contract_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(CONTRACT_KEY)
+        .expect("should have contract key")
+        .into_hash()
+        .expect("should be hash");
+
+    (builder, contract_hash)
+}
+
+#[ignore]
+#[test]
+fn should_keep_named_keys_of_disabled_contract_queryable() {
+    let (builder, contract_hash) = setup();
+
+    let contract = builder
+        .get_contract(contract_hash)
+        .expect("should have contract");
+    assert!(contract.is_disabled());
+
+    let contract_package = match builder
+        .query(None, Key::Hash(contract.contract_package_hash()), &[])
+        .expect("should have contract package")
+    {
+        StoredValue::ContractPackage(contract_package) => contract_package,
+        _ => panic!("should be contract package"),
+    };
+    assert!(contract_package.current_contract_version().is_none());
+
+    let audit_value: u64 = builder.query_expect(Key::Hash(contract_hash), &[AUDIT_KEY]);
+    assert_eq!(audit_value, AUDIT_VALUE);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_disabled_contract_from_session() {
+    let (mut builder, contract_hash) = setup();
+
+    run_action(&mut builder, ACTION_CALL);
+
+    let response = builder
+        .get_exec_response(3)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::ContractDisabled(disabled_contract_hash))
+            if *disabled_contract_hash == contract_hash
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_disabled_contract_directly() {
+    let (mut builder, contract_hash) = setup();
+
+    let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+        DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        METHOD_PING,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(3)
+        .expect("should have a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::Exec(execution::Error::ContractDisabled(disabled_contract_hash))
+            if *disabled_contract_hash == contract_hash
+    );
+}
@@ -0,0 +1,68 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ApiError, RuntimeArgs};
+
+const CONTRACT_DOWNGRADE_UREF: &str = "downgrade_uref.wasm";
+const ARG_METHOD: &str = "method";
+const METHOD_READ_VALUE: &str = "read_value";
+const METHOD_WRITE_VALUE: &str = "write_value";
+const METHOD_UPGRADE: &str = "upgrade";
+
+fn run(method: &str) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DOWNGRADE_UREF,
+        runtime_args! { ARG_METHOD => method },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_share_downgraded_uref_with_contract() {
+    let mut builder = run(METHOD_READ_VALUE);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_callee_to_regain_removed_rights() {
+    let builder = run(METHOD_WRITE_VALUE);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::ForgedReference(_)));
+}
+
+#[ignore]
+#[test]
+fn should_not_downgrade_uref_to_more_rights() {
+    let builder = run(METHOD_UPGRADE);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::NoAccessRights))
+    );
+}
@@ -0,0 +1,43 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, RuntimeArgs};
+
+const CONTRACT_EMIT_EVENT: &str = "emit_event.wasm";
+const ARG_NAME: &str = "name";
+const ARG_VALUE: &str = "value";
+const EVENT_NAME: &str = "counter_incremented";
+
+#[ignore]
+#[test]
+fn should_record_emitted_events_in_execution_effect() {
+    let value: u64 = 41;
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENT,
+        runtime_args! { ARG_NAME => EVENT_NAME, ARG_VALUE => value },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    let events = &response[0].effect().events;
+
+    assert_eq!(events.len(), 2);
+    for (index, event) in events.iter().enumerate() {
+        assert_eq!(event.emitter, Key::Account(DEFAULT_ACCOUNT_ADDR));
+        assert_eq!(event.name, EVENT_NAME);
+        let data: u64 = event.data.clone().into_t().expect("should be u64");
+        assert_eq!(data, value + index as u64);
+    }
+}
@@ -1,8 +1,11 @@
+use std::time::Duration;
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
 use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{runtime_args, RuntimeArgs};
+use types::{runtime_args, BlockTime, RuntimeArgs};
 
 const CONTRACT_GET_BLOCKTIME: &str = "get_blocktime.wasm";
 const ARG_KNOWN_BLOCK_TIME: &str = "known_block_time";
@@ -25,3 +28,34 @@ fn should_run_get_blocktime_contract() {
         .commit()
         .expect_success();
 }
+
+fn get_blocktime_request(known_block_time: u64) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BLOCKTIME,
+        runtime_args! { ARG_KNOWN_BLOCK_TIME => known_block_time },
+    )
+    .build()
+}
+
+#[ignore]
+#[test]
+fn should_use_block_time_of_test_clock() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .start_clock(BlockTime::new(1_000))
+        .exec(get_blocktime_request(1_000))
+        .expect_success()
+        .commit()
+        .advance_time(Duration::from_secs(60))
+        .exec(get_blocktime_request(61_000))
+        .expect_success()
+        .commit()
+        .set_block_time(BlockTime::new(100_000))
+        .exec(get_blocktime_request(100_000))
+        .expect_success()
+        .commit();
+
+    assert_eq!(builder.get_block_time(), Some(BlockTime::new(100_000)));
+}
@@ -0,0 +1,80 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{CallStackElement, ContractHash, Key, RuntimeArgs};
+
+const CONTRACT_GET_CALL_STACK: &str = "get_call_stack.wasm";
+const ENTRY_POINT_NAME: &str = "store_call_stack";
+const HASH_KEY_NAME: &str = "get_call_stack_contract";
+const CALL_STACK_KEY: &str = "call_stack";
+const SUBCALL_CALL_STACK_KEY: &str = "subcall_call_stack";
+
+fn setup() -> (InMemoryWasmTestBuilder, ContractHash, CallStackElement) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_CALL_STACK,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let contract_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(HASH_KEY_NAME)
+        .expect("should have contract hash")
+        .into_hash()
+        .expect("should be a hash");
+    let contract_package_hash = builder
+        .get_contract(contract_hash)
+        .expect("should have contract")
+        .contract_package_hash();
+
+    let contract_element = CallStackElement::stored_contract(contract_package_hash, contract_hash);
+
+    (builder, contract_hash, contract_element)
+}
+
+#[ignore]
+#[test]
+fn should_get_call_stack_of_session_and_subcall() {
+    let (builder, _contract_hash, contract_element) = setup();
+    let session_element = CallStackElement::session(DEFAULT_ACCOUNT_ADDR);
+
+    let session_call_stack: Vec<CallStackElement> =
+        builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[CALL_STACK_KEY]);
+    assert_eq!(session_call_stack, vec![session_element]);
+
+    let subcall_call_stack: Vec<CallStackElement> = builder.query_expect(
+        Key::Account(DEFAULT_ACCOUNT_ADDR),
+        &[SUBCALL_CALL_STACK_KEY],
+    );
+    assert_eq!(subcall_call_stack, vec![session_element, contract_element]);
+}
+
+#[ignore]
+#[test]
+fn should_get_call_stack_of_contract_called_by_deploy() {
+    let (mut builder, contract_hash, contract_element) = setup();
+
+    let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+        DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        ENTRY_POINT_NAME,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let call_stack: Vec<CallStackElement> =
+        builder.query_expect(Key::Hash(contract_hash), &[CALL_STACK_KEY]);
+    assert_eq!(call_stack, vec![contract_element]);
+}
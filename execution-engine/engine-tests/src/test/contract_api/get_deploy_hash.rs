@@ -0,0 +1,41 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, RuntimeArgs};
+
+const CONTRACT_GET_DEPLOY_HASH: &str = "get_deploy_hash.wasm";
+const ARG_AMOUNT: &str = "amount";
+const DEPLOY_HASH_KEY: &str = "deploy_hash";
+
+fn run_get_deploy_hash(builder: &mut InMemoryWasmTestBuilder, deploy_hash: [u8; 32]) -> [u8; 32] {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(CONTRACT_GET_DEPLOY_HASH, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash(deploy_hash)
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).expect_success().commit();
+
+    builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[DEPLOY_HASH_KEY])
+}
+
+#[ignore]
+#[test]
+fn should_get_hash_of_executing_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    assert_eq!(run_get_deploy_hash(&mut builder, [1; 32]), [1; 32]);
+    assert_eq!(run_get_deploy_hash(&mut builder, [2; 32]), [2; 32]);
+}
@@ -0,0 +1,137 @@
+use engine_core::engine_state::{genesis::ExecConfig, run_genesis_request::RunGenesisRequest};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_ACCOUNTS, DEFAULT_GAS_PRICE, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_INFLATION_RATE,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_SLASH_RATE,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+        STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_GET_GAS_PRICE: &str = "get_gas_price.wasm";
+const ARG_KNOWN_GAS_PRICE: &str = "known_gas_price";
+const CUSTOM_GAS_PRICE: u64 = 7;
+const UPGRADED_GAS_PRICE: u64 = 3;
+
+fn get_gas_price_request(known_gas_price: u64) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_GAS_PRICE,
+        runtime_args! { ARG_KNOWN_GAS_PRICE => known_gas_price },
+    )
+}
+
+#[ignore]
+#[test]
+fn should_report_default_gas_price() {
+    let exec_request = get_gas_price_request(DEFAULT_GAS_PRICE).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_charge_with_gas_price_from_genesis() {
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        DEFAULT_ACCOUNTS.clone(),
+        *DEFAULT_WASM_COSTS,
+        CUSTOM_GAS_PRICE,
+        DEFAULT_INFLATION_RATE,
+        DEFAULT_SLASH_RATE,
+        DEFAULT_UNBONDING_DELAY,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let exec_request = get_gas_price_request(CUSTOM_GAS_PRICE).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let balance_before = builder.get_purse_balance(main_purse);
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let gas = builder.last_exec_gas_cost();
+    let motes = Motes::from_gas(gas, CUSTOM_GAS_PRICE).expect("should have motes");
+
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        balance_before - motes.value(),
+        "balance should be charged at the genesis gas price"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_change_gas_price_on_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .with_new_gas_price(UPGRADED_GAS_PRICE)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let exec_request = get_gas_price_request(UPGRADED_GAS_PRICE)
+        .with_protocol_version(new_protocol_version)
+        .build();
+
+    builder.exec(exec_request).commit().expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_keep_gas_price_on_upgrade_without_new_gas_price() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let exec_request = get_gas_price_request(DEFAULT_GAS_PRICE)
+        .with_protocol_version(new_protocol_version)
+        .build();
+
+    builder.exec(exec_request).commit().expect_success();
+}
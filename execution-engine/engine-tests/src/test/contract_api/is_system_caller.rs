@@ -0,0 +1,54 @@
+use engine_core::engine_state::{upgrade::Migration, SYSTEM_ACCOUNT_ADDR};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, Key, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_IS_SYSTEM_CALLER: &str = "is_system_caller.wasm";
+const IS_SYSTEM_CALLER_KEY: &str = "is_system_caller";
+
+fn is_system_caller(builder: &InMemoryWasmTestBuilder, account_hash: AccountHash) -> bool {
+    builder.query_expect(Key::Account(account_hash), &[IS_SYSTEM_CALLER_KEY])
+}
+
+#[ignore]
+#[test]
+fn should_not_be_system_caller_as_user_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_IS_SYSTEM_CALLER,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert!(!is_system_caller(&builder, DEFAULT_ACCOUNT_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_be_system_caller_as_system_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // Upgrade migrations are run by the system account.
+    let sem_ver = builder.get_protocol_version().value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+    let migration = Migration::wasm(
+        IS_SYSTEM_CALLER_KEY,
+        utils::read_wasm_file_bytes(CONTRACT_IS_SYSTEM_CALLER),
+        RuntimeArgs::default(),
+    );
+    builder.upgrade_with_migrations(new_protocol_version, vec![migration]);
+
+    assert!(is_system_caller(&builder, SYSTEM_ACCOUNT_ADDR));
+}
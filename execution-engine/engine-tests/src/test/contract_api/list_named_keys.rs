@@ -5,10 +5,12 @@ use engine_test_support::{
 use types::{account::AccountHash, contracts::NamedKeys, runtime_args, Key, RuntimeArgs};
 
 const CONTRACT_LIST_NAMED_KEYS: &str = "list_named_keys.wasm";
+const CONTRACT_LIST_NAMED_KEYS_PAGED: &str = "list_named_keys_paged.wasm";
 const NEW_NAME_ACCOUNT: &str = "Account";
 const NEW_NAME_HASH: &str = "Hash";
 const ARG_INITIAL_NAMED_KEYS: &str = "initial_named_args";
 const ARG_NEW_NAMED_KEYS: &str = "new_named_keys";
+const ARG_KEY_COUNT: &str = "key_count";
 
 #[ignore]
 #[test]
@@ -42,3 +44,22 @@ fn should_list_named_keys() {
 
     builder.exec(exec_request).commit().expect_success();
 }
+
+#[ignore]
+#[test]
+fn should_list_named_keys_paged() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // Spans several pages, with the last one only partially filled.
+    let key_count: u32 = 250;
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_LIST_NAMED_KEYS_PAGED,
+        runtime_args! { ARG_KEY_COUNT => key_count },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+}
@@ -1,16 +1,37 @@
 mod account;
+mod batch_transfer;
+mod call_contract_read_only;
+mod compare_and_swap;
 mod create_purse;
+mod crypto_host_functions;
+mod delete_uref;
+mod dictionary;
+mod disable_contract;
+mod downgrade_uref;
+mod emit_event;
 mod get_arg;
 mod get_blocktime;
+mod get_call_stack;
 mod get_caller;
+mod get_deploy_hash;
+mod get_gas_price;
 mod get_phase;
+mod is_system_caller;
 mod list_named_keys;
 mod main_purse;
 mod mint_purse;
+mod print;
+mod random_bytes;
+mod ret_without_transfer;
 mod revert;
+mod storage_append;
+mod storage_counter;
 mod subcall;
 mod transfer;
 mod transfer_purse_to_account;
 mod transfer_purse_to_purse;
 mod transfer_stored;
 mod transfer_u512_stored;
+mod typed_entry_point;
+mod u512_mul_div;
+mod write_and_return_old;
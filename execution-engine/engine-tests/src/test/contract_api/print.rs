@@ -0,0 +1,58 @@
+use engine_core::engine_state::EngineConfig;
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_PRINT: &str = "print.wasm";
+const ARG_TEXT: &str = "text";
+const SHORT_TEXT: &str = "debug";
+const LONG_TEXT: &str = "a considerably longer line of contract debug output";
+
+fn engine_config(enable_contract_debug: bool) -> EngineConfig {
+    EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_enable_contract_debug(enable_contract_debug)
+}
+
+/// Runs a deploy which prints `text`, returning its cost.
+fn print_deploy_cost(enable_contract_debug: bool, text: &str) -> Gas {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PRINT,
+        runtime_args! { ARG_TEXT => text.to_string() },
+    )
+    .build();
+
+    let mut builder =
+        InMemoryWasmTestBuilder::new_with_config(engine_config(enable_contract_debug));
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder.last_exec_gas_cost()
+}
+
+#[ignore]
+#[test]
+fn should_print_with_contract_debug_enabled() {
+    print_deploy_cost(true, SHORT_TEXT);
+}
+
+#[ignore]
+#[test]
+fn should_charge_for_print_with_contract_debug_disabled() {
+    assert_eq!(
+        print_deploy_cost(false, SHORT_TEXT),
+        print_deploy_cost(true, SHORT_TEXT)
+    );
+    assert_eq!(
+        print_deploy_cost(false, LONG_TEXT),
+        print_deploy_cost(true, LONG_TEXT)
+    );
+    assert!(print_deploy_cost(false, LONG_TEXT) > print_deploy_cost(false, SHORT_TEXT));
+}
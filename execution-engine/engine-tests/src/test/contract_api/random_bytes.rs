@@ -0,0 +1,100 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{execute_request::ExecuteRequest, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, RuntimeArgs};
+
+const CONTRACT_RANDOM_BYTES: &str = "random_bytes.wasm";
+const ARG_AMOUNT: &str = "amount";
+const ARG_COUNT: &str = "count";
+const RANDOM_BYTES_KEY: &str = "random_bytes";
+const RANDOM_BYTES_COUNT: u32 = 64;
+const OUT_OF_BOUNDS_COUNT: u32 = u32::max_value();
+
+fn random_bytes_request(deploy_hash: [u8; 32], block_time: u64, count: u32) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_RANDOM_BYTES, runtime_args! { ARG_COUNT => count })
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+fn run_random_bytes(deploy_hash: [u8; 32], block_time: u64) -> Vec<u8> {
+    let exec_request = random_bytes_request(deploy_hash, block_time, RANDOM_BYTES_COUNT);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[RANDOM_BYTES_KEY])
+}
+
+#[ignore]
+#[test]
+fn should_return_same_random_bytes_for_same_deploy() {
+    let bytes_1 = run_random_bytes([1; 32], 42);
+    let bytes_2 = run_random_bytes([1; 32], 42);
+
+    assert_eq!(bytes_1.len(), RANDOM_BYTES_COUNT as usize);
+    assert_eq!(bytes_1, bytes_2);
+}
+
+#[ignore]
+#[test]
+fn should_return_different_random_bytes_for_different_deploy_hash() {
+    let bytes_1 = run_random_bytes([1; 32], 42);
+    let bytes_2 = run_random_bytes([2; 32], 42);
+
+    assert_ne!(bytes_1, bytes_2);
+}
+
+#[ignore]
+#[test]
+fn should_return_different_random_bytes_for_different_block_time() {
+    let bytes_1 = run_random_bytes([1; 32], 42);
+    let bytes_2 = run_random_bytes([1; 32], 43);
+
+    assert_ne!(bytes_1, bytes_2);
+}
+
+#[ignore]
+#[test]
+fn should_fail_random_bytes_out_of_memory_bounds() {
+    let exec_request = random_bytes_request([1; 32], 42, OUT_OF_BOUNDS_COUNT);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::MemoryOutOfBounds { size, .. })
+            if *size == OUT_OF_BOUNDS_COUNT
+    );
+}
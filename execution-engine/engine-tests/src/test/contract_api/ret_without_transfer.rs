@@ -0,0 +1,65 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_RET_WITHOUT_TRANSFER: &str = "ret_without_transfer.wasm";
+const CONTRACT_RET_WITHOUT_TRANSFER_CALL: &str = "ret_without_transfer_call.wasm";
+const ARG_METHOD: &str = "method";
+const METHOD_RET: &str = "ret";
+const METHOD_RET_WITHOUT_TRANSFER: &str = "ret_without_transfer";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RET_WITHOUT_TRANSFER,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_method(builder: &mut InMemoryWasmTestBuilder, method: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RET_WITHOUT_TRANSFER_CALL,
+        runtime_args! { ARG_METHOD => method },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_grant_returned_urefs_to_caller() {
+    let mut builder = setup();
+    call_method(&mut builder, METHOD_RET);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_grant_urefs_returned_without_transfer() {
+    let mut builder = setup();
+    call_method(&mut builder, METHOD_RET_WITHOUT_TRANSFER);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::ForgedReference(_)));
+}
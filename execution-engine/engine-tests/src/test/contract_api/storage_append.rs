@@ -0,0 +1,36 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, RuntimeArgs};
+
+const CONTRACT_STORAGE_APPEND: &str = "storage_append.wasm";
+const LOG_NAME: &str = "log";
+
+#[ignore]
+#[test]
+fn should_append_items_to_stored_list() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORAGE_APPEND,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let log_key: Key = *account
+        .named_keys()
+        .get(LOG_NAME)
+        .expect("should have log key");
+    let log: Vec<String> = builder.query_expect(log_key, &[]);
+    assert_eq!(log, vec!["first", "second", "third"]);
+}
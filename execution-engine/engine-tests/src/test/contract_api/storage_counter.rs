@@ -0,0 +1,51 @@
+use engine_shared::transform::Transform;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, RuntimeArgs};
+
+const CONTRACT_STORAGE_COUNTER: &str = "storage_counter.wasm";
+const COUNTER_NAME: &str = "counter";
+const LAST_ID_NAME: &str = "last_id";
+
+fn run_storage_counter(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORAGE_COUNTER,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_increment_counter_by_adding_to_it() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    run_storage_counter(&mut builder);
+    let last_id: u64 = builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[LAST_ID_NAME]);
+    assert_eq!(last_id, 2);
+
+    run_storage_counter(&mut builder);
+    let last_id: u64 = builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[LAST_ID_NAME]);
+    assert_eq!(last_id, 4);
+
+    let counter: u64 = builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[COUNTER_NAME]);
+    assert_eq!(counter, 4);
+
+    let counter_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(COUNTER_NAME)
+        .expect("should have counter key");
+    let transforms = builder.get_transforms();
+    assert_eq!(
+        transforms[1].get(&counter_key.normalize()),
+        Some(&Transform::AddUInt64(2))
+    );
+}
@@ -0,0 +1,87 @@
+use std::convert::TryFrom;
+
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, CLValue, Key, RuntimeArgs, U512};
+
+const CONTRACT_TYPED_ENTRY_POINT: &str = "typed_entry_point.wasm";
+const CONTRACT_TYPED_ENTRY_POINT_CALL: &str = "typed_entry_point_call.wasm";
+const ARG_VALUE: &str = "value";
+const ARG_USE_WRONG_TYPE: &str = "use_wrong_type";
+const CONTRACT_KEY: &str = "typed_entry_point";
+const VALUE_KEY: &str = "value";
+const VALUE: u64 = 42;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TYPED_ENTRY_POINT,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_set_value(builder: &mut InMemoryWasmTestBuilder, use_wrong_type: bool) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TYPED_ENTRY_POINT_CALL,
+        runtime_args! { ARG_VALUE => VALUE, ARG_USE_WRONG_TYPE => use_wrong_type },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn read_value(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let stored_value = builder
+        .query(
+            None,
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            &[CONTRACT_KEY, VALUE_KEY],
+        )
+        .expect("should have value");
+    CLValue::try_from(stored_value)
+        .expect("should have CLValue")
+        .into_t()
+        .expect("should convert successfully")
+}
+
+#[ignore]
+#[test]
+fn should_call_entry_point_with_declared_arg_type() {
+    let mut builder = setup();
+    call_set_value(&mut builder, false);
+    builder.expect_success();
+
+    assert_eq!(read_value(&builder), U512::from(VALUE));
+}
+
+#[ignore]
+#[test]
+fn should_reject_call_with_mismatched_arg_type() {
+    let mut builder = setup();
+    call_set_value(&mut builder, true);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::TypeMismatch(_)));
+
+    assert_eq!(read_value(&builder), U512::zero());
+}
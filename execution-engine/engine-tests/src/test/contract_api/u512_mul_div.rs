@@ -0,0 +1,48 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_U512_MUL_DIV: &str = "u512_mul_div.wasm";
+const ARG_A: &str = "a";
+const ARG_B: &str = "b";
+const ARG_C: &str = "c";
+const RESULT_KEY: &str = "result";
+
+fn run_u512_mul_div(a: U512, b: U512, c: U512) -> Option<U512> {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_U512_MUL_DIV,
+        runtime_args! { ARG_A => a, ARG_B => b, ARG_C => c },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[RESULT_KEY])
+}
+
+#[ignore]
+#[test]
+fn should_mul_div_without_overflowing_intermediate() {
+    let max = U512::max_value();
+    assert_eq!(run_u512_mul_div(max, max - 1, max), Some(max - 1));
+    assert_eq!(
+        run_u512_mul_div(U512::from(1_000), 3.into(), 7.into()),
+        Some(428.into())
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_none_for_zero_denominator_or_overflowing_result() {
+    let max = U512::max_value();
+    assert_eq!(run_u512_mul_div(max, 1.into(), 0.into()), None);
+    assert_eq!(run_u512_mul_div(max, 2.into(), 1.into()), None);
+}
@@ -0,0 +1,44 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, CLTyped, Key, RuntimeArgs, U512};
+
+const CONTRACT_WRITE_AND_RETURN_OLD: &str = "write_and_return_old.wasm";
+const VALUE_NAME: &str = "value";
+const COUNTER_NAME: &str = "counter";
+
+fn query_named_value<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let key: Key = *account
+        .named_keys()
+        .get(name)
+        .expect("should have named key");
+    builder.query_expect(key, &[])
+}
+
+#[ignore]
+#[test]
+fn should_write_and_return_old_value_and_add_and_read() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_WRITE_AND_RETURN_OLD,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let value: String = query_named_value(&builder, VALUE_NAME);
+    assert_eq!(value, "third");
+
+    let counter: U512 = query_named_value(&builder, COUNTER_NAME);
+    assert_eq!(counter, U512::from(12));
+}
@@ -0,0 +1,180 @@
+use contract::contract_api::error::UserError;
+use contract_macro::CLError;
+use engine_core::{
+    engine_state::{self, execution_effect::ExecutionEffect},
+    execution,
+};
+use engine_grpc_server::engine_server::ipc::DeployResult;
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, runtime_args, ApiError, CLTyped, Key, RuntimeArgs, U512};
+
+const CONTRACT_MACRO: &str = "contract_macro.wasm";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_NAME: &str = "name";
+const ARG_VALUE: &str = "value";
+const ARG_LHS: &str = "lhs";
+const ARG_RHS: &str = "rhs";
+const METHOD_STORE_VALUE: &str = "store_value";
+const METHOD_STORE_SUM: &str = "store_sum";
+const STORED_NAME: &str = "stored";
+
+#[derive(CLError, Debug, PartialEq)]
+enum Error {
+    Overflow = 10,
+}
+
+fn query_stored<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder) -> T {
+    builder.query_expect(Key::Account(DEFAULT_ACCOUNT_ADDR), &[STORED_NAME])
+}
+
+#[ignore]
+#[test]
+fn should_dispatch_to_method_with_named_args() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MACRO,
+        runtime_args! {
+            ARG_ENTRY_POINT => METHOD_STORE_VALUE,
+            ARG_NAME => STORED_NAME,
+            ARG_VALUE => U512::from(100),
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(query_stored::<U512>(&builder), U512::from(100));
+}
+
+#[ignore]
+#[test]
+fn should_dispatch_to_method_returning_value() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MACRO,
+        runtime_args! {
+            ARG_ENTRY_POINT => METHOD_STORE_SUM,
+            ARG_NAME => STORED_NAME,
+            ARG_LHS => 2u64,
+            ARG_RHS => 3u64,
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(query_stored::<u64>(&builder), 5);
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_unknown_entry_point() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MACRO,
+        runtime_args! { ARG_ENTRY_POINT => "unknown" },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = utils::get_error_message(response);
+    assert!(error_message.contains(&format!("{:?}", ApiError::InvalidArgument)));
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_missing_method_arg() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MACRO,
+        runtime_args! {
+            ARG_ENTRY_POINT => METHOD_STORE_SUM,
+            ARG_NAME => STORED_NAME,
+            ARG_LHS => 2u64,
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = utils::get_error_message(response);
+    assert!(error_message.contains(&format!("{:?}", ApiError::MissingArgument)));
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_derived_user_error() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MACRO,
+        runtime_args! {
+            ARG_ENTRY_POINT => METHOD_STORE_SUM,
+            ARG_NAME => STORED_NAME,
+            ARG_LHS => u64::max_value(),
+            ARG_RHS => 1u64,
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let api_error = match execution_result.as_error() {
+        Some(engine_state::Error::Exec(execution::Error::Revert(api_error))) => *api_error,
+        other => panic!("should have reverted, got {:?}", other),
+    };
+    assert_eq!(Some(Error::Overflow), Error::from_api_error(api_error));
+
+    // The error should also be recoverable from the deploy result reported over gRPC.
+    let mut deploy_result = DeployResult::from((
+        execution::Error::Revert(api_error),
+        ExecutionEffect::default(),
+        Gas::default(),
+    ));
+    let message = deploy_result
+        .take_execution_result()
+        .take_error()
+        .take_exec_error()
+        .take_message();
+    let error = Error::from_error_message(&message).expect("should be a user error");
+    assert_eq!(error, Error::Overflow);
+    assert_eq!(error.name(), "Overflow");
+}
@@ -0,0 +1,145 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{deploy_item::EXECUTED_DEPLOY_RETENTION_MILLIS, Error},
+    DeployHash,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const ARG_AMOUNT: &str = "amount";
+const TIMESTAMP: u64 = 1_000;
+const TTL_MILLIS: u32 = 500;
+
+fn deploy_item_builder(deploy_hash: DeployHash) -> DeployItemBuilder {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+}
+
+fn exec_with_ttl(block_time: u64) -> InMemoryWasmTestBuilder {
+    let deploy = deploy_item_builder([1; 32])
+        .with_ttl(TIMESTAMP, TTL_MILLIS)
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_until_expiry() {
+    let expiry = TIMESTAMP + u64::from(TTL_MILLIS);
+    exec_with_ttl(expiry).expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_expired_deploy() {
+    let expiry = TIMESTAMP + u64::from(TTL_MILLIS);
+    let builder = exec_with_ttl(expiry + 1);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::DeployExpired { expiry: actual_expiry, block_time }
+        if *actual_expiry == expiry && *block_time == expiry + 1
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_missing_dependency() {
+    let dependency = [1; 32];
+    let deploy = deploy_item_builder([2; 32])
+        .with_dependencies(&[dependency])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::MissingDependency(hash) if hash.value() == dependency
+    );
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_whose_dependency_was_committed() {
+    let dependency = [1; 32];
+    let exec_request_1 =
+        ExecuteRequestBuilder::from_deploy_item(deploy_item_builder(dependency).build()).build();
+    let deploy = deploy_item_builder([2; 32])
+        .with_dependencies(&[dependency])
+        .build();
+    let exec_request_2 = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_expired_dependency_record() {
+    let dependency = [1; 32];
+    let exec_request_1 =
+        ExecuteRequestBuilder::from_deploy_item(deploy_item_builder(dependency).build())
+            .with_block_time(TIMESTAMP)
+            .build();
+    let deploy = deploy_item_builder([2; 32])
+        .with_dependencies(&[dependency])
+        .build();
+    let exec_request_2 = ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(TIMESTAMP + EXECUTED_DEPLOY_RETENTION_MILLIS + 1)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::MissingDependency(hash) if hash.value() == dependency
+    );
+}
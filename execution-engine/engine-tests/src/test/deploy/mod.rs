@@ -1,3 +1,7 @@
+mod deploy_header;
 mod non_standard_payment;
 mod preconditions;
+mod replay_protection;
+mod speculative_exec;
+mod standard_payment_source;
 mod stored_contracts;
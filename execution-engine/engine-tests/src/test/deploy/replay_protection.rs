@@ -0,0 +1,160 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, Error};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ARG_AMOUNT: &str = "amount";
+const ARG_TARGET: &str = "target";
+const TRANSFER_RESULT_KEY: &str = "transfer_result";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+
+fn run_genesis_request_with_nonces() -> RunGenesisRequest {
+    let mut exec_config = DEFAULT_EXEC_CONFIG.clone();
+    exec_config.set_nonces_enabled(true);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn deploy_item_builder(deploy_hash: [u8; 32], nonce: u64) -> DeployItemBuilder {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .with_nonce(nonce)
+}
+
+fn do_nothing_request(deploy_hash: [u8; 32], nonce: u64) -> ExecuteRequestBuilder {
+    let deploy = deploy_item_builder(deploy_hash, nonce)
+        .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::default())
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy)
+}
+
+fn account_nonce(builder: &InMemoryWasmTestBuilder, account_hash: AccountHash) -> Option<u64> {
+    builder
+        .get_account(account_hash)
+        .expect("should have account")
+        .nonce()
+}
+
+#[ignore]
+#[test]
+fn should_not_set_nonces_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    assert_eq!(account_nonce(&builder, DEFAULT_ACCOUNT_ADDR), None);
+
+    // Without replay protection, the deploy's nonce is ignored.
+    builder
+        .exec(do_nothing_request([1; 32], 5).build())
+        .expect_success()
+        .commit();
+    assert_eq!(account_nonce(&builder, DEFAULT_ACCOUNT_ADDR), None);
+}
+
+#[ignore]
+#[test]
+fn should_increment_nonce_of_executed_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request_with_nonces());
+    assert_eq!(account_nonce(&builder, DEFAULT_ACCOUNT_ADDR), Some(0));
+
+    builder
+        .exec(do_nothing_request([1; 32], 0).build())
+        .expect_success()
+        .commit();
+    assert_eq!(account_nonce(&builder, DEFAULT_ACCOUNT_ADDR), Some(1));
+
+    builder
+        .exec(do_nothing_request([2; 32], 1).build())
+        .expect_success()
+        .commit();
+    assert_eq!(account_nonce(&builder, DEFAULT_ACCOUNT_ADDR), Some(2));
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_replayed_nonce() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request_with_nonces())
+        .exec(do_nothing_request([1; 32], 0).build())
+        .expect_success()
+        .commit()
+        .exec(do_nothing_request([2; 32], 0).build());
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::InvalidNonce {
+            expected: 1,
+            actual: 0
+        }
+    );
+}
+
+#[ignore]
+#[test]
+fn should_keep_session_changes_to_account_when_incrementing_nonce() {
+    let transferred_amount = U512::from(1_000_000_000);
+    let deploy = deploy_item_builder([1; 32], 0)
+        .with_session_code(
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => transferred_amount },
+        )
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request_with_nonces())
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.named_keys().contains_key(TRANSFER_RESULT_KEY));
+    assert_eq!(account.nonce(), Some(1));
+    // Accounts created after genesis are protected as well.
+    assert_eq!(account_nonce(&builder, ACCOUNT_1_ADDR), Some(0));
+}
+
+#[ignore]
+#[test]
+fn should_protect_accounts_created_by_wasmless_transfer() {
+    let deploy = deploy_item_builder([1; 32], 0)
+        .with_transfer_args(runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(1_000_000_000),
+        })
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request_with_nonces())
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(account_nonce(&builder, ACCOUNT_1_ADDR), Some(0));
+}
@@ -0,0 +1,200 @@
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{ipc, ipc_grpc::ExecutionEngineService};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, InMemoryWasmTestBuilder, DEFAULT_GAS_PRICE, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_REVERT: &str = "revert.wasm";
+const ARG_AMOUNT: &str = "amount";
+
+fn speculative_exec_request(
+    parent_state_hash: Vec<u8>,
+    session: &str,
+    payment_amount: U512,
+    estimate_gas: bool,
+) -> ipc::SpeculativeExecRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(session, RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => payment_amount })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+
+    let mut request = ipc::SpeculativeExecRequest::new();
+    request.set_parent_state_hash(parent_state_hash);
+    request.set_deploy(deploy.into());
+    request.set_protocol_version(ProtocolVersion::V1_1_0.into());
+    request.set_estimate_gas(estimate_gas);
+    request
+}
+
+fn cost(mut deploy_result: ipc::DeployResult) -> U512 {
+    assert!(deploy_result.has_execution_result());
+    let mut execution_result = deploy_result.take_execution_result();
+    U512::try_from(execution_result.take_cost()).expect("should parse cost")
+}
+
+fn speculative_exec(
+    builder: &InMemoryWasmTestBuilder,
+    request: ipc::SpeculativeExecRequest,
+) -> ipc::SpeculativeExecResponse {
+    builder
+        .get_engine_state()
+        .speculative_exec(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should get speculative exec response")
+}
+
+#[ignore]
+#[test]
+fn should_return_cost_and_effects_without_committing() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let post_state_hash = builder.get_post_state_hash();
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let balance_before = builder.get_purse_balance(main_purse);
+
+    let request = speculative_exec_request(
+        post_state_hash.clone(),
+        CONTRACT_DO_NOTHING,
+        *DEFAULT_PAYMENT,
+        false,
+    );
+    let mut response = speculative_exec(&builder, request);
+
+    assert!(response.has_success());
+    let mut deploy_result = response.take_success();
+    assert!(deploy_result.has_execution_result());
+    let mut execution_result = deploy_result.take_execution_result();
+    assert!(!execution_result.has_error());
+    assert!(!execution_result
+        .get_effects()
+        .get_transform_map()
+        .is_empty());
+    let cost = U512::try_from(execution_result.take_cost()).expect("should parse cost");
+    assert!(cost > U512::zero());
+
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+    assert_eq!(builder.get_purse_balance(main_purse), balance_before);
+}
+
+#[ignore]
+#[test]
+fn should_report_revert() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let request = speculative_exec_request(
+        builder.get_post_state_hash(),
+        CONTRACT_REVERT,
+        *DEFAULT_PAYMENT,
+        false,
+    );
+    let mut response = speculative_exec(&builder, request);
+
+    assert!(response.has_success());
+    let execution_result = response.take_success().take_execution_result();
+    assert!(execution_result.has_error());
+    assert!(!response.has_gas_breakdown());
+}
+
+#[ignore]
+#[test]
+fn should_report_missing_parent() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_parent = vec![255; 32];
+    let request = speculative_exec_request(
+        missing_parent.clone(),
+        CONTRACT_DO_NOTHING,
+        *DEFAULT_PAYMENT,
+        false,
+    );
+    let response = speculative_exec(&builder, request);
+
+    assert!(response.has_missing_parent());
+    assert_eq!(
+        response.get_missing_parent().get_hash(),
+        &missing_parent[..]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_estimate_gas_with_breakdown() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let post_state_hash = builder.get_post_state_hash();
+
+    let request = speculative_exec_request(
+        post_state_hash.clone(),
+        CONTRACT_DO_NOTHING,
+        *DEFAULT_PAYMENT,
+        false,
+    );
+    let expected_cost = cost(speculative_exec(&builder, request).take_success());
+
+    let request =
+        speculative_exec_request(post_state_hash, CONTRACT_DO_NOTHING, *DEFAULT_PAYMENT, true);
+    let mut response = speculative_exec(&builder, request);
+
+    assert!(response.has_gas_breakdown());
+    let mut gas_breakdown = response.take_gas_breakdown();
+    let opcodes = U512::try_from(gas_breakdown.take_opcodes()).expect("should parse opcodes");
+    let host_functions =
+        U512::try_from(gas_breakdown.take_host_functions()).expect("should parse host functions");
+
+    let estimated_cost = cost(response.take_success());
+    assert_eq!(estimated_cost, expected_cost);
+    assert!(opcodes > U512::zero());
+    assert_eq!(opcodes + host_functions, estimated_cost);
+}
+
+#[ignore]
+#[test]
+fn should_estimate_gas_beyond_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let post_state_hash = builder.get_post_state_hash();
+
+    let request = speculative_exec_request(
+        post_state_hash.clone(),
+        CONTRACT_DO_NOTHING,
+        *DEFAULT_PAYMENT,
+        true,
+    );
+    let required_gas = cost(speculative_exec(&builder, request).take_success());
+
+    // one mote short of paying for the whole deploy
+    let payment_amount = required_gas * U512::from(DEFAULT_GAS_PRICE) - U512::one();
+
+    let request = speculative_exec_request(
+        post_state_hash.clone(),
+        CONTRACT_DO_NOTHING,
+        payment_amount,
+        false,
+    );
+    let response = speculative_exec(&builder, request);
+    assert!(response.get_success().get_execution_result().has_error());
+
+    let request =
+        speculative_exec_request(post_state_hash, CONTRACT_DO_NOTHING, payment_amount, true);
+    let mut response = speculative_exec(&builder, request);
+    let deploy_result = response.take_success();
+    assert!(!deploy_result.get_execution_result().has_error());
+    assert_eq!(cost(deploy_result), required_gas);
+}
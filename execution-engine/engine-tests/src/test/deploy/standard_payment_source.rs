@@ -0,0 +1,152 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{Error, CONV_RATE},
+    execution,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_ACCOUNT_KEY, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, URef, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const TRANSFER_PURSE_TO_ACCOUNT_WASM: &str = "transfer_purse_to_account.wasm";
+const TRANSFER_MAIN_PURSE_TO_NEW_PURSE_WASM: &str = "transfer_main_purse_to_new_purse.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const ARG_SOURCE: &str = "source";
+const ARG_DESTINATION: &str = "destination";
+const TEST_PURSE_NAME: &str = "test-purse";
+
+/// Creates account 1 and a purse funded by it, stored under `TEST_PURSE_NAME`.
+fn setup() -> (InMemoryWasmTestBuilder, URef) {
+    let account_1_funding_amount = U512::from(100_000_000);
+    let account_1_purse_funding_amount = U512::from(50_000_000);
+
+    let create_account_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        TRANSFER_PURSE_TO_ACCOUNT_WASM,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => account_1_funding_amount },
+    )
+    .build();
+
+    let create_purse_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        TRANSFER_MAIN_PURSE_TO_NEW_PURSE_WASM,
+        runtime_args! {
+            ARG_DESTINATION => TEST_PURSE_NAME,
+            ARG_AMOUNT => account_1_purse_funding_amount
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(create_account_request)
+        .expect_success()
+        .commit()
+        .exec(create_purse_request)
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    let purse = account_1.named_keys()[TEST_PURSE_NAME]
+        .into_uref()
+        .expect("should have uref");
+
+    assert_eq!(
+        builder.get_purse_balance(purse),
+        account_1_purse_funding_amount
+    );
+
+    (builder, purse)
+}
+
+#[ignore]
+#[test]
+fn should_charge_source_purse_with_standard_payment() {
+    let (mut builder, purse) = setup();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    let purse_starting_balance = builder.get_purse_balance(purse);
+    let main_purse_starting_balance = builder.get_purse_balance(account_1.main_purse());
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => U512::from(10_000_000),
+                ARG_SOURCE => purse
+            })
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .with_deploy_hash([3; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response")
+        .clone();
+    let motes = Motes::from_gas(utils::get_success_result(&response).cost(), CONV_RATE)
+        .expect("should have motes");
+
+    assert_eq!(
+        builder.get_purse_balance(purse),
+        purse_starting_balance - motes.value(),
+        "source purse should pay for the deploy"
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        main_purse_starting_balance,
+        "main purse should be untouched"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_charge_source_purse_of_another_account() {
+    let (mut builder, purse) = setup();
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT,
+                ARG_SOURCE => purse
+            })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_deploy_hash([3; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::Exec(execution::Error::ForgedReference(uref)) if uref == purse
+    );
+}
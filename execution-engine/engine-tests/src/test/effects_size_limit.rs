@@ -0,0 +1,123 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{run_genesis_request::RunGenesisRequest, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_LARGE_EFFECTS: &str = "large_effects.wasm";
+const ARG_COUNT: &str = "count";
+const ARG_SIZE: &str = "size";
+const MAX_EFFECTS_SIZE: usize = 64 * 1024;
+
+fn run_genesis_request_with_max_effects_size() -> RunGenesisRequest {
+    let mut exec_config = DEFAULT_EXEC_CONFIG.clone();
+    exec_config.set_max_effects_size(MAX_EFFECTS_SIZE as u64);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn large_effects_request(count: u32, size: u32) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_LARGE_EFFECTS,
+        runtime_args! { ARG_COUNT => count, ARG_SIZE => size },
+    )
+}
+
+fn run_large_effects(count: u32, size: u32) -> InMemoryWasmTestBuilder {
+    let exec_request = large_effects_request(count, size).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request_with_max_effects_size())
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_allow_effects_within_limit() {
+    let mut builder = run_large_effects(4, 1_024);
+    builder.expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.named_keys().contains_key("value_3"));
+}
+
+#[ignore]
+#[test]
+fn should_fail_deploy_exceeding_effects_limit() {
+    let builder = run_large_effects(16, 8 * 1_024);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::EffectsLimit(MAX_EFFECTS_SIZE))
+    );
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key("value_0"));
+}
+
+#[ignore]
+#[test]
+fn should_change_effects_limit_on_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .with_new_max_effects_size(MAX_EFFECTS_SIZE as u64)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let exec_request = large_effects_request(16, 8 * 1_024)
+        .with_protocol_version(new_protocol_version)
+        .build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::EffectsLimit(MAX_EFFECTS_SIZE))
+    );
+}
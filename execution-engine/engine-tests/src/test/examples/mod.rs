@@ -0,0 +1 @@
+mod erc20;
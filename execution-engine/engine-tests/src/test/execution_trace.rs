@@ -0,0 +1,115 @@
+use assert_matches::assert_matches;
+
+use engine_core::{execution, resolvers::v1_function_index::FunctionIndex};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_TYPED_ENTRY_POINT: &str = "typed_entry_point.wasm";
+const CONTRACT_TYPED_ENTRY_POINT_CALL: &str = "typed_entry_point_call.wasm";
+const ARG_VALUE: &str = "value";
+const ARG_USE_WRONG_TYPE: &str = "use_wrong_type";
+const VALUE: u64 = 42;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TYPED_ENTRY_POINT,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_set_value(
+    builder: &mut InMemoryWasmTestBuilder,
+    use_wrong_type: bool,
+    enable_execution_trace: bool,
+) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TYPED_ENTRY_POINT_CALL,
+        runtime_args! { ARG_VALUE => VALUE, ARG_USE_WRONG_TYPE => use_wrong_type },
+    )
+    .build();
+    if enable_execution_trace {
+        builder.exec_with_trace(exec_request);
+    } else {
+        builder.exec(exec_request);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_trace_by_default() {
+    let mut builder = setup();
+    call_set_value(&mut builder, false, false);
+    builder.expect_success();
+
+    assert!(builder.last_exec_trace().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_trace_host_calls_of_called_contract() {
+    let mut builder = setup();
+    call_set_value(&mut builder, false, true);
+    builder.expect_success();
+
+    let trace = builder.last_exec_trace();
+    let call_position = trace
+        .iter()
+        .position(|host_call| host_call.function() == FunctionIndex::CallContractFuncIndex)
+        .expect("should have traced call_contract");
+    let call = &trace[call_position];
+    assert_eq!(call.call_depth(), 0);
+    assert!(call.error().is_none());
+
+    // Host calls of the called contract directly follow the call which made them.
+    let nested_calls: Vec<_> = trace[call_position + 1..]
+        .iter()
+        .take_while(|host_call| host_call.call_depth() == 1)
+        .collect();
+    assert!(nested_calls
+        .iter()
+        .any(|host_call| host_call.function() == FunctionIndex::WriteFuncIndex));
+    assert_eq!(
+        trace
+            .iter()
+            .filter(|host_call| host_call.call_depth() != 0)
+            .count(),
+        nested_calls.len()
+    );
+
+    // The gas charged for a call includes that of the host calls made by the called contract.
+    let nested_gas = nested_calls
+        .iter()
+        .fold(Gas::default(), |total, host_call| total + host_call.gas());
+    assert!(call.gas() > nested_gas);
+}
+
+#[ignore]
+#[test]
+fn should_trace_error_of_failing_host_call() {
+    let mut builder = setup();
+    call_set_value(&mut builder, true, true);
+    assert!(builder.is_error());
+
+    let last_call = builder
+        .last_exec_trace()
+        .last()
+        .expect("should have traced host calls");
+    assert_eq!(last_call.function(), FunctionIndex::CallContractFuncIndex);
+    assert_eq!(last_call.call_depth(), 0);
+    assert_matches!(last_call.error(), Some(execution::Error::TypeMismatch(_)));
+}
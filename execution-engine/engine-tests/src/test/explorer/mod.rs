@@ -1,2 +1,3 @@
 mod faucet;
 mod faucet_stored;
+mod stream_execution_effects;
@@ -0,0 +1,108 @@
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_grpc_server::engine_server::{ipc, ipc_grpc::ExecutionEngineService};
+use engine_shared::{additive_map::AdditiveMap, transform::Transform};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, RuntimeArgs};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const ARG_AMOUNT: &str = "amount";
+
+fn do_nothing_request(deploy_hashes: &[[u8; 32]]) -> ExecuteRequest {
+    deploy_hashes
+        .iter()
+        .fold(
+            ExecuteRequestBuilder::new(),
+            |request_builder, deploy_hash| {
+                let deploy = DeployItemBuilder::new()
+                    .with_address(DEFAULT_ACCOUNT_ADDR)
+                    .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::default())
+                    .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+                    .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+                    .with_deploy_hash(*deploy_hash)
+                    .build();
+                request_builder.push_deploy(deploy)
+            },
+        )
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_stream_effects_of_each_deploy() {
+    let deploy_hashes = [[1; 32], [2; 32]];
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let mut ipc_request: ipc::ExecuteRequest = do_nothing_request(&deploy_hashes).into();
+    ipc_request.set_parent_state_hash(builder.get_post_state_hash());
+
+    let responses = builder
+        .get_engine_state()
+        .stream_execution_effects(RequestOptions::new(), ipc_request)
+        .collect()
+        .wait_drop_metadata()
+        .expect("should stream effects");
+    assert_eq!(responses.len(), deploy_hashes.len());
+
+    // executing the same deploys directly should produce the same transforms
+    let exec_request = do_nothing_request(&deploy_hashes);
+    builder.exec(exec_request).expect_success();
+    let expected_transforms: Vec<AdditiveMap<Key, Transform>> = builder
+        .get_exec_response(0)
+        .expect("should have exec response")
+        .iter()
+        .map(|result| result.effect().transforms.clone())
+        .collect();
+
+    for (index, mut response) in responses.into_iter().enumerate() {
+        assert!(response.has_deploy_effects());
+        let mut deploy_effects = response.take_deploy_effects();
+        assert_eq!(deploy_effects.get_deploy_index() as usize, index);
+        assert_eq!(deploy_effects.get_deploy_hash(), &deploy_hashes[index][..]);
+
+        let transforms: AdditiveMap<Key, Transform> = deploy_effects
+            .take_transform_map()
+            .into_iter()
+            .map(|entry| <(Key, Transform)>::try_from(entry).expect("should parse entry"))
+            .collect();
+        assert!(!transforms.is_empty());
+        assert_eq!(transforms, expected_transforms[index]);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_stream_missing_parent() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_parent = vec![255; 32];
+    let mut ipc_request: ipc::ExecuteRequest = do_nothing_request(&[[1; 32]]).into();
+    ipc_request.set_parent_state_hash(missing_parent.clone());
+
+    let mut responses = builder
+        .get_engine_state()
+        .stream_execution_effects(RequestOptions::new(), ipc_request)
+        .collect()
+        .wait_drop_metadata()
+        .expect("should stream response");
+    assert_eq!(responses.len(), 1);
+
+    let response = responses.pop().unwrap();
+    assert!(response.has_missing_parent());
+    assert_eq!(
+        response.get_missing_parent().get_hash(),
+        &missing_parent[..]
+    );
+}
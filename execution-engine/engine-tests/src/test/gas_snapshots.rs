@@ -0,0 +1,57 @@
+use engine_test_support::{
+    gas_snapshot,
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_CREATE_PURSE_01: &str = "create_purse_01.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE_NAME: &str = "purse_name";
+
+fn exec_session(session_file: &str, args: RuntimeArgs) -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, session_file, args).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_track_do_nothing_gas() {
+    let builder = exec_session(CONTRACT_DO_NOTHING, RuntimeArgs::default());
+
+    gas_snapshot!("do_nothing", builder.last_exec_gas_cost());
+}
+
+#[ignore]
+#[test]
+fn should_track_create_purse_gas() {
+    let builder = exec_session(
+        CONTRACT_CREATE_PURSE_01,
+        runtime_args! { ARG_PURSE_NAME => "purse" },
+    );
+
+    gas_snapshot!("create_purse", builder.last_exec_gas_cost());
+}
+
+#[ignore]
+#[test]
+fn should_track_transfer_to_account_gas() {
+    let builder = exec_session(
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(1_000_000) },
+    );
+
+    gas_snapshot!("transfer_to_account", builder.last_exec_gas_cost());
+}
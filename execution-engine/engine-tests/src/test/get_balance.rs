@@ -0,0 +1,52 @@
+use engine_test_support::{
+    internal::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::AccountHash, Key, U512};
+
+#[ignore]
+#[test]
+fn should_get_main_purse_balance_with_proofs() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (motes, proofs) = builder
+        .get_balance_with_proof(None, DEFAULT_ACCOUNT_ADDR)
+        .expect("should get balance");
+
+    assert_eq!(motes, U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE));
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    assert_eq!(motes, builder.get_purse_balance(main_purse));
+
+    assert_eq!(proofs.len(), 3);
+    assert_eq!(proofs[0].key(), &Key::Account(DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(proofs[1].key(), &Key::Hash(main_purse.addr()));
+
+    let post_state_hash = builder.get_post_state_hash();
+    for proof in proofs {
+        assert_eq!(
+            proof
+                .compute_state_hash()
+                .expect("should compute state hash")
+                .to_vec(),
+            post_state_hash
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_get_balance_of_missing_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let error = builder
+        .get_balance_with_proof(None, AccountHash::new([42; 32]))
+        .expect_err("should not find account");
+
+    assert!(error.contains("Account not found"), "{}", error);
+}
@@ -0,0 +1,62 @@
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_RUN_GENESIS_REQUEST,
+};
+use types::{account::AccountHash, U512};
+
+const VALIDATOR_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const VALIDATOR_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+const VALIDATOR_1_STAKE: u64 = 50_000;
+const VALIDATOR_2_STAKE: u64 = 70_000;
+
+#[ignore]
+#[test]
+fn should_get_validator_weights() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for (account_hash, stake) in &[
+            (VALIDATOR_1_ADDR, VALIDATOR_1_STAKE),
+            (VALIDATOR_2_ADDR, VALIDATOR_2_STAKE),
+        ] {
+            let account = GenesisAccount::new(
+                *account_hash,
+                Motes::new((*stake).into()) * Motes::new(2.into()),
+                Motes::new((*stake).into()),
+            );
+            tmp.push(account);
+        }
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let validator_weights = builder
+        .get_validator_weights(None)
+        .expect("should get validator weights");
+
+    assert_eq!(validator_weights.len(), 2);
+    assert_eq!(
+        validator_weights.get(&VALIDATOR_1_ADDR),
+        Some(&U512::from(VALIDATOR_1_STAKE))
+    );
+    assert_eq!(
+        validator_weights.get(&VALIDATOR_2_ADDR),
+        Some(&U512::from(VALIDATOR_2_STAKE))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_get_validator_weights_of_missing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let error = builder
+        .get_validator_weights(Some(vec![42; 32]))
+        .expect_err("should not find root");
+
+    assert!(error.contains("Root not found"), "{}", error);
+}
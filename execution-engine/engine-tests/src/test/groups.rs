@@ -10,7 +10,8 @@ use engine_test_support::{
     DEFAULT_ACCOUNT_ADDR,
 };
 use types::{
-    account::AccountHash, contracts::CONTRACT_INITIAL_VERSION, runtime_args, Key, RuntimeArgs, U512,
+    account::AccountHash, contracts::CONTRACT_INITIAL_VERSION, runtime_args, ContractHash, Key,
+    RuntimeArgs, U512,
 };
 
 const CONTRACT_GROUPS: &str = "groups.wasm";
@@ -27,6 +28,8 @@ const RESTRICTED_CONTRACT_CALLER_AS_SESSION: &str = "restricted_contract_caller_
 const UNCALLABLE_SESSION: &str = "uncallable_session";
 const UNCALLABLE_CONTRACT: &str = "uncallable_contract";
 const CALL_RESTRICTED_ENTRY_POINTS: &str = "call_restricted_entry_points";
+const CONTRACT_CALL_RESTRICTED_CONTRACT_BY_HASH: &str = "call_restricted_contract_by_hash.wasm";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
 const ARG_AMOUNT: &str = "amount";
 const ARG_TARGET: &str = "target";
 
@@ -919,3 +922,77 @@ fn should_not_call_uncallable_session_from_deploy() {
     };
     builder.exec(exec_request_3).expect_success().commit();
 }
+
+/// Installs the groups contract, funds account 1 and returns the hash of the installed contract.
+fn setup_contract_call_by_hash(builder: &mut InMemoryWasmTestBuilder) -> ContractHash {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GROUPS,
+        RuntimeArgs::default(),
+    )
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => *TRANSFER_1_AMOUNT },
+    )
+    .build();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder.exec(exec_request_1).expect_success().commit();
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let package_hash = builder
+        .query(
+            None,
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            &[PACKAGE_HASH_KEY],
+        )
+        .expect("should have contract package");
+    package_hash
+        .as_contract_package()
+        .expect("should be contract package")
+        .current_contract_hash()
+        .expect("should have current contract")
+}
+
+#[ignore]
+#[test]
+fn should_call_group_restricted_contract_by_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let contract_hash = setup_contract_call_by_hash(&mut builder);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CALL_RESTRICTED_CONTRACT_BY_HASH,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_call_group_restricted_contract_by_hash_from_wrong_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let contract_hash = setup_contract_call_by_hash(&mut builder);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_CALL_RESTRICTED_CONTRACT_BY_HASH,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash },
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    assert_eq!(response.len(), 1);
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::InvalidContext));
+}
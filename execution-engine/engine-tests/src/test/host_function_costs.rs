@@ -0,0 +1,286 @@
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, EngineConfig};
+use engine_shared::{
+    gas::Gas,
+    host_function_costs::{HostFunctionCost, HostFunctionCosts, StorageCosts},
+    stored_value::StoredValue,
+};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::ToBytes, runtime_args, CLValue, ProtocolVersion, RuntimeArgs, U512};
+
+const CONTRACT_MACRO: &str = "contract_macro.wasm";
+const CONTRACT_CRYPTO_HOST_FUNCTIONS: &str = "crypto_host_functions.wasm";
+const CONTRACT_STORAGE_APPEND: &str = "storage_append.wasm";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_NAME: &str = "name";
+const ARG_VALUE: &str = "value";
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const MESSAGE_LENGTH: usize = 1_000;
+const METHOD_STORE_VALUE: &str = "store_value";
+const STORED_NAME: &str = "stored";
+const EXTRA_FIXED_COST: u32 = 1_000_000;
+const EXTRA_PER_BYTE_COST: u32 = 1_000;
+
+fn engine_config() -> EngineConfig {
+    EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+}
+
+fn run_genesis_request(host_function_costs: HostFunctionCosts) -> RunGenesisRequest {
+    let mut exec_config = DEFAULT_EXEC_CONFIG.clone();
+    exec_config.set_host_function_costs(host_function_costs);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn put_key_request(protocol_version: ProtocolVersion) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MACRO,
+        runtime_args! {
+            ARG_ENTRY_POINT => METHOD_STORE_VALUE,
+            ARG_NAME => STORED_NAME,
+            ARG_VALUE => U512::from(1),
+        },
+    )
+    .with_protocol_version(protocol_version)
+}
+
+/// Runs a deploy which calls `put_key` exactly once, returning its cost.
+fn put_key_deploy_cost(host_function_costs: HostFunctionCosts) -> Gas {
+    let exec_request = put_key_request(*DEFAULT_PROTOCOL_VERSION).build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config());
+    builder
+        .run_genesis(&run_genesis_request(host_function_costs))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder.last_exec_gas_cost()
+}
+
+/// Runs a deploy which stores a list under a new uref and appends two items to it, returning its
+/// cost.
+fn append_deploy_cost(host_function_costs: HostFunctionCosts) -> Gas {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORAGE_APPEND,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config());
+    builder
+        .run_genesis(&run_genesis_request(host_function_costs))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder.last_exec_gas_cost()
+}
+
+/// Runs a deploy which hashes a `MESSAGE_LENGTH`-byte message with `blake2b` exactly once,
+/// returning its cost.
+fn hash_deploy_cost(host_function_costs: HostFunctionCosts) -> Gas {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO_HOST_FUNCTIONS,
+        runtime_args! {
+            ARG_MESSAGE => vec![7u8; MESSAGE_LENGTH],
+            ARG_SIGNATURE => vec![0u8; 64],
+            ARG_PUBLIC_KEY => vec![0u8; 32],
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config());
+    builder
+        .run_genesis(&run_genesis_request(host_function_costs))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder.last_exec_gas_cost()
+}
+
+#[ignore]
+#[test]
+fn should_charge_fixed_host_function_cost() {
+    let default_costs = HostFunctionCosts::default();
+    let host_function_costs = HostFunctionCosts {
+        put_key: HostFunctionCost::new(
+            default_costs.put_key.fixed + EXTRA_FIXED_COST,
+            default_costs.put_key.per_byte,
+        ),
+        ..default_costs
+    };
+
+    let default_cost = put_key_deploy_cost(default_costs);
+    let increased_cost = put_key_deploy_cost(host_function_costs);
+
+    assert_eq!(
+        increased_cost,
+        default_cost + Gas::new(U512::from(EXTRA_FIXED_COST))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_per_byte_host_function_cost() {
+    let default_costs = HostFunctionCosts::default();
+    let host_function_costs = HostFunctionCosts {
+        put_key: HostFunctionCost::new(
+            default_costs.put_key.fixed,
+            default_costs.put_key.per_byte + EXTRA_PER_BYTE_COST,
+        ),
+        ..default_costs
+    };
+
+    let default_cost = put_key_deploy_cost(default_costs);
+    let increased_cost = put_key_deploy_cost(host_function_costs);
+
+    // The name is serialized with a 4-byte length prefix, and the key is a `Key::URef`: a 1-byte
+    // tag, the 32-byte address and a 1-byte access rights.
+    let input_size = 4 + STORED_NAME.len() as u32 + 34;
+    assert_eq!(
+        increased_cost,
+        default_cost + Gas::new(U512::from(EXTRA_PER_BYTE_COST * input_size))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_storage_cost_per_byte_written() {
+    let default_costs = HostFunctionCosts::default();
+    let host_function_costs = HostFunctionCosts {
+        storage: StorageCosts::new(
+            default_costs.storage.gas_per_byte + EXTRA_PER_BYTE_COST,
+            default_costs.storage.refund_percent,
+        ),
+        ..default_costs
+    };
+
+    let default_cost = put_key_deploy_cost(default_costs);
+    let increased_cost = put_key_deploy_cost(host_function_costs);
+
+    // The deploy stores the value under a new uref, so all of its bytes are added to global state.
+    let stored_value = StoredValue::CLValue(CLValue::from_t(U512::from(1)).unwrap());
+    let stored_size = stored_value.serialized_length() as u32;
+    assert_eq!(
+        increased_cost,
+        default_cost + Gas::new(U512::from(EXTRA_PER_BYTE_COST * stored_size))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_storage_cost_per_byte_appended() {
+    let default_costs = HostFunctionCosts::default();
+    let host_function_costs = HostFunctionCosts {
+        storage: StorageCosts::new(
+            default_costs.storage.gas_per_byte + EXTRA_PER_BYTE_COST,
+            default_costs.storage.refund_percent,
+        ),
+        ..default_costs
+    };
+
+    let default_cost = append_deploy_cost(default_costs);
+    let increased_cost = append_deploy_cost(host_function_costs);
+
+    // The initial list is charged in full when stored, and each append only for the bytes of the
+    // item it adds, so the deploy pays for exactly the bytes of the final list.
+    let items = vec![
+        String::from("first"),
+        String::from("second"),
+        String::from("third"),
+    ];
+    let stored_value = StoredValue::CLValue(CLValue::from_t(items).unwrap());
+    let stored_size = stored_value.serialized_length() as u32;
+    assert_eq!(
+        increased_cost,
+        default_cost + Gas::new(U512::from(EXTRA_PER_BYTE_COST * stored_size))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_hashing_per_byte_of_input() {
+    let default_costs = HostFunctionCosts::default();
+    let host_function_costs = HostFunctionCosts {
+        blake2b: HostFunctionCost::new(
+            default_costs.blake2b.fixed,
+            default_costs.blake2b.per_byte + EXTRA_PER_BYTE_COST,
+        ),
+        ..default_costs
+    };
+
+    let default_cost = hash_deploy_cost(default_costs);
+    let increased_cost = hash_deploy_cost(host_function_costs);
+
+    assert_eq!(
+        increased_cost,
+        default_cost + Gas::new(U512::from(EXTRA_PER_BYTE_COST * MESSAGE_LENGTH as u32))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_replace_host_function_costs_on_upgrade() {
+    let default_costs = HostFunctionCosts::default();
+    let host_function_costs = HostFunctionCosts {
+        put_key: HostFunctionCost::new(
+            default_costs.put_key.fixed + EXTRA_FIXED_COST,
+            default_costs.put_key.per_byte,
+        ),
+        ..default_costs
+    };
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    // The first deploy adds the named key to the account, and those after it only replace it, so
+    // the cost of the second one is comparable to that of the one run after the upgrade.
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config());
+    builder
+        .run_genesis(&run_genesis_request(default_costs))
+        .exec(put_key_request(*DEFAULT_PROTOCOL_VERSION).build())
+        .expect_success()
+        .commit()
+        .exec(put_key_request(*DEFAULT_PROTOCOL_VERSION).build())
+        .expect_success()
+        .commit();
+    let default_cost = builder.last_exec_gas_cost();
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .with_new_host_function_costs(host_function_costs)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    builder
+        .exec(put_key_request(new_protocol_version).build())
+        .expect_success()
+        .commit();
+    let increased_cost = builder.last_exec_gas_cost();
+
+    assert_eq!(
+        increased_cost,
+        default_cost + Gas::new(U512::from(EXTRA_FIXED_COST))
+    );
+}
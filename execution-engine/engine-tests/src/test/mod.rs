@@ -1,12 +1,27 @@
+mod call_depth_limit;
 mod check_transfer_success;
 mod contract_api;
 mod contract_context;
+mod contract_macro;
 mod counter;
 mod deploy;
+mod effects_size_limit;
+mod examples;
+mod execution_trace;
 mod explorer;
+mod gas_snapshots;
+mod get_balance;
+mod get_validator_weights;
 mod groups;
+mod host_function_costs;
 mod manage_groups;
+mod non_reentrant;
+mod query_proofs;
 mod regression;
+mod scratch_global_state;
+mod stack_height_limit;
 mod system_contracts;
 mod upgrade;
+mod wasm_validation;
 mod wasmless_transfer;
+mod write_limits;
@@ -0,0 +1,97 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ContractHash, RuntimeArgs};
+
+const CONTRACT_NON_REENTRANT: &str = "non_reentrant.wasm";
+const CONTRACT_KEY: &str = "non_reentrant";
+const METHOD_RECURSE: &str = "recurse";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_DEPTH: &str = "depth";
+const ARG_NON_REENTRANT: &str = "non_reentrant";
+
+fn setup(non_reentrant: bool) -> (InMemoryWasmTestBuilder, ContractHash) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NON_REENTRANT,
+        runtime_args! { ARG_NON_REENTRANT => non_reentrant },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let contract_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(CONTRACT_KEY)
+        .expect("should have contract key")
+        .into_hash()
+        .expect("should be hash");
+
+    let contract = builder
+        .get_contract(contract_hash)
+        .expect("should have contract");
+    assert_eq!(contract.is_non_reentrant(), non_reentrant);
+
+    (builder, contract_hash)
+}
+
+fn recurse(builder: &mut InMemoryWasmTestBuilder, contract_hash: ContractHash, depth: u32) {
+    let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+        DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        METHOD_RECURSE,
+        runtime_args! {
+            ARG_CONTRACT_HASH => contract_hash,
+            ARG_DEPTH => depth,
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_allow_reentrant_calls_by_default() {
+    let (mut builder, contract_hash) = setup(false);
+    recurse(&mut builder, contract_hash, 2);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_allow_non_reentrant_contract_without_reentrant_call() {
+    let (mut builder, contract_hash) = setup(true);
+    recurse(&mut builder, contract_hash, 0);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_reject_reentrant_call_to_non_reentrant_contract() {
+    let (mut builder, contract_hash) = setup(true);
+    recurse(&mut builder, contract_hash, 1);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::ReentrantCall(reentrant_contract_hash))
+            if *reentrant_contract_hash == contract_hash
+    );
+}
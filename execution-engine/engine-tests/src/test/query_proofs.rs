@@ -0,0 +1,103 @@
+use engine_storage::trie::merkle_proof::TrieMerkleProof;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, Key, RuntimeArgs};
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const COUNTER_VALUE_UREF: &str = "counter";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+
+#[ignore]
+#[test]
+fn should_return_proof_of_queried_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let (value, proofs) = builder
+        .query_with_proof(None, account_key, &[])
+        .expect("should query account");
+
+    assert_eq!(proofs.len(), 1);
+    let proof = &proofs[0];
+    assert_eq!(proof.key(), &account_key);
+    assert_eq!(proof.value(), &value);
+    assert_eq!(
+        proof
+            .compute_state_hash()
+            .expect("should compute state hash")
+            .to_vec(),
+        builder.get_post_state_hash()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_proof_of_every_key_on_query_path() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let (value, proofs) = builder
+        .query_with_proof(
+            None,
+            account_key,
+            &[COUNTER_CONTRACT_HASH_KEY_NAME, COUNTER_VALUE_UREF],
+        )
+        .expect("should query counter value");
+
+    assert_eq!(proofs.len(), 3);
+    assert_eq!(proofs[0].key(), &account_key);
+    assert_eq!(proofs[2].value(), &value);
+
+    let post_state_hash = builder.get_post_state_hash();
+    for proof in proofs {
+        assert_eq!(
+            proof
+                .compute_state_hash()
+                .expect("should compute state hash")
+                .to_vec(),
+            post_state_hash
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_tampered_proof() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (_value, proofs) = builder
+        .query_with_proof(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[])
+        .expect("should query account");
+    let genuine_proof = &proofs[0];
+
+    let tampered_proof = TrieMerkleProof::new(
+        Key::Account(AccountHash::new([42; 32])),
+        genuine_proof.value().clone(),
+        genuine_proof.proof_steps().to_vec(),
+    );
+
+    assert_ne!(
+        tampered_proof
+            .compute_state_hash()
+            .expect("should compute state hash")
+            .to_vec(),
+        builder.get_post_state_hash()
+    );
+}
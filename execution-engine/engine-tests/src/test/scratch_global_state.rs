@@ -0,0 +1,83 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ScratchWasmTestBuilder,
+        DEFAULT_ACCOUNT_KEY, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const TRANSFER_PURSE_TO_ACCOUNT_WASM: &str = "transfer_purse_to_account.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+fn transfer_request(deploy_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            TRANSFER_PURSE_TO_ACCOUNT_WASM,
+            runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(100_000_000) },
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+#[ignore]
+#[test]
+fn should_query_unsealed_state() {
+    let mut builder = ScratchWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(transfer_request([1; 32]))
+        .expect_success()
+        .commit();
+
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_seal_to_same_state_root_as_in_memory_global_state() {
+    let mut in_memory_builder = InMemoryWasmTestBuilder::default();
+    in_memory_builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(transfer_request([1; 32]))
+        .expect_success()
+        .commit()
+        .exec(transfer_request([2; 32]))
+        .expect_success()
+        .commit();
+
+    let mut scratch_builder = ScratchWasmTestBuilder::default();
+    scratch_builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(transfer_request([1; 32]))
+        .expect_success()
+        .commit()
+        .exec(transfer_request([2; 32]))
+        .expect_success()
+        .commit();
+
+    let unsealed_hash = scratch_builder.get_post_state_hash();
+    assert_ne!(unsealed_hash, in_memory_builder.get_post_state_hash());
+
+    scratch_builder.seal();
+    assert_eq!(
+        scratch_builder.get_post_state_hash(),
+        in_memory_builder.get_post_state_hash()
+    );
+
+    // Sealing an already sealed state leaves it as it is.
+    scratch_builder.seal();
+    assert_eq!(
+        scratch_builder.get_post_state_hash(),
+        in_memory_builder.get_post_state_hash()
+    );
+}
@@ -0,0 +1,88 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+// Each frame of `$recurse` costs at least its 16 locals of stack height, so the default limit is
+// reached well before the interpreter's own call stack runs out.
+const SESSION_WAT_WITH_UNBOUNDED_RECURSION: &str = r#"
+(module
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $recurse (type 0)
+      (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+      call $recurse)
+    (func $call (type 0)
+      call $recurse)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_UNREACHABLE: &str = r#"
+(module
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      unreachable)
+    (export "call" (func $call)))
+"#;
+
+fn exec_session_wat(session_wat: &str) -> InMemoryWasmTestBuilder {
+    let session_bytes = wabt::wat2wasm(session_wat).expect("should parse");
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(session_bytes, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .build();
+
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_fail_with_stack_overflow_beyond_stack_height_limit() {
+    let builder = exec_session_wat(SESSION_WAT_WITH_UNBOUNDED_RECURSION);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::StackOverflow));
+    assert!(
+        execution_result.cost() > Gas::default(),
+        "should charge gas"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_report_unreachable_as_stack_overflow() {
+    let builder = exec_session_wat(SESSION_WAT_WITH_UNREACHABLE);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::Interpreter(_)));
+}
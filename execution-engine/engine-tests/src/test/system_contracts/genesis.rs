@@ -1,12 +1,13 @@
 use engine_core::engine_state::{
-    genesis::{ExecConfig, GenesisAccount},
+    genesis::{ExecConfig, GenesisAccount, POS_INFLATION_RATE},
     run_genesis_request::RunGenesisRequest,
     SYSTEM_ACCOUNT_ADDR,
 };
 use engine_shared::{motes::Motes, stored_value::StoredValue};
 use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
-    POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+    utils, InMemoryWasmTestBuilder, DEFAULT_GAS_PRICE, DEFAULT_INFLATION_RATE, DEFAULT_SLASH_RATE,
+    DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
 use types::{account::AccountHash, ProtocolVersion, U512};
 
@@ -60,6 +61,10 @@ fn should_run_genesis() {
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
+        DEFAULT_GAS_PRICE,
+        DEFAULT_INFLATION_RATE,
+        DEFAULT_SLASH_RATE,
+        DEFAULT_UNBONDING_DELAY,
     );
     let run_genesis_request =
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config);
@@ -103,6 +108,16 @@ fn should_run_genesis() {
     } else {
         panic!("contract not found at pos hash");
     }
+
+    let inflation_rate: u64 = builder
+        .query(None, pos_contract_hash.into(), &[POS_INFLATION_RATE])
+        .expect("should have inflation rate")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(inflation_rate, DEFAULT_INFLATION_RATE);
 }
 
 #[cfg(feature = "use-system-contracts")]
@@ -145,6 +160,10 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            DEFAULT_GAS_PRICE,
+            DEFAULT_INFLATION_RATE,
+            DEFAULT_SLASH_RATE,
+            DEFAULT_UNBONDING_DELAY,
         );
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
     };
@@ -193,6 +212,10 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            DEFAULT_GAS_PRICE,
+            DEFAULT_INFLATION_RATE,
+            DEFAULT_SLASH_RATE,
+            DEFAULT_UNBONDING_DELAY,
         );
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
     };
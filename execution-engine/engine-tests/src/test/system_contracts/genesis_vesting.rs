@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use engine_core::engine_state::genesis::{GenesisAccount, VestingSchedule};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::AccountHash, runtime_args, BlockTime, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+const VESTING_ACCOUNT_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const LOCKED_AMOUNT: u64 = DEFAULT_ACCOUNT_INITIAL_BALANCE / 2;
+const CLIFF_MILLIS: u64 = 1_000;
+const DURATION_MILLIS: u64 = 10_000;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let vesting_schedule = VestingSchedule::new(
+        BlockTime::from_millis(0),
+        Duration::from_millis(CLIFF_MILLIS),
+        Duration::from_millis(DURATION_MILLIS),
+        U512::from(LOCKED_AMOUNT),
+    );
+    let vesting_account = GenesisAccount::new(
+        VESTING_ACCOUNT_ADDR,
+        Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+        Motes::new(U512::zero()),
+    )
+    .with_vesting_schedule(vesting_schedule);
+
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    accounts.push(vesting_account);
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn transfer_at(builder: &mut InMemoryWasmTestBuilder, block_time: u64, amount: U512) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        VESTING_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => DEFAULT_ACCOUNT_ADDR, ARG_AMOUNT => amount },
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn last_exec_succeeded(builder: &InMemoryWasmTestBuilder) -> bool {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    response[0].is_success()
+}
+
+#[ignore]
+#[test]
+fn should_lock_whole_amount_before_cliff() {
+    let mut builder = setup();
+
+    // Would leave less than the locked amount in the main purse
+    let amount = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE - LOCKED_AMOUNT + 1);
+    transfer_at(&mut builder, CLIFF_MILLIS - 1, amount);
+    assert!(!last_exec_succeeded(&builder));
+
+    // Unlocked funds can still be moved
+    let amount = U512::from(LOCKED_AMOUNT / 2);
+    transfer_at(&mut builder, CLIFF_MILLIS - 1, amount);
+    assert!(last_exec_succeeded(&builder));
+}
+
+#[ignore]
+#[test]
+fn should_release_locked_amount_linearly_after_cliff() {
+    let mut builder = setup();
+
+    // Half of the locked amount is released halfway through the schedule
+    let halfway = DURATION_MILLIS / 2;
+    let amount = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE - LOCKED_AMOUNT + LOCKED_AMOUNT / 4);
+    transfer_at(&mut builder, halfway, amount);
+    assert!(last_exec_succeeded(&builder));
+
+    // The other half is still locked
+    let amount = U512::from(LOCKED_AMOUNT / 2);
+    transfer_at(&mut builder, halfway, amount);
+    assert!(!last_exec_succeeded(&builder));
+}
+
+#[ignore]
+#[test]
+fn should_release_whole_amount_after_duration() {
+    // Leaves a little less in the main purse than is still locked just before the end
+    let amount = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - *DEFAULT_PAYMENT - U512::one();
+
+    let mut builder = setup();
+    transfer_at(&mut builder, DURATION_MILLIS - 1, amount);
+    assert!(!last_exec_succeeded(&builder));
+
+    let mut builder = setup();
+    transfer_at(&mut builder, DURATION_MILLIS, amount);
+    assert!(last_exec_succeeded(&builder));
+}
@@ -0,0 +1,169 @@
+use std::convert::TryFrom;
+
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{Error, CONV_RATE},
+    execution,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    runtime_args, system_contract_errors::mint, ApiError, CLValue, Key, RuntimeArgs, U512,
+};
+
+const CONTRACT_MINT_BURN: &str = "mint_burn.wasm";
+const CONTRACT_MINT_READ_TOTAL_SUPPLY: &str = "mint_read_total_supply.wasm";
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+const ARG_AMOUNT: &str = "amount";
+const ARG_ADD_ONLY: &str = "add_only";
+
+fn query_total_supply(builder: &InMemoryWasmTestBuilder, total_supply_key: Key) -> U512 {
+    let total_supply: CLValue = builder
+        .query(None, total_supply_key, &[])
+        .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
+        .expect("should have total supply");
+    total_supply.into_t().expect("should be U512")
+}
+
+fn get_total_supply(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let mint_contract = builder
+        .get_contract(builder.get_mint_contract_hash())
+        .expect("should have mint contract");
+    let total_supply_key: Key = *mint_contract
+        .named_keys()
+        .get(TOTAL_SUPPLY_KEY)
+        .expect("should have total supply key");
+    query_total_supply(builder, total_supply_key)
+}
+
+/// Returns the total supply as read by a contract through the mint's `read_total_supply` entry
+/// point.
+fn read_total_supply(builder: &mut InMemoryWasmTestBuilder) -> U512 {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_READ_TOTAL_SUPPLY,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let total_supply_key: Key = *account
+        .named_keys()
+        .get(TOTAL_SUPPLY_KEY)
+        .expect("should have total supply key");
+    query_total_supply(builder, total_supply_key)
+}
+
+fn burn(builder: &mut InMemoryWasmTestBuilder, amount: U512, add_only: bool) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_BURN,
+        runtime_args! { ARG_AMOUNT => amount, ARG_ADD_ONLY => add_only },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_last_exec_reverted_with(builder: &InMemoryWasmTestBuilder, expected: mint::Error) {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let error = response[0].as_error().expect("should have error");
+    let error = assert_matches!(error, Error::Exec(execution::Error::Revert(e)) => e);
+    assert_eq!(*error, ApiError::from(expected));
+}
+
+#[ignore]
+#[test]
+fn should_track_total_supply_from_genesis() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(get_total_supply(&builder) >= builder.get_purse_balance(account.main_purse()));
+}
+
+#[ignore]
+#[test]
+fn should_burn_from_writeable_purse() {
+    let amount = U512::from(1_000_000);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let balance_before = builder.get_purse_balance(account.main_purse());
+    let total_supply_before = get_total_supply(&builder);
+
+    burn(&mut builder, amount, false);
+    builder.expect_success();
+
+    let motes = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should have motes")
+        .value();
+    assert_eq!(
+        builder.get_purse_balance(account.main_purse()),
+        balance_before - motes - amount
+    );
+    // Paying for the deploy moves motes between purses, so only the burned amount is destroyed
+    assert_eq!(get_total_supply(&builder), total_supply_before - amount);
+}
+
+#[ignore]
+#[test]
+fn should_not_burn_more_than_purse_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let balance = builder.get_purse_balance(account.main_purse());
+    let total_supply_before = get_total_supply(&builder);
+
+    burn(&mut builder, balance + U512::one(), false);
+    assert_last_exec_reverted_with(&builder, mint::Error::InsufficientFunds);
+    assert_eq!(get_total_supply(&builder), total_supply_before);
+}
+
+#[ignore]
+#[test]
+fn should_not_burn_without_write_access() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let total_supply_before = get_total_supply(&builder);
+
+    burn(&mut builder, U512::one(), true);
+    assert_last_exec_reverted_with(&builder, mint::Error::InvalidAccessRights);
+    assert_eq!(get_total_supply(&builder), total_supply_before);
+}
+
+#[ignore]
+#[test]
+fn should_read_total_supply() {
+    let amount = U512::from(1_000_000);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let total_supply = read_total_supply(&mut builder);
+    assert_eq!(total_supply, get_total_supply(&builder));
+
+    burn(&mut builder, amount, false);
+    builder.expect_success();
+
+    assert_eq!(read_total_supply(&mut builder), total_supply - amount);
+}
@@ -0,0 +1,94 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, system_contract_errors::mint, ApiError, Key, RuntimeArgs, U512};
+
+const CONTRACT_MINT_CUSTOM_TOKEN: &str = "mint_custom_token.wasm";
+const ARG_TOKEN_ID: &str = "token_id";
+const ARG_INITIAL_SUPPLY: &str = "initial_supply";
+const ARG_AMOUNT: &str = "amount";
+const SOURCE_BALANCE_KEY: &str = "source_balance";
+const TARGET_BALANCE_KEY: &str = "target_balance";
+const NATIVE_BALANCE_KEY: &str = "native_balance";
+const TOTAL_SUPPLY_KEY: &str = "token_total_supply";
+const TOKEN_ID: &str = "stablecoin";
+
+fn create_token(builder: &mut InMemoryWasmTestBuilder, initial_supply: U512, amount: U512) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_CUSTOM_TOKEN,
+        runtime_args! {
+            ARG_TOKEN_ID => TOKEN_ID.to_string(),
+            ARG_INITIAL_SUPPLY => initial_supply,
+            ARG_AMOUNT => amount,
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_last_exec_reverted_with(builder: &InMemoryWasmTestBuilder, expected: mint::Error) {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let error = response[0].as_error().expect("should have error");
+    let error = assert_matches!(error, Error::Exec(execution::Error::Revert(e)) => e);
+    assert_eq!(*error, ApiError::from(expected));
+}
+
+#[ignore]
+#[test]
+fn should_create_and_transfer_custom_token() {
+    let initial_supply = U512::from(1_000);
+    let amount = U512::from(300);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    create_token(&mut builder, initial_supply, amount);
+    builder.expect_success();
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let source_balance: Option<U512> = builder.query_expect(account_key, &[SOURCE_BALANCE_KEY]);
+    let target_balance: Option<U512> = builder.query_expect(account_key, &[TARGET_BALANCE_KEY]);
+    let total_supply: U512 = builder.query_expect(account_key, &[TOTAL_SUPPLY_KEY]);
+    assert_eq!(source_balance, Some(initial_supply - amount));
+    assert_eq!(target_balance, Some(amount));
+    assert_eq!(total_supply, initial_supply);
+
+    // Token purses aren't usable as purses of the native token
+    let native_balance: Option<U512> = builder.query_expect(account_key, &[NATIVE_BALANCE_KEY]);
+    assert_eq!(native_balance, None);
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_more_than_token_balance() {
+    let initial_supply = U512::from(1_000);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    create_token(&mut builder, initial_supply, initial_supply + U512::one());
+    assert_last_exec_reverted_with(&builder, mint::Error::InsufficientFunds);
+}
+
+#[ignore]
+#[test]
+fn should_not_create_token_twice() {
+    let initial_supply = U512::from(1_000);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    create_token(&mut builder, initial_supply, U512::zero());
+    builder.expect_success();
+
+    create_token(&mut builder, initial_supply, U512::zero());
+    assert_last_exec_reverted_with(&builder, mint::Error::TokenAlreadyExists);
+}
@@ -1,7 +1,13 @@
 mod genesis;
+mod genesis_vesting;
+mod mint_burn;
+mod mint_custom_token;
 mod mint_install;
 mod pos_install;
 mod proof_of_stake;
+mod scheduler;
 mod standard_payment;
 mod standard_payment_install;
+mod system_contract_registry;
 mod upgrade;
+mod upgrade_migrations;
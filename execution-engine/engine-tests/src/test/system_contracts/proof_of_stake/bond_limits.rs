@@ -0,0 +1,133 @@
+use engine_core::engine_state::{genesis::GenesisAccount, run_genesis_request::RunGenesisRequest};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, runtime_args, system_contract_errors::pos, ApiError, BlockTime,
+    RuntimeArgs, U512,
+};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const MIN_BOND: u64 = 10_000;
+const MAX_BOND: u64 = 100_000;
+const MAX_VALIDATOR_SLOTS: u32 = 1;
+
+const TEST_BOND: &str = "bond";
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+/// Runs genesis with a single bonded validator, bond limits and a single validator slot.
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_ADDR,
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let mut exec_config = utils::create_exec_config(accounts);
+    exec_config.set_min_bond(MIN_BOND);
+    exec_config.set_max_bond(MAX_BOND);
+    exec_config.set_max_validator_slots(MAX_VALIDATOR_SLOTS);
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn bond(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_BOND),
+            ARG_AMOUNT => U512::from(amount),
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_last_bond_failed_with(builder: &InMemoryWasmTestBuilder, expected: pos::Error) {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response")
+        .clone();
+    let error_message = utils::get_error_message(response);
+    let expected = if cfg!(feature = "enable-bonding") {
+        ApiError::from(expected)
+    } else {
+        ApiError::Unhandled
+    };
+    assert!(
+        error_message.contains(&format!("{:?}", expected)),
+        "error is {:?}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_bonds_outside_of_limits() {
+    let mut builder = setup();
+
+    bond(&mut builder, MIN_BOND - 1);
+    assert_last_bond_failed_with(&builder, pos::Error::BondTooSmall);
+
+    bond(&mut builder, MAX_BOND + 1);
+    assert_last_bond_failed_with(&builder, pos::Error::BondTooLarge);
+}
+
+#[ignore]
+#[test]
+fn should_only_take_validator_slot_by_outbidding_lowest_stake() {
+    let mut builder = setup();
+
+    bond(&mut builder, GENESIS_VALIDATOR_STAKE);
+    assert_last_bond_failed_with(&builder, pos::Error::NoValidatorSlotAvailable);
+
+    bond(&mut builder, GENESIS_VALIDATOR_STAKE + 1);
+    if !cfg!(feature = "enable-bonding") {
+        assert_last_bond_failed_with(&builder, pos::Error::NoValidatorSlotAvailable);
+        return;
+    }
+    builder.expect_success();
+
+    // The outbid validator is evicted and its stake paid out at the next era boundary.
+    let validator_weights = builder
+        .get_validator_weights(None)
+        .expect("should get validator weights");
+    assert_eq!(validator_weights.len(), 1);
+    assert_eq!(
+        validator_weights.get(&DEFAULT_ACCOUNT_ADDR),
+        Some(&U512::from(GENESIS_VALIDATOR_STAKE + 1))
+    );
+
+    let validator_purse = builder
+        .get_account(VALIDATOR_ADDR)
+        .expect("should get validator account")
+        .main_purse();
+    let balance_before = builder.get_purse_balance(validator_purse);
+    builder.step(*DEFAULT_PROTOCOL_VERSION, BlockTime::new(0));
+    assert_eq!(
+        builder.get_purse_balance(validator_purse),
+        balance_before + GENESIS_VALIDATOR_STAKE
+    );
+}
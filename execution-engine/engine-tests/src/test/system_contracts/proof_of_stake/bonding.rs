@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use engine_core::engine_state::{
     genesis::{GenesisAccount, POS_BONDING_PURSE},
     CONV_RATE,
@@ -48,6 +50,12 @@ fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
     builder.get_purse_balance(purse)
 }
 
+fn get_validator_weights(builder: &InMemoryWasmTestBuilder) -> BTreeMap<AccountHash, U512> {
+    builder
+        .get_validator_weights(None)
+        .expect("should get validator weights")
+}
+
 #[ignore]
 #[test]
 fn should_run_successful_bond_and_unbond() {
@@ -71,8 +79,6 @@ fn should_run_successful_bond_and_unbond() {
         .get_account(DEFAULT_ACCOUNT_ADDR)
         .expect("should get account 1");
 
-    let pos = builder.get_pos_contract_hash();
-
     let exec_request_1 = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_POS_BONDING,
@@ -97,14 +103,10 @@ fn should_run_successful_bond_and_unbond() {
         .expect("should have exec response");
     let mut genesis_gas_cost = utils::get_exec_costs(exec_response)[0];
 
-    let contract = builder.get_contract(pos).expect("should have contract");
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes()),
-        GENESIS_ACCOUNT_STAKE
+    assert_eq!(
+        get_validator_weights(&builder).get(&DEFAULT_ACCOUNT_ADDR),
+        Some(&U512::from(GENESIS_ACCOUNT_STAKE))
     );
-    assert!(contract.named_keys().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
     // the test contract
@@ -154,17 +156,11 @@ fn should_run_successful_bond_and_unbond() {
         .get_account(ACCOUNT_1_ADDR)
         .expect("should get account 1");
 
-    let pos = builder.get_pos_contract_hash();
-
-    // Verify that genesis account is in validator queue
-    let contract = builder.get_contract(pos).expect("should have contract");
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_STAKE
+    // Verify that account 1 is among the validators
+    assert_eq!(
+        get_validator_weights(&builder).get(&ACCOUNT_1_ADDR),
+        Some(&U512::from(ACCOUNT_1_STAKE))
     );
-    assert!(contract.named_keys().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
     // the test contract
@@ -213,23 +209,11 @@ fn should_run_successful_bond_and_unbond() {
         U512::from(GENESIS_VALIDATOR_STAKE + GENESIS_ACCOUNT_STAKE + ACCOUNT_1_UNBOND_2)
     );
 
-    let pos_contract = builder.get_pos_contract();
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_STAKE
-    );
-    assert!(!pos_contract.named_keys().contains_key(&lookup_key));
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_UNBOND_2
+    // Account 1 is still a validator, with the remaining stake
+    assert_eq!(
+        get_validator_weights(&builder).get(&ACCOUNT_1_ADDR),
+        Some(&U512::from(ACCOUNT_1_UNBOND_2))
     );
-    // Account 1 is still tracked anymore in the bonding queue with different uref
-    // name
-    assert!(pos_contract.named_keys().contains_key(&lookup_key));
 
     //
     // Stage 2b - Genesis unbonds by decreasing less than 50% (and is still in the
@@ -317,15 +301,8 @@ fn should_run_successful_bond_and_unbond() {
         U512::from(GENESIS_VALIDATOR_STAKE + GENESIS_ACCOUNT_UNBOND_2)
     );
 
-    let pos_contract = builder.get_pos_contract();
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_UNBOND_2
-    );
-    // Account 1 isn't tracked anymore in the bonding queue
-    assert!(!pos_contract.named_keys().contains_key(&lookup_key));
+    // Account 1 isn't a validator anymore
+    assert!(!get_validator_weights(&builder).contains_key(&ACCOUNT_1_ADDR));
 
     //
     // Stage 3b - Fully unbond account1 with Some(TOTAL_AMOUNT)
@@ -375,53 +352,16 @@ fn should_run_successful_bond_and_unbond() {
         U512::from(GENESIS_VALIDATOR_STAKE)
     );
 
-    let pos_contract = builder.get_pos_contract();
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes()),
-        GENESIS_ACCOUNT_UNBOND_2
-    );
-    // Genesis is still tracked anymore in the bonding queue with different uref
-    // name
-    assert!(!pos_contract.named_keys().contains_key(&lookup_key));
-
     //
-    // Final checks on validator queue
+    // Final checks on validator set
     //
 
-    // Account 1 is still tracked anymore in the bonding queue with any amount
-    // suffix
-    assert_eq!(
-        pos_contract
-            .named_keys()
-            .iter()
-            .filter(|(key, _)| key.starts_with(&format!(
-                "v_{}",
-                base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes())
-            )))
-            .count(),
-        0
-    );
-    assert_eq!(
-        pos_contract
-            .named_keys()
-            .iter()
-            .filter(|(key, _)| key.starts_with(&format!(
-                "v_{}",
-                base16::encode_lower(ACCOUNT_1_ADDR.as_bytes())
-            )))
-            .count(),
-        0
-    );
-    // only genesis validator is still in the queue
-    assert_eq!(
-        pos_contract
-            .named_keys()
-            .iter()
-            .filter(|(key, _)| key.starts_with("v_"))
-            .count(),
-        1
-    );
+    // Neither the genesis account nor account 1 are validators anymore; only the genesis
+    // validator is still bonded
+    let validator_weights = get_validator_weights(&builder);
+    assert!(!validator_weights.contains_key(&DEFAULT_ACCOUNT_ADDR));
+    assert!(!validator_weights.contains_key(&ACCOUNT_1_ADDR));
+    assert_eq!(validator_weights.len(), 1);
 }
 
 #[ignore]
@@ -0,0 +1,117 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::{
+    bonding_event::BondingEvent, deploy_item::DeployItem, genesis::GenesisAccount,
+};
+use engine_shared::{motes::Motes, newtypes::CorrelationId};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_ACCOUNTS, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const BOND_AMOUNT: u64 = 10_000;
+const UNBOND_AMOUNT: u64 = 20_000;
+
+const TEST_BOND: &str = "bond";
+const TEST_UNBOND: &str = "unbond";
+
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+fn pos_bonding_deploy_item(
+    account_hash: AccountHash,
+    deploy_hash: [u8; 32],
+    session_args: RuntimeArgs,
+) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(account_hash)
+        .with_session_code(CONTRACT_POS_BONDING, session_args)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[account_hash])
+        .with_deploy_hash(deploy_hash)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_report_bonding_events_of_each_deploy() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_ADDR,
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let bond_deploy = pos_bonding_deploy_item(
+        DEFAULT_ACCOUNT_ADDR,
+        [1; 32],
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_BOND),
+            ARG_AMOUNT => U512::from(BOND_AMOUNT),
+        },
+    );
+    let unbond_deploy = pos_bonding_deploy_item(
+        VALIDATOR_ADDR,
+        [2; 32],
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_UNBOND),
+            ARG_AMOUNT => Some(U512::from(UNBOND_AMOUNT)),
+        },
+    );
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(bond_deploy)
+        .push_deploy(unbond_deploy)
+        .with_pre_state_hash(&pre_state_hash)
+        .build();
+
+    let engine_state = builder.get_engine_state();
+    let correlation_id = CorrelationId::new();
+    let execution_results = engine_state
+        .run_execute(correlation_id, exec_request)
+        .expect("should execute");
+    let bonding_events = engine_state
+        .get_bonding_events(
+            correlation_id,
+            pre_state_hash
+                .as_slice()
+                .try_into()
+                .expect("should convert to Blake2bHash"),
+            *DEFAULT_PROTOCOL_VERSION,
+            &execution_results,
+        )
+        .expect("should get bonding events");
+
+    if !cfg!(feature = "enable-bonding") {
+        assert_eq!(bonding_events, vec![vec![], vec![]]);
+        return;
+    }
+
+    assert_eq!(
+        bonding_events,
+        vec![
+            vec![BondingEvent::Bond {
+                validator: DEFAULT_ACCOUNT_ADDR,
+                amount: U512::from(BOND_AMOUNT),
+            }],
+            vec![BondingEvent::Unbond {
+                validator: VALIDATOR_ADDR,
+                amount: U512::from(UNBOND_AMOUNT),
+            }],
+        ]
+    );
+}
@@ -0,0 +1,158 @@
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, POS_BONDING_PURSE},
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const DELEGATION_AMOUNT: u64 = 10_000;
+
+const TEST_DELEGATE: &str = "delegate";
+const TEST_UNDELEGATE: &str = "undelegate";
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_VALIDATOR: &str = "validator";
+
+fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS bonding purse");
+    builder.get_purse_balance(purse)
+}
+
+fn get_validator_stake(builder: &InMemoryWasmTestBuilder, validator: AccountHash) -> Option<U512> {
+    builder
+        .get_validator_weights(None)
+        .expect("should get validator weights")
+        .get(&validator)
+        .cloned()
+}
+
+fn delegation_lookup_key(delegator: AccountHash, validator: AccountHash, amount: u64) -> String {
+    format!(
+        "d_{}_{}_{}",
+        base16::encode_lower(delegator.as_bytes()),
+        base16::encode_lower(validator.as_bytes()),
+        amount
+    )
+}
+
+#[ignore]
+#[test]
+fn should_delegate_and_undelegate() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_ADDR,
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let balance_before = builder.get_purse_balance(default_account.main_purse());
+    let bonding_purse_balance_before = get_pos_bonding_purse_balance(&builder);
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_DELEGATE),
+            ARG_VALIDATOR => VALIDATOR_ADDR,
+            ARG_AMOUNT => U512::from(DELEGATION_AMOUNT),
+        },
+    )
+    .build();
+
+    builder.exec(delegate_request);
+    if !cfg!(feature = "enable-bonding") && builder.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let delegate_gas_cost = utils::get_exec_costs(
+        builder
+            .get_exec_response(0)
+            .expect("should have exec response"),
+    )[0];
+
+    // The delegation is tracked per (delegator, validator) and counts towards the validator's
+    // stake.
+    let pos_contract = builder.get_pos_contract();
+    assert!(pos_contract
+        .named_keys()
+        .contains_key(&delegation_lookup_key(
+            DEFAULT_ACCOUNT_ADDR,
+            VALIDATOR_ADDR,
+            DELEGATION_AMOUNT
+        )));
+    assert_eq!(
+        get_validator_stake(&builder, VALIDATOR_ADDR),
+        Some(U512::from(GENESIS_VALIDATOR_STAKE + DELEGATION_AMOUNT))
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        bonding_purse_balance_before + DELEGATION_AMOUNT
+    );
+
+    let undelegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_UNDELEGATE),
+            ARG_VALIDATOR => VALIDATOR_ADDR,
+            ARG_AMOUNT => None as Option<U512>,
+        },
+    )
+    .build();
+
+    builder.exec(undelegate_request).expect_success().commit();
+
+    let undelegate_gas_cost = utils::get_exec_costs(
+        builder
+            .get_exec_response(1)
+            .expect("should have exec response"),
+    )[0];
+
+    let pos_contract = builder.get_pos_contract();
+    assert!(!pos_contract
+        .named_keys()
+        .keys()
+        .any(|name| name.starts_with("d_")));
+    assert_eq!(
+        get_validator_stake(&builder, VALIDATOR_ADDR),
+        Some(U512::from(GENESIS_VALIDATOR_STAKE))
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        bonding_purse_balance_before
+    );
+
+    // The delegated funds are returned to the delegator's main purse.
+    let total_gas_cost = Motes::from_gas(delegate_gas_cost + undelegate_gas_cost, CONV_RATE)
+        .expect("should convert");
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        balance_before - total_gas_cost.value()
+    );
+}
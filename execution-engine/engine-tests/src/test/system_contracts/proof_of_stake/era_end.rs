@@ -0,0 +1,158 @@
+use engine_core::engine_state::{
+    genesis::{ExecConfig, GenesisAccount},
+    run_genesis_request::RunGenesisRequest,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_GAS_PRICE,
+    DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_INFLATION_RATE, DEFAULT_PROTOCOL_VERSION,
+    DEFAULT_SLASH_RATE, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    STANDARD_PAYMENT_INSTALL_CONTRACT,
+};
+use types::{account::AccountHash, runtime_args, BlockTime, RuntimeArgs, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const UNBOND_AMOUNT: u64 = 10_000;
+const UNBONDING_DELAY: u64 = 1_000;
+const UNBOND_TIME: u64 = 100;
+const INFLATION_RATE_DENOMINATOR: u64 = 1_000_000;
+const ERA_ID: u64 = 1;
+
+const TEST_UNBOND: &str = "unbond";
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+fn expected_reward(stake: u64) -> U512 {
+    U512::from(stake * DEFAULT_INFLATION_RATE / INFLATION_RATE_DENOMINATOR)
+}
+
+fn get_validator_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let validator = builder
+        .get_account(VALIDATOR_ADDR)
+        .expect("should get validator account");
+    builder.get_purse_balance(validator.main_purse())
+}
+
+fn get_validator_weight(builder: &InMemoryWasmTestBuilder) -> Option<U512> {
+    builder
+        .get_bonded_validators()
+        .last()
+        .expect("should have validator weights")
+        .get(&VALIDATOR_ADDR)
+        .cloned()
+}
+
+/// Runs genesis with a bonded validator and a non-zero unbonding delay.
+fn setup_validator() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_ADDR,
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        accounts,
+        *DEFAULT_WASM_COSTS,
+        DEFAULT_GAS_PRICE,
+        DEFAULT_INFLATION_RATE,
+        DEFAULT_SLASH_RATE,
+        UNBONDING_DELAY,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_distribute_rewards_at_era_end() {
+    let mut builder = setup_validator();
+    let validator_balance_before = get_validator_balance(&builder);
+
+    builder.run_era_end(
+        *DEFAULT_PROTOCOL_VERSION,
+        ERA_ID,
+        BlockTime::new(UNBOND_TIME),
+    );
+
+    assert_eq!(
+        get_validator_balance(&builder),
+        validator_balance_before + expected_reward(GENESIS_VALIDATOR_STAKE)
+    );
+    assert_eq!(
+        get_validator_weight(&builder),
+        Some(U512::from(GENESIS_VALIDATOR_STAKE))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_unbonded_stake_at_era_end_after_delay() {
+    let mut builder = setup_validator();
+
+    let unbond_request = ExecuteRequestBuilder::standard(
+        VALIDATOR_ADDR,
+        CONTRACT_POS_BONDING,
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_UNBOND),
+            ARG_AMOUNT => Some(U512::from(UNBOND_AMOUNT)),
+        },
+    )
+    .with_block_time(UNBOND_TIME)
+    .build();
+
+    let result = builder.exec(unbond_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let remaining_stake = GENESIS_VALIDATOR_STAKE - UNBOND_AMOUNT;
+    let validator_balance_before = get_validator_balance(&builder);
+
+    // Before the delay has passed, only the rewards for the remaining stake are paid out.
+    builder.run_era_end(
+        *DEFAULT_PROTOCOL_VERSION,
+        ERA_ID,
+        BlockTime::new(UNBOND_TIME + UNBONDING_DELAY - 1),
+    );
+    let validator_balance_after_first_era = get_validator_balance(&builder);
+    assert_eq!(
+        validator_balance_after_first_era,
+        validator_balance_before + expected_reward(remaining_stake)
+    );
+    assert_eq!(
+        get_validator_weight(&builder),
+        Some(U512::from(remaining_stake))
+    );
+
+    builder.run_era_end(
+        *DEFAULT_PROTOCOL_VERSION,
+        ERA_ID + 1,
+        BlockTime::new(UNBOND_TIME + UNBONDING_DELAY),
+    );
+    assert_eq!(
+        get_validator_balance(&builder),
+        validator_balance_after_first_era + expected_reward(remaining_stake) + UNBOND_AMOUNT
+    );
+    assert_eq!(
+        get_validator_weight(&builder),
+        Some(U512::from(remaining_stake))
+    );
+}
@@ -1,3 +1,10 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::{
+    genesis::{POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
+    CONV_RATE,
+};
+use engine_shared::{additive_map::AdditiveMap, motes::Motes, transform::Transform};
 use engine_test_support::{
     internal::{
         ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
@@ -5,13 +12,14 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+use types::{account::AccountHash, runtime_args, CLValue, Key, RuntimeArgs, URef, U512};
 
 const CONTRACT_POS_GET_PAYMENT_PURSE: &str = "pos_get_payment_purse.wasm";
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
 const ACCOUNT_1_INITIAL_BALANCE: u64 = 100_000_000 + 100;
 const ARG_AMOUNT: &str = "amount";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
 
 #[ignore]
 #[test]
@@ -57,3 +65,69 @@ fn should_run_get_payment_purse_contract_account_1() {
         .expect_success()
         .commit();
 }
+
+fn get_pos_purse(builder: &InMemoryWasmTestBuilder, name: &str) -> URef {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .get(name)
+        .and_then(|key| key.into_uref())
+        .expect("should have PoS purse")
+}
+
+#[ignore]
+#[test]
+fn should_sweep_leftover_payment_purse_funds_into_rewards_purse() {
+    let leftover_amount = U512::from(1_000_000);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let payment_purse = get_pos_purse(&builder, POS_PAYMENT_PURSE);
+    let rewards_purse = get_pos_purse(&builder, POS_REWARDS_PURSE);
+
+    // Strand funds in the payment purse outside of any deploy
+    let payment_purse_balance_key: Key = builder
+        .query(None, Key::Hash(payment_purse.addr()), &[])
+        .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
+        .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
+        .expect("should find balance key");
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        payment_purse_balance_key,
+        Transform::AddUInt512(leftover_amount),
+    );
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, effects);
+    assert_eq!(builder.get_purse_balance(payment_purse), leftover_amount);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let main_purse_balance_before = builder.get_purse_balance(account.main_purse());
+    let rewards_purse_balance_before = builder.get_purse_balance(rewards_purse);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let motes = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should have motes")
+        .value();
+
+    // The leftover funds are neither refunded to the deploying account nor available to later
+    // deploys
+    assert_eq!(builder.get_purse_balance(payment_purse), U512::zero());
+    assert_eq!(
+        builder.get_purse_balance(account.main_purse()),
+        main_purse_balance_before - motes
+    );
+    assert_eq!(
+        builder.get_purse_balance(rewards_purse),
+        rewards_purse_balance_before + motes + leftover_amount
+    );
+}
@@ -1,5 +1,11 @@
+mod bond_limits;
 mod bonding;
+mod bonding_events;
 mod commit_validators;
+mod delegation;
+mod era_end;
 mod finalize_payment;
 mod get_payment_purse;
 mod refund_purse;
+mod slashing;
+mod unbonding_delay;
@@ -0,0 +1,50 @@
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_PROTOCOL_VERSION, DEFAULT_SLASH_RATE,
+};
+use types::{account::AccountHash, U512};
+
+const VALIDATOR_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const VALIDATOR_2_ADDR: AccountHash = AccountHash::new([43u8; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+
+fn get_validator_stake(builder: &InMemoryWasmTestBuilder, validator: AccountHash) -> Option<U512> {
+    builder
+        .get_validator_weights(None)
+        .expect("should get validator weights")
+        .get(&validator)
+        .cloned()
+}
+
+#[ignore]
+#[test]
+fn should_slash_validator() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for validator in &[VALIDATOR_1_ADDR, VALIDATOR_2_ADDR] {
+            let account = GenesisAccount::new(
+                *validator,
+                Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+                Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+            );
+            tmp.push(account);
+        }
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    builder.slash(*DEFAULT_PROTOCOL_VERSION, vec![VALIDATOR_1_ADDR]);
+
+    let stake = U512::from(GENESIS_VALIDATOR_STAKE);
+    let slashed_stake = stake - stake * DEFAULT_SLASH_RATE / 1_000_000;
+
+    assert_eq!(
+        get_validator_stake(&builder, VALIDATOR_1_ADDR),
+        Some(slashed_stake)
+    );
+    assert_eq!(get_validator_stake(&builder, VALIDATOR_2_ADDR), Some(stake));
+}
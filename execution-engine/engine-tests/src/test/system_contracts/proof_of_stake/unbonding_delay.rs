@@ -0,0 +1,175 @@
+use engine_core::engine_state::{
+    genesis::{ExecConfig, GenesisAccount, POS_BONDING_PURSE},
+    run_genesis_request::RunGenesisRequest,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_GAS_PRICE,
+    DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_INFLATION_RATE, DEFAULT_PROTOCOL_VERSION,
+    DEFAULT_SLASH_RATE, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    STANDARD_PAYMENT_INSTALL_CONTRACT,
+};
+use types::{account::AccountHash, runtime_args, BlockTime, Key, RuntimeArgs, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const UNBOND_AMOUNT: u64 = 10_000;
+const UNBONDING_DELAY: u64 = 1_000;
+const UNBOND_TIME: u64 = 100;
+
+const TEST_UNBOND: &str = "unbond";
+const TEST_WITHDRAW_UNBONDED: &str = "withdraw_unbonded";
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS bonding purse");
+    builder.get_purse_balance(purse)
+}
+
+fn get_validator_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let validator = builder
+        .get_account(VALIDATOR_ADDR)
+        .expect("should get validator account");
+    builder.get_purse_balance(validator.main_purse())
+}
+
+/// Runs genesis with a bonded validator and a non-zero unbonding delay, then has the validator
+/// unbond part of its stake.
+///
+/// Returns `None` if bonding is disabled.
+fn setup_unbonded_validator() -> Option<InMemoryWasmTestBuilder> {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_ADDR,
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        accounts,
+        *DEFAULT_WASM_COSTS,
+        DEFAULT_GAS_PRICE,
+        DEFAULT_INFLATION_RATE,
+        DEFAULT_SLASH_RATE,
+        UNBONDING_DELAY,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let unbond_request = ExecuteRequestBuilder::standard(
+        VALIDATOR_ADDR,
+        CONTRACT_POS_BONDING,
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_UNBOND),
+            ARG_AMOUNT => Some(U512::from(UNBOND_AMOUNT)),
+        },
+    )
+    .with_block_time(UNBOND_TIME)
+    .build();
+
+    let result = builder.exec(unbond_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return None;
+    }
+    builder.expect_success().commit();
+
+    // The unbonded funds are kept in the bonding purse until the delay has passed.
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+    Some(builder)
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_unbonded_stake_at_era_boundary_after_delay() {
+    let mut builder = match setup_unbonded_validator() {
+        Some(builder) => builder,
+        None => return,
+    };
+    let validator_balance_before = get_validator_balance(&builder);
+
+    builder.step(
+        *DEFAULT_PROTOCOL_VERSION,
+        BlockTime::new(UNBOND_TIME + UNBONDING_DELAY - 1),
+    );
+    assert_eq!(get_validator_balance(&builder), validator_balance_before);
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+
+    builder.step(
+        *DEFAULT_PROTOCOL_VERSION,
+        BlockTime::new(UNBOND_TIME + UNBONDING_DELAY),
+    );
+    assert_eq!(
+        get_validator_balance(&builder),
+        validator_balance_before + UNBOND_AMOUNT
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE - UNBOND_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_withdraw_unbonded_stake_only_after_delay() {
+    let mut builder = match setup_unbonded_validator() {
+        Some(builder) => builder,
+        None => return,
+    };
+
+    let withdraw_request = |block_time: u64| {
+        ExecuteRequestBuilder::standard(
+            VALIDATOR_ADDR,
+            CONTRACT_POS_BONDING,
+            runtime_args! {
+                ARG_ENTRY_POINT => String::from(TEST_WITHDRAW_UNBONDED),
+            },
+        )
+        .with_block_time(block_time)
+        .build()
+    };
+
+    builder
+        .exec(withdraw_request(UNBOND_TIME + UNBONDING_DELAY - 1))
+        .commit();
+    assert!(builder.is_error());
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+
+    builder
+        .exec(withdraw_request(UNBOND_TIME + UNBONDING_DELAY))
+        .expect_success()
+        .commit();
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE - UNBOND_AMOUNT)
+    );
+}
@@ -0,0 +1,286 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{execution_result::ExecutionResult, upgrade::ActivationPoint, Error},
+    execution,
+};
+use engine_grpc_server::engine_server::ipc::DeployCode;
+use engine_shared::{gas::Gas, motes::Motes};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_GAS_PRICE, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    runtime_args,
+    scheduled_call::{SCHEDULER_NAME, SCHEDULER_PURSE_KEY, SCHEDULER_QUEUE_KEY},
+    system_contract_type::SystemContractRegistry,
+    ApiError, BlockTime, ContractHash, Key, ProtocolVersion, RuntimeArgs, ScheduledCall, U512,
+};
+
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const CONTRACT_SCHEDULER_INSTALL: &str = "scheduler_install.wasm";
+const CONTRACT_SCHEDULED_CALL: &str = "scheduled_call.wasm";
+const CONTRACT_HASH_KEY: &str = "scheduled_call_contract_hash";
+const TICKS_KEY: &str = "ticks";
+const ARG_ACTIVATION_TIME: &str = "activation_time";
+const ARG_DIRECT: &str = "direct";
+const ARG_PAYMENT: &str = "payment";
+const ACTIVATION_TIME: u64 = 10_000;
+const SECOND_TICK_DELAY: u64 = 1_000;
+const CALL_GAS_LIMIT: u64 = 10_000_000_000;
+/// The payment for each scheduled call, which buys exactly the per-call gas limit.
+const PAYMENT: u64 = CALL_GAS_LIMIT * DEFAULT_GAS_PRICE;
+
+fn upgrade_with_scheduler(builder: &mut InMemoryWasmTestBuilder) -> ProtocolVersion {
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = {
+        let bytes = utils::read_wasm_file_bytes(CONTRACT_SCHEDULER_INSTALL);
+        let mut installer_code = DeployCode::new();
+        installer_code.set_code(bytes);
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_installer_code(installer_code)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    new_protocol_version
+}
+
+fn schedule_calls(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    direct: bool,
+    payment: u64,
+) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SCHEDULED_CALL,
+        runtime_args! {
+            ARG_ACTIVATION_TIME => ACTIVATION_TIME,
+            ARG_DIRECT => direct,
+            ARG_PAYMENT => U512::from(payment),
+        },
+    )
+    .with_protocol_version(protocol_version)
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn run_scheduled_calls_with_results(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    blocktime: u64,
+) -> Vec<ExecutionResult> {
+    builder.run_scheduled_calls(
+        protocol_version,
+        BlockTime::new(blocktime),
+        Gas::new(U512::from(CALL_GAS_LIMIT)),
+    )
+}
+
+fn run_scheduled_calls(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    blocktime: u64,
+) -> Vec<bool> {
+    run_scheduled_calls_with_results(builder, protocol_version, blocktime)
+        .iter()
+        .map(|result| result.is_success())
+        .collect()
+}
+
+fn get_scheduler_hash(builder: &InMemoryWasmTestBuilder) -> ContractHash {
+    let registry: SystemContractRegistry = builder.query_expect(Key::SystemRegistry, &[]);
+    *registry
+        .get(SCHEDULER_NAME)
+        .expect("should have registered scheduler")
+}
+
+fn get_queue(builder: &InMemoryWasmTestBuilder) -> Vec<ScheduledCall> {
+    let scheduler_hash = get_scheduler_hash(builder);
+    builder.query_expect(Key::Hash(scheduler_hash), &[SCHEDULER_QUEUE_KEY])
+}
+
+fn get_scheduler_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_contract(get_scheduler_hash(builder))
+        .expect("should have scheduler")
+        .named_keys()
+        .get(SCHEDULER_PURSE_KEY)
+        .expect("should have purse")
+        .into_uref()
+        .expect("should be uref");
+    builder.get_purse_balance(purse)
+}
+
+fn get_account_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    builder.get_purse_balance(purse)
+}
+
+fn get_ticks(builder: &InMemoryWasmTestBuilder) -> u64 {
+    let contract_hash: ContractHash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(CONTRACT_HASH_KEY)
+        .expect("should have contract hash")
+        .into_hash()
+        .expect("should be hash");
+    builder.query_expect(Key::Hash(contract_hash), &[TICKS_KEY])
+}
+
+#[ignore]
+#[test]
+fn should_make_matured_scheduled_calls_in_order() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let protocol_version = upgrade_with_scheduler(&mut builder);
+
+    schedule_calls(&mut builder, protocol_version, false, PAYMENT);
+    builder.expect_success();
+    assert_eq!(get_scheduler_balance(&builder), U512::from(3 * PAYMENT));
+
+    let queue = get_queue(&builder);
+    let activation_times: Vec<u64> = queue
+        .iter()
+        .map(|scheduled_call| scheduled_call.activation_time().into())
+        .collect();
+    assert_eq!(
+        activation_times,
+        vec![
+            ACTIVATION_TIME,
+            ACTIVATION_TIME,
+            ACTIVATION_TIME + SECOND_TICK_DELAY
+        ]
+    );
+    assert!(queue
+        .iter()
+        .all(|scheduled_call| scheduled_call.account_hash() == DEFAULT_ACCOUNT_ADDR));
+
+    // Nothing is due yet
+    assert!(run_scheduled_calls(&mut builder, protocol_version, ACTIVATION_TIME - 1).is_empty());
+    assert_eq!(get_queue(&builder).len(), 3);
+    assert_eq!(get_ticks(&builder), 0);
+
+    // The failing call doesn't prevent the other call due at the same time
+    assert_eq!(
+        run_scheduled_calls(&mut builder, protocol_version, ACTIVATION_TIME),
+        vec![true, false]
+    );
+    assert_eq!(get_queue(&builder).len(), 1);
+    assert_eq!(get_ticks(&builder), 1);
+
+    assert_eq!(
+        run_scheduled_calls(
+            &mut builder,
+            protocol_version,
+            ACTIVATION_TIME + SECOND_TICK_DELAY
+        ),
+        vec![true]
+    );
+    assert!(get_queue(&builder).is_empty());
+    assert_eq!(get_ticks(&builder), 2);
+    assert_eq!(get_scheduler_balance(&builder), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_refund_unused_payment_of_scheduled_calls() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let protocol_version = upgrade_with_scheduler(&mut builder);
+
+    schedule_calls(&mut builder, protocol_version, false, PAYMENT);
+    builder.expect_success();
+
+    let balance_before = get_account_balance(&builder);
+    let results = run_scheduled_calls_with_results(&mut builder, protocol_version, ACTIVATION_TIME);
+    assert_eq!(results.len(), 2);
+
+    // Both the successful and the failed call pay for the gas they used, and no more
+    let expected_refund = results
+        .iter()
+        .map(|result| {
+            let cost = Motes::from_gas(result.cost(), DEFAULT_GAS_PRICE).expect("should convert");
+            assert!(!cost.value().is_zero());
+            U512::from(PAYMENT) - cost.value()
+        })
+        .fold(U512::zero(), |total, refund| total + refund);
+    assert_eq!(get_account_balance(&builder), balance_before + expected_refund);
+    assert_eq!(get_scheduler_balance(&builder), U512::from(PAYMENT));
+}
+
+#[ignore]
+#[test]
+fn should_limit_scheduled_call_gas_to_what_its_payment_buys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let protocol_version = upgrade_with_scheduler(&mut builder);
+
+    // Not enough for a single unit of gas
+    schedule_calls(&mut builder, protocol_version, false, DEFAULT_GAS_PRICE - 1);
+    builder.expect_success();
+
+    let results = run_scheduled_calls_with_results(&mut builder, protocol_version, ACTIVATION_TIME);
+    assert_eq!(results.len(), 2);
+    for result in &results {
+        assert_matches!(result.as_error(), Some(Error::Exec(execution::Error::GasLimit)));
+    }
+    assert_eq!(get_ticks(&builder), 0);
+}
+
+#[ignore]
+#[test]
+fn should_not_schedule_calls_from_session_code() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let protocol_version = upgrade_with_scheduler(&mut builder);
+
+    schedule_calls(&mut builder, protocol_version, true, PAYMENT);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::User(0)))
+    );
+    assert!(get_queue(&builder).is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_do_nothing_without_scheduler() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let post_state_hash = builder.get_post_state_hash();
+    assert!(run_scheduled_calls(&mut builder, PROTOCOL_VERSION, ACTIVATION_TIME).is_empty());
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
@@ -0,0 +1,207 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{upgrade::ActivationPoint, Error},
+    execution,
+};
+use engine_grpc_server::engine_server::ipc::DeployCode;
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    runtime_args, system_contract_type::SystemContractRegistry, ApiError, CLValue, Key,
+    ProtocolVersion, RuntimeArgs, SystemContractType,
+};
+
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const CONTRACT_GET_SYSTEM_CONTRACT: &str = "get_system_contract.wasm";
+const CONTRACT_SYSTEM_CONTRACT_REGISTRAR: &str = "system_contract_registrar.wasm";
+const ARG_NAME: &str = "name";
+const REGISTERED_SYSTEM_CONTRACT_NAME: &str = "auction";
+
+fn get_system_contract_registry(builder: &InMemoryWasmTestBuilder) -> SystemContractRegistry {
+    let stored_value = builder
+        .query(None, Key::SystemRegistry, &[])
+        .expect("should have system contract registry");
+    let cl_value: CLValue = match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value,
+        other => panic!("expected CLValue but got {:?}", other),
+    };
+    cl_value
+        .into_t()
+        .expect("should be system contract registry")
+}
+
+fn upgrade_with_registrar(builder: &mut InMemoryWasmTestBuilder) -> ProtocolVersion {
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = {
+        let bytes = utils::read_wasm_file_bytes(CONTRACT_SYSTEM_CONTRACT_REGISTRAR);
+        let mut installer_code = DeployCode::new();
+        installer_code.set_code(bytes);
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_installer_code(installer_code)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    new_protocol_version
+}
+
+#[ignore]
+#[test]
+fn should_register_system_contracts_at_genesis() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let registry = get_system_contract_registry(&builder);
+
+    assert_eq!(registry.len(), 3);
+    assert_eq!(
+        registry.get(&SystemContractType::Mint.to_string()),
+        Some(&builder.get_mint_contract_hash())
+    );
+    assert_eq!(
+        registry.get(&SystemContractType::ProofOfStake.to_string()),
+        Some(&builder.get_pos_contract_hash())
+    );
+    assert_eq!(
+        registry.get(&SystemContractType::StandardPayment.to_string()),
+        Some(&builder.get_standard_payment_contract_hash())
+    );
+}
+
+#[ignore]
+#[test]
+fn should_get_system_contract_by_name() {
+    let name = SystemContractType::Mint.to_string();
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_SYSTEM_CONTRACT,
+        runtime_args! { ARG_NAME => name.clone() },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.named_keys().get(&name),
+        Some(&Key::Hash(builder.get_mint_contract_hash()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_get_unregistered_system_contract() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_SYSTEM_CONTRACT,
+        runtime_args! { ARG_NAME => REGISTERED_SYSTEM_CONTRACT_NAME.to_string() },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::InvalidSystemContract))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_register_system_contract_at_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let new_protocol_version = upgrade_with_registrar(&mut builder);
+
+    let registry = get_system_contract_registry(&builder);
+    assert_eq!(registry.len(), 4);
+    let registered_contract_hash = *registry
+        .get(REGISTERED_SYSTEM_CONTRACT_NAME)
+        .expect("should have registered system contract");
+    assert_eq!(
+        registry.get(&SystemContractType::Mint.to_string()),
+        Some(&builder.get_mint_contract_hash())
+    );
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_SYSTEM_CONTRACT,
+        runtime_args! { ARG_NAME => REGISTERED_SYSTEM_CONTRACT_NAME.to_string() },
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.named_keys().get(REGISTERED_SYSTEM_CONTRACT_NAME),
+        Some(&Key::Hash(registered_contract_hash))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_register_system_contract_outside_of_upgrade() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SYSTEM_CONTRACT_REGISTRAR,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::InvalidContext));
+
+    let registry = get_system_contract_registry(&builder);
+    assert!(!registry.contains_key(REGISTERED_SYSTEM_CONTRACT_NAME));
+}
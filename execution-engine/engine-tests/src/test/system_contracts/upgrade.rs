@@ -2,20 +2,22 @@ use engine_core::engine_state::{upgrade::ActivationPoint, Error};
 use engine_grpc_server::engine_server::ipc::DeployCode;
 #[cfg(feature = "use-system-contracts")]
 use engine_shared::{stored_value::StoredValue, transform::Transform};
-use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
-    DEFAULT_WASM_COSTS,
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
 };
-#[cfg(feature = "use-system-contracts")]
-use engine_test_support::{internal::ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::ProtocolVersion;
 #[cfg(feature = "use-system-contracts")]
-use types::{runtime_args, CLValue, Key, RuntimeArgs, U512};
+use types::{runtime_args, CLValue, Key, U512};
+use types::{ProtocolVersion, RuntimeArgs};
 
-const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
 const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
 const MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME: &str = "modified_system_upgrader.wasm";
+const DO_NOTHING_CONTRACT_NAME: &str = "do_nothing.wasm";
 #[cfg(feature = "use-system-contracts")]
 const MODIFIED_MINT_CALLER_CONTRACT_NAME: &str = "modified_mint_caller.wasm";
 #[cfg(feature = "use-system-contracts")]
@@ -35,6 +37,8 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        verify_ed25519: 10_000,
+        verify_secp256k1: 10_000,
     }
 }
 
@@ -642,3 +646,77 @@ fn should_fail_major_upgrade_without_installer() {
         Error::InvalidUpgradeConfig.to_string()
     );
 }
+
+#[ignore]
+#[test]
+fn should_chain_consecutive_upgrades() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let protocol_version_1 =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+    let protocol_version_2 =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+    let upgraded_wasm_costs = get_upgraded_wasm_costs();
+
+    builder.upgrade_with(
+        UpgradeRequestBuilder::new()
+            .with_new_protocol_version(protocol_version_1)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT),
+    );
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        DO_NOTHING_CONTRACT_NAME,
+        RuntimeArgs::default(),
+    )
+    .with_protocol_version(protocol_version_1)
+    .build();
+    builder
+        .exec(exec_request)
+        .expect_success()
+        .expect_protocol_version(protocol_version_1)
+        .commit();
+
+    builder.upgrade_with(
+        UpgradeRequestBuilder::new()
+            .with_new_protocol_version(protocol_version_2)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_costs(upgraded_wasm_costs),
+    );
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        DO_NOTHING_CONTRACT_NAME,
+        RuntimeArgs::default(),
+    )
+    .with_protocol_version(protocol_version_2)
+    .build();
+    builder
+        .exec(exec_request)
+        .expect_success()
+        .expect_protocol_version(protocol_version_2)
+        .commit();
+
+    assert_eq!(
+        builder.get_protocol_versions(),
+        &[PROTOCOL_VERSION, protocol_version_1, protocol_version_2]
+    );
+    assert_eq!(builder.get_protocol_version(), protocol_version_2);
+    assert_eq!(
+        builder.get_exec_protocol_version(0),
+        Some(protocol_version_1)
+    );
+
+    let costs_of = |protocol_version| {
+        *builder
+            .get_protocol_data(protocol_version)
+            .expect("should have protocol data")
+            .wasm_costs()
+    };
+    assert_eq!(costs_of(PROTOCOL_VERSION), *DEFAULT_WASM_COSTS);
+    assert_eq!(costs_of(protocol_version_1), *DEFAULT_WASM_COSTS);
+    assert_eq!(costs_of(protocol_version_2), upgraded_wasm_costs);
+}
@@ -0,0 +1,154 @@
+use std::convert::TryInto;
+
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{
+        upgrade::{Migration, MigrationContext, UpgradeConfig},
+        Error, SYSTEM_ACCOUNT_ADDR,
+    },
+    execution,
+};
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+use engine_test_support::internal::{utils, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use types::{runtime_args, ApiError, CLValue, Key, ProtocolVersion, RuntimeArgs, U512};
+
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+const CREATE_PURSE_01_CONTRACT_NAME: &str = "create_purse_01.wasm";
+const ARG_PURSE_NAME: &str = "purse_name";
+const MIGRATED_PURSE_NAME: &str = "migrated_purse";
+const COUNTER_MIGRATION_NAME: &str = "increment_counter";
+const FAILING_MIGRATION_NAME: &str = "fail";
+const CREATE_PURSE_MIGRATION_NAME: &str = "create_purse";
+const COUNTER_KEY: Key = Key::Hash([42u8; 32]);
+
+fn next_protocol_version() -> ProtocolVersion {
+    let sem_ver = PROTOCOL_VERSION.value();
+    ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1)
+}
+
+fn increment_counter(context: &mut dyn MigrationContext) -> Result<(), execution::Error> {
+    let count: u64 = match context.read(&COUNTER_KEY)? {
+        Some(StoredValue::CLValue(cl_value)) => cl_value.into_t()?,
+        Some(_) => panic!("counter should be a CLValue"),
+        None => 0,
+    };
+    let cl_value = CLValue::from_t(count + 1)?;
+    context.write(COUNTER_KEY, StoredValue::CLValue(cl_value));
+    Ok(())
+}
+
+fn fail(_context: &mut dyn MigrationContext) -> Result<(), execution::Error> {
+    Err(execution::Error::Revert(ApiError::User(1)))
+}
+
+#[ignore]
+#[test]
+fn should_run_native_migration_once() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let new_protocol_version = next_protocol_version();
+    let migration = Migration::native(COUNTER_MIGRATION_NAME, increment_counter);
+
+    builder.upgrade_with_migrations(
+        new_protocol_version,
+        vec![migration.clone(), migration.clone()],
+    );
+
+    assert_eq!(builder.query_expect::<u64>(COUNTER_KEY, &[]), 1);
+    assert_eq!(builder.get_protocol_version(), new_protocol_version);
+
+    let marker: String = builder.query_expect(migration.marker_key(new_protocol_version), &[]);
+    assert_eq!(marker, COUNTER_MIGRATION_NAME);
+}
+
+#[ignore]
+#[test]
+fn should_run_migrations_of_each_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let protocol_version_1 = next_protocol_version();
+    let sem_ver = PROTOCOL_VERSION.value();
+    let protocol_version_2 =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+    let migration = Migration::native(COUNTER_MIGRATION_NAME, increment_counter);
+
+    builder.upgrade_with_migrations(protocol_version_1, vec![migration.clone()]);
+    assert_eq!(builder.query_expect::<u64>(COUNTER_KEY, &[]), 1);
+
+    builder.upgrade_with_migrations(protocol_version_2, vec![migration]);
+    assert_eq!(builder.query_expect::<u64>(COUNTER_KEY, &[]), 2);
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_when_migration_fails() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let pre_state_hash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should convert to Blake2bHash");
+    let upgrade_config = UpgradeConfig::new(
+        pre_state_hash,
+        PROTOCOL_VERSION,
+        next_protocol_version(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .with_migration(Migration::native(COUNTER_MIGRATION_NAME, increment_counter))
+    .with_migration(Migration::native(FAILING_MIGRATION_NAME, fail));
+
+    let error = builder
+        .get_engine_state()
+        .commit_upgrade(CorrelationId::new(), upgrade_config)
+        .expect_err("should fail upgrade");
+
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::User(1)))
+    );
+    assert!(builder.query(None, COUNTER_KEY, &[]).is_err());
+}
+
+#[ignore]
+#[test]
+fn should_run_wasm_migration_as_system_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let migration = Migration::wasm(
+        CREATE_PURSE_MIGRATION_NAME,
+        utils::read_wasm_file_bytes(CREATE_PURSE_01_CONTRACT_NAME),
+        runtime_args! { ARG_PURSE_NAME => MIGRATED_PURSE_NAME },
+    );
+
+    builder.upgrade_with_migrations(next_protocol_version(), vec![migration]);
+
+    let system_account = builder
+        .get_account(SYSTEM_ACCOUNT_ADDR)
+        .expect("should have system account");
+    let purse = system_account
+        .named_keys()
+        .get(MIGRATED_PURSE_NAME)
+        .expect("should have migrated purse")
+        .into_uref()
+        .expect("should be a uref");
+    assert_eq!(builder.get_purse_balance(purse), U512::zero());
+}
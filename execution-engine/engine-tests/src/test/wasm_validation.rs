@@ -0,0 +1,279 @@
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, EngineConfig};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::validation::WasmValidationConfig;
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const SESSION_WAT_WITH_FLOATS: &str = r#"
+(module
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      f32.const 0.1
+      f32.const 0.2
+      f32.add
+      drop)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_LARGE_MEMORY: &str = r#"
+(module
+    (memory (;0;) 1 65)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      nop)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_BOUNDED_MEMORY: &str = r#"
+(module
+    (memory (;0;) 1 48)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      nop)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_MEMORY_ABOVE_DEFAULT_LIMIT: &str = r#"
+(module
+    (memory (;0;) 1 100)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      nop)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_LARGE_TABLE: &str = r#"
+(module
+    (memory (;0;) 1)
+    (table (;0;) 5000 funcref)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      nop)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_UNKNOWN_IMPORT: &str = r#"
+(module
+    (import "env" "no_such_host_function" (func $no_such_host_function))
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      call $no_such_host_function)
+    (export "call" (func $call)))
+"#;
+
+const SESSION_WAT_WITH_RANDOM_BYTES_IMPORT: &str = r#"
+(module
+    (import "env" "random_bytes" (func $random_bytes (param i32 i32)))
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (type (;0;) (func))
+    (func $call (type 0)
+      i32.const 0
+      i32.const 32
+      call $random_bytes)
+    (export "call" (func $call)))
+"#;
+
+fn builder_with_max_memory_pages(max_memory_pages: u32) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_max_memory_pages(max_memory_pages);
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn exec_session_wat(builder: &mut InMemoryWasmTestBuilder, session_wat: &str) {
+    exec_session_wat_under(builder, session_wat, *DEFAULT_PROTOCOL_VERSION);
+}
+
+fn exec_session_wat_under(
+    builder: &mut InMemoryWasmTestBuilder,
+    session_wat: &str,
+    protocol_version: ProtocolVersion,
+) {
+    let session_bytes = wabt::wat2wasm(session_wat).expect("should parse");
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(session_bytes, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .build();
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_protocol_version(protocol_version)
+        .build();
+
+    builder.exec(exec_request).commit();
+}
+
+fn assert_rejected_with(builder: &InMemoryWasmTestBuilder, expected: &str) {
+    let message = builder.exec_error_message(0).expect("should fail");
+    assert!(
+        message.contains(expected),
+        "Error message {:?} does not contain expected pattern {:?}",
+        message,
+        expected
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_floating_point_instructions() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_FLOATS);
+
+    assert_rejected_with(&builder, "Floating point instruction is not allowed");
+}
+
+#[ignore]
+#[test]
+fn should_reject_memory_above_default_limit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_LARGE_MEMORY);
+
+    assert_rejected_with(&builder, "Memory of 65 pages exceeds the limit of 64 pages");
+}
+
+#[ignore]
+#[test]
+fn should_reject_table_above_default_limit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_LARGE_TABLE);
+
+    assert_rejected_with(&builder, "Table of 5000 entries exceeds the limit");
+}
+
+#[ignore]
+#[test]
+fn should_reject_import_of_unknown_host_function() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_UNKNOWN_IMPORT);
+
+    assert_rejected_with(
+        &builder,
+        "Import env::no_such_host_function is not provided by the host",
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_import_of_host_function_introduced_by_later_protocol_version() {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        ProtocolVersion::V1_0_0,
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    exec_session_wat_under(
+        &mut builder,
+        SESSION_WAT_WITH_RANDOM_BYTES_IMPORT,
+        ProtocolVersion::V1_0_0,
+    );
+
+    assert_rejected_with(
+        &builder,
+        "Import env::random_bytes is not provided by the host",
+    );
+}
+
+#[ignore]
+#[test]
+fn should_accept_import_of_host_function_under_protocol_version_introducing_it() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat_under(
+        &mut builder,
+        SESSION_WAT_WITH_RANDOM_BYTES_IMPORT,
+        ProtocolVersion::V1_1_0,
+    );
+
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_accept_memory_within_default_limit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_BOUNDED_MEMORY);
+
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_reject_memory_above_configured_limit() {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_wasm_validation_config(WasmValidationConfig::new().with_max_memory_pages(32));
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_BOUNDED_MEMORY);
+
+    assert_rejected_with(&builder, "Memory of 48 pages exceeds the limit of 32 pages");
+}
+
+#[ignore]
+#[test]
+fn should_accept_memory_at_configured_limit() {
+    let mut builder = builder_with_max_memory_pages(48);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_BOUNDED_MEMORY);
+
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_reject_memory_one_page_above_configured_limit() {
+    let mut builder = builder_with_max_memory_pages(47);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_BOUNDED_MEMORY);
+
+    assert_rejected_with(&builder, "Memory of 48 pages exceeds the limit of 47 pages");
+}
+
+#[ignore]
+#[test]
+fn should_accept_memory_above_default_limit_when_configured() {
+    let mut builder = builder_with_max_memory_pages(100);
+
+    exec_session_wat(&mut builder, SESSION_WAT_WITH_MEMORY_ABOVE_DEFAULT_LIMIT);
+
+    builder.expect_success();
+}
@@ -1,6 +1,11 @@
 use lazy_static::lazy_static;
 
-use engine_core::{engine_state::Error as CoreError, execution::Error as ExecError};
+use engine_core::{
+    engine_state::{genesis::POS_REWARDS_PURSE, Error as CoreError, CONV_RATE},
+    execution::Error as ExecError,
+};
+use engine_shared::{gas::Gas, motes::Motes};
+use engine_storage::protocol_data::DEFAULT_TRANSFER_COST;
 use engine_test_support::{
     internal::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
@@ -21,6 +26,10 @@ lazy_static! {
     static ref TRANSFER_2_AMOUNT_WITH_ADV: U512 = *DEFAULT_PAYMENT + *TRANSFER_2_AMOUNT;
     static ref TRANSFER_TOO_MUCH: U512 = U512::from(u64::max_value());
     static ref ACCOUNT_1_INITIAL_BALANCE: U512 = *DEFAULT_PAYMENT;
+    static ref TRANSFER_FEE: U512 =
+        Motes::from_gas(Gas::new(U512::from(DEFAULT_TRANSFER_COST)), CONV_RATE)
+            .expect("should convert transfer cost to motes")
+            .value();
 }
 
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
@@ -84,6 +93,7 @@ fn transfer_wasmless(wasmless_transfer: WasmlessTransfer) {
 
     let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
     let account_2_starting_balance = builder.get_purse_balance(account_2_purse);
+    let rewards_purse_starting_balance = get_pos_rewards_purse_balance(&builder);
 
     let runtime_args = match wasmless_transfer {
         WasmlessTransfer::AccountMainPurseToPurse => {
@@ -119,7 +129,7 @@ fn transfer_wasmless(wasmless_transfer: WasmlessTransfer) {
         .commit();
 
     assert_eq!(
-        account_1_starting_balance - transfer_amount,
+        account_1_starting_balance - transfer_amount - *TRANSFER_FEE,
         builder.get_purse_balance(account_1_purse),
         "account 1 ending balance incorrect"
     );
@@ -128,6 +138,116 @@ fn transfer_wasmless(wasmless_transfer: WasmlessTransfer) {
         builder.get_purse_balance(account_2_purse),
         "account 2 ending balance incorrect"
     );
+    assert_eq!(
+        rewards_purse_starting_balance + *TRANSFER_FEE,
+        get_pos_rewards_purse_balance(&builder),
+        "transfer fee should be paid into the rewards purse"
+    );
+    assert_eq!(
+        builder.last_exec_gas_cost(),
+        Gas::new(U512::from(DEFAULT_TRANSFER_COST)),
+        "wasmless transfer should report its flat cost"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_fee_for_failed_wasmless_transfer() {
+    let create_account_2: bool = true;
+    let mut builder = init_wasmless_transform_builder(create_account_2);
+
+    let account_1_purse = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1")
+        .main_purse();
+
+    let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
+    let rewards_purse_starting_balance = get_pos_rewards_purse_balance(&builder);
+
+    let no_wasm_transfer_request = {
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_empty_payment_bytes(runtime_args! {})
+            .with_transfer_args(
+                runtime_args! { ARG_TARGET => ACCOUNT_2_ADDR, ARG_AMOUNT => *TRANSFER_TOO_MUCH },
+            )
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+    };
+
+    builder.exec(no_wasm_transfer_request).commit();
+
+    assert_eq!(
+        account_1_starting_balance - *TRANSFER_FEE,
+        builder.get_purse_balance(account_1_purse),
+        "only the transfer fee should be taken from account 1"
+    );
+    assert_eq!(
+        rewards_purse_starting_balance + *TRANSFER_FEE,
+        get_pos_rewards_purse_balance(&builder),
+        "transfer fee should be paid into the rewards purse"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_wasmless_without_funds_for_fee() {
+    let create_account_2: bool = true;
+    let mut builder = init_wasmless_transform_builder(create_account_2);
+
+    let account_1_purse = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1")
+        .main_purse();
+    let account_1_balance = builder.get_purse_balance(account_1_purse);
+
+    // leaves account 1 with less than the transfer fee
+    let drain_account_1_request = {
+        let amount = account_1_balance - *TRANSFER_FEE - 1;
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_empty_payment_bytes(runtime_args! {})
+            .with_transfer_args(
+                runtime_args! { ARG_TARGET => ACCOUNT_2_ADDR, ARG_AMOUNT => amount },
+            )
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+    };
+
+    builder
+        .exec(drain_account_1_request)
+        .expect_success()
+        .commit();
+
+    let no_wasm_transfer_request = {
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_empty_payment_bytes(runtime_args! {})
+            .with_transfer_args(
+                runtime_args! { ARG_TARGET => ACCOUNT_2_ADDR, ARG_AMOUNT => 1u64 },
+            )
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+    };
+
+    builder.exec(no_wasm_transfer_request);
+
+    let result = builder
+        .get_exec_responses()
+        .last()
+        .expect("Expected to be called after run()")
+        .get(0)
+        .expect("Unable to get first deploy result");
+
+    let error = result.as_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::InsufficientPayment),
+        "unexpected error: {}",
+        error
+    );
 }
 
 #[ignore]
@@ -461,6 +581,17 @@ fn get_default_account_named_uref(builder: &mut InMemoryWasmTestBuilder, name: &
         .to_owned()
 }
 
+fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let rewards_purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS rewards purse");
+    builder.get_purse_balance(rewards_purse)
+}
+
 fn init_wasmless_transform_builder(create_account_2: bool) -> InMemoryWasmTestBuilder {
     let mut builder = InMemoryWasmTestBuilder::default();
     let create_account_1_request = ExecuteRequestBuilder::standard(
@@ -0,0 +1,188 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{run_genesis_request::RunGenesisRequest, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_WRITE_LIMITS: &str = "write_limits.wasm";
+const ARG_NAME_LENGTH: &str = "name_length";
+const ARG_VALUE_SIZE: &str = "value_size";
+const ARG_APPEND_SIZE: &str = "append_size";
+const MAX_VALUE_SIZE: usize = 1_024;
+const MAX_NAMED_KEY_LENGTH: usize = 16;
+
+fn run_genesis_request_with_write_limits() -> RunGenesisRequest {
+    let mut exec_config = DEFAULT_EXEC_CONFIG.clone();
+    exec_config.set_max_value_size(MAX_VALUE_SIZE as u64);
+    exec_config.set_max_named_key_length(MAX_NAMED_KEY_LENGTH as u64);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn write_limits_request(
+    name_length: u32,
+    value_size: u32,
+    append_size: u32,
+) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_WRITE_LIMITS,
+        runtime_args! {
+            ARG_NAME_LENGTH => name_length,
+            ARG_VALUE_SIZE => value_size,
+            ARG_APPEND_SIZE => append_size,
+        },
+    )
+}
+
+fn run_write_limits(
+    name_length: u32,
+    value_size: u32,
+    append_size: u32,
+) -> InMemoryWasmTestBuilder {
+    let exec_request = write_limits_request(name_length, value_size, append_size).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request_with_write_limits())
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_allow_writes_within_limits() {
+    let mut builder = run_write_limits(MAX_NAMED_KEY_LENGTH as u32, 256, 256);
+    builder.expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account
+        .named_keys()
+        .contains_key(&"k".repeat(MAX_NAMED_KEY_LENGTH)));
+}
+
+#[ignore]
+#[test]
+fn should_fail_writing_value_exceeding_limit() {
+    let builder = run_write_limits(1, 2 * MAX_VALUE_SIZE as u32, 0);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::ValueTooLarge { size, max: MAX_VALUE_SIZE })
+            if *size > MAX_VALUE_SIZE
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_appending_past_value_size_limit() {
+    let value_size = MAX_VALUE_SIZE as u32 / 2;
+    let builder = run_write_limits(1, value_size, value_size);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::ValueTooLarge { size, max: MAX_VALUE_SIZE })
+            if *size > MAX_VALUE_SIZE
+    );
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key("k"));
+}
+
+#[ignore]
+#[test]
+fn should_fail_putting_key_with_name_exceeding_limit() {
+    let name_length = MAX_NAMED_KEY_LENGTH + 1;
+    let builder = run_write_limits(name_length as u32, 1, 0);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::NamedKeyTooLong {
+            length,
+            max: MAX_NAMED_KEY_LENGTH,
+        }) if *length == name_length
+    );
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key(&"k".repeat(name_length)));
+}
+
+#[ignore]
+#[test]
+fn should_change_write_limits_on_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .with_new_max_value_size(MAX_VALUE_SIZE as u64)
+        .with_new_max_named_key_length(MAX_NAMED_KEY_LENGTH as u64)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let exec_request = write_limits_request(1, 2 * MAX_VALUE_SIZE as u32, 0)
+        .with_protocol_version(new_protocol_version)
+        .build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::ValueTooLarge {
+            max: MAX_VALUE_SIZE,
+            ..
+        })
+    );
+}
@@ -1,11 +1,16 @@
+pub mod stack_overflow;
+pub mod validation;
 pub mod wasm_costs;
 
-use std::fmt::{self, Display, Formatter};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
 
 use parity_wasm::elements::{self, Module};
 use pwasm_utils::{self, stack_height};
 
-use crate::wasm_costs::WasmCosts;
+use crate::{validation::WasmValidationConfig, wasm_costs::WasmCosts};
 
 //NOTE: size of Wasm memory page is 64 KiB
 pub const MEM_PAGES: u32 = 64;
@@ -15,6 +20,11 @@ pub enum PreprocessingError {
     Deserialize(String),
     OperationForbiddenByGasRules,
     StackLimiter,
+    FloatingPointInstruction(String),
+    MemoryLimitExceeded { pages: u32, max_pages: u32 },
+    TableLimitExceeded { size: u32, max_size: u32 },
+    UnknownImport(String),
+    UnknownGlobal(u32),
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -29,31 +39,75 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::FloatingPointInstruction(instruction) => {
+                write!(f, "Floating point instruction is not allowed: {}", instruction)
+            }
+            PreprocessingError::MemoryLimitExceeded { pages, max_pages } => write!(
+                f,
+                "Memory of {} pages exceeds the limit of {} pages",
+                pages, max_pages
+            ),
+            PreprocessingError::TableLimitExceeded { size, max_size } => write!(
+                f,
+                "Table of {} entries exceeds the limit of {} entries",
+                size, max_size
+            ),
+            PreprocessingError::UnknownImport(import) => {
+                write!(f, "Import {} is not provided by the host", import)
+            }
+            PreprocessingError::UnknownGlobal(index) => {
+                write!(f, "Global {} is not defined by the module", index)
+            }
         }
     }
 }
 
 pub struct Preprocessor {
     wasm_costs: WasmCosts,
-    // Number of memory pages.
-    mem_pages: u32,
+    validation_config: WasmValidationConfig,
+    host_functions: Option<BTreeSet<String>>,
 }
 
 impl Preprocessor {
     pub fn new(wasm_costs: WasmCosts) -> Self {
         Self {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            validation_config: WasmValidationConfig::default(),
+            host_functions: None,
         }
     }
 
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+
+    pub fn with_validation_config(mut self, validation_config: WasmValidationConfig) -> Self {
+        self.validation_config = validation_config;
+        self
+    }
+
+    /// Restricts the functions a module may import to the host functions named in
+    /// `host_functions`.  Without a restriction, imports are only checked when the module is
+    /// instantiated.
+    pub fn with_host_functions(mut self, host_functions: BTreeSet<String>) -> Self {
+        self.host_functions = Some(host_functions);
+        self
+    }
+
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
         let module = deserialize(module_bytes)?;
-        let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
+        validation::validate(&module, self.validation_config)?;
+        if let Some(host_functions) = &self.host_functions {
+            validation::validate_imports(&module, host_functions)?;
+        }
+        let module =
+            pwasm_utils::externalize_mem(module, None, self.validation_config.max_memory_pages());
         let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
         let module = stack_height::inject_limiter(module, self.wasm_costs.max_stack_height)
             .map_err(|_| PreprocessingError::StackLimiter)?;
+        let module =
+            stack_overflow::inject_stack_overflow_call(module, self.wasm_costs.max_stack_height);
         Ok(module)
     }
 }
@@ -0,0 +1,217 @@
+//! Reporting of stack height limit violations.
+//!
+//! The stack height limiter injected by `pwasm_utils` traps with `unreachable` once the limit is
+//! exceeded, which can't be told apart from an `unreachable` in the module's own code.  The
+//! trapping instruction of each limit check is therefore preceded by a call to the host function
+//! [`FUNCTION_NAME`], which aborts execution with a dedicated error.
+//!
+//! The limiter appends the global holding the stack height after all of the module's own globals,
+//! and modules referencing globals they don't define are rejected by validation, so only the
+//! limiter's checks read that global.  A check of the module's own which merely looks the same
+//! is left alone.
+
+use parity_wasm::{
+    builder,
+    elements::{BlockType, ImportCountType, Instruction, Internal, Module, Section},
+};
+
+/// Name of the host function, imported from the `env` module, called when the stack height limit
+/// is exceeded.
+pub const FUNCTION_NAME: &str = "stack_overflow";
+
+/// Number of instructions in the check injected by the stack height limiter, where `$stack_height`
+/// is the global added by the limiter:
+///
+/// ```text
+/// get_global $stack_height
+/// i32.const $stack_limit
+/// i32.gt_u
+/// if
+///   unreachable
+/// end
+/// ```
+const LIMIT_CHECK_LENGTH: usize = 6;
+
+/// Offset of the `unreachable` instruction within the limit check.
+const LIMIT_CHECK_TRAP_OFFSET: usize = 4;
+
+/// Imports [`FUNCTION_NAME`] into `module` and calls it ahead of the trap of every check against
+/// `stack_limit` injected by the stack height limiter.
+pub fn inject_stack_overflow_call(module: Module, stack_limit: u32) -> Module {
+    let mut module_builder = builder::from_module(module);
+    let signature = module_builder.push_signature(builder::signature().build_sig());
+    module_builder.push_import(
+        builder::import()
+            .module("env")
+            .field(FUNCTION_NAME)
+            .external()
+            .func(signature)
+            .build(),
+    );
+    let mut module = module_builder.build();
+
+    // The stack height global is the last one, as the limiter added it after all others.
+    let stack_height_global = match global_count(&module).checked_sub(1) {
+        Some(stack_height_global) => stack_height_global,
+        None => return module,
+    };
+
+    // The new import is the last imported function, so every function defined by the module moves
+    // up by one index.
+    let stack_overflow_function = module.import_count(ImportCountType::Function) as u32 - 1;
+    for section in module.sections_mut() {
+        match section {
+            Section::Code(code_section) => {
+                for function_body in code_section.bodies_mut() {
+                    let instructions = function_body.code_mut().elements_mut();
+                    for instruction in instructions.iter_mut() {
+                        if let Instruction::Call(function_index) = instruction {
+                            if *function_index >= stack_overflow_function {
+                                *function_index += 1;
+                            }
+                        }
+                    }
+                    insert_calls(
+                        instructions,
+                        stack_height_global,
+                        stack_limit,
+                        stack_overflow_function,
+                    );
+                }
+            }
+            Section::Export(export_section) => {
+                for export in export_section.entries_mut() {
+                    if let Internal::Function(function_index) = export.internal_mut() {
+                        if *function_index >= stack_overflow_function {
+                            *function_index += 1;
+                        }
+                    }
+                }
+            }
+            Section::Element(elements_section) => {
+                for segment in elements_section.entries_mut() {
+                    for function_index in segment.members_mut() {
+                        if *function_index >= stack_overflow_function {
+                            *function_index += 1;
+                        }
+                    }
+                }
+            }
+            Section::Start(function_index) => {
+                if *function_index >= stack_overflow_function {
+                    *function_index += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    module
+}
+
+/// Returns the number of globals in `module`'s index space, imported ones included.
+pub fn global_count(module: &Module) -> u32 {
+    let defined = module
+        .global_section()
+        .map(|section| section.entries().len())
+        .unwrap_or_default();
+    (module.import_count(ImportCountType::Global) + defined) as u32
+}
+
+fn insert_calls(
+    instructions: &mut Vec<Instruction>,
+    stack_height_global: u32,
+    stack_limit: u32,
+    function_index: u32,
+) {
+    let trap_positions: Vec<usize> = instructions
+        .windows(LIMIT_CHECK_LENGTH)
+        .enumerate()
+        .filter(|(_, window)| is_limit_check(window, stack_height_global, stack_limit))
+        .map(|(position, _)| position + LIMIT_CHECK_TRAP_OFFSET)
+        .collect();
+
+    for position in trap_positions.into_iter().rev() {
+        instructions.insert(position, Instruction::Call(function_index));
+    }
+}
+
+fn is_limit_check(
+    instructions: &[Instruction],
+    stack_height_global: u32,
+    stack_limit: u32,
+) -> bool {
+    let expected_check = [
+        Instruction::GetGlobal(stack_height_global),
+        Instruction::I32Const(stack_limit as i32),
+        Instruction::I32GtU,
+        Instruction::If(BlockType::NoResult),
+        Instruction::Unreachable,
+        Instruction::End,
+    ];
+    instructions[..] == expected_check[..]
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{
+        CodeSection, Func, FuncBody, FunctionSection, FunctionType, GlobalEntry, GlobalSection,
+        GlobalType, InitExpr, Instructions, Type, TypeSection, ValueType,
+    };
+
+    use super::*;
+
+    const STACK_LIMIT: u32 = 10;
+    const OWN_GLOBAL: u32 = 0;
+    const STACK_HEIGHT_GLOBAL: u32 = 1;
+
+    fn limit_check(global: u32) -> Vec<Instruction> {
+        vec![
+            Instruction::GetGlobal(global),
+            Instruction::I32Const(STACK_LIMIT as i32),
+            Instruction::I32GtU,
+            Instruction::If(BlockType::NoResult),
+            Instruction::Unreachable,
+            Instruction::End,
+        ]
+    }
+
+    /// Returns a module whose only function runs a lookalike check on a global of its own, then the
+    /// check of the stack height limiter, whose global is the last one.
+    fn instrumented_module() -> Module {
+        let global = GlobalEntry::new(
+            GlobalType::new(ValueType::I32, true),
+            InitExpr::new(vec![Instruction::I32Const(0), Instruction::End]),
+        );
+        let mut code = limit_check(OWN_GLOBAL);
+        code.extend(limit_check(STACK_HEIGHT_GLOBAL));
+        code.push(Instruction::End);
+        Module::new(vec![
+            Section::Type(TypeSection::with_types(vec![Type::Function(
+                FunctionType::new(Vec::new(), None),
+            )])),
+            Section::Function(FunctionSection::with_entries(vec![Func::new(0)])),
+            Section::Global(GlobalSection::with_entries(vec![global.clone(), global])),
+            Section::Code(CodeSection::with_bodies(vec![FuncBody::new(
+                Vec::new(),
+                Instructions::new(code),
+            )])),
+        ])
+    }
+
+    #[test]
+    fn should_only_report_checks_of_the_stack_height_limiter() {
+        let module = inject_stack_overflow_call(instrumented_module(), STACK_LIMIT);
+
+        // the module has no other imports, so the stack overflow function has index zero
+        let mut expected_code = limit_check(OWN_GLOBAL);
+        let mut limiter_check = limit_check(STACK_HEIGHT_GLOBAL);
+        limiter_check.insert(LIMIT_CHECK_TRAP_OFFSET, Instruction::Call(0));
+        expected_code.extend(limiter_check);
+        expected_code.push(Instruction::End);
+
+        let code = module.code_section().expect("should have code").bodies()[0]
+            .code()
+            .elements();
+        assert_eq!(code, &expected_code[..]);
+    }
+}
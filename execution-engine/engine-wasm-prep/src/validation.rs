@@ -0,0 +1,193 @@
+//! Validation of Wasm modules against the constraints which keep their execution deterministic.
+
+use std::collections::BTreeSet;
+
+use parity_wasm::elements::{External, Instruction, Module, ResizableLimits};
+use pwasm_utils::rules::InstructionType;
+
+use crate::{stack_overflow, PreprocessingError, MEM_PAGES};
+
+/// Default maximum number of entries in a module's function table.
+pub const DEFAULT_MAX_TABLE_SIZE: u32 = 4096;
+
+/// The limits a Wasm module has to satisfy before it is executed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WasmValidationConfig {
+    max_memory_pages: u32,
+    max_table_size: u32,
+}
+
+impl Default for WasmValidationConfig {
+    fn default() -> Self {
+        WasmValidationConfig {
+            max_memory_pages: MEM_PAGES,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+        }
+    }
+}
+
+impl WasmValidationConfig {
+    /// Creates a new validation configuration with default limits.
+    pub fn new() -> WasmValidationConfig {
+        Default::default()
+    }
+
+    /// Returns the maximum number of 64 KiB memory pages a module may declare, and so grow its
+    /// memory to.
+    pub fn max_memory_pages(self) -> u32 {
+        self.max_memory_pages
+    }
+
+    pub fn with_max_memory_pages(mut self, max_memory_pages: u32) -> WasmValidationConfig {
+        self.max_memory_pages = max_memory_pages;
+        self
+    }
+
+    /// Returns the maximum number of entries a module's function table may declare.
+    pub fn max_table_size(self) -> u32 {
+        self.max_table_size
+    }
+
+    pub fn with_max_table_size(mut self, max_table_size: u32) -> WasmValidationConfig {
+        self.max_table_size = max_table_size;
+        self
+    }
+}
+
+/// Checks that `module` contains no floating-point instructions nor references to globals it
+/// doesn't define, and that its memory and table stay within the limits of `config`.
+///
+/// Floating-point results may differ between platforms (e.g. in the bits of a NaN), so they are
+/// rejected outright rather than metered.  The declared memory maximum bounds what `memory.grow`
+/// can reach, so checking declarations is enough to keep growth within limits.
+pub fn validate(module: &Module, config: WasmValidationConfig) -> Result<(), PreprocessingError> {
+    validate_memory(module, config.max_memory_pages)?;
+    validate_table(module, config.max_table_size)?;
+    validate_instructions(module)
+}
+
+/// Checks that every function imported by `module` is one of the `host_functions` of the `env`
+/// module.
+pub fn validate_imports(
+    module: &Module,
+    host_functions: &BTreeSet<String>,
+) -> Result<(), PreprocessingError> {
+    let import_entries = module
+        .import_section()
+        .map(|section| section.entries())
+        .unwrap_or_default();
+
+    for import_entry in import_entries {
+        if let External::Function(_) = import_entry.external() {
+            if import_entry.module() != "env" || !host_functions.contains(import_entry.field()) {
+                return Err(PreprocessingError::UnknownImport(format!(
+                    "{}::{}",
+                    import_entry.module(),
+                    import_entry.field()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_memory(module: &Module, max_pages: u32) -> Result<(), PreprocessingError> {
+    let declared = module
+        .memory_section()
+        .map(|section| section.entries())
+        .unwrap_or_default()
+        .iter()
+        .map(|memory_type| memory_type.limits());
+    let imported = imported_externals(module).filter_map(|external| match external {
+        External::Memory(memory_type) => Some(memory_type.limits()),
+        _ => None,
+    });
+
+    for limits in declared.chain(imported) {
+        let pages = largest_limit(limits);
+        if pages > max_pages {
+            return Err(PreprocessingError::MemoryLimitExceeded { pages, max_pages });
+        }
+    }
+    Ok(())
+}
+
+fn validate_table(module: &Module, max_size: u32) -> Result<(), PreprocessingError> {
+    let declared = module
+        .table_section()
+        .map(|section| section.entries())
+        .unwrap_or_default()
+        .iter()
+        .map(|table_type| table_type.limits());
+    let imported = imported_externals(module).filter_map(|external| match external {
+        External::Table(table_type) => Some(table_type.limits()),
+        _ => None,
+    });
+
+    for limits in declared.chain(imported) {
+        let size = largest_limit(limits);
+        if size > max_size {
+            return Err(PreprocessingError::TableLimitExceeded { size, max_size });
+        }
+    }
+    Ok(())
+}
+
+fn validate_instructions(module: &Module) -> Result<(), PreprocessingError> {
+    let function_bodies = module
+        .code_section()
+        .map(|section| section.bodies())
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|body| body.code().elements());
+    let global_initializers = module
+        .global_section()
+        .map(|section| section.entries())
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|global_entry| global_entry.init_expr().code());
+
+    let global_count = stack_overflow::global_count(module);
+
+    for instruction in function_bodies.chain(global_initializers) {
+        if is_floating_point(instruction) {
+            return Err(PreprocessingError::FloatingPointInstruction(format!(
+                "{:?}",
+                instruction
+            )));
+        }
+        match instruction {
+            Instruction::GetGlobal(index) | Instruction::SetGlobal(index)
+                if *index >= global_count =>
+            {
+                return Err(PreprocessingError::UnknownGlobal(*index));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn imported_externals(module: &Module) -> impl Iterator<Item = &External> {
+    module
+        .import_section()
+        .map(|section| section.entries())
+        .unwrap_or_default()
+        .iter()
+        .map(|import_entry| import_entry.external())
+}
+
+/// Returns the maximum of `limits` if declared, otherwise its initial size.
+fn largest_limit(limits: &ResizableLimits) -> u32 {
+    limits.maximum().unwrap_or_else(|| limits.initial())
+}
+
+fn is_floating_point(instruction: &Instruction) -> bool {
+    matches!(
+        InstructionType::op(instruction),
+        InstructionType::Float
+            | InstructionType::FloatConst
+            | InstructionType::FloatComparison
+            | InstructionType::FloatConversion
+    )
+}
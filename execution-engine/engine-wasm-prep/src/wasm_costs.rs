@@ -4,11 +4,18 @@ use pwasm_utils::rules::{InstructionType, Metering, Set};
 
 use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
 
-const NUM_FIELDS: usize = 10;
+const NUM_FIELDS: usize = 12;
 pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+/// The serialized length of the fields preceding the signature verification costs, which are all
+/// that was serialized before those costs were added.
+pub const LEGACY_WASM_COSTS_SERIALIZED_LENGTH: usize = 10 * U32_SERIALIZED_LENGTH;
+/// The cost of verifying an Ed25519 signature used when none is configured.
+pub const DEFAULT_VERIFY_ED25519_COST: u32 = 10_000;
+/// The cost of verifying a secp256k1 signature used when none is configured.
+pub const DEFAULT_VERIFY_SECP256K1_COST: u32 = 10_000;
 
 // Taken (partially) from parity-ethereum
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct WasmCosts {
     /// Default opcode cost
     pub regular: u32,
@@ -33,6 +40,10 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Fixed cost of verifying an Ed25519 signature on the host
+    pub verify_ed25519: u32,
+    /// Fixed cost of verifying a secp256k1 signature on the host
+    pub verify_secp256k1: u32,
 }
 
 impl WasmCosts {
@@ -64,6 +75,8 @@ impl ToBytes for WasmCosts {
         ret.append(&mut self.max_stack_height.to_bytes()?);
         ret.append(&mut self.opcodes_mul.to_bytes()?);
         ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.verify_ed25519.to_bytes()?);
+        ret.append(&mut self.verify_secp256k1.to_bytes()?);
         Ok(ret)
     }
 
@@ -84,6 +97,8 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (verify_ed25519, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (verify_secp256k1, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let wasm_costs = WasmCosts {
             regular,
             div,
@@ -95,6 +110,8 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            verify_ed25519,
+            verify_secp256k1,
         };
         Ok((wasm_costs, rem))
     }
@@ -117,6 +134,7 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            (verify_ed25519, verify_secp256k1) in (num::u32::ANY, num::u32::ANY),
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -129,6 +147,8 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                verify_ed25519,
+                verify_secp256k1,
             }
         }
     }
@@ -155,6 +175,8 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            verify_ed25519: 10_000,
+            verify_secp256k1: 10_000,
         }
     }
 
@@ -170,6 +192,8 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            verify_ed25519: 0,
+            verify_secp256k1: 0,
         }
     }
 
@@ -1,18 +1,103 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 mod runtime_provider;
 mod storage_provider;
+mod vesting;
 
+use alloc::{format, string::String, vec::Vec};
 use core::convert::TryFrom;
 
-use types::{account::AccountHash, system_contract_errors::mint::Error, Key, URef, U512};
+use types::{account::SYSTEM_ACCOUNT, system_contract_errors::mint::Error, Key, Motes, URef, U512};
+
+pub use crate::{
+    runtime_provider::RuntimeProvider, storage_provider::StorageProvider, vesting::VestingSchedule,
+};
+
+/// Name of the mint's named key under which the total supply of motes is stored.
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+/// Returns the name of the mint's named key under which the total supply of the application-defined
+/// token `id` is stored.
+fn token_supply_key_name(id: &str) -> String {
+    format!("token_supply_{}", id)
+}
+
+/// Returns the name of the mint's named key under which the balance of `purse` in the
+/// application-defined token `id` is stored.
+///
+/// Token purses are deliberately not registered in the mint's local state, so they can't be used
+/// where a purse of the native token is expected.
+fn token_purse_key_name(id: &str, purse: URef) -> String {
+    format!("token_{}_{}", id, purse.remove_access_rights().as_string())
+}
+
+/// Returns the `URef` holding the total supply of the token `id`.
+fn token_supply_uref<P: RuntimeProvider + ?Sized>(provider: &P, id: &str) -> Result<URef, Error> {
+    let key = provider
+        .get_key(&token_supply_key_name(id))
+        .ok_or(Error::TokenNotFound)?;
+    TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)
+}
+
+/// Returns the `URef` holding the balance of `purse` in the token `id`, or `None` if `purse` is not
+/// a purse of that token.
+fn token_balance_uref<P: RuntimeProvider + ?Sized>(
+    provider: &P,
+    id: &str,
+    purse: URef,
+) -> Result<Option<URef>, Error> {
+    match provider.get_key(&token_purse_key_name(id, purse)) {
+        Some(key) => TryFrom::<Key>::try_from(key)
+            .map(Some)
+            .map_err(|_| Error::InvalidAccessRights),
+        None => Ok(None),
+    }
+}
 
-pub use crate::{runtime_provider::RuntimeProvider, storage_provider::StorageProvider};
+/// Creates a purse of the token `id` holding `initial_balance`.
+fn new_token_purse<P: RuntimeProvider + StorageProvider + ?Sized>(
+    provider: &mut P,
+    id: &str,
+    initial_balance: U512,
+) -> URef {
+    let balance_key: Key = provider.new_uref(initial_balance).into();
+    let purse_uref: URef = provider.new_uref(());
+    provider.put_key(&token_purse_key_name(id, purse_uref), balance_key);
+    purse_uref
+}
+
+/// Returns the name of the mint's named key under which the vesting schedule of `purse` is stored.
+fn vesting_schedule_key_name(purse: URef) -> String {
+    format!("vesting_{}", purse.remove_access_rights().as_string())
+}
 
-const SYSTEM_ACCOUNT: AccountHash = AccountHash::new([0; 32]);
+/// Returns an error if leaving only `remaining` in `purse` would release funds still locked by its
+/// vesting schedule.
+fn check_vesting_schedule<P: RuntimeProvider + StorageProvider + ?Sized>(
+    provider: &mut P,
+    purse: URef,
+    remaining: U512,
+) -> Result<(), Error> {
+    let key = match provider.get_key(&vesting_schedule_key_name(purse)) {
+        Some(key) => key,
+        None => return Ok(()),
+    };
+    let vesting_schedule_uref: URef =
+        TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?;
+    let vesting_schedule: VestingSchedule = provider
+        .read(vesting_schedule_uref)?
+        .ok_or(Error::Storage)?;
+    if remaining < vesting_schedule.locked_amount_at(provider.get_block_time()) {
+        return Err(Error::FundsLocked);
+    }
+    Ok(())
+}
 
 pub trait Mint: RuntimeProvider + StorageProvider {
-    fn mint(&mut self, initial_balance: U512) -> Result<URef, Error> {
+    fn mint(&mut self, initial_balance: Motes) -> Result<URef, Error> {
+        let initial_balance = initial_balance.value();
         let caller = self.get_caller();
         if !initial_balance.is_zero() && caller != SYSTEM_ACCOUNT {
             return Err(Error::InvalidNonEmptyPurseCreation);
@@ -29,6 +114,20 @@ pub trait Mint: RuntimeProvider + StorageProvider {
         self.write_local(purse_uref.addr(), balance_key);
         // self.write(purse_uref.addr(), Key::Hash)
 
+        if !initial_balance.is_zero() {
+            match self.get_key(TOTAL_SUPPLY_KEY) {
+                Some(key) => {
+                    let total_supply_uref: URef =
+                        TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?;
+                    self.add(total_supply_uref, initial_balance)?;
+                }
+                None => {
+                    let total_supply_uref = self.new_uref(initial_balance);
+                    self.put_key(TOTAL_SUPPLY_KEY, total_supply_uref.into());
+                }
+            }
+        }
+
         Ok(purse_uref)
     }
 
@@ -55,15 +154,183 @@ pub trait Mint: RuntimeProvider + StorageProvider {
             Some(source_value) => source_value,
             None => return Err(Error::SourceNotFound),
         };
-        if amount > source_value {
-            return Err(Error::InsufficientFunds);
-        }
+        let source_value = source_value
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientFunds)?;
+        check_vesting_schedule(self, source, source_value)?;
         let target_balance: URef = match self.read_local(&target.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Err(Error::DestNotFound),
         };
-        self.write(source_balance, source_value - amount)?;
+        self.write(source_balance, source_value)?;
         self.add(target_balance, amount)?;
         Ok(())
     }
+
+    /// Transfers to every `(target, amount)` pair in `transfers` from `source`, reading and writing
+    /// the source balance only once.  Either all transfers are applied or none are.
+    fn transfer_batch(&mut self, source: URef, transfers: Vec<(URef, U512)>) -> Result<(), Error> {
+        if !source.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let source_balance: URef = match self.read_local(&source.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::SourceNotFound),
+        };
+        let source_value: U512 = match self.read(source_balance)? {
+            Some(source_value) => source_value,
+            None => return Err(Error::SourceNotFound),
+        };
+        let total = transfers
+            .iter()
+            .try_fold(U512::zero(), |total, (_, amount)| {
+                total.checked_add(*amount)
+            })
+            .ok_or(Error::InsufficientFunds)?;
+        let source_value = source_value
+            .checked_sub(total)
+            .ok_or(Error::InsufficientFunds)?;
+        check_vesting_schedule(self, source, source_value)?;
+
+        let mut target_balances = Vec::with_capacity(transfers.len());
+        for (target, amount) in transfers {
+            if !target.is_addable() {
+                return Err(Error::InvalidAccessRights);
+            }
+            let target_balance: URef = match self.read_local(&target.addr())? {
+                Some(key) => {
+                    TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?
+                }
+                None => return Err(Error::DestNotFound),
+            };
+            target_balances.push((target_balance, amount));
+        }
+
+        self.write(source_balance, source_value)?;
+        for (target_balance, amount) in target_balances {
+            self.add(target_balance, amount)?;
+        }
+        Ok(())
+    }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let purse_balance: URef = match self.read_local(&purse.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::PurseNotFound),
+        };
+        let purse_value: U512 = match self.read(purse_balance)? {
+            Some(purse_value) => purse_value,
+            None => return Err(Error::PurseNotFound),
+        };
+        let purse_value = purse_value
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientFunds)?;
+        check_vesting_schedule(self, purse, purse_value)?;
+        self.write(purse_balance, purse_value)?;
+
+        // Supply minted before it was tracked isn't accounted for, hence saturating.
+        if let Some(key) = self.get_key(TOTAL_SUPPLY_KEY) {
+            let total_supply_uref: URef =
+                TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?;
+            let total_supply: U512 = self.read(total_supply_uref)?.ok_or(Error::Storage)?;
+            self.write(total_supply_uref, total_supply.saturating_sub(amount))?;
+        }
+        Ok(())
+    }
+
+    /// Locks funds in `purse` according to `vesting_schedule`, replacing any previous schedule.
+    /// Only the system account may do this, e.g. for genesis accounts.
+    fn set_vesting_schedule(
+        &mut self,
+        purse: URef,
+        vesting_schedule: VestingSchedule,
+    ) -> Result<(), Error> {
+        if self.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::InvalidCaller);
+        }
+        if self.read_local::<_, Key>(&purse.addr())?.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        let vesting_schedule_uref = self.new_uref(vesting_schedule);
+        self.put_key(
+            &vesting_schedule_key_name(purse),
+            vesting_schedule_uref.into(),
+        );
+        Ok(())
+    }
+
+    /// Creates the application-defined token `id` with a fixed supply of `initial_supply`, and
+    /// returns a new purse of that token holding the whole supply.
+    fn create_token(&mut self, id: String, initial_supply: U512) -> Result<URef, Error> {
+        if self.get_key(&token_supply_key_name(&id)).is_some() {
+            return Err(Error::TokenAlreadyExists);
+        }
+        let total_supply_uref = self.new_uref(initial_supply);
+        self.put_key(&token_supply_key_name(&id), total_supply_uref.into());
+        Ok(new_token_purse(self, &id, initial_supply))
+    }
+
+    /// Returns a new, empty purse of the token `id`.
+    fn create_token_purse(&mut self, id: String) -> Result<URef, Error> {
+        token_supply_uref(self, &id)?;
+        Ok(new_token_purse(self, &id, U512::zero()))
+    }
+
+    /// Returns the balance of `purse` in the token `id`, or `None` if `purse` is not a purse of
+    /// that token.
+    fn token_balance(&mut self, id: String, purse: URef) -> Result<Option<U512>, Error> {
+        token_supply_uref(self, &id)?;
+        let balance_uref = match token_balance_uref(self, &id, purse)? {
+            Some(balance_uref) => balance_uref,
+            None => return Ok(None),
+        };
+        match self.read(balance_uref)? {
+            some @ Some(_) => Ok(some),
+            None => Err(Error::PurseNotFound),
+        }
+    }
+
+    /// Transfers `amount` of the token `id` from `source` to `target`, both of which must be purses
+    /// of that token.
+    fn token_transfer(
+        &mut self,
+        id: String,
+        source: URef,
+        target: URef,
+        amount: U512,
+    ) -> Result<(), Error> {
+        if !source.is_writeable() || !target.is_addable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        token_supply_uref(self, &id)?;
+        let source_balance = token_balance_uref(self, &id, source)?.ok_or(Error::SourceNotFound)?;
+        let source_value: U512 = self.read(source_balance)?.ok_or(Error::SourceNotFound)?;
+        let source_value = source_value
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientFunds)?;
+        let target_balance = token_balance_uref(self, &id, target)?.ok_or(Error::DestNotFound)?;
+        self.write(source_balance, source_value)?;
+        self.add(target_balance, amount)?;
+        Ok(())
+    }
+
+    /// Returns the total supply of the token `id`.
+    fn token_total_supply(&mut self, id: String) -> Result<U512, Error> {
+        let total_supply_uref = token_supply_uref(self, &id)?;
+        self.read(total_supply_uref)?.ok_or(Error::Storage)
+    }
+
+    fn total_supply(&mut self) -> Result<U512, Error> {
+        match self.get_key(TOTAL_SUPPLY_KEY) {
+            Some(key) => {
+                let total_supply_uref: URef =
+                    TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?;
+                self.read(total_supply_uref)?.ok_or(Error::Storage)
+            }
+            None => Ok(U512::zero()),
+        }
+    }
 }
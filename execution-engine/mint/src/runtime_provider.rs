@@ -1,7 +1,11 @@
-use types::{account::AccountHash, Key};
+use types::{account::AccountHash, BlockTime, Key};
 
 pub trait RuntimeProvider {
     fn get_caller(&self) -> AccountHash;
 
     fn put_key(&mut self, name: &str, key: Key);
+
+    fn get_key(&self, name: &str) -> Option<Key>;
+
+    fn get_block_time(&self) -> BlockTime;
 }
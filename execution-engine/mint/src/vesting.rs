@@ -0,0 +1,181 @@
+use alloc::vec::Vec;
+use core::{convert::TryFrom, time::Duration};
+
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    BlockTime, CLType, CLTyped, U512,
+};
+
+/// A lockup of part of a purse's balance, released linearly over time after an initial cliff.
+///
+/// Until `cliff` has passed since `start`, the whole `locked_amount` is locked.  From then on, the
+/// share of `locked_amount` proportional to the time passed since `start` is released, until all
+/// of it is released once `duration` has passed since `start`.  Durations are in whole
+/// milliseconds, matching [`BlockTime`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VestingSchedule {
+    start: BlockTime,
+    cliff_millis: u64,
+    duration_millis: u64,
+    locked_amount: U512,
+}
+
+impl VestingSchedule {
+    pub fn new(start: BlockTime, cliff: Duration, duration: Duration, locked_amount: U512) -> Self {
+        let to_millis = |duration: Duration| {
+            u64::try_from(duration.as_millis()).unwrap_or_else(|_| u64::max_value())
+        };
+        VestingSchedule {
+            start,
+            cliff_millis: to_millis(cliff),
+            duration_millis: to_millis(duration),
+            locked_amount,
+        }
+    }
+
+    pub fn start(&self) -> BlockTime {
+        self.start
+    }
+
+    pub fn cliff(&self) -> Duration {
+        Duration::from_millis(self.cliff_millis)
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_millis)
+    }
+
+    /// Returns the amount locked before any of it is released.
+    pub fn locked_amount(&self) -> U512 {
+        self.locked_amount
+    }
+
+    /// Returns the amount which is still locked at `time`.
+    pub fn locked_amount_at(&self, time: BlockTime) -> U512 {
+        let elapsed = time.as_millis().saturating_sub(self.start.as_millis());
+        if elapsed < self.cliff_millis {
+            return self.locked_amount;
+        }
+        if elapsed >= self.duration_millis {
+            return U512::zero();
+        }
+        // Split the multiplication so that it can't overflow
+        let duration = U512::from(self.duration_millis);
+        let elapsed = U512::from(elapsed);
+        let released = self.locked_amount / duration * elapsed
+            + self.locked_amount % duration * elapsed / duration;
+        self.locked_amount - released
+    }
+}
+
+impl CLTyped for VestingSchedule {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for VestingSchedule {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut self.start.to_bytes()?);
+        result.append(&mut self.cliff_millis.to_bytes()?);
+        result.append(&mut self.duration_millis.to_bytes()?);
+        result.append(&mut self.locked_amount.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.start.serialized_length()
+            + U64_SERIALIZED_LENGTH
+            + U64_SERIALIZED_LENGTH
+            + self.locked_amount.serialized_length()
+    }
+}
+
+impl FromBytes for VestingSchedule {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (start, bytes) = BlockTime::from_bytes(bytes)?;
+        let (cliff_millis, bytes) = u64::from_bytes(bytes)?;
+        let (duration_millis, bytes) = u64::from_bytes(bytes)?;
+        let (locked_amount, bytes) = U512::from_bytes(bytes)?;
+        let vesting_schedule = VestingSchedule {
+            start,
+            cliff_millis,
+            duration_millis,
+            locked_amount,
+        };
+        Ok((vesting_schedule, bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use types::{bytesrepr, BlockTime, U512};
+
+    use super::VestingSchedule;
+
+    fn schedule() -> VestingSchedule {
+        VestingSchedule::new(
+            BlockTime::new(1_000),
+            Duration::from_millis(100),
+            Duration::from_millis(1_000),
+            U512::from(10_000),
+        )
+    }
+
+    #[test]
+    fn should_lock_everything_until_cliff() {
+        let schedule = schedule();
+        assert_eq!(schedule.locked_amount_at(BlockTime::new(0)), 10_000.into());
+        assert_eq!(
+            schedule.locked_amount_at(BlockTime::new(1_099)),
+            10_000.into()
+        );
+    }
+
+    #[test]
+    fn should_release_linearly_after_cliff() {
+        let schedule = schedule();
+        assert_eq!(
+            schedule.locked_amount_at(BlockTime::new(1_100)),
+            9_000.into()
+        );
+        assert_eq!(
+            schedule.locked_amount_at(BlockTime::new(1_500)),
+            5_000.into()
+        );
+        assert_eq!(schedule.locked_amount_at(BlockTime::new(1_999)), 10.into());
+        assert_eq!(schedule.locked_amount_at(BlockTime::new(2_000)), 0.into());
+        assert_eq!(schedule.locked_amount_at(BlockTime::new(5_000)), 0.into());
+    }
+
+    #[test]
+    fn should_not_overflow() {
+        let schedule = VestingSchedule::new(
+            BlockTime::new(0),
+            Duration::default(),
+            Duration::from_millis(3),
+            U512::MAX,
+        );
+        assert_eq!(
+            schedule.locked_amount_at(BlockTime::new(1)),
+            U512::MAX - U512::MAX / 3
+        );
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let schedule = schedule();
+        let bytes = bytesrepr::serialize(schedule).expect("should serialize");
+        assert_eq!(
+            bytes.len(),
+            bytesrepr::ToBytes::serialized_length(&schedule)
+        );
+        assert_eq!(
+            bytesrepr::deserialize::<VestingSchedule>(bytes),
+            Ok(schedule)
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use alloc::{
+    collections::{btree_map::Iter, BTreeMap},
+    format,
+    string::String,
+    vec::Vec,
+};
+
+use types::{
+    account::AccountHash,
+    system_contract_errors::pos::{Error, Result},
+    U512,
+};
+
+/// The prefix of the named keys under which delegations are recorded.
+const DELEGATION_PREFIX: &str = "d_";
+
+/// The delegations map, assigning the amount of motes each delegator has staked with each
+/// validator. Entries are keyed by `(delegator, validator)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Delegations(pub BTreeMap<(AccountHash, AccountHash), U512>);
+
+impl Delegations {
+    pub fn new(map: BTreeMap<(AccountHash, AccountHash), U512>) -> Delegations {
+        Delegations(map)
+    }
+
+    pub fn iter(&self) -> Iter<(AccountHash, AccountHash), U512> {
+        self.0.iter()
+    }
+
+    /// Parses the delegations from the given named key names, ignoring any name which doesn't
+    /// start with the delegation prefix.
+    ///
+    /// This is the inverse of [`Delegations::strings`].
+    pub fn from_strings<I, S>(names: I) -> Result<Delegations>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut delegations = BTreeMap::new();
+        for name in names {
+            let name = name.as_ref();
+            if !Self::is_delegation_string(name) {
+                continue;
+            }
+            let mut split_name = name[DELEGATION_PREFIX.len()..].split('_');
+            let delegator = split_name
+                .next()
+                .ok_or(Error::DelegationsKeyDeserializationFailed)
+                .and_then(account_hash_from_hex)?;
+            let validator = split_name
+                .next()
+                .ok_or(Error::DelegationsKeyDeserializationFailed)
+                .and_then(account_hash_from_hex)?;
+            let amount = split_name
+                .next()
+                .and_then(|amount| U512::from_dec_str(amount).ok())
+                .ok_or(Error::DelegationsDeserializationFailed)?;
+            delegations.insert((delegator, validator), amount);
+        }
+        Ok(Delegations(delegations))
+    }
+
+    /// Encodes each delegation as a named key name of the form
+    /// `d_{delegator}_{validator}_{amount}`.
+    pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter().map(|((delegator, validator), amount)| {
+            format!(
+                "{}{}_{}_{}",
+                DELEGATION_PREFIX,
+                base16::encode_lower(&delegator.as_bytes()),
+                base16::encode_lower(&validator.as_bytes()),
+                amount
+            )
+        })
+    }
+
+    /// Returns `true` if the given name is a named key name encoding a delegation.
+    pub fn is_delegation_string(name: &str) -> bool {
+        name.starts_with(DELEGATION_PREFIX)
+    }
+
+    /// Returns the amount the delegator has staked with the validator.
+    pub fn delegated(&self, delegator: &AccountHash, validator: &AccountHash) -> U512 {
+        self.0
+            .get(&(*delegator, *validator))
+            .cloned()
+            .unwrap_or_else(U512::zero)
+    }
+
+    /// Returns the total amount delegated to the validator by all delegators.
+    pub fn total_delegated_to(&self, validator: &AccountHash) -> U512 {
+        self.0
+            .iter()
+            .filter(|((_, v), _)| v == validator)
+            .fold(U512::zero(), |sum, (_, amount)| sum.saturating_add(*amount))
+    }
+
+    /// Adds `amount` to the delegator's stake with the validator.
+    pub fn delegate(&mut self, delegator: &AccountHash, validator: &AccountHash, amount: U512) {
+        self.0
+            .entry((*delegator, *validator))
+            .and_modify(|x| *x = x.saturating_add(amount))
+            .or_insert(amount);
+    }
+
+    /// If `maybe_amount` is `None`, removes the delegator's whole stake with the validator,
+    /// otherwise subtracts the given amount.
+    ///
+    /// Returns the amount that was actually subtracted, or an error if
+    /// * the delegator has no delegation to the validator,
+    /// * the specified amount exceeds the delegated amount.
+    pub fn undelegate(
+        &mut self,
+        delegator: &AccountHash,
+        validator: &AccountHash,
+        maybe_amount: Option<U512>,
+    ) -> Result<U512> {
+        let key = (*delegator, *validator);
+        let delegated = *self.0.get(&key).ok_or(Error::NotDelegated)?;
+        let amount = maybe_amount.unwrap_or(delegated);
+        if amount > delegated {
+            return Err(Error::UnbondTooLarge);
+        }
+        if amount == delegated {
+            self.0.remove(&key);
+        } else {
+            self.0.insert(key, delegated - amount);
+        }
+        Ok(amount)
+    }
+
+    /// Reduces every delegation to the validator by `slash_rate` / `denominator` of its amount.
+    ///
+    /// The slashed amounts are rounded up, so the remaining delegations never exceed what is left
+    /// of the validator's stake.  Delegations reduced to nothing are removed.
+    pub fn slash(
+        &mut self,
+        validator: &AccountHash,
+        slash_rate: U512,
+        denominator: U512,
+    ) -> Result<()> {
+        let mut emptied = Vec::new();
+        for (key, amount) in self.0.iter_mut().filter(|((_, v), _)| v == validator) {
+            let slashed = amount
+                .checked_mul(slash_rate)
+                .and_then(|product| product.checked_add(denominator - 1))
+                .ok_or(Error::ArithmeticOverflow)?
+                / denominator;
+            *amount = amount.saturating_sub(slashed);
+            if amount.is_zero() {
+                emptied.push(*key);
+            }
+        }
+        for key in emptied {
+            self.0.remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Removes all delegations to the validator.
+    pub fn remove_validator(&mut self, validator: &AccountHash) {
+        let keys: Vec<_> = self
+            .0
+            .keys()
+            .filter(|(_, v)| v == validator)
+            .cloned()
+            .collect();
+        for key in keys {
+            self.0.remove(&key);
+        }
+    }
+}
+
+fn account_hash_from_hex(hex_key: &str) -> Result<AccountHash> {
+    if hex_key.len() != 64 {
+        return Err(Error::DelegationsKeyDeserializationFailed);
+    }
+    let mut key_bytes = [0u8; 32];
+    let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
+        .map_err(|_| Error::DelegationsKeyDeserializationFailed)?;
+    debug_assert!(_bytes_written == key_bytes.len());
+    Ok(AccountHash::new(key_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+
+    use types::{account::AccountHash, system_contract_errors::pos::Error, U512};
+
+    use super::Delegations;
+
+    const DELEGATOR: AccountHash = AccountHash::new([1; 32]);
+    const VALIDATOR_1: AccountHash = AccountHash::new([2; 32]);
+    const VALIDATOR_2: AccountHash = AccountHash::new([3; 32]);
+
+    #[test]
+    fn test_delegate_and_undelegate() {
+        let mut delegations = Delegations::default();
+        delegations.delegate(&DELEGATOR, &VALIDATOR_1, U512::from(10));
+        delegations.delegate(&DELEGATOR, &VALIDATOR_1, U512::from(5));
+        delegations.delegate(&DELEGATOR, &VALIDATOR_2, U512::from(7));
+        assert_eq!(
+            delegations.delegated(&DELEGATOR, &VALIDATOR_1),
+            U512::from(15)
+        );
+        assert_eq!(delegations.total_delegated_to(&VALIDATOR_2), U512::from(7));
+
+        assert_eq!(
+            Ok(U512::from(4)),
+            delegations.undelegate(&DELEGATOR, &VALIDATOR_1, Some(U512::from(4)))
+        );
+        assert_eq!(
+            delegations.delegated(&DELEGATOR, &VALIDATOR_1),
+            U512::from(11)
+        );
+        assert_eq!(
+            Ok(U512::from(11)),
+            delegations.undelegate(&DELEGATOR, &VALIDATOR_1, None)
+        );
+        assert_eq!(
+            delegations.delegated(&DELEGATOR, &VALIDATOR_1),
+            U512::zero()
+        );
+        assert_eq!(
+            Err(Error::NotDelegated),
+            delegations.undelegate(&DELEGATOR, &VALIDATOR_1, None)
+        );
+    }
+
+    #[test]
+    fn test_slash() {
+        let mut delegations = Delegations::default();
+        delegations.delegate(&DELEGATOR, &VALIDATOR_1, U512::from(15));
+        delegations.delegate(&DELEGATOR, &VALIDATOR_2, U512::from(7));
+
+        // 10% of 15 is 1.5, rounded up to 2.
+        delegations
+            .slash(&VALIDATOR_1, U512::from(100_000), U512::from(1_000_000))
+            .expect("should slash");
+        assert_eq!(
+            delegations.delegated(&DELEGATOR, &VALIDATOR_1),
+            U512::from(13)
+        );
+        assert_eq!(
+            delegations.delegated(&DELEGATOR, &VALIDATOR_2),
+            U512::from(7)
+        );
+
+        delegations
+            .slash(&VALIDATOR_1, U512::from(1_000_000), U512::from(1_000_000))
+            .expect("should slash");
+        assert_eq!(delegations.0.len(), 1);
+
+        delegations.remove_validator(&VALIDATOR_2);
+        assert!(delegations.0.is_empty());
+    }
+
+    #[test]
+    fn test_undelegate_too_much() {
+        let mut delegations = Delegations::default();
+        delegations.delegate(&DELEGATOR, &VALIDATOR_1, U512::from(10));
+        assert_eq!(
+            Err(Error::UnbondTooLarge),
+            delegations.undelegate(&DELEGATOR, &VALIDATOR_1, Some(U512::from(11)))
+        );
+    }
+
+    #[test]
+    fn test_strings_round_trip() {
+        let mut delegations = Delegations::default();
+        delegations.delegate(&DELEGATOR, &VALIDATOR_1, U512::from(10));
+        delegations.delegate(&DELEGATOR, &VALIDATOR_2, U512::from(20));
+
+        let mut names: Vec<_> = delegations.strings().collect();
+        names.push("pos_bonding_purse".into());
+        names.push(format!(
+            "v_{}_100",
+            base16::encode_lower(&VALIDATOR_1.as_bytes())
+        ));
+        assert!(names
+            .iter()
+            .take(2)
+            .all(|name| Delegations::is_delegation_string(name)));
+
+        assert_eq!(Ok(delegations), Delegations::from_strings(names));
+    }
+}
@@ -2,10 +2,14 @@
 
 extern crate alloc;
 
+mod delegations;
+mod delegations_provider;
 mod mint_provider;
 mod queue;
 mod queue_provider;
+mod rewards_provider;
 mod runtime_provider;
+mod slashing_provider;
 mod stakes;
 mod stakes_provider;
 
@@ -14,36 +18,42 @@ use core::marker::Sized;
 use types::{
     account::AccountHash,
     system_contract_errors::pos::{Error, Result},
-    AccessRights, TransferredTo, URef, U512,
+    AccessRights, Motes, URef, U512,
 };
 
 pub use crate::{
+    delegations::Delegations, delegations_provider::DelegationsProvider,
     mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
-    runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
+    rewards_provider::RewardsProvider, runtime_provider::RuntimeProvider,
+    slashing_provider::SlashingProvider, stakes::Stakes, stakes_provider::StakesProvider,
 };
 
 pub trait ProofOfStake:
-    MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
+    DelegationsProvider
+    + MintProvider
+    + QueueProvider
+    + RewardsProvider
+    + RuntimeProvider
+    + SlashingProvider
+    + StakesProvider
+    + Sized
 {
     fn bond(&mut self, validator: AccountHash, amount: U512, source: URef) -> Result<()> {
         if amount.is_zero() {
             return Err(Error::BondTooSmall);
         }
-        let target = internal::get_bonding_purse(self)?;
+        let pos_purse = internal::get_bonding_purse(self)?;
         let timestamp = self.get_block_time();
-        // Transfer `amount` from the `source` purse to PoS internal purse. POS_PURSE is a constant,
-        // it is the URef of the proof-of-stake contract's own purse.
 
-        self.transfer_purse_to_purse(source, target, amount)
+        self.transfer_purse_to_purse(source, pos_purse, amount)
             .map_err(|_| Error::BondTransferFailed)?;
         internal::bond(self, amount, validator, timestamp)?;
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
+        // Process all due requests; the system also does so at every era boundary via `step`.
         let unbonds = internal::step(self, timestamp)?;
         for entry in unbonds {
-            let _: TransferredTo = self
-                .transfer_purse_to_account(source, entry.validator, entry.amount)
-                .map_err(|_| Error::BondTransferFailed)?;
+            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
+                .map_err(|_| Error::UnbondTransferFailed)?;
         }
         Ok(())
     }
@@ -53,7 +63,52 @@ pub trait ProofOfStake:
         let timestamp = self.get_block_time();
         internal::unbond(self, maybe_amount, validator, timestamp)?;
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
+        // Process all due requests; the system also does so at every era boundary via `step`.
+        let unbonds = internal::step(self, timestamp)?;
+        for entry in unbonds {
+            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
+                .map_err(|_| Error::UnbondTransferFailed)?;
+        }
+        Ok(())
+    }
+
+    fn delegate(
+        &mut self,
+        delegator: AccountHash,
+        validator: AccountHash,
+        amount: U512,
+        source: URef,
+    ) -> Result<()> {
+        if amount.is_zero() {
+            return Err(Error::BondTooSmall);
+        }
+        let pos_purse = internal::get_bonding_purse(self)?;
+        let timestamp = self.get_block_time();
+
+        self.transfer_purse_to_purse(source, pos_purse, amount)
+            .map_err(|_| Error::BondTransferFailed)?;
+        internal::delegate(self, amount, delegator, validator, timestamp)?;
+
+        // Process all due requests; the system also does so at every era boundary via `step`.
+        let unbonds = internal::step(self, timestamp)?;
+        for entry in unbonds {
+            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
+                .map_err(|_| Error::UnbondTransferFailed)?;
+        }
+        Ok(())
+    }
+
+    fn undelegate(
+        &mut self,
+        delegator: AccountHash,
+        validator: AccountHash,
+        maybe_amount: Option<U512>,
+    ) -> Result<()> {
+        let pos_purse = internal::get_bonding_purse(self)?;
+        let timestamp = self.get_block_time();
+        internal::undelegate(self, maybe_amount, delegator, validator, timestamp)?;
+
+        // Process all due requests; the system also does so at every era boundary via `step`.
         let unbonds = internal::step(self, timestamp)?;
         for entry in unbonds {
             self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
@@ -80,28 +135,62 @@ pub trait ProofOfStake:
         Ok(maybe_purse.map(|p| p.remove_access_rights()))
     }
 
-    fn finalize_payment(&mut self, amount_spent: U512, account: AccountHash) -> Result<()> {
+    fn finalize_payment(&mut self, amount_spent: Motes, account: AccountHash) -> Result<()> {
         internal::finalize_payment(self, amount_spent, account)
     }
+
+    fn distribute_rewards(&mut self) -> Result<()> {
+        internal::distribute_rewards(self)
+    }
+
+    fn slash(&mut self, validator: AccountHash) -> Result<()> {
+        let pos_purse = internal::get_bonding_purse(self)?;
+        let burned = internal::slash(self, validator)?;
+        if !burned.is_zero() {
+            self.burn(pos_purse, burned)
+                .map_err(|_| Error::SlashBurnFailed)?;
+        }
+        Ok(())
+    }
+
+    fn withdraw_unbonded(&mut self, account: AccountHash) -> Result<()> {
+        let pos_purse = internal::get_bonding_purse(self)?;
+        let timestamp = self.get_block_time();
+        let amount = internal::withdraw_unbonded(self, account, timestamp)?;
+        self.transfer_purse_to_account(pos_purse, account, amount)
+            .map_err(|_| Error::UnbondTransferFailed)?;
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<()> {
+        internal::check_system_call(self)?;
+        let pos_purse = internal::get_bonding_purse(self)?;
+        let timestamp = self.get_block_time();
+        let unbonds = internal::step(self, timestamp)?;
+        for entry in unbonds {
+            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
+                .map_err(|_| Error::UnbondTransferFailed)?;
+        }
+        Ok(())
+    }
 }
 
 mod internal {
     use alloc::vec::Vec;
 
     use types::{
-        account::AccountHash,
+        account::{AccountHash, SYSTEM_ACCOUNT},
         system_contract_errors::pos::{Error, PurseLookupError, Result},
-        BlockTime, Key, Phase, URef, U512,
+        BlockTime, Key, Motes, Phase, URef, U512,
     };
 
     use crate::{
+        delegations::Delegations, delegations_provider::DelegationsProvider,
         mint_provider::MintProvider, queue::QueueEntry, queue_provider::QueueProvider,
-        runtime_provider::RuntimeProvider, stakes_provider::StakesProvider,
+        rewards_provider::RewardsProvider, runtime_provider::RuntimeProvider,
+        slashing_provider::SlashingProvider, stakes::Stakes, stakes_provider::StakesProvider,
     };
 
-    /// Account used to run system functions (in particular `finalize_payment`).
-    const SYSTEM_ACCOUNT: AccountHash = AccountHash::new([0u8; 32]);
-
     /// The uref name where the PoS purse is stored. It contains all staked motes, and all unbonded
     /// motes that are yet to be paid out.
     const BONDING_PURSE_KEY: &str = "pos_bonding_purse";
@@ -119,18 +208,25 @@ mod internal {
     /// The time from a bonding request until the bond becomes effective and part of the stake.
     const BOND_DELAY: u64 = 0;
 
-    /// The time from an unbonding request until the stakes are paid out.
-    const UNBOND_DELAY: u64 = 0;
-
     /// The maximum number of pending bonding requests.
     const MAX_BOND_LEN: usize = 100;
 
     /// The maximum number of pending unbonding requests.
     const MAX_UNBOND_LEN: usize = 1000;
 
+    /// The denominator of the inflation rate, which is given in millionths.
+    const INFLATION_RATE_DENOMINATOR: u64 = 1_000_000;
+
+    /// The denominator of the slash rate, which is given in millionths.
+    const SLASH_RATE_DENOMINATOR: u64 = 1_000_000;
+
     /// Enqueues the deploy's creator for becoming a validator. The bond `amount` is paid from the
     /// purse `source`.
-    pub fn bond<P: QueueProvider + StakesProvider>(
+    ///
+    /// The resulting stake has to lie within the configured minimum and maximum bond. If all
+    /// validator slots are taken, a new validator has to outbid the lowest stake of a validator
+    /// without delegations, which is then evicted once the bond becomes effective.
+    pub fn bond<P: DelegationsProvider + QueueProvider + StakesProvider>(
         provider: &mut P,
         amount: U512,
         validator: AccountHash,
@@ -148,15 +244,55 @@ mod internal {
         }
         stakes.validate_bonding(&validator, amount)?;
 
+        let stake = stakes
+            .0
+            .get(&validator)
+            .cloned()
+            .unwrap_or_else(U512::zero)
+            .saturating_add(amount);
+        if stake < U512::from(provider.read_min_bond()?) {
+            return Err(Error::BondTooSmall);
+        }
+        let max_bond = provider.read_max_bond()?;
+        if max_bond != 0 && stake > U512::from(max_bond) {
+            return Err(Error::BondTooLarge);
+        }
+        let max_validator_slots = provider.read_max_validator_slots()? as usize;
+        if max_validator_slots != 0
+            && !stakes.0.contains_key(&validator)
+            && stakes.0.len() >= max_validator_slots
+        {
+            let delegations = provider.read_delegations()?;
+            match lowest_evictable_stake(&stakes, &delegations) {
+                Some((_, lowest)) if stake > lowest => {}
+                _ => return Err(Error::NoValidatorSlotAvailable),
+            }
+        }
+
         queue.push(validator, amount, timestamp)?;
         provider.write_bonding(queue);
         Ok(())
     }
 
+    /// Returns the validator with the lowest stake which can be evicted to free a validator slot,
+    /// i.e. which has no delegations, and its stake.
+    fn lowest_evictable_stake(
+        stakes: &Stakes,
+        delegations: &Delegations,
+    ) -> Option<(AccountHash, U512)> {
+        stakes
+            .iter()
+            .filter(|(validator, _)| delegations.total_delegated_to(validator).is_zero())
+            .min_by_key(|(_, stake)| **stake)
+            .map(|(validator, stake)| (*validator, *stake))
+    }
+
     /// Enqueues the deploy's creator for unbonding. Their vote weight as a validator is decreased
     /// immediately, but the funds will only be released after a delay. If `maybe_amount` is `None`,
     /// all funds are enqueued for withdrawal, terminating the validator status.
-    pub fn unbond<P: QueueProvider + StakesProvider>(
+    ///
+    /// A validator can't unbond the part of their stake which was delegated to them.
+    pub fn unbond<P: DelegationsProvider + QueueProvider + StakesProvider>(
         provider: &mut P,
         maybe_amount: Option<U512>,
         validator: AccountHash,
@@ -168,6 +304,14 @@ mod internal {
         }
 
         let mut stakes = provider.read()?;
+        let delegated = provider.read_delegations()?.total_delegated_to(&validator);
+        if !delegated.is_zero() {
+            let stake = stakes.0.get(&validator).cloned().ok_or(Error::NotBonded)?;
+            let amount = maybe_amount.ok_or(Error::UnbondTooLarge)?;
+            if stake.saturating_sub(amount) < delegated {
+                return Err(Error::UnbondTooLarge);
+            }
+        }
         let payout = stakes.unbond(&validator, maybe_amount)?;
         provider.write(&stakes);
         // TODO: Make sure the destination is valid and the amount can be paid. The actual payment
@@ -178,21 +322,78 @@ mod internal {
         Ok(())
     }
 
-    /// Removes all due requests from the queues and applies them.
-    pub fn step<P: QueueProvider + StakesProvider>(
+    /// Enqueues a delegation of `amount` from `delegator` to the bonded `validator`. The
+    /// delegation is recorded immediately, while the validator's stake is increased once the
+    /// bonding request becomes due.
+    pub fn delegate<P: DelegationsProvider + QueueProvider + StakesProvider>(
+        provider: &mut P,
+        amount: U512,
+        delegator: AccountHash,
+        validator: AccountHash,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        if !provider.read()?.0.contains_key(&validator) {
+            return Err(Error::NotBonded);
+        }
+        bond(provider, amount, validator, timestamp)?;
+
+        let mut delegations = provider.read_delegations()?;
+        delegations.delegate(&delegator, &validator, amount);
+        provider.write_delegations(&delegations);
+        Ok(())
+    }
+
+    /// Enqueues the delegator's stake with `validator` for withdrawal. The validator's vote
+    /// weight is decreased immediately, and the funds are paid out to the delegator after the
+    /// unbonding delay. If `maybe_amount` is `None`, the whole delegation is withdrawn.
+    pub fn undelegate<P: DelegationsProvider + QueueProvider + StakesProvider>(
+        provider: &mut P,
+        maybe_amount: Option<U512>,
+        delegator: AccountHash,
+        validator: AccountHash,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        let mut queue = provider.read_unbonding();
+        if queue.0.len() >= MAX_UNBOND_LEN {
+            return Err(Error::TooManyEventsInQueue);
+        }
+
+        let mut delegations = provider.read_delegations()?;
+        let payout = delegations.undelegate(&delegator, &validator, maybe_amount)?;
+        // Whatever is still waiting in the bonding queue hasn't been added to the stakes yet, so
+        // cancel that first and only unbond the part that has already been applied.
+        let mut bonding_queue = provider.read_bonding();
+        let cancelled = bonding_queue.reduce(&validator, payout);
+        let mut stakes = provider.read()?;
+        if payout > cancelled {
+            stakes.unbond(&validator, Some(payout - cancelled))?;
+        }
+        // The unbonding queue entry is keyed by the delegator, so that the funds are paid out to
+        // the delegator's account once due.
+        queue.push(delegator, payout, timestamp)?;
+        provider.write(&stakes);
+        provider.write_bonding(bonding_queue);
+        provider.write_delegations(&delegations);
+        provider.write_unbonding(queue);
+        Ok(())
+    }
+
+    /// Removes all due requests from the queues and applies them. If the applied bonds exceed the
+    /// validator slots, the validators with the lowest stakes and no delegations are evicted and
+    /// their stakes enqueued for withdrawal.
+    ///
+    /// Returns the matured unbonding requests, which are to be paid out.
+    pub fn step<P: DelegationsProvider + QueueProvider + StakesProvider>(
         provider: &mut P,
         timestamp: BlockTime,
     ) -> Result<Vec<QueueEntry>> {
+        let unbonding_delay = BlockTime::new(provider.read_unbonding_delay()?);
         let mut bonding_queue = provider.read_bonding();
         let mut unbonding_queue = provider.read_unbonding();
 
         let bonds = bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(BOND_DELAY)));
-        let unbonds =
-            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)));
-
-        if !unbonds.is_empty() {
-            provider.write_unbonding(unbonding_queue);
-        }
+        let unbonds = unbonding_queue.pop_due(timestamp.saturating_sub(unbonding_delay));
+        let mut evicted = false;
 
         if !bonds.is_empty() {
             provider.write_bonding(bonding_queue);
@@ -200,12 +401,63 @@ mod internal {
             for entry in bonds {
                 stakes.bond(&entry.validator, entry.amount);
             }
+            let max_validator_slots = provider.read_max_validator_slots()? as usize;
+            if max_validator_slots != 0 && stakes.0.len() > max_validator_slots {
+                let delegations = provider.read_delegations()?;
+                while stakes.0.len() > max_validator_slots {
+                    let (validator, stake) = match lowest_evictable_stake(&stakes, &delegations) {
+                        Some(lowest) => lowest,
+                        None => break,
+                    };
+                    stakes.0.remove(&validator);
+                    // An evicted validator may have a pending partial unbonding already.
+                    let amount = match unbonding_queue.remove(&validator) {
+                        Some(entry) => entry.amount.saturating_add(stake),
+                        None => stake,
+                    };
+                    unbonding_queue.push(validator, amount, timestamp)?;
+                    evicted = true;
+                }
+            }
             provider.write(&stakes);
         }
 
+        if !unbonds.is_empty() || evicted {
+            provider.write_unbonding(unbonding_queue);
+        }
+
         Ok(unbonds)
     }
 
+    /// Removes the account's unbonding request from the queue if its delay has passed.
+    ///
+    /// Returns the amount to be paid out to the account.
+    pub fn withdraw_unbonded<P: QueueProvider>(
+        provider: &mut P,
+        account: AccountHash,
+        timestamp: BlockTime,
+    ) -> Result<U512> {
+        let unbonding_delay = BlockTime::new(provider.read_unbonding_delay()?);
+        let mut queue = provider.read_unbonding();
+        let entry = queue.remove(&account).ok_or(Error::NoMaturedUnbonding)?;
+        if entry.timestamp > timestamp.saturating_sub(unbonding_delay) {
+            return Err(Error::NoMaturedUnbonding);
+        }
+        provider.write_unbonding(queue);
+        Ok(entry.amount)
+    }
+
+    /// Returns an error unless called by the system account during the system phase.
+    pub fn check_system_call<R: RuntimeProvider>(runtime_provider: &R) -> Result<()> {
+        if runtime_provider.get_phase() != Phase::System {
+            return Err(Error::SystemFunctionCalledOutsideSystemPhase);
+        }
+        if runtime_provider.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+        Ok(())
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse<R: RuntimeProvider>(
         runtime_provider: &R,
@@ -261,9 +513,10 @@ mod internal {
     /// deploy and that the refund purse is unset at the beginning and end of each deploy.
     pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
         provider: &mut P,
-        amount_spent: U512,
+        amount_spent: Motes,
         account: AccountHash,
     ) -> Result<()> {
+        let amount_spent = amount_spent.value();
         let caller = provider.get_caller();
         if caller != SYSTEM_ACCOUNT {
             return Err(Error::SystemFunctionCalledByUserAccount);
@@ -275,10 +528,9 @@ mod internal {
             None => return Err(Error::PaymentPurseBalanceNotFound),
         };
 
-        if total < amount_spent {
-            return Err(Error::InsufficientPaymentForAmountSpent);
-        }
-        let refund_amount = total - amount_spent;
+        let refund_amount = total
+            .checked_sub(amount_spent)
+            .ok_or(Error::InsufficientPaymentForAmountSpent)?;
 
         let rewards_purse = get_rewards_purse(provider)?;
         let refund_purse = get_refund_purse(provider)?;
@@ -310,6 +562,131 @@ mod internal {
         Ok(())
     }
 
+    /// Mints the era rewards and pays them out to the main purses of the bonded validators and
+    /// their delegators. The inflation rate's share of each validator's stake is split between the
+    /// validator and its delegators in proportion to the part of the stake each of them provided,
+    /// with any remainder from rounding going to the validator.
+    ///
+    /// This function can only be called by the system account during the system phase.
+    pub fn distribute_rewards<P>(provider: &mut P) -> Result<()>
+    where
+        P: DelegationsProvider + MintProvider + RewardsProvider + RuntimeProvider + StakesProvider,
+    {
+        check_system_call(provider)?;
+
+        let inflation_rate = U512::from(provider.read_inflation_rate()?);
+        let stakes = provider.read()?;
+        let delegations = provider.read_delegations()?;
+        let mut rewards: Vec<(AccountHash, U512)> = Vec::new();
+        let mut total_rewards = U512::zero();
+        for (validator, stake) in stakes.iter() {
+            let reward = stake
+                .checked_mul(inflation_rate)
+                .ok_or(Error::ArithmeticOverflow)?
+                / INFLATION_RATE_DENOMINATOR;
+            if reward.is_zero() {
+                continue;
+            }
+            total_rewards = total_rewards
+                .checked_add(reward)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            let mut validator_reward = reward;
+            for ((delegator, _), amount) in delegations.iter().filter(|((_, v), _)| v == validator)
+            {
+                let delegator_reward = reward
+                    .checked_mul(*amount)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / *stake;
+                // Delegations never exceed the stake they are part of, so neither do their rewards.
+                validator_reward = validator_reward
+                    .checked_sub(delegator_reward)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                if !delegator_reward.is_zero() {
+                    rewards.push((*delegator, delegator_reward));
+                }
+            }
+            if !validator_reward.is_zero() {
+                rewards.push((*validator, validator_reward));
+            }
+        }
+        if total_rewards.is_zero() {
+            return Ok(());
+        }
+
+        let rewards_purse = provider
+            .mint(total_rewards)
+            .map_err(|_| Error::MintRewardsFailed)?;
+        for (validator, reward) in rewards {
+            provider
+                .transfer_purse_to_account(rewards_purse, validator, reward)
+                .map_err(|_| Error::FailedTransferToValidatorPurse)?;
+        }
+        Ok(())
+    }
+
+    /// Punishes an equivocating validator: the slash rate's share of its stake is slashed, and
+    /// any pending bonding or unbonding request of the validator is evicted from its queue and
+    /// forfeited.  The delegations to the validator are slashed at the same rate.
+    ///
+    /// Returns the amount of motes forfeited, which the caller burns from the bonding purse.
+    ///
+    /// This function can only be called by the system account during the system phase.
+    pub fn slash<P>(provider: &mut P, validator: AccountHash) -> Result<U512>
+    where
+        P: DelegationsProvider
+            + QueueProvider
+            + RuntimeProvider
+            + SlashingProvider
+            + StakesProvider,
+    {
+        check_system_call(provider)?;
+
+        let slash_rate = U512::from(provider.read_slash_rate()?);
+        let mut stakes = provider.read()?;
+        let mut bonding_queue = provider.read_bonding();
+        let mut unbonding_queue = provider.read_unbonding();
+        let evicted = bonding_queue.remove(&validator);
+        let evicted_unbond = unbonding_queue.remove(&validator);
+        let mut burned = evicted
+            .iter()
+            .chain(evicted_unbond.iter())
+            .fold(U512::zero(), |sum, entry| sum.saturating_add(entry.amount));
+
+        match stakes.0.get(&validator).cloned() {
+            Some(stake) => {
+                let amount = stake
+                    .checked_mul(slash_rate)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / SLASH_RATE_DENOMINATOR;
+                stakes.slash(&validator, amount)?;
+                burned = burned.saturating_add(amount);
+
+                let mut delegations = provider.read_delegations()?;
+                if stakes.0.contains_key(&validator) {
+                    delegations.slash(
+                        &validator,
+                        slash_rate,
+                        U512::from(SLASH_RATE_DENOMINATOR),
+                    )?;
+                } else {
+                    delegations.remove_validator(&validator);
+                }
+                provider.write(&stakes);
+                provider.write_delegations(&delegations);
+            }
+            None if evicted.is_none() && evicted_unbond.is_none() => return Err(Error::NotBonded),
+            None => {}
+        }
+        if evicted.is_some() {
+            provider.write_bonding(bonding_queue);
+        }
+        if evicted_unbond.is_some() {
+            provider.write_unbonding(unbonding_queue);
+        }
+        Ok(burned)
+    }
+
     pub fn refund_to_account<M: MintProvider>(
         mint_provider: &mut M,
         payment_purse: URef,
@@ -326,18 +703,28 @@ mod internal {
     mod tests {
         extern crate std;
 
-        use std::{cell::RefCell, iter, thread_local};
+        use std::{cell::RefCell, iter, thread_local, vec::Vec};
 
-        use types::{account::AccountHash, system_contract_errors::pos::Result, BlockTime, U512};
+        use types::{
+            account::{AccountHash, SYSTEM_ACCOUNT},
+            system_contract_errors::pos::{Error, Result},
+            AccessRights, BlockTime, Key, Phase, TransferResult, TransferredTo, URef, U512,
+        };
 
-        use super::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+        use super::{
+            bond, delegate, distribute_rewards, slash, step, unbond, undelegate, withdraw_unbonded,
+            BOND_DELAY,
+        };
         use crate::{
-            queue::Queue, queue_provider::QueueProvider, stakes::Stakes,
-            stakes_provider::StakesProvider,
+            delegations::Delegations, delegations_provider::DelegationsProvider,
+            mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
+            rewards_provider::RewardsProvider, runtime_provider::RuntimeProvider,
+            slashing_provider::SlashingProvider, stakes::Stakes, stakes_provider::StakesProvider,
         };
 
         const KEY1: [u8; 32] = [1; 32];
         const KEY2: [u8; 32] = [2; 32];
+        const KEY3: [u8; 32] = [3; 32];
 
         thread_local! {
             static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
@@ -345,6 +732,24 @@ mod internal {
             static STAKES: RefCell<Stakes> = RefCell::new(
                 Stakes(iter::once((AccountHash::new(KEY1), U512::from(1_000))).collect())
             );
+            static DELEGATIONS: RefCell<Delegations> = RefCell::new(Delegations::default());
+            static PHASE: RefCell<Phase> = RefCell::new(Phase::System);
+            static CALLER: RefCell<AccountHash> = RefCell::new(SYSTEM_ACCOUNT);
+            static MINTED: RefCell<U512> = RefCell::new(U512::zero());
+            static PAYOUTS: RefCell<Vec<(AccountHash, U512)>> = RefCell::new(Vec::new());
+            static MIN_BOND: RefCell<u64> = RefCell::new(0);
+            static MAX_BOND: RefCell<u64> = RefCell::new(0);
+            static MAX_VALIDATOR_SLOTS: RefCell<u32> = RefCell::new(0);
+        }
+
+        const INFLATION_RATE: u64 = 20_000;
+
+        const SLASH_RATE: u64 = 100_000;
+
+        const UNBONDING_DELAY: u64 = 10;
+
+        fn rewards_purse() -> URef {
+            URef::new([9; 32], AccessRights::READ_ADD_WRITE)
         }
 
         struct Provider;
@@ -365,16 +770,111 @@ mod internal {
             fn write_unbonding(&mut self, queue: Queue) {
                 UNBONDING.with(|ub| ub.replace(queue));
             }
+
+            fn read_unbonding_delay(&mut self) -> Result<u64> {
+                Ok(UNBONDING_DELAY)
+            }
         }
 
         impl StakesProvider for Provider {
-            fn read(&self) -> Result<Stakes> {
+            fn read(&mut self) -> Result<Stakes> {
                 STAKES.with(|s| Ok(s.borrow().clone()))
             }
 
             fn write(&mut self, stakes: &Stakes) {
                 STAKES.with(|s| s.replace(stakes.clone()));
             }
+
+            fn read_min_bond(&mut self) -> Result<u64> {
+                MIN_BOND.with(|m| Ok(*m.borrow()))
+            }
+
+            fn read_max_bond(&mut self) -> Result<u64> {
+                MAX_BOND.with(|m| Ok(*m.borrow()))
+            }
+
+            fn read_max_validator_slots(&mut self) -> Result<u32> {
+                MAX_VALIDATOR_SLOTS.with(|m| Ok(*m.borrow()))
+            }
+        }
+
+        impl DelegationsProvider for Provider {
+            fn read_delegations(&self) -> Result<Delegations> {
+                DELEGATIONS.with(|d| Ok(d.borrow().clone()))
+            }
+
+            fn write_delegations(&mut self, delegations: &Delegations) {
+                DELEGATIONS.with(|d| d.replace(delegations.clone()));
+            }
+        }
+
+        impl MintProvider for Provider {
+            fn transfer_purse_to_account(
+                &mut self,
+                source: URef,
+                target: AccountHash,
+                amount: U512,
+            ) -> TransferResult {
+                assert_eq!(source, rewards_purse());
+                PAYOUTS.with(|p| p.borrow_mut().push((target, amount)));
+                Ok(TransferredTo::ExistingAccount)
+            }
+
+            fn transfer_purse_to_purse(
+                &mut self,
+                _source: URef,
+                _target: URef,
+                _amount: U512,
+            ) -> core::result::Result<(), ()> {
+                Ok(())
+            }
+
+            fn balance(&mut self, _purse: URef) -> Option<U512> {
+                None
+            }
+
+            fn mint(&mut self, amount: U512) -> core::result::Result<URef, ()> {
+                MINTED.with(|m| *m.borrow_mut() += amount);
+                Ok(rewards_purse())
+            }
+
+            fn burn(&mut self, _purse: URef, _amount: U512) -> core::result::Result<(), ()> {
+                Ok(())
+            }
+        }
+
+        impl RewardsProvider for Provider {
+            fn read_inflation_rate(&mut self) -> Result<u64> {
+                Ok(INFLATION_RATE)
+            }
+        }
+
+        impl SlashingProvider for Provider {
+            fn read_slash_rate(&mut self) -> Result<u64> {
+                Ok(SLASH_RATE)
+            }
+        }
+
+        impl RuntimeProvider for Provider {
+            fn get_key(&self, _name: &str) -> Option<Key> {
+                None
+            }
+
+            fn put_key(&mut self, _name: &str, _key: Key) {}
+
+            fn remove_key(&mut self, _name: &str) {}
+
+            fn get_phase(&self) -> Phase {
+                PHASE.with(|p| *p.borrow())
+            }
+
+            fn get_block_time(&self) -> BlockTime {
+                BlockTime::new(0)
+            }
+
+            fn get_caller(&self) -> AccountHash {
+                CALLER.with(|c| *c.borrow())
+            }
         }
 
         fn assert_stakes(stakes: &[([u8; 32], usize)]) {
@@ -415,8 +915,361 @@ mod internal {
 
             // Unbonding becomes effective immediately.
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
-            step::<Provider>(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
+            step::<Provider>(&mut provider, BlockTime::new(2 + UNBONDING_DELAY)).expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
+
+        #[test]
+        fn test_delegate_step_undelegate() {
+            let mut provider = Provider;
+            let delegator = AccountHash::new(KEY3);
+            let validator = AccountHash::new(KEY1);
+
+            delegate(
+                &mut provider,
+                U512::from(300),
+                delegator,
+                AccountHash::new(KEY2),
+                BlockTime::new(1),
+            )
+            .expect_err("should not delegate to unbonded validator");
+
+            delegate(
+                &mut provider,
+                U512::from(300),
+                delegator,
+                validator,
+                BlockTime::new(1),
+            )
+            .expect("delegate to validator 1");
+            step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+            assert_stakes(&[(KEY1, 1_300)]);
+
+            // The validator can't unbond the delegated stake.
+            unbond(
+                &mut provider,
+                Some(U512::from(1_001)),
+                validator,
+                BlockTime::new(2),
+            )
+            .expect_err("should not unbond delegated stake");
+
+            undelegate(
+                &mut provider,
+                Some(U512::from(100)),
+                delegator,
+                validator,
+                BlockTime::new(2),
+            )
+            .expect("partly undelegate");
+            assert_stakes(&[(KEY1, 1_200)]);
+            assert_eq!(
+                Provider
+                    .read_delegations()
+                    .unwrap()
+                    .delegated(&delegator, &validator),
+                U512::from(200)
+            );
+
+            let unbonds = step(&mut provider, BlockTime::new(2 + UNBONDING_DELAY)).expect("step 2");
+            assert_eq!(unbonds.len(), 1);
+            assert_eq!(unbonds[0].validator, delegator);
+            assert_eq!(unbonds[0].amount, U512::from(100));
+        }
+
+        #[test]
+        fn test_undelegate_before_step() {
+            let mut provider = Provider;
+            let delegator = AccountHash::new(KEY3);
+            let validator = AccountHash::new(KEY1);
+
+            delegate(
+                &mut provider,
+                U512::from(300),
+                delegator,
+                validator,
+                BlockTime::new(1),
+            )
+            .expect("delegate to validator 1");
+
+            // The queued bond is cancelled, and only the remainder is taken from the stakes.
+            undelegate(
+                &mut provider,
+                Some(U512::from(100)),
+                delegator,
+                validator,
+                BlockTime::new(1),
+            )
+            .expect("partly undelegate the queued bond");
+            assert_stakes(&[(KEY1, 1_000)]);
+            let bonds = Provider.read_bonding().0;
+            assert_eq!(bonds.len(), 1);
+            assert_eq!(bonds[0].validator, validator);
+            assert_eq!(bonds[0].amount, U512::from(200));
+
+            undelegate(&mut provider, None, delegator, validator, BlockTime::new(1))
+                .expect("fully undelegate");
+            assert_stakes(&[(KEY1, 1_000)]);
+            assert!(Provider.read_bonding().0.is_empty());
+
+            step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step");
+            assert_stakes(&[(KEY1, 1_000)]);
+        }
+
+        #[test]
+        fn test_distribute_rewards() {
+            let mut provider = Provider;
+            STAKES.with(|s| {
+                s.borrow_mut()
+                    .bond(&AccountHash::new(KEY2), U512::from(500))
+            });
+
+            distribute_rewards(&mut provider).expect("should distribute rewards");
+
+            // 2% of each validator's stake is minted and paid out.
+            MINTED.with(|m| assert_eq!(*m.borrow(), U512::from(30)));
+            PAYOUTS.with(|p| {
+                assert_eq!(
+                    *p.borrow(),
+                    [
+                        (AccountHash::new(KEY1), U512::from(20)),
+                        (AccountHash::new(KEY2), U512::from(10)),
+                    ]
+                    .to_vec()
+                )
+            });
+        }
+
+        #[test]
+        fn test_distribute_rewards_to_delegators() {
+            let mut provider = Provider;
+            let validator = AccountHash::new(KEY1);
+            DELEGATIONS.with(|d| {
+                let mut delegations = d.borrow_mut();
+                delegations.delegate(&AccountHash::new(KEY2), &validator, U512::from(400));
+                delegations.delegate(&AccountHash::new(KEY3), &validator, U512::from(25));
+            });
+
+            distribute_rewards(&mut provider).expect("should distribute rewards");
+
+            // The validator's reward of 20 is split in proportion to the delegated amounts, and the
+            // validator keeps the rest, including what's left over from rounding.
+            MINTED.with(|m| assert_eq!(*m.borrow(), U512::from(20)));
+            PAYOUTS.with(|p| {
+                assert_eq!(
+                    *p.borrow(),
+                    [
+                        (AccountHash::new(KEY2), U512::from(8)),
+                        (validator, U512::from(12)),
+                    ]
+                    .to_vec()
+                )
+            });
+        }
+
+        #[test]
+        fn test_distribute_rewards_outside_system_phase() {
+            let mut provider = Provider;
+
+            PHASE.with(|p| p.replace(Phase::Session));
+            assert_eq!(
+                distribute_rewards(&mut provider),
+                Err(Error::SystemFunctionCalledOutsideSystemPhase)
+            );
+
+            PHASE.with(|p| p.replace(Phase::System));
+            CALLER.with(|c| c.replace(AccountHash::new(KEY1)));
+            assert_eq!(
+                distribute_rewards(&mut provider),
+                Err(Error::SystemFunctionCalledByUserAccount)
+            );
+            MINTED.with(|m| assert!(m.borrow().is_zero()));
+        }
+
+        #[test]
+        fn test_slash() {
+            let mut provider = Provider;
+            let validator = AccountHash::new(KEY2);
+            STAKES.with(|s| s.borrow_mut().bond(&validator, U512::from(500)));
+            DELEGATIONS.with(|d| {
+                d.borrow_mut()
+                    .delegate(&AccountHash::new(KEY3), &validator, U512::from(200))
+            });
+            bond(&mut provider, U512::from(100), validator, BlockTime::new(1))
+                .expect("bond validator 2");
+            unbond(
+                &mut provider,
+                Some(U512::from(20)),
+                validator,
+                BlockTime::new(1),
+            )
+            .expect("partly unbond validator 2");
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 480)]);
+
+            let burned = slash(&mut provider, validator).expect("should slash validator 2");
+
+            // 10% of the stake is burned and the pending bond and unbond are forfeited.
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 432)]);
+            assert_eq!(Provider.read_bonding(), Queue::default());
+            assert_eq!(Provider.read_unbonding(), Queue::default());
+            assert_eq!(burned, U512::from(168));
+            // The delegation to the validator is slashed at the same rate.
+            assert_eq!(
+                Provider
+                    .read_delegations()
+                    .unwrap()
+                    .delegated(&AccountHash::new(KEY3), &validator),
+                U512::from(180)
+            );
+
+            slash(&mut provider, AccountHash::new(KEY3)).expect_err("should not slash unbonded");
+
+            CALLER.with(|c| c.replace(AccountHash::new(KEY1)));
+            assert_eq!(
+                slash(&mut provider, validator),
+                Err(Error::SystemFunctionCalledByUserAccount)
+            );
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 432)]);
+        }
+
+        #[test]
+        fn test_unbond_withdraw_after_delay() {
+            let mut provider = Provider;
+            let validator = AccountHash::new(KEY2);
+            STAKES.with(|s| s.borrow_mut().bond(&validator, U512::from(500)));
+
+            unbond(
+                &mut provider,
+                Some(U512::from(200)),
+                validator,
+                BlockTime::new(5),
+            )
+            .expect("partly unbond validator 2");
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 300)]);
+
+            // The funds can't be withdrawn before the delay has passed.
+            assert_eq!(
+                withdraw_unbonded(
+                    &mut provider,
+                    validator,
+                    BlockTime::new(4 + UNBONDING_DELAY)
+                ),
+                Err(Error::NoMaturedUnbonding)
+            );
+            assert!(step(&mut provider, BlockTime::new(4 + UNBONDING_DELAY))
+                .expect("step 1")
+                .is_empty());
+
+            assert_eq!(
+                withdraw_unbonded(
+                    &mut provider,
+                    validator,
+                    BlockTime::new(5 + UNBONDING_DELAY)
+                ),
+                Ok(U512::from(200))
+            );
+            assert_eq!(
+                withdraw_unbonded(
+                    &mut provider,
+                    validator,
+                    BlockTime::new(5 + UNBONDING_DELAY)
+                ),
+                Err(Error::NoMaturedUnbonding)
+            );
+        }
+
+        #[test]
+        fn test_bond_limits() {
+            let mut provider = Provider;
+            let validator = AccountHash::new(KEY2);
+            MIN_BOND.with(|m| m.replace(100));
+            MAX_BOND.with(|m| m.replace(500));
+
+            assert_eq!(
+                bond(&mut provider, U512::from(99), validator, BlockTime::new(1)),
+                Err(Error::BondTooSmall)
+            );
+            assert_eq!(
+                bond(&mut provider, U512::from(501), validator, BlockTime::new(1)),
+                Err(Error::BondTooLarge)
+            );
+            bond(&mut provider, U512::from(100), validator, BlockTime::new(1))
+                .expect("bond the minimum");
+            step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 100)]);
+
+            // The maximum applies to the resulting stake, not to the bonded amount.
+            assert_eq!(
+                bond(&mut provider, U512::from(401), validator, BlockTime::new(2)),
+                Err(Error::BondTooLarge)
+            );
+            bond(&mut provider, U512::from(400), validator, BlockTime::new(2))
+                .expect("bond up to the maximum");
+        }
+
+        #[test]
+        fn test_bond_validator_slots() {
+            let mut provider = Provider;
+            let validator_2 = AccountHash::new(KEY2);
+            let validator_3 = AccountHash::new(KEY3);
+            MAX_VALIDATOR_SLOTS.with(|m| m.replace(2));
+            STAKES.with(|s| s.borrow_mut().bond(&validator_2, U512::from(500)));
+
+            // All slots are taken, so a new validator has to outbid the lowest stake.
+            assert_eq!(
+                bond(
+                    &mut provider,
+                    U512::from(500),
+                    validator_3,
+                    BlockTime::new(1)
+                ),
+                Err(Error::NoValidatorSlotAvailable)
+            );
+            bond(
+                &mut provider,
+                U512::from(501),
+                validator_3,
+                BlockTime::new(1),
+            )
+            .expect("outbid validator 2");
+
+            // The lowest validator is evicted once the bond becomes effective.
+            let unbonds = step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+            assert!(unbonds.is_empty());
+            assert_stakes(&[(KEY1, 1_000), (KEY3, 501)]);
+            let unbonds = step(
+                &mut provider,
+                BlockTime::new(1 + BOND_DELAY + UNBONDING_DELAY),
+            )
+            .expect("step 2");
+            assert_eq!(unbonds.len(), 1);
+            assert_eq!(unbonds[0].validator, validator_2);
+            assert_eq!(unbonds[0].amount, U512::from(500));
+        }
+
+        #[test]
+        fn test_bond_validator_slots_keeps_delegated_validators() {
+            let mut provider = Provider;
+            let validator_2 = AccountHash::new(KEY2);
+            MAX_VALIDATOR_SLOTS.with(|m| m.replace(1));
+            DELEGATIONS.with(|d| {
+                d.borrow_mut().delegate(
+                    &AccountHash::new(KEY3),
+                    &AccountHash::new(KEY1),
+                    U512::from(1),
+                )
+            });
+
+            // The only validator has delegations, so it can't be evicted.
+            assert_eq!(
+                bond(
+                    &mut provider,
+                    U512::from(2_000),
+                    validator_2,
+                    BlockTime::new(1)
+                ),
+                Err(Error::NoValidatorSlotAvailable)
+            );
+        }
     }
 }
@@ -16,4 +16,10 @@ pub trait MintProvider {
     ) -> Result<(), ()>;
 
     fn balance(&mut self, purse: URef) -> Option<U512>;
+
+    /// Mints a new purse holding `amount` of new motes.
+    fn mint(&mut self, amount: U512) -> Result<URef, ()>;
+
+    /// Destroys `amount` of the motes held in `purse`.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), ()>;
 }
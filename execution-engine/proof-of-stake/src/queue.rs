@@ -11,7 +11,7 @@ use types::{
 /// A pending entry in the bonding or unbonding queue.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct QueueEntry {
-    /// The validator who is bonding or unbonding.
+    /// The validator who is bonding or unbonding, or the delegator who is undelegating.
     pub validator: AccountHash,
     /// The amount by which to change the stakes.
     pub amount: U512,
@@ -92,6 +92,36 @@ impl Queue {
         Ok(())
     }
 
+    /// Removes and returns the validator's pending request, if any.
+    pub fn remove(&mut self, validator: &AccountHash) -> Option<QueueEntry> {
+        let index = self
+            .0
+            .iter()
+            .position(|entry| entry.validator == *validator)?;
+        Some(self.0.remove(index))
+    }
+
+    /// Reduces the validator's pending request by up to `amount`, removing it if nothing is left.
+    ///
+    /// Returns the amount by which the request was reduced.
+    pub fn reduce(&mut self, validator: &AccountHash, amount: U512) -> U512 {
+        let index = match self
+            .0
+            .iter()
+            .position(|entry| entry.validator == *validator)
+        {
+            Some(index) => index,
+            None => return U512::zero(),
+        };
+        let entry = &mut self.0[index];
+        let reduced = amount.min(entry.amount);
+        entry.amount -= reduced;
+        if entry.amount.is_zero() {
+            self.0.remove(index);
+        }
+        reduced
+    }
+
     /// Returns all queue entries at least as old as the specified timestamp.
     pub fn pop_due(&mut self, timestamp: BlockTime) -> Vec<QueueEntry> {
         let (older_than, rest) = self
@@ -191,6 +221,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remove() {
+        let val1 = AccountHash::new(KEY1);
+        let val2 = AccountHash::new(KEY2);
+        let mut queue: Queue = Default::default();
+        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
+        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
+        assert_eq!(
+            Some(QueueEntry::new(val1, U512::from(5), BlockTime::new(100))),
+            queue.remove(&val1)
+        );
+        assert_eq!(None, queue.remove(&val1));
+        assert_eq!(
+            vec![QueueEntry::new(val2, U512::from(6), BlockTime::new(101))],
+            queue.0
+        );
+    }
+
+    #[test]
+    fn test_reduce() {
+        let val1 = AccountHash::new(KEY1);
+        let val2 = AccountHash::new(KEY2);
+        let val3 = AccountHash::new(KEY3);
+        let mut queue: Queue = Default::default();
+        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
+        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
+        assert_eq!(U512::from(2), queue.reduce(&val1, U512::from(2)));
+        assert_eq!(U512::from(6), queue.reduce(&val2, U512::from(10)));
+        assert_eq!(U512::zero(), queue.reduce(&val3, U512::from(1)));
+        assert_eq!(
+            vec![QueueEntry::new(val1, U512::from(3), BlockTime::new(100))],
+            queue.0
+        );
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let val1 = AccountHash::new(KEY1);
@@ -1,4 +1,4 @@
-use crate::queue::Queue;
+use crate::{queue::Queue, Result};
 
 pub trait QueueProvider {
     /// Reads bonding queue.
@@ -12,4 +12,8 @@ pub trait QueueProvider {
 
     /// Writes unbonding queue.
     fn write_unbonding(&mut self, queue: Queue);
+
+    /// Reads the time an unbonding request has to wait in the queue before its funds can be
+    /// withdrawn.
+    fn read_unbonding_delay(&mut self) -> Result<u64>;
 }
@@ -0,0 +1,7 @@
+use crate::Result;
+
+/// A `RewardsProvider` that reads the reward parameters from the contract's known urefs.
+pub trait RewardsProvider {
+    /// Reads the inflation rate, in millionths of the bonded stakes, minted as rewards per era.
+    fn read_inflation_rate(&mut self) -> Result<u64>;
+}
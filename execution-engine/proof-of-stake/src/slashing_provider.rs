@@ -0,0 +1,7 @@
+use crate::Result;
+
+/// A `SlashingProvider` that reads the slashing parameters from the contract's known urefs.
+pub trait SlashingProvider {
+    /// Reads the slash rate, in millionths of a validator's stake, burned when it is slashed.
+    fn read_slash_rate(&mut self) -> Result<u64>;
+}
@@ -1,10 +1,6 @@
-use alloc::{
-    collections::{
-        btree_map::{Iter, Values},
-        BTreeMap,
-    },
-    format,
-    string::String,
+use alloc::collections::{
+    btree_map::{Iter, Values},
+    BTreeMap,
 };
 
 use types::{
@@ -48,16 +44,9 @@ impl Stakes {
         self.0.values()
     }
 
-    pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
-        self.iter().map(|(account_hash, balance)| {
-            let key_bytes = account_hash.as_bytes();
-            let hex_key = base16::encode_lower(&key_bytes);
-            format!("v_{}_{}", hex_key, balance)
-        })
-    }
-
     pub fn total_bonds(&self) -> U512 {
-        self.values().fold(U512::zero(), |x, y| x + y)
+        self.values()
+            .fold(U512::zero(), |sum, s| sum.saturating_add(*s))
     }
 
     /// If `maybe_amount` is `None`, removes all the validator's stakes,
@@ -74,7 +63,8 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let max_decrease = MAX_DECREASE.min(self.sum() * MAX_REL_DECREASE / 1_000_000);
+        let max_decrease =
+            MAX_DECREASE.min(self.sum().saturating_mul(MAX_REL_DECREASE.into()) / 1_000_000);
 
         if let Some(amount) = maybe_amount {
             // The minimum stake value to not violate the maximum spread.
@@ -109,11 +99,29 @@ impl Stakes {
         Ok(stake)
     }
 
+    /// Subtracts `amount` from the validator's stakes, bypassing the limits that apply to
+    /// unbonding. If nothing remains, the validator is removed.
+    ///
+    /// Returns an error if the validator was not bonded, or if it is the last validator and would
+    /// be removed.
+    pub fn slash(&mut self, validator: &AccountHash, amount: U512) -> Result<()> {
+        let stake = self.0.get_mut(validator).ok_or(Error::NotBonded)?;
+        if *stake > amount {
+            *stake -= amount;
+            return Ok(());
+        }
+        if self.0.len() == 1 {
+            return Err(Error::CannotUnbondLastValidator);
+        }
+        self.0.remove(validator);
+        Ok(())
+    }
+
     /// Adds `amount` to the validator's stakes.
     pub fn bond(&mut self, validator: &AccountHash, amount: U512) {
         self.0
             .entry(*validator)
-            .and_modify(|x| *x += amount)
+            .and_modify(|x| *x = x.saturating_add(amount))
             .or_insert(amount);
     }
 
@@ -127,11 +135,15 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let stake = self.0.get(validator).map(|s| *s + amount).unwrap_or(amount);
+        let stake = match self.0.get(validator) {
+            Some(stake) => stake.checked_add(amount).ok_or(Error::BondTooLarge)?,
+            None => amount,
+        };
         if stake > max || stake < min {
             return Err(Error::SpreadTooHigh);
         }
-        let max_increase = MAX_INCREASE.min(self.sum() * MAX_REL_INCREASE / 1_000_000);
+        let max_increase =
+            MAX_INCREASE.min(self.sum().saturating_mul(MAX_REL_INCREASE.into()) / 1_000_000);
         if (stake.is_zero() && amount > min.saturating_add(max_increase))
             || (!stake.is_zero() && amount > max_increase)
         {
@@ -230,6 +242,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bond_overflow() {
+        let stakes = new_stakes(&[(KEY1, 1_000), (KEY2, 1_000)]);
+        assert_eq!(
+            Err(Error::BondTooLarge),
+            stakes.validate_bonding(&AccountHash::new(KEY1), U512::MAX),
+        );
+    }
+
     #[test]
     fn test_unbond() {
         let mut stakes = new_stakes(&[(KEY1, 5), (KEY2, 100)]);
@@ -249,6 +270,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_slash() {
+        let mut stakes = new_stakes(&[(KEY1, 50), (KEY2, 100)]);
+        assert_eq!(
+            Ok(()),
+            stakes.slash(&AccountHash::new(KEY2), U512::from(99))
+        );
+        assert_eq!(new_stakes(&[(KEY1, 50), (KEY2, 1)]), stakes);
+        assert_eq!(
+            Ok(()),
+            stakes.slash(&AccountHash::new(KEY1), U512::from(50))
+        );
+        assert_eq!(new_stakes(&[(KEY2, 1)]), stakes);
+        assert_eq!(
+            Err(Error::CannotUnbondLastValidator),
+            stakes.slash(&AccountHash::new(KEY2), U512::from(1))
+        );
+        assert_eq!(
+            Err(Error::NotBonded),
+            stakes.slash(&AccountHash::new(KEY1), U512::from(1))
+        );
+    }
+
     #[test]
     fn test_partially_unbond() {
         let mut stakes = new_stakes(&[(KEY1, 50)]);
@@ -2,7 +2,21 @@ use crate::{stakes::Stakes, Result};
 
 /// A `StakesProvider` that reads and writes the stakes to/from the contract's known urefs.
 pub trait StakesProvider {
-    fn read(&self) -> Result<Stakes>;
+    fn read(&mut self) -> Result<Stakes>;
 
     fn write(&mut self, stakes: &Stakes);
+
+    /// Reads the minimum stake, in motes, a validator has to hold after bonding.
+    ///
+    /// The bond limits are only installed at genesis, so networks upgraded from before they were
+    /// introduced don't have them.  Providers return zero, i.e. no limit, for a missing limit.
+    fn read_min_bond(&mut self) -> Result<u64>;
+
+    /// Reads the maximum stake, in motes, a validator may hold after bonding. Zero means there is
+    /// no maximum.
+    fn read_max_bond(&mut self) -> Result<u64>;
+
+    /// Reads the maximum number of validators. Zero means there is no limit, in which case
+    /// `run_era_end` doesn't evict any validators.
+    fn read_max_validator_slots(&mut self) -> Result<u32>;
 }
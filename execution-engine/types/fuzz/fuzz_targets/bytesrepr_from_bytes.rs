@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use types::bytesrepr::fuzz;
+
+fuzz_target!(|data: &[u8]| fuzz::from_arbitrary_bytes(data));
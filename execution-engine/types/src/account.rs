@@ -152,6 +152,11 @@ pub const ACCOUNT_HASH_SERIALIZED_LENGTH: usize = 32;
 /// A type alias for the raw bytes of an Account Hash.
 pub type AccountHashBytes = [u8; ACCOUNT_HASH_LENGTH];
 
+/// The reserved account which runs system functions such as genesis, upgrades, payment
+/// finalization and slashing.  Only it may call the privileged entry points of the system
+/// contracts and the privileged host functions.
+pub const SYSTEM_ACCOUNT: AccountHash = AccountHash::new([0u8; ACCOUNT_HASH_LENGTH]);
+
 /// A newtype wrapping a [`AccountHashBytes`] which is the raw bytes of
 /// the AccountHash, a hash of Public Key and Algorithm
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
@@ -335,6 +340,37 @@ impl TryFrom<i32> for UpdateKeyFailure {
     }
 }
 
+/// Errors that can occur while replacing an account's main purse.
+#[derive(PartialEq, Eq, Fail, Debug, Copy, Clone)]
+#[repr(i32)]
+pub enum SetMainPurseFailure {
+    /// Caller doesn't have sufficient permissions to replace the main purse of the given account.
+    #[fail(display = "Unable to set main purse due to insufficient permissions")]
+    PermissionDenied = 1,
+    /// The given `URef` isn't a purse with read, add and write access rights in the
+    /// current context.
+    #[fail(display = "Unable to set main purse to an invalid purse")]
+    InvalidPurse = 2,
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<i32> for SetMainPurseFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == SetMainPurseFailure::PermissionDenied as i32 => {
+                Ok(SetMainPurseFailure::PermissionDenied)
+            }
+            d if d == SetMainPurseFailure::InvalidPurse as i32 => {
+                Ok(SetMainPurseFailure::InvalidPurse)
+            }
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{convert::TryFrom, vec::Vec};
@@ -403,4 +439,15 @@ mod tests {
                    `UpdateKeyFailure`, or `max_valid_value_for_variant` in this test?"
         );
     }
+
+    #[test]
+    fn try_from_i32_for_set_main_purse_failure() {
+        let max_valid_value_for_variant = SetMainPurseFailure::InvalidPurse as i32;
+        assert_eq!(
+            Err(TryFromIntError(())),
+            SetMainPurseFailure::try_from(max_valid_value_for_variant + 1),
+            "Did you forget to update `SetMainPurseFailure::try_from` for a new variant of \
+                   `SetMainPurseFailure`, or `max_valid_value_for_variant` in this test?"
+        );
+    }
 }
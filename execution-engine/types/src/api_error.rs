@@ -7,7 +7,7 @@ use core::{
 
 use crate::{
     account::{
-        AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, TryFromIntError,
+        AddKeyFailure, RemoveKeyFailure, SetMainPurseFailure, SetThresholdFailure, TryFromIntError,
         TryFromSliceForAccountHashError, UpdateKeyFailure,
     },
     bytesrepr, contracts,
@@ -225,6 +225,18 @@ const HEADER_ERROR_MAX: u32 = HEADER_ERROR_OFFSET + u8::MAX as u32;
 /// # show_and_check!(
 /// 65_031 => MintError::PurseNotFound
 /// # );
+/// # show_and_check!(
+/// 65_032 => MintError::FundsLocked
+/// # );
+/// # show_and_check!(
+/// 65_033 => MintError::InvalidCaller
+/// # );
+/// # show_and_check!(
+/// 65_034 => MintError::TokenAlreadyExists
+/// # );
+/// # show_and_check!(
+/// 65_035 => MintError::TokenNotFound
+/// # );
 ///
 /// // Proof of stake errors:
 /// use casperlabs_types::system_contract_errors::pos::Error as PosError;
@@ -309,6 +321,72 @@ const HEADER_ERROR_MAX: u32 = HEADER_ERROR_OFFSET + u8::MAX as u32;
 /// # show_and_check!(
 /// 65_306 => PosError::SetRefundPurseCalledOutsidePayment
 /// # );
+/// # show_and_check!(
+/// 65_307 => PosError::NotDelegated
+/// # );
+/// # show_and_check!(
+/// 65_308 => PosError::DelegationsKeyDeserializationFailed
+/// # );
+/// # show_and_check!(
+/// 65_309 => PosError::DelegationsDeserializationFailed
+/// # );
+/// # show_and_check!(
+/// 65_310 => PosError::SystemFunctionCalledOutsideSystemPhase
+/// # );
+/// # show_and_check!(
+/// 65_311 => PosError::InflationRateNotFound
+/// # );
+/// # show_and_check!(
+/// 65_312 => PosError::InflationRateKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_313 => PosError::MintRewardsFailed
+/// # );
+/// # show_and_check!(
+/// 65_314 => PosError::FailedTransferToValidatorPurse
+/// # );
+/// # show_and_check!(
+/// 65_315 => PosError::SlashRateNotFound
+/// # );
+/// # show_and_check!(
+/// 65_316 => PosError::SlashRateKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_317 => PosError::UnbondingDelayNotFound
+/// # );
+/// # show_and_check!(
+/// 65_318 => PosError::UnbondingDelayKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_319 => PosError::NoMaturedUnbonding
+/// # );
+/// # show_and_check!(
+/// 65_320 => PosError::ArithmeticOverflow
+/// # );
+/// # show_and_check!(
+/// 65_321 => PosError::MinBondNotFound
+/// # );
+/// # show_and_check!(
+/// 65_322 => PosError::MinBondKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_323 => PosError::MaxBondNotFound
+/// # );
+/// # show_and_check!(
+/// 65_324 => PosError::MaxBondKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_325 => PosError::ValidatorSlotsNotFound
+/// # );
+/// # show_and_check!(
+/// 65_326 => PosError::ValidatorSlotsKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_327 => PosError::NoValidatorSlotAvailable
+/// # );
+/// # show_and_check!(
+/// 65_328 => PosError::SlashBurnFailed
+/// # );
 ///
 /// // User-defined errors:
 /// # show_and_check!(
@@ -478,6 +556,15 @@ impl From<RemoveKeyFailure> for ApiError {
     }
 }
 
+impl From<SetMainPurseFailure> for ApiError {
+    fn from(error: SetMainPurseFailure) -> Self {
+        match error {
+            SetMainPurseFailure::PermissionDenied => ApiError::PermissionDenied,
+            SetMainPurseFailure::InvalidPurse => ApiError::InvalidPurse,
+        }
+    }
+}
+
 impl From<SetThresholdFailure> for ApiError {
     fn from(error: SetThresholdFailure) -> Self {
         match error {
@@ -1,12 +1,23 @@
 use alloc::vec::Vec;
+use core::{
+    convert::TryFrom,
+    ops::{Add, Sub},
+    time::Duration,
+};
 
 use crate::bytesrepr::{Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH};
 
 /// The number of bytes in a serialized [`BlockTime`].
 pub const BLOCKTIME_SERIALIZED_LENGTH: usize = U64_SERIALIZED_LENGTH;
 
-/// A newtype wrapping a [`u64`] which represents the block time.
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd)]
+/// A newtype wrapping a [`u64`] which represents the block time, in milliseconds since the Unix
+/// epoch.
+///
+/// A [`Duration`] can be added to or subtracted from a `BlockTime`, and subtracting one
+/// `BlockTime` from another yields the [`Duration`] between them.  Durations are truncated to
+/// whole milliseconds.  As for the integer types, the operators panic on overflow; use the
+/// `checked_*` or `saturating_*` methods where that is a concern.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BlockTime(u64);
 
 impl BlockTime {
@@ -15,11 +26,90 @@ impl BlockTime {
         BlockTime(value)
     }
 
+    /// Constructs a `BlockTime` from milliseconds since the Unix epoch.
+    pub fn from_millis(millis: u64) -> Self {
+        BlockTime(millis)
+    }
+
+    /// Returns the number of milliseconds since the Unix epoch.
+    pub fn as_millis(self) -> u64 {
+        self.0
+    }
+
     /// Saturating integer subtraction. Computes `self - other`, saturating at `0` instead of
     /// overflowing.
     pub fn saturating_sub(self, other: BlockTime) -> Self {
         BlockTime(self.0.saturating_sub(other.0))
     }
+
+    /// Checked addition. Returns `None` if the result would overflow.
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        let millis = u64::try_from(duration.as_millis()).ok()?;
+        self.0.checked_add(millis).map(BlockTime)
+    }
+
+    /// Checked subtraction. Returns `None` if the result would be before the Unix epoch.
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        let millis = u64::try_from(duration.as_millis()).ok()?;
+        self.0.checked_sub(millis).map(BlockTime)
+    }
+
+    /// Saturating addition. Computes `self + duration`, saturating at the maximum `BlockTime`
+    /// instead of overflowing.
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        self.checked_add(duration)
+            .unwrap_or_else(|| BlockTime(u64::max_value()))
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or `None` if `earlier` is later than
+    /// `self`.
+    pub fn checked_duration_since(self, earlier: BlockTime) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration::from_millis)
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or a zero duration if `earlier` is later
+    /// than `self`.
+    pub fn saturating_duration_since(self, earlier: BlockTime) -> Duration {
+        Duration::from_millis(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Returns `true` if at least `duration` has passed from `start` to `self`.
+    pub fn has_elapsed(self, start: BlockTime, duration: Duration) -> bool {
+        self.saturating_duration_since(start) >= duration
+    }
+}
+
+impl Add<Duration> for BlockTime {
+    type Output = BlockTime;
+
+    fn add(self, duration: Duration) -> BlockTime {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to block time")
+    }
+}
+
+impl Sub<Duration> for BlockTime {
+    type Output = BlockTime;
+
+    fn sub(self, duration: Duration) -> BlockTime {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from block time")
+    }
+}
+
+impl Sub<BlockTime> for BlockTime {
+    type Output = Duration;
+
+    fn sub(self, earlier: BlockTime) -> Duration {
+        self.checked_duration_since(earlier)
+            .expect("overflow when subtracting block times")
+    }
+}
+
+impl From<u64> for BlockTime {
+    fn from(millis: u64) -> Self {
+        BlockTime(millis)
+    }
 }
 
 impl Into<u64> for BlockTime {
@@ -44,3 +134,60 @@ impl FromBytes for BlockTime {
         Ok((BlockTime::new(time), rem))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::BlockTime;
+
+    #[test]
+    fn should_add_and_subtract_durations() {
+        let time = BlockTime::from_millis(10_000);
+        assert_eq!(
+            time + Duration::from_secs(2),
+            BlockTime::from_millis(12_000)
+        );
+        assert_eq!(time - Duration::from_secs(2), BlockTime::from_millis(8_000));
+        assert_eq!(
+            BlockTime::from_millis(12_500) - time,
+            Duration::from_millis(2_500)
+        );
+        // Sub-millisecond parts are truncated
+        assert_eq!(
+            time + Duration::from_micros(1_999),
+            BlockTime::from_millis(10_001)
+        );
+    }
+
+    #[test]
+    fn should_not_overflow_checked_and_saturating() {
+        let max = BlockTime::from_millis(u64::max_value());
+        assert_eq!(max.checked_add(Duration::from_millis(1)), None);
+        assert_eq!(max.saturating_add(Duration::from_secs(1)), max);
+        assert_eq!(
+            BlockTime::default().checked_sub(Duration::from_millis(1)),
+            None
+        );
+        assert_eq!(BlockTime::default().checked_duration_since(max), None);
+        assert_eq!(
+            BlockTime::default().saturating_duration_since(max),
+            Duration::default()
+        );
+    }
+
+    #[test]
+    fn should_check_elapsed() {
+        let start = BlockTime::from_millis(1_000);
+        let period = Duration::from_secs(1);
+        assert!(!BlockTime::from_millis(1_999).has_elapsed(start, period));
+        assert!(BlockTime::from_millis(2_000).has_elapsed(start, period));
+        assert!(!BlockTime::from_millis(0).has_elapsed(start, period));
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_on_overflow() {
+        let _ = BlockTime::default() - Duration::from_millis(1);
+    }
+}
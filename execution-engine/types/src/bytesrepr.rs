@@ -18,6 +18,9 @@ use core::ptr::NonNull;
 
 use failure::Fail;
 
+#[cfg(any(feature = "fuzzing", test))]
+pub mod fuzz;
+
 /// The number of bytes in a serialized `()`.
 pub const UNIT_SERIALIZED_LENGTH: usize = 0;
 /// The number of bytes in a serialized `bool`.
@@ -123,11 +126,37 @@ pub fn deserialize<T: FromBytes>(bytes: Vec<u8>) -> Result<T, Error> {
     }
 }
 
+/// Deserializes `bytes` into an instance of `T` without taking ownership of them.
+///
+/// Returns an error if the bytes cannot be deserialized into `T` or if not all of the input bytes
+/// are consumed in the operation.
+pub fn deserialize_from_slice<T: FromBytes>(bytes: &[u8]) -> Result<T, Error> {
+    let (t, remainder) = T::from_bytes(bytes)?;
+    if remainder.is_empty() {
+        Ok(t)
+    } else {
+        Err(Error::LeftOverBytes)
+    }
+}
+
 /// Serializes `t` into a `Vec<u8>`.
 pub fn serialize(t: impl ToBytes) -> Result<Vec<u8>, Error> {
     t.into_bytes()
 }
 
+/// Deserializes an instance of `T` which was appended to a type's original serialized layout, or
+/// returns `default` if `bytes` is empty, as it is for values serialized before `T` was appended.
+///
+/// Only the values of types which are never followed by other data in their serialized form, such
+/// as those stored directly in global state, can be extended this way.
+pub fn from_trailing_bytes<T: FromBytes>(bytes: &[u8], default: T) -> Result<(T, &[u8]), Error> {
+    if bytes.is_empty() {
+        Ok((default, bytes))
+    } else {
+        T::from_bytes(bytes)
+    }
+}
+
 pub(crate) fn safe_split_at(bytes: &[u8], n: usize) -> Result<(&[u8], &[u8]), Error> {
     if n > bytes.len() {
         Err(Error::EarlyEndOfStream)
@@ -907,6 +936,21 @@ mod tests {
         // Assert the `INSTANCE_COUNT` has dropped to zero again.
         INSTANCE_COUNT.with(|count| assert_eq!(0, *count.borrow()));
     }
+
+    #[test]
+    fn deserialize_from_slice_should_match_deserialize() {
+        let bytes = String::from("hello").to_bytes().unwrap();
+        let from_slice: String = deserialize_from_slice(&bytes).unwrap();
+        let from_vec: String = deserialize(bytes.clone()).unwrap();
+        assert_eq!(from_slice, from_vec);
+
+        let mut too_many_bytes = bytes;
+        too_many_bytes.push(0);
+        assert_eq!(
+            deserialize_from_slice::<String>(&too_many_bytes),
+            Err(Error::LeftOverBytes)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,479 @@
+//! Entry points for fuzzing the [`bytesrepr`](super) encoding, e.g. from `cargo fuzz` targets.
+//!
+//! [`round_trip`] treats its input as a source of [`Entropy`] from which it builds an arbitrary
+//! [`CLValue`], then checks the value survives serialization.  [`from_arbitrary_bytes`] feeds its
+//! input directly to the deserializers of many types, and checks they reject it cleanly or accept
+//! it consistently.
+//!
+//! [`round_trip_corpus`] and [`malformed_corpus`] provide seed inputs for the two entry points.
+
+// Can be removed once https://github.com/rust-lang/rustfmt/issues/3362 is resolved.
+#[rustfmt::skip]
+use alloc::vec;
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::{fmt::Debug, iter};
+
+use super::{FromBytes, ToBytes};
+use crate::{
+    account::AccountHash,
+    contracts::{Contract, ContractPackage, EntryPoints},
+    AccessRights, BlockTime, CLType, CLTyped, CLValue, CallStackElement, Key, NamedArg, Phase,
+    ProtocolVersion, RuntimeArgs, SemVer, URef, U128, U256, U512,
+};
+
+/// The deepest nesting of the `CLType`s generated by [`arbitrary_cl_type`].
+const MAX_CL_TYPE_DEPTH: usize = 4;
+/// The number of `CLType` variants other than `Any` which don't contain another `CLType`.
+const SIMPLE_CL_TYPE_COUNT: u8 = 13;
+/// The number of `CLType` variants other than `Any`.
+const CL_TYPE_COUNT: u8 = 21;
+/// The greatest number of entries in a generated string, list or map.
+const MAX_COLLECTION_LENGTH: u8 = 8;
+/// The greatest length of a generated `CLType::FixedList`.
+const MAX_FIXED_LIST_LENGTH: u8 = 4;
+
+const OPTION_NONE_TAG: u8 = 0;
+const OPTION_SOME_TAG: u8 = 1;
+const RESULT_ERR_TAG: u8 = 0;
+const RESULT_OK_TAG: u8 = 1;
+
+/// A source of arbitrary choices, drawn from the bytes given by a fuzzer.
+///
+/// Once the bytes are exhausted, every choice is zero, so any input yields a finite value.
+pub struct Entropy<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Entropy<'a> {
+    /// Creates an `Entropy` drawing from `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Entropy { bytes }
+    }
+
+    /// Takes the next byte.
+    pub fn u8(&mut self) -> u8 {
+        match self.bytes.split_first() {
+            Some((byte, remainder)) => {
+                self.bytes = remainder;
+                *byte
+            }
+            None => 0,
+        }
+    }
+
+    /// Takes the next `count` bytes.
+    pub fn bytes(&mut self, count: usize) -> Vec<u8> {
+        (0..count).map(|_| self.u8()).collect()
+    }
+
+    fn below(&mut self, bound: u8) -> u8 {
+        self.u8() % bound
+    }
+
+    fn flag(&mut self) -> bool {
+        self.u8() % 2 == 1
+    }
+
+    fn array_32(&mut self) -> [u8; 32] {
+        let mut array = [0; 32];
+        array.iter_mut().for_each(|byte| *byte = self.u8());
+        array
+    }
+}
+
+/// Returns an arbitrary `CLType` other than `CLType::Any`, nested at most four deep.
+pub fn arbitrary_cl_type(entropy: &mut Entropy) -> CLType {
+    cl_type_within_depth(entropy, MAX_CL_TYPE_DEPTH)
+}
+
+fn cl_type_within_depth(entropy: &mut Entropy, depth: usize) -> CLType {
+    let variant_count = if depth == 0 {
+        SIMPLE_CL_TYPE_COUNT
+    } else {
+        CL_TYPE_COUNT
+    };
+    match entropy.below(variant_count) {
+        0 => CLType::Bool,
+        1 => CLType::I32,
+        2 => CLType::I64,
+        3 => CLType::U8,
+        4 => CLType::U32,
+        5 => CLType::U64,
+        6 => CLType::U128,
+        7 => CLType::U256,
+        8 => CLType::U512,
+        9 => CLType::Unit,
+        10 => CLType::String,
+        11 => CLType::Key,
+        12 => CLType::URef,
+        13 => CLType::Option(inner_cl_type(entropy, depth)),
+        14 => CLType::List(inner_cl_type(entropy, depth)),
+        15 => {
+            let inner = inner_cl_type(entropy, depth);
+            let length = entropy.below(MAX_FIXED_LIST_LENGTH + 1);
+            CLType::FixedList(inner, u32::from(length))
+        }
+        16 => CLType::Result {
+            ok: inner_cl_type(entropy, depth),
+            err: inner_cl_type(entropy, depth),
+        },
+        17 => CLType::Map {
+            key: inner_cl_type(entropy, depth),
+            value: inner_cl_type(entropy, depth),
+        },
+        18 => CLType::Tuple1([inner_cl_type(entropy, depth)]),
+        19 => CLType::Tuple2([inner_cl_type(entropy, depth), inner_cl_type(entropy, depth)]),
+        20 => CLType::Tuple3([
+            inner_cl_type(entropy, depth),
+            inner_cl_type(entropy, depth),
+            inner_cl_type(entropy, depth),
+        ]),
+        _ => unreachable!("variant should be below {}", CL_TYPE_COUNT),
+    }
+}
+
+fn inner_cl_type(entropy: &mut Entropy, depth: usize) -> Box<CLType> {
+    Box::new(cl_type_within_depth(entropy, depth - 1))
+}
+
+/// Returns an arbitrary `CLValue` of a type given by [`arbitrary_cl_type`].
+pub fn arbitrary_cl_value(entropy: &mut Entropy) -> CLValue {
+    let cl_type = arbitrary_cl_type(entropy);
+    let mut bytes = Vec::new();
+    append_arbitrary_value(&cl_type, entropy, &mut bytes);
+    CLValue::from_components(cl_type, bytes)
+}
+
+fn append_arbitrary_value(cl_type: &CLType, entropy: &mut Entropy, stream: &mut Vec<u8>) {
+    match cl_type {
+        CLType::Bool => append(&entropy.flag(), stream),
+        CLType::I32 | CLType::U32 => stream.extend(entropy.bytes(4)),
+        CLType::I64 | CLType::U64 => stream.extend(entropy.bytes(8)),
+        CLType::U8 => stream.push(entropy.u8()),
+        CLType::U128 => append(&U128::from_little_endian(&entropy.bytes(16)), stream),
+        CLType::U256 => append(&U256::from_little_endian(&entropy.bytes(32)), stream),
+        CLType::U512 => append(&U512::from_little_endian(&entropy.bytes(64)), stream),
+        CLType::Unit => (),
+        CLType::String => append(&arbitrary_string(entropy), stream),
+        CLType::Key => append(&arbitrary_key(entropy), stream),
+        CLType::URef => append(&arbitrary_uref(entropy), stream),
+        CLType::Option(inner) => {
+            if entropy.flag() {
+                stream.push(OPTION_SOME_TAG);
+                append_arbitrary_value(inner, entropy, stream);
+            } else {
+                stream.push(OPTION_NONE_TAG);
+            }
+        }
+        CLType::List(inner) => {
+            let length = entropy.below(MAX_COLLECTION_LENGTH + 1);
+            append(&u32::from(length), stream);
+            for _ in 0..length {
+                append_arbitrary_value(inner, entropy, stream);
+            }
+        }
+        CLType::FixedList(inner, length) => {
+            for _ in 0..*length {
+                append_arbitrary_value(inner, entropy, stream);
+            }
+        }
+        CLType::Result { ok, err } => {
+            if entropy.flag() {
+                stream.push(RESULT_OK_TAG);
+                append_arbitrary_value(ok, entropy, stream);
+            } else {
+                stream.push(RESULT_ERR_TAG);
+                append_arbitrary_value(err, entropy, stream);
+            }
+        }
+        CLType::Map { key, value } => {
+            let length = entropy.below(MAX_COLLECTION_LENGTH + 1);
+            append(&u32::from(length), stream);
+            for _ in 0..length {
+                append_arbitrary_value(key, entropy, stream);
+                append_arbitrary_value(value, entropy, stream);
+            }
+        }
+        CLType::Tuple1(inner_types) => append_arbitrary_values(inner_types, entropy, stream),
+        CLType::Tuple2(inner_types) => append_arbitrary_values(inner_types, entropy, stream),
+        CLType::Tuple3(inner_types) => append_arbitrary_values(inner_types, entropy, stream),
+        CLType::Any => panic!("should not generate values of CLType::Any"),
+    }
+}
+
+fn append_arbitrary_values(cl_types: &[Box<CLType>], entropy: &mut Entropy, stream: &mut Vec<u8>) {
+    for cl_type in cl_types {
+        append_arbitrary_value(cl_type, entropy, stream);
+    }
+}
+
+fn append<T: ToBytes>(value: &T, stream: &mut Vec<u8>) {
+    stream.extend(value.to_bytes().expect("should serialize arbitrary value"));
+}
+
+fn arbitrary_string(entropy: &mut Entropy) -> String {
+    let length = entropy.below(MAX_COLLECTION_LENGTH + 1);
+    String::from_utf8_lossy(&entropy.bytes(usize::from(length))).into_owned()
+}
+
+fn arbitrary_uref(entropy: &mut Entropy) -> URef {
+    let address = entropy.array_32();
+    URef::new(address, AccessRights::from_bits_truncate(entropy.u8()))
+}
+
+fn arbitrary_key(entropy: &mut Entropy) -> Key {
+    match entropy.below(7) {
+        0 => Key::Account(AccountHash::new(entropy.array_32())),
+        1 => Key::Hash(entropy.array_32()),
+        2 => Key::URef(arbitrary_uref(entropy)),
+        3 => Key::SystemRegistry,
+        4 => Key::Dictionary(entropy.array_32()),
+        5 => Key::ExecutedDeploy(entropy.array_32()),
+        _ => Key::Migration(entropy.array_32()),
+    }
+}
+
+/// Builds an arbitrary `CLValue` from `data` and checks that it serializes to
+/// `serialized_length()` bytes which deserialize to an equal `CLValue`.
+///
+/// Values of types with a Rust equivalent are also converted with `CLValue::into_t` and checked to
+/// re-serialize to the same bytes.
+///
+/// Panics if any check fails.
+pub fn round_trip(data: &[u8]) {
+    let cl_value = arbitrary_cl_value(&mut Entropy::new(data));
+
+    let serialized = cl_value.to_bytes().expect("should serialize CLValue");
+    assert_eq!(serialized.len(), cl_value.serialized_length());
+    let (parsed, remainder) = CLValue::from_bytes(&serialized).expect("should parse CLValue");
+    assert!(
+        remainder.is_empty(),
+        "should consume all of {:?}",
+        serialized
+    );
+    assert_eq!(parsed, cl_value);
+
+    match cl_value.cl_type() {
+        CLType::Bool => check_into_t::<bool>(&cl_value),
+        CLType::I32 => check_into_t::<i32>(&cl_value),
+        CLType::I64 => check_into_t::<i64>(&cl_value),
+        CLType::U8 => check_into_t::<u8>(&cl_value),
+        CLType::U32 => check_into_t::<u32>(&cl_value),
+        CLType::U64 => check_into_t::<u64>(&cl_value),
+        CLType::U128 => check_into_t::<U128>(&cl_value),
+        CLType::U256 => check_into_t::<U256>(&cl_value),
+        CLType::U512 => check_into_t::<U512>(&cl_value),
+        CLType::Unit => check_into_t::<()>(&cl_value),
+        CLType::String => check_into_t::<String>(&cl_value),
+        CLType::Key => check_into_t::<Key>(&cl_value),
+        CLType::URef => check_into_t::<URef>(&cl_value),
+        _ => (),
+    }
+}
+
+fn check_into_t<T: CLTyped + FromBytes + ToBytes>(cl_value: &CLValue) {
+    let value: T = cl_value.clone().into_t().expect("should convert CLValue");
+    let serialized = value.to_bytes().expect("should serialize converted value");
+    assert_eq!(&serialized, cl_value.inner_bytes());
+}
+
+/// Parses `data` as each of a range of serializable types.
+///
+/// Parsing must not panic.  Where it succeeds, the parsed value must serialize to
+/// `serialized_length()` bytes which parse to an equal value.
+///
+/// Panics if any check fails.
+pub fn from_arbitrary_bytes(data: &[u8]) {
+    check_from_bytes::<bool>(data);
+    check_from_bytes::<u8>(data);
+    check_from_bytes::<i32>(data);
+    check_from_bytes::<i64>(data);
+    check_from_bytes::<u32>(data);
+    check_from_bytes::<u64>(data);
+    check_from_bytes::<U128>(data);
+    check_from_bytes::<U256>(data);
+    check_from_bytes::<U512>(data);
+    check_from_bytes::<()>(data);
+    check_from_bytes::<String>(data);
+    check_from_bytes::<Vec<u8>>(data);
+    check_from_bytes::<Vec<u64>>(data);
+    check_from_bytes::<Vec<String>>(data);
+    check_from_bytes::<[u8; 32]>(data);
+    check_from_bytes::<Option<Key>>(data);
+    check_from_bytes::<Result<String, u32>>(data);
+    check_from_bytes::<BTreeMap<String, Key>>(data);
+    check_from_bytes::<(u8, String, Key)>(data);
+    check_from_bytes::<AccessRights>(data);
+    check_from_bytes::<URef>(data);
+    check_from_bytes::<Key>(data);
+    check_from_bytes::<AccountHash>(data);
+    check_from_bytes::<CLType>(data);
+    check_from_bytes::<CLValue>(data);
+    check_from_bytes::<NamedArg>(data);
+    check_from_bytes::<RuntimeArgs>(data);
+    check_from_bytes::<BlockTime>(data);
+    check_from_bytes::<Phase>(data);
+    check_from_bytes::<CallStackElement>(data);
+    check_from_bytes::<SemVer>(data);
+    check_from_bytes::<ProtocolVersion>(data);
+    check_from_bytes::<EntryPoints>(data);
+    check_from_bytes::<Contract>(data);
+    check_from_bytes::<ContractPackage>(data);
+}
+
+fn check_from_bytes<T: FromBytes + ToBytes + PartialEq + Debug>(data: &[u8]) {
+    if let Ok((value, _remainder)) = T::from_bytes(data) {
+        let serialized = value.to_bytes().expect("should serialize parsed value");
+        assert_eq!(serialized.len(), value.serialized_length());
+        let (reparsed, remainder) = T::from_bytes(&serialized).expect("should reparse value");
+        assert!(
+            remainder.is_empty(),
+            "should consume all of {:?}",
+            serialized
+        );
+        assert_eq!(reparsed, value);
+    }
+}
+
+/// Returns seed inputs for [`round_trip`], which between them start with every choice of
+/// top-level `CLType`.
+pub fn round_trip_corpus() -> Vec<Vec<u8>> {
+    (0..CL_TYPE_COUNT)
+        .flat_map(|variant| {
+            vec![
+                iter::once(variant)
+                    .chain(iter::repeat(0x5a).take(256))
+                    .collect(),
+                iter::once(variant)
+                    .chain((0..=u8::max_value()).cycle().take(1024))
+                    .collect(),
+            ]
+        })
+        .collect()
+}
+
+/// Returns seed inputs for [`from_arbitrary_bytes`] which are all or mostly invalid: truncated
+/// values, huge length prefixes, unknown tags, invalid UTF-8 and deeply nested `CLType`s.
+pub fn malformed_corpus() -> Vec<Vec<u8>> {
+    let uref = URef::new([7; 32], AccessRights::READ_ADD_WRITE);
+    let valid = vec![
+        to_bytes(&CLValue::from_t(u64::max_value()).expect("should create CLValue")),
+        to_bytes(&CLValue::from_t(String::from("bytesrepr")).expect("should create CLValue")),
+        to_bytes(&CLValue::from_t(Key::URef(uref)).expect("should create CLValue")),
+        to_bytes(&CLValue::from_t(U512::max_value()).expect("should create CLValue")),
+        to_bytes(&CLValue::from_t(Some(Key::Hash([3; 32]))).expect("should create CLValue")),
+        to_bytes(
+            &CLValue::from_t(vec![String::from("a"), String::from("bc")])
+                .expect("should create CLValue"),
+        ),
+        to_bytes(&RuntimeArgs::default()),
+    ];
+
+    // Every proper prefix of each valid value.
+    let mut corpus: Vec<Vec<u8>> = valid
+        .iter()
+        .flat_map(|bytes| (0..bytes.len()).map(move |length| bytes[..length].to_vec()))
+        .collect();
+
+    // A length prefix of `u32::MAX` with too few bytes following.
+    let mut huge_length = to_bytes(&u32::max_value());
+    huge_length.extend(&[0; 8]);
+    corpus.push(huge_length.clone());
+    let mut huge_cl_value = huge_length;
+    huge_cl_value.push(0);
+    corpus.push(huge_cl_value);
+
+    // Tags which no type uses.
+    corpus.push(vec![2]);
+    corpus.push(vec![u8::max_value()]);
+    corpus.push(
+        iter::once(u8::max_value())
+            .chain(iter::repeat(0).take(33))
+            .collect(),
+    );
+
+    // A string of invalid UTF-8.
+    corpus.push(vec![2, 0, 0, 0, 0xc3, 0x28]);
+
+    // A `U512` claiming more than 64 bytes.
+    corpus.push(iter::once(65).chain(iter::repeat(1).take(65)).collect());
+
+    // A `URef` with unknown access rights bits.
+    corpus.push(iter::repeat(0).take(32).chain(iter::once(0xf8)).collect());
+
+    // Deeply nested `CLType`s, both complete and missing their innermost type.
+    let mut nested = CLType::Bool;
+    for _ in 0..64 {
+        nested = CLType::List(Box::new(CLType::Option(Box::new(nested))));
+    }
+    let mut nested_bytes = Vec::new();
+    nested.append_bytes(&mut nested_bytes);
+    corpus.push(nested_bytes.clone());
+    nested_bytes.pop();
+    corpus.push(nested_bytes);
+
+    corpus
+}
+
+fn to_bytes<T: ToBytes>(value: &T) -> Vec<u8> {
+    value.to_bytes().expect("should serialize corpus value")
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn should_round_trip_corpus() {
+        for input in round_trip_corpus() {
+            round_trip(&input);
+        }
+    }
+
+    #[test]
+    fn should_generate_every_cl_type() {
+        let tags: Vec<u8> = round_trip_corpus()
+            .iter()
+            .map(|input| {
+                let mut bytes = Vec::new();
+                arbitrary_cl_type(&mut Entropy::new(input)).append_bytes(&mut bytes);
+                bytes[0]
+            })
+            .collect();
+        for tag in 0..CL_TYPE_COUNT {
+            assert!(
+                tags.contains(&tag),
+                "should generate CLType with tag {}",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_malformed_corpus_without_panicking() {
+        for input in malformed_corpus() {
+            from_arbitrary_bytes(&input);
+        }
+    }
+
+    #[test]
+    fn should_treat_exhausted_entropy_as_zeros() {
+        let mut entropy = Entropy::new(&[1, 2]);
+        assert_eq!(entropy.bytes(4), vec![1, 2, 0, 0]);
+        assert_eq!(entropy.u8(), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn should_round_trip_arbitrary_input(data in vec(any::<u8>(), 0..512)) {
+            round_trip(&data);
+        }
+
+        #[test]
+        fn should_parse_arbitrary_input_without_panicking(data in vec(any::<u8>(), 0..512)) {
+            from_arbitrary_bytes(&data);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use alloc::vec::Vec;
+
+use crate::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, ContractHash, ContractPackageHash,
+};
+
+const SESSION_TAG: u8 = 0;
+const STORED_SESSION_TAG: u8 = 1;
+const STORED_CONTRACT_TAG: u8 = 2;
+
+/// An element of the call stack of an execution, identifying the code running at that depth.
+///
+/// The first element is the code invoked by the deploy and the last one is the currently running
+/// code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallStackElement {
+    /// Session code deployed as module bytes, run by the given account.
+    Session {
+        /// The account running the session code.
+        account_hash: AccountHash,
+    },
+    /// Stored session code, run in the context of the given account.
+    StoredSession {
+        /// The account in whose context the session code runs.
+        account_hash: AccountHash,
+        /// The package of the stored session code.
+        contract_package_hash: ContractPackageHash,
+        /// The stored session code.
+        contract_hash: ContractHash,
+    },
+    /// A stored contract, run in its own context.
+    StoredContract {
+        /// The package of the contract.
+        contract_package_hash: ContractPackageHash,
+        /// The contract.
+        contract_hash: ContractHash,
+    },
+}
+
+impl CallStackElement {
+    /// Creates a [`CallStackElement::Session`].
+    pub fn session(account_hash: AccountHash) -> Self {
+        CallStackElement::Session { account_hash }
+    }
+
+    /// Creates a [`CallStackElement::StoredSession`].
+    pub fn stored_session(
+        account_hash: AccountHash,
+        contract_package_hash: ContractPackageHash,
+        contract_hash: ContractHash,
+    ) -> Self {
+        CallStackElement::StoredSession {
+            account_hash,
+            contract_package_hash,
+            contract_hash,
+        }
+    }
+
+    /// Creates a [`CallStackElement::StoredContract`].
+    pub fn stored_contract(
+        contract_package_hash: ContractPackageHash,
+        contract_hash: ContractHash,
+    ) -> Self {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            contract_hash,
+        }
+    }
+
+    /// Returns the hash of the stored contract or stored session code, or `None` for session code
+    /// deployed as module bytes.
+    pub fn contract_hash(&self) -> Option<&ContractHash> {
+        match self {
+            CallStackElement::Session { .. } => None,
+            CallStackElement::StoredSession { contract_hash, .. }
+            | CallStackElement::StoredContract { contract_hash, .. } => Some(contract_hash),
+        }
+    }
+
+    /// Returns the account in whose context session code runs, or `None` for a stored contract.
+    pub fn account_hash(&self) -> Option<&AccountHash> {
+        match self {
+            CallStackElement::Session { account_hash }
+            | CallStackElement::StoredSession { account_hash, .. } => Some(account_hash),
+            CallStackElement::StoredContract { .. } => None,
+        }
+    }
+}
+
+impl ToBytes for CallStackElement {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            CallStackElement::Session { account_hash } => {
+                result.push(SESSION_TAG);
+                result.append(&mut account_hash.to_bytes()?);
+            }
+            CallStackElement::StoredSession {
+                account_hash,
+                contract_package_hash,
+                contract_hash,
+            } => {
+                result.push(STORED_SESSION_TAG);
+                result.append(&mut account_hash.to_bytes()?);
+                result.append(&mut contract_package_hash.to_bytes()?);
+                result.append(&mut contract_hash.to_bytes()?);
+            }
+            CallStackElement::StoredContract {
+                contract_package_hash,
+                contract_hash,
+            } => {
+                result.push(STORED_CONTRACT_TAG);
+                result.append(&mut contract_package_hash.to_bytes()?);
+                result.append(&mut contract_hash.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                CallStackElement::Session { account_hash } => account_hash.serialized_length(),
+                CallStackElement::StoredSession {
+                    account_hash,
+                    contract_package_hash,
+                    contract_hash,
+                } => {
+                    account_hash.serialized_length()
+                        + contract_package_hash.serialized_length()
+                        + contract_hash.serialized_length()
+                }
+                CallStackElement::StoredContract {
+                    contract_package_hash,
+                    contract_hash,
+                } => contract_package_hash.serialized_length() + contract_hash.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for CallStackElement {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            SESSION_TAG => {
+                let (account_hash, remainder) = AccountHash::from_bytes(remainder)?;
+                Ok((CallStackElement::session(account_hash), remainder))
+            }
+            STORED_SESSION_TAG => {
+                let (account_hash, remainder) = AccountHash::from_bytes(remainder)?;
+                let (contract_package_hash, remainder) =
+                    ContractPackageHash::from_bytes(remainder)?;
+                let (contract_hash, remainder) = ContractHash::from_bytes(remainder)?;
+                Ok((
+                    CallStackElement::stored_session(
+                        account_hash,
+                        contract_package_hash,
+                        contract_hash,
+                    ),
+                    remainder,
+                ))
+            }
+            STORED_CONTRACT_TAG => {
+                let (contract_package_hash, remainder) =
+                    ContractPackageHash::from_bytes(remainder)?;
+                let (contract_hash, remainder) = ContractHash::from_bytes(remainder)?;
+                Ok((
+                    CallStackElement::stored_contract(contract_package_hash, contract_hash),
+                    remainder,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl CLTyped for CallStackElement {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{bytesrepr, gens};
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize(call_stack_element in gens::call_stack_element_arb()) {
+            bytesrepr::test_serialization_roundtrip(&call_stack_element);
+        }
+    }
+}
@@ -651,6 +651,29 @@ mod tests {
         round_trip(&x);
     }
 
+    #[test]
+    fn nested_option_should_work() {
+        let x: Option<Option<u64>> = Some(Some(1));
+        let y: Option<Option<u64>> = Some(None);
+        let z: Option<Option<u64>> = None;
+
+        round_trip(&x);
+        round_trip(&y);
+        round_trip(&z);
+    }
+
+    #[test]
+    fn nested_composites_should_work() {
+        let x: Result<Option<(u8, String, Vec<u64>)>, (i32,)> =
+            Ok(Some((1, String::from("a"), vec![1, 2, 3])));
+        let y: Result<Option<(u8, String, Vec<u64>)>, (i32,)> = Ok(None);
+        let z: Result<Option<(u8, String, Vec<u64>)>, (i32,)> = Err((-1,));
+
+        round_trip(&x);
+        round_trip(&y);
+        round_trip(&z);
+    }
+
     #[test]
     fn any_should_work() {
         #[derive(PartialEq, Debug, Clone)]
@@ -457,6 +457,9 @@ impl From<Vec<EntryPoint>> for EntryPoints {
 /// Collection of named keys
 pub type NamedKeys = BTreeMap<String, Key>;
 
+/// Maximum number of named keys the host returns in a single page.
+pub const MAX_NAMED_KEYS_PAGE_SIZE: u32 = 1_000;
+
 /// Methods and type signatures supported by a contract.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Contract {
@@ -465,6 +468,8 @@ pub struct Contract {
     named_keys: NamedKeys,
     entry_points: EntryPoints,
     protocol_version: ProtocolVersion,
+    non_reentrant: bool,
+    disabled: bool,
 }
 
 impl From<Contract>
@@ -502,6 +507,8 @@ impl Contract {
             named_keys,
             entry_points,
             protocol_version,
+            non_reentrant: false,
+            disabled: false,
         }
     }
 
@@ -569,6 +576,29 @@ impl Contract {
     pub fn is_compatible_protocol_version(&self, protocol_version: ProtocolVersion) -> bool {
         self.protocol_version.value().major == protocol_version.value().major
     }
+
+    /// Returns `true` if the engine rejects calls to this contract made while it is already on the
+    /// call stack.
+    pub fn is_non_reentrant(&self) -> bool {
+        self.non_reentrant
+    }
+
+    /// Sets whether the engine rejects calls to this contract made while it is already on the call
+    /// stack.
+    pub fn set_non_reentrant(&mut self, non_reentrant: bool) {
+        self.non_reentrant = non_reentrant;
+    }
+
+    /// Returns `true` if this contract has been retired, in which case it can no longer be called.
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Retires this contract, turning it into a tombstone which can no longer be called.  Its named
+    /// keys are kept, so they remain queryable.
+    pub fn disable(&mut self) {
+        self.disabled = true;
+    }
 }
 
 impl ToBytes for Contract {
@@ -579,15 +609,35 @@ impl ToBytes for Contract {
         result.append(&mut self.named_keys.to_bytes()?);
         result.append(&mut self.entry_points.to_bytes()?);
         result.append(&mut self.protocol_version.to_bytes()?);
+        // Trailing flags which are at their default are omitted, so that contracts stored before
+        // the flags were added keep their encoding.
+        if self.non_reentrant || self.disabled {
+            result.append(&mut self.non_reentrant.to_bytes()?);
+        }
+        if self.disabled {
+            result.append(&mut self.disabled.to_bytes()?);
+        }
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
+        let non_reentrant_length = if self.non_reentrant || self.disabled {
+            ToBytes::serialized_length(&self.non_reentrant)
+        } else {
+            0
+        };
+        let disabled_length = if self.disabled {
+            ToBytes::serialized_length(&self.disabled)
+        } else {
+            0
+        };
         ToBytes::serialized_length(&self.entry_points)
             + ToBytes::serialized_length(&self.contract_package_hash)
             + ToBytes::serialized_length(&self.contract_wasm_hash)
             + ToBytes::serialized_length(&self.protocol_version)
             + ToBytes::serialized_length(&self.named_keys)
+            + non_reentrant_length
+            + disabled_length
     }
 }
 
@@ -598,6 +648,8 @@ impl FromBytes for Contract {
         let (named_keys, bytes) = NamedKeys::from_bytes(bytes)?;
         let (entry_points, bytes) = EntryPoints::from_bytes(bytes)?;
         let (protocol_version, bytes) = ProtocolVersion::from_bytes(bytes)?;
+        let (non_reentrant, bytes) = bytesrepr::from_trailing_bytes(bytes, false)?;
+        let (disabled, bytes) = bytesrepr::from_trailing_bytes(bytes, false)?;
         Ok((
             Contract {
                 contract_package_hash,
@@ -605,6 +657,8 @@ impl FromBytes for Contract {
                 named_keys,
                 entry_points,
                 protocol_version,
+                non_reentrant,
+                disabled,
             },
             bytes,
         ))
@@ -619,6 +673,8 @@ impl Default for Contract {
             contract_wasm_hash: [0; KEY_HASH_LENGTH],
             contract_package_hash: [0; KEY_HASH_LENGTH],
             protocol_version: ProtocolVersion::V1_0_0,
+            non_reentrant: false,
+            disabled: false,
         }
     }
 }
@@ -875,6 +931,11 @@ impl Parameter {
         }
     }
 
+    /// Get the name of this argument.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Get the type of this argument.
     pub fn cl_type(&self) -> &CLType {
         &self.cl_type
@@ -1049,4 +1110,38 @@ mod tests {
             "version should not be enabled"
         );
     }
+
+    #[test]
+    fn should_round_trip_legacy_layout_of_contract_without_flags() {
+        let contract = Contract::default();
+        // the encoding of contracts stored before the trailing flags were added
+        let mut legacy_bytes = vec![];
+        legacy_bytes.append(&mut contract.contract_package_hash.to_bytes().unwrap());
+        legacy_bytes.append(&mut contract.contract_wasm_hash.to_bytes().unwrap());
+        legacy_bytes.append(&mut contract.named_keys.to_bytes().unwrap());
+        legacy_bytes.append(&mut contract.entry_points.to_bytes().unwrap());
+        legacy_bytes.append(&mut contract.protocol_version.to_bytes().unwrap());
+
+        let (decoded, rem) = Contract::from_bytes(&legacy_bytes).expect("should deserialize");
+        assert_eq!(decoded, contract);
+        assert!(rem.is_empty());
+        assert_eq!(decoded.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(decoded.serialized_length(), legacy_bytes.len());
+    }
+
+    #[test]
+    fn should_round_trip_contract_with_flags() {
+        let mut non_reentrant = Contract::default();
+        non_reentrant.set_non_reentrant(true);
+        bytesrepr::test_serialization_roundtrip(&non_reentrant);
+
+        // a set `disabled` flag needs the `non_reentrant` one before it, even at its default
+        let mut disabled = Contract::default();
+        disabled.disable();
+        bytesrepr::test_serialization_roundtrip(&disabled);
+        assert_eq!(
+            disabled.serialized_length(),
+            Contract::default().serialized_length() + 2 * bytesrepr::BOOL_SERIALIZED_LENGTH
+        );
+    }
 }
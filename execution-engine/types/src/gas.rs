@@ -0,0 +1,238 @@
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use num_traits::Zero;
+
+use crate::{
+    bytesrepr::{Error, FromBytes, ToBytes},
+    CLType, CLTyped, Motes, U512,
+};
+
+/// A newtype wrapping a [`U512`] which represents an amount of gas, the unit in which the cost of
+/// executing Wasm is measured.
+///
+/// `Gas` is only converted to and from [`Motes`] at a given gas price, so that the two can't be
+/// mixed up.  It serializes exactly as the wrapped `U512` does.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Gas(U512);
+
+impl Gas {
+    /// Constructs a `Gas`.
+    pub fn new(value: U512) -> Self {
+        Gas(value)
+    }
+
+    /// Returns the wrapped value.
+    pub fn value(&self) -> U512 {
+        self.0
+    }
+
+    /// Converts `motes` to gas at the given gas price `conv_rate`, rounding down.  Returns `None`
+    /// if `conv_rate` is zero.
+    pub fn from_motes(motes: Motes, conv_rate: u64) -> Option<Self> {
+        motes
+            .value()
+            .checked_div(U512::from(conv_rate))
+            .map(Self::new)
+    }
+
+    /// Checked addition.  Returns `None` if the result would overflow.
+    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.value()).map(Self::new)
+    }
+
+    /// Checked subtraction.  Returns `None` if `rhs` is greater than `self`.
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.value()).map(Self::new)
+    }
+}
+
+impl fmt::Display for Gas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Add for Gas {
+    type Output = Gas;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let val = self.value() + rhs.value();
+        Gas::new(val)
+    }
+}
+
+impl Sub for Gas {
+    type Output = Gas;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let val = self.value() - rhs.value();
+        Gas::new(val)
+    }
+}
+
+impl Div for Gas {
+    type Output = Gas;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let val = self.value() / rhs.value();
+        Gas::new(val)
+    }
+}
+
+impl Mul for Gas {
+    type Output = Gas;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let val = self.value() * rhs.value();
+        Gas::new(val)
+    }
+}
+
+impl Zero for Gas {
+    fn zero() -> Self {
+        Gas::new(U512::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl CLTyped for Gas {
+    fn cl_type() -> CLType {
+        CLType::U512
+    }
+}
+
+impl ToBytes for Gas {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for Gas {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (value, rem) = FromBytes::from_bytes(bytes)?;
+        Ok((Gas::new(value), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytesrepr, Gas, Motes, U512};
+
+    #[test]
+    fn should_be_able_to_get_instance_of_gas() {
+        let initial_value = 1;
+        let gas = Gas::new(U512::from(initial_value));
+        assert_eq!(
+            initial_value,
+            gas.value().as_u64(),
+            "should have equal value"
+        )
+    }
+
+    #[test]
+    fn should_be_able_to_compare_two_instances_of_gas() {
+        let left_gas = Gas::new(U512::from(1));
+        let right_gas = Gas::new(U512::from(1));
+        assert_eq!(left_gas, right_gas, "should be equal");
+        let right_gas = Gas::new(U512::from(2));
+        assert_ne!(left_gas, right_gas, "should not be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_add_two_instances_of_gas() {
+        let left_gas = Gas::new(U512::from(1));
+        let right_gas = Gas::new(U512::from(1));
+        let expected_gas = Gas::new(U512::from(2));
+        assert_eq!((left_gas + right_gas), expected_gas, "should be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_subtract_two_instances_of_gas() {
+        let left_gas = Gas::new(U512::from(1));
+        let right_gas = Gas::new(U512::from(1));
+        let expected_gas = Gas::new(U512::from(0));
+        assert_eq!((left_gas - right_gas), expected_gas, "should be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_multiply_two_instances_of_gas() {
+        let left_gas = Gas::new(U512::from(100));
+        let right_gas = Gas::new(U512::from(10));
+        let expected_gas = Gas::new(U512::from(1000));
+        assert_eq!((left_gas * right_gas), expected_gas, "should be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_divide_two_instances_of_gas() {
+        let left_gas = Gas::new(U512::from(1000));
+        let right_gas = Gas::new(U512::from(100));
+        let expected_gas = Gas::new(U512::from(10));
+        assert_eq!((left_gas / right_gas), expected_gas, "should be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_convert_from_mote() {
+        let mote = Motes::new(U512::from(100));
+        let gas = Gas::from_motes(mote, 10).expect("should have gas");
+        let expected_gas = Gas::new(U512::from(10));
+        assert_eq!(gas, expected_gas, "should be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_default() {
+        let gas = Gas::default();
+        let expected_gas = Gas::new(U512::from(0));
+        assert_eq!(gas, expected_gas, "should be equal")
+    }
+
+    #[test]
+    fn should_be_able_to_compare_relative_value() {
+        let left_gas = Gas::new(U512::from(100));
+        let right_gas = Gas::new(U512::from(10));
+        assert!(left_gas > right_gas, "should be gt");
+        let right_gas = Gas::new(U512::from(100));
+        assert!(left_gas >= right_gas, "should be gte");
+        assert!(left_gas <= right_gas, "should be lte");
+        let left_gas = Gas::new(U512::from(10));
+        assert!(left_gas < right_gas, "should be lt");
+    }
+
+    #[test]
+    fn should_default() {
+        let left_gas = Gas::new(U512::from(0));
+        let right_gas = Gas::default();
+        assert_eq!(left_gas, right_gas, "should be equal");
+        let u512 = U512::zero();
+        assert_eq!(left_gas.value(), u512, "should be equal");
+    }
+
+    #[test]
+    fn should_support_checked_div_from_motes() {
+        let motes = Motes::new(U512::zero());
+        let conv_rate = 0;
+        let maybe = Gas::from_motes(motes, conv_rate);
+        assert!(maybe.is_none(), "should be none due to divide by zero");
+    }
+
+    #[test]
+    fn should_serialize_as_u512() {
+        let value = U512::from(1_000_000);
+        let gas = Gas::new(value);
+        assert_eq!(
+            bytesrepr::serialize(&gas).unwrap(),
+            bytesrepr::serialize(&value).unwrap()
+        );
+        bytesrepr::test_serialization_roundtrip(&gas);
+    }
+}
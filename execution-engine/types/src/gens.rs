@@ -15,9 +15,9 @@ use proptest::{
 use crate::{
     account::{AccountHash, Weight},
     contracts::{ContractVersions, DisabledVersions, Groups, NamedKeys, Parameters},
-    AccessRights, CLType, CLValue, Contract, ContractPackage, ContractVersionKey, ContractWasm,
-    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key, NamedArg, Parameter,
-    Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
+    AccessRights, CLType, CLValue, CallStackElement, Contract, ContractPackage, ContractVersionKey,
+    ContractWasm, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key, NamedArg,
+    Parameter, Phase, ProtocolVersion, RuntimeArgs, ScheduledCall, SemVer, URef, U128, U256, U512,
 };
 
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
@@ -63,6 +63,10 @@ pub fn key_arb() -> impl Strategy<Value = Key> {
         account_hash_arb().prop_map(Key::Account),
         u8_slice_32().prop_map(Key::Hash),
         uref_arb().prop_map(Key::URef),
+        Just(Key::SystemRegistry),
+        u8_slice_32().prop_map(Key::Dictionary),
+        u8_slice_32().prop_map(Key::ExecutedDeploy),
+        u8_slice_32().prop_map(Key::Migration),
     ]
 }
 
@@ -70,6 +74,20 @@ pub fn account_hash_arb() -> impl Strategy<Value = AccountHash> {
     u8_slice_32().prop_map(AccountHash::new)
 }
 
+pub fn call_stack_element_arb() -> impl Strategy<Value = CallStackElement> {
+    prop_oneof![
+        account_hash_arb().prop_map(CallStackElement::session),
+        (account_hash_arb(), u8_slice_32(), u8_slice_32()).prop_map(
+            |(account_hash, contract_package_hash, contract_hash)| {
+                CallStackElement::stored_session(account_hash, contract_package_hash, contract_hash)
+            }
+        ),
+        (u8_slice_32(), u8_slice_32()).prop_map(|(contract_package_hash, contract_hash)| {
+            CallStackElement::stored_contract(contract_package_hash, contract_hash)
+        }),
+    ]
+}
+
 pub fn weight_arb() -> impl Strategy<Value = Weight> {
     any::<u8>().prop_map(Weight::new)
 }
@@ -223,6 +241,33 @@ pub fn named_args_arb() -> impl Strategy<Value = NamedArg> {
     (".*", cl_value_arb()).prop_map(|(name, value)| NamedArg::new(name, value))
 }
 
+pub fn runtime_args_arb() -> impl Strategy<Value = RuntimeArgs> {
+    vec(named_args_arb(), 0..10).prop_map(RuntimeArgs::from)
+}
+
+pub fn scheduled_call_arb() -> impl Strategy<Value = ScheduledCall> {
+    (
+        any::<u64>(),
+        account_hash_arb(),
+        u8_slice_32(),
+        "[a-z_]*",
+        runtime_args_arb(),
+        u512_arb(),
+    )
+        .prop_map(
+            |(activation_time, account_hash, contract_hash, entry_point, args, payment)| {
+                ScheduledCall::new(
+                    activation_time.into(),
+                    account_hash,
+                    contract_hash,
+                    entry_point,
+                    args,
+                    payment,
+                )
+            },
+        )
+}
+
 pub fn group_arb() -> impl Strategy<Value = Group> {
     ".*".prop_map(Group::new)
 }
@@ -275,6 +320,8 @@ pub fn contract_arb() -> impl Strategy<Value = Contract> {
         u8_slice_32(),
         u8_slice_32(),
         named_keys_arb(20),
+        any::<bool>(),
+        any::<bool>(),
     )
         .prop_map(
             |(
@@ -283,14 +330,21 @@ pub fn contract_arb() -> impl Strategy<Value = Contract> {
                 contract_package_hash_arb,
                 contract_wasm_hash,
                 named_keys,
+                non_reentrant,
+                disabled,
             )| {
-                Contract::new(
+                let mut contract = Contract::new(
                     contract_package_hash_arb,
                     contract_wasm_hash,
                     named_keys,
                     entry_points,
                     protocol_version,
-                )
+                );
+                contract.set_non_reentrant(non_reentrant);
+                if disabled {
+                    contract.disable();
+                }
+                contract
             },
         )
 }
@@ -1,23 +1,40 @@
 use alloc::{format, string::String, vec::Vec};
-use core::fmt::{self, Debug, Display, Formatter};
+use core::{
+    array::TryFromSliceError,
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+    num::ParseIntError,
+};
 
 use hex_fmt::HexFmt;
 
 use crate::{
     account::AccountHash,
     bytesrepr::{self, Error, FromBytes, ToBytes},
-    URef, UREF_SERIALIZED_LENGTH,
+    AccessRights, URef, UREF_SERIALIZED_LENGTH,
 };
 
 const ACCOUNT_ID: u8 = 0;
 const HASH_ID: u8 = 1;
 const UREF_ID: u8 = 2;
+const SYSTEM_REGISTRY_ID: u8 = 3;
+const DICTIONARY_ID: u8 = 4;
+const EXECUTED_DEPLOY_ID: u8 = 5;
+const MIGRATION_ID: u8 = 6;
 
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
 /// The number of bytes in a [`Key::Hash`].
 pub const KEY_HASH_LENGTH: usize = 32;
 
+const ACCOUNT_HASH_FORMATTED_STRING_PREFIX: &str = "account-hash-";
+const HASH_FORMATTED_STRING_PREFIX: &str = "hash-";
+const UREF_FORMATTED_STRING_PREFIX: &str = "uref-";
+const SYSTEM_REGISTRY_FORMATTED_STRING: &str = "system-registry";
+const DICTIONARY_FORMATTED_STRING_PREFIX: &str = "dictionary-";
+const EXECUTED_DEPLOY_FORMATTED_STRING_PREFIX: &str = "executed-deploy-";
+const MIGRATION_FORMATTED_STRING_PREFIX: &str = "migration-";
+
 const KEY_ID_SERIALIZED_LENGTH: usize = 1;
 // u8 used to determine the ID
 const KEY_HASH_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LENGTH;
@@ -39,6 +56,44 @@ pub type ContractWasmHash = HashAddr;
 /// An alias for [`Key`]s hash variant.
 pub type ContractPackageHash = HashAddr;
 
+/// Error while parsing a [`Key`] from its formatted string representation.
+#[derive(Debug)]
+pub enum KeyFromStrError {
+    /// The string doesn't start with any of the known prefixes.
+    InvalidPrefix,
+    /// The address is not valid Base16.
+    Hex(base16::DecodeError),
+    /// The address doesn't have the expected length.
+    Address(TryFromSliceError),
+    /// The access rights of a URef are missing or are not a valid octal number.
+    AccessRights(ParseIntError),
+    /// The access rights of a URef contain unknown bits.
+    InvalidAccessRights,
+}
+
+impl From<base16::DecodeError> for KeyFromStrError {
+    fn from(error: base16::DecodeError) -> Self {
+        KeyFromStrError::Hex(error)
+    }
+}
+
+impl From<TryFromSliceError> for KeyFromStrError {
+    fn from(error: TryFromSliceError) -> Self {
+        KeyFromStrError::Address(error)
+    }
+}
+
+impl From<ParseIntError> for KeyFromStrError {
+    fn from(error: ParseIntError) -> Self {
+        KeyFromStrError::AccessRights(error)
+    }
+}
+
+fn decode_addr(input: &str) -> Result<[u8; KEY_HASH_LENGTH], KeyFromStrError> {
+    let bytes = base16::decode(input)?;
+    Ok(<[u8; KEY_HASH_LENGTH]>::try_from(bytes.as_ref())?)
+}
+
 /// The type under which data (e.g. [`CLValue`](crate::CLValue)s, smart contracts, user accounts)
 /// are indexed on the network.
 #[repr(C)]
@@ -51,6 +106,20 @@ pub enum Key {
     Hash(HashAddr),
     /// A `Key` which is a [`URef`], under which most types of data can be stored.
     URef(URef),
+    /// The well-known `Key` under which the
+    /// [`SystemContractRegistry`](crate::system_contract_type::SystemContractRegistry) is stored.
+    SystemRegistry,
+    /// A `Key` under which a dictionary item is stored.  It can only be accessed through the
+    /// dictionary host functions, which check the access rights of the dictionary's seed
+    /// [`URef`].
+    Dictionary(HashAddr),
+    /// A `Key` under which the record of an executed deploy is stored, addressed by the deploy's
+    /// hash.  It is only written by the engine and cannot be accessed by contracts.
+    ExecutedDeploy(HashAddr),
+    /// A `Key` under which the completion of a state migration is recorded by an upgrade,
+    /// addressed by a hash of the migration's protocol version and name.  It is only written by
+    /// the engine and cannot be accessed by contracts.
+    Migration(HashAddr),
 }
 
 impl Key {
@@ -61,6 +130,10 @@ impl Key {
             Key::Account(_) => String::from("Key::Account"),
             Key::Hash(_) => String::from("Key::Hash"),
             Key::URef(_) => String::from("Key::URef"),
+            Key::SystemRegistry => String::from("Key::SystemRegistry"),
+            Key::Dictionary(_) => String::from("Key::Dictionary"),
+            Key::ExecutedDeploy(_) => String::from("Key::ExecutedDeploy"),
+            Key::Migration(_) => String::from("Key::Migration"),
         }
     }
 
@@ -87,9 +160,92 @@ impl Key {
             ),
             Key::Hash(addr) => format!("hash-{}", base16::encode_lower(addr)),
             Key::URef(uref) => uref.as_string(),
+            Key::SystemRegistry => String::from("system-registry"),
+            Key::Dictionary(addr) => format!("dictionary-{}", base16::encode_lower(addr)),
+            Key::ExecutedDeploy(addr) => format!("executed-deploy-{}", base16::encode_lower(addr)),
+            Key::Migration(addr) => format!("migration-{}", base16::encode_lower(addr)),
         }
     }
 
+    /// Returns a human-readable version of `self` which can be parsed back with
+    /// [`Key::from_formatted_str`].
+    ///
+    /// The inner bytes are encoded to Base16 and prefixed by the kind of key, i.e.
+    /// `account-hash-`, `hash-` or `uref-`.  A URef additionally has its access rights appended as
+    /// a three-digit octal number, as per `URef::as_string`.
+    pub fn to_formatted_string(&self) -> String {
+        match self {
+            Key::Account(account_hash) => format!(
+                "{}{}",
+                ACCOUNT_HASH_FORMATTED_STRING_PREFIX,
+                base16::encode_lower(&account_hash.value())
+            ),
+            Key::Hash(addr) => format!(
+                "{}{}",
+                HASH_FORMATTED_STRING_PREFIX,
+                base16::encode_lower(addr)
+            ),
+            Key::URef(uref) => uref.as_string(),
+            Key::SystemRegistry => String::from(SYSTEM_REGISTRY_FORMATTED_STRING),
+            Key::Dictionary(addr) => format!(
+                "{}{}",
+                DICTIONARY_FORMATTED_STRING_PREFIX,
+                base16::encode_lower(addr)
+            ),
+            Key::ExecutedDeploy(addr) => format!(
+                "{}{}",
+                EXECUTED_DEPLOY_FORMATTED_STRING_PREFIX,
+                base16::encode_lower(addr)
+            ),
+            Key::Migration(addr) => format!(
+                "{}{}",
+                MIGRATION_FORMATTED_STRING_PREFIX,
+                base16::encode_lower(addr)
+            ),
+        }
+    }
+
+    /// Parses a [`Key`] from the string representation returned by
+    /// [`Key::to_formatted_string`].
+    pub fn from_formatted_str(input: &str) -> Result<Key, KeyFromStrError> {
+        if input.starts_with(ACCOUNT_HASH_FORMATTED_STRING_PREFIX) {
+            let addr = decode_addr(&input[ACCOUNT_HASH_FORMATTED_STRING_PREFIX.len()..])?;
+            return Ok(Key::Account(AccountHash::new(addr)));
+        }
+        if input.starts_with(HASH_FORMATTED_STRING_PREFIX) {
+            let addr = decode_addr(&input[HASH_FORMATTED_STRING_PREFIX.len()..])?;
+            return Ok(Key::Hash(addr));
+        }
+        if input.starts_with(UREF_FORMATTED_STRING_PREFIX) {
+            let remainder = &input[UREF_FORMATTED_STRING_PREFIX.len()..];
+            let (addr, access_rights) = match remainder.rfind('-') {
+                Some(index) => (&remainder[..index], &remainder[index + 1..]),
+                None => (remainder, ""),
+            };
+            let addr = decode_addr(addr)?;
+            let access_rights = u8::from_str_radix(access_rights, 8)?;
+            let access_rights = AccessRights::from_bits(access_rights)
+                .ok_or(KeyFromStrError::InvalidAccessRights)?;
+            return Ok(Key::URef(URef::new(addr, access_rights)));
+        }
+        if input.starts_with(DICTIONARY_FORMATTED_STRING_PREFIX) {
+            let addr = decode_addr(&input[DICTIONARY_FORMATTED_STRING_PREFIX.len()..])?;
+            return Ok(Key::Dictionary(addr));
+        }
+        if input.starts_with(EXECUTED_DEPLOY_FORMATTED_STRING_PREFIX) {
+            let addr = decode_addr(&input[EXECUTED_DEPLOY_FORMATTED_STRING_PREFIX.len()..])?;
+            return Ok(Key::ExecutedDeploy(addr));
+        }
+        if input.starts_with(MIGRATION_FORMATTED_STRING_PREFIX) {
+            let addr = decode_addr(&input[MIGRATION_FORMATTED_STRING_PREFIX.len()..])?;
+            return Ok(Key::Migration(addr));
+        }
+        if input == SYSTEM_REGISTRY_FORMATTED_STRING {
+            return Ok(Key::SystemRegistry);
+        }
+        Err(KeyFromStrError::InvalidPrefix)
+    }
+
     /// Returns the inner bytes of `self` if `self` is of type [`Key::Account`], otherwise returns
     /// `None`.
     pub fn into_account(self) -> Option<AccountHash> {
@@ -131,6 +287,10 @@ impl Key {
             Key::Account(account_hash) => account_hash.value(),
             Key::Hash(bytes) => bytes,
             Key::URef(uref) => uref.addr(),
+            Key::SystemRegistry => [0; BLAKE2B_DIGEST_LENGTH],
+            Key::Dictionary(addr) => addr,
+            Key::ExecutedDeploy(addr) => addr,
+            Key::Migration(addr) => addr,
         }
     }
 }
@@ -141,6 +301,10 @@ impl Display for Key {
             Key::Account(account_hash) => write!(f, "Key::Account({})", account_hash),
             Key::Hash(addr) => write!(f, "Key::Hash({})", HexFmt(addr)),
             Key::URef(uref) => write!(f, "Key::{}", uref), /* Display impl for URef will append */
+            Key::SystemRegistry => write!(f, "Key::SystemRegistry"),
+            Key::Dictionary(addr) => write!(f, "Key::Dictionary({})", HexFmt(addr)),
+            Key::ExecutedDeploy(addr) => write!(f, "Key::ExecutedDeploy({})", HexFmt(addr)),
+            Key::Migration(addr) => write!(f, "Key::Migration({})", HexFmt(addr)),
         }
     }
 }
@@ -179,6 +343,19 @@ impl ToBytes for Key {
                 result.push(UREF_ID);
                 result.append(&mut uref.to_bytes()?);
             }
+            Key::SystemRegistry => result.push(SYSTEM_REGISTRY_ID),
+            Key::Dictionary(addr) => {
+                result.push(DICTIONARY_ID);
+                result.append(&mut addr.to_bytes()?);
+            }
+            Key::ExecutedDeploy(addr) => {
+                result.push(EXECUTED_DEPLOY_ID);
+                result.append(&mut addr.to_bytes()?);
+            }
+            Key::Migration(addr) => {
+                result.push(MIGRATION_ID);
+                result.append(&mut addr.to_bytes()?);
+            }
         }
         Ok(result)
     }
@@ -188,8 +365,11 @@ impl ToBytes for Key {
             Key::Account(account_hash) => {
                 KEY_ID_SERIALIZED_LENGTH + account_hash.serialized_length()
             }
-            Key::Hash(_) => KEY_HASH_SERIALIZED_LENGTH,
+            Key::Hash(_) | Key::Dictionary(_) | Key::ExecutedDeploy(_) | Key::Migration(_) => {
+                KEY_HASH_SERIALIZED_LENGTH
+            }
             Key::URef(_) => KEY_UREF_SERIALIZED_LENGTH,
+            Key::SystemRegistry => KEY_ID_SERIALIZED_LENGTH,
         }
     }
 }
@@ -210,6 +390,19 @@ impl FromBytes for Key {
                 let (uref, rem) = URef::from_bytes(remainder)?;
                 Ok((Key::URef(uref), rem))
             }
+            SYSTEM_REGISTRY_ID => Ok((Key::SystemRegistry, remainder)),
+            DICTIONARY_ID => {
+                let (addr, rem) = <[u8; KEY_HASH_LENGTH]>::from_bytes(remainder)?;
+                Ok((Key::Dictionary(addr), rem))
+            }
+            EXECUTED_DEPLOY_ID => {
+                let (addr, rem) = <[u8; KEY_HASH_LENGTH]>::from_bytes(remainder)?;
+                Ok((Key::ExecutedDeploy(addr), rem))
+            }
+            MIGRATION_ID => {
+                let (addr, rem) = <[u8; KEY_HASH_LENGTH]>::from_bytes(remainder)?;
+                Ok((Key::Migration(addr), rem))
+            }
             _ => Err(Error::Formatting),
         }
     }
@@ -288,6 +481,19 @@ mod tests {
             format!("{}", hash_key),
             format!("Key::Hash({})", expected_hash)
         );
+        assert_eq!(format!("{}", Key::SystemRegistry), "Key::SystemRegistry");
+        assert_eq!(
+            format!("{}", Key::Dictionary(addr_array)),
+            format!("Key::Dictionary({})", expected_hash)
+        );
+        assert_eq!(
+            format!("{}", Key::ExecutedDeploy(addr_array)),
+            format!("Key::ExecutedDeploy({})", expected_hash)
+        );
+        assert_eq!(
+            format!("{}", Key::Migration(addr_array)),
+            format!("Key::Migration({})", expected_hash)
+        );
     }
 
     #[test]
@@ -329,6 +535,77 @@ mod tests {
         assert_eq!(key1.as_uref(), Some(&uref));
     }
 
+    #[test]
+    fn formatted_string_round_trip() {
+        let keys = [
+            Key::Account(AccountHash::new([42; BLAKE2B_DIGEST_LENGTH])),
+            Key::Hash([43; KEY_HASH_LENGTH]),
+            Key::URef(URef::new(
+                [44; BLAKE2B_DIGEST_LENGTH],
+                AccessRights::READ_ADD_WRITE,
+            )),
+            Key::URef(URef::new([45; BLAKE2B_DIGEST_LENGTH], AccessRights::NONE)),
+            Key::SystemRegistry,
+            Key::Dictionary([46; KEY_HASH_LENGTH]),
+            Key::ExecutedDeploy([47; KEY_HASH_LENGTH]),
+            Key::Migration([48; KEY_HASH_LENGTH]),
+        ];
+        for key in keys.iter() {
+            let formatted = key.to_formatted_string();
+            let parsed = Key::from_formatted_str(&formatted).expect("should parse");
+            assert_eq!(parsed, *key);
+        }
+    }
+
+    #[test]
+    fn formatted_string_prefixes() {
+        let account_key = Key::Account(AccountHash::new([1; BLAKE2B_DIGEST_LENGTH]));
+        assert_eq!(
+            account_key.to_formatted_string(),
+            format!("account-hash-{}", "01".repeat(32))
+        );
+        let hash_key = Key::Hash([2; KEY_HASH_LENGTH]);
+        assert_eq!(
+            hash_key.to_formatted_string(),
+            format!("hash-{}", "02".repeat(32))
+        );
+        let uref_key = Key::URef(URef::new([3; BLAKE2B_DIGEST_LENGTH], AccessRights::READ));
+        assert_eq!(
+            uref_key.to_formatted_string(),
+            format!("uref-{}-001", "03".repeat(32))
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_malformed_formatted_strings() {
+        let valid_addr = "2a".repeat(32);
+
+        assert!(matches!(
+            Key::from_formatted_str(&format!("account_hash-{}", valid_addr)),
+            Err(KeyFromStrError::InvalidPrefix)
+        ));
+        assert!(matches!(
+            Key::from_formatted_str(&format!("hash-{}", "zz".repeat(32))),
+            Err(KeyFromStrError::Hex(_))
+        ));
+        assert!(matches!(
+            Key::from_formatted_str(&format!("hash-{}", "2a".repeat(31))),
+            Err(KeyFromStrError::Address(_))
+        ));
+        assert!(matches!(
+            Key::from_formatted_str(&format!("uref-{}", valid_addr)),
+            Err(KeyFromStrError::AccessRights(_))
+        ));
+        assert!(matches!(
+            Key::from_formatted_str(&format!("uref-{}-008", valid_addr)),
+            Err(KeyFromStrError::AccessRights(_))
+        ));
+        assert!(matches!(
+            Key::from_formatted_str(&format!("uref-{}-010", valid_addr)),
+            Err(KeyFromStrError::InvalidAccessRights)
+        ));
+    }
+
     #[test]
     fn key_max_serialized_length() {
         let key_account = Key::Account(AccountHash::new([42; BLAKE2B_DIGEST_LENGTH]));
@@ -339,5 +616,16 @@ mod tests {
 
         let key_uref = Key::URef(URef::new([42; BLAKE2B_DIGEST_LENGTH], AccessRights::READ));
         assert!(key_uref.serialized_length() <= Key::max_serialized_length());
+
+        assert!(Key::SystemRegistry.serialized_length() <= Key::max_serialized_length());
+
+        let key_dictionary = Key::Dictionary([42; KEY_HASH_LENGTH]);
+        assert!(key_dictionary.serialized_length() <= Key::max_serialized_length());
+
+        let key_executed_deploy = Key::ExecutedDeploy([42; KEY_HASH_LENGTH]);
+        assert!(key_executed_deploy.serialized_length() <= Key::max_serialized_length());
+
+        let key_migration = Key::Migration([42; KEY_HASH_LENGTH]);
+        assert!(key_migration.serialized_length() <= Key::max_serialized_length());
     }
 }
@@ -28,16 +28,20 @@ pub mod account;
 pub mod api_error;
 mod block_time;
 pub mod bytesrepr;
+mod call_stack_element;
 mod cl_type;
 mod cl_value;
 mod contract_wasm;
 pub mod contracts;
+mod gas;
 #[cfg(any(feature = "gens", test))]
 pub mod gens;
 mod key;
+mod motes;
 mod phase;
 mod protocol_version;
 pub mod runtime_args;
+pub mod scheduled_call;
 mod semver;
 pub mod system_contract_errors;
 pub mod system_contract_type;
@@ -50,6 +54,7 @@ pub use access_rights::{AccessRights, ACCESS_RIGHTS_SERIALIZED_LENGTH};
 #[doc(inline)]
 pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
+pub use call_stack_element::CallStackElement;
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
 pub use contracts::{
@@ -58,14 +63,17 @@ pub use contracts::{
 };
 //pub use contract_ref::ContractRef;
 pub use contract_wasm::ContractWasm;
+pub use gas::Gas;
 #[doc(inline)]
 pub use key::{
-    ContractHash, ContractPackageHash, ContractWasmHash, HashAddr, Key, BLAKE2B_DIGEST_LENGTH,
-    KEY_HASH_LENGTH,
+    ContractHash, ContractPackageHash, ContractWasmHash, HashAddr, Key, KeyFromStrError,
+    BLAKE2B_DIGEST_LENGTH, KEY_HASH_LENGTH,
 };
+pub use motes::Motes;
 pub use phase::{Phase, PHASE_SERIALIZED_LENGTH};
 pub use protocol_version::{ProtocolVersion, VersionCheckResult};
 pub use runtime_args::{NamedArg, RuntimeArgs};
+pub use scheduled_call::ScheduledCall;
 pub use semver::{SemVer, SEM_VER_SERIALIZED_LENGTH};
 pub use system_contract_type::SystemContractType;
 pub use transfer_result::{TransferResult, TransferredTo};
@@ -51,6 +51,13 @@ impl ProtocolVersion {
         patch: 0,
     });
 
+    /// Version 1.1.0.
+    pub const V1_1_0: ProtocolVersion = ProtocolVersion(SemVer {
+        major: 1,
+        minor: 1,
+        patch: 0,
+    });
+
     /// Constructs a new `ProtocolVersion` from `version`.
     pub fn new(version: SemVer) -> ProtocolVersion {
         ProtocolVersion(version)
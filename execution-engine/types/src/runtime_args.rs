@@ -4,7 +4,7 @@ use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
 use crate::{
     bytesrepr::{self, Error, FromBytes, ToBytes},
-    CLTyped, CLValue,
+    CLType, CLTyped, CLValue,
 };
 
 /// Named arguments to a contract
@@ -140,6 +140,12 @@ impl ToBytes for RuntimeArgs {
     }
 }
 
+impl CLTyped for RuntimeArgs {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
 impl FromBytes for RuntimeArgs {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
         let (args, remainder) = Vec::<NamedArg>::from_bytes(bytes)?;
@@ -0,0 +1,157 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockTime, CLType, CLTyped, ContractHash, RuntimeArgs, U512,
+};
+
+/// The name under which the scheduler is registered in the system contract registry.
+pub const SCHEDULER_NAME: &str = "scheduler";
+
+/// The name of the scheduler's named key under which its queue of [`ScheduledCall`]s is stored.
+pub const SCHEDULER_QUEUE_KEY: &str = "queue";
+
+/// The name of the scheduler's named key under which the purse holding the payments for its queued
+/// calls is stored.
+pub const SCHEDULER_PURSE_KEY: &str = "purse";
+
+/// The maximum number of calls the scheduler's queue holds at once.
+pub const SCHEDULER_MAX_QUEUE_LENGTH: usize = 1_000;
+
+/// A call to a stored contract's entry point which the scheduler makes once the block time reaches
+/// the call's activation time.
+///
+/// The call is made on behalf of the account whose deploy scheduled it, rather than the system
+/// account, so that it has no more privileges than the scheduling contract had at the time.
+///
+/// The call's gas is paid for when it is scheduled.  The payment is held in the scheduler's purse
+/// until the call is made, when the cost of the gas used goes to the proof-of-stake rewards purse
+/// and the rest is refunded to the main purse of the account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduledCall {
+    activation_time: BlockTime,
+    account_hash: AccountHash,
+    contract_hash: ContractHash,
+    entry_point: String,
+    args: RuntimeArgs,
+    payment: U512,
+}
+
+impl ScheduledCall {
+    /// Creates a new [`ScheduledCall`].
+    pub fn new(
+        activation_time: BlockTime,
+        account_hash: AccountHash,
+        contract_hash: ContractHash,
+        entry_point: String,
+        args: RuntimeArgs,
+        payment: U512,
+    ) -> Self {
+        ScheduledCall {
+            activation_time,
+            account_hash,
+            contract_hash,
+            entry_point,
+            args,
+            payment,
+        }
+    }
+
+    /// Returns the earliest block time at which the call is made.
+    pub fn activation_time(&self) -> BlockTime {
+        self.activation_time
+    }
+
+    /// Returns the account on whose behalf the call is made.
+    pub fn account_hash(&self) -> AccountHash {
+        self.account_hash
+    }
+
+    /// Returns the hash of the called contract.
+    pub fn contract_hash(&self) -> ContractHash {
+        self.contract_hash
+    }
+
+    /// Returns the name of the called entry point.
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+
+    /// Returns the arguments passed to the called entry point.
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    /// Returns the motes paid in advance for the call's gas.
+    pub fn payment(&self) -> U512 {
+        self.payment
+    }
+
+    /// Returns whether the call is due at `blocktime`.
+    pub fn is_matured(&self, blocktime: BlockTime) -> bool {
+        self.activation_time <= blocktime
+    }
+}
+
+impl ToBytes for ScheduledCall {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.activation_time.to_bytes()?);
+        result.append(&mut self.account_hash.to_bytes()?);
+        result.append(&mut self.contract_hash.to_bytes()?);
+        result.append(&mut self.entry_point.to_bytes()?);
+        result.append(&mut self.args.to_bytes()?);
+        result.append(&mut self.payment.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.activation_time.serialized_length()
+            + self.account_hash.serialized_length()
+            + self.contract_hash.serialized_length()
+            + self.entry_point.serialized_length()
+            + self.args.serialized_length()
+            + self.payment.serialized_length()
+    }
+}
+
+impl FromBytes for ScheduledCall {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (activation_time, remainder) = BlockTime::from_bytes(bytes)?;
+        let (account_hash, remainder) = AccountHash::from_bytes(remainder)?;
+        let (contract_hash, remainder) = ContractHash::from_bytes(remainder)?;
+        let (entry_point, remainder) = String::from_bytes(remainder)?;
+        let (args, remainder) = RuntimeArgs::from_bytes(remainder)?;
+        let (payment, remainder) = U512::from_bytes(remainder)?;
+        let scheduled_call = ScheduledCall::new(
+            activation_time,
+            account_hash,
+            contract_hash,
+            entry_point,
+            args,
+            payment,
+        );
+        Ok((scheduled_call, remainder))
+    }
+}
+
+impl CLTyped for ScheduledCall {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{bytesrepr, gens};
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize(scheduled_call in gens::scheduled_call_arb()) {
+            bytesrepr::test_serialization_roundtrip(&scheduled_call);
+        }
+    }
+}
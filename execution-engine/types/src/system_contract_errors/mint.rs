@@ -38,6 +38,19 @@ pub enum Error {
     /// Purse not found while trying to get balance.
     #[fail(display = "Purse not found")]
     PurseNotFound = 7,
+    /// Tried to withdraw funds which are still locked by the purse's vesting schedule.
+    #[fail(display = "Funds locked")]
+    FundsLocked = 8,
+    /// The caller isn't allowed to call this function, e.g. a user account called a function
+    /// reserved for the system account.
+    #[fail(display = "Invalid caller")]
+    InvalidCaller = 9,
+    /// Tried to create an application-defined token with an id which is already in use.
+    #[fail(display = "Token already exists")]
+    TokenAlreadyExists = 10,
+    /// No application-defined token with the given id exists.
+    #[fail(display = "Token not found")]
+    TokenNotFound = 11,
 }
 
 impl From<PurseError> for Error {
@@ -79,6 +92,12 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::Storage as u8 => Ok(Error::Storage),
+            d if d == Error::PurseNotFound as u8 => Ok(Error::PurseNotFound),
+            d if d == Error::FundsLocked as u8 => Ok(Error::FundsLocked),
+            d if d == Error::InvalidCaller as u8 => Ok(Error::InvalidCaller),
+            d if d == Error::TokenAlreadyExists as u8 => Ok(Error::TokenAlreadyExists),
+            d if d == Error::TokenNotFound as u8 => Ok(Error::TokenNotFound),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -105,6 +105,73 @@ pub enum Error {
     /// deploy, but was called by the session code.
     #[fail(display = "Set refund purse was called outside payment")]
     SetRefundPurseCalledOutsidePayment,
+    /// The given delegator has no delegation to the given validator.
+    #[fail(display = "Not delegated")]
+    NotDelegated,
+    /// Internal error: failed to deserialize the delegation's key.
+    #[fail(display = "Failed to deserialize delegation's key")]
+    DelegationsKeyDeserializationFailed,
+    /// Internal error: failed to deserialize the delegation's amount.
+    #[fail(display = "Failed to deserialize delegation's amount")]
+    DelegationsDeserializationFailed,
+    /// The invoked PoS function can only be called during the system phase.
+    #[fail(display = "System function was called outside system phase")]
+    SystemFunctionCalledOutsideSystemPhase,
+    /// Internal error: the PoS contract's inflation rate wasn't found.
+    #[fail(display = "Inflation rate not found")]
+    InflationRateNotFound,
+    /// Internal error: the PoS contract's inflation rate key was the wrong type.
+    #[fail(display = "Inflation rate key has unexpected type")]
+    InflationRateKeyUnexpectedType,
+    /// Internal error: while distributing rewards, failed to mint the era rewards.
+    #[fail(display = "Minting rewards has failed")]
+    MintRewardsFailed,
+    /// Internal error: while distributing rewards, failed to pay a validator (the transfer from
+    /// the minted rewards purse to the validator's main purse failed).
+    #[fail(display = "Transfer to validator's purse failed")]
+    FailedTransferToValidatorPurse,
+    /// Internal error: the PoS contract's slash rate wasn't found.
+    #[fail(display = "Slash rate not found")]
+    SlashRateNotFound,
+    /// Internal error: the PoS contract's slash rate key was the wrong type.
+    #[fail(display = "Slash rate key has unexpected type")]
+    SlashRateKeyUnexpectedType,
+    /// Internal error: the PoS contract's unbonding delay wasn't found.
+    #[fail(display = "Unbonding delay not found")]
+    UnbondingDelayNotFound,
+    /// Internal error: the PoS contract's unbonding delay key was the wrong type.
+    #[fail(display = "Unbonding delay key has unexpected type")]
+    UnbondingDelayKeyUnexpectedType,
+    /// The caller has no unbonding request whose delay has passed.
+    #[fail(display = "No matured unbonding request")]
+    NoMaturedUnbonding,
+    /// Internal error: an amount of motes overflowed while computing rewards or slashing.
+    #[fail(display = "Arithmetic overflow")]
+    ArithmeticOverflow,
+    /// Internal error: the PoS contract's minimum bond wasn't found.
+    #[fail(display = "Minimum bond not found")]
+    MinBondNotFound,
+    /// Internal error: the PoS contract's minimum bond key was the wrong type.
+    #[fail(display = "Minimum bond key has unexpected type")]
+    MinBondKeyUnexpectedType,
+    /// Internal error: the PoS contract's maximum bond wasn't found.
+    #[fail(display = "Maximum bond not found")]
+    MaxBondNotFound,
+    /// Internal error: the PoS contract's maximum bond key was the wrong type.
+    #[fail(display = "Maximum bond key has unexpected type")]
+    MaxBondKeyUnexpectedType,
+    /// Internal error: the PoS contract's maximum number of validator slots wasn't found.
+    #[fail(display = "Validator slots not found")]
+    ValidatorSlotsNotFound,
+    /// Internal error: the PoS contract's validator slots key was the wrong type.
+    #[fail(display = "Validator slots key has unexpected type")]
+    ValidatorSlotsKeyUnexpectedType,
+    /// All validator slots are taken and the bond doesn't outbid the lowest evictable stake.
+    #[fail(display = "No validator slot available")]
+    NoValidatorSlotAvailable,
+    /// Internal error: burning a slashed validator's stake failed.
+    #[fail(display = "Burning slashed stake failed")]
+    SlashBurnFailed,
 }
 
 impl CLTyped for Error {
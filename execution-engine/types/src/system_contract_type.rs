@@ -1,11 +1,12 @@
 //! Home of system contract type enum.
 
+use alloc::{collections::BTreeMap, string::String};
 use core::{
     convert::TryFrom,
     fmt::{self, Display, Formatter},
 };
 
-use crate::ApiError;
+use crate::{ApiError, ContractHash};
 
 /// System contract types.
 ///
@@ -28,6 +29,12 @@ pub const PROOF_OF_STAKE: &str = "proof of stake";
 /// Name of standard payment system contract
 const STANDARD_PAYMENT: &str = "standard payment";
 
+/// Registry of system contracts keyed by name, stored under
+/// [`Key::SystemRegistry`](crate::Key::SystemRegistry).
+///
+/// The built-in system contracts are registered under the names of their [`SystemContractType`]s.
+pub type SystemContractRegistry = BTreeMap<String, ContractHash>;
+
 impl From<SystemContractType> for u32 {
     fn from(system_contract_type: SystemContractType) -> u32 {
         match system_contract_type {
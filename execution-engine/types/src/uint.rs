@@ -1,7 +1,10 @@
 use alloc::vec::Vec;
 
 use num_integer::Integer;
-use num_traits::{AsPrimitive, Bounded, Num, One, Unsigned, WrappingAdd, WrappingSub, Zero};
+use num_traits::{
+    AsPrimitive, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Saturating,
+    Unsigned, WrappingAdd, WrappingMul, WrappingSub, Zero,
+};
 
 use crate::bytesrepr::{self, Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
 
@@ -9,7 +12,8 @@ use crate::bytesrepr::{self, Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
     clippy::assign_op_pattern,
     clippy::ptr_offset_with_cast,
     clippy::range_plus_one,
-    clippy::transmute_ptr_to_ptr
+    clippy::transmute_ptr_to_ptr,
+    dead_code
 )]
 mod macro_code {
     use uint::construct_uint;
@@ -23,8 +27,13 @@ mod macro_code {
     construct_uint! {
         pub struct U128(2);
     }
+    // Only used for the intermediate product of `U512::checked_mul_div`.
+    construct_uint! {
+        pub struct U1024(16);
+    }
 }
 
+use self::macro_code::U1024;
 pub use self::macro_code::{U128, U256, U512};
 
 /// Error type for parsing [`U128`], [`U256`], [`U512`] from a string.
@@ -42,21 +51,27 @@ macro_rules! impl_traits_for_uint {
     ($type:ident, $total_bytes:expr, $test_mod:ident) => {
         impl ToBytes for $type {
             fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                // Serialize into a fixed-width buffer on the stack, so that the only allocation
+                // is the exactly-sized result.
                 let mut buf = [0u8; $total_bytes];
                 self.to_little_endian(&mut buf);
-                let mut non_zero_bytes: Vec<u8> =
-                    buf.iter().rev().skip_while(|b| **b == 0).cloned().collect();
-                let num_bytes = non_zero_bytes.len() as u8;
-                non_zero_bytes.push(num_bytes);
-                non_zero_bytes.reverse();
-                Ok(non_zero_bytes)
+                let num_bytes = self.non_zero_bytes();
+                let mut result = Vec::with_capacity(U8_SERIALIZED_LENGTH + num_bytes);
+                result.push(num_bytes as u8);
+                result.extend_from_slice(&buf[..num_bytes]);
+                Ok(result)
             }
 
             fn serialized_length(&self) -> usize {
-                let mut buf = [0u8; $total_bytes];
-                self.to_little_endian(&mut buf);
-                let non_zero_bytes = buf.iter().rev().skip_while(|b| **b == 0).count();
-                U8_SERIALIZED_LENGTH + non_zero_bytes
+                U8_SERIALIZED_LENGTH + self.non_zero_bytes()
+            }
+        }
+
+        impl $type {
+            /// Returns the number of bytes needed to represent the value, i.e. excluding the
+            /// most significant zero bytes.
+            fn non_zero_bytes(&self) -> usize {
+                (self.bits() + 7) / 8
             }
         }
 
@@ -132,6 +147,46 @@ macro_rules! impl_traits_for_uint {
             }
         }
 
+        impl WrappingMul for $type {
+            fn wrapping_mul(&self, other: &$type) -> $type {
+                self.overflowing_mul(*other).0
+            }
+        }
+
+        impl CheckedAdd for $type {
+            fn checked_add(&self, other: &$type) -> Option<$type> {
+                $type::checked_add(*self, *other)
+            }
+        }
+
+        impl CheckedSub for $type {
+            fn checked_sub(&self, other: &$type) -> Option<$type> {
+                $type::checked_sub(*self, *other)
+            }
+        }
+
+        impl CheckedMul for $type {
+            fn checked_mul(&self, other: &$type) -> Option<$type> {
+                $type::checked_mul(*self, *other)
+            }
+        }
+
+        impl CheckedDiv for $type {
+            fn checked_div(&self, other: &$type) -> Option<$type> {
+                $type::checked_div(*self, *other)
+            }
+        }
+
+        impl Saturating for $type {
+            fn saturating_add(self, other: $type) -> $type {
+                $type::saturating_add(self, other)
+            }
+
+            fn saturating_sub(self, other: $type) -> $type {
+                $type::saturating_sub(self, other)
+            }
+        }
+
         impl Integer for $type {
             /// Unsigned integer division. Returns the same result as `div` (`/`).
             #[inline]
@@ -388,6 +443,94 @@ macro_rules! impl_traits_for_uint {
             fn underflow_sub_test() {
                 let _ = $type::zero() - $type::from(1);
             }
+
+            fn checked<T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv>(
+                x: T,
+                y: T,
+            ) -> [Option<T>; 4] {
+                [
+                    x.checked_add(&y),
+                    x.checked_sub(&y),
+                    x.checked_mul(&y),
+                    x.checked_div(&y),
+                ]
+            }
+
+            #[test]
+            fn checked_ops_test() {
+                assert_eq!(
+                    checked($type::from(6), $type::from(3)),
+                    [
+                        Some($type::from(9)),
+                        Some($type::from(3)),
+                        Some($type::from(18)),
+                        Some($type::from(2))
+                    ]
+                );
+                assert_eq!(
+                    checked($type::MAX, $type::from(2)),
+                    [
+                        None,
+                        Some($type::MAX - $type::from(2)),
+                        None,
+                        Some($type::MAX / $type::from(2))
+                    ]
+                );
+                assert_eq!(
+                    checked($type::zero(), $type::zero()),
+                    [
+                        Some($type::zero()),
+                        Some($type::zero()),
+                        Some($type::zero()),
+                        None
+                    ]
+                );
+                assert_eq!(checked($type::zero(), $type::one())[1], None);
+            }
+
+            #[test]
+            fn saturating_ops_test() {
+                assert_eq!(
+                    Saturating::saturating_add($type::MAX, $type::one()),
+                    $type::MAX
+                );
+                assert_eq!(
+                    Saturating::saturating_sub($type::zero(), $type::one()),
+                    $type::zero()
+                );
+                assert_eq!(
+                    Saturating::saturating_add($type::from(1), $type::from(2)),
+                    $type::from(3)
+                );
+            }
+
+            #[test]
+            fn wrapping_ops_test() {
+                assert_eq!($type::MAX.wrapping_add(&$type::one()), $type::zero());
+                assert_eq!($type::zero().wrapping_sub(&$type::one()), $type::MAX);
+                assert_eq!(
+                    $type::MAX.wrapping_mul(&$type::from(2)),
+                    $type::MAX - $type::one()
+                );
+            }
+
+            #[test]
+            fn serialized_length_test() {
+                for value in &[
+                    $type::zero(),
+                    $type::one(),
+                    $type::from(255),
+                    $type::from(256),
+                    $type::MAX,
+                ] {
+                    let bytes = value.to_bytes().expect("should serialize");
+                    assert_eq!(bytes.len(), value.serialized_length());
+                    assert_eq!(bytesrepr::deserialize::<$type>(bytes).unwrap(), *value);
+                }
+                assert_eq!($type::zero().to_bytes().unwrap(), vec![0]);
+                assert_eq!($type::from(256).to_bytes().unwrap(), vec![2, 0, 1]);
+                assert_eq!($type::MAX.serialized_length(), 1 + $total_bytes);
+            }
         }
     };
 }
@@ -462,6 +605,32 @@ impl AsPrimitive<U512> for U512 {
     }
 }
 
+impl U512 {
+    /// Returns `self * numerator / denominator`, or `None` if `denominator` is zero or the result
+    /// doesn't fit in a `U512`.
+    ///
+    /// The product is computed with a 1024-bit intermediate, so it can't overflow.
+    pub fn checked_mul_div(self, numerator: U512, denominator: U512) -> Option<U512> {
+        if denominator.is_zero() {
+            return None;
+        }
+
+        let widen = |value: U512| {
+            let mut bytes = [0u8; 64];
+            value.to_little_endian(&mut bytes);
+            U1024::from_little_endian(&bytes)
+        };
+        let result = widen(self) * widen(numerator) / widen(denominator);
+        if result.bits() > 512 {
+            return None;
+        }
+
+        let mut bytes = [0u8; 128];
+        result.to_little_endian(&mut bytes);
+        Some(U512::from_little_endian(&bytes[..64]))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -785,4 +954,17 @@ mod tests {
         let value = min.wrapping_sub(&1.into());
         assert_eq!(value, U128::max_value());
     }
+
+    #[test]
+    fn checked_mul_div_test_u512() {
+        let max = U512::max_value();
+        assert_eq!(
+            U512::from(10).checked_mul_div(3.into(), 4.into()),
+            Some(7.into())
+        );
+        assert_eq!(max.checked_mul_div(max, max), Some(max));
+        assert_eq!(max.checked_mul_div(max - 1, max), Some(max - 1));
+        assert_eq!(max.checked_mul_div(2.into(), 1.into()), None);
+        assert_eq!(U512::one().checked_mul_div(1.into(), 0.into()), None);
+    }
 }
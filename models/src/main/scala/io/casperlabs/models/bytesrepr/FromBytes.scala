@@ -105,6 +105,13 @@ object FromBytes {
   val string: Deserializer[String] =
     bytes.flatMapF(chars => attempt(new String(chars.toArray, StandardCharsets.UTF_8)))
 
+  // Deserializes a value which was appended to a type's original layout, or returns `default` if
+  // no bytes remain, as is the case for values serialized before it was appended.
+  def trailing[A](desA: => Deserializer[A], default: A): Deserializer[A] =
+    getState.flatMap { state =>
+      if (state.nonEmpty) desA else pure(default)
+    }
+
   // Pass in `desA` lazily for stack safety when recursively chaining Deserializers.
   // It is also more efficient in the None case because we do not evaluate `desA`.
   def option[A](desA: => Deserializer[A]): Deserializer[Option[A]] =
@@ -8,7 +8,8 @@ case class Account(
     namedKeys: Map[String, Key],
     mainPurse: URef,
     associatedKeys: Map[PublicKey, Weight],
-    actionThresholds: ActionThresholds
+    actionThresholds: ActionThresholds,
+    nonce: Option[Long] = None
 )
 
 object Account {
@@ -36,13 +37,15 @@ object Account {
           .exists { case (b1, b2) => b1 < b2 })
   }
 
+  // An unset nonce is omitted, so that accounts stored before it was added encode as they did.
   implicit val toBytesAccount: ToBytes[Account] = new ToBytes[Account] {
     override def toBytes(a: Account): Array[Byte] =
       ToBytes.toBytes(a.publicKey) ++
         ToBytes.toBytes(a.namedKeys) ++
         ToBytes.toBytes(a.mainPurse) ++
         ToBytes.toBytes(a.associatedKeys) ++
-        ToBytes.toBytes(a.actionThresholds)
+        ToBytes.toBytes(a.actionThresholds) ++
+        a.nonce.fold(Array.empty[Byte])(_ => ToBytes.toBytes(a.nonce))
   }
 
   val deserializer: FromBytes.Deserializer[Account] =
@@ -52,5 +55,6 @@ object Account {
       mainPurse        <- URef.deserializer
       associatedKeys   <- FromBytes.map(ByteArray32.deserializer, FromBytes.byte)
       actionThresholds <- desActionThresholds
-    } yield Account(publicKey, namedKeys, mainPurse, associatedKeys, actionThresholds)
+      nonce            <- FromBytes.trailing(FromBytes.option(FromBytes.long), None)
+    } yield Account(publicKey, namedKeys, mainPurse, associatedKeys, actionThresholds, nonce)
 }
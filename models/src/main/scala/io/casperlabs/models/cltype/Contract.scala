@@ -7,19 +7,29 @@ case class Contract(
     contractWasmHash: ByteArray32,
     namedKeys: Map[String, Key],
     entryPoints: Map[String, EntryPoint],
-    protocolVersion: SemVer
+    protocolVersion: SemVer,
+    nonReentrant: Boolean = false,
+    disabled: Boolean = false
 )
 
 object Contract {
+  // Trailing flags which are at their default are omitted, so that contracts stored before the
+  // flags were added encode as they did.
   implicit val toBytesContract: ToBytes[Contract] = new ToBytes[Contract] {
     override def toBytes(c: Contract): Array[Byte] =
       ToBytes.toBytes(c.contractPackageHash) ++
         ToBytes.toBytes(c.contractWasmHash) ++
         ToBytes.toBytes(c.namedKeys) ++
         ToBytes.toBytes(c.entryPoints) ++
-        ToBytes.toBytes(c.protocolVersion)
+        ToBytes.toBytes(c.protocolVersion) ++
+        trailingFlags(c)
   }
 
+  private def trailingFlags(c: Contract): Array[Byte] =
+    if (c.disabled) ToBytes.toBytes(c.nonReentrant) ++ ToBytes.toBytes(c.disabled)
+    else if (c.nonReentrant) ToBytes.toBytes(c.nonReentrant)
+    else Array.empty[Byte]
+
   val deserializer: FromBytes.Deserializer[Contract] =
     for {
       contractPackageHash <- ByteArray32.deserializer
@@ -27,6 +37,15 @@ object Contract {
       namedKeys           <- FromBytes.map(FromBytes.string, Key.deserializer)
       entryPoints         <- FromBytes.map(FromBytes.string, EntryPoint.deserializer)
       protocolVersion     <- SemVer.deserializer
-
-    } yield Contract(contractPackageHash, contractHash, namedKeys, entryPoints, protocolVersion)
+      nonReentrant        <- FromBytes.trailing(FromBytes.bool, false)
+      disabled            <- FromBytes.trailing(FromBytes.bool, false)
+    } yield Contract(
+      contractPackageHash,
+      contractHash,
+      namedKeys,
+      entryPoints,
+      protocolVersion,
+      nonReentrant,
+      disabled
+    )
 }
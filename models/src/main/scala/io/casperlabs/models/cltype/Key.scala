@@ -36,6 +36,38 @@ object Key {
     val tag: Byte = 2
   }
 
+  case object SystemRegistry extends Key {
+    override val tag: Byte                  = 3
+    protected def innerToBytes: Array[Byte] = Array.empty[Byte]
+  }
+
+  case class Dictionary(address: ByteArray32) extends Key {
+    override protected val tag: Byte        = Dictionary.tag
+    protected def innerToBytes: Array[Byte] = ToBytes[ByteArray32].toBytes(address)
+  }
+
+  object Dictionary {
+    val tag: Byte = 4
+  }
+
+  case class ExecutedDeploy(deployHash: ByteArray32) extends Key {
+    override protected val tag: Byte        = ExecutedDeploy.tag
+    protected def innerToBytes: Array[Byte] = ToBytes[ByteArray32].toBytes(deployHash)
+  }
+
+  object ExecutedDeploy {
+    val tag: Byte = 5
+  }
+
+  case class Migration(address: ByteArray32) extends Key {
+    override protected val tag: Byte        = Migration.tag
+    protected def innerToBytes: Array[Byte] = ToBytes[ByteArray32].toBytes(address)
+  }
+
+  object Migration {
+    val tag: Byte = 6
+  }
+
   implicit val toBytesKey: ToBytes[Key] = new ToBytes[Key] {
     override def toBytes(k: Key): Array[Byte] =
       k.tag +: k.innerToBytes
@@ -47,6 +79,14 @@ object Key {
         ByteArray32.deserializer.map[Key](address => Account(address))
       case tag if tag == Hash.tag => ByteArray32.deserializer.map[Key](address => Hash(address))
       case tag if tag == URef.tag => cltype.URef.deserializer.map[Key](uref => URef(uref))
+      case tag if tag == SystemRegistry.tag =>
+        FromBytes.pure[Key](SystemRegistry)
+      case tag if tag == Dictionary.tag =>
+        ByteArray32.deserializer.map[Key](address => Dictionary(address))
+      case tag if tag == ExecutedDeploy.tag =>
+        ByteArray32.deserializer.map[Key](deployHash => ExecutedDeploy(deployHash))
+      case tag if tag == Migration.tag =>
+        ByteArray32.deserializer.map[Key](address => Migration(address))
       case other                  => FromBytes.raise(FromBytes.Error.InvalidVariantTag(other, "Key"))
     }
 }
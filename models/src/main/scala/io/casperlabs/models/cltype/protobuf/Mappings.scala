@@ -76,7 +76,9 @@ object Mappings {
     entryPoints = c.entryPoints.map {
       case (k @ _, v) => toProto(v)
     }.toSeq,
-    protocolVersion = Some(toProto(c.protocolVersion))
+    protocolVersion = Some(toProto(c.protocolVersion)),
+    nonReentrant = c.nonReentrant,
+    disabled = c.disabled
   )
 
   def toProto(c: ContractPackage): state.ContractPackage = state.ContractPackage(
@@ -166,6 +168,32 @@ object Mappings {
         state.Key.Value.Uref(toProto(uref))
       )
 
+    case Key.SystemRegistry =>
+      state.Key(
+        state.Key.Value.SystemRegistry(state.Key.SystemRegistry())
+      )
+
+    case Key.Dictionary(address) =>
+      state.Key(
+        state.Key.Value.Dictionary(
+          state.Key.Dictionary(ByteString.copyFrom(address.bytes.toArray))
+        )
+      )
+
+    case Key.ExecutedDeploy(deployHash) =>
+      state.Key(
+        state.Key.Value.ExecutedDeploy(
+          state.Key.ExecutedDeploy(ByteString.copyFrom(deployHash.bytes.toArray))
+        )
+      )
+
+    case Key.Migration(address) =>
+      state.Key(
+        state.Key.Value.Migration(
+          state.Key.Migration(ByteString.copyFrom(address.bytes.toArray))
+        )
+      )
+
   }
 
   def toProto(version: SemVer): state.ProtocolVersion = state.ProtocolVersion(
@@ -347,6 +375,17 @@ object Mappings {
       toByteArray32(address).map(Key.Hash.apply)
 
     case state.Key.Value.Uref(uref) => fromProto(uref).map(Key.URef.apply)
+
+    case state.Key.Value.SystemRegistry(_) => Right(Key.SystemRegistry)
+
+    case state.Key.Value.Dictionary(state.Key.Dictionary(address)) =>
+      toByteArray32(address).map(Key.Dictionary.apply)
+
+    case state.Key.Value.ExecutedDeploy(state.Key.ExecutedDeploy(deployHash)) =>
+      toByteArray32(deployHash).map(Key.ExecutedDeploy.apply)
+
+    case state.Key.Value.Migration(state.Key.Migration(address)) =>
+      toByteArray32(address).map(Key.Migration.apply)
   }
 
   def fromProto(proto: state.CLType): Either[Error, CLType] =
@@ -1,5 +1,6 @@
 package io.casperlabs.models.cltype
 
+import io.casperlabs.models.bytesrepr.{FromBytes, ToBytes}
 import io.casperlabs.models.bytesrepr.SerializationTest.roundTrip
 import org.scalacheck.{Arbitrary, Gen}
 import org.scalatest.{FlatSpec, Matchers}
@@ -10,6 +11,18 @@ class AccountSerializationTest extends FlatSpec with Matchers with PropertyCheck
   "Accounts" should "serialize properly" in forAll { (a: Account) =>
     roundTrip(a, Account.deserializer)
   }
+
+  it should "serialize with a nonce" in forAll { (a: Account, nonce: Long) =>
+    roundTrip(a.copy(nonce = Some(nonce)), Account.deserializer)
+  }
+
+  it should "deserialize without the trailing nonce" in forAll { (a: Account) =>
+    val legacy = a.copy(nonce = None)
+    // strip the nonce to get the encoding of accounts stored before it was added
+    val bytes = ToBytes.toBytes(a.copy(nonce = Some(0L))).dropRight(9)
+    ToBytes.toBytes(legacy) shouldBe bytes
+    FromBytes.deserialize(Account.deserializer, bytes) shouldBe Right(legacy)
+  }
 }
 
 object AccountSerializationTest {
@@ -29,7 +42,8 @@ object AccountSerializationTest {
     actionThresholds <- genWeight.flatMap { d =>
                          genWeight.map(k => Account.ActionThresholds(d, k))
                        }
-  } yield Account(publicKey, namedKeys, mainPurse, associatedKeys, actionThresholds)
+    nonce <- Gen.option(Arbitrary.arbitrary[Long])
+  } yield Account(publicKey, namedKeys, mainPurse, associatedKeys, actionThresholds, nonce)
 
   implicit val arbAccount: Arbitrary[Account] = Arbitrary(genAccount)
 }
@@ -1,5 +1,6 @@
 package io.casperlabs.models.cltype
 
+import io.casperlabs.models.bytesrepr.{FromBytes, ToBytes}
 import io.casperlabs.models.bytesrepr.SerializationTest.roundTrip
 import org.scalacheck.{Arbitrary, Gen}
 import org.scalatest.{FlatSpec, Matchers}
@@ -11,6 +12,20 @@ class ContractSerializationTest extends FlatSpec with Matchers with PropertyChec
   "Contracts" should "serialize properly" in forAll { (c: Contract) =>
     roundTrip(c, Contract.deserializer)
   }
+
+  it should "deserialize without the trailing flags" in forAll { (c: Contract) =>
+    val legacy = c.copy(nonReentrant = false, disabled = false)
+    // strip the flags to get the encoding of contracts stored before they were added
+    val bytes = ToBytes.toBytes(c.copy(nonReentrant = false, disabled = true)).dropRight(2)
+    FromBytes.deserialize(Contract.deserializer, bytes) shouldBe Right(legacy)
+    ToBytes.toBytes(legacy).toSeq shouldBe bytes.toSeq
+  }
+
+  it should "only omit the trailing flags at their default" in forAll { (c: Contract) =>
+    val base = ToBytes.toBytes(c.copy(nonReentrant = false, disabled = false)).length
+    ToBytes.toBytes(c.copy(nonReentrant = true, disabled = false)).length shouldBe base + 1
+    ToBytes.toBytes(c.copy(nonReentrant = false, disabled = true)).length shouldBe base + 2
+  }
 }
 
 object ContractSerializationTest {
@@ -20,13 +35,17 @@ object ContractSerializationTest {
     namedKeys <- Gen.mapOf(
                   Gen.alphaStr.flatMap(s => KeySerializationTest.genKey.map(k => s -> k))
                 )
-    version <- SemVerSerializationTest.genSemVer
+    version      <- SemVerSerializationTest.genSemVer
+    nonReentrant <- Gen.oneOf(true, false)
+    disabled     <- Gen.oneOf(true, false)
   } yield Contract(
     contractPackageHash,
     contractWasmHash,
     namedKeys,
     Map.empty[String, EntryPoint],
-    version
+    version,
+    nonReentrant,
+    disabled
   )
 
   implicit val arbContract: Arbitrary[Contract] = Arbitrary(genContract)
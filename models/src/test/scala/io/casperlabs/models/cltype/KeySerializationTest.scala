@@ -18,8 +18,23 @@ object KeySerializationTest {
 
   val genHashKey: Gen[Key.Hash] = ByteArray32SerializationTest.genByteArray32.map(Key.Hash(_))
   val genURefKey: Gen[Key.URef] = URefSerializationTest.genURef.map(Key.URef(_))
+  val genDictionaryKey: Gen[Key.Dictionary] =
+    ByteArray32SerializationTest.genByteArray32.map(Key.Dictionary(_))
+  val genExecutedDeployKey: Gen[Key.ExecutedDeploy] =
+    ByteArray32SerializationTest.genByteArray32.map(Key.ExecutedDeploy(_))
+  val genMigrationKey: Gen[Key.Migration] =
+    ByteArray32SerializationTest.genByteArray32.map(Key.Migration(_))
 
-  val genKey: Gen[Key] = Gen.oneOf(genAccountKey, genHashKey, genURefKey)
+  val genKey: Gen[Key] =
+    Gen.oneOf(
+      genAccountKey,
+      genHashKey,
+      genURefKey,
+      Gen.const(Key.SystemRegistry),
+      genDictionaryKey,
+      genExecutedDeployKey,
+      genMigrationKey
+    )
 
   implicit val arbKey: Arbitrary[Key] = Arbitrary(genKey)
 }
@@ -139,7 +139,9 @@ object ChainSpec extends ParserImplicits {
       memCopyPerByte: Int Refined NonNegative,
       maxStackHeight: Int Refined NonNegative,
       opcodesMultiplier: Int Refined NonNegative,
-      opcodesDivisor: Int Refined Positive
+      opcodesDivisor: Int Refined Positive,
+      verifyEd25519: Int Refined NonNegative,
+      verifySecp256k1: Int Refined NonNegative
   ) extends SubConfig
 
   final case class Account(
@@ -425,6 +427,8 @@ object ChainSpecReader {
           .withMaxStackHeight(wasmCosts.maxStackHeight.value)
           .withOpcodesMul(wasmCosts.opcodesMultiplier.value)
           .withOpcodesDiv(wasmCosts.opcodesDivisor.value)
+          .withVerifyEd25519(wasmCosts.verifyEd25519.value)
+          .withVerifySecp256K1(wasmCosts.verifySecp256k1.value)
       )
 
   private def toDeployConfig(deployConfig: Deploy): ipc.ChainSpec.DeployConfig =
@@ -29,6 +29,8 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
         conf.wasmCosts.regular.value shouldBe 1
         conf.wasmCosts.memInitialPages.value shouldBe 5
         conf.wasmCosts.opcodesDivisor.value shouldBe 10
+        conf.wasmCosts.verifyEd25519.value shouldBe 11
+        conf.wasmCosts.verifySecp256k1.value shouldBe 12
       }
     }
 
@@ -58,6 +60,8 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
         conf.wasmCosts.get.regular.value shouldBe 21
         conf.wasmCosts.get.memInitialPages.value shouldBe 25
         conf.wasmCosts.get.opcodesDivisor.value shouldBe 210
+        conf.wasmCosts.get.verifyEd25519.value shouldBe 211
+        conf.wasmCosts.get.verifySecp256k1.value shouldBe 212
       }
     }
 
@@ -150,6 +154,8 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
           wasmCosts.maxStackHeight shouldBe 8
           wasmCosts.opcodesMul shouldBe 9
           wasmCosts.opcodesDiv shouldBe 10
+          wasmCosts.verifyEd25519 shouldBe 11
+          wasmCosts.verifySecp256K1 shouldBe 12
         }
       }
 
@@ -174,6 +180,8 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
           wasmCosts.maxStackHeight shouldBe 28
           wasmCosts.opcodesMul shouldBe 29
           wasmCosts.opcodesDiv shouldBe 210
+          wasmCosts.verifyEd25519 shouldBe 211
+          wasmCosts.verifySecp256K1 shouldBe 212
         }
       }
 